@@ -292,13 +292,17 @@ impl ApplicationRunner {
         self.cx.process_style_updates();
         unsafe { context.make_not_current() };
 
-        self.cx.process_animations();
+        let animating = self.cx.process_animations();
 
         self.cx.process_visual_updates();
 
-        if self.cx.0.windows.iter().any(|(_, window_state)| !window_state.redraw_list.is_empty()) {
+        if self.cx.0.windows.iter().any(|(_, window_state)| {
+            !window_state.redraw_list.is_empty() || window_state.dirty_rect.is_some()
+        }) {
             self.should_redraw = true;
         }
+
+        self.cx.process_idle_callbacks(animating);
     }
 
     pub fn render(&mut self) {
@@ -416,6 +420,11 @@ impl ApplicationRunner {
                 }
 
                 match s {
+                    // Key repeats, along with the characters they input, are generated by the application when a
+                    // custom repeat rate is set.
+                    MouseButtonState::Pressed
+                        if event.repeat && self.cx.has_custom_key_repeat() => {}
+
                     MouseButtonState::Pressed => {
                         if let vizia_input::Key::Character(written) = &event.key {
                             for chr in written.chars() {
@@ -479,9 +488,18 @@ impl ApplicationRunner {
                         self.window_scale_factor = window_info.scale();
                     }
 
-                    //let user_scale_factor = self.cx.user_scale_factor();
+                    let user_scale_factor = self
+                        .cx
+                        .0
+                        .windows
+                        .get(&Entity::root())
+                        .map(|window| window.window_description.user_scale_factor)
+                        .unwrap_or(1.0);
 
-                    //self.cx.set_scale_factor(self.window_scale_factor * user_scale_factor);
+                    self.cx.set_scale_factor(
+                        Entity::root(),
+                        self.window_scale_factor * user_scale_factor,
+                    );
 
                     let physical_size =
                         (window_info.physical_size().width, window_info.physical_size().height);
@@ -496,6 +514,7 @@ impl ApplicationRunner {
                 }
                 baseview::WindowEvent::WillClose => {
                     self.cx.send_event(Event::new(WindowEvent::WindowClose));
+                    self.cx.on_exit();
                 }
                 _ => {}
             },
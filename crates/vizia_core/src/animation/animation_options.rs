@@ -0,0 +1,121 @@
+use crate::prelude::*;
+
+/// The direction in which each iteration of an animation plays.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationDirection {
+    /// Every iteration plays forwards.
+    #[default]
+    Normal,
+    /// Every iteration plays backwards.
+    Reverse,
+    /// Iterations alternate between playing forwards and backwards, starting forwards.
+    Alternate,
+    /// Iterations alternate between playing backwards and forwards, starting backwards.
+    AlternateReverse,
+}
+
+impl AnimationDirection {
+    // Returns true if the iteration with the given index plays backwards.
+    pub(crate) fn is_reversed(&self, iteration: u32) -> bool {
+        match self {
+            AnimationDirection::Normal => false,
+            AnimationDirection::Reverse => true,
+            AnimationDirection::Alternate => iteration % 2 == 1,
+            AnimationDirection::AlternateReverse => iteration % 2 == 0,
+        }
+    }
+}
+
+/// The number of times an animation plays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IterationCount {
+    /// The animation plays the given number of times.
+    Count(u32),
+    /// The animation repeats until it is cancelled.
+    Infinite,
+}
+
+impl Default for IterationCount {
+    fn default() -> Self {
+        IterationCount::Count(1)
+    }
+}
+
+/// Options used to play an animation with
+/// [`play_animation_with`](crate::context::EventContext::play_animation_with).
+///
+/// # Example
+/// ```no_run
+/// # use vizia_core::prelude::*;
+/// # let cx = &mut Context::default();
+/// # let entity = Entity::root();
+/// cx.play_animation_with(
+///     "pulse",
+///     entity,
+///     AnimationOptions {
+///         duration: Duration::from_millis(300),
+///         iteration_count: IterationCount::Count(3),
+///         direction: AnimationDirection::Alternate,
+///         ..Default::default()
+///     }
+///     .on_complete(|_| println!("Pulse finished")),
+/// );
+/// ```
+pub struct AnimationOptions {
+    /// The duration of a single iteration of the animation.
+    pub duration: Duration,
+    /// The delay before the first iteration of the animation starts.
+    pub delay: Duration,
+    /// The number of times the animation plays.
+    pub iteration_count: IterationCount,
+    /// The direction in which each iteration plays.
+    pub direction: AnimationDirection,
+    /// A callback which is called on the animated view when the last iteration finishes.
+    pub on_complete: Option<Box<dyn Fn(&mut EventContext)>>,
+}
+
+impl Default for AnimationOptions {
+    fn default() -> Self {
+        Self {
+            duration: Duration::from_millis(250),
+            delay: Duration::ZERO,
+            iteration_count: IterationCount::default(),
+            direction: AnimationDirection::default(),
+            on_complete: None,
+        }
+    }
+}
+
+impl AnimationOptions {
+    /// Sets the callback which is called on the animated view when the last iteration finishes.
+    pub fn on_complete(mut self, callback: impl 'static + Fn(&mut EventContext)) -> Self {
+        self.on_complete = Some(Box::new(callback));
+        self
+    }
+}
+
+// The state of an animation played with options, used to repeat, pause and complete it.
+pub(crate) struct AnimationPlayback {
+    pub entity: Entity,
+    pub animation: Animation,
+    // A copy of the animation with its keyframes reversed, created when an iteration first plays backwards.
+    pub reversed: Option<Animation>,
+    pub duration: Duration,
+    pub iteration_count: IterationCount,
+    pub direction: AnimationDirection,
+    pub iteration: u32,
+    // The time at which the current iteration finishes.
+    pub end_time: Instant,
+    pub paused_at: Option<Instant>,
+    pub on_complete: Option<Box<dyn Fn(&mut EventContext)>>,
+}
+
+impl AnimationPlayback {
+    // Returns true if the iteration with the given index is the last one.
+    pub(crate) fn is_last_iteration(&self, iteration: u32) -> bool {
+        match self.iteration_count {
+            IterationCount::Count(count) => iteration + 1 >= count,
+            IterationCount::Infinite => false,
+        }
+    }
+}
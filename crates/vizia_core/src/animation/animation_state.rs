@@ -3,7 +3,7 @@ use hashbrown::HashSet;
 
 use crate::prelude::*;
 
-use super::TimingFunction;
+use super::{SpringCurve, TimingFunction};
 
 /// A keyframe in an animation state.
 #[derive(Debug, Clone)]
@@ -37,6 +37,9 @@ pub(crate) struct AnimationState<T: Interpolator> {
 
     pub active: bool,
 
+    /// The time at which the animation was paused, if it is paused.
+    pub paused_at: Option<Instant>,
+
     /// For transitions. The starting rule for this transition.
     pub from_rule: usize,
     /// For tansitions. The ending rule for this transition.
@@ -63,6 +66,7 @@ where
             t: 0.0,
             dt: 0.0,
             active: false,
+            paused_at: None,
             entities: HashSet::new(),
             from_rule: usize::MAX,
             to_rule: usize::MAX,
@@ -98,6 +102,45 @@ where
         self.entities.insert(entity);
     }
 
+    /// Restarts a spring transition from the `start` value towards the `end` value.
+    ///
+    /// The velocity of the spring is kept so that the motion continues smoothly. When `reversing` back towards the
+    /// value the transition started from, the velocity is scaled to the distance back to that value. Otherwise the
+    /// new value is assumed to lie in the same direction as the previous one and the velocity is scaled to the
+    /// distance which remained to the previous value. Returns false if the transition does not use a spring.
+    pub(crate) fn retarget_spring(&mut self, start: T, end: T, reversing: bool) -> bool {
+        let Some(curve) =
+            self.keyframes.first().and_then(|keyframe| keyframe.timing_function.spring)
+        else {
+            return false;
+        };
+
+        let time = self.t * curve.duration;
+        let progress = curve.position(time);
+        // The remaining distance to the new end value, as a fraction of the distance of the transition, and the
+        // direction of the new transition relative to the previous one.
+        let (distance, direction) =
+            if reversing { (progress, -1.0) } else { (1.0 - progress, 1.0) };
+        let mut velocity = 0.0;
+        if distance.abs() > 0.001 {
+            velocity = (direction * curve.velocity(time) / distance).clamp(-100.0, 100.0);
+        }
+
+        let curve = SpringCurve::new(curve.spring, velocity);
+        for keyframe in self.keyframes.iter_mut() {
+            keyframe.timing_function = TimingFunction::spring(curve);
+        }
+
+        self.keyframes.first_mut().unwrap().value = start;
+        self.keyframes.last_mut().unwrap().value = end;
+        self.duration = Duration::from_secs_f32(curve.duration);
+        self.t = 0.0;
+        self.dt = 0.0;
+        self.start_time = Instant::now();
+
+        true
+    }
+
     pub(crate) fn is_transition(&self) -> bool {
         !(self.from_rule == usize::MAX && self.to_rule == usize::MAX)
     }
@@ -119,9 +162,69 @@ where
             t: 0.0,
             dt: 0.0,
             active: false,
+            paused_at: None,
             entities: HashSet::new(),
             from_rule: usize::MAX,
             to_rule: usize::MAX,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vizia_style::Spring;
+
+    fn spring_transition(start: f32, end: f32) -> AnimationState<f32> {
+        let timing_function = TimingFunction::spring(SpringCurve::new(Spring::default(), 0.0));
+        AnimationState::new(Animation::null())
+            .with_keyframe(Keyframe { time: 0.0, value: start, timing_function })
+            .with_keyframe(Keyframe { time: 1.0, value: end, timing_function })
+    }
+
+    fn initial_velocity(state: &AnimationState<f32>) -> f32 {
+        state.keyframes[0].timing_function.spring.unwrap().velocity(0.0)
+    }
+
+    #[test]
+    fn retarget_keeps_velocity() {
+        let mut state = spring_transition(0.0, 100.0);
+        state.t = 0.1;
+        let curve = state.keyframes[0].timing_function.spring.unwrap();
+        let time = state.t * curve.duration;
+        let (progress, speed) = (curve.position(time), curve.velocity(time));
+        assert!(speed > 0.0);
+
+        // Moving on to a further value keeps the motion going in the same direction.
+        assert!(state.retarget_spring(progress * 100.0, 200.0, false));
+        assert_eq!(state.t, 0.0);
+        assert_eq!(state.keyframes[0].value, progress * 100.0);
+        assert_eq!(state.keyframes[1].value, 200.0);
+        let velocity = initial_velocity(&state);
+        assert!((velocity - speed / (1.0 - progress)).abs() < 1e-3);
+
+        // Reversing back towards the start value keeps the motion going away from it.
+        let mut state = spring_transition(0.0, 100.0);
+        state.t = 0.1;
+        assert!(state.retarget_spring(progress * 100.0, 0.0, true));
+        let velocity = initial_velocity(&state);
+        assert!((velocity + speed / progress).abs() < 1e-3);
+    }
+
+    #[test]
+    fn retarget_without_spring() {
+        let mut state = AnimationState::new(Animation::null())
+            .with_keyframe(Keyframe {
+                time: 0.0,
+                value: 0.0,
+                timing_function: TimingFunction::linear(),
+            })
+            .with_keyframe(Keyframe {
+                time: 1.0,
+                value: 1.0,
+                timing_function: TimingFunction::linear(),
+            });
+        assert!(!state.retarget_spring(0.5, 2.0, false));
+        assert_eq!(state.keyframes[1].value, 1.0);
+    }
+}
@@ -7,8 +7,15 @@ pub(crate) use animation_state::{AnimationState, Keyframe};
 mod interpolator;
 pub(crate) use interpolator::Interpolator;
 
+mod spring;
+pub(crate) use spring::SpringCurve;
+
 mod timing_function;
 pub(crate) use timing_function::TimingFunction;
 
 mod animation_builder;
 pub use animation_builder::*;
+
+mod animation_options;
+pub(crate) use animation_options::AnimationPlayback;
+pub use animation_options::{AnimationDirection, AnimationOptions, IterationCount};
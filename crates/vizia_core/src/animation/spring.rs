@@ -0,0 +1,131 @@
+use vizia_style::Spring;
+
+// The distance from the target and the speed, in progress per second, below which a spring is considered settled.
+const REST_DISTANCE: f32 = 0.001;
+const REST_VELOCITY: f32 = 0.01;
+// The step, in seconds, used to find the time at which a spring settles.
+const SETTLE_STEP: f32 = 1.0 / 120.0;
+// The maximum time, in seconds, a spring is allowed to animate for.
+const MAX_DURATION: f32 = 10.0;
+
+/// The motion of a damped spring moving from a progress of 0.0 to a target progress of 1.0.
+///
+/// Progress can overshoot the target for under-damped springs. The time taken for the spring to settle is computed
+/// when the curve is created and is used as the duration of the animation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct SpringCurve {
+    pub spring: Spring,
+    // Undamped angular frequency.
+    omega0: f32,
+    // Damping ratio.
+    zeta: f32,
+    // The initial velocity in progress per second.
+    velocity: f32,
+    /// The time, in seconds, taken for the spring to settle.
+    pub duration: f32,
+}
+
+impl SpringCurve {
+    /// Creates a new spring curve with the given initial velocity, in progress per second.
+    pub fn new(spring: Spring, velocity: f32) -> Self {
+        let stiffness = spring.stiffness.max(f32::EPSILON);
+        let mass = spring.mass.max(f32::EPSILON);
+        let damping = spring.damping.max(0.0);
+
+        let omega0 = (stiffness / mass).sqrt();
+        let zeta = damping / (2.0 * (stiffness * mass).sqrt());
+
+        let mut curve = Self { spring, omega0, zeta, velocity, duration: MAX_DURATION };
+
+        let mut time = 0.0;
+        while time < MAX_DURATION {
+            time += SETTLE_STEP;
+            if (1.0 - curve.position(time)).abs() < REST_DISTANCE
+                && curve.velocity(time).abs() < REST_VELOCITY
+            {
+                curve.duration = time;
+                break;
+            }
+        }
+
+        curve
+    }
+
+    /// Returns the progress of the spring at the given time in seconds.
+    pub fn position(&self, time: f32) -> f32 {
+        1.0 + self.displacement(time)
+    }
+
+    /// Returns the velocity of the spring, in progress per second, at the given time in seconds.
+    pub fn velocity(&self, time: f32) -> f32 {
+        const H: f32 = 0.0005;
+        if time < H {
+            return self.velocity;
+        }
+
+        (self.displacement(time + H) - self.displacement(time - H)) / (2.0 * H)
+    }
+
+    /// Returns the progress of the spring for a normalized time between 0.0 and 1.0.
+    pub fn value(&self, x: f32) -> f32 {
+        if x >= 1.0 {
+            return 1.0;
+        }
+
+        self.position(x * self.duration)
+    }
+
+    // The signed distance from the target, which starts at -1.0.
+    fn displacement(&self, time: f32) -> f32 {
+        let (omega0, zeta, v0) = (self.omega0, self.zeta, self.velocity);
+        let x0 = -1.0;
+
+        if zeta < 1.0 {
+            // Under-damped
+            let omega_d = omega0 * (1.0 - zeta * zeta).sqrt();
+            let b = (v0 + zeta * omega0 * x0) / omega_d;
+            (-zeta * omega0 * time).exp()
+                * (x0 * (omega_d * time).cos() + b * (omega_d * time).sin())
+        } else if zeta == 1.0 {
+            // Critically damped
+            let b = v0 + omega0 * x0;
+            (x0 + b * time) * (-omega0 * time).exp()
+        } else {
+            // Over-damped
+            let root = (zeta * zeta - 1.0).sqrt();
+            let r1 = -omega0 * (zeta - root);
+            let r2 = -omega0 * (zeta + root);
+            let c2 = (v0 - r1 * x0) / (r2 - r1);
+            let c1 = x0 - c2;
+            c1 * (r1 * time).exp() + c2 * (r2 * time).exp()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settles_at_target() {
+        let curve = SpringCurve::new(Spring::default(), 0.0);
+        assert_eq!(curve.value(0.0), 0.0);
+        assert_eq!(curve.value(1.0), 1.0);
+        assert!(curve.duration < MAX_DURATION);
+        assert!((1.0 - curve.position(curve.duration)).abs() < REST_DISTANCE);
+    }
+
+    #[test]
+    fn under_damped_overshoots() {
+        let curve = SpringCurve::new(Spring::new(300.0, 5.0, 1.0), 0.0);
+        let max = (0..100).map(|i| curve.value(i as f32 / 100.0)).fold(0.0, f32::max);
+        assert!(max > 1.0);
+    }
+
+    #[test]
+    fn initial_velocity() {
+        let curve = SpringCurve::new(Spring::default(), 5.0);
+        assert_eq!(curve.velocity(0.0), 5.0);
+        assert!(curve.position(0.01) > SpringCurve::new(Spring::default(), 0.0).position(0.01));
+    }
+}
@@ -1,9 +1,13 @@
+use super::SpringCurve;
+
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct TimingFunction {
     x1: f32,
     x2: f32,
     y1: f32,
     y2: f32,
+    // A spring which replaces the cubic bezier curve.
+    pub spring: Option<SpringCurve>,
 }
 
 impl Default for TimingFunction {
@@ -28,14 +32,21 @@ impl TimingFunction {
     pub fn ease_in_out() -> Self {
         Self::new(0.42, 0., 0.58, 1.)
     }
+    pub fn spring(curve: SpringCurve) -> Self {
+        Self { spring: Some(curve), ..Self::linear() }
+    }
 }
 
 impl TimingFunction {
     pub fn new(x1: f32, y1: f32, x2: f32, y2: f32) -> Self {
-        Self { x1, y1, x2, y2 }
+        Self { x1, y1, x2, y2, spring: None }
     }
 
     pub fn value(&self, x: f32) -> f32 {
+        if let Some(spring) = &self.spring {
+            return spring.value(x);
+        }
+
         // Linear
         if self.x1 == self.y1 && self.x2 == self.y2 {
             return x;
@@ -83,6 +94,8 @@ impl TimingFunction {
 #[cfg(test)]
 mod tests {
     use super::TimingFunction;
+    use crate::animation::SpringCurve;
+    use vizia_style::Spring;
 
     #[test]
     fn linear() {
@@ -95,4 +108,11 @@ mod tests {
         let timing_func = TimingFunction::ease();
         assert_eq!(timing_func.value(0.25), 0.4085106);
     }
+
+    #[test]
+    fn spring() {
+        let timing_func = TimingFunction::spring(SpringCurve::new(Spring::default(), 0.0));
+        assert_eq!(timing_func.value(0.0), 0.0);
+        assert_eq!(timing_func.value(1.0), 1.0);
+    }
 }
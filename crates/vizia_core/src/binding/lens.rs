@@ -1,12 +1,13 @@
 use std::any::TypeId;
 use std::borrow::Borrow;
+use std::cell::RefCell;
 use std::fmt::{Debug, Formatter};
 use std::hash::Hash;
 use std::marker::PhantomData;
 use std::ops::{BitAnd, BitOr, Deref};
 use std::rc::Rc;
 
-use crate::context::{CURRENT, MAPS, MAP_MANAGER};
+use crate::context::{CURRENT, DATA_GENERATION, MAPS, MAP_MANAGER};
 
 use super::{MapId, StoreId};
 
@@ -133,6 +134,40 @@ pub trait LensExt: Lens {
         MapRef { id, lens: self, o: PhantomData }
     }
 
+    /// Like [`map`](LensExt::map), but caches the computed value and only calls the closure again once the data of
+    /// the models and views may have changed, i.e. after an event has been handled. The lensed data is not cloned or
+    /// compared to decide whether the value is stale.
+    ///
+    /// Useful when the mapping is expensive, such as filtering or sorting a collection. Each access of the lens
+    /// clones the cached value, so expensive outputs should be cheap to clone, e.g. wrapped in an `Rc`.
+    ///
+    /// # Example
+    /// ```compile_fail
+    /// let visible = AppData::items.map_memo(|items| {
+    ///     Rc::new(items.iter().filter(|item| item.visible).cloned().collect::<Vec<_>>())
+    /// });
+    /// ```
+    fn map_memo<O: 'static + Clone, F: 'static + Fn(&Self::Target) -> O>(
+        self,
+        map: F,
+    ) -> MapMemo<Self, O> {
+        let id = MAP_MANAGER.with_borrow_mut(|f| f.create());
+        let entity = CURRENT.with_borrow(|f| *f);
+        MAPS.with_borrow_mut(|f| {
+            f.insert(
+                id,
+                (
+                    entity,
+                    Box::new(MapMemoState::<Self::Target, O> {
+                        closure: Rc::new(map),
+                        cache: Rc::new(RefCell::new(None)),
+                    }),
+                ),
+            )
+        });
+        MapMemo { id, lens: self, o: PhantomData }
+    }
+
     fn unwrap<T: 'static>(self) -> Then<Self, UnwrapLens<T>>
     where
         Self: Lens<Target = Option<T>>,
@@ -159,6 +194,12 @@ pub struct MapRefState<T, O> {
     closure: Rc<dyn Fn(&T) -> &O>,
 }
 
+pub struct MapMemoState<T, O> {
+    closure: Rc<dyn Fn(&T) -> O>,
+    // The data generation when the value was last computed, and the value.
+    cache: Rc<RefCell<Option<(u64, O)>>>,
+}
+
 pub struct Map<L: Lens, O> {
     id: MapId,
     lens: L,
@@ -240,6 +281,55 @@ impl<L: Lens, O: 'static> Debug for MapRef<L, O> {
     }
 }
 
+pub struct MapMemo<L: Lens, O> {
+    id: MapId,
+    lens: L,
+    o: PhantomData<O>,
+}
+
+impl<L: Lens, O: 'static> Copy for MapMemo<L, O> {}
+
+impl<L: Lens, O: 'static> Clone for MapMemo<L, O> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<L: Lens, O: 'static + Clone> Lens for MapMemo<L, O> {
+    type Source = L::Source;
+    type Target = O;
+
+    fn view<'a>(&self, source: &'a Self::Source) -> Option<LensValue<'a, Self::Target>> {
+        let (closure, cache) = MAPS.with_borrow(|f| {
+            let (_, any) = f.get(&self.id)?;
+            let MapMemoState { closure, cache } =
+                any.downcast_ref::<MapMemoState<L::Target, O>>()?;
+            Some((closure.clone(), cache.clone()))
+        })?;
+
+        let generation = DATA_GENERATION.with(|generation| generation.get());
+        if let Some((cached_generation, output)) = &*cache.borrow() {
+            if *cached_generation == generation {
+                return Some(LensValue::Owned(output.clone()));
+            }
+        }
+
+        let output = closure(&*self.lens.view(source)?);
+        *cache.borrow_mut() = Some((generation, output.clone()));
+        Some(LensValue::Owned(output))
+    }
+
+    fn id(&self) -> StoreId {
+        StoreId::Map(self.id.0)
+    }
+}
+
+impl<L: Lens, O: 'static> Debug for MapMemo<L, O> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{:?}.map_memo(?)", self.lens))
+    }
+}
+
 /// `Lens` composed of two lenses joined together
 pub struct Then<A, B> {
     a: A,
@@ -670,3 +760,57 @@ where
         Some(LensValue::Borrowed(*self))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use crate::context::test_support::{update, window_context};
+    use crate::prelude::*;
+
+    #[test]
+    fn map_memo() {
+        #[derive(Lens)]
+        struct Items {
+            items: Vec<u32>,
+        }
+
+        impl Model for Items {
+            fn event(&mut self, _: &mut EventContext, event: &mut Event) {
+                event.map(|item: &u32, _| self.items.push(*item));
+            }
+        }
+
+        let mut cx = window_context(400, 300);
+
+        Items { items: vec![1, 2, 3] }.build(cx.context());
+
+        let calls = Rc::new(std::cell::Cell::new(0));
+        let memo_calls = calls.clone();
+        let sum = Items::items.map_memo(move |items| {
+            memo_calls.set(memo_calls.get() + 1);
+            items.iter().sum::<u32>()
+        });
+        let label = Label::new(cx.context(), sum).entity();
+
+        update(&mut cx);
+        assert_eq!(cx.0.style.text.get(label).map(String::as_str), Some("6"));
+
+        // The value is computed once until the data may have changed.
+        let computed = calls.get();
+        cx.set_current(Entity::root());
+        assert_eq!(sum.get(&cx.0), 6);
+        assert_eq!(sum.get(&cx.0), 6);
+        assert_eq!(calls.get(), computed);
+
+        cx.0.emit_to(Entity::root(), 4u32);
+        update(&mut cx);
+        assert_eq!(cx.0.style.text.get(label).map(String::as_str), Some("10"));
+        assert!(calls.get() > computed);
+
+        let computed = calls.get();
+        cx.set_current(Entity::root());
+        assert_eq!(sum.get(&cx.0), 10);
+        assert_eq!(calls.get(), computed);
+    }
+}
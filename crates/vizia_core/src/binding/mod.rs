@@ -204,6 +204,9 @@ pub(crate) use store::*;
 mod binding_view;
 pub use binding_view::*;
 
+mod stream_binding;
+pub use stream_binding::StreamBinding;
+
 mod data;
 pub use data::*;
 
@@ -138,9 +138,12 @@ impl_res_simple!(ButtonVariant);
 impl_res_simple!(AvatarVariant);
 impl_res_clone!(FamilyOwned);
 impl_res_simple!(TextDecorationLine);
+impl_res_simple!(chrono::NaiveDate);
 impl_res_clone!(TextStroke);
 impl_res_clone!(TextStrokeStyle);
 impl_res_simple!(Alignment);
+impl_res_simple!(SelfAlignment);
+impl_res_simple!(FlexWrap);
 impl_res_simple!(WindowPosition);
 impl_res_simple!(Anchor);
 impl_res_simple!(AnchorTarget);
@@ -0,0 +1,160 @@
+use std::future::poll_fn;
+use std::sync::{Arc, Mutex};
+use std::task::{Poll, Waker};
+
+use futures_core::Stream;
+
+use crate::prelude::*;
+
+// Sent by the thread polling the stream when it produces a new item.
+struct StreamItem<T>(T);
+
+// Shared by the view and the thread polling its stream, which stops once the view is removed.
+struct Polling {
+    alive: bool,
+    // The waker of the task polling the stream, used to wake it when the view is removed.
+    waker: Option<Waker>,
+}
+
+/// A view which rebuilds its contents with the latest item produced by an async [Stream].
+///
+/// The stream is polled on a separate thread and each item is sent back to the main UI thread through the event
+/// proxy. The contents are first built with `None`, before the stream has produced an item. Polling stops when the
+/// stream ends or the view is removed.
+///
+/// # Example
+/// ```ignore
+/// StreamBinding::new(cx, price_updates(), |cx, price: Option<&f32>| {
+///     match price {
+///         Some(price) => Label::new(cx, format!("{:.2}", price)),
+///         None => Label::new(cx, "Loading..."),
+///     };
+/// });
+/// ```
+pub struct StreamBinding<T> {
+    value: Option<T>,
+    #[allow(clippy::type_complexity)]
+    content: Box<dyn Fn(&mut Context, Option<&T>)>,
+    polling: Arc<Mutex<Polling>>,
+}
+
+impl<T: 'static + Send> StreamBinding<T> {
+    /// Creates a new stream binding view which polls the given stream and rebuilds its contents with each item.
+    pub fn new<S, F>(cx: &mut Context, stream: S, content: F) -> Handle<Self>
+    where
+        S: 'static + Send + Stream<Item = T>,
+        F: 'static + Fn(&mut Context, Option<&T>),
+    {
+        let polling = Arc::new(Mutex::new(Polling { alive: true, waker: None }));
+
+        let mut handle = Self { value: None, content: Box::new(content), polling: polling.clone() }
+            .build(cx, |_| {});
+
+        let entity = handle.entity();
+        Self::rebuild(handle.context(), entity);
+
+        handle.context().spawn_async(move |mut proxy| async move {
+            let mut stream = std::pin::pin!(stream);
+            let mut next = |cx: &mut std::task::Context<'_>| {
+                // The waker is stored before polling, so removing the view while the stream is pending wakes the task.
+                let mut polling = polling.lock().unwrap();
+                if !polling.alive {
+                    return Poll::Ready(None);
+                }
+
+                polling.waker = Some(cx.waker().clone());
+                drop(polling);
+
+                stream.as_mut().poll_next(cx)
+            };
+
+            while let Some(item) = poll_fn(&mut next).await {
+                if proxy.emit_to(entity, StreamItem(item)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        handle
+    }
+
+    // Removes the contents of the view and builds them again with the latest item.
+    fn rebuild(cx: &mut Context, entity: Entity) {
+        if let Some(mut view) = cx.views.remove(&entity) {
+            if let Some(stream_binding) = view.downcast_mut::<Self>() {
+                cx.remove_children(entity);
+                cx.with_current(entity, |cx| {
+                    (stream_binding.content)(cx, stream_binding.value.as_ref())
+                });
+            }
+
+            cx.views.insert(entity, view);
+        }
+    }
+}
+
+impl<T: 'static + Send> View for StreamBinding<T> {
+    fn element(&self) -> Option<&'static str> {
+        Some("stream-binding")
+    }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.take(|item: StreamItem<T>, _| {
+            self.value = Some(item.0);
+
+            // The view is borrowed while handling the event, so the contents are rebuilt afterwards.
+            let entity = cx.current();
+            cx.defer(move |cx| Self::rebuild(cx, entity));
+        });
+    }
+}
+
+impl<T> Drop for StreamBinding<T> {
+    fn drop(&mut self) {
+        let mut polling = self.polling.lock().unwrap();
+        polling.alive = false;
+        if let Some(waker) = polling.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::pin::Pin;
+    use std::sync::mpsc::{channel, Sender};
+    use std::task::Context as TaskContext;
+
+    use super::*;
+    use crate::context::test_support::window_context;
+
+    // A stream which never produces an item and reports when it is dropped.
+    struct QuietStream(Sender<()>);
+
+    impl Stream for QuietStream {
+        type Item = u32;
+
+        fn poll_next(self: Pin<&mut Self>, _: &mut TaskContext<'_>) -> Poll<Option<u32>> {
+            Poll::Pending
+        }
+    }
+
+    impl Drop for QuietStream {
+        fn drop(&mut self) {
+            self.0.send(()).ok();
+        }
+    }
+
+    #[test]
+    fn removing_view_stops_polling() {
+        let mut cx = window_context(400, 300);
+
+        let (sender, receiver) = channel();
+        let binding = StreamBinding::new(cx.context(), QuietStream(sender), |_, _| {}).entity();
+        assert!(receiver.recv_timeout(Duration::from_millis(50)).is_err());
+
+        // Removing the view wakes the pending task, which stops polling and drops the stream.
+        cx.0.remove(binding);
+        assert!(receiver.recv_timeout(Duration::from_secs(5)).is_ok());
+    }
+}
@@ -2,7 +2,7 @@
 //! results. The main type here is CachedData, usually accessed via `cx.cache`.
 
 use crate::prelude::*;
-use skia_safe::{Matrix, Path};
+use skia_safe::{Matrix, Path, Picture};
 use vizia_storage::SparseSet;
 
 /// Stores data which can be cached between system runs.
@@ -18,6 +18,14 @@ pub struct CachedData {
     pub(crate) transform: SparseSet<Matrix>,
     pub(crate) clip_path: SparseSet<BoundingBox>,
     pub(crate) path: SparseSet<Path>,
+    pub(crate) picture: SparseSet<CachedPicture>,
+}
+
+/// A drawing of a view recorded with [`DrawContext::draw_cached`], which is replayed until it's invalidated.
+pub(crate) struct CachedPicture {
+    /// The size of the view when the drawing was recorded.
+    pub(crate) size: (f32, f32),
+    pub(crate) picture: Picture,
 }
 
 impl CachedData {
@@ -36,6 +44,7 @@ impl CachedData {
         self.transform.remove(entity);
         self.clip_path.remove(entity);
         self.path.remove(entity);
+        self.picture.remove(entity);
     }
 
     /// Returns the bounding box of the entity, determined by the layout system.
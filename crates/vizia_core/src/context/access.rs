@@ -1,4 +1,4 @@
-use accesskit::{Node, NodeId, Rect, TextDirection, TextSelection};
+use accesskit::{Action, Node, NodeId, Rect, TextDirection, TextSelection};
 
 use crate::{cache::CachedData, prelude::*, text::TextContext};
 
@@ -47,6 +47,11 @@ impl AccessNode {
         self.children.push(child);
     }
 
+    /// Adds an action which can be requested on the node by assistive technologies.
+    pub fn add_action(&mut self, action: Action) {
+        self.node_builder.add_action(action);
+    }
+
     /// Sets the role of the node.
     pub fn set_role(&mut self, role: Role) {
         self.node_builder.set_role(role);
@@ -121,4 +126,9 @@ impl AccessNode {
     pub fn set_max_numeric_value(&mut self, value: f64) {
         self.node_builder.set_max_numeric_value(value);
     }
+
+    /// Sets whether the node is selected, such as the selected row of a table.
+    pub fn set_selected(&mut self, selected: bool) {
+        self.node_builder.set_selected(selected);
+    }
 }
@@ -1,14 +1,17 @@
 use std::any::Any;
 
-use skia_safe::Surface;
+use skia_safe::{AlphaType, ColorType, ImageInfo, Surface};
 use vizia_storage::LayoutTreeIterator;
 use vizia_window::WindowDescription;
 
-use super::EventProxy;
-use crate::{cache::CachedData, prelude::*, systems::*};
+use super::{EventProxy, ResourceContext};
+use crate::{cache::CachedData, events::EventManager, prelude::*, systems::*};
+
+#[cfg(feature = "profiling")]
+use crate::profiling::ProfiledSystem;
 
 #[cfg(feature = "clipboard")]
-use copypasta::ClipboardProvider;
+use super::ClipboardProvider;
 
 /// Context used to integrate vizia with windowing backends such as winit and baseview.
 pub struct BackendContext(pub Context);
@@ -72,7 +75,10 @@ impl BackendContext {
         let physical_width = window_description.inner_size.width as f32 * dpi_factor;
         let physical_height = window_description.inner_size.height as f32 * dpi_factor;
 
-        self.0.style.dpi_factor = dpi_factor as f64;
+        self.0.style.window_scale_factors.insert(window_entity, dpi_factor as f64);
+        if window_entity == Entity::root() {
+            self.0.style.dpi_factor = dpi_factor as f64;
+        }
 
         self.0.cache.set_width(window_entity, physical_width);
         self.0.cache.set_height(window_entity, physical_height);
@@ -119,7 +125,75 @@ impl BackendContext {
         surface: &mut Surface,
         dirty_surface: &mut Surface,
     ) -> bool {
-        draw_system(&mut self.0, window_entity, surface, dirty_surface)
+        #[cfg(feature = "profiling")]
+        let start = Instant::now();
+
+        self.0.style.use_window_scale_factor(window_entity);
+        let drawn = draw_system(&mut self.0, window_entity, surface, dirty_surface);
+        self.0.style.use_window_scale_factor(Entity::root());
+
+        #[cfg(feature = "profiling")]
+        {
+            self.0.profiler.record(ProfiledSystem::Draw, start.elapsed());
+            if drawn {
+                self.0.profiler.end_frame();
+            }
+        }
+
+        drawn
+    }
+
+    /// Returns the area of the text cursor reported since this was last called for the given window, which the
+    /// candidate window of an input method should be placed next to.
+    pub fn take_ime_cursor_area(&mut self, window_entity: Entity) -> Option<BoundingBox> {
+        self.0
+            .windows
+            .get_mut(&window_entity)
+            .and_then(|window_state| window_state.ime_cursor_area.take())
+    }
+
+    /// Renders the root window into an offscreen image with the given physical size, without a platform window.
+    ///
+    /// Any queued events are flushed and the style, layout, and draw systems are run before rendering. If no
+    /// windowing backend has added the root window, it is created with the given size. This can be used to render
+    /// views in tests, for example to compare against reference images. Returns `None` if the offscreen surface could
+    /// not be created.
+    pub fn render_to_image(&mut self, width: u32, height: u32) -> Option<image::RgbaImage> {
+        let mut surface = skia_safe::surfaces::raster_n32_premul((width as i32, height as i32))?;
+        let mut dirty_surface =
+            surface.new_surface_with_dimensions((width as i32, height as i32))?;
+
+        if !self.0.windows.contains_key(&Entity::root()) {
+            let window_description = WindowDescription::new().with_inner_size(width, height);
+            self.add_main_window(Entity::root(), &window_description, 1.0);
+            self.0
+                .windows
+                .insert(Entity::root(), WindowState { window_description, ..Default::default() });
+        }
+
+        self.set_window_size(Entity::root(), width as f32, height as f32);
+        self.needs_refresh(Entity::root());
+
+        EventManager::new().flush_events(&mut self.0, |_| {});
+
+        self.process_style_updates();
+        self.process_animations();
+        self.process_visual_updates();
+
+        self.draw(Entity::root(), &mut surface, &mut dirty_surface);
+
+        let info = ImageInfo::new(
+            (width as i32, height as i32),
+            ColorType::RGBA8888,
+            AlphaType::Unpremul,
+            None,
+        );
+        let mut pixels = vec![0; info.compute_min_byte_size()];
+        if !surface.read_pixels(&info, &mut pixels, info.min_row_bytes(), (0, 0)) {
+            return None;
+        }
+
+        image::RgbaImage::from_raw(width, height, pixels)
     }
 
     /// Set the current entity. This is useful in user code when you're performing black magic and
@@ -129,9 +203,38 @@ impl BackendContext {
         self.0.current = e;
     }
 
-    /// Sets the scale factor used by the application.
-    pub fn set_scale_factor(&mut self, scale: f64) {
-        self.0.style.dpi_factor = scale;
+    /// Sets the scale factor of a window, which is used to lay out and draw the views within it.
+    ///
+    /// If the scale factor changes, images provided by the image loader are loaded again so that the loader can
+    /// provide them at a suitable resolution, and a [`WindowEvent::ScaleFactorChanged`] event is sent to the views
+    /// of the window. The backend should then call [`needs_refresh`](Self::needs_refresh) to restyle and relayout the
+    /// views.
+    pub fn set_scale_factor(&mut self, window_entity: Entity, scale: f64) {
+        if self.0.style.window_scale_factors.get(window_entity) == Some(&scale) {
+            return;
+        }
+
+        self.0.style.window_scale_factors.insert(window_entity, scale);
+        if window_entity == Entity::root() {
+            self.0.style.dpi_factor = scale;
+        }
+
+        if let Some(loader) = self.0.resource_manager.image_loader.take() {
+            let names = self.0.resource_manager.loader_images.iter().cloned().collect::<Vec<_>>();
+            let cx = &mut ResourceContext::new(&mut self.0);
+            for name in names {
+                (loader)(cx, &name);
+            }
+
+            self.0.resource_manager.image_loader = Some(loader);
+        }
+
+        self.0.event_queue.push_back(
+            Event::new(WindowEvent::ScaleFactorChanged(scale as f32))
+                .target(window_entity)
+                .origin(window_entity)
+                .propagate(Propagation::Subtree),
+        );
     }
 
     /// Sets the size of the window.
@@ -146,10 +249,15 @@ impl BackendContext {
             BoundingBox::from_min_max(0.0, 0.0, physical_width, physical_height),
         );
 
-        let logical_width = self.0.style.physical_to_logical(physical_width);
-        let logical_height = self.0.style.physical_to_logical(physical_height);
+        let scale_factor = self.0.style.window_scale_factor(window_entity) as f32;
+        let logical_width = physical_width / scale_factor;
+        let logical_height = physical_height / scale_factor;
         self.0.style.width.insert(window_entity, Units::Pixels(logical_width));
         self.0.style.height.insert(window_entity, Units::Pixels(logical_height));
+
+        if self.0.resize_debounce.is_some() {
+            self.0.last_resize = Some(Instant::now());
+        }
     }
 
     /// Temporarily sets the current entity, calls the provided closure, and then resets the current entity back to previous.
@@ -175,11 +283,11 @@ impl BackendContext {
         self.0.event_proxy = Some(proxy);
     }
 
-    /// You should not call this method unless you are writing a windowing backend, in which case
-    /// you should consult the existing windowing backends for usage information.
+    /// Replaces the platform clipboard with a clipboard provided by the windowing backend, for text, images and
+    /// HTML alike. The built-in backends use the platform clipboard and don't call this method.
     #[cfg(feature = "clipboard")]
     pub fn set_clipboard_provider(&mut self, clipboard: Box<dyn ClipboardProvider>) {
-        self.0.clipboard = clipboard;
+        self.0.clipboard.set_provider(clipboard);
     }
 
     /// Send an event with custom origin and propagation information.
@@ -192,32 +300,104 @@ impl BackendContext {
         !self.0.event_queue.is_empty()
     }
 
+    /// Check whether key repeats are generated by the application, in which case key repeats from the platform
+    /// should be ignored.
+    pub fn has_custom_key_repeat(&self) -> bool {
+        self.0.key_repeat.is_some()
+    }
+
     pub fn renegotiate_language(&mut self) {
         self.0.resource_manager.renegotiate_language();
     }
 
     /// Calls the accessibility system and updates the accesskit node tree.
     pub fn process_tree_updates(&mut self) {
+        #[cfg(feature = "profiling")]
+        let start = Instant::now();
+
         accessibility_system(&mut self.0);
+
+        #[cfg(feature = "profiling")]
+        self.0.profiler.record(ProfiledSystem::Accessibility, start.elapsed());
     }
 
     /// Calls the style system to match entities with shared styles.
     pub fn process_style_updates(&mut self) {
+        #[cfg(feature = "profiling")]
+        let start = Instant::now();
+
         style_system(&mut self.0);
 
         // Load any unloaded images and remove unused images.
         image_system(&mut self.0);
+
+        #[cfg(feature = "profiling")]
+        self.0.profiler.record(ProfiledSystem::Style, start.elapsed());
     }
 
     // Returns true if animations are playing
     pub fn process_animations(&mut self) -> bool {
-        animation_system(&mut self.0)
+        self.process_animations_at(Instant::now())
+    }
+
+    // Ticks all animations to the given time, which lets tests advance time without waiting.
+    pub(crate) fn process_animations_at(&mut self, now: Instant) -> bool {
+        if self.0.frozen.is_some() {
+            return false;
+        }
+
+        animation_system(&mut self.0, now)
+    }
+
+    /// Runs the callbacks registered with [`Context::on_idle`] if the application is idle.
+    ///
+    /// The application is considered idle when there are no queued events, no windows waiting to be redrawn, and
+    /// `animating` is false. Returns true if any callbacks were run.
+    pub fn process_idle_callbacks(&mut self, animating: bool) -> bool {
+        let is_idle = !animating
+            && self.0.event_queue.is_empty()
+            && self.0.windows.values().all(|window_state| {
+                window_state.redraw_list.is_empty() && window_state.dirty_rect.is_none()
+            });
+
+        if !is_idle || self.0.idle_callbacks.is_empty() {
+            return false;
+        }
+
+        let callbacks = std::mem::take(&mut self.0.idle_callbacks);
+        for callback in callbacks {
+            self.0.current = Entity::root();
+            (callback)(&mut self.0);
+        }
+
+        true
+    }
+
+    /// Saves the state of the models serialized by [`Context::serialize_models`], which should be called when the
+    /// application exits.
+    pub fn on_exit(&mut self) {
+        #[cfg(feature = "serde")]
+        if !self.0.persistence.is_empty() {
+            if let Err(err) = self.0.save_state() {
+                log::warn!("Failed to save the application state: {}", err);
+            }
+        }
     }
 
     /// Massages the style system until everything is coherent
     pub fn process_visual_updates(&mut self) {
+        if self.0.frozen.is_some() {
+            return;
+        }
+
+        #[cfg(feature = "profiling")]
+        let start = Instant::now();
+
         // Perform layout.
         layout_system(&mut self.0);
+
+        #[cfg(feature = "profiling")]
+        self.0.profiler.record(ProfiledSystem::Layout, start.elapsed());
     }
 
     pub fn emit_origin<M: Send + Any>(&mut self, message: M) {
@@ -238,6 +418,55 @@ impl BackendContext {
         );
     }
 
+    /// Sends relative mouse motion, as reported by the device, to the view which has locked the pointer, if any.
+    pub fn emit_pointer_motion(&mut self, dx: f32, dy: f32) {
+        self.0.raw_pointer_motion = true;
+
+        if let Some(entity) = self.0.pointer_lock {
+            self.0.event_queue.push_back(
+                Event::new(WindowEvent::MouseMotion(dx, dy))
+                    .target(entity)
+                    .origin(entity)
+                    .propagate(Propagation::Direct),
+            );
+        }
+    }
+
+    /// Sends the unaccelerated motion of a mouse to the view which has locked the pointer, or else to the view which
+    /// has captured the mouse, or else to the hovered view.
+    pub fn emit_raw_mouse_delta(&mut self, device: DeviceId, dx: f64, dy: f64) {
+        let target = self.0.pointer_lock.unwrap_or(if self.0.captured != Entity::null() {
+            self.0.captured
+        } else {
+            self.0.hovered
+        });
+
+        self.0.event_queue.push_back(
+            Event::new(WindowEvent::RawMouseDelta { device, dx, dy })
+                .target(target)
+                .origin(target)
+                .propagate(Propagation::Up),
+        );
+    }
+
+    /// Sends a key press or release, as reported by the keyboard, to the focused view.
+    pub fn emit_raw_key(
+        &mut self,
+        device: DeviceId,
+        code: Code,
+        scancode: Option<u32>,
+        pressed: bool,
+    ) {
+        let target = self.0.focused;
+
+        self.0.event_queue.push_back(
+            Event::new(WindowEvent::RawKey { device, code, scancode, pressed })
+                .target(target)
+                .origin(target)
+                .propagate(Propagation::Up),
+        );
+    }
+
     pub fn needs_refresh(&mut self, window_entity: Entity) {
         self.0.cache.path.remove(window_entity);
         self.0.style.system_flags = SystemFlags::all();
@@ -253,22 +482,31 @@ impl BackendContext {
     }
 
     pub fn process_timers(&mut self) {
+        if self.0.frozen.is_some() {
+            return;
+        }
+
         self.0.tick_timers();
     }
 
     pub fn get_next_timer_time(&self) -> Option<Instant> {
+        if self.0.frozen.is_some() {
+            return None;
+        }
+
         let timer_time = self.0.running_timers.peek().map(|timer_state| timer_state.time);
         let scheduled_event_time = self.0.event_schedule.peek().map(|timed_event| timed_event.time);
+        // Wake up to perform the final relayout once a debounced resize has ended.
+        let resize_end_time = self.0.last_resize.zip(self.0.resize_debounce).map(|(t, d)| t + d);
 
-        match (timer_time, scheduled_event_time) {
-            (Some(t1), Some(t2)) => Some(t1.min(t2)),
-            (Some(t), None) => Some(t),
-            (None, Some(t)) => Some(t),
-            _ => None,
-        }
+        [timer_time, scheduled_event_time, resize_end_time].into_iter().flatten().min()
     }
 
     pub fn emit_scheduled_events(&mut self) {
+        if self.0.frozen.is_some() {
+            return;
+        }
+
         let now = Instant::now();
         while let Some(timed_event) = self.0.event_schedule.peek() {
             if timed_event.time <= now {
@@ -279,3 +517,64 @@ impl BackendContext {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use crate::context::backend::BackendContext;
+    use crate::context::test_support::{draw, update, window_context};
+    use crate::prelude::*;
+
+    #[test]
+    fn idle_callbacks() {
+        let mut cx = window_context(400, 300);
+
+        let calls = Rc::new(RefCell::new(Vec::new()));
+
+        let first = calls.clone();
+        cx.0.on_idle(move |cx| {
+            first.borrow_mut().push("first");
+
+            // Callbacks registered from within an idle callback wait for the next point of idleness.
+            let second = first.clone();
+            cx.on_idle(move |_| second.borrow_mut().push("second"));
+        });
+
+        // The application isn't idle while animating or while events are queued.
+        assert!(!cx.process_idle_callbacks(true));
+        cx.send_event(Event::new(()));
+        assert!(!cx.process_idle_callbacks(false));
+
+        update(&mut cx);
+
+        // Nor while a window needs to be redrawn.
+        cx.0.needs_redraw(Entity::root());
+        assert!(!cx.process_idle_callbacks(false));
+        assert!(calls.borrow().is_empty());
+
+        draw(&mut cx);
+
+        assert!(cx.process_idle_callbacks(false));
+        assert_eq!(*calls.borrow(), ["first"]);
+
+        assert!(cx.process_idle_callbacks(false));
+        assert_eq!(*calls.borrow(), ["first", "second"]);
+
+        assert!(!cx.process_idle_callbacks(false));
+    }
+
+    #[test]
+    fn render_to_image() {
+        // No window is added, so the root window is created by the headless render.
+        let mut cx = BackendContext::new(Context::new());
+
+        Element::new(cx.context()).size(Pixels(100.0)).background_color(Color::rgb(255, 0, 0));
+
+        let image = cx.render_to_image(200, 100).unwrap();
+        assert_eq!(image.dimensions(), (200, 100));
+        assert_eq!(image.get_pixel(50, 50).0, [255, 0, 0, 255]);
+        assert_ne!(image.get_pixel(150, 50).0, [255, 0, 0, 255]);
+    }
+}
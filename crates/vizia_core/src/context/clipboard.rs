@@ -0,0 +1,244 @@
+use std::error::Error;
+
+use skia_safe::{images, AlphaType, CachingHint, ColorType, Data, ImageInfo};
+
+/// An image read from or written to the system clipboard, stored as non-premultiplied RGBA pixels.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClipboardImage {
+    /// The width of the image in pixels.
+    pub width: usize,
+    /// The height of the image in pixels.
+    pub height: usize,
+    /// The RGBA pixels of the image, four bytes per pixel, row by row.
+    pub bytes: Vec<u8>,
+}
+
+impl ClipboardImage {
+    /// Creates a new clipboard image from RGBA pixels.
+    pub fn new(width: usize, height: usize, bytes: Vec<u8>) -> Self {
+        Self { width, height, bytes }
+    }
+
+    /// Creates a clipboard image by reading the pixels of a skia image, returning `None` if they can't be read.
+    pub fn from_image(image: &skia_safe::Image) -> Option<Self> {
+        let (width, height) = (image.width() as usize, image.height() as usize);
+        let info = Self::image_info(width, height);
+        let mut bytes = vec![0; width * height * 4];
+        image
+            .read_pixels(&info, &mut bytes, width * 4, (0, 0), CachingHint::Allow)
+            .then(|| Self::new(width, height, bytes))
+    }
+
+    /// Creates a skia image from the pixels, which can be drawn by a custom view or loaded by an image loader with
+    /// [`ResourceContext::load_image`](crate::context::ResourceContext::load_image). Returns `None` if the size
+    /// doesn't match the number of pixels.
+    pub fn to_image(&self) -> Option<skia_safe::Image> {
+        if self.bytes.len() != self.width * self.height * 4 {
+            return None;
+        }
+
+        let info = Self::image_info(self.width, self.height);
+        images::raster_from_data(&info, Data::new_copy(&self.bytes), self.width * 4)
+    }
+
+    fn image_info(width: usize, height: usize) -> ImageInfo {
+        ImageInfo::new(
+            (width as i32, height as i32),
+            ColorType::RGBA8888,
+            AlphaType::Unpremul,
+            None,
+        )
+    }
+}
+
+/// A clipboard which a windowing backend can provide in place of the platform clipboard.
+///
+/// Once a provider is set, all clipboard access goes through it. Providers which only support text can rely on the
+/// default methods, which reject images and copy HTML as its plain text alternative.
+pub trait ClipboardProvider {
+    /// Returns the text on the clipboard.
+    fn get_text(&mut self) -> Result<String, Box<dyn Error + Send + Sync + 'static>>;
+
+    /// Replaces the contents of the clipboard with text.
+    fn set_text(&mut self, text: String) -> Result<(), Box<dyn Error + Send + Sync + 'static>>;
+
+    /// Returns the image on the clipboard.
+    fn get_image(&mut self) -> Result<ClipboardImage, Box<dyn Error + Send + Sync + 'static>> {
+        Err("The clipboard provider does not support images".into())
+    }
+
+    /// Replaces the contents of the clipboard with an image.
+    fn set_image(
+        &mut self,
+        _image: ClipboardImage,
+    ) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+        Err("The clipboard provider does not support images".into())
+    }
+
+    /// Replaces the contents of the clipboard with HTML, along with an optional plain text alternative.
+    fn set_html(
+        &mut self,
+        _html: &str,
+        alt_text: Option<&str>,
+    ) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+        let alt_text = alt_text.ok_or("The clipboard provider does not support HTML")?;
+        self.set_text(alt_text.to_owned())
+    }
+}
+
+// The platform clipboard, which is connected to the first time it's used.
+//
+// If the platform clipboard is unavailable, for example in a headless environment, text is kept within the
+// application instead so that it can still be copied and pasted between views. A windowing backend can also replace
+// the platform clipboard with its own provider.
+#[derive(Default)]
+pub(crate) struct Clipboard {
+    platform: Option<arboard::Clipboard>,
+    provider: Option<Box<dyn ClipboardProvider>>,
+    // Whether the platform clipboard is never used, which keeps tests independent of the system clipboard.
+    local_only: bool,
+    local_text: Option<String>,
+}
+
+impl Clipboard {
+    // Creates a clipboard which only keeps text within the application.
+    #[cfg(test)]
+    pub(crate) fn local() -> Self {
+        Self { local_only: true, ..Default::default() }
+    }
+
+    pub(crate) fn set_provider(&mut self, provider: Box<dyn ClipboardProvider>) {
+        self.provider = Some(provider);
+    }
+
+    fn clipboard(
+        &mut self,
+    ) -> Result<&mut arboard::Clipboard, Box<dyn Error + Send + Sync + 'static>> {
+        if self.local_only {
+            return Err("The platform clipboard is not used".into());
+        }
+
+        if self.platform.is_none() {
+            self.platform = Some(arboard::Clipboard::new()?);
+        }
+
+        Ok(self.platform.as_mut().unwrap())
+    }
+
+    pub(crate) fn get_text(&mut self) -> Result<String, Box<dyn Error + Send + Sync + 'static>> {
+        if let Some(provider) = &mut self.provider {
+            return provider.get_text();
+        }
+
+        match self.clipboard() {
+            Ok(clipboard) => Ok(clipboard.get_text()?),
+            Err(err) => self.local_text.clone().ok_or(err),
+        }
+    }
+
+    pub(crate) fn set_text(
+        &mut self,
+        text: String,
+    ) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+        if let Some(provider) = &mut self.provider {
+            return provider.set_text(text);
+        }
+
+        match self.clipboard() {
+            Ok(clipboard) => Ok(clipboard.set_text(text)?),
+            Err(_) => {
+                self.local_text = Some(text);
+                Ok(())
+            }
+        }
+    }
+
+    pub(crate) fn get_image(
+        &mut self,
+    ) -> Result<ClipboardImage, Box<dyn Error + Send + Sync + 'static>> {
+        if let Some(provider) = &mut self.provider {
+            return provider.get_image();
+        }
+
+        let image = self.clipboard()?.get_image()?;
+        Ok(ClipboardImage::new(image.width, image.height, image.bytes.into_owned()))
+    }
+
+    pub(crate) fn set_image(
+        &mut self,
+        image: ClipboardImage,
+    ) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+        if let Some(provider) = &mut self.provider {
+            return provider.set_image(image);
+        }
+
+        let image = arboard::ImageData {
+            width: image.width,
+            height: image.height,
+            bytes: image.bytes.into(),
+        };
+        Ok(self.clipboard()?.set_image(image)?)
+    }
+
+    pub(crate) fn set_html(
+        &mut self,
+        html: &str,
+        alt_text: Option<&str>,
+    ) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+        if let Some(provider) = &mut self.provider {
+            return provider.set_html(html, alt_text);
+        }
+
+        match self.clipboard() {
+            Ok(clipboard) => Ok(clipboard.set_html(html, alt_text)?),
+            // The plain text alternative can still be pasted within the application.
+            Err(err) => {
+                self.local_text = Some(alt_text.ok_or(err)?.to_owned());
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    struct SharedClipboard(Arc<Mutex<String>>);
+
+    impl ClipboardProvider for SharedClipboard {
+        fn get_text(&mut self) -> Result<String, Box<dyn Error + Send + Sync + 'static>> {
+            Ok(self.0.lock().unwrap().clone())
+        }
+
+        fn set_text(&mut self, text: String) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+            *self.0.lock().unwrap() = text;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn provider() {
+        let contents = Arc::new(Mutex::new(String::new()));
+        let mut clipboard = Clipboard::local();
+        clipboard.set_provider(Box::new(SharedClipboard(contents.clone())));
+
+        // Text is read and written through the provider set by the backend.
+        clipboard.set_text(String::from("hello")).unwrap();
+        assert_eq!(*contents.lock().unwrap(), "hello");
+        assert!(clipboard.local_text.is_none());
+
+        *contents.lock().unwrap() = String::from("world");
+        assert_eq!(clipboard.get_text().unwrap(), "world");
+
+        // HTML is copied to the provider as its plain text alternative, and images aren't split off to another
+        // clipboard.
+        clipboard.set_html("<b>bold</b>", Some("bold")).unwrap();
+        assert_eq!(*contents.lock().unwrap(), "bold");
+        assert!(clipboard.set_image(ClipboardImage::new(1, 1, vec![0; 4])).is_err());
+        assert!(clipboard.get_image().is_err());
+        assert!(clipboard.local_text.is_none());
+    }
+}
@@ -5,7 +5,7 @@ use skia_safe::rrect::Corner;
 use skia_safe::wrapper::PointerWrapper;
 use skia_safe::{
     BlurStyle, ClipOp, MaskFilter, Matrix, Paint, PaintStyle, Path, PathDirection, PathEffect,
-    Point, RRect, Rect, SamplingOptions, Shader, TileMode,
+    PictureRecorder, Point, RRect, Rect, SamplingOptions, Shader, TileMode,
 };
 use std::any::{Any, TypeId};
 use std::f32::consts::SQRT_2;
@@ -14,7 +14,7 @@ use vizia_style::LengthPercentageOrAuto;
 use hashbrown::HashMap;
 
 use crate::animation::Interpolator;
-use crate::cache::CachedData;
+use crate::cache::{CachedData, CachedPicture};
 use crate::events::ViewHandler;
 use crate::prelude::*;
 use crate::resource::{ImageOrSvg, ResourceManager};
@@ -135,6 +135,95 @@ impl DrawContext<'_> {
         }
     }
 
+    /// Marks a region of the current view, in physical pixels, as needing to be redrawn.
+    ///
+    /// Unlike [`needs_redraw`](Self::needs_redraw), only the part of the region within the drawn bounds of the view is
+    /// repainted, which allows views with small animated parts to avoid repainting their whole bounds every frame.
+    pub fn needs_redraw_region(&mut self, region: BoundingBox) {
+        let Some(draw_bounds) = self.cache.draw_bounds.get(self.current).copied() else {
+            self.needs_redraw();
+            return;
+        };
+
+        let parent_window = self.tree.get_parent_window(self.current).unwrap_or(Entity::root());
+        if let Some(window_state) = self.windows.get_mut(&parent_window) {
+            window_state.add_dirty_region(region.intersection(&draw_bounds));
+        }
+    }
+
+    /// Sets the area of the text cursor of the current view in physical pixels, which the candidate window of an input
+    /// method is placed next to. This should be called each time the cursor of a focused text input is drawn.
+    pub fn set_ime_cursor_area(&mut self, area: BoundingBox) {
+        let parent_window = self.tree.get_parent_window(self.current).unwrap_or(Entity::root());
+        if let Some(window_state) = self.windows.get_mut(&parent_window) {
+            window_state.ime_cursor_area = Some(area);
+        }
+    }
+
+    /// Draws a retained drawing of the current view, which is recorded by calling the given closure with a recording
+    /// canvas the first time the view is drawn and then replayed on later frames without calling the closure again.
+    ///
+    /// The drawing is recorded again when the size of the view changes, or after it's been invalidated with
+    /// [`EventContext::request_draw_cache_invalidation`], so the closure should only draw content which doesn't
+    /// otherwise change between frames. Moving the view doesn't invalidate the drawing.
+    ///
+    /// # Example
+    /// ```
+    /// # use vizia_core::prelude::*;
+    /// # use vizia_core::vg;
+    /// pub struct Plot {}
+    ///
+    /// impl View for Plot {
+    ///     fn draw(&self, cx: &mut DrawContext, canvas: &Canvas) {
+    ///         cx.draw_cached(canvas, |cx, canvas| {
+    ///             let bounds = cx.bounds();
+    ///             let mut path = vg::Path::new();
+    ///             path.move_to((bounds.x, bounds.bottom()));
+    ///             path.line_to((bounds.right(), bounds.y));
+    ///             let mut paint = vg::Paint::default();
+    ///             paint.set_style(vg::PaintStyle::Stroke);
+    ///             canvas.draw_path(&path, &paint);
+    ///         });
+    ///     }
+    /// }
+    /// ```
+    pub fn draw_cached(&mut self, canvas: &Canvas, record: impl FnOnce(&mut DrawContext, &Canvas)) {
+        let bounds = self.bounds();
+        let cached = self
+            .cache
+            .picture
+            .get(self.current)
+            .filter(|cached| cached.size == (bounds.w, bounds.h))
+            .map(|cached| cached.picture.clone());
+
+        let picture = match cached {
+            Some(picture) => picture,
+            None => {
+                // The drawing is recorded relative to the top left of the view so it can be replayed after it moves.
+                let mut recorder = PictureRecorder::new();
+                {
+                    let recording_canvas =
+                        recorder.begin_recording(Rect::from_wh(bounds.w, bounds.h), None);
+                    recording_canvas.translate((-bounds.x, -bounds.y));
+                    record(self, recording_canvas);
+                }
+
+                let Some(picture) = recorder.finish_recording_as_picture(None) else {
+                    return;
+                };
+
+                self.cache.picture.insert(
+                    self.current,
+                    CachedPicture { size: (bounds.w, bounds.h), picture: picture.clone() },
+                );
+
+                picture
+            }
+        };
+
+        canvas.draw_picture(&picture, Some(&Matrix::translate(bounds.top_left())), None);
+    }
+
     /// Returns the z-index of the current view.
     pub fn z_index(&self) -> i32 {
         self.style.z_index.get(self.current).copied().unwrap_or_default()
@@ -805,8 +894,9 @@ impl DrawContext<'_> {
 
             path.offset(bounds.top_left());
 
+            // The first shadow is drawn on top.
             for shadow in shadows.iter().rev() {
-                let shadow_color = shadow.color.unwrap_or_default();
+                let shadow_color = shadow.color.unwrap_or_else(|| self.font_color());
 
                 let shadow_x_offset = shadow.x_offset.to_px().unwrap_or(0.0) * self.scale_factor();
                 let shadow_y_offset = shadow.y_offset.to_px().unwrap_or(0.0) * self.scale_factor();
@@ -815,7 +905,8 @@ impl DrawContext<'_> {
                         * self.scale_factor();
 
                 let blur_radius =
-                    shadow.blur_radius.as_ref().and_then(|br| br.to_px()).unwrap_or(0.0);
+                    shadow.blur_radius.as_ref().and_then(|br| br.to_px()).unwrap_or(0.0).max(0.0)
+                        * self.scale_factor();
 
                 if shadow_color.a() == 0
                     || (shadow_x_offset == 0.0
@@ -828,6 +919,7 @@ impl DrawContext<'_> {
 
                 let mut shadow_paint = Paint::default();
 
+                // The spread grows an outer shadow and shrinks the hole which casts an inset shadow.
                 let outset = if shadow.inset { -spread_radius } else { spread_radius };
 
                 shadow_paint.set_style(PaintStyle::Fill);
@@ -838,6 +930,7 @@ impl DrawContext<'_> {
                 shadow_paint.set_color(shadow_color);
 
                 if blur_radius > 0.0 {
+                    // As in CSS, the shadow is blurred with a standard deviation of half the blur radius.
                     shadow_paint.set_mask_filter(MaskFilter::blur(
                         BlurStyle::Normal,
                         blur_radius / 2.0,
@@ -848,7 +941,17 @@ impl DrawContext<'_> {
                 shadow_path.offset((shadow_x_offset, shadow_y_offset));
 
                 if shadow.inset {
-                    shadow_path = path.op(&shadow_path, skia_safe::PathOp::Difference).unwrap();
+                    // An inset shadow is cast by the area around the hole, which extends past the view so that the
+                    // shadow stays darkest at the edges of the view once it's blurred.
+                    let margin = blur_radius * 2.0
+                        + spread_radius.abs()
+                        + shadow_x_offset.abs()
+                        + shadow_y_offset.abs();
+                    let mut outer_path = Path::new();
+                    outer_path.add_rect(Rect::from(bounds.expand(margin)), None);
+                    shadow_path = outer_path
+                        .op(&shadow_path, skia_safe::PathOp::Difference)
+                        .unwrap_or_default();
                 }
 
                 canvas.save();
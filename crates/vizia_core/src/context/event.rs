@@ -9,19 +9,22 @@ use vizia_storage::{LayoutTreeIterator, TreeIterator};
 
 use crate::animation::{AnimId, Interpolator};
 use crate::cache::CachedData;
-use crate::events::{TimedEvent, TimedEventHandle, TimerState, ViewHandler};
+use crate::events::{Interceptors, TimedEvent, TimedEventHandle, TimerState, ViewHandler};
 use crate::prelude::*;
 use crate::resource::ResourceManager;
-use crate::tree::{focus_backward, focus_forward, is_navigatable};
+use crate::systems::query_selector;
+use crate::tree::{focus_backward, focus_forward, focus_lock, is_navigatable, window_of};
 use vizia_input::MouseState;
 
 use skia_safe::Matrix;
 
 use crate::text::TextContext;
-#[cfg(feature = "clipboard")]
-use copypasta::ClipboardProvider;
 
-use super::{LocalizationContext, ModelData, DARK_THEME, LIGHT_THEME};
+#[cfg(feature = "clipboard")]
+use super::Clipboard;
+#[cfg(feature = "serde")]
+use super::{model_key, Persistence};
+use super::{InternalEvent, Jobs, LocalizationContext, ModelData, DARK_THEME, LIGHT_THEME};
 
 type Views = HashMap<Entity, Box<dyn ViewHandler>>;
 type Models = HashMap<Entity, HashMap<TypeId, Box<dyn ModelData>>>;
@@ -61,8 +64,11 @@ type Models = HashMap<Entity, HashMap<TypeId, Box<dyn ModelData>>>;
 pub struct EventContext<'a> {
     pub(crate) current: Entity,
     pub(crate) captured: &'a mut Entity,
+    pub(crate) pointer_lock: &'a mut Option<Entity>,
     pub(crate) focused: &'a mut Entity,
+    pub(crate) focus_scopes: &'a mut HashMap<Entity, Vec<(Entity, Entity)>>,
     pub(crate) hovered: &'a Entity,
+    pub(crate) hover_capture: &'a Option<Entity>,
     pub(crate) triggered: &'a mut Entity,
     pub(crate) style: &'a mut Style,
     pub(crate) entity_identifiers: &'a HashMap<String, Entity>,
@@ -72,6 +78,7 @@ pub struct EventContext<'a> {
     pub(crate) views: &'a mut Views,
     pub(crate) listeners:
         &'a mut HashMap<Entity, Box<dyn Fn(&mut dyn ViewHandler, &mut EventContext, &mut Event)>>,
+    pub(crate) interceptors: &'a mut Interceptors,
     pub(crate) resource_manager: &'a mut ResourceManager,
     pub(crate) text_context: &'a mut TextContext,
     pub(crate) modifiers: &'a Modifiers,
@@ -83,11 +90,17 @@ pub struct EventContext<'a> {
     pub(crate) running_timers: &'a mut BinaryHeap<TimerState>,
     cursor_icon_locked: &'a mut bool,
     #[cfg(feature = "clipboard")]
-    clipboard: &'a mut Box<dyn ClipboardProvider>,
+    clipboard: &'a mut Clipboard,
+    #[cfg(feature = "serde")]
+    persistence: &'a Persistence,
     pub(crate) event_proxy: &'a mut Option<Box<dyn crate::context::EventProxy>>,
     pub(crate) ignore_default_theme: &'a bool,
+    pub(crate) catch_panics: &'a bool,
     pub(crate) drop_data: &'a mut Option<DropData>,
     pub windows: &'a mut HashMap<Entity, WindowState>,
+    pub(crate) scoped_classes: &'a mut HashMap<(Entity, String), (TimedEventHandle, bool)>,
+    pub(crate) deferred: &'a mut Vec<(Entity, Box<dyn FnOnce(&mut Context)>)>,
+    pub(crate) jobs: &'a mut Jobs,
 }
 
 macro_rules! get_length_property {
@@ -115,8 +128,11 @@ impl<'a> EventContext<'a> {
         Self {
             current: cx.current,
             captured: &mut cx.captured,
+            pointer_lock: &mut cx.pointer_lock,
             focused: &mut cx.focused,
+            focus_scopes: &mut cx.focus_scopes,
             hovered: &cx.hovered,
+            hover_capture: &cx.hover_capture,
             triggered: &mut cx.triggered,
             entity_identifiers: &cx.entity_identifiers,
             style: &mut cx.style,
@@ -125,6 +141,7 @@ impl<'a> EventContext<'a> {
             models: &mut cx.models,
             views: &mut cx.views,
             listeners: &mut cx.listeners,
+            interceptors: &mut cx.interceptors,
             resource_manager: &mut cx.resource_manager,
             text_context: &mut cx.text_context,
             modifiers: &cx.modifiers,
@@ -137,10 +154,16 @@ impl<'a> EventContext<'a> {
             cursor_icon_locked: &mut cx.cursor_icon_locked,
             #[cfg(feature = "clipboard")]
             clipboard: &mut cx.clipboard,
+            #[cfg(feature = "serde")]
+            persistence: &cx.persistence,
             event_proxy: &mut cx.event_proxy,
             ignore_default_theme: &cx.ignore_default_theme,
+            catch_panics: &cx.catch_panics,
             drop_data: &mut cx.drop_data,
             windows: &mut cx.windows,
+            scoped_classes: &mut cx.scoped_classes,
+            deferred: &mut cx.deferred,
+            jobs: &mut cx.jobs,
         }
     }
 
@@ -149,8 +172,11 @@ impl<'a> EventContext<'a> {
         Self {
             current,
             captured: &mut cx.captured,
+            pointer_lock: &mut cx.pointer_lock,
             focused: &mut cx.focused,
+            focus_scopes: &mut cx.focus_scopes,
             hovered: &cx.hovered,
+            hover_capture: &cx.hover_capture,
             triggered: &mut cx.triggered,
             entity_identifiers: &cx.entity_identifiers,
             style: &mut cx.style,
@@ -159,6 +185,7 @@ impl<'a> EventContext<'a> {
             models: &mut cx.models,
             views: &mut cx.views,
             listeners: &mut cx.listeners,
+            interceptors: &mut cx.interceptors,
             resource_manager: &mut cx.resource_manager,
             text_context: &mut cx.text_context,
             modifiers: &cx.modifiers,
@@ -171,10 +198,16 @@ impl<'a> EventContext<'a> {
             cursor_icon_locked: &mut cx.cursor_icon_locked,
             #[cfg(feature = "clipboard")]
             clipboard: &mut cx.clipboard,
+            #[cfg(feature = "serde")]
+            persistence: &cx.persistence,
             event_proxy: &mut cx.event_proxy,
             ignore_default_theme: &cx.ignore_default_theme,
+            catch_panics: &cx.catch_panics,
             drop_data: &mut cx.drop_data,
             windows: &mut cx.windows,
+            scoped_classes: &mut cx.scoped_classes,
+            deferred: &mut cx.deferred,
+            jobs: &mut cx.jobs,
         }
     }
 
@@ -199,6 +232,26 @@ impl<'a> EventContext<'a> {
         self.entity_identifiers.get(id).cloned()
     }
 
+    /// Returns the entities which match a CSS selector, in tree order.
+    ///
+    /// See [`Context::query`].
+    pub fn query(&self, selector: &str) -> Vec<Entity> {
+        match vizia_style::parse_selector_list(selector) {
+            Ok(selectors) => query_selector(&selectors, self.style, self.tree),
+            Err(err) => {
+                log::warn!("Failed to parse selector '{}': {:?}", selector, err);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Shows or hides the inspector once the current event has been handled.
+    ///
+    /// See [`Context::toggle_inspector`].
+    pub fn toggle_inspector(&mut self) {
+        self.defer(|cx| cx.toggle_inspector());
+    }
+
     /// Returns the [Entity] id of the current view.
     pub fn current(&self) -> Entity {
         self.current
@@ -244,19 +297,19 @@ impl<'a> EventContext<'a> {
     //     self.cache.set_bounds(self.current, bounds);
     // }
 
-    /// Returns the scale factor.
+    /// Returns the scale factor of the window containing the current view.
     pub fn scale_factor(&self) -> f32 {
-        self.style.dpi_factor as f32
+        self.style.window_scale_factor(window_of(self.tree, self.current)) as f32
     }
 
     /// Converts logical points to physical pixels.
     pub fn logical_to_physical(&self, logical: f32) -> f32 {
-        self.style.logical_to_physical(logical)
+        (logical * self.scale_factor()).round()
     }
 
     /// Convert physical pixels to logical points.
     pub fn physical_to_logical(&self, physical: f32) -> f32 {
-        self.style.physical_to_logical(physical)
+        physical / self.scale_factor()
     }
 
     /// Returns the clip bounds of the current view.
@@ -391,6 +444,64 @@ impl<'a> EventContext<'a> {
         }
     }
 
+    /// Plays the same animation on each of the given entities, with the start of each animation delayed by
+    /// an additional `per_item_delay` after the previous one, producing a cascade effect.
+    pub fn stagger_animation(
+        &mut self,
+        anim_id: impl AnimId,
+        entities: &[Entity],
+        duration: Duration,
+        per_item_delay: Duration,
+    ) {
+        if let Some(animation_id) = anim_id.get(self) {
+            for (index, entity) in entities.iter().enumerate() {
+                self.style.enqueue_animation(
+                    *entity,
+                    animation_id,
+                    duration,
+                    per_item_delay * index as u32,
+                );
+            }
+        }
+    }
+
+    /// Plays an animation with the given id on a target view, using the given options to control the delay,
+    /// repetition and direction of the animation and to be notified when it completes.
+    ///
+    /// Playing an animation which is already playing on the view with options restarts it.
+    pub fn play_animation_with(
+        &mut self,
+        anim_id: impl AnimId,
+        entity: Entity,
+        options: AnimationOptions,
+    ) {
+        if let Some(animation_id) = anim_id.get(self) {
+            self.style.play_animation_with(entity, animation_id, options);
+        }
+    }
+
+    /// Pauses an animation with the given id playing on a target view, holding the animated values.
+    pub fn pause_animation(&mut self, anim_id: impl AnimId, entity: Entity) {
+        if let Some(animation_id) = anim_id.get(self) {
+            self.style.pause_animation(entity, animation_id);
+        }
+    }
+
+    /// Resumes a paused animation with the given id on a target view from where it was paused.
+    pub fn resume_animation(&mut self, anim_id: impl AnimId, entity: Entity) {
+        if let Some(animation_id) = anim_id.get(self) {
+            self.style.resume_animation(entity, animation_id);
+        }
+    }
+
+    /// Stops an animation with the given id playing on a target view without calling its completion callback.
+    pub fn cancel_animation(&mut self, anim_id: impl AnimId, entity: Entity) {
+        if let Some(animation_id) = anim_id.get(self) {
+            self.style.cancel_animation(entity, animation_id);
+            self.with_current(entity, |cx| cx.needs_relayout());
+        }
+    }
+
     /// Returns true if the current view is currently animating with the given animation id.
     pub fn is_animating(&self, anim_id: impl AnimId) -> bool {
         if let Some(animation_id) = anim_id.get(self) {
@@ -420,6 +531,22 @@ impl<'a> EventContext<'a> {
         );
     }
 
+    /// Adds a global interceptor to the application, returning an id which can be used to remove it.
+    ///
+    /// See [`Context::add_interceptor`] for how interceptors are called.
+    pub fn add_interceptor<F>(&mut self, priority: i32, interceptor: F) -> InterceptorId
+    where
+        F: 'static + Fn(&mut EventContext, &mut Event),
+    {
+        self.interceptors.add(priority, interceptor)
+    }
+
+    /// Removes a global interceptor added with [`add_interceptor`](Self::add_interceptor), returning whether it was
+    /// found.
+    pub fn remove_interceptor(&mut self, id: InterceptorId) -> bool {
+        self.interceptors.remove(id)
+    }
+
     /// Sets the language used by the application for localization.
     pub fn set_language(&mut self, lang: LanguageIdentifier) {
         if let Some(mut models) = self.models.remove(&Entity::root()) {
@@ -443,6 +570,74 @@ impl<'a> EventContext<'a> {
         }
     }
 
+    /// Sets how the mouse cursor is grabbed by the window of the current view.
+    ///
+    /// A locked cursor which the platform doesn't support is confined to the window instead. Unlike
+    /// [`lock_pointer`](Self::lock_pointer), the cursor isn't hidden and no [`WindowEvent::MouseMotion`] events are
+    /// sent.
+    pub fn set_cursor_grab(&mut self, mode: CursorGrabMode) {
+        let window = self.parent_window();
+        self.emit_to(window, WindowEvent::SetCursorGrab(mode));
+    }
+
+    /// Shows or hides the mouse cursor while it's over the window of the current view.
+    ///
+    /// A view which hides the cursor while dragging, such as a knob, can store the position of the cursor when the
+    /// drag starts and move the cursor back with [`set_cursor_position`](Self::set_cursor_position) when it shows
+    /// the cursor again on release.
+    pub fn set_cursor_visible(&mut self, visible: bool) {
+        let window = self.parent_window();
+        self.emit_to(window, WindowEvent::SetCursorVisible(visible));
+    }
+
+    /// Moves the mouse cursor to the given (x,y) position in physical pixels relative to the window of the current
+    /// view, such as the position of [`mouse`](Self::mouse) when a drag started. Not all platforms support moving
+    /// the cursor.
+    pub fn set_cursor_position(&mut self, x: f32, y: f32) {
+        let window = self.parent_window();
+        self.emit_to(window, WindowEvent::SetCursorPosition(x.max(0.0) as u32, y.max(0.0) as u32));
+    }
+
+    /// Locks the mouse pointer in place and hides it, sending the relative motion of the mouse to the current view as
+    /// [`WindowEvent::MouseMotion`] events so that dragging is not bounded by the edges of the screen.
+    ///
+    /// On platforms which cannot lock the pointer it is confined to the window instead, with the motion computed
+    /// from the movement of the cursor. The unaccelerated motion of each mouse is also sent to the view as
+    /// [`WindowEvent::RawMouseDelta`] events where the platform reports it.
+    pub fn lock_pointer(&mut self) {
+        *self.pointer_lock = Some(self.current);
+        self.set_cursor_grab(CursorGrabMode::Locked);
+        self.set_cursor_visible(false);
+    }
+
+    /// Keeps the current view hovered, regardless of the position of the mouse cursor, until
+    /// [`release_hover`](Self::release_hover) is called.
+    ///
+    /// This prevents the `:hover` state of other views from flickering as the cursor crosses them during an
+    /// interaction such as a drag.
+    pub fn capture_hover(&mut self) {
+        self.emit_custom(
+            Event::new(InternalEvent::CaptureHover(Some(self.current))).target(Entity::root()),
+        );
+    }
+
+    /// Releases the hover captured by the current view, recomputing the hovered view from the mouse cursor.
+    pub fn release_hover(&mut self) {
+        if *self.hover_capture == Some(self.current) {
+            self.emit_custom(Event::new(InternalEvent::CaptureHover(None)).target(Entity::root()));
+        }
+    }
+
+    /// Releases a pointer locked with [`lock_pointer`](Self::lock_pointer), showing the cursor again.
+    pub fn unlock_pointer(&mut self) {
+        if let Some(entity) = self.pointer_lock.take() {
+            self.with_current(entity, |cx| {
+                cx.set_cursor_grab(CursorGrabMode::None);
+                cx.set_cursor_visible(true);
+            });
+        }
+    }
+
     /// Enables or disables PseudoClassFlags for the focus of an entity
     fn set_focus_pseudo_classes(&mut self, focused: Entity, enabled: bool, focus_visible: bool) {
         if let Some(pseudo_classes) = self.style.pseudo_classes.get_mut(focused) {
@@ -450,12 +645,24 @@ impl<'a> EventContext<'a> {
             if !enabled || focus_visible {
                 pseudo_classes.set(PseudoClassFlags::FOCUS_VISIBLE, enabled);
             }
+
+            self.style.invalidate_pseudo_classes(
+                self.tree,
+                focused,
+                PseudoClassFlags::FOCUS | PseudoClassFlags::FOCUS_VISIBLE,
+            );
         }
 
         for ancestor in focused.parent_iter(self.tree) {
-            let entity = ancestor;
-            if let Some(pseudo_classes) = self.style.pseudo_classes.get_mut(entity) {
-                pseudo_classes.set(PseudoClassFlags::FOCUS_WITHIN, enabled);
+            if let Some(pseudo_classes) = self.style.pseudo_classes.get_mut(ancestor) {
+                if pseudo_classes.contains(PseudoClassFlags::FOCUS_WITHIN) != enabled {
+                    pseudo_classes.set(PseudoClassFlags::FOCUS_WITHIN, enabled);
+                    self.style.invalidate_pseudo_classes(
+                        self.tree,
+                        ancestor,
+                        PseudoClassFlags::FOCUS_WITHIN,
+                    );
+                }
             }
         }
     }
@@ -474,8 +681,6 @@ impl<'a> EventContext<'a> {
 
         self.emit_custom(Event::new(WindowEvent::FocusVisibility(focus_visible)).target(old_focus));
         self.emit_custom(Event::new(WindowEvent::FocusVisibility(focus_visible)).target(new_focus));
-
-        self.needs_restyle();
     }
 
     /// Sets application focus to the current view using the previous focus visibility.
@@ -492,9 +697,39 @@ impl<'a> EventContext<'a> {
         self.focus_with_visibility(old_focus_visible)
     }
 
+    /// Synthesizes a press of the given entity without any real input.
+    ///
+    /// A [`PressDown`](WindowEvent::PressDown) followed by a [`Press`](WindowEvent::Press) is sent directly to the entity,
+    /// triggering its `on_press_down` and `on_press` callbacks as if it had been clicked. This is useful for handling
+    /// accessibility actions and for scripting interactions in tests. Disabled views ignore the press.
+    pub fn press(&mut self, entity: Entity) {
+        for message in
+            [WindowEvent::PressDown { mouse: false }, WindowEvent::Press { mouse: false }]
+        {
+            self.emit_custom(
+                Event::new(message)
+                    .target(entity)
+                    .origin(entity)
+                    .propagate(Propagation::Direct)
+                    .synthesized(),
+            );
+        }
+    }
+
+    /// Scrolls the nearest ancestor [ScrollView] of the given entity until the entity is visible, optionally
+    /// animating the scroll.
+    pub fn scroll_to(&mut self, entity: Entity, animate: bool) {
+        self.emit_custom(
+            Event::new(ScrollEvent::ScrollTo { entity, animate })
+                .target(entity)
+                .origin(self.current)
+                .propagate(Propagation::Up),
+        );
+    }
+
     /// Moves the keyboard focus to the next navigable view.
     pub fn focus_next(&mut self) {
-        let lock_focus_to = self.tree.lock_focus_within(*self.focused);
+        let lock_focus_to = focus_lock(self.tree, self.focus_scopes, *self.focused);
         let next_focused = if let Some(next_focused) =
             focus_forward(self.tree, self.style, *self.focused, lock_focus_to)
         {
@@ -516,14 +751,18 @@ impl<'a> EventContext<'a> {
             if let Some(pseudo_classes) = self.style.pseudo_classes.get_mut(*self.triggered) {
                 pseudo_classes.set(PseudoClassFlags::ACTIVE, false);
             }
-            self.needs_restyle();
+            self.style.invalidate_pseudo_classes(
+                self.tree,
+                *self.triggered,
+                PseudoClassFlags::ACTIVE,
+            );
             *self.triggered = Entity::null();
         }
     }
 
     /// Moves the keyboard focus to the previous navigable view.
     pub fn focus_prev(&mut self) {
-        let lock_focus_to = self.tree.lock_focus_within(*self.focused);
+        let lock_focus_to = focus_lock(self.tree, self.focus_scopes, *self.focused);
         let prev_focused = if let Some(prev_focused) =
             focus_backward(self.tree, self.style, *self.focused, lock_focus_to)
         {
@@ -546,11 +785,45 @@ impl<'a> EventContext<'a> {
             if let Some(pseudo_classes) = self.style.pseudo_classes.get_mut(*self.triggered) {
                 pseudo_classes.set(PseudoClassFlags::ACTIVE, false);
             }
-            self.needs_restyle();
+            self.style.invalidate_pseudo_classes(
+                self.tree,
+                *self.triggered,
+                PseudoClassFlags::ACTIVE,
+            );
             *self.triggered = Entity::null();
         }
     }
 
+    /// Traps keyboard focus within the subtree of the given view, so that navigating with Tab and Shift+Tab cycles
+    /// through the navigable views of the subtree only, which is useful for modal dialogs.
+    ///
+    /// Focus scopes are stacked per window, so a scope only traps focus within the window containing the view. If
+    /// the focused view is outside of the subtree, focus moves to the first navigable view within it. The scope is
+    /// closed with [`pop_focus_scope`](Self::pop_focus_scope), or when the view is removed.
+    pub fn push_focus_scope(&mut self, entity: Entity) {
+        let previous = *self.focused;
+        self.focus_scopes.entry(window_of(self.tree, entity)).or_default().push((entity, previous));
+
+        if !previous.is_descendant_of(self.tree, entity) {
+            if let Some(first) = TreeIterator::subtree(self.tree, entity)
+                .find(|node| is_navigatable(self.tree, self.style, *node, entity))
+            {
+                self.with_current(first, |cx| cx.focus());
+            }
+        }
+    }
+
+    /// Closes the most recently pushed focus scope of the window containing the current view, returning focus to the
+    /// view which was focused when it was pushed.
+    pub fn pop_focus_scope(&mut self) {
+        let window = window_of(self.tree, self.current);
+        if let Some((_, previous)) =
+            self.focus_scopes.get_mut(&window).and_then(|scopes| scopes.pop())
+        {
+            self.with_current(previous, |cx| cx.focus());
+        }
+    }
+
     /// Returns the currently hovered view.
     pub fn hovered(&self) -> Entity {
         *self.hovered
@@ -600,6 +873,12 @@ impl<'a> EventContext<'a> {
             .unwrap_or_default()
     }
 
+    /// Returns true if a press, made with the mouse or keyboard over the `over` entity, was made over the current
+    /// view. Presses synthesized by [`press`](Self::press) aren't made over any entity and always count.
+    pub(crate) fn is_press_over(&self, meta: &EventMeta, over: Entity) -> bool {
+        meta.synthesized || over == self.current
+    }
+
     /// Returns true if the current view is disabled.
     pub fn is_disabled(&self) -> bool {
         self.style.disabled.get(self.current()).cloned().unwrap_or_default()
@@ -648,23 +927,89 @@ impl<'a> EventContext<'a> {
         *self.drop_data = Some(data.into())
     }
 
+    /// Starts a native drag of the data out of the window, so that it can be dropped onto other applications such as
+    /// a file manager. This is usually called while the left mouse button is held, such as from a
+    /// [`Pan`](crate::modifiers::GestureEvent::Pan) gesture.
+    ///
+    /// The current view receives a [`WindowEvent::ExternalDragEnded`] event when the drag ends. Dragging out of the
+    /// window is only supported by the winit backend on Windows and macOS with the `drag-out` feature enabled.
+    pub fn start_external_drag(&mut self, payload: DragPayload) {
+        let window = self.parent_window();
+        self.emit_to(window, WindowEvent::StartExternalDrag(payload));
+    }
+
     /// Get the contents of the system clipboard.
     ///
     /// This may fail for a variety of backend-specific reasons.
     #[cfg(feature = "clipboard")]
     pub fn get_clipboard(&mut self) -> Result<String, Box<dyn Error + Send + Sync + 'static>> {
-        self.clipboard.get_contents()
+        self.clipboard.get_text()
     }
 
     /// Set the contents of the system clipboard.
     ///
-    /// This may fail for a variety of backend-specific reasons.
+    /// If the system clipboard is unavailable, the text is kept within the application so that it can still be
+    /// pasted. This may fail for a variety of backend-specific reasons.
     #[cfg(feature = "clipboard")]
     pub fn set_clipboard(
         &mut self,
         text: String,
     ) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
-        self.clipboard.set_contents(text)
+        self.clipboard.set_text(text)
+    }
+
+    /// Get the image contents of the system clipboard.
+    ///
+    /// This fails if the clipboard doesn't contain an image, as well as for a variety of backend-specific reasons.
+    #[cfg(feature = "clipboard")]
+    pub fn get_clipboard_image(
+        &mut self,
+    ) -> Result<ClipboardImage, Box<dyn Error + Send + Sync + 'static>> {
+        self.clipboard.get_image()
+    }
+
+    /// Set the contents of the system clipboard to an image.
+    ///
+    /// This may fail for a variety of backend-specific reasons.
+    #[cfg(feature = "clipboard")]
+    pub fn set_clipboard_image(
+        &mut self,
+        image: ClipboardImage,
+    ) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+        self.clipboard.set_image(image)
+    }
+
+    /// Set the contents of the system clipboard to HTML, along with an optional plain text alternative for
+    /// applications which can't paste HTML.
+    ///
+    /// This may fail for a variety of backend-specific reasons.
+    #[cfg(feature = "clipboard")]
+    pub fn set_clipboard_html(
+        &mut self,
+        html: &str,
+        alt_text: Option<&str>,
+    ) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+        self.clipboard.set_html(html, alt_text)
+    }
+
+    /// Saves the state of the models serialized by [`Context::serialize_models`] to the state file.
+    #[cfg(feature = "serde")]
+    pub fn save_state(&self) -> std::io::Result<()> {
+        self.persistence.save(self.models, |entity, name| {
+            model_key(self.tree, &*self.style, &*self.views, entity, name)
+        })
+    }
+
+    /// Restores the models serialized by [`Context::serialize_models`] from the state file, returning the number of
+    /// models restored.
+    #[cfg(feature = "serde")]
+    pub fn load_state(&mut self) -> std::io::Result<usize> {
+        let (tree, style, views) = (&*self.tree, &*self.style, &*self.views);
+        self.persistence.load(
+            self.models,
+            |entity, name| model_key(tree, style, views, entity, name),
+            None,
+        )
     }
 
     /// Toggles the addition/removal of a class name for the current view.
@@ -690,7 +1035,57 @@ impl<'a> EventContext<'a> {
             self.style.classes.insert(current, class_list);
         }
 
-        self.needs_restyle();
+        self.style.invalidate_class(self.tree, current, class_name);
+    }
+
+    /// Adds a class to the current view for the given duration, after which it is removed.
+    ///
+    /// Calling this again with the same class before the duration has elapsed extends the existing scope
+    /// rather than removing the class early. This is useful for transient effects such as flashing a view when its value changes.
+    /// If the view already has the class it is left in place once the duration has elapsed.
+    pub fn add_scoped_class(&mut self, class_name: &str, duration: Duration) {
+        let entity = self.current();
+        let added = match self.scoped_classes.remove(&(entity, class_name.to_string())) {
+            Some((handle, added)) => {
+                self.cancel_scheduled(handle);
+                added
+            }
+
+            None => self.style.add_class(entity, class_name),
+        };
+
+        if added {
+            self.style.invalidate_class(self.tree, entity, class_name);
+        }
+
+        let handle = self.schedule_emit_custom(
+            Event::new(InternalEvent::RemoveScopedClass { entity, class: class_name.to_string() })
+                .target(Entity::root()),
+            Instant::now() + duration,
+        );
+
+        self.scoped_classes.insert((entity, class_name.to_string()), (handle, added));
+    }
+
+    /// Sends an event containing a clone of the provided message directly to every entity in the tree, in tree
+    /// pre-order. See [`Context::emit_to_all`].
+    pub fn emit_to_all<M: Any + Send + Clone>(&mut self, message: M) {
+        for entity in TreeIterator::full(self.tree) {
+            self.emit_custom(
+                Event::new(message.clone())
+                    .target(entity)
+                    .origin(self.current)
+                    .propagate(Propagation::Direct),
+            );
+        }
+    }
+
+    /// Queues a closure to be run after the current event dispatch cycle has completed.
+    ///
+    /// This allows event handlers to safely make structural changes to the tree, such as adding or removing views,
+    /// without affecting the dispatch of the events in the current cycle. See [`Context::defer`].
+    pub fn defer(&mut self, f: impl FnOnce(&mut Context) + 'static) {
+        self.deferred.push((self.current, Box::new(f)));
     }
 
     /// Returns a reference to the [Environment] model.
@@ -700,6 +1095,8 @@ impl<'a> EventContext<'a> {
 
     /// Sets the current [theme mode](ThemeMode).
     pub fn set_theme_mode(&mut self, theme_mode: ThemeMode) {
+        self.resource_manager.theme_mode = theme_mode;
+
         if !self.ignore_default_theme {
             match theme_mode {
                 ThemeMode::LightMode => {
@@ -721,6 +1118,36 @@ impl<'a> EventContext<'a> {
         }
     }
 
+    /// Discards the retained drawing of a view recorded with [`DrawContext::draw_cached`] and marks the view as needing
+    /// to be redrawn, so that the drawing is recorded again the next time the view is drawn.
+    ///
+    /// Redrawing a view without invalidating its drawing, such as with [`needs_redraw`](Self::needs_redraw) or when an
+    /// overlapping view is redrawn, replays the recorded drawing.
+    pub fn request_draw_cache_invalidation(&mut self, entity: Entity) {
+        self.cache.picture.remove(entity);
+
+        let parent_window = self.tree.get_parent_window(entity).unwrap_or(Entity::root());
+        if let Some(window_state) = self.windows.get_mut(&parent_window) {
+            window_state.redraw_list.insert(entity);
+        }
+    }
+
+    /// Marks a region of the current view, in physical pixels, as needing to be redrawn.
+    ///
+    /// Unlike [`needs_redraw`](Self::needs_redraw), only the part of the region within the drawn bounds of the view is
+    /// repainted, which allows views with small animated parts to avoid repainting their whole bounds every frame.
+    pub fn needs_redraw_region(&mut self, region: BoundingBox) {
+        let Some(draw_bounds) = self.cache.draw_bounds.get(self.current).copied() else {
+            self.needs_redraw();
+            return;
+        };
+
+        let parent_window = self.tree.get_parent_window(self.current).unwrap_or(Entity::root());
+        if let Some(window_state) = self.windows.get_mut(&parent_window) {
+            window_state.add_dirty_region(region.intersection(&draw_bounds));
+        }
+    }
+
     /// Marks the current view as needing a layout computation.
     pub fn needs_relayout(&mut self) {
         self.style.needs_relayout();
@@ -744,7 +1171,10 @@ impl<'a> EventContext<'a> {
 
     /// Reloads the stylesheets linked to the application.
     pub fn reload_styles(&mut self) -> Result<(), std::io::Error> {
-        if self.resource_manager.themes.is_empty() && self.resource_manager.styles.is_empty() {
+        if self.resource_manager.themes.is_empty()
+            && self.resource_manager.styles.is_empty()
+            && self.resource_manager.theme_styles.is_empty()
+        {
             return Ok(());
         }
 
@@ -764,6 +1194,18 @@ impl<'a> EventContext<'a> {
             overall_theme += &style_string;
         }
 
+        // Stylesheets for the current theme mode are added last so that they override the other styles.
+        let theme_mode = self.resource_manager.theme_mode;
+        for style_string in self
+            .resource_manager
+            .theme_styles
+            .iter()
+            .filter(|(mode, _)| *mode == theme_mode)
+            .flat_map(|(_, style)| style.get_style())
+        {
+            overall_theme += &style_string;
+        }
+
         self.style.parse_theme(&overall_theme);
 
         for entity in self.tree.into_iter() {
@@ -789,6 +1231,29 @@ impl<'a> EventContext<'a> {
         std::thread::spawn(move || target(&mut cxp));
     }
 
+    /// Spawns a thread which runs the future returned by `target` to completion, providing a [ContextProxy] for
+    /// sending events back to the main UI thread.
+    pub fn spawn_async<F, Fut>(&self, target: F)
+    where
+        F: 'static + Send + FnOnce(ContextProxy) -> Fut,
+        Fut: std::future::Future<Output = ()>,
+    {
+        self.get_proxy().spawn_async(target);
+    }
+
+    /// Runs a job on the shared pool of background threads, returning a [JobHandle] used to handle its result on the
+    /// main thread with [`on_complete`](JobHandle::on_complete).
+    ///
+    /// The job is cancelled when the current view is removed. See [`Context::spawn_job`].
+    pub fn spawn_job<T, F>(&mut self, job: F) -> JobHandle<'_, T>
+    where
+        T: 'static + Send,
+        F: 'static + Send + FnOnce(&CancellationToken) -> T,
+    {
+        let event_proxy = self.event_proxy.as_ref().map(|p| p.make_clone());
+        self.jobs.spawn(self.current, event_proxy, job)
+    }
+
     /// Returns a [ContextProxy] which can be moved between threads and used to send events back to the main UI thread.
     pub fn get_proxy(&self) -> ContextProxy {
         ContextProxy {
@@ -797,14 +1262,46 @@ impl<'a> EventContext<'a> {
         }
     }
 
-    pub fn modify<V: View>(&mut self, f: impl FnOnce(&mut V)) {
-        if let Some(view) = self
+    /// Modifies the internal state of the current view if it's a view of type `V`, returning whether it was.
+    ///
+    /// This lets callbacks which run on behalf of a view, such as those added with modifiers, change the state of the
+    /// view without emitting an event for it. The view is relaid out and redrawn after it's modified, and any bindings
+    /// to the state of the view are updated.
+    ///
+    /// # Example
+    /// ```
+    /// # use vizia_core::prelude::*;
+    /// # let cx = &mut Context::default();
+    /// #[derive(Lens)]
+    /// pub struct Counter {
+    ///     count: i32,
+    /// }
+    ///
+    /// impl View for Counter {}
+    ///
+    /// Counter { count: 0 }
+    ///     .build(cx, |cx| {
+    ///         Label::new(cx, Counter::count);
+    ///     })
+    ///     .on_press(|cx| {
+    ///         cx.modify::<Counter>(|counter| counter.count += 1);
+    ///     });
+    /// ```
+    pub fn modify<V: View>(&mut self, f: impl FnOnce(&mut V)) -> bool {
+        let Some(view) = self
             .views
             .get_mut(&self.current)
             .and_then(|view_handler| view_handler.downcast_mut::<V>())
-        {
-            (f)(view);
-        }
+        else {
+            return false;
+        };
+
+        (f)(view);
+
+        self.needs_relayout();
+        self.request_draw_cache_invalidation(self.current);
+
+        true
     }
 
     // TODO: Abstract this to shared trait for all contexts
@@ -841,7 +1338,7 @@ impl<'a> EventContext<'a> {
             pseudo_classes.set(PseudoClassFlags::HOVER, flag);
         }
 
-        self.needs_restyle();
+        self.style.invalidate_pseudo_classes(self.tree, self.current, PseudoClassFlags::HOVER);
     }
 
     /// Set the active state for the current view.
@@ -857,7 +1354,7 @@ impl<'a> EventContext<'a> {
             pseudo_classes.set(PseudoClassFlags::ACTIVE, active);
         }
 
-        self.needs_restyle();
+        self.style.invalidate_pseudo_classes(self.tree, self.current, PseudoClassFlags::ACTIVE);
     }
 
     pub fn set_read_only(&mut self, flag: bool) {
@@ -866,7 +1363,7 @@ impl<'a> EventContext<'a> {
             pseudo_classes.set(PseudoClassFlags::READ_ONLY, flag);
         }
 
-        self.needs_restyle();
+        self.style.invalidate_pseudo_classes(self.tree, self.current, PseudoClassFlags::READ_ONLY);
     }
 
     pub fn set_read_write(&mut self, flag: bool) {
@@ -875,7 +1372,7 @@ impl<'a> EventContext<'a> {
             pseudo_classes.set(PseudoClassFlags::READ_WRITE, flag);
         }
 
-        self.needs_restyle();
+        self.style.invalidate_pseudo_classes(self.tree, self.current, PseudoClassFlags::READ_WRITE);
     }
 
     /// Sets the checked state of the current view.
@@ -892,7 +1389,29 @@ impl<'a> EventContext<'a> {
             pseudo_classes.set(PseudoClassFlags::CHECKED, flag);
         }
 
-        self.needs_restyle();
+        self.style.invalidate_pseudo_classes(self.tree, self.current, PseudoClassFlags::CHECKED);
+    }
+
+    /// Sets the indeterminate state of the current view.
+    ///
+    /// Indeterminate elements can be selected with the `:indeterminate` CSS pseudo-class selector:
+    /// ```css
+    /// element:indeterminate {
+    ///     background-color: red;
+    /// }
+    /// ```
+    pub fn set_indeterminate(&mut self, flag: bool) {
+        let current = self.current();
+        if let Some(pseudo_classes) = self.style.pseudo_classes.get_mut(current) {
+            pseudo_classes.set(PseudoClassFlags::INDETERMINATE, flag);
+        }
+
+        self.style.invalidate_pseudo_classes(
+            self.tree,
+            self.current,
+            PseudoClassFlags::INDETERMINATE,
+        );
+        self.style.needs_access_update(current);
     }
 
     /// Sets the valid state of the current view.
@@ -910,7 +1429,11 @@ impl<'a> EventContext<'a> {
             pseudo_classes.set(PseudoClassFlags::INVALID, !flag);
         }
 
-        self.needs_restyle();
+        self.style.invalidate_pseudo_classes(
+            self.tree,
+            self.current,
+            PseudoClassFlags::VALID | PseudoClassFlags::INVALID,
+        );
     }
 
     pub fn set_placeholder_shown(&mut self, flag: bool) {
@@ -919,7 +1442,11 @@ impl<'a> EventContext<'a> {
             pseudo_classes.set(PseudoClassFlags::PLACEHOLDER_SHOWN, flag);
         }
 
-        self.needs_restyle();
+        self.style.invalidate_pseudo_classes(
+            self.tree,
+            self.current,
+            PseudoClassFlags::PLACEHOLDER_SHOWN,
+        );
     }
 
     // TODO: Move me
@@ -1153,8 +1680,37 @@ impl<'a> EventContext<'a> {
         self.needs_redraw();
     }
 
+    /// Sets whether the current view accepts pointer events. Views which don't accept pointer events are passed
+    /// through by the cursor, so the views beneath them are hovered instead, while their descendants inherit the value
+    /// unless they set their own.
     pub fn set_pointer_events(&mut self, pointer_events: impl Into<PointerEvents>) {
         self.style.pointer_events.insert(self.current, pointer_events.into());
+        self.rehover();
+    }
+
+    /// Sets whether the current view and its descendants can be hit-tested by the cursor, which is equivalent to
+    /// the [`hoverable`](crate::modifiers::AbilityModifiers::hoverable) modifier.
+    ///
+    /// This is the hit-test ability of a view. Unlike [`set_pointer_events`](Self::set_pointer_events), the
+    /// descendants of a view which can't be hit-tested can't be hovered either.
+    pub fn set_hoverable(&mut self, flag: bool) {
+        if let Some(abilities) = self.style.abilities.get_mut(self.current) {
+            abilities.set(Abilities::HOVERABLE, flag);
+        } else {
+            let mut abilities = Abilities::default();
+            abilities.set(Abilities::HOVERABLE, flag);
+            self.style.abilities.insert(self.current, abilities);
+        }
+
+        self.needs_restyle();
+        self.rehover();
+    }
+
+    // Recomputes the hovered view, as the cursor may now be over a different view without having moved.
+    fn rehover(&mut self) {
+        self.emit_custom(
+            Event::new(InternalEvent::Rehover).target(Entity::root()).origin(self.current),
+        );
     }
 
     // GETTERS
@@ -1178,6 +1734,10 @@ impl<'a> EventContext<'a> {
     ///
     /// Returns a `Timer` id which can be used to start and stop the timer.  
     ///
+    /// Timers are driven by the event loop of the windowing backend, so blinking carets, polling, debouncing, and
+    /// dismissing views after a delay don't need a separate thread. A running timer can be cancelled at any time with
+    /// `stop_timer()`.
+    ///
     /// # Example
     /// Creates a timer which calls the provided callback every second for 5 seconds:
     /// ```rust
@@ -1206,25 +1766,15 @@ impl<'a> EventContext<'a> {
         duration: Option<Duration>,
         callback: impl Fn(&mut EventContext, TimerAction) + 'static,
     ) -> Timer {
-        let id = Timer(self.timers.len());
-        self.timers.push(TimerState {
-            entity: Entity::root(),
-            id,
-            time: Instant::now(),
-            interval,
-            duration,
-            start_time: Instant::now(),
-            callback: Rc::new(callback),
-            ticking: false,
-            stopping: false,
-        });
-
-        id
+        TimerState::add(self.timers, interval, duration, Rc::new(callback))
     }
 
     /// Starts a timer with the provided timer id.
     ///
     /// Events sent within the timer callback provided in `add_timer()` will target the current view.
+    ///
+    /// Starting a timer which is already running restarts it, so a timer can be used to debounce an action by
+    /// starting it on each input and handling the `TimerAction::Stop` of a timer with equal interval and duration.
     pub fn start_timer(&mut self, timer: Timer) {
         let current = self.current;
         if !self.timer_is_running(timer) {
@@ -1245,16 +1795,18 @@ impl<'a> EventContext<'a> {
 
     /// Modifies the state of an existing timer with the provided `Timer` id.
     pub fn modify_timer(&mut self, timer: Timer, timer_function: impl Fn(&mut TimerState)) {
-        while let Some(next_timer_state) = self.running_timers.peek() {
-            if next_timer_state.id == timer {
-                let mut timer_state = self.running_timers.pop().unwrap();
-
-                (timer_function)(&mut timer_state);
+        if self.timer_is_running(timer) {
+            // The heap is rebuilt because the timer may not be the next to tick and its time may change.
+            let mut running_timers = std::mem::take(&mut *self.running_timers).into_vec();
+            for timer_state in
+                running_timers.iter_mut().filter(|timer_state| timer_state.id == timer)
+            {
+                (timer_function)(timer_state);
+            }
 
-                self.running_timers.push(timer_state);
+            *self.running_timers = running_timers.into();
 
-                return;
-            }
+            return;
         }
 
         for pending_timer in self.timers.iter_mut() {
@@ -1269,16 +1821,10 @@ impl<'a> EventContext<'a> {
         timer: Timer,
         timer_function: impl Fn(&TimerState) -> T,
     ) -> Option<T> {
-        while let Some(next_timer_state) = self.running_timers.peek() {
-            if next_timer_state.id == timer {
-                let timer_state = self.running_timers.pop().unwrap();
-
-                let t = (timer_function)(&timer_state);
-
-                self.running_timers.push(timer_state);
-
-                return Some(t);
-            }
+        if let Some(timer_state) =
+            self.running_timers.iter().find(|timer_state| timer_state.id == timer)
+        {
+            return Some(timer_function(timer_state));
         }
 
         for pending_timer in self.timers.iter() {
@@ -1318,6 +1864,18 @@ impl<'a> EventContext<'a> {
         *self.running_timers =
             running_timers.drain().filter(|timer_state| timer_state.id != timer).collect();
     }
+
+    /// Removes a timer added with `add_timer()`, stopping it without calling its callback if it's running.
+    ///
+    /// The callback of the timer is dropped and its id may be reused by a timer added later, so a view which adds a
+    /// timer should remove it when the view is destroyed.
+    pub fn remove_timer(&mut self, timer: Timer) {
+        self.running_timers.retain(|timer_state| timer_state.id != timer);
+
+        if let Some(timer_state) = self.timers.get_mut(timer.0) {
+            timer_state.remove();
+        }
+    }
 }
 
 impl DataContext for EventContext<'_> {
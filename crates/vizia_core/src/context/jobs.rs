@@ -0,0 +1,279 @@
+use std::any::Any;
+use std::marker::PhantomData;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Sender};
+use std::sync::{Arc, Mutex};
+
+use hashbrown::HashMap;
+
+use super::{EventProxy, InternalEvent};
+use crate::prelude::*;
+
+type Work = Box<dyn FnOnce() + Send>;
+
+/// A token which is cancelled when the view which spawned a job is removed, or when the job is cancelled manually.
+///
+/// A job which hasn't started yet when its token is cancelled is never run, and the result of a job which finishes
+/// after its token is cancelled is dropped without calling the `on_complete` callback. The token is passed to the job,
+/// so long running jobs can check it to stop early.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Creates a new token which isn't cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+
+    /// Returns whether the token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+}
+
+/// A handle to a job spawned with [`spawn_job`](Context::spawn_job), used to set what happens when it completes.
+pub struct JobHandle<'a, T> {
+    id: u64,
+    token: CancellationToken,
+    jobs: &'a mut Jobs,
+    p: PhantomData<T>,
+}
+
+impl<T: 'static> JobHandle<'_, T> {
+    /// Sets the callback which is called on the main thread with the result of the job once it completes. The
+    /// callback isn't called if the job is cancelled.
+    pub fn on_complete<F>(self, callback: F) -> Self
+    where
+        F: 'static + FnOnce(&mut EventContext, T),
+    {
+        if let Some(job) = self.jobs.pending.get_mut(&self.id) {
+            job.on_complete = Some(Box::new(move |cx, result| {
+                if let Ok(result) = result.downcast::<T>() {
+                    (callback)(cx, *result);
+                }
+            }));
+        }
+
+        self
+    }
+
+    /// Returns the cancellation token of the job.
+    pub fn token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+
+    /// Cancels the job.
+    pub fn cancel(&self) {
+        self.token.cancel();
+    }
+}
+
+// A job which is waiting for its result to be handled on the main thread.
+struct PendingJob {
+    entity: Entity,
+    token: CancellationToken,
+    on_complete: Option<Box<dyn FnOnce(&mut EventContext, Box<dyn Any + Send>)>>,
+}
+
+// Records a job which stops without sending its result to the main thread, because it was cancelled, panicked, or
+// there's no event proxy to send it with, so that its pending entry can be removed.
+struct AbandonGuard {
+    id: u64,
+    abandoned: Arc<Mutex<Vec<u64>>>,
+    sent: bool,
+}
+
+impl Drop for AbandonGuard {
+    fn drop(&mut self) {
+        if !self.sent {
+            if let Ok(mut abandoned) = self.abandoned.lock() {
+                abandoned.push(self.id);
+            }
+        }
+    }
+}
+
+// The shared pool of worker threads and the jobs which have been spawned onto it.
+#[derive(Default)]
+pub(crate) struct Jobs {
+    // The sender of the queue of work, which is created along with the worker threads when the first job is spawned.
+    sender: Option<Sender<Work>>,
+    pending: HashMap<u64, PendingJob>,
+    // The ids of jobs which stopped without sending a result, whose pending entries are removed on the main thread.
+    abandoned: Arc<Mutex<Vec<u64>>>,
+    next_id: u64,
+}
+
+impl Jobs {
+    pub(crate) fn spawn<T, F>(
+        &mut self,
+        entity: Entity,
+        event_proxy: Option<Box<dyn EventProxy>>,
+        job: F,
+    ) -> JobHandle<'_, T>
+    where
+        T: 'static + Send,
+        F: 'static + Send + FnOnce(&CancellationToken) -> T,
+    {
+        self.remove_abandoned();
+
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let token = CancellationToken::new();
+        self.pending.insert(id, PendingJob { entity, token: token.clone(), on_complete: None });
+
+        let job_token = token.clone();
+        let mut guard = AbandonGuard { id, abandoned: self.abandoned.clone(), sent: false };
+        let work = Box::new(move || {
+            if job_token.is_cancelled() {
+                return;
+            }
+
+            let result: Box<dyn Any + Send> = Box::new(job(&job_token));
+            if job_token.is_cancelled() {
+                return;
+            }
+
+            if let Some(proxy) = event_proxy {
+                let event = Event::new(InternalEvent::JobComplete { id, result })
+                    .target(entity)
+                    .origin(entity);
+                guard.sent = proxy.send(event).is_ok();
+            }
+        });
+
+        let sender = self.sender.get_or_insert_with(start_workers);
+        if sender.send(work).is_err() {
+            log::warn!("Failed to spawn job: the job pool has shut down");
+        }
+
+        JobHandle { id, token, jobs: self, p: PhantomData }
+    }
+
+    // Removes a completed job, returning its callback and the entity which spawned it if it wasn't cancelled.
+    pub(crate) fn complete(
+        &mut self,
+        id: u64,
+    ) -> Option<(Entity, Box<dyn FnOnce(&mut EventContext, Box<dyn Any + Send>)>)> {
+        self.remove_abandoned();
+
+        let job = self.pending.remove(&id)?;
+        if job.token.is_cancelled() {
+            return None;
+        }
+
+        Some((job.entity, job.on_complete?))
+    }
+
+    // Removes the pending entries of jobs which stopped without sending a result.
+    fn remove_abandoned(&mut self) {
+        let abandoned = match self.abandoned.lock() {
+            Ok(mut abandoned) => std::mem::take(&mut *abandoned),
+            Err(_) => return,
+        };
+
+        for id in abandoned {
+            self.pending.remove(&id);
+        }
+    }
+
+    // Cancels the jobs spawned by an entity which is being removed.
+    pub(crate) fn cancel_entity(&mut self, entity: Entity) {
+        self.pending.retain(|_, job| {
+            if job.entity == entity {
+                job.token.cancel();
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
+// Starts a worker thread for each available core, which run work from the queue until the sender is dropped.
+fn start_workers() -> Sender<Work> {
+    let (sender, receiver) = channel::<Work>();
+    let receiver = Arc::new(Mutex::new(receiver));
+    let threads = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4);
+
+    for index in 0..threads {
+        let receiver = receiver.clone();
+        let spawned =
+            std::thread::Builder::new().name(format!("vizia-job-{index}")).spawn(move || loop {
+                let work = match receiver.lock() {
+                    Ok(receiver) => receiver.recv(),
+                    Err(_) => return,
+                };
+
+                match work {
+                    // Keep the worker alive if a job panics.
+                    Ok(work) => {
+                        if std::panic::catch_unwind(AssertUnwindSafe(work)).is_err() {
+                            log::error!("A job panicked");
+                        }
+                    }
+                    Err(_) => return,
+                }
+            });
+
+        if let Err(err) = spawned {
+            log::warn!("Failed to start job worker thread: {}", err);
+        }
+    }
+
+    sender
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    // Waits for the workers to finish the pending jobs, removing the entries of those which were abandoned.
+    fn wait_for_abandoned(jobs: &mut Jobs) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !jobs.pending.is_empty() {
+            assert!(Instant::now() < deadline, "jobs weren't abandoned");
+            jobs.remove_abandoned();
+            std::thread::yield_now();
+        }
+    }
+
+    #[test]
+    fn abandoned_jobs_are_removed() {
+        let mut jobs = Jobs::default();
+
+        // Without an event proxy the result can't be sent back, so the job is abandoned once it completes.
+        jobs.spawn(Entity::root(), None, |token| token.is_cancelled());
+        // A job which panics is abandoned too.
+        jobs.spawn::<(), _>(Entity::root(), None, |_| panic!("job failed"));
+        assert_eq!(jobs.pending.len(), 2);
+
+        wait_for_abandoned(&mut jobs);
+    }
+
+    #[test]
+    fn job_receives_token() {
+        let mut jobs = Jobs::default();
+
+        let (sender, receiver) = channel();
+        let token = jobs
+            .spawn(Entity::root(), None, move |token| {
+                sender.send(token.clone()).ok();
+            })
+            .token();
+
+        let job_token = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        token.cancel();
+        assert!(job_token.is_cancelled());
+
+        wait_for_abandoned(&mut jobs);
+    }
+}
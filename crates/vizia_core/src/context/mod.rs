@@ -3,18 +3,28 @@
 mod access;
 #[doc(hidden)]
 pub mod backend;
+#[cfg(feature = "clipboard")]
+mod clipboard;
 mod draw;
 mod event;
+mod jobs;
+#[cfg(feature = "serde")]
+mod persistence;
 mod proxy;
 mod resource;
+#[cfg(test)]
+pub(crate) mod test_support;
+#[cfg(test)]
+mod tests;
 
 use log::debug;
+use morphorm::Node;
 use skia_safe::{
     svg,
     textlayout::{FontCollection, TypefaceFontProvider},
     FontMgr,
 };
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::{BinaryHeap, VecDeque};
 use std::rc::Rc;
 use std::sync::Mutex;
@@ -25,35 +35,49 @@ use std::{
 use vizia_id::IdManager;
 use vizia_window::WindowDescription;
 
-#[cfg(all(feature = "clipboard", feature = "x11"))]
-use copypasta::ClipboardContext;
-#[cfg(feature = "clipboard")]
-use copypasta::{nop_clipboard::NopClipboardContext, ClipboardProvider};
 use hashbrown::{hash_map::Entry, HashMap, HashSet};
 
 pub use access::*;
+#[cfg(feature = "clipboard")]
+pub use clipboard::*;
 pub use draw::*;
 pub use event::*;
+pub use jobs::*;
 pub use proxy::*;
 pub use resource::*;
 
 use crate::{
+    animation::AnimId,
     binding::{Store, StoreId},
-    events::{TimedEvent, TimedEventHandle, TimerState, ViewHandler},
+    events::{Interceptors, TimedEvent, TimedEventHandle, TimerState, ViewHandler},
     model::ModelData,
 };
 
+#[cfg(feature = "serde")]
+use crate::model::PersistentModel;
+#[cfg(feature = "serde")]
+use persistence::Persistence;
+
+#[cfg(feature = "profiling")]
+use crate::profiling::{FrameStats, FrameStatsData, Profiler};
+
+use jobs::Jobs;
+
 use crate::{
     binding::{BindingHandler, MapId},
-    resource::StoredImage,
+    resource::{is_svg, StoredImage},
 };
 use crate::{cache::CachedData, resource::ImageOrSvg};
 
+use crate::inspector::Inspector;
 use crate::prelude::*;
 use crate::resource::ResourceManager;
+use crate::systems::{
+    baseline_offset, binding_system, delay_animations, hover_system, query_selector, restyle,
+};
 use crate::text::TextContext;
 use vizia_input::MouseState;
-use vizia_storage::{ChildIterator, LayoutTreeIterator};
+use vizia_storage::{ChildIterator, LayoutParentIterator, LayoutTreeIterator};
 
 static DEFAULT_LAYOUT: &str = include_str!("../../resources/themes/default_layout.css");
 static DARK_THEME: &str = include_str!("../../resources/themes/dark_theme.css");
@@ -72,6 +96,14 @@ thread_local! {
     pub static MAPS: RefCell<HashMap<MapId, (Entity, Box<dyn Any>)>> = RefCell::new(HashMap::new());
     /// The 'current' entity which is used for storing lens map mapping functions as per above.
     pub static CURRENT: RefCell<Entity> = RefCell::new(Entity::root());
+    /// A counter which is incremented whenever the data of a model or view may have changed, used to invalidate the
+    /// cached values of memoized lens maps.
+    pub(crate) static DATA_GENERATION: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Marks the data of the models and views as possibly changed, invalidating the cached values of memoized lens maps.
+pub(crate) fn data_changed() {
+    DATA_GENERATION.with(|generation| generation.set(generation.get().wrapping_add(1)));
 }
 
 #[derive(Default, Clone)]
@@ -82,10 +114,53 @@ pub struct WindowState {
     pub needs_redraw: bool,
     pub redraw_list: HashSet<Entity>,
     pub dirty_rect: Option<BoundingBox>,
+    /// The separate regions, in physical pixels, which make up the dirty rect of the window.
+    pub dirty_regions: Vec<BoundingBox>,
     pub owner: Option<Entity>,
     pub is_modal: bool,
     pub should_close: bool,
     pub content: Option<Arc<dyn Fn(&mut Context)>>,
+    /// Whether the window has already been resized to fit its content.
+    pub content_fitted: bool,
+    /// The minimum logical size of the content of the window, set with `Context::set_minimum_content_size`.
+    pub min_content_size: Option<WindowSize>,
+    /// The area of the text cursor in physical pixels, reported while drawing, which the candidate window of an input
+    /// method is placed next to.
+    pub ime_cursor_area: Option<BoundingBox>,
+}
+
+// The maximum number of separate dirty regions tracked per window before they are merged into one.
+const MAX_DIRTY_REGIONS: usize = 16;
+
+impl WindowState {
+    /// Adds a region, in physical pixels, to the area of the window which will be repainted on the next draw.
+    ///
+    /// Overlapping regions are merged so that only the damaged parts of the window are repainted.
+    pub(crate) fn add_dirty_region(&mut self, region: BoundingBox) {
+        if region.w <= 0.0 || region.h <= 0.0 {
+            return;
+        }
+
+        if let Some(dirty_rect) = &mut self.dirty_rect {
+            *dirty_rect = dirty_rect.union(&region);
+        } else {
+            self.dirty_rect = Some(region);
+        }
+
+        let mut region = region;
+        while let Some(index) = self.dirty_regions.iter().position(|r| r.intersects(&region)) {
+            region = region.union(&self.dirty_regions.swap_remove(index));
+        }
+
+        if self.dirty_regions.len() >= MAX_DIRTY_REGIONS {
+            self.dirty_regions.clear();
+            if let Some(dirty_rect) = self.dirty_rect {
+                region = dirty_rect;
+            }
+        }
+
+        self.dirty_regions.push(region);
+    }
 }
 
 /// The main storage and control object for a Vizia application.
@@ -107,6 +182,7 @@ pub struct Context {
     pub(crate) listeners:
         HashMap<Entity, Box<dyn Fn(&mut dyn ViewHandler, &mut EventContext, &mut Event)>>,
     pub(crate) global_listeners: Vec<Box<dyn Fn(&mut EventContext, &mut Event)>>,
+    pub(crate) interceptors: Interceptors,
     pub(crate) style: Style,
     pub(crate) cache: CachedData,
     pub windows: HashMap<Entity, WindowState>,
@@ -115,10 +191,16 @@ pub struct Context {
     pub(crate) modifiers: Modifiers,
 
     pub(crate) captured: Entity,
+    pub(crate) pointer_lock: Option<Entity>,
+    pub(crate) raw_pointer_motion: bool,
     pub(crate) triggered: Entity,
     pub(crate) hovered: Entity,
+    pub(crate) hover_capture: Option<Entity>,
     pub(crate) focused: Entity,
-    pub(crate) focus_stack: Vec<Entity>,
+    // Subtrees which keyboard focus is trapped within, with the view focused before each was pushed, stacked per
+    // window.
+    pub(crate) focus_scopes: HashMap<Entity, Vec<(Entity, Entity)>>,
+    pub(crate) inspector: Option<Entity>,
     pub(crate) cursor_icon_locked: bool,
 
     pub(crate) resource_manager: ResourceManager,
@@ -128,7 +210,7 @@ pub struct Context {
     pub(crate) event_proxy: Option<Box<dyn EventProxy>>,
 
     #[cfg(feature = "clipboard")]
-    pub(crate) clipboard: Box<dyn ClipboardProvider>,
+    pub(crate) clipboard: Clipboard,
 
     pub(crate) click_time: Instant,
     pub(crate) clicks: usize,
@@ -139,6 +221,40 @@ pub struct Context {
     pub window_has_focus: bool,
 
     pub(crate) drop_data: Option<DropData>,
+
+    // The scheduled removal of each class added with `add_scoped_class`, and whether the class was added by it rather
+    // than already being present, in which case it is left in place.
+    pub(crate) scoped_classes: HashMap<(Entity, String), (TimedEventHandle, bool)>,
+
+    pub(crate) resize_debounce: Option<Duration>,
+    pub(crate) last_resize: Option<Instant>,
+    pub(crate) last_resize_layout: Instant,
+
+    pub(crate) idle_callbacks: Vec<Box<dyn FnOnce(&mut Context)>>,
+    pub(crate) deferred: Vec<(Entity, Box<dyn FnOnce(&mut Context)>)>,
+    pub(crate) jobs: Jobs,
+
+    pub(crate) catch_panics: bool,
+
+    pub(crate) frozen: Option<(Instant, bool)>,
+    pub(crate) frozen_input: Vec<Event>,
+
+    pub(crate) key_repeat: Option<(Duration, Timer)>,
+    // The held key, the character it input, the window it was pressed in, and when it was pressed.
+    pub(crate) held_key: Option<(Code, Option<Key>, Option<char>, Entity, Instant)>,
+    // The last character input, which is repeated along with the key pressed after it.
+    pub(crate) char_input: Option<char>,
+
+    #[cfg(feature = "hot-reload")]
+    pub(crate) watched_stylesheets: Vec<(std::path::PathBuf, Option<std::time::SystemTime>)>,
+    #[cfg(feature = "hot-reload")]
+    pub(crate) stylesheet_watcher: Option<Timer>,
+
+    #[cfg(feature = "serde")]
+    pub(crate) persistence: Persistence,
+
+    #[cfg(feature = "profiling")]
+    pub(crate) profiler: Profiler,
 }
 
 impl Default for Context {
@@ -173,13 +289,18 @@ impl Context {
             tree_updates: Vec::new(),
             listeners: HashMap::default(),
             global_listeners: Vec::new(),
+            interceptors: Interceptors::default(),
             mouse: MouseState::default(),
             modifiers: Modifiers::empty(),
             captured: Entity::null(),
+            pointer_lock: None,
+            raw_pointer_motion: false,
             triggered: Entity::null(),
             hovered: Entity::root(),
+            hover_capture: None,
             focused: Entity::root(),
-            focus_stack: Vec::new(),
+            focus_scopes: HashMap::new(),
+            inspector: None,
             cursor_icon_locked: false,
             resource_manager: ResourceManager::new(),
             text_context: {
@@ -199,22 +320,14 @@ impl Context {
                     asset_provider,
                     text_bounds: Default::default(),
                     text_paragraphs: Default::default(),
+                    font_fallback: Default::default(),
                 }
             },
 
             event_proxy: None,
 
             #[cfg(feature = "clipboard")]
-            clipboard: {
-                #[cfg(feature = "x11")]
-                if let Ok(context) = ClipboardContext::new() {
-                    Box::new(context)
-                } else {
-                    Box::new(NopClipboardContext::new().unwrap())
-                }
-                #[cfg(not(feature = "x11"))]
-                Box::new(NopClipboardContext::new().unwrap())
-            },
+            clipboard: Clipboard::default(),
             click_time: Instant::now(),
             clicks: 0,
             click_pos: (0.0, 0.0),
@@ -224,6 +337,36 @@ impl Context {
             window_has_focus: true,
 
             drop_data: None,
+
+            scoped_classes: HashMap::new(),
+
+            resize_debounce: None,
+            last_resize: None,
+            last_resize_layout: Instant::now(),
+
+            idle_callbacks: Vec::new(),
+            deferred: Vec::new(),
+            jobs: Jobs::default(),
+
+            frozen: None,
+            frozen_input: Vec::new(),
+
+            key_repeat: None,
+            held_key: None,
+            char_input: None,
+
+            #[cfg(feature = "hot-reload")]
+            watched_stylesheets: Vec::new(),
+            #[cfg(feature = "hot-reload")]
+            stylesheet_watcher: None,
+
+            catch_panics: false,
+
+            #[cfg(feature = "serde")]
+            persistence: Persistence::default(),
+
+            #[cfg(feature = "profiling")]
+            profiler: Profiler::default(),
         };
 
         result.tree.set_window(Entity::root(), true);
@@ -235,6 +378,9 @@ impl Context {
         // Build the environment model at the root.
         Environment::new(&mut result).build(&mut result);
 
+        #[cfg(feature = "profiling")]
+        FrameStatsData { stats: FrameStats::default() }.build(&mut result);
+
         result.entity_manager.create();
 
         result.style.role.insert(Entity::root(), Role::Window);
@@ -264,14 +410,45 @@ impl Context {
         self.data::<Environment>().unwrap()
     }
 
+    /// Returns a lens to the [`FrameStats`] of the application, which are averaged and updated every half second.
+    ///
+    /// # Example
+    /// ```
+    /// # use vizia_core::prelude::*;
+    /// # let cx = &mut Context::default();
+    /// let fps = cx.frame_stats().map(|stats| format!("{:.0} fps", stats.fps));
+    /// Label::new(cx, fps);
+    /// ```
+    #[cfg(feature = "profiling")]
+    pub fn frame_stats(&self) -> impl Lens<Target = FrameStats> {
+        FrameStatsData::stats
+    }
+
+    /// Shows or hides the inspector, a panel for debugging the views of the main window.
+    ///
+    /// The inspector shows the tree of views, along with the classes, pseudo-classes, layout and matched style rules
+    /// of the selected view, and highlights the bounds of the hovered view. Style properties of the selected view can
+    /// be edited by submitting declarations, such as `background-color: red;`, which apply until the styles are
+    /// reloaded. In debug builds the inspector can also be toggled with `Ctrl+Shift+I`.
+    pub fn toggle_inspector(&mut self) {
+        if let Some(inspector) = self.inspector.take() {
+            self.remove(inspector);
+        } else {
+            let inspector = self.with_current(Entity::root(), |cx| Inspector::new(cx).entity());
+            self.inspector = Some(inspector);
+        }
+
+        self.needs_redraw(Entity::root());
+    }
+
     /// Returns the entity id of the  parent window to the current view.
     pub fn parent_window(&self) -> Entity {
         self.tree.get_parent_window(self.current).unwrap_or(Entity::root())
     }
 
-    /// Returns the scale factor of the display.
+    /// Returns the scale factor of the display of the window containing the current view.
     pub fn scale_factor(&self) -> f32 {
-        self.style.dpi_factor as f32
+        self.style.window_scale_factor(crate::tree::window_of(&self.tree, self.current)) as f32
     }
 
     /// Mark the application as needing to rerun the draw method
@@ -299,11 +476,395 @@ impl Context {
         // self.style.needs_restyle();
     }
 
+    /// Serializes every model built with [`build_persistent`](crate::model::PersistentModel::build_persistent) or
+    /// registered with [`persist`](Context::persist).
+    ///
+    /// Models registered with `persist` are keyed by the key they were registered under. Other models are keyed by the
+    /// [entity path](Context::entity_path) of the entity they are attached to and the type name of the model, so that
+    /// the state can be restored with [`restore_models`](Context::restore_models) after the application is rebuilt.
+    #[cfg(feature = "serde")]
+    pub fn serialize_models(&self) -> serde_json::Map<String, serde_json::Value> {
+        self.persistence.serialize(&self.models, |entity, name| {
+            model_key(&self.tree, &self.style, &self.views, entity, name)
+        })
+    }
+
+    /// Restores models previously saved with [`serialize_models`](Context::serialize_models).
+    ///
+    /// Models are matched by key, so the model must have been built with
+    /// [`build_persistent`](crate::model::PersistentModel::build_persistent) at the same position in the tree, or
+    /// registered with [`persist`](Context::persist) under the same key. Entries which do not match a model, or which
+    /// fail to deserialize, are ignored. Returns the number of models restored.
+    #[cfg(feature = "serde")]
+    pub fn restore_models(&mut self, state: &serde_json::Map<String, serde_json::Value>) -> usize {
+        let (tree, style, views) = (&self.tree, &self.style, &self.views);
+        self.persistence.restore(
+            &mut self.models,
+            state,
+            |entity, name| model_key(tree, style, views, entity, name),
+            None,
+        )
+    }
+
+    /// Registers the nearest model of type `M`, attached to the current view or one of its ancestors, to be saved
+    /// under the given key, and restores the model from the saved state if there is one.
+    ///
+    /// The state of registered models is saved to a `state.json` file in the platform config directory when the
+    /// application exits, and can also be saved and restored manually with [`save_state`](Context::save_state) and
+    /// [`load_state`](Context::load_state).
+    ///
+    /// # Example
+    /// ```ignore
+    /// # use vizia_core::prelude::*;
+    /// # let cx = &mut Context::default();
+    /// #[derive(Lens, serde::Serialize, serde::Deserialize)]
+    /// pub struct Settings {
+    ///     volume: f32,
+    /// }
+    ///
+    /// impl Model for Settings {}
+    ///
+    /// Settings { volume: 1.0 }.build(cx);
+    /// cx.persist::<Settings>("settings");
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn persist<M: PersistentModel>(&mut self, key: impl Into<String>) {
+        let type_id = TypeId::of::<M>();
+        let Some(entity) = self.current.parent_iter(&self.tree).find(|entity| {
+            self.models.get(entity).is_some_and(|models| models.contains_key(&type_id))
+        }) else {
+            log::warn!(
+                "Cannot persist {}, the model has not been built",
+                std::any::type_name::<M>()
+            );
+            return;
+        };
+
+        let key = key.into();
+        self.persistence.register::<M>(entity, Some(key.clone()));
+        let (tree, style, views) = (&self.tree, &self.style, &self.views);
+        let key_of = |entity, name: &str| model_key(tree, style, views, entity, name);
+        if let Err(err) = self.persistence.load(&mut self.models, key_of, Some(&key)) {
+            log::warn!("Failed to restore the state of {}: {}", key, err);
+        }
+    }
+
+    /// Saves the state of the models serialized by [`serialize_models`](Context::serialize_models) to the state file.
+    ///
+    /// Fails without overwriting the state file if the existing file can't be read.
+    #[cfg(feature = "serde")]
+    pub fn save_state(&self) -> std::io::Result<()> {
+        self.persistence.save(&self.models, |entity, name| {
+            model_key(&self.tree, &self.style, &self.views, entity, name)
+        })
+    }
+
+    /// Restores the models serialized by [`serialize_models`](Context::serialize_models) from the state file,
+    /// returning the number of models restored.
+    #[cfg(feature = "serde")]
+    pub fn load_state(&mut self) -> std::io::Result<usize> {
+        let (tree, style, views) = (&self.tree, &self.style, &self.views);
+        self.persistence.load(
+            &mut self.models,
+            |entity, name| model_key(tree, style, views, entity, name),
+            None,
+        )
+    }
+
+    /// Sets the path of the file the state of persisted models is saved to, in place of the default `state.json` in
+    /// the platform config directory.
+    #[cfg(feature = "serde")]
+    pub fn set_state_path(&mut self, path: impl Into<std::path::PathBuf>) {
+        self.persistence.set_path(path.into());
+    }
+
     /// Mark the application as needing to rerun layout computations
     pub fn needs_relayout(&mut self) {
         self.style.needs_relayout();
     }
 
+    /// Registers a callback to be run once, the next time the application becomes idle.
+    ///
+    /// The application is idle when there are no queued events, no pending redraws, and no running animations. This is
+    /// useful for deferred work such as prefetching or saving. Callbacks registered from within an idle callback are run
+    /// at the next point of idleness rather than immediately, so registering work repeatedly will not busy-loop.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vizia_core::prelude::*;
+    /// # let cx = &mut Context::default();
+    /// cx.on_idle(|cx| {
+    ///     // Save application state here
+    /// });
+    /// ```
+    pub fn on_idle(&mut self, callback: impl FnOnce(&mut Context) + 'static) {
+        self.idle_callbacks.push(Box::new(callback));
+    }
+
+    /// Sends an event containing a clone of the provided message directly to every entity in the tree.
+    ///
+    /// The events are delivered in tree pre-order, i.e. each entity receives the message before its descendants
+    /// and before its following siblings.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use vizia_core::prelude::*;
+    /// # let cx = &mut Context::default();
+    /// # #[derive(Clone)]
+    /// # enum AppEvent {Save}
+    /// cx.emit_to_all(AppEvent::Save);
+    /// ```
+    pub fn emit_to_all<M: Any + Send + Clone>(&mut self, message: M) {
+        EventContext::new(self).emit_to_all(message);
+    }
+
+    /// Queues a closure to be run after the current event dispatch cycle has completed.
+    ///
+    /// This allows event handlers to safely make structural changes to the tree, such as adding or removing views,
+    /// without affecting the dispatch of the events in the current cycle. The closure is called with the current entity
+    /// set to the entity which deferred it, or to the root if that entity has since been removed.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vizia_core::prelude::*;
+    /// # let cx = &mut Context::default();
+    /// cx.defer(|cx| cx.remove(cx.current()));
+    /// ```
+    pub fn defer(&mut self, f: impl FnOnce(&mut Context) + 'static) {
+        self.deferred.push((self.current, Box::new(f)));
+    }
+
+    /// Restyles the views which need restyling, returning the style properties which changed for each view along with
+    /// their computed values before and after the restyle.
+    ///
+    /// Views whose properties did not change are omitted. This is mainly useful for testing style rules and
+    /// transitions, for example by toggling a pseudo-class and checking which properties were affected.
+    pub fn diff_and_patch_style(&mut self) -> HashMap<Entity, Vec<StyleChange>> {
+        let mut diffs = HashMap::new();
+        restyle(self, Some(&mut diffs));
+        diffs
+    }
+
+    /// Returns the distance, in physical pixels, from the top of the bounds of an entity to the baseline of the first
+    /// line of its text, or `None` if the entity has no text.
+    ///
+    /// This can be used to align text of different sizes across views. For views within a row, the `align-self: baseline`
+    /// style property performs this alignment automatically.
+    pub fn measure_baseline(&mut self, entity: Entity) -> Option<f32> {
+        baseline_offset(&EventContext::new(self), entity)
+    }
+
+    /// Sets whether panics inside the event handlers of views and models should be caught.
+    ///
+    /// When enabled, a panic in a handler, including callbacks such as `on_press`, is logged and the event is
+    /// treated as handled, allowing the application to continue processing subsequent events. Disabled by default.
+    pub fn set_catch_panics(&mut self, flag: bool) {
+        self.catch_panics = flag;
+    }
+
+    /// Sets the interval used to throttle relayout while a window is being resized, or `None` to relayout on every resize.
+    ///
+    /// While a resize is in progress text is measured and laid out at most once per interval, which is usually the most
+    /// expensive part of a relayout. In between, the tree is laid out using the size of the text from the previous
+    /// layout so that views still follow the size of the window. Once no resize has occurred for the interval, a final
+    /// relayout is performed so the layout matches the final window size.
+    pub fn set_resize_debounce(&mut self, interval: Option<Duration>) {
+        self.resize_debounce = interval;
+
+        if interval.is_none() {
+            self.last_resize = None;
+        }
+    }
+
+    /// Freezes the application, pausing animations, timers, scheduled events and layout until [`thaw`](Self::thaw)
+    /// is called, so that a stable state can be captured, e.g. for screenshots or stepping through an interaction.
+    ///
+    /// Mouse and keyboard input received while frozen is queued and handled once the application is thawed if
+    /// `queue_input` is true, otherwise it is dropped.
+    pub fn freeze(&mut self, queue_input: bool) {
+        match &mut self.frozen {
+            Some((_, queue)) => *queue = queue_input,
+            None => self.frozen = Some((Instant::now(), queue_input)),
+        }
+    }
+
+    /// Resumes an application frozen with [`freeze`](Self::freeze).
+    ///
+    /// Animations, timers and scheduled events continue from where they were when the application was frozen.
+    pub fn thaw(&mut self) {
+        self.thaw_at(Instant::now());
+    }
+
+    // Thaws the application as if at the given time, which lets tests advance time without waiting.
+    pub(crate) fn thaw_at(&mut self, now: Instant) {
+        let Some((frozen_at, _)) = self.frozen.take() else {
+            return;
+        };
+
+        let elapsed = now.saturating_duration_since(frozen_at);
+
+        self.running_timers = self
+            .running_timers
+            .drain()
+            .map(|mut timer_state| {
+                timer_state.time += elapsed;
+                timer_state.start_time += elapsed;
+                timer_state
+            })
+            .collect();
+
+        self.event_schedule = self
+            .event_schedule
+            .drain()
+            .map(|mut timed_event| {
+                timed_event.time += elapsed;
+                timed_event
+            })
+            .collect();
+
+        delay_animations(self, elapsed);
+
+        self.event_queue.extend(self.frozen_input.drain(..));
+        self.style.needs_relayout();
+    }
+
+    /// Returns true if the application is frozen.
+    pub fn is_frozen(&self) -> bool {
+        self.frozen.is_some()
+    }
+
+    /// Sets the delay before a held key starts to repeat and the interval between repeats, or `None` to use the key
+    /// repeat of the platform.
+    ///
+    /// While a key is held, a [`WindowEvent::KeyDown`] is sent to the focused view once the delay has elapsed and then
+    /// at each interval until the key is released, preceded by a [`WindowEvent::CharInput`] if the key input a
+    /// character. Key repeats generated by the platform are ignored.
+    pub fn set_repeat_rate(&mut self, rate: Option<(Duration, Duration)>) {
+        let timer = if let Some((_, timer)) = self.key_repeat.take() {
+            self.stop_timer(timer);
+            Some(timer)
+        } else {
+            None
+        };
+
+        self.held_key = None;
+
+        if let Some((delay, interval)) = rate {
+            let timer = match timer {
+                Some(timer) => {
+                    self.timers[timer.0].interval = interval;
+                    timer
+                }
+
+                None => self.add_timer(interval, None, |cx, action| {
+                    if let TimerAction::Tick(_) = action {
+                        cx.emit_custom(Event::new(InternalEvent::RepeatKey).target(Entity::root()));
+                    }
+                }),
+            };
+
+            self.key_repeat = Some((delay, timer));
+        }
+    }
+
+    // Stops repeating the held key, if any.
+    pub(crate) fn release_held_key(&mut self) {
+        if self.held_key.take().is_some() {
+            if let Some((_, timer)) = self.key_repeat {
+                self.stop_timer(timer);
+            }
+        }
+    }
+
+    /// Requests that a window be resized to fit its content, using the bounds computed by the last layout pass.
+    ///
+    /// The content size is the extent of the children of the window plus its right and bottom padding, clamped to
+    /// the minimum and maximum inner size of the window. Returns the requested logical inner size, or `None` if the
+    /// entity is not a window.
+    pub fn measure_and_fit(&mut self, window: Entity) -> Option<WindowSize> {
+        let window_bounds = self.cache.get_bounds(window);
+
+        let mut right = 0.0f32;
+        let mut bottom = 0.0f32;
+
+        for child in window.child_iter(&self.tree) {
+            if self.style.display.get(child).copied().unwrap_or_default() == Display::None {
+                continue;
+            }
+
+            let bounds = self.cache.get_bounds(child);
+            right = right.max(bounds.right() - window_bounds.x);
+            bottom = bottom.max(bounds.bottom() - window_bounds.y);
+        }
+
+        right += window
+            .padding_right(&self.style)
+            .map(|padding| padding.to_px(window_bounds.w, 0.0))
+            .unwrap_or_default();
+        bottom += window
+            .padding_bottom(&self.style)
+            .map(|padding| padding.to_px(window_bounds.h, 0.0))
+            .unwrap_or_default();
+
+        let scale_factor = self.style.window_scale_factor(window) as f32;
+
+        let window_state = self.windows.get_mut(&window)?;
+        let description = &mut window_state.window_description;
+
+        let mut width = (right / scale_factor).ceil() as u32;
+        let mut height = (bottom / scale_factor).ceil() as u32;
+
+        for min_size in description.min_inner_size.iter().chain(&window_state.min_content_size) {
+            width = width.max(min_size.width);
+            height = height.max(min_size.height);
+        }
+
+        if let Some(max_size) = description.max_inner_size {
+            width = width.min(max_size.width);
+            height = height.min(max_size.height);
+        }
+
+        let size = WindowSize::new(width, height);
+
+        description.inner_size = size;
+        window_state.content_fitted = true;
+
+        self.emit_to(window, WindowEvent::SetSize(size));
+
+        Some(size)
+    }
+
+    /// Sets the minimum logical size of the content of a window, or `None` to remove the constraint.
+    ///
+    /// A minimum inner size of at least the content size is requested for the window, and the window is laid out at
+    /// no less than this size so that its content doesn't collapse if the window becomes smaller, for example on
+    /// platforms which ignore the request. Content which then overflows the window can be made reachable by placing
+    /// it within a [`ScrollView`](crate::views::ScrollView).
+    pub fn set_minimum_content_size(&mut self, window: Entity, size: Option<WindowSize>) {
+        let Some(window_state) = self.windows.get_mut(&window) else {
+            return;
+        };
+
+        window_state.min_content_size = size;
+
+        let min_inner_size = match (window_state.window_description.min_inner_size, size) {
+            (Some(min_size), Some(size)) => Some(WindowSize::new(
+                min_size.width.max(size.width),
+                min_size.height.max(size.height),
+            )),
+            (min_size, size) => min_size.or(size),
+        };
+
+        if let Some(size) = size {
+            self.style.min_content_size.insert(window, (size.width as f32, size.height as f32));
+        } else {
+            self.style.min_content_size.remove(window);
+        }
+
+        self.style.needs_relayout();
+        self.emit_to(window, WindowEvent::SetMinSize(min_inner_size));
+    }
+
     pub(crate) fn set_system_flags(&mut self, entity: Entity, system_flags: SystemFlags) {
         if system_flags.contains(SystemFlags::RESTYLE) {
             self.needs_restyle(entity);
@@ -345,16 +906,26 @@ impl Context {
             if !enabled || focus_visible {
                 pseudo_classes.set(PseudoClassFlags::FOCUS_VISIBLE, enabled);
                 self.style.needs_access_update(focused);
-                self.needs_restyle(focused);
             }
+
+            self.style.invalidate_pseudo_classes(
+                &self.tree,
+                focused,
+                PseudoClassFlags::FOCUS | PseudoClassFlags::FOCUS_VISIBLE,
+            );
         }
 
         for ancestor in focused.parent_iter(&self.tree) {
-            let entity = ancestor;
-            if let Some(pseudo_classes) = self.style.pseudo_classes.get_mut(entity) {
-                pseudo_classes.set(PseudoClassFlags::FOCUS_WITHIN, enabled);
+            if let Some(pseudo_classes) = self.style.pseudo_classes.get_mut(ancestor) {
+                if pseudo_classes.contains(PseudoClassFlags::FOCUS_WITHIN) != enabled {
+                    pseudo_classes.set(PseudoClassFlags::FOCUS_WITHIN, enabled);
+                    self.style.invalidate_pseudo_classes(
+                        &self.tree,
+                        ancestor,
+                        PseudoClassFlags::FOCUS_WITHIN,
+                    );
+                }
             }
-            // self.needs_restyle(entity);
         }
     }
 
@@ -373,8 +944,6 @@ impl Context {
         self.emit_custom(Event::new(WindowEvent::FocusVisibility(focus_visible)).target(old_focus));
         self.emit_custom(Event::new(WindowEvent::FocusVisibility(focus_visible)).target(new_focus));
 
-        self.needs_restyle(self.focused);
-        self.needs_restyle(self.current);
         self.style.needs_access_update(self.focused);
         self.style.needs_access_update(self.current);
     }
@@ -391,6 +960,25 @@ impl Context {
         self.focus_with_visibility(old_focus_visible)
     }
 
+    /// Synthesizes a press of the given entity without any real input.
+    ///
+    /// A [`PressDown`](WindowEvent::PressDown) followed by a [`Press`](WindowEvent::Press) is sent directly to the entity,
+    /// triggering its `on_press_down` and `on_press` callbacks as if it had been clicked. This is useful for handling
+    /// accessibility actions and for scripting interactions in tests. Disabled views ignore the press.
+    pub fn press(&mut self, entity: Entity) {
+        for message in
+            [WindowEvent::PressDown { mouse: false }, WindowEvent::Press { mouse: false }]
+        {
+            self.emit_custom(
+                Event::new(message)
+                    .target(entity)
+                    .origin(entity)
+                    .propagate(Propagation::Direct)
+                    .synthesized(),
+            );
+        }
+    }
+
     /// Removes the children of the provided entity from the application.
     pub(crate) fn remove_children(&mut self, entity: Entity) {
         let child_iter = ChildIterator::new(&self.tree, entity);
@@ -408,7 +996,27 @@ impl Context {
             self.style.needs_restyle(self.current);
             self.style.needs_relayout();
             self.needs_redraw(self.current);
+
+            // Restyling the removed view restyles its siblings, which may match different structural pseudo-classes,
+            // such as `:nth-child`, once it has been removed.
+            self.needs_restyle(entity);
+        }
+
+        // Close any focus scopes within the removed subtree. If the focused view is removed, focus returns to the
+        // view which was focused before the outermost of the scopes was pushed.
+        let focus_removed = delete_list.contains(&self.focused);
+        let mut restore_focus = None;
+        for scopes in self.focus_scopes.values_mut() {
+            scopes.retain(|(scope, previous)| {
+                if delete_list.contains(scope) {
+                    restore_focus.get_or_insert(*previous);
+                    false
+                } else {
+                    true
+                }
+            });
         }
+        self.focus_scopes.retain(|_, scopes| !scopes.is_empty());
 
         for entity in delete_list.iter().rev() {
             if let Some(mut view) = self.views.remove(entity) {
@@ -420,10 +1028,9 @@ impl Context {
                 self.views.insert(*entity, view);
             }
 
+            // Drop the binding so that rebuilding content doesn't leak the bindings of removed views.
             if let Some(binding) = self.bindings.remove(entity) {
                 binding.remove(self);
-
-                self.bindings.insert(*entity, binding);
             }
 
             for image in self.resource_manager.images.values_mut() {
@@ -435,22 +1042,36 @@ impl Context {
                 self.entity_identifiers.remove(identifier);
             }
 
-            if let Some(index) = self.focus_stack.iter().position(|r| r == entity) {
-                self.focus_stack.remove(index);
+            for (_, previous) in self.focus_scopes.values_mut().flatten() {
+                if previous == entity {
+                    *previous = Entity::root();
+                }
             }
 
-            if self.focused == *entity {
-                if let Some(new_focus) = self.focus_stack.pop() {
-                    self.with_current(new_focus, |cx| cx.focus());
-                } else {
-                    self.with_current(Entity::root(), |cx| cx.focus());
-                }
+            if restore_focus == Some(*entity) {
+                restore_focus = Some(Entity::root());
+            }
+
+            if self.focused == *entity && restore_focus.is_none() {
+                self.with_current(Entity::root(), |cx| cx.focus());
             }
 
             if self.captured == *entity {
                 self.captured = Entity::null();
             }
 
+            if self.inspector == Some(*entity) {
+                self.inspector = None;
+            }
+
+            if self.hover_capture == Some(*entity) {
+                self.hover_capture = None;
+            }
+
+            if self.pointer_lock == Some(*entity) {
+                self.with_current(*entity, |cx| cx.unlock_pointer());
+            }
+
             // Remove any map lenses associated with the entity.
 
             MAP_MANAGER.with_borrow_mut(|manager| {
@@ -482,18 +1103,26 @@ impl Context {
                 self.stop_timer(timer);
             }
 
+            // Cancel the removal of any scoped classes so that it isn't applied to a view which reuses the entity.
+            let mut scoped_classes = Vec::new();
+            self.scoped_classes.retain(|(scoped_entity, _), (handle, _)| {
+                if scoped_entity == entity {
+                    scoped_classes.push(*handle);
+                    false
+                } else {
+                    true
+                }
+            });
+
+            for handle in scoped_classes {
+                self.cancel_scheduled(handle);
+            }
+
             let window_entity = self.tree.get_parent_window(*entity).unwrap_or(Entity::root());
 
             if !self.tree.is_window(*entity) {
                 if let Some(draw_bounds) = self.cache.draw_bounds.get(*entity) {
-                    if let Some(dirty_rect) =
-                        &mut self.windows.get_mut(&window_entity).unwrap().dirty_rect
-                    {
-                        *dirty_rect = dirty_rect.union(draw_bounds);
-                    } else {
-                        self.windows.get_mut(&window_entity).unwrap().dirty_rect =
-                            Some(*draw_bounds);
-                    }
+                    self.windows.get_mut(&window_entity).unwrap().add_dirty_region(*draw_bounds);
                 }
             }
 
@@ -507,39 +1136,131 @@ impl Context {
             self.cache.remove(*entity);
             self.style.remove(*entity);
             self.models.remove(entity);
+            #[cfg(feature = "serde")]
+            self.persistence.remove_entity(*entity);
             self.stores.remove(entity);
             self.views.remove(entity);
+            self.jobs.cancel_entity(*entity);
             self.text_context.text_bounds.remove(*entity);
             self.text_context.text_paragraphs.remove(*entity);
             self.entity_manager.destroy(*entity);
         }
+
+        if let Some(previous) = restore_focus.filter(|_| focus_removed) {
+            self.with_current(previous, |cx| cx.focus());
+        }
     }
 
-    /// Add a listener to an entity.
+    /// Locks the mouse pointer in place and hides it, sending the relative motion of the mouse to the current view as
+    /// [`WindowEvent::MouseMotion`] events so that dragging is not bounded by the edges of the screen.
     ///
-    /// A listener can be used to handle events which would not normally propagate to the entity.
-    /// For example, mouse events when a different entity has captured them. Useful for things like
-    /// closing a popup when clicking outside of its bounding box.
-    pub fn add_listener<F, W>(&mut self, listener: F)
-    where
-        W: View,
-        F: 'static + Fn(&mut W, &mut EventContext, &mut Event),
-    {
-        self.listeners.insert(
-            self.current,
-            Box::new(move |event_handler, context, event| {
-                if let Some(widget) = event_handler.downcast_mut::<W>() {
-                    (listener)(widget, context, event);
-                }
-            }),
-        );
+    /// On platforms which cannot lock the pointer it is confined to the window instead, with the motion computed
+    /// from the movement of the cursor.
+    pub fn lock_pointer(&mut self) {
+        EventContext::new(self).lock_pointer();
     }
 
-    /// Adds a global listener to the application.
+    /// Releases a pointer locked with [`lock_pointer`](Self::lock_pointer), showing the cursor again.
+    pub fn unlock_pointer(&mut self) {
+        EventContext::new(self).unlock_pointer();
+    }
+
+    /// Keeps the current view hovered, regardless of the position of the mouse cursor, until
+    /// [`release_hover`](Self::release_hover) is called.
     ///
-    /// Global listeners have the first opportunity to handle every event that is sent in an
-    /// application. They will *never* be removed. If you need a listener tied to the lifetime of a
-    /// view, use `add_listener`.
+    /// This prevents the `:hover` state of other views from flickering as the cursor crosses them during an
+    /// interaction such as a drag.
+    pub fn capture_hover(&mut self) {
+        self.set_hover_capture(Some(self.current));
+    }
+
+    /// Releases the hover captured by the current view, recomputing the hovered view from the mouse cursor.
+    pub fn release_hover(&mut self) {
+        if self.hover_capture == Some(self.current) {
+            self.set_hover_capture(None);
+        }
+    }
+
+    pub(crate) fn set_hover_capture(&mut self, capture: Option<Entity>) {
+        self.hover_capture = capture;
+
+        let previous = self.hovered;
+        let was_hovered = self.hovered_ancestors(previous);
+
+        match capture {
+            Some(entity) if entity != previous => {
+                for ancestor in LayoutParentIterator::new(&self.tree, previous) {
+                    if let Some(pseudo_classes) = self.style.pseudo_classes.get_mut(ancestor) {
+                        pseudo_classes.set(PseudoClassFlags::HOVER, false);
+                    }
+                }
+
+                for ancestor in LayoutParentIterator::new(&self.tree, entity) {
+                    if let Some(pseudo_classes) = self.style.pseudo_classes.get_mut(ancestor) {
+                        pseudo_classes.set(PseudoClassFlags::HOVER, true);
+                    }
+                }
+
+                self.event_queue.push_back(Event::new(WindowEvent::MouseEnter).direct(entity));
+                self.event_queue.push_back(Event::new(WindowEvent::MouseLeave).direct(previous));
+                self.event_queue.push_back(Event::new(WindowEvent::MouseOver).target(entity));
+                self.event_queue.push_back(Event::new(WindowEvent::MouseOut).target(previous));
+
+                self.hovered = entity;
+            }
+
+            Some(_) => {}
+
+            None => {
+                let window = self.tree.get_parent_window(self.hovered).unwrap_or(Entity::root());
+                self.with_current(window, |cx| hover_system(cx, window));
+            }
+        }
+
+        // Restyle every view whose hover state changed, as rules can match the `:hover` state of an ancestor.
+        let is_hovered = self.hovered_ancestors(self.hovered);
+        for entity in was_hovered.symmetric_difference(&is_hovered) {
+            self.style.invalidate_pseudo_classes(&self.tree, *entity, PseudoClassFlags::HOVER);
+        }
+    }
+
+    // Returns the given view and those of its ancestors which are hovered.
+    fn hovered_ancestors(&self, entity: Entity) -> HashSet<Entity> {
+        LayoutParentIterator::new(&self.tree, entity)
+            .filter(|ancestor| {
+                self.style
+                    .pseudo_classes
+                    .get(*ancestor)
+                    .is_some_and(|pseudo_classes| pseudo_classes.contains(PseudoClassFlags::HOVER))
+            })
+            .collect()
+    }
+
+    /// Add a listener to an entity.
+    ///
+    /// A listener can be used to handle events which would not normally propagate to the entity.
+    /// For example, mouse events when a different entity has captured them. Useful for things like
+    /// closing a popup when clicking outside of its bounding box.
+    pub fn add_listener<F, W>(&mut self, listener: F)
+    where
+        W: View,
+        F: 'static + Fn(&mut W, &mut EventContext, &mut Event),
+    {
+        self.listeners.insert(
+            self.current,
+            Box::new(move |event_handler, context, event| {
+                if let Some(widget) = event_handler.downcast_mut::<W>() {
+                    (listener)(widget, context, event);
+                }
+            }),
+        );
+    }
+
+    /// Adds a global listener to the application.
+    ///
+    /// Global listeners have the first opportunity to handle every event that is sent in an
+    /// application. They will *never* be removed. If you need a listener tied to the lifetime of a
+    /// view, use `add_listener`.
     pub fn add_global_listener<F>(&mut self, listener: F)
     where
         F: 'static + Fn(&mut EventContext, &mut Event),
@@ -547,6 +1268,40 @@ impl Context {
         self.global_listeners.push(Box::new(listener));
     }
 
+    /// Adds a global interceptor to the application, returning an id which can be used to remove it.
+    ///
+    /// Interceptors are called with every event before it's sent to any listeners or dispatched to its target, from
+    /// the highest to the lowest priority, and interceptors with the same priority are called in the order they were
+    /// added. An interceptor can consume an event to stop it from being dispatched, for example to block input outside
+    /// of a tutorial overlay or to handle a global shortcut.
+    ///
+    /// # Example
+    /// ```
+    /// # use vizia_core::prelude::*;
+    /// # let cx = &mut Context::default();
+    /// let interceptor = cx.add_interceptor(10, |_cx, event| {
+    ///     event.map(|window_event, meta| {
+    ///         if let WindowEvent::KeyDown(Code::F1, _) = window_event {
+    ///             meta.consume();
+    ///         }
+    ///     });
+    /// });
+    ///
+    /// cx.remove_interceptor(interceptor);
+    /// ```
+    pub fn add_interceptor<F>(&mut self, priority: i32, interceptor: F) -> InterceptorId
+    where
+        F: 'static + Fn(&mut EventContext, &mut Event),
+    {
+        self.interceptors.add(priority, interceptor)
+    }
+
+    /// Removes a global interceptor added with [`add_interceptor`](Self::add_interceptor), returning whether it was
+    /// found.
+    pub fn remove_interceptor(&mut self, id: InterceptorId) -> bool {
+        self.interceptors.remove(id)
+    }
+
     /// Sets the language used by the application for localization.
     pub fn set_language(&mut self, lang: LanguageIdentifier) {
         let cx = &mut EventContext::new(self);
@@ -559,6 +1314,42 @@ impl Context {
         }
     }
 
+    /// Provides mutable access to the [Environment] model. If the theme or locale is changed, the styles are reloaded
+    /// or the application is relayouted, and every view is sent an [`EnvironmentEvent::ThemeChanged`] or
+    /// [`EnvironmentEvent::LocaleChanged`]. Any views bound to the environment are updated.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vizia_core::prelude::*;
+    /// # let cx = &mut Context::default();
+    /// cx.with_environment(|environment| {
+    ///     environment.theme.app_theme = AppTheme::BuiltIn(ThemeMode::DarkMode);
+    /// });
+    /// ```
+    pub fn with_environment<T>(&mut self, f: impl FnOnce(&mut Environment) -> T) -> T {
+        let mut models = self.models.remove(&Entity::root()).expect("Failed to get environment");
+        let environment = models
+            .get_mut(&TypeId::of::<Environment>())
+            .and_then(|model| model.downcast_mut::<Environment>())
+            .expect("Failed to get environment");
+
+        let previous_locale = environment.locale.clone();
+        let ret = f(environment);
+
+        // Apply the changed settings as the environment does when they're set with an event.
+        let locale = std::mem::replace(&mut environment.locale, previous_locale);
+        let cx = &mut EventContext::new(self);
+        environment.update_locale(cx, locale);
+        environment.update_theme_mode(cx);
+
+        self.models.insert(Entity::root(), models);
+
+        // Update any views bound to the environment.
+        binding_system(self);
+
+        ret
+    }
+
     pub fn add_font_mem(&mut self, data: impl AsRef<[u8]>) {
         // self.text_context.font_system().db_mut().load_font_data(data.as_ref().to_vec());
         self.text_context.asset_provider.register_typeface(
@@ -567,6 +1358,28 @@ impl Context {
         );
     }
 
+    /// Registers a font family which is used for the characters of a script which the font families of a text don't
+    /// cover, or for the characters of every script if `script` is `None`.
+    ///
+    /// Fallback fonts are tried in the order they were registered, after the font families of the text. The color
+    /// emoji font of the platform is registered by default. With the `system-fonts` feature enabled, a system font is
+    /// found for any script without a registered fallback font.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vizia_core::prelude::*;
+    /// # let cx = &mut Context::default();
+    /// cx.add_font_fallback(Some(FontScript::Han), "Noto Sans CJK SC");
+    /// cx.add_font_fallback(None, "Noto Sans");
+    /// ```
+    pub fn add_font_fallback(&mut self, script: Option<FontScript>, family: &str) {
+        self.text_context.font_fallback.add(script, family);
+
+        for entity in self.tree.into_iter() {
+            self.style.needs_text_update(entity);
+        }
+    }
+
     /// Sets the global default font for the application.
     pub fn set_default_font(&mut self, names: &[&str]) {
         self.style.default_font = names
@@ -583,7 +1396,16 @@ impl Context {
         EventContext::new(self).reload_styles().expect("Failed to reload styles");
     }
 
+    /// Add a stylesheet to the application.
+    ///
+    /// With the `hot-reload` feature enabled, stylesheets loaded from a file are watched and the styles are reloaded
+    /// whenever the file is modified, after which an [`EnvironmentEvent::StylesReloaded`] is sent to every view.
     pub fn add_stylesheet(&mut self, style: impl IntoCssStr) -> Result<(), std::io::Error> {
+        #[cfg(feature = "hot-reload")]
+        if let Some(path) = style.path() {
+            self.watch_stylesheet(path.to_owned());
+        }
+
         self.resource_manager.styles.push(Box::new(style));
 
         EventContext::new(self).reload_styles().expect("Failed to reload styles");
@@ -591,6 +1413,77 @@ impl Context {
         Ok(())
     }
 
+    /// Add a stylesheet to the application which only applies while the application is in the given theme mode.
+    ///
+    /// The stylesheets for the current theme mode are applied after all other stylesheets, and are swapped when the
+    /// theme mode changes, such as when the application follows the system theme and the system theme changes.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vizia_core::prelude::*;
+    /// # let cx = &mut Context::default();
+    /// cx.add_stylesheet_for_theme(ThemeMode::LightMode, ":root { background-color: white; }")
+    ///     .expect("Failed to add stylesheet");
+    /// cx.add_stylesheet_for_theme(ThemeMode::DarkMode, ":root { background-color: black; }")
+    ///     .expect("Failed to add stylesheet");
+    /// ```
+    pub fn add_stylesheet_for_theme(
+        &mut self,
+        theme_mode: ThemeMode,
+        style: impl IntoCssStr,
+    ) -> Result<(), std::io::Error> {
+        #[cfg(feature = "hot-reload")]
+        if let Some(path) = style.path() {
+            self.watch_stylesheet(path.to_owned());
+        }
+
+        self.resource_manager.theme_styles.push((theme_mode, Box::new(style)));
+
+        EventContext::new(self).reload_styles().expect("Failed to reload styles");
+
+        Ok(())
+    }
+
+    // Starts polling the modification time of a stylesheet file.
+    #[cfg(feature = "hot-reload")]
+    fn watch_stylesheet(&mut self, path: std::path::PathBuf) {
+        let modified = std::fs::metadata(&path).and_then(|metadata| metadata.modified()).ok();
+        self.watched_stylesheets.push((path, modified));
+
+        if self.stylesheet_watcher.is_none() {
+            let timer = self.add_timer(Duration::from_millis(250), None, |cx, action| {
+                if let TimerAction::Tick(_) = action {
+                    cx.emit_custom(
+                        Event::new(InternalEvent::CheckStylesheets).target(Entity::root()),
+                    );
+                }
+            });
+
+            self.with_current(Entity::root(), |cx| cx.start_timer(timer));
+            self.stylesheet_watcher = Some(timer);
+        }
+    }
+
+    // Reloads the styles if any of the watched stylesheet files have been modified since they were last loaded.
+    #[cfg(feature = "hot-reload")]
+    pub(crate) fn check_stylesheets(&mut self) {
+        let mut changed = false;
+        for (path, modified) in self.watched_stylesheets.iter_mut() {
+            let current = std::fs::metadata(&*path).and_then(|metadata| metadata.modified()).ok();
+            if current.is_some() && current != *modified {
+                *modified = current;
+                changed = true;
+            }
+        }
+
+        if changed {
+            match EventContext::new(self).reload_styles() {
+                Ok(_) => self.emit_to_all(EnvironmentEvent::StylesReloaded),
+                Err(err) => log::warn!("Failed to reload styles: {}", err),
+            }
+        }
+    }
+
     /// Remove all user themes from the application.
     pub fn remove_user_themes(&mut self) {
         self.resource_manager.themes.clear();
@@ -610,6 +1503,71 @@ impl Context {
         self.style.add_animation(animation)
     }
 
+    /// Plays the same animation on each of the given entities, with the start of each animation delayed by
+    /// an additional `per_item_delay` after the previous one, producing a cascade effect.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vizia_core::prelude::*;
+    /// # let cx = &mut Context::default();
+    /// let fade_in = cx.add_animation(
+    ///     AnimationBuilder::new().keyframe(0.0, |key| key.opacity(0.0)).keyframe(1.0, |key| key.opacity(1.0)),
+    /// );
+    ///
+    /// let items = (0..3).map(|i| Label::new(cx, i).entity()).collect::<Vec<_>>();
+    ///
+    /// cx.stagger_animation(fade_in, &items, Duration::from_millis(300), Duration::from_millis(50));
+    /// ```
+    pub fn stagger_animation(
+        &mut self,
+        anim_id: impl AnimId,
+        entities: &[Entity],
+        duration: Duration,
+        per_item_delay: Duration,
+    ) {
+        EventContext::new(self).stagger_animation(anim_id, entities, duration, per_item_delay);
+    }
+
+    /// Plays an animation with the given id on a target view using the given [AnimationOptions].
+    ///
+    /// See [`EventContext::play_animation_with`].
+    pub fn play_animation_with(
+        &mut self,
+        anim_id: impl AnimId,
+        entity: Entity,
+        options: AnimationOptions,
+    ) {
+        EventContext::new(self).play_animation_with(anim_id, entity, options);
+    }
+
+    /// Pauses an animation with the given id playing on a target view.
+    pub fn pause_animation(&mut self, anim_id: impl AnimId, entity: Entity) {
+        EventContext::new(self).pause_animation(anim_id, entity);
+    }
+
+    /// Resumes a paused animation with the given id on a target view.
+    pub fn resume_animation(&mut self, anim_id: impl AnimId, entity: Entity) {
+        EventContext::new(self).resume_animation(anim_id, entity);
+    }
+
+    /// Stops an animation with the given id playing on a target view without calling its completion callback.
+    pub fn cancel_animation(&mut self, anim_id: impl AnimId, entity: Entity) {
+        EventContext::new(self).cancel_animation(anim_id, entity);
+    }
+
+    /// Traps keyboard focus within the subtree of the given view.
+    ///
+    /// See [`EventContext::push_focus_scope`].
+    pub fn push_focus_scope(&mut self, entity: Entity) {
+        EventContext::new(self).push_focus_scope(entity);
+    }
+
+    /// Closes the most recently pushed focus scope, returning focus to the view which was focused when it was
+    /// pushed.
+    pub fn pop_focus_scope(&mut self) {
+        EventContext::new(self).pop_focus_scope();
+    }
+
     pub fn set_image_loader<F: 'static + Fn(&mut ResourceContext, &str)>(&mut self, loader: F) {
         self.resource_manager.image_loader = Some(Box::new(loader));
     }
@@ -626,6 +1584,10 @@ impl Context {
     ///
     /// Returns a `Timer` id which can be used to start and stop the timer.  
     ///
+    /// Timers are driven by the event loop of the windowing backend, so blinking carets, polling, debouncing, and
+    /// dismissing views after a delay don't need a separate thread. A running timer can be cancelled at any time with
+    /// `stop_timer()`.
+    ///
     /// # Example
     /// Creates a timer which calls the provided callback every second for 5 seconds:
     /// ```rust
@@ -654,25 +1616,15 @@ impl Context {
         duration: Option<Duration>,
         callback: impl Fn(&mut EventContext, TimerAction) + 'static,
     ) -> Timer {
-        let id = Timer(self.timers.len());
-        self.timers.push(TimerState {
-            entity: Entity::root(),
-            id,
-            time: Instant::now(),
-            interval,
-            duration,
-            start_time: Instant::now(),
-            callback: Rc::new(callback),
-            ticking: false,
-            stopping: false,
-        });
-
-        id
+        TimerState::add(&mut self.timers, interval, duration, Rc::new(callback))
     }
 
     /// Starts a timer with the provided timer id.
     ///
     /// Events sent within the timer callback provided in `add_timer()` will target the current view.
+    ///
+    /// Starting a timer which is already running restarts it, so a timer can be used to debounce an action by
+    /// starting it on each input and handling the `TimerAction::Stop` of a timer with equal interval and duration.
     pub fn start_timer(&mut self, timer: Timer) {
         let current = self.current;
         if !self.timer_is_running(timer) {
@@ -691,18 +1643,44 @@ impl Context {
         });
     }
 
-    /// Modifies the state of an existing timer with the provided `Timer` id.
-    pub fn modify_timer(&mut self, timer: Timer, timer_function: impl Fn(&mut TimerState)) {
-        while let Some(next_timer_state) = self.running_timers.peek() {
-            if next_timer_state.id == timer {
-                let mut timer_state = self.running_timers.pop().unwrap();
+    /// Sets the interval at which the text caret of a textbox toggles between visible and hidden. Defaults to 530ms.
+    ///
+    /// The caret remains solid while the user is typing or moving the cursor, and resumes blinking after one interval.
+    pub fn set_caret_blink_rate(&mut self, rate: Duration) {
+        let caret_timer = self.environment().caret_timer;
 
-                (timer_function)(&mut timer_state);
+        for timer_state in
+            self.timers.iter_mut().filter(|timer_state| timer_state.id == caret_timer)
+        {
+            timer_state.interval = rate;
+        }
 
-                self.running_timers.push(timer_state);
+        self.running_timers = self
+            .running_timers
+            .drain()
+            .map(|mut timer_state| {
+                if timer_state.id == caret_timer {
+                    timer_state.interval = rate;
+                }
+                timer_state
+            })
+            .collect();
+    }
 
-                return;
+    /// Modifies the state of an existing timer with the provided `Timer` id.
+    pub fn modify_timer(&mut self, timer: Timer, timer_function: impl Fn(&mut TimerState)) {
+        if self.timer_is_running(timer) {
+            // The heap is rebuilt because the timer may not be the next to tick and its time may change.
+            let mut running_timers = std::mem::take(&mut self.running_timers).into_vec();
+            for timer_state in
+                running_timers.iter_mut().filter(|timer_state| timer_state.id == timer)
+            {
+                (timer_function)(timer_state);
             }
+
+            self.running_timers = running_timers.into();
+
+            return;
         }
 
         for pending_timer in self.timers.iter_mut() {
@@ -742,9 +1720,25 @@ impl Context {
             running_timers.drain().filter(|timer_state| timer_state.id != timer).collect();
     }
 
+    /// Removes a timer added with `add_timer()`, stopping it without calling its callback if it's running.
+    ///
+    /// The callback of the timer is dropped and its id may be reused by a timer added later, so a view which adds a
+    /// timer should remove it when the view is destroyed.
+    pub fn remove_timer(&mut self, timer: Timer) {
+        self.running_timers.retain(|timer_state| timer_state.id != timer);
+
+        if let Some(timer_state) = self.timers.get_mut(timer.0) {
+            timer_state.remove();
+        }
+    }
+
     // Tick all timers.
     pub(crate) fn tick_timers(&mut self) {
-        let now = Instant::now();
+        self.tick_timers_at(Instant::now());
+    }
+
+    // Tick all timers which are due at the given time, which lets tests advance time without waiting.
+    pub(crate) fn tick_timers_at(&mut self, now: Instant) {
         while let Some(next_timer_state) = self.running_timers.peek() {
             if next_timer_state.time <= now {
                 let mut timer_state = self.running_timers.pop().unwrap();
@@ -777,6 +1771,11 @@ impl Context {
     }
 
     pub fn load_image(&mut self, path: &str, data: &'static [u8], policy: ImageRetentionPolicy) {
+        if is_svg(data) {
+            self.replace_svg(path, data, policy);
+            return;
+        }
+
         let id = if let Some(image_id) = self.resource_manager.image_ids.get(path) {
             *image_id
         } else {
@@ -808,9 +1807,83 @@ impl Context {
         }
     }
 
+    /// Loads an image from a file path on a separate thread, showing a placeholder until the image has been decoded.
+    ///
+    /// With the `reqwest` feature enabled, paths starting with `http://` or `https://` are fetched over the network.
+    /// If the image fails to load the placeholder remains in place.
+    pub fn load_image_async(&mut self, path: &str, policy: ImageRetentionPolicy) {
+        if self.resource_manager.image_ids.contains_key(path) {
+            return;
+        }
+
+        let id = self.resource_manager.image_id_manager.create();
+        self.resource_manager.image_ids.insert(path.to_owned(), id);
+
+        let placeholder = match &self.resource_manager.images[&ImageId::root()].image {
+            ImageOrSvg::Image(image) => image.clone(),
+            ImageOrSvg::Svg(_) => unreachable!(),
+        };
+
+        self.resource_manager.images.insert(
+            id,
+            StoredImage {
+                image: ImageOrSvg::Image(placeholder),
+                retention_policy: policy,
+                used: true,
+                dirty: false,
+                observers: HashSet::new(),
+            },
+        );
+
+        let path = path.to_owned();
+        self.spawn(move |cx| {
+            let data = if path.starts_with("http://") || path.starts_with("https://") {
+                #[cfg(feature = "reqwest")]
+                {
+                    reqwest::blocking::get(&path)
+                        .and_then(|response| response.bytes())
+                        .map(|bytes| bytes.to_vec())
+                        .ok()
+                }
+
+                #[cfg(not(feature = "reqwest"))]
+                None
+            } else {
+                std::fs::read(&path).ok()
+            };
+
+            match data {
+                Some(data) => {
+                    let _ = cx.load_image(path, &data, policy);
+                }
+
+                None => debug!("Failed to load image: {}", path),
+            }
+        });
+    }
+
+    // Parses an SVG document and stores it, replacing any image previously loaded with the same path.
+    pub(crate) fn replace_svg(&mut self, path: &str, data: &[u8], policy: ImageRetentionPolicy) {
+        match svg::Dom::from_bytes(data, self.text_context.default_font_manager.clone()) {
+            Ok(svg) => ResourceContext::new(self).load_svg(path.to_owned(), svg, policy),
+            Err(_) => debug!("Failed to parse SVG image: {}", path),
+        }
+    }
+
+    /// Loads an SVG image with the given name, unless an SVG image with the same name has already been loaded.
+    ///
+    /// A raster image with the same name, such as the placeholder shown while an image is loaded asynchronously, is
+    /// replaced by the SVG image.
     pub fn load_svg(&mut self, path: &str, data: &[u8], policy: ImageRetentionPolicy) -> ImageId {
         let id = if let Some(image_id) = self.resource_manager.image_ids.get(path) {
-            return *image_id;
+            if matches!(
+                self.resource_manager.images.get(image_id).map(|stored| &stored.image),
+                Some(ImageOrSvg::Svg(_))
+            ) {
+                return *image_id;
+            }
+
+            *image_id
         } else {
             let id = self.resource_manager.image_id_manager.create();
             self.resource_manager.image_ids.insert(path.to_owned(), id);
@@ -853,6 +1926,55 @@ impl Context {
         std::thread::spawn(move || target(&mut cxp));
     }
 
+    /// Spawns a thread which runs the future returned by `target` to completion, providing a [ContextProxy] for
+    /// sending events back to the main UI thread.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vizia_core::prelude::*;
+    /// # let cx = &mut Context::default();
+    /// # enum AppEvent { Loaded(String) }
+    /// # async fn fetch() -> String { String::new() }
+    /// cx.spawn_async(|mut proxy| async move {
+    ///     let text = fetch().await;
+    ///     proxy.emit(AppEvent::Loaded(text)).expect("Failed to send event");
+    /// });
+    /// ```
+    pub fn spawn_async<F, Fut>(&self, target: F)
+    where
+        F: 'static + Send + FnOnce(ContextProxy) -> Fut,
+        Fut: std::future::Future<Output = ()>,
+    {
+        self.get_proxy().spawn_async(target);
+    }
+
+    /// Runs a job on the shared pool of background threads, returning a [JobHandle] used to handle its result on the
+    /// main thread with [`on_complete`](JobHandle::on_complete).
+    ///
+    /// Unlike [`spawn`](Context::spawn), which starts a new thread for each call, jobs share a pool with a thread for
+    /// each available core. The job is tied to the current view: if the view is removed before the job completes, the
+    /// job is cancelled and its result is dropped. Jobs which complete are only handled when the context has an event
+    /// proxy, which is provided by the windowing backends. The job is passed the [CancellationToken] of the job, which
+    /// long running jobs can check to stop early.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vizia_core::prelude::*;
+    /// # let cx = &mut Context::default();
+    /// # enum AppEvent { Loaded(usize) }
+    /// # fn count_lines() -> usize { 0 }
+    /// cx.spawn_job(|_token| count_lines())
+    ///     .on_complete(|cx, lines| cx.emit(AppEvent::Loaded(lines)));
+    /// ```
+    pub fn spawn_job<T, F>(&mut self, job: F) -> JobHandle<'_, T>
+    where
+        T: 'static + Send,
+        F: 'static + Send + FnOnce(&CancellationToken) -> T,
+    {
+        let event_proxy = self.event_proxy.as_ref().map(|p| p.make_clone());
+        self.jobs.spawn(self.current, event_proxy, job)
+    }
+
     pub fn get_proxy(&self) -> ContextProxy {
         ContextProxy {
             current: self.current,
@@ -860,11 +1982,76 @@ impl Context {
         }
     }
 
+    /// Returns the entities which match a CSS selector, such as `.my-class button:checked`, in tree order.
+    ///
+    /// The selector is matched against the current classes, ids and pseudo-classes of the views, in the same way as
+    /// the selectors of style rules. Returns an empty list if the selector cannot be parsed.
+    ///
+    /// # Example
+    /// ```
+    /// # use vizia_core::prelude::*;
+    /// # let cx = &mut Context::default();
+    /// let checked = cx.query("checkbox:checked");
+    /// ```
+    pub fn query(&self, selector: &str) -> Vec<Entity> {
+        match vizia_style::parse_selector_list(selector) {
+            Ok(selectors) => query_selector(&selectors, &self.style, &self.tree),
+            Err(err) => {
+                log::warn!("Failed to parse selector '{}': {:?}", selector, err);
+                Vec::new()
+            }
+        }
+    }
+
     /// Finds the entity that identifier identifies
     pub fn resolve_entity_identifier(&self, identity: &str) -> Option<Entity> {
         self.entity_identifiers.get(identity).cloned()
     }
 
+    /// Returns a path which can be used to address an entity across rebuilds of the application, e.g. `root > hstack:nth(0) > button#submit`.
+    ///
+    /// Each segment of the path is the element name of a view followed by either its id, if no sibling with the same
+    /// element name shares the id, or its index among the siblings with the same element name. Views without an element
+    /// name are given the name `view`. Any `>`, `#` or `\` characters in an id are escaped with a backslash. The path can
+    /// be resolved back to an entity with [`entity_at_path`](Context::entity_at_path).
+    pub fn entity_path(&self, entity: Entity) -> String {
+        entity_path(&self.tree, &self.style, &self.views, entity)
+    }
+
+    /// Resolves a path produced by [`entity_path`](Context::entity_path) to the entity it addresses.
+    ///
+    /// Returns `None` if the path is malformed or no longer matches an entity in the tree.
+    pub fn entity_at_path(&self, path: &str) -> Option<Entity> {
+        let mut segments = parse_entity_path(path)?.into_iter();
+
+        if segments.next()? != (String::from("root"), None) {
+            return None;
+        }
+
+        let mut current = Entity::root();
+
+        for (segment, id) in segments {
+            let mut children = ChildIterator::new(&self.tree, current);
+
+            current = if let Some(id) = id {
+                children.find(|child| {
+                    self.element_name(*child) == segment
+                        && self.style.ids.get(*child).is_some_and(|child_id| *child_id == id)
+                })?
+            } else {
+                let (name, index) = segment.strip_suffix(')')?.split_once(":nth(")?;
+                let index = index.parse::<usize>().ok()?;
+                children.filter(|child| self.element_name(*child) == name).nth(index)?
+            };
+        }
+
+        Some(current)
+    }
+
+    fn element_name(&self, entity: Entity) -> &'static str {
+        element_name(&self.views, entity)
+    }
+
     /// Toggles the addition/removal of a class name for the current view.
     ///
     /// # Example
@@ -888,13 +2075,193 @@ impl Context {
             self.style.classes.insert(current, class_list);
         }
 
-        self.style.needs_restyle(self.current);
+        self.style.invalidate_class(&self.tree, current, class_name);
     }
+
+    /// Adds a class to an entity for the duration of the closure, then removes it.
+    ///
+    /// The views affected by the class are invalidated when it is added and again when it is removed, so styles
+    /// computed within the closure see the class and are recomputed once it returns. If the entity already has the
+    /// class it is left in place and nothing is invalidated.
+    pub fn with_style_class_scope<T>(
+        &mut self,
+        entity: Entity,
+        class_name: &str,
+        f: impl FnOnce(&mut Context) -> T,
+    ) -> T {
+        let added = self.style.add_class(entity, class_name);
+        if added {
+            self.style.invalidate_class(&self.tree, entity, class_name);
+        }
+
+        let result = f(self);
+
+        if added && self.style.remove_class(entity, class_name) {
+            self.style.invalidate_class(&self.tree, entity, class_name);
+        }
+
+        result
+    }
+
+    /// Adds a class to an entity for the given duration, after which it is removed.
+    ///
+    /// Calling this again for the same entity and class before the duration has elapsed extends the existing scope
+    /// rather than removing the class early. This is useful for transient effects such as flashing a view when its
+    /// value changes. If the entity already has the class it is left in place once the duration has elapsed.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use vizia_core::prelude::*;
+    /// # let cx = &mut Context::default();
+    /// let entity = Element::new(cx).entity();
+    /// cx.add_scoped_class(entity, "highlight", Duration::from_millis(300));
+    /// ```
+    pub fn add_scoped_class(&mut self, entity: Entity, class_name: &str, duration: Duration) {
+        let added = match self.scoped_classes.remove(&(entity, class_name.to_string())) {
+            Some((handle, added)) => {
+                self.cancel_scheduled(handle);
+                added
+            }
+
+            None => self.style.add_class(entity, class_name),
+        };
+
+        if added {
+            self.style.invalidate_class(&self.tree, entity, class_name);
+        }
+
+        let handle = self.schedule_emit_custom(
+            Event::new(InternalEvent::RemoveScopedClass { entity, class: class_name.to_string() })
+                .target(Entity::root()),
+            Instant::now() + duration,
+        );
+
+        self.scoped_classes.insert((entity, class_name.to_string()), (handle, added));
+    }
+}
+
+/// Returns the path of an entity, as described by [`Context::entity_path`].
+fn entity_path(tree: &Tree<Entity>, style: &Style, views: &Views, entity: Entity) -> String {
+    let mut segments = Vec::new();
+    let mut current = entity;
+
+    while let Some(parent) = tree.get_parent(current) {
+        let name = element_name(views, current);
+        let siblings = ChildIterator::new(&tree, parent)
+            .filter(|sibling| element_name(views, *sibling) == name)
+            .collect::<Vec<_>>();
+
+        let id = style.ids.get(current).filter(|id| {
+            siblings.iter().filter(|sibling| style.ids.get(**sibling) == Some(*id)).count() == 1
+        });
+
+        if let Some(id) = id {
+            segments.push(format!("{}#{}", name, escape_path_id(id)));
+        } else {
+            let index = siblings.iter().position(|sibling| *sibling == current).unwrap_or(0);
+            segments.push(format!("{}:nth({})", name, index));
+        }
+
+        current = parent;
+    }
+
+    segments.push(String::from("root"));
+    segments.reverse();
+
+    segments.join(" > ")
+}
+
+/// Returns the element name of the view attached to an entity, used when computing entity paths.
+fn element_name(views: &Views, entity: Entity) -> &'static str {
+    views.get(&entity).and_then(|view| view.element()).unwrap_or("view")
+}
+
+/// Returns the key of a model built with [`build_persistent`](crate::model::PersistentModel::build_persistent), made
+/// of the path of the entity it is attached to and its type name.
+#[cfg(feature = "serde")]
+fn model_key(
+    tree: &Tree<Entity>,
+    style: &Style,
+    views: &Views,
+    entity: Entity,
+    name: &str,
+) -> String {
+    format!("{} :: {}", entity_path(tree, style, views, entity), name)
+}
+
+/// Escapes the characters of an id which have a special meaning in an entity path.
+fn escape_path_id(id: &str) -> String {
+    let mut escaped = String::with_capacity(id.len());
+    for c in id.chars() {
+        if matches!(c, '>' | '#' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Splits an entity path into its segments at each unescaped `>`. Each segment is split at the first unescaped `#`
+/// into the part before it and the unescaped id after it, if there is one. Returns `None` if the path ends with an
+/// incomplete escape.
+fn parse_entity_path(path: &str) -> Option<Vec<(String, Option<String>)>> {
+    let mut segments = Vec::new();
+    let mut segment = String::new();
+    let mut id = None;
+
+    let mut chars = path.chars();
+    while let Some(c) = chars.next() {
+        let in_id = id.is_some();
+        let target = match &mut id {
+            Some(id) => id,
+            None => &mut segment,
+        };
+
+        match c {
+            '\\' => target.push(chars.next()?),
+            '>' => {
+                let id = id.take().map(|id: String| id.trim().to_string());
+                segments.push((segment.trim().to_string(), id));
+                segment.clear();
+            }
+            '#' if !in_id => id = Some(String::new()),
+            _ => target.push(c),
+        }
+    }
+
+    let id = id.map(|id| id.trim().to_string());
+    segments.push((segment.trim().to_string(), id));
+
+    Some(segments)
 }
 
 pub(crate) enum InternalEvent {
     Redraw,
-    LoadImage { path: String, image: Mutex<Option<skia_safe::Image>>, policy: ImageRetentionPolicy },
+    CaptureHover(Option<Entity>),
+    // Recomputes the hovered view of the window of the origin, after the hit-testing of a view changed.
+    Rehover,
+    RepeatKey,
+    #[cfg(feature = "hot-reload")]
+    CheckStylesheets,
+    RemoveScopedClass {
+        entity: Entity,
+        class: String,
+    },
+    LoadImage {
+        path: String,
+        image: Mutex<Option<skia_safe::Image>>,
+        policy: ImageRetentionPolicy,
+    },
+    // SVG documents can't be sent between threads, so the data is parsed on the main thread.
+    LoadSvg {
+        path: String,
+        data: Vec<u8>,
+        policy: ImageRetentionPolicy,
+    },
+    JobComplete {
+        id: u64,
+        result: Box<dyn Any + Send>,
+    },
 }
 
 pub struct LocalizationContext<'a> {
@@ -0,0 +1,264 @@
+use std::any::TypeId;
+use std::io::{Error, ErrorKind};
+use std::path::PathBuf;
+
+use hashbrown::HashMap;
+
+use super::Models;
+use crate::entity::Entity;
+use crate::model::{ModelSerializer, PersistentModel};
+
+type State = serde_json::Map<String, serde_json::Value>;
+
+// A model registered with `PersistentModel::build_persistent` or `Context::persist`.
+struct PersistedModel {
+    // The key given to `Context::persist`. Models built with `build_persistent` are keyed by their entity path instead.
+    key: Option<String>,
+    serializer: ModelSerializer,
+}
+
+// The models which are serialized with `Context::serialize_models` and saved to the state file.
+#[derive(Default)]
+pub(crate) struct Persistence {
+    models: HashMap<(Entity, TypeId), PersistedModel>,
+    path: Option<PathBuf>,
+}
+
+impl Persistence {
+    // Registers the model of type `M` attached to the given entity, replacing any model registered with the same key.
+    pub(crate) fn register<M: PersistentModel>(&mut self, entity: Entity, key: Option<String>) {
+        if key.is_some() {
+            self.models.retain(|_, model| model.key != key);
+        }
+
+        self.models.insert(
+            (entity, TypeId::of::<M>()),
+            PersistedModel { key, serializer: ModelSerializer::new::<M>() },
+        );
+    }
+
+    // Drops the registrations of the models attached to an entity which is being removed.
+    pub(crate) fn remove_entity(&mut self, entity: Entity) {
+        self.models.retain(|(model_entity, _), _| *model_entity != entity);
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.models.is_empty()
+    }
+
+    // Returns the key of each registered model, using `model_key` for the models which weren't given a key.
+    fn keyed(
+        &self,
+        model_key: impl Fn(Entity, &str) -> String,
+    ) -> Vec<(Entity, TypeId, &ModelSerializer, String)> {
+        self.models
+            .iter()
+            .map(|(&(entity, type_id), model)| {
+                let key =
+                    model.key.clone().unwrap_or_else(|| model_key(entity, model.serializer.name));
+                (entity, type_id, &model.serializer, key)
+            })
+            .collect()
+    }
+
+    // Serializes the registered models into a map of their keys to their state.
+    pub(crate) fn serialize(
+        &self,
+        models: &Models,
+        model_key: impl Fn(Entity, &str) -> String,
+    ) -> State {
+        let mut state = State::new();
+
+        for (entity, type_id, serializer, key) in self.keyed(model_key) {
+            let value = models
+                .get(&entity)
+                .and_then(|models| models.get(&type_id))
+                .and_then(|model| (serializer.serialize)(model.as_ref()));
+
+            if let Some(value) = value {
+                state.insert(key, value);
+            }
+        }
+
+        state
+    }
+
+    // Replaces the registered models, or only the model with the given key, with their state. Returns the number of
+    // models restored.
+    pub(crate) fn restore(
+        &self,
+        models: &mut Models,
+        state: &State,
+        model_key: impl Fn(Entity, &str) -> String,
+        only: Option<&str>,
+    ) -> usize {
+        let mut restored = 0;
+
+        for (entity, type_id, serializer, key) in self.keyed(model_key) {
+            if only.is_some_and(|only| only != key) {
+                continue;
+            }
+
+            let Some(models) =
+                models.get_mut(&entity).filter(|models| models.contains_key(&type_id))
+            else {
+                continue;
+            };
+
+            if let Some(model) = state.get(&key).cloned().and_then(serializer.deserialize) {
+                models.insert(type_id, model);
+                restored += 1;
+            }
+        }
+
+        restored
+    }
+
+    pub(crate) fn set_path(&mut self, path: PathBuf) {
+        self.path = Some(path);
+    }
+
+    // Returns the path of the state file, which defaults to `state.json` within a directory named after the
+    // executable in the platform config directory.
+    pub(crate) fn path(&self) -> Result<PathBuf, Error> {
+        if let Some(path) = &self.path {
+            return Ok(path.clone());
+        }
+
+        let config_dir = dirs::config_dir()
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, "no config directory found"))?;
+        let name = std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.file_stem().map(|stem| stem.to_string_lossy().to_string()))
+            .unwrap_or_else(|| String::from("vizia"));
+
+        Ok(config_dir.join(name).join("state.json"))
+    }
+
+    // Reads the saved state, which is empty if nothing has been saved yet.
+    fn read(&self) -> Result<State, Error> {
+        match std::fs::read(self.path()?) {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(State::new()),
+            Err(err) => Err(err),
+        }
+    }
+
+    // Writes the registered models to the state file, keeping the saved state of any models which aren't registered.
+    // A state file which can't be read is left in place rather than overwritten.
+    pub(crate) fn save(
+        &self,
+        models: &Models,
+        model_key: impl Fn(Entity, &str) -> String,
+    ) -> Result<(), Error> {
+        let mut state = self.read()?;
+        state.extend(self.serialize(models, model_key));
+
+        let path = self.path()?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        std::fs::write(path, serde_json::to_vec_pretty(&state)?)
+    }
+
+    // Replaces the registered models, or only the model with the given key, with their saved state. Returns the
+    // number of models restored.
+    pub(crate) fn load(
+        &self,
+        models: &mut Models,
+        model_key: impl Fn(Entity, &str) -> String,
+        only: Option<&str>,
+    ) -> Result<usize, Error> {
+        let state = self.read()?;
+        Ok(self.restore(models, &state, model_key, only))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::context::test_support::window_context;
+    use crate::prelude::*;
+
+    #[derive(Lens, serde::Serialize, serde::Deserialize)]
+    struct Settings {
+        volume: u32,
+    }
+
+    impl Model for Settings {}
+
+    fn volume(cx: &Context) -> u32 {
+        cx.data::<Settings>().unwrap().volume
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let path = std::env::temp_dir()
+            .join(format!("vizia-persistence-{}", std::process::id()))
+            .join("state.json");
+
+        let mut cx = window_context(400, 300);
+        cx.0.set_state_path(&path);
+        Settings { volume: 7 }.build(cx.context());
+        cx.0.persist::<Settings>("settings");
+        cx.0.save_state().unwrap();
+
+        // A rebuilt model is restored from the saved state when it is registered.
+        let mut cx = window_context(400, 300);
+        cx.0.set_state_path(&path);
+        Settings { volume: 0 }.build(cx.context());
+        cx.0.persist::<Settings>("settings");
+        assert_eq!(volume(&cx.0), 7);
+
+        // Registering the model again under the same key replaces the registration.
+        cx.0.persist::<Settings>("settings");
+        assert_eq!(cx.0.persistence.models.len(), 1);
+
+        std::fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn built_and_persisted_models_are_saved_together() {
+        let path = std::env::temp_dir()
+            .join(format!("vizia-persistence-shared-{}", std::process::id()))
+            .join("state.json");
+
+        let mut cx = window_context(400, 300);
+        cx.0.set_state_path(&path);
+        VStack::new(cx.context(), |cx| Settings { volume: 3 }.build_persistent(cx));
+        Settings { volume: 5 }.build(cx.context());
+        cx.0.persist::<Settings>("settings");
+
+        // Models built with `build_persistent` and models registered with `persist` are serialized and saved alike.
+        let state = cx.0.serialize_models();
+        assert_eq!(state.len(), 2);
+        assert_eq!(state["settings"], serde_json::json!({ "volume": 5 }));
+
+        cx.0.save_state().unwrap();
+        let saved: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(saved, state);
+
+        // A state file which can't be read is left in place rather than overwritten.
+        std::fs::write(&path, "not json").unwrap();
+        assert!(cx.0.save_state().is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "not json");
+
+        std::fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn removed_models_are_unregistered() {
+        let mut cx = window_context(400, 300);
+
+        let stack = VStack::new(cx.context(), |cx| {
+            Settings { volume: 1 }.build(cx);
+            cx.persist::<Settings>("settings");
+        })
+        .entity();
+        assert!(!cx.0.persistence.is_empty());
+
+        cx.0.remove(stack);
+        assert!(cx.0.persistence.is_empty());
+    }
+}
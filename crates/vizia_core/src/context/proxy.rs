@@ -1,8 +1,11 @@
 use std::any::Any;
 use std::fmt::Formatter;
-use std::sync::Mutex;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::task::{Poll, Wake, Waker};
 
 use super::InternalEvent;
+use crate::resource::is_svg;
 
 use crate::prelude::*;
 
@@ -42,6 +45,7 @@ impl std::fmt::Display for ProxyEmitError {
 impl std::error::Error for ProxyEmitError {}
 
 impl ContextProxy {
+    /// Sends an event containing the message up the tree from the entity the proxy was created from.
     pub fn emit<M: Any + Send>(&mut self, message: M) -> Result<(), ProxyEmitError> {
         if let Some(proxy) = &self.event_proxy {
             let event = Event::new(message)
@@ -55,6 +59,8 @@ impl ContextProxy {
         }
     }
 
+    /// Sends an event containing the message directly to the target entity, such as an entity other than the one the
+    /// proxy was created from.
     pub fn emit_to<M: Any + Send>(
         &mut self,
         target: Entity,
@@ -72,17 +78,56 @@ impl ContextProxy {
         }
     }
 
+    /// Requests that the window containing the entity the proxy was created from is redrawn, for example after
+    /// updating data which is read by a custom view when drawing.
     pub fn redraw(&mut self) -> Result<(), ProxyEmitError> {
         self.emit(InternalEvent::Redraw)
     }
 
+    /// Sends a request to the main UI thread and returns a [Response] which resolves to the reply.
+    ///
+    /// The `message` closure receives the [Responder] used to reply and returns the message to send, which is emitted
+    /// up the tree like [`emit`](ContextProxy::emit). The handler of the message replies by calling
+    /// [`Responder::respond`]. The response can be awaited within [`spawn_async`](ContextProxy::spawn_async) or waited
+    /// for with [`Response::wait`], and resolves to `None` if the responder is dropped without replying.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vizia_core::prelude::*;
+    /// # let cx = &mut Context::default();
+    /// pub enum AppEvent {
+    ///     Confirm(String, Responder<bool>),
+    /// }
+    ///
+    /// cx.spawn(|proxy| {
+    ///     let response = proxy
+    ///         .request(|responder| AppEvent::Confirm("Overwrite file?".to_string(), responder))
+    ///         .expect("Failed to send request");
+    ///
+    ///     if response.wait() == Some(true) {
+    ///         // Overwrite the file
+    ///     }
+    /// });
+    /// ```
+    pub fn request<R: Send + 'static, M: Any + Send>(
+        &mut self,
+        message: impl FnOnce(Responder<R>) -> M,
+    ) -> Result<Response<R>, ProxyEmitError> {
+        let shared = Arc::new(Mutex::new(Shared { value: None, waker: None, closed: false }));
+        self.emit(message(Responder { shared: shared.clone() }))?;
+        Ok(Response { shared })
+    }
+
     pub fn load_image(
         &mut self,
         path: String,
         data: &[u8],
         policy: ImageRetentionPolicy,
     ) -> Result<(), ProxyEmitError> {
-        if let Some(image) = skia_safe::Image::from_encoded(skia_safe::Data::new_copy(data)) {
+        if is_svg(data) {
+            self.emit(InternalEvent::LoadSvg { path, data: data.to_vec(), policy })?
+        } else if let Some(image) = skia_safe::Image::from_encoded(skia_safe::Data::new_copy(data))
+        {
             self.emit(InternalEvent::LoadImage { path, image: Mutex::new(Some(image)), policy })?
         }
 
@@ -96,6 +141,106 @@ impl ContextProxy {
         let mut cxp = self.clone();
         std::thread::spawn(move || target(&mut cxp));
     }
+
+    /// Spawns a thread which runs the future returned by `target` to completion, providing a [ContextProxy] for
+    /// sending events back to the main UI thread.
+    ///
+    /// The future is polled by a minimal executor on the spawned thread, so futures which rely on a specific async
+    /// runtime, such as tokio, should be run within that runtime instead.
+    pub fn spawn_async<F, Fut>(&self, target: F)
+    where
+        F: 'static + Send + FnOnce(ContextProxy) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        let cxp = self.clone();
+        std::thread::spawn(move || block_on(target(cxp)));
+    }
+}
+
+// The state shared by a responder and its response.
+struct Shared<R> {
+    value: Option<R>,
+    waker: Option<Waker>,
+    // Whether the responder has replied or been dropped.
+    closed: bool,
+}
+
+/// Used to reply to a request sent with [`ContextProxy::request`].
+pub struct Responder<R> {
+    shared: Arc<Mutex<Shared<R>>>,
+}
+
+impl<R> Responder<R> {
+    /// Replies to the request, resolving the [Response] of the sender.
+    pub fn respond(self, value: R) {
+        self.shared.lock().unwrap().value = Some(value);
+    }
+}
+
+impl<R> Drop for Responder<R> {
+    fn drop(&mut self) {
+        let mut shared = self.shared.lock().unwrap();
+        shared.closed = true;
+        if let Some(waker) = shared.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl<R> std::fmt::Debug for Responder<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Responder")
+    }
+}
+
+/// The reply to a request sent with [`ContextProxy::request`], which is a future resolving to the reply, or to `None`
+/// if the request was dropped without a reply.
+pub struct Response<R> {
+    shared: Arc<Mutex<Shared<R>>>,
+}
+
+impl<R> Response<R> {
+    /// Blocks the current thread until the reply is received.
+    pub fn wait(self) -> Option<R> {
+        block_on(self)
+    }
+}
+
+impl<R> Future for Response<R> {
+    type Output = Option<R>;
+
+    fn poll(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        let mut shared = self.shared.lock().unwrap();
+        if shared.closed {
+            Poll::Ready(shared.value.take())
+        } else {
+            shared.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+// Wakes a thread which is parked while waiting for a future.
+struct ThreadWaker(std::thread::Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+// Polls a future on the current thread until it completes, parking the thread while the future is pending.
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = std::pin::pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+    let mut cx = std::task::Context::from_waker(&waker);
+
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => std::thread::park(),
+        }
+    }
 }
 
 impl Clone for ContextProxy {
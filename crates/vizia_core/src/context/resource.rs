@@ -31,6 +31,11 @@ impl<'a> ResourceContext<'a> {
         }
     }
 
+    /// Returns the scale factor, which can be used by an image loader to load images at a suitable resolution.
+    pub fn scale_factor(&self) -> f32 {
+        self.style.scale_factor()
+    }
+
     /// Executes the given closure in a spawned thread.
     pub fn spawn<F>(&self, target: F)
     where
@@ -51,21 +56,40 @@ impl<'a> ResourceContext<'a> {
         image: skia_safe::Image,
         policy: ImageRetentionPolicy,
     ) {
+        self.store_image(path, ImageOrSvg::Image(image), policy);
+    }
+
+    /// Loads the provided SVG document into the resource manager.
+    ///
+    /// SVG images are drawn as vectors, so they remain sharp at any size and scale factor.
+    pub fn load_svg(
+        &mut self,
+        path: String,
+        svg: skia_safe::svg::Dom,
+        policy: ImageRetentionPolicy,
+    ) {
+        self.store_image(path, ImageOrSvg::Svg(svg), policy);
+    }
+
+    // Stores an image, replacing any image previously loaded with the same path.
+    fn store_image(&mut self, path: String, image: ImageOrSvg, policy: ImageRetentionPolicy) {
         let id = if let Some(image_id) = self.resource_manager.image_ids.get(&path) {
             *image_id
         } else {
-            self.resource_manager.image_id_manager.create()
+            let id = self.resource_manager.image_id_manager.create();
+            self.resource_manager.image_ids.insert(path, id);
+            id
         };
 
         match self.resource_manager.images.entry(id) {
             Entry::Occupied(mut occ) => {
-                occ.get_mut().image = ImageOrSvg::Image(image);
+                occ.get_mut().image = image;
                 occ.get_mut().dirty = true;
                 occ.get_mut().retention_policy = policy;
             }
             Entry::Vacant(vac) => {
                 vac.insert(StoredImage {
-                    image: ImageOrSvg::Image(image),
+                    image,
                     retention_policy: policy,
                     used: true,
                     dirty: false,
@@ -0,0 +1,49 @@
+//! Helpers for testing views and systems without a windowing backend.
+
+use vizia_window::WindowDescription;
+
+use super::backend::BackendContext;
+use crate::events::EventManager;
+use crate::prelude::*;
+
+/// Creates a context with a root window of the given logical size, as a windowing backend would.
+pub(crate) fn window_context(width: u32, height: u32) -> BackendContext {
+    let mut cx = BackendContext::new(Context::new());
+    #[cfg(feature = "clipboard")]
+    {
+        cx.0.clipboard = super::Clipboard::local();
+    }
+
+    let window_description = WindowDescription::new().with_inner_size(width, height);
+    cx.add_main_window(Entity::root(), &window_description, 1.0);
+    cx.0.windows.insert(
+        Entity::root(),
+        WindowState { window_description, scale_factor: 1.0, ..Default::default() },
+    );
+    cx.set_window_size(Entity::root(), width as f32, height as f32);
+
+    cx
+}
+
+/// Clears the redraw state of the windows, as drawing them would.
+pub(crate) fn draw(cx: &mut BackendContext) {
+    for window_state in cx.0.windows.values_mut() {
+        window_state.redraw_list.clear();
+        window_state.dirty_rect = None;
+        window_state.dirty_regions.clear();
+    }
+}
+
+/// Dispatches any queued events and then runs the style, animation, and layout systems, as the event loop does for
+/// each frame.
+pub(crate) fn update(cx: &mut BackendContext) {
+    update_at(cx, Instant::now());
+}
+
+/// Like [`update`], but ticks the animations to the given time, which lets tests advance time without waiting.
+pub(crate) fn update_at(cx: &mut BackendContext, now: Instant) {
+    EventManager::new().flush_events(&mut cx.0, |_| {});
+    cx.process_style_updates();
+    cx.process_animations_at(now);
+    cx.process_visual_updates();
+}
@@ -0,0 +1,245 @@
+use std::sync::{Arc, Mutex};
+
+use super::backend::BackendContext;
+use super::test_support::{draw, update, update_at, window_context};
+use crate::events::EventManager;
+use crate::prelude::*;
+
+#[test]
+fn measure_and_fit() {
+    let mut cx = window_context(400, 300);
+
+    Element::new(cx.context()).width(Pixels(120.0)).height(Pixels(80.0));
+    Element::new(cx.context()).size(Pixels(50.0));
+
+    cx.0.windows.get_mut(&Entity::root()).unwrap().window_description.fit_content = true;
+
+    update(&mut cx);
+
+    // The window is fitted to its content once it has been laid out.
+    let window_state = &cx.0.windows[&Entity::root()];
+    assert!(window_state.content_fitted);
+    assert_eq!(window_state.window_description.inner_size, WindowSize::new(120, 130));
+
+    // The requested size is clamped to the minimum inner size of the window.
+    cx.0.windows.get_mut(&Entity::root()).unwrap().window_description.min_inner_size =
+        Some(WindowSize::new(200, 100));
+    assert_eq!(cx.0.measure_and_fit(Entity::root()), Some(WindowSize::new(200, 130)));
+
+    // Views which aren't windows can't be fitted.
+    let element = Element::new(cx.context()).entity();
+    assert_eq!(cx.0.measure_and_fit(element), None);
+}
+
+#[test]
+fn scoped_class() {
+    let mut cx = window_context(400, 300);
+
+    let has_class = |cx: &BackendContext, entity: Entity| {
+        cx.0.style.classes.get(entity).is_some_and(|classes| classes.contains("flash"))
+    };
+
+    let element = Element::new(cx.context()).entity();
+    let styled = Element::new(cx.context()).class("flash").entity();
+
+    // Adding the class again extends the scope rather than scheduling a second removal.
+    cx.0.add_scoped_class(element, "flash", Duration::ZERO);
+    cx.0.add_scoped_class(element, "flash", Duration::ZERO);
+    cx.0.add_scoped_class(styled, "flash", Duration::ZERO);
+    assert!(has_class(&cx, element));
+    assert_eq!(cx.0.event_schedule.len(), 2);
+
+    cx.emit_scheduled_events();
+    update(&mut cx);
+
+    // A class which the view already had is left in place.
+    assert!(!has_class(&cx, element));
+    assert!(has_class(&cx, styled));
+    assert!(cx.0.scoped_classes.is_empty());
+
+    // Removing the view cancels the removal of its scoped classes.
+    let removed = Element::new(cx.context()).entity();
+    cx.0.add_scoped_class(removed, "flash", Duration::from_secs(60));
+    cx.0.remove(removed);
+    assert!(cx.0.scoped_classes.is_empty());
+
+    // A class added for the duration of a closure is only present while the closure runs.
+    let present = cx.0.with_style_class_scope(element, "flash", |cx| {
+        cx.style.classes.get(element).is_some_and(|classes| classes.contains("flash"))
+    });
+    assert!(present);
+    assert!(!has_class(&cx, element));
+
+    // Nothing is restyled if the view already has the class.
+    update(&mut cx);
+    cx.0.with_style_class_scope(styled, "flash", |_| {});
+    assert!(has_class(&cx, styled));
+    assert!(cx.0.style.restyle.is_empty());
+    assert!(cx.0.event_schedule.is_empty());
+}
+
+#[test]
+fn entity_path_round_trip() {
+    let mut cx = window_context(400, 300);
+
+    let mut entities = Vec::new();
+    VStack::new(cx.context(), |cx| {
+        for id in ["a > b", "c#d", "e\\f", "plain"] {
+            entities.push(Element::new(cx).id(id).entity());
+        }
+
+        // Siblings which share an id are addressed by index.
+        entities.push(Element::new(cx).id("shared").entity());
+        entities.push(Element::new(cx).id("shared").entity());
+
+        HStack::new(cx, |cx| {
+            entities.push(Label::new(cx, "nested").id("x>#y").entity());
+        });
+    });
+
+    for entity in entities {
+        let path = cx.0.entity_path(entity);
+        assert_eq!(cx.0.entity_at_path(&path), Some(entity), "{}", path);
+    }
+
+    assert_eq!(cx.0.entity_path(Entity::root()), "root");
+    assert_eq!(cx.0.entity_at_path("root"), Some(Entity::root()));
+    assert_eq!(
+        cx.0.entity_at_path("root > vstack:nth(0) > element#c\\#d"),
+        cx.0.resolve_entity_identifier("c#d")
+    );
+
+    // Malformed or stale paths don't resolve.
+    assert_eq!(cx.0.entity_at_path("window > vstack:nth(0)"), None);
+    assert_eq!(cx.0.entity_at_path("root > vstack:nth(1)"), None);
+    assert_eq!(cx.0.entity_at_path("root > vstack:nth(0) > element#c\\"), None);
+}
+
+#[test]
+fn with_environment() {
+    struct Listener {
+        received: Arc<Mutex<Vec<EnvironmentEvent>>>,
+    }
+
+    impl View for Listener {
+        fn event(&mut self, _: &mut EventContext, event: &mut Event) {
+            event.map(|environment_event: &EnvironmentEvent, _| {
+                self.received.lock().unwrap().push(environment_event.clone());
+            });
+        }
+    }
+
+    let mut cx = window_context(400, 300);
+
+    let received = Arc::new(Mutex::new(Vec::new()));
+    Listener { received: received.clone() }.build(cx.context(), |_| {});
+    let label =
+        Label::new(cx.context(), Environment::locale.map(|locale| locale.to_string())).entity();
+
+    update(&mut cx);
+    draw(&mut cx);
+
+    cx.0.with_environment(|environment| {
+        environment.locale = langid!("fr-FR");
+        environment.theme.app_theme = AppTheme::BuiltIn(ThemeMode::DarkMode);
+    });
+
+    // Views bound to the environment are updated immediately.
+    assert_eq!(cx.0.style.text.get(label).map(String::as_str), Some("fr-FR"));
+    assert_eq!(cx.0.resource_manager.theme_mode, ThemeMode::DarkMode);
+    assert!(cx.0.style.system_flags.contains(crate::style::SystemFlags::RELAYOUT));
+
+    update(&mut cx);
+
+    // Every view is notified of the changes.
+    let received = received.lock().unwrap();
+    assert!(matches!(
+        received.as_slice(),
+        [EnvironmentEvent::LocaleChanged(locale), EnvironmentEvent::ThemeChanged(ThemeMode::DarkMode)]
+            if *locale == langid!("fr-FR")
+    ));
+}
+
+#[test]
+fn minimum_content_size() {
+    let mut cx = window_context(400, 300);
+
+    let content = Element::new(cx.context()).width(Stretch(1.0)).height(Stretch(1.0)).entity();
+
+    cx.0.set_minimum_content_size(Entity::root(), Some(WindowSize::new(500, 200)));
+
+    // The window is asked not to become smaller than its content.
+    let mut min_sizes = Vec::new();
+    EventManager::new().flush_events(&mut cx.0, |window_event| {
+        if let WindowEvent::SetMinSize(size) = window_event {
+            min_sizes.push(*size);
+        }
+    });
+    assert_eq!(min_sizes, [Some(WindowSize::new(500, 200))]);
+
+    update(&mut cx);
+
+    // The content is laid out at no less than the minimum size, even though the window is narrower.
+    assert_eq!(cx.0.cache.get_width(content), 500.0);
+    assert_eq!(cx.0.cache.get_height(content), 300.0);
+
+    // The minimum size of the window itself is kept, and the larger of the two applies.
+    cx.0.style.min_width.insert(Entity::root(), Pixels(450.0));
+    cx.0.style.min_height.insert(Entity::root(), Pixels(350.0));
+    cx.0.style.needs_relayout();
+    update(&mut cx);
+
+    assert_eq!(cx.0.cache.get_width(content), 500.0);
+    assert_eq!(cx.0.cache.get_height(content), 350.0);
+
+    cx.0.set_minimum_content_size(Entity::root(), None);
+    update(&mut cx);
+
+    assert_eq!(cx.0.cache.get_width(content), 450.0);
+    assert_eq!(cx.0.cache.get_height(content), 350.0);
+    assert_eq!(cx.0.windows[&Entity::root()].min_content_size, None);
+
+    cx.0.style.min_width.remove(Entity::root());
+    cx.0.style.min_height.remove(Entity::root());
+    cx.0.style.needs_relayout();
+    update(&mut cx);
+
+    assert_eq!(cx.0.cache.get_width(content), 400.0);
+}
+
+#[test]
+fn freeze() {
+    let mut cx = window_context(400, 300);
+    cx.context()
+        .add_stylesheet(
+            ".item { opacity: 1; } .item:checked { opacity: 0; transition: opacity 200ms; }",
+        )
+        .unwrap();
+
+    let entity = Element::new(cx.context()).class("item").size(Pixels(100.0)).entity();
+    update(&mut cx);
+
+    EventContext::new_with_current(&mut cx.0, entity).set_checked(true);
+    update(&mut cx);
+
+    let opacity = |cx: &BackendContext| cx.0.style.opacity.get(entity).unwrap().0;
+    let start = opacity(&cx);
+
+    // Neither the clock nor input advances the application while it's frozen.
+    cx.0.freeze(true);
+    let (frozen_at, _) = cx.0.frozen.unwrap();
+    cx.send_event(Event::new(WindowEvent::MouseMove(50.0, 50.0)).origin(Entity::root()));
+    update_at(&mut cx, frozen_at + Duration::from_millis(300));
+    assert_eq!(opacity(&cx), start);
+    assert_eq!(cx.0.mouse.cursor_x, -1.0);
+
+    // Thawing resumes the transition from where it was frozen and handles the queued input.
+    let thawed_at = frozen_at + Duration::from_millis(300);
+    cx.0.thaw_at(thawed_at);
+    update_at(&mut cx, thawed_at + Duration::from_millis(100));
+    assert!(opacity(&cx) > 0.0 && opacity(&cx) < 1.0);
+    assert_eq!(cx.0.mouse.cursor_x, 50.0);
+
+    update_at(&mut cx, thawed_at + Duration::from_millis(250));
+    assert_eq!(opacity(&cx), 0.0);
+}
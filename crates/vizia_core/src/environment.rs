@@ -6,7 +6,7 @@ use vizia_derive::Lens;
 use web_time::Duration;
 
 /// And enum which represents the current built-in theme mode.
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Data)]
 pub enum ThemeMode {
     /// The built-in vizia dark theme.
     DarkMode,
@@ -60,6 +60,9 @@ pub struct Environment {
     pub locale: LanguageIdentifier,
     /// Current application and system theme.
     pub theme: Theme,
+    /// The theme mode currently applied to the application, which follows the system theme when the application
+    /// theme is [`AppTheme::System`].
+    pub theme_mode: ThemeMode,
     /// The timer used to blink the caret of a textbox.
     pub(crate) caret_timer: Timer,
 }
@@ -72,11 +75,36 @@ impl Environment {
                 cx.emit(TextEvent::ToggleCaret);
             }
         });
-        Self { locale, theme: Theme::default(), caret_timer }
+        let theme = Theme::default();
+        let theme_mode = theme.get_current_theme();
+        Self { locale, theme, theme_mode, caret_timer }
+    }
+
+    // Applies the current theme mode if it has changed, notifying every view.
+    pub(crate) fn update_theme_mode(&mut self, cx: &mut EventContext) {
+        let theme_mode = self.theme.get_current_theme();
+        if theme_mode != self.theme_mode {
+            self.theme_mode = theme_mode;
+
+            cx.set_theme_mode(theme_mode);
+            cx.reload_styles().unwrap();
+            cx.emit_to_all(EnvironmentEvent::ThemeChanged(theme_mode));
+        }
+    }
+
+    // Sets the locale if it has changed, relayouting the application for the translated text and notifying every view.
+    pub(crate) fn update_locale(&mut self, cx: &mut EventContext, locale: LanguageIdentifier) {
+        if locale != self.locale {
+            self.locale = locale;
+
+            cx.with_current(Entity::root(), |cx| cx.needs_relayout());
+            cx.emit_to_all(EnvironmentEvent::LocaleChanged(self.locale.clone()));
+        }
     }
 }
 
 /// Events for setting the state in the [Environment].
+#[derive(Debug, Clone)]
 pub enum EnvironmentEvent {
     /// Set the locale used for the whole application.
     SetLocale(LanguageIdentifier),
@@ -87,25 +115,32 @@ pub enum EnvironmentEvent {
     UseSystemLocale,
     /// Alternate between dark and light theme modes.
     ToggleThemeMode,
+    /// Sent to every view after the styles have been reloaded because a watched stylesheet file was modified.
+    StylesReloaded,
+    /// Sent to every view after the theme mode of the application has changed, either because the application theme
+    /// was set or because the system theme changed while the application follows it.
+    ThemeChanged(ThemeMode),
+    /// Sent to every view after the locale of the application has changed.
+    LocaleChanged(LanguageIdentifier),
 }
 
 impl Model for Environment {
     fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
         event.take(|event, _| match event {
             EnvironmentEvent::SetLocale(locale) => {
-                self.locale = locale;
+                self.update_locale(cx, locale);
             }
 
             EnvironmentEvent::SetThemeMode(theme) => {
                 theme.clone_into(&mut self.theme.app_theme);
 
-                cx.set_theme_mode(self.theme.get_current_theme());
-                cx.reload_styles().unwrap();
+                self.update_theme_mode(cx);
             }
 
             EnvironmentEvent::UseSystemLocale => {
-                self.locale =
+                let locale =
                     sys_locale::get_locale().map(|l| l.parse().unwrap()).unwrap_or_default();
+                self.update_locale(cx, locale);
             }
 
             EnvironmentEvent::ToggleThemeMode => {
@@ -116,18 +151,18 @@ impl Model for Environment {
 
                 self.theme.app_theme = AppTheme::BuiltIn(theme_mode);
 
-                cx.set_theme_mode(theme_mode);
-                cx.reload_styles().unwrap();
+                self.update_theme_mode(cx);
             }
+
+            EnvironmentEvent::StylesReloaded
+            | EnvironmentEvent::ThemeChanged(_)
+            | EnvironmentEvent::LocaleChanged(_) => {}
         });
 
         event.map(|event, _| match event {
             WindowEvent::ThemeChanged(theme) => {
                 self.theme.sys_theme = Some(*theme);
-                if self.theme.app_theme == AppTheme::System {
-                    cx.set_theme_mode(*theme);
-                    cx.reload_styles().unwrap();
-                }
+                self.update_theme_mode(cx);
             }
             _ => (),
         })
@@ -6,12 +6,14 @@ use web_time::Instant;
 /// Determines how an event propagates through the tree.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Propagation {
-    // /// Events propagate down the tree to the target entity, e.g. from grand-parent to parent to child (target)
-    // Down,
+    /// Events propagate down the tree from the root to the target entity, e.g. from grand-parent to parent to child (target),
+    /// and then back up the tree from the target entity to the root. While the event travels down the tree it is only
+    /// handled with [`Event::map_during_capture`], which lets an ancestor intercept it before it reaches the target.
+    ///
+    /// Mouse and keyboard input which propagates up the tree is also captured in this way before it reaches its target.
+    Down,
     /// Events propagate up the tree from the target entity from ancestor to ancestor, e.g. from child (target) to parent to grand-parent etc.
     Up,
-    // /// Events propagate down the tree to the target entity and then back up to the root
-    // DownUp,
     /// Events propagate starting at the target entity and visiting every entity that is a descendent of the target.
     Subtree,
     /// Events propagate directly to the target entity and to no others.
@@ -66,6 +68,12 @@ impl Event {
         self
     }
 
+    /// Marks the event as synthesized by `press()` rather than produced by real input.
+    pub(crate) fn synthesized(mut self) -> Self {
+        self.meta.synthesized = true;
+        self
+    }
+
     /// Consumes the event to prevent it from continuing on its propagation path.
     pub fn consume(&mut self) {
         self.meta.consume();
@@ -74,6 +82,8 @@ impl Event {
     /// Tries to downcast the event message to the specified type. If the downcast was successful,
     /// the message and the event metadata get passed into `f`.
     ///
+    /// Does nothing while the event is in the [`EventPhase::Capturing`] phase, see [`Event::map_during_capture`].
+    ///
     /// # Example
     /// ```no_run
     /// # use vizia_core::prelude::*;
@@ -101,6 +111,16 @@ impl Event {
     /// # }
     /// ```
     pub fn map<M, F>(&mut self, f: F)
+    where
+        M: Any + Send,
+        F: FnOnce(&M, &mut EventMeta),
+    {
+        if self.meta.phase != EventPhase::Capturing {
+            self.map_message(f);
+        }
+    }
+
+    fn map_message<M, F>(&mut self, f: F)
     where
         M: Any + Send,
         F: FnOnce(&M, &mut EventMeta),
@@ -112,9 +132,48 @@ impl Event {
         }
     }
 
+    /// Like [`Event::map`], but only calls `f` while the event is in the [`EventPhase::Capturing`] phase, i.e. when
+    /// an ancestor of the target receives an event with [`Propagation::Down`], or mouse and keyboard input, before it
+    /// reaches the target.
+    ///
+    /// This allows a view to intercept an event, for example by consuming it, before its descendants can handle it.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vizia_core::prelude::*;
+    /// # pub struct Overlay {
+    /// #     blocking: bool,
+    /// # }
+    /// # pub enum OverlayEvent {
+    /// #     Interact,
+    /// # }
+    /// # impl View for Overlay {
+    /// #     fn event(&mut self, _cx: &mut EventContext, event: &mut Event) {
+    /// event.map_during_capture(|overlay_event, meta| match overlay_event {
+    ///     OverlayEvent::Interact => {
+    ///         if self.blocking {
+    ///             meta.consume();
+    ///         }
+    ///     }
+    /// });
+    /// #     }
+    /// # }
+    /// ```
+    pub fn map_during_capture<M, F>(&mut self, f: F)
+    where
+        M: Any + Send,
+        F: FnOnce(&M, &mut EventMeta),
+    {
+        if self.meta.phase == EventPhase::Capturing {
+            self.map_message(f);
+        }
+    }
+
     /// Tries to downcast the event message to the specified type. If the downcast was successful,
     /// return the message by value and consume the event. Otherwise, do nothing.
     ///
+    /// Like [`Event::map`], this does nothing while the event is in the [`EventPhase::Capturing`] phase.
+    ///
     /// # Example
     /// ```
     /// # use vizia_core::prelude::*;
@@ -145,6 +204,10 @@ impl Event {
     where
         F: FnOnce(M, &mut EventMeta),
     {
+        if self.meta.phase == EventPhase::Capturing {
+            return;
+        }
+
         if let Some(message) = &self.message {
             if message.as_ref().is::<M>() {
                 // Safe to unwrap because we already checked it exists
@@ -158,6 +221,17 @@ impl Event {
     }
 }
 
+/// The phase of propagation an [`Event`] is in when it is handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventPhase {
+    /// The event is travelling down the tree from the root towards the target entity.
+    Capturing,
+    /// The event is being handled by the target entity.
+    AtTarget,
+    /// The event is travelling from the target entity towards the root or through the subtree of the target.
+    Bubbling,
+}
+
 /// The metadata of an [`Event`].
 #[derive(Debug, Clone, Copy)]
 pub struct EventMeta {
@@ -167,8 +241,12 @@ pub struct EventMeta {
     pub target: Entity,
     /// How the event propagates through the tree.
     pub propagation: Propagation,
+    /// The phase of propagation the event is currently in.
+    pub phase: EventPhase,
     /// Determines whether the event should continue to be propagated.
     pub(crate) consumed: bool,
+    /// Whether the event was synthesized by `press()` rather than produced by real input.
+    pub(crate) synthesized: bool,
 }
 
 impl EventMeta {
@@ -184,7 +262,9 @@ impl Default for EventMeta {
             origin: Entity::null(),
             target: Entity::root(),
             propagation: Propagation::Up,
+            phase: EventPhase::AtTarget,
             consumed: false,
+            synthesized: false,
         }
     }
 }
@@ -10,6 +10,8 @@ pub(crate) trait ViewHandler: Any {
 
     fn event(&mut self, cx: &mut EventContext, event: &mut Event);
 
+    fn on_pre_draw(&mut self, cx: &mut DrawContext);
+
     fn draw(&self, cx: &mut DrawContext, canvas: &Canvas);
 
     fn accessibility(&self, cx: &mut AccessContext, node: &mut AccessNode);
@@ -1,13 +1,14 @@
-use crate::context::{InternalEvent, ResourceContext};
+use crate::context::{data_changed, InternalEvent, ResourceContext, CURRENT};
 use crate::events::EventMeta;
 use crate::prelude::*;
 #[cfg(debug_assertions)]
 use crate::systems::compute_matched_rules;
 use crate::systems::{binding_system, hover_system};
-use crate::tree::{focus_backward, focus_forward, is_navigatable};
+use crate::tree::{focus_backward, focus_forward, focus_lock, is_navigatable};
 #[cfg(debug_assertions)]
 use log::debug;
 use std::any::Any;
+use std::panic::AssertUnwindSafe;
 use vizia_storage::LayoutParentIterator;
 #[cfg(debug_assertions)]
 use vizia_storage::ParentIterator;
@@ -43,6 +44,12 @@ impl EventManager {
         cx: &mut Context,
         mut window_event_callback: impl FnMut(&WindowEvent),
     ) {
+        // Publish the frame stats before the bindings are updated so that views bound to them are updated below.
+        #[cfg(feature = "profiling")]
+        crate::profiling::publish_frame_stats(cx);
+        #[cfg(feature = "profiling")]
+        let start = Instant::now();
+
         while {
             // Clear the event queue in the event manager.
             self.event_queue.clear();
@@ -54,15 +61,99 @@ impl EventManager {
             // Loop over the events in the event queue.
             'events: for event in self.event_queue.iter_mut() {
                 // Handle internal events.
-                event.take(|internal_event, _| match internal_event {
-                    InternalEvent::Redraw => cx.needs_redraw(Entity::root()),
+                event.take(|internal_event, meta| match internal_event {
+                    InternalEvent::Redraw => {
+                        let window = cx.tree.get_parent_window(meta.origin);
+                        cx.needs_redraw(window.unwrap_or(Entity::root()));
+                    }
+                    InternalEvent::CaptureHover(capture) => cx.set_hover_capture(capture),
+                    InternalEvent::Rehover => {
+                        let window =
+                            cx.tree.get_parent_window(meta.origin).unwrap_or(Entity::root());
+                        cx.with_current(window, |cx| hover_system(cx, window));
+                    }
+                    InternalEvent::RepeatKey => {
+                        if let (Some((code, key, character, window, pressed)), Some((delay, _))) =
+                            (cx.held_key.clone(), cx.key_repeat)
+                        {
+                            if pressed.elapsed() >= delay {
+                                if let Some(character) = character {
+                                    cx.event_queue.push_back(
+                                        Event::new(WindowEvent::CharInput(character))
+                                            .target(window)
+                                            .origin(window),
+                                    );
+                                }
+                                cx.event_queue.push_back(
+                                    Event::new(WindowEvent::KeyDown(code, key))
+                                        .target(window)
+                                        .origin(window),
+                                );
+                            }
+                        }
+                    }
+                    #[cfg(feature = "hot-reload")]
+                    InternalEvent::CheckStylesheets => cx.check_stylesheets(),
+                    InternalEvent::RemoveScopedClass { entity, class } => {
+                        if let Some((_, true)) = cx.scoped_classes.remove(&(entity, class.clone()))
+                        {
+                            if cx.style.remove_class(entity, &class) {
+                                cx.style.invalidate_class(&cx.tree, entity, &class);
+                            }
+                        }
+                    }
                     InternalEvent::LoadImage { path, image, policy } => {
                         if let Some(image) = image.lock().unwrap().take() {
                             ResourceContext::new(cx).load_image(path, image, policy);
                         }
                     }
+                    InternalEvent::LoadSvg { path, data, policy } => {
+                        cx.replace_svg(&path, &data, policy);
+                    }
+                    InternalEvent::JobComplete { id, result } => {
+                        if let Some((entity, on_complete)) = cx.jobs.complete(id) {
+                            let mut cx = EventContext::new(cx);
+                            cx.current = entity;
+                            (on_complete)(&mut cx, result);
+                        }
+                    }
                 });
 
+                // Hold back or drop input while the application is frozen.
+                if let Some((_, queue_input)) = cx.frozen {
+                    let mut is_input = false;
+                    event.map(|window_event: &WindowEvent, meta| {
+                        is_input =
+                            cx.windows.contains_key(&meta.origin) && is_input_event(window_event);
+                    });
+
+                    if is_input {
+                        if queue_input {
+                            cx.frozen_input.push(std::mem::replace(event, Event::new(())));
+                        }
+
+                        continue 'events;
+                    }
+                }
+
+                // Send events to the global interceptors, which can consume them before they're dispatched.
+                if !cx.interceptors.is_empty() {
+                    for (id, interceptor) in cx.interceptors.callbacks() {
+                        // Skip interceptors which were removed by an earlier interceptor.
+                        if !cx.interceptors.contains(id) {
+                            continue;
+                        }
+
+                        cx.with_current(Entity::root(), |cx| {
+                            (interceptor)(&mut EventContext::new(cx), event)
+                        });
+
+                        if event.meta.consumed {
+                            continue 'events;
+                        }
+                    }
+                }
+
                 // Send events to any global listeners.
                 let mut global_listeners = vec![];
                 std::mem::swap(&mut cx.global_listeners, &mut global_listeners);
@@ -98,8 +189,10 @@ impl EventManager {
                 }
 
                 // Handle state updates for window events.
+                let mut is_input = false;
                 event.map(|window_event, meta| {
                     if cx.windows.contains_key(&meta.origin) {
+                        is_input = is_input_event(window_event);
                         internal_state_updates(cx, window_event, meta);
                     }
                 });
@@ -114,7 +207,29 @@ impl EventManager {
                 // Copy the target to prevent multiple mutable borrows error.
                 let target = event.meta.target;
 
+                // Propagate down from root to target (not including the target). Input which bubbles up from the
+                // hovered or focused entity is captured by its ancestors first.
+                let propagation = event.meta.propagation;
+                if propagation == Propagation::Down || (is_input && propagation == Propagation::Up)
+                {
+                    event.meta.phase = EventPhase::Capturing;
+
+                    // Collect the ancestors of the target so they can be visited root first.
+                    let ancestors = target.parent_iter(cx.tree).skip(1).collect::<Vec<_>>();
+
+                    for entity in ancestors.into_iter().rev() {
+                        // Send event to all ancestors of the target.
+                        visit_entity(cx, entity, event);
+
+                        // Skip to the next event if the current event was consumed.
+                        if event.meta.consumed {
+                            continue 'events;
+                        }
+                    }
+                }
+
                 // Send event to target.
+                event.meta.phase = EventPhase::AtTarget;
                 visit_entity(cx, target, event);
 
                 // Skip to next event if the current event was consumed.
@@ -122,8 +237,10 @@ impl EventManager {
                     continue 'events;
                 }
 
+                event.meta.phase = EventPhase::Bubbling;
+
                 // Propagate up from target to root (not including the target).
-                if event.meta.propagation == Propagation::Up {
+                if matches!(event.meta.propagation, Propagation::Up | Propagation::Down) {
                     // Create a parent iterator and skip the first element which is the target.
                     let iter = target.parent_iter(cx.tree).skip(1);
 
@@ -159,11 +276,21 @@ impl EventManager {
                 });
             }
 
+            // Run any closures deferred by event handlers now that dispatch has completed.
+            for (entity, f) in std::mem::take(&mut cx.deferred) {
+                let current =
+                    if cx.entity_manager.is_alive(entity) { entity } else { Entity::root() };
+                cx.with_current(current, f);
+            }
+
             binding_system(cx);
 
             // Return true if there are new events in the queue.
             !cx.event_queue.is_empty()
         } {}
+
+        #[cfg(feature = "profiling")]
+        cx.profiler.record(crate::profiling::ProfiledSystem::Event, start.elapsed());
     }
 }
 
@@ -178,7 +305,7 @@ fn visit_entity(cx: &mut EventContext, entity: Entity, event: &mut Event) {
             {
                 cx.current = entity;
 
-                model.event(cx, event);
+                call_handler(cx, entity, event, |cx, event| model.event(cx, event));
 
                 cx.models.get_mut(&entity).and_then(|models| models.insert(id, model));
             }
@@ -193,12 +320,69 @@ fn visit_entity(cx: &mut EventContext, entity: Entity, event: &mut Event) {
     // Send event to the view attached to the entity
     if let Some(mut view) = cx.views.remove(&entity) {
         cx.current = entity;
-        view.event(cx, event);
+
+        call_handler(cx, entity, event, |cx, event| view.event(cx, event));
 
         cx.views.insert(entity, view);
     }
 }
 
+/// Calls an event handler, catching and logging any panic if panic catching is enabled.
+///
+/// A handler which panics consumes the event so that it doesn't propagate further.
+fn call_handler(
+    cx: &mut EventContext,
+    entity: Entity,
+    event: &mut Event,
+    handler: impl FnOnce(&mut EventContext, &mut Event),
+) {
+    // The handler may change the data of the model or view.
+    data_changed();
+
+    if !*cx.catch_panics {
+        handler(cx, event);
+        return;
+    }
+
+    let previous = CURRENT.with_borrow(|current| *current);
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| handler(cx, event)));
+
+    if let Err(payload) = result {
+        let message = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("unknown panic");
+
+        log::error!("Event handler of entity {:?} panicked: {}", entity, message);
+
+        // Restore the current entity, which may have been changed by the handler before it panicked.
+        CURRENT.with_borrow_mut(|current| *current = previous);
+        cx.current = entity;
+        event.consume();
+    }
+}
+
+// Returns true if the window event is mouse or keyboard input from the platform.
+fn is_input_event(window_event: &WindowEvent) -> bool {
+    matches!(
+        window_event,
+        WindowEvent::MouseDown(_)
+            | WindowEvent::MouseUp(_)
+            | WindowEvent::MouseDoubleClick(_)
+            | WindowEvent::MouseTripleClick(_)
+            | WindowEvent::MouseMove(_, _)
+            | WindowEvent::MouseScroll(_, _)
+            | WindowEvent::PinchGesture(_)
+            | WindowEvent::MouseMotion(_, _)
+            | WindowEvent::KeyDown(_, _)
+            | WindowEvent::KeyUp(_, _)
+            | WindowEvent::CharInput(_)
+            | WindowEvent::ImePreedit(_, _)
+            | WindowEvent::ImeCommit(_)
+    )
+}
+
 /// Update the internal state of the cx based on received window event and emit window event to relevant target.
 fn internal_state_updates(cx: &mut Context, window_event: &WindowEvent, meta: &mut EventMeta) {
     cx.current = meta.target;
@@ -218,6 +402,20 @@ fn internal_state_updates(cx: &mut Context, window_event: &WindowEvent, meta: &m
                 hover_system(cx, meta.origin);
 
                 mutate_direct_or_up(meta, cx.captured, cx.hovered, false);
+
+                // Compute the relative motion from the cursor when the backend does not report it.
+                if let Some(entity) = cx.pointer_lock {
+                    if !cx.raw_pointer_motion {
+                        let dx = cx.mouse.cursor_x - cx.mouse.previous_cursor_x;
+                        let dy = cx.mouse.cursor_y - cx.mouse.previous_cursor_y;
+                        cx.event_queue.push_back(
+                            Event::new(WindowEvent::MouseMotion(dx, dy))
+                                .target(entity)
+                                .origin(entity)
+                                .propagate(Propagation::Direct),
+                        );
+                    }
+                }
             }
 
             // if cx.mouse.cursor_x != cx.mouse.previous_cursor_x
@@ -250,7 +448,11 @@ fn internal_state_updates(cx: &mut Context, window_event: &WindowEvent, meta: &m
                     if let Some(pseudo_classes) = cx.style.pseudo_classes.get_mut(cx.triggered) {
                         if !disabled {
                             pseudo_classes.set(PseudoClassFlags::ACTIVE, true);
-                            cx.needs_restyle(cx.triggered);
+                            cx.style.invalidate_pseudo_classes(
+                                &cx.tree,
+                                cx.triggered,
+                                PseudoClassFlags::ACTIVE,
+                            );
                         }
                     }
                     let focusable = cx
@@ -356,19 +558,34 @@ fn internal_state_updates(cx: &mut Context, window_event: &WindowEvent, meta: &m
                     pseudo_classes.set(PseudoClassFlags::ACTIVE, false);
                 }
 
-                cx.needs_restyle(cx.triggered);
+                cx.style.invalidate_pseudo_classes(
+                    &cx.tree,
+                    cx.triggered,
+                    PseudoClassFlags::ACTIVE,
+                );
 
                 cx.triggered = Entity::null();
             }
 
             mutate_direct_or_up(meta, cx.captured, cx.hovered, true);
         }
-        WindowEvent::MouseScroll(_, _) => {
+        WindowEvent::MouseScroll(_, _) | WindowEvent::PinchGesture(_) => {
             meta.target = cx.hovered;
         }
-        WindowEvent::KeyDown(code, _) => {
+        WindowEvent::KeyDown(code, key) => {
             meta.target = cx.focused;
 
+            // Start repeating a newly held key, along with the character it input. Repeats of the held key do not
+            // restart the delay.
+            let character = cx.char_input.take();
+            if let Some((_, timer)) = cx.key_repeat {
+                if cx.held_key.as_ref().map(|(held, ..)| held) != Some(code) {
+                    cx.held_key =
+                        Some((*code, key.clone(), character, meta.origin, Instant::now()));
+                    cx.with_current(Entity::root(), |cx| cx.start_timer(timer));
+                }
+            }
+
             #[cfg(debug_assertions)]
             if *code == Code::KeyP && cx.modifiers.ctrl() {
                 for entity in TreeIterator::full(&cx.tree) {
@@ -509,12 +726,18 @@ fn internal_state_updates(cx: &mut Context, window_event: &WindowEvent, meta: &m
                 // }
             }
 
+            #[cfg(debug_assertions)]
+            if *code == Code::KeyI && cx.modifiers == Modifiers::CTRL | Modifiers::SHIFT {
+                cx.toggle_inspector();
+            }
+
             if *code == Code::F5 {
                 EventContext::new(cx).reload_styles().unwrap();
             }
 
-            if *code == Code::Tab {
-                let lock_focus_to = cx.tree.lock_focus_within(cx.focused);
+            // Ctrl+Tab is left to views, such as a tabview, which use it to switch between pages.
+            if *code == Code::Tab && !cx.modifiers.ctrl() {
+                let lock_focus_to = focus_lock(&cx.tree, &cx.focus_scopes, cx.focused);
                 if cx.modifiers.shift() {
                     let prev_focused = if let Some(prev_focused) =
                         focus_backward(&cx.tree, &cx.style, cx.focused, lock_focus_to)
@@ -548,7 +771,11 @@ fn internal_state_updates(cx: &mut Context, window_event: &WindowEvent, meta: &m
                         if let Some(pseudo_classes) = cx.style.pseudo_classes.get_mut(cx.triggered)
                         {
                             pseudo_classes.set(PseudoClassFlags::ACTIVE, false);
-                            cx.needs_restyle(cx.triggered);
+                            cx.style.invalidate_pseudo_classes(
+                                &cx.tree,
+                                cx.triggered,
+                                PseudoClassFlags::ACTIVE,
+                            );
                         }
                         cx.triggered = Entity::null();
                     }
@@ -582,7 +809,11 @@ fn internal_state_updates(cx: &mut Context, window_event: &WindowEvent, meta: &m
                         if let Some(pseudo_classes) = cx.style.pseudo_classes.get_mut(cx.triggered)
                         {
                             pseudo_classes.set(PseudoClassFlags::ACTIVE, false);
-                            cx.needs_restyle(cx.triggered);
+                            cx.style.invalidate_pseudo_classes(
+                                &cx.tree,
+                                cx.triggered,
+                                PseudoClassFlags::ACTIVE,
+                            );
                         }
                         cx.triggered = Entity::null();
                     }
@@ -599,6 +830,10 @@ fn internal_state_updates(cx: &mut Context, window_event: &WindowEvent, meta: &m
         }
         WindowEvent::KeyUp(code, _) => {
             meta.target = cx.focused;
+            if cx.held_key.as_ref().is_some_and(|(held, ..)| held == code) {
+                cx.release_held_key();
+            }
+
             if matches!(code, Code::Enter | Code::NumpadEnter | Code::Space) {
                 if cx.focused == cx.triggered {
                     cx.with_current(cx.triggered, |cx| {
@@ -608,21 +843,28 @@ fn internal_state_updates(cx: &mut Context, window_event: &WindowEvent, meta: &m
                 if let Some(pseudo_classes) = cx.style.pseudo_classes.get_mut(cx.triggered) {
                     pseudo_classes.set(PseudoClassFlags::ACTIVE, false);
                 }
-                cx.needs_restyle(cx.triggered);
+                cx.style.invalidate_pseudo_classes(
+                    &cx.tree,
+                    cx.triggered,
+                    PseudoClassFlags::ACTIVE,
+                );
                 cx.triggered = Entity::null();
             }
         }
-        WindowEvent::CharInput(_) => {
+        WindowEvent::CharInput(character) => {
+            meta.target = cx.focused;
+            cx.char_input = Some(*character);
+        }
+        WindowEvent::ImePreedit(_, _) | WindowEvent::ImeCommit(_) => {
             meta.target = cx.focused;
         }
         WindowEvent::WindowFocused(is_focused) => {
             if *is_focused {
                 cx.set_focus_pseudo_classes(cx.focused, true, true);
-                cx.needs_restyle(cx.focused);
                 cx.needs_redraw(cx.focused);
             } else {
+                cx.release_held_key();
                 cx.set_focus_pseudo_classes(cx.focused, false, true);
-                cx.needs_restyle(cx.focused);
 
                 cx.event_queue.push_back(
                     Event::new(WindowEvent::FocusVisibility(false))
@@ -649,7 +891,7 @@ fn internal_state_updates(cx: &mut Context, window_event: &WindowEvent, meta: &m
             for ancestor in parent_iter {
                 if let Some(pseudo_classes) = cx.style.pseudo_classes.get_mut(ancestor) {
                     pseudo_classes.set(PseudoClassFlags::HOVER, false);
-                    cx.style.needs_restyle(ancestor);
+                    cx.style.invalidate_pseudo_classes(&cx.tree, ancestor, PseudoClassFlags::HOVER);
                 }
             }
 
@@ -683,3 +925,297 @@ fn emit_direct_or_up<M: Any + Send>(
     mutate_direct_or_up(&mut event.meta, direct, up, root);
     cx.emit_custom(event);
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::context::backend::BackendContext;
+    use crate::context::test_support::{update, window_context};
+    use crate::events::EventManager;
+    use crate::prelude::*;
+
+    #[test]
+    fn capture_order() {
+        struct Recorder {
+            name: &'static str,
+            block: bool,
+            log: Arc<Mutex<Vec<(&'static str, EventPhase)>>>,
+        }
+
+        impl View for Recorder {
+            fn event(&mut self, _: &mut EventContext, event: &mut Event) {
+                event.map_during_capture(|_: &WindowEvent, meta| {
+                    self.log.lock().unwrap().push((self.name, meta.phase));
+                    if self.block {
+                        meta.consume();
+                    }
+                });
+
+                event.map(|window_event, meta| {
+                    if let WindowEvent::CharInput(_) = window_event {
+                        self.log.lock().unwrap().push((self.name, meta.phase));
+                    }
+                });
+            }
+        }
+
+        let mut cx = window_context(400, 300);
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        let mut target = Entity::null();
+        let outer = Recorder { name: "outer", block: false, log: log.clone() }
+            .build(cx.context(), |cx| {
+                target = Recorder { name: "target", block: false, log: log.clone() }
+                    .build(cx, |_| {})
+                    .entity();
+            })
+            .entity();
+
+        cx.0.with_current(target, |cx| cx.focus());
+        update(&mut cx);
+
+        // Input from the window is captured by the ancestors of its target before the target handles it.
+        cx.send_event(Event::new(WindowEvent::CharInput('a')).origin(Entity::root()));
+        update(&mut cx);
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            [
+                ("outer", EventPhase::Capturing),
+                ("target", EventPhase::AtTarget),
+                ("outer", EventPhase::Bubbling),
+            ]
+        );
+
+        // A capturing handler which consumes the input prevents the target from receiving it.
+        log.lock().unwrap().clear();
+        cx.0.views
+            .get_mut(&outer)
+            .and_then(|view| view.downcast_mut::<Recorder>())
+            .unwrap()
+            .block = true;
+        cx.send_event(Event::new(WindowEvent::CharInput('b')).origin(Entity::root()));
+        update(&mut cx);
+
+        assert_eq!(*log.lock().unwrap(), [("outer", EventPhase::Capturing)]);
+    }
+
+    #[test]
+    fn deferred_removal() {
+        struct Removable {
+            received: Arc<Mutex<u32>>,
+        }
+
+        impl View for Removable {
+            fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+                event.map(|message: &&'static str, _| {
+                    *self.received.lock().unwrap() += 1;
+                    if *message == "remove" {
+                        cx.defer(|cx| cx.remove(cx.current()));
+                    }
+                });
+            }
+        }
+
+        let mut cx = window_context(400, 300);
+
+        let received = Arc::new(Mutex::new(0));
+        let entity = Removable { received: received.clone() }.build(cx.context(), |_| {}).entity();
+
+        update(&mut cx);
+
+        // Both events are dispatched in the same cycle, so the view isn't removed until after it has received both.
+        cx.0.emit_to(entity, "remove");
+        cx.0.emit_to(entity, "after");
+        update(&mut cx);
+
+        assert_eq!(*received.lock().unwrap(), 2);
+        assert!(!cx.0.entity_manager.is_alive(entity));
+        assert!(!cx.0.views.contains_key(&entity));
+    }
+
+    #[test]
+    fn catch_panics() {
+        struct Panicking {
+            log: Arc<Mutex<Vec<&'static str>>>,
+        }
+
+        impl View for Panicking {
+            fn event(&mut self, _: &mut EventContext, event: &mut Event) {
+                event.map(|message: &&'static str, _| {
+                    if *message == "panic" {
+                        panic!("handler panicked");
+                    }
+
+                    self.log.lock().unwrap().push(message);
+                });
+            }
+        }
+
+        let mut cx = window_context(400, 300);
+        cx.0.set_catch_panics(true);
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let entity = Panicking { log: log.clone() }.build(cx.context(), |_| {}).entity();
+
+        update(&mut cx);
+
+        cx.0.emit_to(entity, "panic");
+        cx.0.emit_to(entity, "after");
+        update(&mut cx);
+
+        // The panic is caught and the following event is still dispatched to the view.
+        assert_eq!(*log.lock().unwrap(), ["after"]);
+        assert!(cx.0.views.contains_key(&entity));
+    }
+
+    #[test]
+    fn broadcast_order() {
+        #[derive(Clone)]
+        struct Broadcast;
+
+        struct Receiver {
+            name: &'static str,
+            log: Arc<Mutex<Vec<&'static str>>>,
+        }
+
+        impl View for Receiver {
+            fn event(&mut self, _: &mut EventContext, event: &mut Event) {
+                event.map(|_: &Broadcast, _| self.log.lock().unwrap().push(self.name));
+            }
+        }
+
+        let mut cx = window_context(400, 300);
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        Receiver { name: "a", log: log.clone() }.build(cx.context(), |cx| {
+            Receiver { name: "a1", log: log.clone() }.build(cx, |cx| {
+                Receiver { name: "a1a", log: log.clone() }.build(cx, |_| {});
+            });
+            Receiver { name: "a2", log: log.clone() }.build(cx, |_| {});
+        });
+        Receiver { name: "b", log: log.clone() }.build(cx.context(), |_| {});
+
+        update(&mut cx);
+
+        // Each view receives the message once, before its descendants and its following siblings.
+        cx.0.emit_to_all(Broadcast);
+        update(&mut cx);
+
+        assert_eq!(*log.lock().unwrap(), ["a", "a1", "a1a", "a2", "b"]);
+    }
+
+    #[test]
+    fn key_repeat() {
+        struct Keys {
+            presses: Arc<Mutex<u32>>,
+            chars: Arc<Mutex<String>>,
+        }
+
+        impl View for Keys {
+            fn event(&mut self, _: &mut EventContext, event: &mut Event) {
+                event.map(|window_event, _| match window_event {
+                    WindowEvent::KeyDown(Code::KeyA, _) => *self.presses.lock().unwrap() += 1,
+                    WindowEvent::CharInput(character) => {
+                        self.chars.lock().unwrap().push(*character)
+                    }
+                    _ => {}
+                });
+            }
+        }
+
+        let mut cx = window_context(400, 300);
+
+        let interval = Duration::from_millis(50);
+        cx.0.set_repeat_rate(Some((Duration::ZERO, interval)));
+        assert!(cx.has_custom_key_repeat());
+
+        let presses = Arc::new(Mutex::new(0));
+        let chars = Arc::new(Mutex::new(String::new()));
+        let entity = Keys { presses: presses.clone(), chars: chars.clone() }
+            .build(cx.context(), |_| {})
+            .entity();
+        cx.0.with_current(entity, |cx| cx.focus());
+        update(&mut cx);
+
+        // The backend sends the character input by a key before the key is pressed.
+        cx.send_event(Event::new(WindowEvent::CharInput('a')).origin(Entity::root()));
+        cx.send_event(Event::new(WindowEvent::KeyDown(Code::KeyA, None)).origin(Entity::root()));
+        update(&mut cx);
+        let start = Instant::now();
+        cx.0.tick_timers_at(start);
+        update(&mut cx);
+        assert_eq!(*presses.lock().unwrap(), 1);
+
+        // The held key is repeated once the interval has elapsed, along with the character it input.
+        cx.0.tick_timers_at(start + interval + interval / 5);
+        update(&mut cx);
+        assert_eq!(*presses.lock().unwrap(), 2);
+        assert_eq!(*chars.lock().unwrap(), "aa");
+
+        // Releasing the key stops the repeats.
+        cx.send_event(Event::new(WindowEvent::KeyUp(Code::KeyA, None)).origin(Entity::root()));
+        update(&mut cx);
+        cx.0.tick_timers_at(start + 2 * (interval + interval / 5));
+        update(&mut cx);
+        assert_eq!(*presses.lock().unwrap(), 2);
+    }
+
+    #[test]
+    fn pointer_lock() {
+        struct Dragged {
+            motion: Arc<Mutex<Vec<(f32, f32)>>>,
+        }
+
+        impl View for Dragged {
+            fn event(&mut self, _: &mut EventContext, event: &mut Event) {
+                event.map(|window_event, _| {
+                    if let WindowEvent::MouseMotion(dx, dy) = window_event {
+                        self.motion.lock().unwrap().push((*dx, *dy));
+                    }
+                });
+            }
+        }
+
+        let mut cx = window_context(400, 300);
+
+        let motion = Arc::new(Mutex::new(Vec::new()));
+        let entity = Dragged { motion: motion.clone() }
+            .build(cx.context(), |_| {})
+            .size(Pixels(50.0))
+            .entity();
+        update(&mut cx);
+
+        let mut window_events = Vec::new();
+        let mut flush = |cx: &mut BackendContext| {
+            EventManager::new().flush_events(&mut cx.0, |window_event| match window_event {
+                WindowEvent::SetCursorGrab(mode) => window_events.push(format!("grab {mode:?}")),
+                WindowEvent::SetCursorVisible(visible) => {
+                    window_events.push(format!("visible {visible}"))
+                }
+                _ => {}
+            });
+        };
+
+        cx.send_event(Event::new(WindowEvent::MouseMove(10.0, 10.0)).origin(Entity::root()));
+        flush(&mut cx);
+
+        // Locking the pointer grabs and hides the cursor through the window.
+        cx.0.with_current(entity, |cx| cx.lock_pointer());
+        flush(&mut cx);
+
+        // The motion of the cursor is sent to the view which locked the pointer.
+        cx.send_event(Event::new(WindowEvent::MouseMove(15.0, 7.0)).origin(Entity::root()));
+        flush(&mut cx);
+        assert_eq!(*motion.lock().unwrap(), [(5.0, -3.0)]);
+
+        // Removing the view releases the pointer.
+        cx.0.remove(entity);
+        flush(&mut cx);
+
+        assert_eq!(window_events, ["grab Locked", "visible false", "grab None", "visible true"]);
+        assert_eq!(cx.0.pointer_lock, None);
+    }
+}
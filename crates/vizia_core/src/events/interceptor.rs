@@ -0,0 +1,111 @@
+use std::rc::Rc;
+
+use crate::prelude::*;
+
+/// A handle used to remove an interceptor added with `cx.add_interceptor()`.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct InterceptorId(u64);
+
+type InterceptorCallback = Rc<dyn Fn(&mut EventContext, &mut Event)>;
+
+// The global interceptors of the application, ordered from highest to lowest priority.
+#[derive(Default)]
+pub(crate) struct Interceptors {
+    interceptors: Vec<(InterceptorId, i32, InterceptorCallback)>,
+    next_id: u64,
+}
+
+impl Interceptors {
+    pub(crate) fn add<F>(&mut self, priority: i32, interceptor: F) -> InterceptorId
+    where
+        F: 'static + Fn(&mut EventContext, &mut Event),
+    {
+        let id = InterceptorId(self.next_id);
+        self.next_id += 1;
+
+        // Interceptors with the same priority run in the order they were added.
+        let index = self.interceptors.partition_point(|(_, p, _)| *p >= priority);
+        self.interceptors.insert(index, (id, priority, Rc::new(interceptor)));
+
+        id
+    }
+
+    pub(crate) fn remove(&mut self, id: InterceptorId) -> bool {
+        let len = self.interceptors.len();
+        self.interceptors.retain(|(interceptor, _, _)| *interceptor != id);
+        self.interceptors.len() != len
+    }
+
+    // Returns the interceptors in the order they should run. They're cloned so that interceptors can be added and
+    // removed while the event is being intercepted.
+    pub(crate) fn callbacks(&self) -> Vec<(InterceptorId, InterceptorCallback)> {
+        self.interceptors.iter().map(|(id, _, callback)| (*id, callback.clone())).collect()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.interceptors.is_empty()
+    }
+
+    pub(crate) fn contains(&self, id: InterceptorId) -> bool {
+        self.interceptors.iter().any(|(interceptor, _, _)| *interceptor == id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+
+    use crate::context::test_support::{update, window_context};
+    use crate::prelude::*;
+
+    #[test]
+    fn interceptors() {
+        struct Ping;
+
+        let mut cx = window_context(400, 300);
+
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let record = |name: &'static str, consume: bool| {
+            let calls = calls.clone();
+            move |_: &mut EventContext, event: &mut Event| {
+                event.map(|_: &Ping, meta| {
+                    calls.borrow_mut().push(name);
+                    if consume {
+                        meta.consume();
+                    }
+                });
+            }
+        };
+
+        cx.0.add_interceptor(0, record("low", false));
+        cx.0.add_interceptor(5, record("first", false));
+        cx.0.add_interceptor(5, record("second", false));
+
+        // An interceptor removed by an earlier interceptor isn't called for the event being dispatched.
+        let removed = Rc::new(Cell::new(None));
+        let (remover_calls, target) = (calls.clone(), removed.clone());
+        cx.0.add_interceptor(3, move |cx, event| {
+            event.map(|_: &Ping, _| {
+                remover_calls.borrow_mut().push("remover");
+                if let Some(id) = target.get() {
+                    assert!(cx.remove_interceptor(id));
+                }
+            });
+        });
+        removed.set(Some(cx.0.add_interceptor(1, record("removed", false))));
+
+        // Consuming the event stops it from reaching lower priority interceptors and the views.
+        cx.0.add_interceptor(-1, record("consumer", true));
+        cx.0.add_interceptor(-2, record("last", false));
+        let listener_calls = calls.clone();
+        cx.0.add_global_listener(move |_, event| {
+            event.map(|_: &Ping, _| listener_calls.borrow_mut().push("listener"));
+        });
+
+        cx.0.emit_to(Entity::root(), Ping);
+        update(&mut cx);
+
+        assert_eq!(*calls.borrow(), ["first", "second", "remover", "low", "consumer"]);
+    }
+}
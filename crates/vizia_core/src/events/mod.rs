@@ -90,11 +90,15 @@ pub use event_manager::EventManager;
 
 mod event;
 pub(crate) use event::TimedEvent;
-pub use event::{Event, EventMeta, Propagation, TimedEventHandle};
+pub use event::{Event, EventMeta, EventPhase, Propagation, TimedEventHandle};
 
 mod event_handler;
 pub(crate) use event_handler::ViewHandler;
 
+mod interceptor;
+pub use interceptor::InterceptorId;
+pub(crate) use interceptor::Interceptors;
+
 mod timer;
 pub(crate) use timer::TimerState;
 pub use timer::{Timer, TimerAction};
@@ -30,9 +30,50 @@ pub struct TimerState {
     pub(crate) callback: Rc<dyn Fn(&mut EventContext, TimerAction)>,
     pub(crate) ticking: bool,
     pub(crate) stopping: bool,
+    // Whether the timer was removed, in which case its id is reused by the next timer to be added.
+    pub(crate) removed: bool,
 }
 
 impl TimerState {
+    // Adds a timer to the given timers, reusing the id of a removed timer if there is one.
+    pub(crate) fn add(
+        timers: &mut Vec<TimerState>,
+        interval: Duration,
+        duration: Option<Duration>,
+        callback: Rc<dyn Fn(&mut EventContext, TimerAction)>,
+    ) -> Timer {
+        let id = Timer(
+            timers.iter().position(|timer_state| timer_state.removed).unwrap_or(timers.len()),
+        );
+
+        let timer_state = TimerState {
+            entity: Entity::root(),
+            id,
+            time: Instant::now(),
+            interval,
+            duration,
+            start_time: Instant::now(),
+            callback,
+            ticking: false,
+            stopping: false,
+            removed: false,
+        };
+
+        if id.0 < timers.len() {
+            timers[id.0] = timer_state;
+        } else {
+            timers.push(timer_state);
+        }
+
+        id
+    }
+
+    // Marks a timer as removed, dropping its callback so that anything captured by it is freed.
+    pub(crate) fn remove(&mut self) {
+        self.removed = true;
+        self.callback = Rc::new(|_, _| {});
+    }
+
     pub fn start_time(&self) -> Instant {
         self.start_time
     }
@@ -96,3 +137,42 @@ impl Ord for TimerState {
 /// A handle used to start, stop, and check the running status of a timer added with `cx.add_timer()`.
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub struct Timer(pub usize);
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use crate::context::test_support::window_context;
+    use crate::prelude::*;
+
+    #[test]
+    fn restart_timer() {
+        let mut cx = window_context(400, 300);
+
+        let actions = Rc::new(RefCell::new(Vec::new()));
+        let log = actions.clone();
+        let slow = cx.0.add_timer(Duration::from_secs(10), None, move |_, action| {
+            log.borrow_mut().push(matches!(action, TimerAction::Start));
+        });
+        let fast = cx.0.add_timer(Duration::from_millis(10), None, |_, _| {});
+
+        cx.0.start_timer(slow);
+        cx.0.start_timer(fast);
+        cx.process_timers();
+        assert_eq!(*actions.borrow(), [true]);
+        assert_eq!(cx.0.running_timers.peek().map(|timer_state| timer_state.id), Some(fast));
+
+        // Restarting the slow timer, which isn't the next to tick, moves it to the front of the running timers.
+        cx.0.start_timer(slow);
+        assert_eq!(cx.0.running_timers.peek().map(|timer_state| timer_state.id), Some(slow));
+        assert_eq!(
+            EventContext::new(&mut cx.0).query_timer(slow, |timer_state| timer_state.ticking),
+            Some(false)
+        );
+
+        cx.process_timers();
+        assert_eq!(*actions.borrow(), [true, true]);
+        assert_eq!(cx.0.running_timers.len(), 2);
+    }
+}
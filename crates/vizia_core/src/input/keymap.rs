@@ -42,12 +42,21 @@ use indexmap::IndexMap;
 ///     (KeyChord::new(Modifiers::CTRL | Modifiers::SHIFT, Code::KeyC), KeymapEntry::new(Action::Three, |_| debug!("Action Three"))),
 /// ]);
 /// ```
+///
+/// Actions can also be bound to a sequence of key chords, such as `CTRL+K` followed by `CTRL+C`, using
+/// [`KeymapEvent::InsertSequence`].
+///
+/// A keymap only receives key events from the view it is built into and its descendants, so building a keymap into
+/// a view scopes its bindings to when that subtree contains the focused view. A keymap built into the root view
+/// applies to the whole application.
 #[derive(Default)]
 pub struct Keymap<T>
 where
     T: 'static + Clone + PartialEq + Send + Sync,
 {
     entries: IndexMap<KeyChord, Vec<KeymapEntry<T>>>,
+    sequences: IndexMap<Vec<KeyChord>, Vec<KeymapEntry<T>>>,
+    pending: Vec<KeyChord>,
 }
 
 impl<T> Keymap<T>
@@ -71,7 +80,7 @@ where
     /// let keymap = Keymap::<Action>::new();
     /// ```
     pub fn new() -> Self {
-        Self { entries: IndexMap::new() }
+        Self { entries: IndexMap::new(), sequences: IndexMap::new(), pending: Vec::new() }
     }
 
     /// Inserts an entry into the keymap.
@@ -104,6 +113,111 @@ where
         }
     }
 
+    /// Inserts an entry for a sequence of key chords into the keymap.
+    ///
+    /// This method is for internal use only.
+    /// To insert a sequence into the keymap at runtime use the [`KeymapEvent::InsertSequence`] event.
+    fn insert_sequence(&mut self, mut sequence: Vec<KeyChord>, keymap_entry: KeymapEntry<T>) {
+        match sequence.len() {
+            0 => {}
+            1 => self.insert(sequence.remove(0), keymap_entry),
+            _ => {
+                let actions = self.sequences.entry(sequence).or_default();
+                if !actions.contains(&keymap_entry) {
+                    actions.push(keymap_entry);
+                }
+            }
+        }
+    }
+
+    /// Removes an entry for a sequence of key chords from the keymap.
+    ///
+    /// This method is for internal use only.
+    /// To remove a sequence from the keymap at runtime use the [`KeymapEvent::RemoveSequence`] event.
+    fn remove_sequence(&mut self, sequence: &[KeyChord], action: &T) {
+        if sequence.len() == 1 {
+            self.remove(&sequence[0], action);
+        } else if let Some(actions) = self.sequences.get_mut(sequence) {
+            actions.retain(|entry| entry != action);
+            if actions.is_empty() {
+                self.sequences.swap_remove(sequence);
+            }
+        }
+
+        self.pending.clear();
+    }
+
+    /// Adds a key chord to the pending sequence, returning the entries of the sequence if it is complete.
+    ///
+    /// The pending sequence is restarted from the key chord if it no longer matches the start of any sequence.
+    /// Modifier keys are ignored, so that pressing or releasing a modifier between the chords of a sequence, such as
+    /// `CTRL+K` followed by `CTRL+S`, doesn't restart it.
+    fn advance_sequence(&mut self, chord: KeyChord) -> Option<Vec<KeymapEntry<T>>> {
+        if is_modifier(chord.code) {
+            return None;
+        }
+
+        let is_prefix =
+            |pending: &[KeyChord]| self.sequences.keys().any(|seq| seq.starts_with(pending));
+
+        self.pending.push(chord);
+        if !is_prefix(&self.pending) {
+            self.pending.clear();
+            if is_prefix(&[chord]) {
+                self.pending.push(chord);
+            }
+        }
+
+        let entries = self.sequences.get(&self.pending).cloned();
+        if entries.is_some() {
+            self.pending.clear();
+        }
+
+        entries
+    }
+
+    /// Returns the key chord sequences which conflict with another binding in the keymap.
+    ///
+    /// A sequence conflicts if it is bound to more than one action, or if it starts with a shorter sequence or key
+    /// chord which is also bound, in which case the shorter binding is triggered as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use vizia_core::prelude::*;
+    /// #
+    /// # #[derive(Debug, PartialEq, Copy, Clone)]
+    /// # enum Action {
+    /// #     One,
+    /// # }
+    /// #
+    /// # let keymap = Keymap::<Action>::new();
+    /// #
+    /// for sequence in keymap.conflicts() {
+    ///     debug!("The key chords {:?} conflict with another binding!", sequence);
+    /// }
+    /// ```
+    pub fn conflicts(&self) -> Vec<Vec<KeyChord>> {
+        let mut conflicts = Vec::new();
+
+        for (chord, entries) in self.entries.iter() {
+            if entries.len() > 1 {
+                conflicts.push(vec![*chord]);
+            }
+        }
+
+        for (sequence, entries) in self.sequences.iter() {
+            let has_bound_prefix = self.entries.contains_key(&sequence[0])
+                || (2..sequence.len()).any(|len| self.sequences.contains_key(&sequence[..len]));
+
+            if entries.len() > 1 || has_bound_prefix {
+                conflicts.push(sequence.clone());
+            }
+        }
+
+        conflicts
+    }
+
     /// Returns an iterator over every pressed keymap entry.
     ///
     /// # Examples
@@ -136,6 +250,8 @@ where
 
     /// Exports all keymap entries and their associated key chords.
     ///
+    /// Entries bound to a sequence of key chords are exported by [`Keymap::export_sequences`].
+    ///
     /// This is useful if you want to have a settings window and need to access every key chord
     /// and keymap entry of a keymap.
     ///
@@ -166,6 +282,17 @@ where
         }
         vec
     }
+
+    /// Exports all keymap entries bound to a sequence of key chords and their associated sequences.
+    pub fn export_sequences(&self) -> Vec<(&[KeyChord], &KeymapEntry<T>)> {
+        let mut vec = Vec::new();
+        for (sequence, entries) in self.sequences.iter() {
+            for entry in entries {
+                vec.push((sequence.as_slice(), entry));
+            }
+        }
+        vec
+    }
 }
 
 impl<T> Model for Keymap<T>
@@ -176,10 +303,29 @@ where
         event.map(|keymap_event, _| match keymap_event {
             KeymapEvent::InsertAction(chord, entry) => self.insert(*chord, entry.clone()),
             KeymapEvent::RemoveAction(chord, action) => self.remove(chord, action),
+            KeymapEvent::InsertSequence(sequence, entry) => {
+                self.insert_sequence(sequence.clone(), entry.clone())
+            }
+            KeymapEvent::RemoveSequence(sequence, action) => self.remove_sequence(sequence, action),
         });
         event.map(|window_event, _| match window_event {
             WindowEvent::KeyDown(code, _) => {
-                if let Some(entries) = self.entries.get(&KeyChord::new(*cx.modifiers, *code)) {
+                let chord = KeyChord::new(*cx.modifiers, *code);
+
+                if let Some(entries) = self.advance_sequence(chord) {
+                    for entry in entries {
+                        (entry.on_action())(cx)
+                    }
+
+                    return;
+                }
+
+                // Chords which continue a sequence don't trigger single chord bindings.
+                if self.pending.len() > 1 {
+                    return;
+                }
+
+                if let Some(entries) = self.entries.get(&chord) {
                     for entry in entries {
                         (entry.on_action())(cx)
                     }
@@ -190,6 +336,21 @@ where
     }
 }
 
+// Returns true if the key code is for a modifier key.
+fn is_modifier(code: Code) -> bool {
+    matches!(
+        code,
+        Code::ShiftLeft
+            | Code::ShiftRight
+            | Code::ControlLeft
+            | Code::ControlRight
+            | Code::AltLeft
+            | Code::AltRight
+            | Code::MetaLeft
+            | Code::MetaRight
+    )
+}
+
 impl<T> From<Vec<(KeyChord, KeymapEntry<T>)>> for Keymap<T>
 where
     T: 'static + Clone + PartialEq + Send + Sync,
@@ -248,4 +409,73 @@ where
     /// ));
     /// ```
     RemoveAction(KeyChord, T),
+    /// Inserts an entry for a sequence of key chords into the [`Keymap`].
+    ///
+    /// The action is triggered when the key chords are pressed one after the other.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use vizia_core::prelude::*;
+    /// #
+    /// # #[derive(PartialEq, Copy, Clone)]
+    /// # enum Action {
+    /// #     One,
+    /// # }
+    /// #
+    /// # let cx = &mut Context::default();
+    /// #
+    /// cx.emit(KeymapEvent::InsertSequence(
+    ///     vec![KeyChord::new(Modifiers::CTRL, Code::KeyK), KeyChord::new(Modifiers::CTRL, Code::KeyC)],
+    ///     KeymapEntry::new(Action::One, |_| debug!("Action One")),
+    /// ));
+    /// ```
+    InsertSequence(Vec<KeyChord>, KeymapEntry<T>),
+    /// Removes an entry for a sequence of key chords from the [`Keymap`].
+    RemoveSequence(Vec<KeyChord>, T),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Action {
+        Save,
+    }
+
+    #[test]
+    fn sequence_with_modifiers() {
+        let mut keymap = Keymap::new();
+        keymap.insert_sequence(
+            vec![
+                KeyChord::new(Modifiers::CTRL, Code::KeyK),
+                KeyChord::new(Modifiers::CTRL, Code::KeyS),
+            ],
+            KeymapEntry::new(Action::Save, |_| {}),
+        );
+
+        // Holding CTRL throughout: CTRL, K, S.
+        assert!(keymap
+            .advance_sequence(KeyChord::new(Modifiers::CTRL, Code::ControlLeft))
+            .is_none());
+        assert!(keymap.advance_sequence(KeyChord::new(Modifiers::CTRL, Code::KeyK)).is_none());
+        let entries = keymap.advance_sequence(KeyChord::new(Modifiers::CTRL, Code::KeyS)).unwrap();
+        assert_eq!(entries.iter().map(|entry| *entry.action()).collect::<Vec<_>>(), [Action::Save]);
+
+        // Pressing CTRL again between the chords doesn't restart the sequence.
+        assert!(keymap
+            .advance_sequence(KeyChord::new(Modifiers::CTRL, Code::ControlLeft))
+            .is_none());
+        assert!(keymap.advance_sequence(KeyChord::new(Modifiers::CTRL, Code::KeyK)).is_none());
+        assert!(keymap
+            .advance_sequence(KeyChord::new(Modifiers::CTRL, Code::ControlRight))
+            .is_none());
+        assert!(keymap.advance_sequence(KeyChord::new(Modifiers::CTRL, Code::KeyS)).is_some());
+
+        // Any other key does.
+        assert!(keymap.advance_sequence(KeyChord::new(Modifiers::CTRL, Code::KeyK)).is_none());
+        assert!(keymap.advance_sequence(KeyChord::new(Modifiers::CTRL, Code::KeyA)).is_none());
+        assert!(keymap.advance_sequence(KeyChord::new(Modifiers::CTRL, Code::KeyS)).is_none());
+    }
 }
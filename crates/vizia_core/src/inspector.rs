@@ -0,0 +1,245 @@
+//! A debugging overlay for inspecting the views, styles and layout of an application.
+//!
+//! The inspector is toggled with [`Context::toggle_inspector`], or with `Ctrl+Shift+I` in debug builds.
+
+use vizia_storage::TreeIterator;
+use vizia_style::{selectors::bloom::BloomFilter, ToCss};
+
+use crate::prelude::*;
+use crate::systems::{compute_element_hash, compute_matched_rules};
+
+// The width of the inspector panel in logical pixels.
+const PANEL_WIDTH: f32 = 360.0;
+// The indentation, in logical pixels, of each level of the tree panel.
+const INDENT: f32 = 12.0;
+
+// The color used to highlight the selected row and the inspected view.
+pub(crate) const HIGHLIGHT_COLOR: Color = Color::rgba(66, 135, 245, 96);
+
+/// A row within the tree panel of the inspector.
+#[derive(Clone, Data)]
+pub(crate) struct InspectorEntry {
+    entity: Entity,
+    depth: usize,
+    label: String,
+}
+
+enum InspectorEvent {
+    Refresh,
+    Select(Entity),
+    SetEditText(String),
+    Apply,
+}
+
+/// A panel which shows the tree of views, along with the classes, pseudo-classes, layout and matched style rules of
+/// the selected view, and allows style properties to be edited while the application is running.
+#[derive(Lens)]
+pub(crate) struct Inspector {
+    entries: Vec<InspectorEntry>,
+    pub(crate) selected: Option<Entity>,
+    details: String,
+    edit_text: String,
+}
+
+impl Inspector {
+    pub(crate) fn new(cx: &mut Context) -> Handle<Self> {
+        Self {
+            entries: Vec::new(),
+            selected: None,
+            details: String::new(),
+            edit_text: String::new(),
+        }
+        .build(cx, |cx| {
+            HStack::new(cx, |cx| {
+                Label::new(cx, "Inspector").width(Stretch(1.0));
+                Button::new(cx, |cx| Label::new(cx, "Refresh"))
+                    .on_press(|cx| cx.emit(InspectorEvent::Refresh));
+            })
+            .height(Auto);
+
+            ScrollView::new(cx, |cx| {
+                Binding::new(cx, Inspector::entries, |cx, entries| {
+                    for entry in entries.get(cx) {
+                        let entity = entry.entity;
+                        Label::new(cx, entry.label.as_str())
+                            .width(Stretch(1.0))
+                            .padding_left(Pixels(4.0 + INDENT * entry.depth as f32))
+                            .background_color(Inspector::selected.map(move |selected| {
+                                if *selected == Some(entity) {
+                                    HIGHLIGHT_COLOR
+                                } else {
+                                    Color::transparent()
+                                }
+                            }))
+                            .on_press(move |cx| cx.emit(InspectorEvent::Select(entity)));
+                    }
+                });
+            })
+            .height(Stretch(1.0));
+
+            ScrollView::new(cx, |cx| {
+                Label::new(cx, Inspector::details).width(Stretch(1.0)).text_wrap(true);
+            })
+            .height(Stretch(1.0));
+
+            Textbox::new(cx, Inspector::edit_text)
+                .placeholder("property: value;")
+                .width(Stretch(1.0))
+                .on_edit(|cx, text| cx.emit(InspectorEvent::SetEditText(text)))
+                .on_submit(|cx, _, enter| {
+                    if enter {
+                        cx.emit(InspectorEvent::Apply);
+                    }
+                });
+
+            cx.emit(InspectorEvent::Refresh);
+        })
+        .position_type(PositionType::Absolute)
+        .left(Stretch(1.0))
+        .right(Pixels(0.0))
+        .width(Pixels(PANEL_WIDTH))
+        .height(Stretch(1.0))
+        .padding(Pixels(8.0))
+        .gap(Pixels(8.0))
+        .background_color(Color::rgba(30, 30, 30, 240))
+        .color(Color::rgb(230, 230, 230))
+        .font_size(12.0)
+        .z_index(1000)
+    }
+
+    // Takes a snapshot of the tree, excluding the inspector itself, and updates the details of the selected view.
+    fn refresh(&mut self, cx: &mut EventContext) {
+        let inspector = cx.current();
+
+        self.entries = TreeIterator::full(cx.tree)
+            .filter(|entity| {
+                !cx.tree.is_ignored(*entity)
+                    && *entity != inspector
+                    && !entity.is_descendant_of(cx.tree, inspector)
+            })
+            .map(|entity| InspectorEntry {
+                entity,
+                depth: entity
+                    .parent_iter(cx.tree)
+                    .skip(1)
+                    .filter(|e| !cx.tree.is_ignored(*e))
+                    .count(),
+                label: entry_label(cx, entity),
+            })
+            .collect();
+
+        self.selected =
+            self.selected.filter(|selected| self.entries.iter().any(|e| e.entity == *selected));
+        self.details = self.selected.map(|entity| details(cx, entity)).unwrap_or_default();
+    }
+}
+
+impl View for Inspector {
+    fn element(&self) -> Option<&'static str> {
+        Some("inspector")
+    }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.take(|inspector_event, _| match inspector_event {
+            InspectorEvent::Refresh => {
+                self.refresh(cx);
+            }
+
+            InspectorEvent::Select(entity) => {
+                self.selected = Some(entity);
+                self.details = details(cx, entity);
+                cx.needs_redraw();
+            }
+
+            InspectorEvent::SetEditText(text) => {
+                self.edit_text = text;
+            }
+
+            InspectorEvent::Apply => {
+                let Some(entity) = self.selected else { return };
+
+                // Edited properties are applied with a rule which only matches the selected view.
+                let class = format!("inspector-{}", entity.index());
+                if cx.style.add_inspector_rule(&class, &self.edit_text) {
+                    if let Some(classes) = cx.style.classes.get_mut(entity) {
+                        classes.insert(class);
+                    }
+
+                    cx.with_current(entity, |cx| {
+                        cx.needs_restyle();
+                        cx.needs_relayout();
+                        cx.needs_redraw();
+                    });
+
+                    self.edit_text.clear();
+                    self.refresh(cx);
+                }
+            }
+        });
+    }
+}
+
+// Returns the element name of a view followed by its id and classes, e.g. `button#submit.accent`.
+fn entry_label(cx: &EventContext, entity: Entity) -> String {
+    let mut label =
+        cx.views.get(&entity).and_then(|view| view.element()).unwrap_or("view").to_string();
+
+    if let Some(id) = cx.style.ids.get(entity) {
+        label.push('#');
+        label.push_str(id);
+    }
+
+    if let Some(classes) = cx.style.classes.get(entity) {
+        let mut classes = classes.iter().collect::<Vec<_>>();
+        classes.sort();
+        for class in classes {
+            label.push('.');
+            label.push_str(class);
+        }
+    }
+
+    label
+}
+
+// Returns a description of the pseudo-classes, layout and matched style rules of a view.
+fn details(cx: &EventContext, entity: Entity) -> String {
+    let pseudo_classes = cx
+        .style
+        .pseudo_classes
+        .get(entity)
+        .map(|flags| {
+            flags
+                .iter_names()
+                .map(|(name, _)| format!(":{}", name.to_lowercase().replace('_', "-")))
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .unwrap_or_default();
+
+    let scale_factor = cx.scale_factor();
+    let bounds = cx.cache.get_bounds(entity);
+
+    let mut details = format!(
+        "{}\nEntity: {}\nPseudo-classes: {}\n\nLayout\nx: {:.1} y: {:.1} width: {:.1} height: {:.1}\nDeclared width: {:?}\nDeclared height: {:?}\n\nMatched rules",
+        entry_label(cx, entity),
+        entity,
+        pseudo_classes,
+        bounds.x / scale_factor,
+        bounds.y / scale_factor,
+        bounds.w / scale_factor,
+        bounds.h / scale_factor,
+        cx.style.width.get(entity),
+        cx.style.height.get(entity),
+    );
+
+    let mut filter = BloomFilter::default();
+    compute_element_hash(entity, cx.tree, cx.style, &mut filter);
+    for (rule, _) in compute_matched_rules(entity, cx.style, cx.tree, &filter) {
+        if let Some(style_rule) = cx.style.rules.get(&rule) {
+            details.push('\n');
+            details.push_str(&style_rule.selector.to_css_string());
+        }
+    }
+
+    details
+}
@@ -9,6 +9,9 @@ use crate::text::TextContext;
 pub struct SubLayout<'a> {
     pub text_context: &'a mut TextContext,
     pub resource_manager: &'a ResourceManager,
+    /// Whether text is measured using the bounds from the previous layout rather than being laid out again, which is
+    /// used to cheaply follow the size of a window while it's being resized.
+    pub reuse_text: bool,
 }
 
 impl Node for Entity {
@@ -67,6 +70,15 @@ impl Node for Entity {
     }
 
     fn width(&self, store: &Self::Store) -> Option<morphorm::Units> {
+        // A column with wrapped children grows to fit its lines.
+        if store.width.get(*self) == Some(&Units::Auto)
+            && store.layout_type.get(*self).copied().unwrap_or_default() == LayoutType::Column
+        {
+            if let Some(size) = store.wrapped_size.get(*self) {
+                return Some(Units::Pixels(*size));
+            }
+        }
+
         store.width.get(*self).cloned().map(|w| match w {
             Units::Pixels(val) => Units::Pixels(store.logical_to_physical(val)),
             t => t,
@@ -74,7 +86,9 @@ impl Node for Entity {
     }
 
     fn min_width(&self, store: &Self::Store) -> Option<morphorm::Units> {
-        store.min_width.get(*self).cloned().map(|w| match w {
+        let min_width = store.min_width.get(*self).cloned();
+        let content_width = store.min_content_size.get(*self).map(|(width, _)| *width);
+        with_min_content(min_width, content_width).map(|w| match w {
             Units::Pixels(val) => Units::Pixels(store.logical_to_physical(val)),
             t => t,
         })
@@ -94,7 +108,7 @@ impl Node for Entity {
         width: Option<f32>,
         height: Option<f32>,
     ) -> Option<(f32, f32)> {
-        if let Some(paragraph) = sublayout.text_context.text_paragraphs.get_mut(*self) {
+        if sublayout.text_context.text_paragraphs.get(*self).is_some() {
             // // If the width is known use that, else use 0 for wrapping text or 999999 for non-wrapping text.
             // let max_width = if let Some(width) = width {
             //     let padding_left =
@@ -115,8 +129,6 @@ impl Node for Entity {
             //     f32::MAX
             // };
 
-            paragraph.layout(f32::MAX);
-
             let padding_left = store.padding_left.get(*self).copied().unwrap_or_default();
             let padding_right = store.padding_right.get(*self).copied().unwrap_or_default();
             let padding_top = store.padding_top.get(*self).copied().unwrap_or_default();
@@ -161,6 +173,18 @@ impl Node for Entity {
             p_left += border_width;
             p_top += border_width;
 
+            // Reuse the size of the text from the previous layout if it shouldn't be laid out again.
+            if sublayout.reuse_text {
+                if let Some(text_bounds) = sublayout.text_context.text_bounds.get(*self) {
+                    let width = width.unwrap_or(text_bounds.w.round() + child_space_x);
+                    let height = height.unwrap_or(text_bounds.h.round() + child_space_y);
+                    return Some((width, height));
+                }
+            }
+
+            let paragraph = sublayout.text_context.text_paragraphs.get_mut(*self)?;
+            paragraph.layout(f32::MAX);
+
             let text_width = match (
                 store.text_wrap.get(*self).copied().unwrap_or(true),
                 store.text_overflow.get(*self).copied(),
@@ -234,7 +258,7 @@ impl Node for Entity {
                                         svg.inner().fContainerSize.fWidth * store.scale_factor(),
                                     );
                                     max_height = max_height.max(
-                                        svg.inner().fContainerSize.fWidth * store.scale_factor(),
+                                        svg.inner().fContainerSize.fHeight * store.scale_factor(),
                                     );
                                 }
 
@@ -255,6 +279,15 @@ impl Node for Entity {
     }
 
     fn height(&self, store: &Self::Store) -> Option<morphorm::Units> {
+        // A row with wrapped children grows to fit its lines.
+        if store.height.get(*self) == Some(&Units::Auto)
+            && store.layout_type.get(*self).copied().unwrap_or_default() == LayoutType::Row
+        {
+            if let Some(size) = store.wrapped_size.get(*self) {
+                return Some(Units::Pixels(*size));
+            }
+        }
+
         store.height.get(*self).cloned().map(|h| match h {
             Units::Pixels(val) => Units::Pixels(store.logical_to_physical(val)),
             t => t,
@@ -262,7 +295,9 @@ impl Node for Entity {
     }
 
     fn min_height(&self, store: &Self::Store) -> Option<morphorm::Units> {
-        store.min_height.get(*self).cloned().map(|h| match h {
+        let min_height = store.min_height.get(*self).cloned();
+        let content_height = store.min_content_size.get(*self).map(|(_, height)| *height);
+        with_min_content(min_height, content_height).map(|h| match h {
             Units::Pixels(val) => Units::Pixels(store.logical_to_physical(val)),
             t => t,
         })
@@ -416,18 +451,47 @@ impl Node for Entity {
     }
 
     fn column_start(&self, store: &Self::Store) -> Option<usize> {
-        store.column_start.get(*self).copied()
+        store
+            .column_start
+            .get(*self)
+            .copied()
+            .or_else(|| store.grid_column.get(*self).map(|placement| placement.start_index()))
     }
 
     fn column_span(&self, store: &Self::Store) -> Option<usize> {
-        store.column_span.get(*self).copied()
+        store
+            .column_span
+            .get(*self)
+            .copied()
+            .or_else(|| store.grid_column.get(*self).map(|placement| placement.span))
     }
 
     fn row_start(&self, store: &Self::Store) -> Option<usize> {
-        store.row_start.get(*self).copied()
+        store
+            .row_start
+            .get(*self)
+            .copied()
+            .or_else(|| store.grid_row.get(*self).map(|placement| placement.start_index()))
     }
 
     fn row_span(&self, store: &Self::Store) -> Option<usize> {
-        store.row_span.get(*self).copied()
+        store
+            .row_span
+            .get(*self)
+            .copied()
+            .or_else(|| store.grid_row.get(*self).map(|placement| placement.span))
+    }
+}
+
+// Combines the minimum size of a view with the minimum logical size of the content of a window, set with
+// `Context::set_minimum_content_size`. A minimum size in pixels is raised to the content size, while other units are
+// replaced by it as they can't be compared before layout.
+fn with_min_content(min_size: Option<Units>, content_size: Option<f32>) -> Option<Units> {
+    match (min_size, content_size) {
+        (Some(Units::Pixels(size)), Some(content_size)) => {
+            Some(Units::Pixels(size.max(content_size)))
+        }
+        (_, Some(content_size)) => Some(Units::Pixels(content_size)),
+        (min_size, None) => min_size,
     }
 }
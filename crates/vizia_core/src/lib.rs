@@ -21,16 +21,20 @@ pub(crate) mod entity;
 pub mod environment;
 pub mod events;
 pub mod input;
+pub(crate) mod inspector;
 pub mod layout;
 pub mod localization;
 pub mod model;
 pub mod modifiers;
+#[cfg(feature = "profiling")]
+pub mod profiling;
 pub mod resource;
 pub mod style;
 pub(crate) mod systems;
 pub(crate) mod text;
 #[doc(hidden)]
 pub mod tree;
+pub mod undo;
 /// Helper utilities
 pub mod util;
 pub mod view;
@@ -58,35 +62,48 @@ pub mod backend {
 #[doc(hidden)]
 pub mod prelude {
     pub use super::binding::{
-        Binding, Data, Index, Lens, LensExt, LensValue, Map, MapRef, Res, ResGet, StaticLens, Then,
-        UnwrapLens, Wrapper,
+        Binding, Data, Index, Lens, LensExt, LensValue, Map, MapMemo, MapRef, Res, ResGet,
+        StaticLens, StreamBinding, Then, UnwrapLens, Wrapper,
     };
 
     pub use super::impl_res_simple;
 
     pub use crate::model::Model;
+    #[cfg(feature = "serde")]
+    pub use crate::model::PersistentModel;
+    #[cfg(feature = "profiling")]
+    pub use crate::profiling::{FrameStats, FrameStatsOverlay};
 
-    pub use super::animation::{Animation, AnimationBuilder, KeyframeBuilder};
+    pub use super::animation::{
+        Animation, AnimationBuilder, AnimationDirection, AnimationOptions, IterationCount,
+        KeyframeBuilder,
+    };
+    #[cfg(feature = "clipboard")]
+    pub use super::context::ClipboardImage;
     pub use super::context::{
-        AccessContext, AccessNode, Context, ContextProxy, DataContext, DrawContext, EmitContext,
-        EventContext, ProxyEmitError, WindowState,
+        AccessContext, AccessNode, CancellationToken, Context, ContextProxy, DataContext,
+        DrawContext, EmitContext, EventContext, JobHandle, ProxyEmitError, Responder, Response,
+        WindowState,
     };
     pub use super::entity::Entity;
     pub use super::environment::{AppTheme, Environment, EnvironmentEvent, ThemeMode};
-    pub use super::events::{Event, Propagation, Timer, TimerAction};
+    pub use super::events::{Event, EventPhase, InterceptorId, Propagation, Timer, TimerAction};
     pub use super::include_style;
     pub use super::input::{Keymap, KeymapEntry, KeymapEvent};
     pub use super::layout::{BoundingBox, GeoChanged};
     pub use super::localization::{Localized, ToStringLocalized};
     pub use super::modifiers::{
-        AbilityModifiers, AccessibilityModifiers, ActionModifiers, LayoutModifiers,
-        LinearGradientBuilder, ShadowBuilder, StyleModifiers, TextModifiers,
+        AbilityModifiers, AccessibilityModifiers, ActionModifiers, GestureEvent, GesturePhase,
+        LayoutModifiers, LinearGradientBuilder, ShadowBuilder, StyleModifiers, SwipeDirection,
+        TextModifiers,
     };
     pub use super::resource::{ImageId, ImageRetentionPolicy};
     pub use super::util::{IntoCssStr, CSS};
     pub use super::view::{Handle, View};
     pub use super::views::*;
-    pub use super::window::{DropData, WindowEvent};
+    pub use super::window::{
+        CursorGrabMode, CursorImage, DeviceId, DragPayload, DropData, WindowEvent,
+    };
     pub use accesskit::{Action, Live, Role};
     pub use skia_safe::Canvas;
     pub use vizia_derive::{Data, Lens};
@@ -96,6 +113,8 @@ pub mod prelude {
     pub use vizia_window::{Anchor, AnchorTarget, WindowButtons, WindowPosition, WindowSize};
 
     pub use super::style::*;
+    pub use super::text::{FontScript, InputMask};
+    pub use super::undo::{UndoEvent, UndoManager};
 
     pub use morphorm::Units::*;
     pub use morphorm::{LayoutType, PositionType, Units};
@@ -68,6 +68,39 @@
 //! # }
 //! Label::new(cx, Localized::new("welcome").arg("user", AppData::user));
 //! ```
+//! The translation is resolved again whenever the locale or the data bound to one of its arguments changes.
+//!
+//! ## Plurals
+//! A numeric argument can be used with a [selector](https://projectfluent.org/fluent/guide/selectors.html) to choose a
+//! variant based on the plural rules of the current locale.
+//! ```ftl
+//! unread-count =
+//!     { $count ->
+//!         [one] You have one unread message.
+//!        *[other] You have { $count } unread messages.
+//!     }
+//! ```
+//! ```ignore
+//! # use vizia_core::prelude::*;
+//! # let mut cx = &mut Context::default();
+//! # #[derive(Lens)]
+//! # pub struct AppData {
+//! #   count: u32,
+//! # }
+//! Label::new(cx, Localized::new("unread-count").arg("count", AppData::count));
+//! ```
+//! The built-in `NUMBER` function formats a number with options, such as selecting an ordinal category.
+//! ```ftl
+//! place =
+//!     { NUMBER($position, type: "ordinal") ->
+//!         [one] { $position }st place
+//!         [two] { $position }nd place
+//!         [few] { $position }rd place
+//!        *[other] { $position }th place
+//!     }
+//! ```
+//! A locale without a translation of its own uses the closest available translation, so `en-GB` uses an `en-US`
+//! translation.
 use crate::context::LocalizationContext;
 use crate::prelude::*;
 use fluent_bundle::FluentArgs;
@@ -57,11 +57,7 @@ pub trait Model: 'static + Sized {
     /// }
     /// ```
     fn build(self, cx: &mut Context) {
-        let current = if cx.tree.is_ignored(cx.current) {
-            cx.tree.get_layout_parent(cx.current).unwrap()
-        } else {
-            cx.current
-        };
+        let current = model_entity(cx);
 
         if let Some(models) = cx.models.get_mut(&current) {
             models.insert(TypeId::of::<Self>(), Box::new(self));
@@ -115,11 +111,83 @@ pub trait Model: 'static + Sized {
     }
 }
 
+/// A [Model] which can be saved and restored with [`Context::serialize_models`] and [`Context::restore_models`].
+///
+/// A model can also be registered with [`Context::persist`] to save its state to disk when the application exits and
+/// restore it when the application starts.
+///
+/// This trait is implemented for any model which implements `serde::Serialize` and `serde::Deserialize`, and
+/// requires the `serde` feature.
+///
+/// # Examples
+///
+/// ```ignore
+/// # use vizia_core::prelude::*;
+/// #[derive(Lens, serde::Serialize, serde::Deserialize)]
+/// pub struct FormData {
+///     name: String,
+/// }
+///
+/// impl Model for FormData {}
+///
+/// # let cx = &mut Context::default();
+/// FormData { name: String::new() }.build_persistent(cx);
+///
+/// let state = cx.serialize_models();
+/// ```
+#[cfg(feature = "serde")]
+pub trait PersistentModel: Model + serde::Serialize + serde::de::DeserializeOwned {
+    /// Build the model data into the application tree and register it for serialization.
+    fn build_persistent(self, cx: &mut Context) {
+        let entity = model_entity(cx);
+        cx.persistence.register::<Self>(entity, None);
+        self.build(cx);
+    }
+}
+
+// Returns the entity which a model built in the current view is attached to.
+fn model_entity(cx: &Context) -> Entity {
+    if cx.tree.is_ignored(cx.current) {
+        cx.tree.get_layout_parent(cx.current).unwrap()
+    } else {
+        cx.current
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Model + serde::Serialize + serde::de::DeserializeOwned> PersistentModel for T {}
+
+/// Type-erased functions used to save and restore a [PersistentModel].
+#[cfg(feature = "serde")]
+pub(crate) struct ModelSerializer {
+    pub name: &'static str,
+    pub serialize: fn(&dyn ModelData) -> Option<serde_json::Value>,
+    pub deserialize: fn(serde_json::Value) -> Option<Box<dyn ModelData>>,
+}
+
+#[cfg(feature = "serde")]
+impl ModelSerializer {
+    pub fn new<M: PersistentModel>() -> Self {
+        Self {
+            name: std::any::type_name::<M>(),
+            serialize: |model| {
+                model.downcast_ref::<M>().and_then(|model| serde_json::to_value(model).ok())
+            },
+            deserialize: |value| {
+                serde_json::from_value::<M>(value)
+                    .ok()
+                    .map(|model| Box::new(model) as Box<dyn ModelData>)
+            },
+        }
+    }
+}
+
 pub(crate) trait ModelData: Any {
     #[allow(unused_variables)]
     fn event(&mut self, cx: &mut EventContext, event: &mut Event) {}
 
     fn as_any_ref(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
     #[cfg(debug_assertions)]
     fn name(&self) -> Option<&'static str>;
 }
@@ -128,6 +196,10 @@ impl dyn ModelData {
     pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
         self.as_any_ref().downcast_ref()
     }
+
+    pub fn downcast_mut<T: Any>(&mut self) -> Option<&mut T> {
+        self.as_any_mut().downcast_mut()
+    }
 }
 
 impl<T: Model> ModelData for T {
@@ -139,6 +211,10 @@ impl<T: Model> ModelData for T {
         self
     }
 
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
     #[cfg(debug_assertions)]
     fn name(&self) -> Option<&'static str> {
         <T as Model>::name(self)
@@ -161,3 +237,55 @@ impl<'a> ModelOrView<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::context::backend::BackendContext;
+    use crate::context::test_support::window_context;
+    use crate::prelude::*;
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn model_round_trip() {
+        use std::any::TypeId;
+
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Counter {
+            count: u32,
+        }
+
+        impl Model for Counter {}
+
+        fn build(count: u32) -> (BackendContext, Entity, Entity) {
+            let mut cx = window_context(400, 300);
+            let stack =
+                VStack::new(cx.context(), |cx| Counter { count }.build_persistent(cx)).entity();
+            let plain = VStack::new(cx.context(), |cx| Counter { count }.build(cx)).entity();
+            (cx, stack, plain)
+        }
+
+        fn count(cx: &BackendContext, entity: Entity) -> Option<u32> {
+            cx.0.models
+                .get(&entity)
+                .and_then(|models| models.get(&TypeId::of::<Counter>()))
+                .and_then(|model| model.downcast_ref::<Counter>())
+                .map(|counter| counter.count)
+        }
+
+        // Only the model built with `build_persistent` is serialized, not other models of the same type.
+        let (cx, _, _) = build(5);
+        let state = cx.0.serialize_models();
+        assert_eq!(state.len(), 1);
+
+        // The state is restored into a model at the same position in a rebuilt tree.
+        let (mut cx, stack, plain) = build(0);
+        assert_eq!(cx.0.restore_models(&state), 1);
+        assert_eq!(count(&cx, stack), Some(5));
+        assert_eq!(count(&cx, plain), Some(0));
+
+        // Entries which don't match a model are ignored.
+        let mut state = state;
+        state.insert(String::from("unknown"), serde_json::Value::Null);
+        assert_eq!(cx.0.restore_models(&state), 1);
+    }
+}
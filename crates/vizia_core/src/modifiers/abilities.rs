@@ -8,6 +8,7 @@ pub trait AbilityModifiers: internal::Modifiable {
     /// Accepts a bool or a lens to some boolean state.
     /// Views which cannot be hovered will not receive mouse input events unless
     /// the view has captured the mouse input, see [`cx.capture()`](crate::prelude::EventContext::capture).
+    /// Unlike the `pointer-events` style property, this also applies to all of the descendants of the view.
     ///
     /// # Example
     /// ```
@@ -18,7 +19,7 @@ pub trait AbilityModifiers: internal::Modifiable {
     /// ```
     fn hoverable<U: Into<bool>>(mut self, state: impl Res<U>) -> Self {
         let entity = self.entity();
-        let current = self.entity();
+        let current = self.current();
         self.context().with_current(current, move |cx| {
             state.set_or_bind(cx, entity, move |cx, v| {
                 let val = v.get(cx).into();
@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use crate::views::ContextMenu;
 use std::any::TypeId;
 
 #[derive(Lens)]
@@ -78,6 +79,8 @@ pub(crate) struct ActionsModel {
     pub(crate) on_geo_changed: Option<Box<dyn Fn(&mut EventContext, GeoChanged) + Send + Sync>>,
     pub(crate) on_drag_start: Option<Box<dyn Fn(&mut EventContext) + Send + Sync>>,
     pub(crate) on_drop: Option<Box<dyn Fn(&mut EventContext, DropData) + Send + Sync>>,
+    pub(crate) on_gesture: Option<Box<dyn Fn(&mut EventContext, &GestureEvent) + Send + Sync>>,
+    gestures: Option<GestureRecognizer>,
 }
 
 impl ActionsModel {
@@ -98,6 +101,8 @@ impl ActionsModel {
             on_geo_changed: None,
             on_drag_start: None,
             on_drop: None,
+            on_gesture: None,
+            gestures: None,
         }
     }
 }
@@ -166,12 +171,23 @@ impl Model for ActionsModel {
             ActionsEvent::OnDrop(on_drop) => {
                 self.on_drop = Some(on_drop);
             }
+
+            ActionsEvent::OnGesture(on_gesture) => {
+                self.on_gesture = Some(on_gesture);
+                if self.gestures.is_none() {
+                    self.gestures = Some(GestureRecognizer::new(cx));
+                }
+            }
         });
 
+        if let (Some(gestures), Some(on_gesture)) = (&mut self.gestures, &self.on_gesture) {
+            gestures.event(cx, event, on_gesture.as_ref());
+        }
+
         event.map(|window_event, meta| match window_event {
             WindowEvent::Press { mouse } => {
                 let over = if *mouse { cx.hovered() } else { cx.focused() };
-                if cx.current() != over && !over.is_descendant_of(cx.tree, cx.current()) {
+                if !cx.is_press_over(meta, over) && !over.is_descendant_of(cx.tree, cx.current()) {
                     return;
                 }
 
@@ -184,7 +200,7 @@ impl Model for ActionsModel {
 
             WindowEvent::PressDown { mouse } => {
                 let over = if *mouse { cx.hovered() } else { cx.focused() };
-                if cx.current() != over && !over.is_descendant_of(cx.tree, cx.current()) {
+                if !cx.is_press_over(meta, over) && !over.is_descendant_of(cx.tree, cx.current()) {
                     return;
                 }
                 if !cx.is_disabled() && cx.current == meta.target {
@@ -312,6 +328,7 @@ pub(crate) enum ActionsEvent {
     OnGeoChanged(Box<dyn Fn(&mut EventContext, GeoChanged) + Send + Sync>),
     OnDragStart(Box<dyn Fn(&mut EventContext) + Send + Sync>),
     OnDrop(Box<dyn Fn(&mut EventContext, DropData) + Send + Sync>),
+    OnGesture(Box<dyn Fn(&mut EventContext, &GestureEvent) + Send + Sync>),
 }
 
 /// Modifiers which add an action callback to a view.
@@ -488,6 +505,22 @@ pub trait ActionModifiers<V> {
     /// Adds a popup menu to the view.
     fn menu<C: FnOnce(&mut Context) -> Handle<'_, T>, T: View>(self, content: C) -> Self;
 
+    /// Adds a popup menu which opens at the position of the cursor when the view is right-clicked.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use vizia_core::prelude::*;
+    /// # let mut cx = &mut Context::default();
+    /// Element::new(cx).on_context_menu(|cx| {
+    ///     MenuButton::new(cx, |_| debug!("Copy"), |cx| Label::new(cx, "Copy"));
+    ///     MenuDivider::new(cx);
+    ///     MenuButton::new(cx, |_| debug!("Paste"), |cx| Label::new(cx, "Paste"));
+    /// });
+    /// ```
+    fn on_context_menu<F>(self, menu: F) -> Self
+    where
+        F: 'static + Fn(&mut Context);
+
     /// Adds a callback which is performed when the view is dragged during a drag and drop operation.
     fn on_drag<F>(self, action: F) -> Self
     where
@@ -497,6 +530,23 @@ pub trait ActionModifiers<V> {
     fn on_drop<F>(self, action: F) -> Self
     where
         F: 'static + Fn(&mut EventContext, DropData) + Send + Sync;
+
+    /// Adds a callback which is performed when a pan, pinch, long press, swipe, or double tap gesture is recognized
+    /// on the view. See [`GestureEvent`] for how each gesture is recognized.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use vizia_core::prelude::*;
+    /// # let mut cx = &mut Context::default();
+    /// Element::new(cx).on_gesture(|_, gesture| match gesture {
+    ///     GestureEvent::Pan { dx, dy, .. } => debug!("Panned by {} {}", dx, dy),
+    ///     GestureEvent::Pinch { scale, .. } => debug!("Zoomed by {}", scale),
+    ///     _ => {}
+    /// });
+    /// ```
+    fn on_gesture<F>(self, action: F) -> Self
+    where
+        F: 'static + Fn(&mut EventContext, &GestureEvent) + Send + Sync;
 }
 
 // If the entity doesn't have an `ActionsModel` then add one to the entity
@@ -562,6 +612,19 @@ impl<V: View> ActionModifiers<V> for Handle<'_, V> {
         self
     }
 
+    fn on_context_menu<F>(self, menu: F) -> Self
+    where
+        F: 'static + Fn(&mut Context),
+    {
+        let entity = self.entity();
+
+        self.cx.with_current(entity, |cx| {
+            ContextMenu::new(cx, menu);
+        });
+
+        self
+    }
+
     fn on_press<F>(mut self, action: F) -> Self
     where
         F: 'static + Fn(&mut EventContext) + Send + Sync,
@@ -802,4 +865,90 @@ impl<V: View> ActionModifiers<V> for Handle<'_, V> {
 
         self
     }
+
+    fn on_gesture<F>(self, action: F) -> Self
+    where
+        F: 'static + Fn(&mut EventContext, &GestureEvent) + Send + Sync,
+    {
+        build_action_model(self.cx, self.entity);
+
+        self.cx.emit_custom(
+            Event::new(ActionsEvent::OnGesture(Box::new(action)))
+                .target(self.entity)
+                .origin(self.entity),
+        );
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::context::test_support::{update, window_context};
+    use crate::prelude::*;
+
+    #[test]
+    fn press() {
+        let mut cx = window_context(400, 300);
+
+        let presses = Arc::new(Mutex::new(Vec::new()));
+
+        let mut elements = Vec::new();
+        for disabled in [false, true] {
+            let on_press_down = presses.clone();
+            let on_press = presses.clone();
+            elements.push(
+                Element::new(cx.context())
+                    .disabled(disabled)
+                    .on_press_down(move |_| on_press_down.lock().unwrap().push(("down", disabled)))
+                    .on_press(move |_| on_press.lock().unwrap().push(("press", disabled)))
+                    .entity(),
+            );
+        }
+
+        update(&mut cx);
+
+        // The synthesized press doesn't depend on the hovered or focused view.
+        for element in elements {
+            cx.0.press(element);
+        }
+
+        update(&mut cx);
+
+        // Disabled views ignore the press.
+        assert_eq!(*presses.lock().unwrap(), [("down", false), ("press", false)]);
+    }
+
+    #[test]
+    fn press_unfocused_toggle() {
+        #[derive(Lens)]
+        struct AppData {
+            on: bool,
+        }
+
+        impl Model for AppData {}
+
+        let mut cx = window_context(400, 300);
+        AppData { on: false }.build(cx.context());
+
+        let toggles = Arc::new(Mutex::new(0));
+        let on_toggle = toggles.clone();
+        let toggle = ToggleButton::new(cx.context(), AppData::on, |cx| Label::new(cx, "Toggle"))
+            .on_toggle(move |_| *on_toggle.lock().unwrap() += 1)
+            .entity();
+        update(&mut cx);
+
+        // A press sent directly to the view isn't synthesized and still requires it to be focused.
+        assert_ne!(cx.0.focused, toggle);
+        cx.0.emit_to(toggle, WindowEvent::Press { mouse: false });
+        update(&mut cx);
+        assert_eq!(*toggles.lock().unwrap(), 0);
+
+        // Accessibility clicks are synthesized presses, which don't require the view to be focused first.
+        cx.0.press(toggle);
+        update(&mut cx);
+        assert_eq!(*toggles.lock().unwrap(), 1);
+    }
 }
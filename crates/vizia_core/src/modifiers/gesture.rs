@@ -0,0 +1,293 @@
+use std::collections::VecDeque;
+
+use crate::prelude::*;
+
+// The distance in logical pixels the pointer must move while pressed before a pan begins.
+const PAN_THRESHOLD: f32 = 4.0;
+// How long the pointer must be held without moving to trigger a long press.
+const LONG_PRESS_DELAY: Duration = Duration::from_millis(500);
+// The speed in logical pixels per second a pan must be released at to trigger a swipe.
+const SWIPE_VELOCITY: f32 = 1000.0;
+// The period over which the velocity of the pointer is measured.
+const VELOCITY_WINDOW: Duration = Duration::from_millis(100);
+// The change in scale of a pinch for each line scrolled while the ctrl key is held.
+const SCROLL_PINCH_SCALE: f32 = 1.1;
+
+/// The phase of a continuous gesture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GesturePhase {
+    /// The gesture has been recognized.
+    Began,
+    /// The gesture has moved.
+    Changed,
+    /// The gesture has finished.
+    Ended,
+}
+
+/// The direction of a swipe gesture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwipeDirection {
+    /// The pointer moved towards the left.
+    Left,
+    /// The pointer moved towards the right.
+    Right,
+    /// The pointer moved upwards.
+    Up,
+    /// The pointer moved downwards.
+    Down,
+}
+
+/// A gesture recognized from the mouse and touchpad input of a view, which can be handled with the
+/// [`on_gesture`](crate::modifiers::ActionModifiers::on_gesture) modifier.
+///
+/// Positions and deltas are in physical pixels, and velocities are in physical pixels per second.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GestureEvent {
+    /// The left mouse button was pressed on the view and dragged further than a small threshold. The view captures
+    /// the mouse for the duration of the pan.
+    Pan {
+        /// The phase of the pan.
+        phase: GesturePhase,
+        /// The horizontal distance moved since the previous pan event.
+        dx: f32,
+        /// The vertical distance moved since the previous pan event.
+        dy: f32,
+        /// The horizontal velocity of the pointer.
+        velocity_x: f32,
+        /// The vertical velocity of the pointer.
+        velocity_y: f32,
+    },
+    /// A pinch on a touchpad, or a scroll of the mouse wheel while the ctrl key is held.
+    Pinch {
+        /// The factor by which the scale changed since the previous pinch event, greater than one when zooming in.
+        scale: f32,
+        /// The horizontal position of the pointer at the center of the pinch.
+        x: f32,
+        /// The vertical position of the pointer at the center of the pinch.
+        y: f32,
+    },
+    /// The left mouse button was held on the view without moving.
+    LongPress {
+        /// The horizontal position of the pointer.
+        x: f32,
+        /// The vertical position of the pointer.
+        y: f32,
+    },
+    /// A pan was released while moving quickly.
+    Swipe {
+        /// The main direction of the swipe.
+        direction: SwipeDirection,
+        /// The horizontal velocity of the pointer when released.
+        velocity_x: f32,
+        /// The vertical velocity of the pointer when released.
+        velocity_y: f32,
+    },
+    /// The left mouse button was double clicked on the view.
+    DoubleTap {
+        /// The horizontal position of the pointer.
+        x: f32,
+        /// The vertical position of the pointer.
+        y: f32,
+    },
+}
+
+// Emitted by the long press timer of a recognizer to the view it belongs to.
+pub(crate) enum GestureRecognizerEvent {
+    LongPress,
+}
+
+// A position of the pointer at a moment in time.
+#[derive(Clone, Copy)]
+struct Sample {
+    x: f32,
+    y: f32,
+    time: Instant,
+}
+
+// Recognizes gestures from the window events received by a view.
+pub(crate) struct GestureRecognizer {
+    long_press_timer: Timer,
+    // Where and when the left mouse button was pressed, if it's still pressed.
+    press: Option<Sample>,
+    panning: bool,
+    // Recent positions of the pointer while pressed, used to measure its velocity.
+    samples: VecDeque<Sample>,
+}
+
+impl GestureRecognizer {
+    pub(crate) fn new(cx: &mut EventContext) -> Self {
+        let long_press_timer = cx.add_timer(LONG_PRESS_DELAY, None, |cx, action| {
+            if let TimerAction::Tick(_) = action {
+                cx.emit(GestureRecognizerEvent::LongPress);
+            }
+        });
+
+        Self { long_press_timer, press: None, panning: false, samples: VecDeque::new() }
+    }
+
+    pub(crate) fn event(
+        &mut self,
+        cx: &mut EventContext,
+        event: &mut Event,
+        on_gesture: &dyn Fn(&mut EventContext, &GestureEvent),
+    ) {
+        event.map(|recognizer_event, meta| match recognizer_event {
+            GestureRecognizerEvent::LongPress => {
+                if meta.target != cx.current() {
+                    return;
+                }
+
+                cx.stop_timer(self.long_press_timer);
+                if let Some(press) = self.press.filter(|_| !self.panning) {
+                    (on_gesture)(cx, &GestureEvent::LongPress { x: press.x, y: press.y });
+                }
+            }
+        });
+
+        event.map(|window_event, meta| match window_event {
+            WindowEvent::MouseDown(MouseButton::Left) => {
+                if cx.is_disabled() {
+                    return;
+                }
+
+                let press =
+                    Sample { x: cx.mouse().cursor_x, y: cx.mouse().cursor_y, time: Instant::now() };
+                self.press = Some(press);
+                self.panning = false;
+                self.samples.clear();
+                self.samples.push_back(press);
+                cx.start_timer(self.long_press_timer);
+            }
+
+            WindowEvent::MouseMove(x, y) => {
+                let Some(press) = self.press else {
+                    return;
+                };
+
+                let previous = self.samples.back().copied().unwrap_or(press);
+                let now = Instant::now();
+                self.samples.push_back(Sample { x: *x, y: *y, time: now });
+                while self.samples.len() > 2
+                    && now.duration_since(self.samples[0].time) > VELOCITY_WINDOW
+                {
+                    self.samples.pop_front();
+                }
+
+                let (velocity_x, velocity_y) = self.velocity(now);
+                if self.panning {
+                    (on_gesture)(
+                        cx,
+                        &GestureEvent::Pan {
+                            phase: GesturePhase::Changed,
+                            dx: *x - previous.x,
+                            dy: *y - previous.y,
+                            velocity_x,
+                            velocity_y,
+                        },
+                    );
+                } else {
+                    let (dx, dy) = (*x - press.x, *y - press.y);
+                    if dx.hypot(dy) > PAN_THRESHOLD * cx.scale_factor() {
+                        self.panning = true;
+                        cx.stop_timer(self.long_press_timer);
+                        cx.capture();
+                        (on_gesture)(
+                            cx,
+                            &GestureEvent::Pan {
+                                phase: GesturePhase::Began,
+                                dx,
+                                dy,
+                                velocity_x,
+                                velocity_y,
+                            },
+                        );
+                    }
+                }
+            }
+
+            WindowEvent::MouseUp(MouseButton::Left) => {
+                if self.press.take().is_none() {
+                    return;
+                }
+
+                cx.stop_timer(self.long_press_timer);
+                if !self.panning {
+                    return;
+                }
+
+                self.panning = false;
+                cx.release();
+
+                let (velocity_x, velocity_y) = self.velocity(Instant::now());
+                (on_gesture)(
+                    cx,
+                    &GestureEvent::Pan {
+                        phase: GesturePhase::Ended,
+                        dx: 0.0,
+                        dy: 0.0,
+                        velocity_x,
+                        velocity_y,
+                    },
+                );
+
+                if velocity_x.hypot(velocity_y) > SWIPE_VELOCITY * cx.scale_factor() {
+                    let direction = if velocity_x.abs() > velocity_y.abs() {
+                        if velocity_x > 0.0 {
+                            SwipeDirection::Right
+                        } else {
+                            SwipeDirection::Left
+                        }
+                    } else if velocity_y > 0.0 {
+                        SwipeDirection::Down
+                    } else {
+                        SwipeDirection::Up
+                    };
+
+                    (on_gesture)(cx, &GestureEvent::Swipe { direction, velocity_x, velocity_y });
+                }
+            }
+
+            WindowEvent::MouseDoubleClick(MouseButton::Left) => {
+                if !cx.is_disabled() {
+                    let (x, y) = (cx.mouse().cursor_x, cx.mouse().cursor_y);
+                    (on_gesture)(cx, &GestureEvent::DoubleTap { x, y });
+                }
+            }
+
+            WindowEvent::PinchGesture(delta) => {
+                let (x, y) = (cx.mouse().cursor_x, cx.mouse().cursor_y);
+                (on_gesture)(cx, &GestureEvent::Pinch { scale: 1.0 + *delta, x, y });
+                meta.consume();
+            }
+
+            WindowEvent::MouseScroll(_, lines) if cx.modifiers().ctrl() => {
+                let (x, y) = (cx.mouse().cursor_x, cx.mouse().cursor_y);
+                (on_gesture)(
+                    cx,
+                    &GestureEvent::Pinch { scale: SCROLL_PINCH_SCALE.powf(*lines), x, y },
+                );
+                meta.consume();
+            }
+
+            _ => {}
+        });
+    }
+
+    // Returns the velocity of the pointer over the recent samples, which is zero if the pointer hasn't moved recently.
+    fn velocity(&self, now: Instant) -> (f32, f32) {
+        let (Some(first), Some(last)) = (self.samples.front(), self.samples.back()) else {
+            return (0.0, 0.0);
+        };
+
+        if now.duration_since(last.time) > VELOCITY_WINDOW {
+            return (0.0, 0.0);
+        }
+
+        let elapsed = last.time.duration_since(first.time).as_secs_f32();
+        if elapsed <= 0.0 {
+            return (0.0, 0.0);
+        }
+
+        ((last.x - first.x) / elapsed, (last.y - first.y) / elapsed)
+    }
+}
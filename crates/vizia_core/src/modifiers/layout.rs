@@ -35,6 +35,28 @@ pub trait LayoutModifiers: internal::Modifiable {
         SystemFlags::RELAYOUT
     );
 
+    modifier!(
+        /// Sets the direction of the view, which is inherited by its descendants.
+        ///
+        /// When the direction is `Direction::Rtl` the horizontal positions of the children are mirrored, so rows are
+        /// laid out from the right and the left and right spacing of the children is swapped, and text is laid out
+        /// right-to-left.
+        ///
+        /// # Example
+        /// ```
+        /// # use vizia_core::prelude::*;
+        /// # let cx = &mut Context::default();
+        /// HStack::new(cx, |cx| {
+        ///     Label::new(cx, "First");
+        ///     Label::new(cx, "Second");
+        /// })
+        /// .direction(Direction::Rtl);
+        /// ```
+        direction,
+        Direction,
+        SystemFlags::RELAYOUT | SystemFlags::REFLOW | SystemFlags::REDRAW
+    );
+
     modifier!(
         /// Sets the position type of the view.
         ///
@@ -233,6 +255,66 @@ pub trait LayoutModifiers: internal::Modifiable {
         SystemFlags::RELAYOUT
     );
 
+    modifier!(
+        /// Overrides the alignment of the parent along its cross axis for this view only.
+        ///
+        /// For a parent with a `Row` layout type this controls the vertical position of the view,
+        /// and for a `Column` layout type the horizontal position.
+        ///
+        /// # Example
+        /// ```
+        /// # use vizia_core::prelude::*;
+        /// # let cx = &mut Context::default();
+        /// HStack::new(cx, |cx| {
+        ///     Element::new(cx).size(Pixels(50.0));
+        ///     Element::new(cx).size(Pixels(50.0)).align_self(SelfAlignment::Start);
+        /// })
+        /// .alignment(Alignment::Center);
+        /// ```
+        align_self,
+        SelfAlignment,
+        SystemFlags::RELAYOUT
+    );
+
+    modifier!(
+        /// Overrides the alignment of the parent along its main axis for this view only.
+        ///
+        /// Because relative children are stacked one after another along the main axis, this applies only to views
+        /// with a position type of `Absolute`.
+        justify_self,
+        SelfAlignment,
+        SystemFlags::RELAYOUT
+    );
+
+    modifier!(
+        /// Sets whether the children of a `Row` or `Column` stack can wrap onto multiple lines.
+        ///
+        /// When set to `FlexWrap::Wrap`, children which would exceed the main-axis size of the view are moved onto a new line.
+        /// Children within a line are separated by the main-axis gap, and lines are separated by the cross-axis gap,
+        /// with the size of each line determined by its largest child. A view with an `Auto` cross-axis size grows to
+        /// fit all of its lines.
+        ///
+        /// The `alignment` of the view positions the lines and the children within them, and `align_self` positions a
+        /// child within its line.
+        ///
+        /// # Example
+        /// ```
+        /// # use vizia_core::prelude::*;
+        /// # let cx = &mut Context::default();
+        /// HStack::new(cx, |cx| {
+        ///     for _ in 0..10 {
+        ///         Element::new(cx).size(Pixels(50.0));
+        ///     }
+        /// })
+        /// .width(Pixels(200.0))
+        /// .flex_wrap(FlexWrap::Wrap)
+        /// .gap(Pixels(10.0));
+        /// ```
+        flex_wrap,
+        FlexWrap,
+        SystemFlags::RELAYOUT
+    );
+
     /// Sets the space between the vew and its children.
     ///
     /// The child_space works by overriding the `Auto` space properties of its children.
@@ -91,6 +91,9 @@ pub use accessibility::*;
 mod actions;
 pub use actions::*;
 
+mod gesture;
+pub use gesture::*;
+
 mod layout;
 pub use layout::*;
 
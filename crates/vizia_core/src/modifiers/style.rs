@@ -57,7 +57,8 @@ pub trait StyleModifiers: internal::Modifiable {
             class_list.insert(name.to_string());
         }
 
-        self.context().needs_restyle(entity);
+        let cx = self.context();
+        cx.style.invalidate_class(&cx.tree, entity, name);
 
         self
     }
@@ -78,7 +79,7 @@ pub trait StyleModifiers: internal::Modifiable {
                     }
                 }
 
-                cx.needs_restyle(entity);
+                cx.style.invalidate_class(&cx.tree, entity, &name);
             });
         });
 
@@ -103,7 +104,30 @@ pub trait StyleModifiers: internal::Modifiable {
                 if let Some(pseudo_classes) = cx.style.pseudo_classes.get_mut(entity) {
                     pseudo_classes.set(PseudoClassFlags::CHECKED, val);
                 }
-                cx.needs_restyle(entity);
+                cx.style.invalidate_pseudo_classes(&cx.tree, entity, PseudoClassFlags::CHECKED);
+            });
+        });
+
+        self
+    }
+
+    /// Sets the indeterminate state of the view, which can be selected with the `:indeterminate` CSS pseudo-class
+    /// selector and is reported to assistive technologies as a mixed state.
+    fn indeterminate<U: Into<bool>>(mut self, state: impl Res<U>) -> Self {
+        let entity = self.entity();
+        let current = self.current();
+        self.context().with_current(current, move |cx| {
+            state.set_or_bind(cx, entity, move |cx, val| {
+                let val = val.get(cx).into();
+                if let Some(pseudo_classes) = cx.style.pseudo_classes.get_mut(entity) {
+                    pseudo_classes.set(PseudoClassFlags::INDETERMINATE, val);
+                }
+                cx.style.invalidate_pseudo_classes(
+                    &cx.tree,
+                    entity,
+                    PseudoClassFlags::INDETERMINATE,
+                );
+                cx.style.needs_access_update(entity);
             });
         });
 
@@ -171,7 +195,11 @@ pub trait StyleModifiers: internal::Modifiable {
                     pseudo_classes.set(PseudoClassFlags::READ_ONLY, val);
                 }
 
-                cx.needs_restyle(cx.current);
+                cx.style.invalidate_pseudo_classes(
+                    &cx.tree,
+                    cx.current,
+                    PseudoClassFlags::READ_ONLY,
+                );
             });
         });
 
@@ -189,7 +217,11 @@ pub trait StyleModifiers: internal::Modifiable {
                     pseudo_classes.set(PseudoClassFlags::READ_WRITE, val);
                 }
 
-                cx.needs_restyle(cx.current);
+                cx.style.invalidate_pseudo_classes(
+                    &cx.tree,
+                    cx.current,
+                    PseudoClassFlags::READ_WRITE,
+                );
             });
         });
 
@@ -207,7 +239,11 @@ pub trait StyleModifiers: internal::Modifiable {
                     pseudo_classes.set(PseudoClassFlags::PLACEHOLDER_SHOWN, val);
                 }
 
-                cx.needs_restyle(cx.current);
+                cx.style.invalidate_pseudo_classes(
+                    &cx.tree,
+                    cx.current,
+                    PseudoClassFlags::PLACEHOLDER_SHOWN,
+                );
             });
         });
 
@@ -252,16 +288,19 @@ pub trait StyleModifiers: internal::Modifiable {
 
     /// Sets the z-index of the view.
     ///
-    /// Views with a higher z-index will be rendered on top of those with a lower z-order.
-    /// Views with the same z-index are rendered in tree order.
+    /// A view with a non-zero z-index forms a stacking context, which is drawn and hit-tested above the other views of
+    /// the stacking context of its nearest ancestor with a non-zero z-index if the z-index is positive, or below them
+    /// if it's negative, and isn't clipped by its ancestors. Views with the same z-index are rendered in tree order.
     fn z_index<U: Into<i32>>(mut self, value: impl Res<U>) -> Self {
         let entity = self.entity();
-        // value.set_or_bind(self.context(), entity, |cx, v| {
-        let cx = self.context();
-        let value = value.get(cx).into();
-        cx.style.z_index.insert(entity, value);
-        cx.needs_redraw(entity);
-        // });
+        let current = self.current();
+        self.context().with_current(current, |cx| {
+            value.set_or_bind(cx, entity, move |cx, value| {
+                let value = value.get(cx).into();
+                cx.style.z_index.insert(entity, value);
+                cx.needs_redraw(entity);
+            });
+        });
 
         self
     }
@@ -711,6 +750,30 @@ pub trait StyleModifiers: internal::Modifiable {
         Scale,
         SystemFlags::REDRAW
     );
+
+    // Transitions
+
+    /// Animates changes to a style or layout property of the view with spring physics.
+    ///
+    /// The spring plays whenever the view matches a new style rule which sets the property, for example when it is
+    /// hovered or pressed, and overrides any transition for the property declared in a stylesheet. A spring which
+    /// is interrupted part way, such as when the pointer leaves before the hover transition finishes, is retargeted
+    /// from its current value and velocity.
+    ///
+    /// # Example
+    /// ```
+    /// # use vizia_core::prelude::*;
+    /// # let cx = &mut Context::default();
+    /// Element::new(cx)
+    ///     .transition_spring("background-color", Spring::default())
+    ///     .transition_spring("width", Spring::new(300.0, 15.0, 1.0));
+    /// ```
+    fn transition_spring(mut self, property: &str, spring: Spring) -> Self {
+        let entity = self.entity();
+        self.context().style.insert_spring_transition(entity, property, spring);
+
+        self
+    }
 }
 
 impl<V: View> StyleModifiers for Handle<'_, V> {}
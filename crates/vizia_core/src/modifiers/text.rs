@@ -164,6 +164,27 @@ pub trait TextModifiers: internal::Modifiable {
         TextStrokeStyle,
         SystemFlags::REFLOW
     );
+
+    modifier!(
+        /// Sets the extra space added between the characters of the text.
+        letter_spacing,
+        Length,
+        SystemFlags::REFLOW
+    );
+
+    modifier!(
+        /// Sets the extra space added between the words of the text.
+        word_spacing,
+        Length,
+        SystemFlags::REFLOW
+    );
+
+    modifier!(
+        /// Sets the shadows drawn behind the text of the view.
+        text_shadow,
+        Vec<TextShadow>,
+        SystemFlags::REFLOW
+    );
 }
 
 impl<V> TextModifiers for Handle<'_, V> {}
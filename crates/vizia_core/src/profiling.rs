@@ -0,0 +1,159 @@
+//! Timing of the systems which run each frame, enabled with the `profiling` feature.
+//!
+//! The stats are averaged over half a second and can be bound to with the lens returned by
+//! [`Context::frame_stats`], or shown with the built-in [`FrameStatsOverlay`].
+
+use std::any::TypeId;
+
+use crate::prelude::*;
+
+// The interval over which the stats are averaged before being published.
+const PUBLISH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The time spent in each system per frame, averaged over the last half second.
+#[derive(Debug, Default, Clone, PartialEq, Data, Lens)]
+pub struct FrameStats {
+    /// The number of frames drawn per second.
+    pub fps: f32,
+    /// The time spent dispatching events and updating bindings, in milliseconds per frame.
+    pub event_ms: f32,
+    /// The time spent matching and applying styles, in milliseconds per frame.
+    pub style_ms: f32,
+    /// The time spent computing layout, in milliseconds per frame.
+    pub layout_ms: f32,
+    /// The time spent drawing, in milliseconds per frame.
+    pub draw_ms: f32,
+    /// The time spent updating the accessibility tree, in milliseconds per frame.
+    pub accessibility_ms: f32,
+    /// The number of entities in the tree.
+    pub entity_count: usize,
+    /// The number of style rules.
+    pub rule_count: usize,
+}
+
+// The systems which are timed.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum ProfiledSystem {
+    Event,
+    Style,
+    Layout,
+    Draw,
+    Accessibility,
+}
+
+// Accumulates the time spent in each system until the stats are next published.
+pub(crate) struct Profiler {
+    start: Instant,
+    frames: u32,
+    totals: [Duration; 5],
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self { start: Instant::now(), frames: 0, totals: [Duration::ZERO; 5] }
+    }
+}
+
+impl Profiler {
+    pub(crate) fn record(&mut self, system: ProfiledSystem, elapsed: Duration) {
+        self.totals[system as usize] += elapsed;
+    }
+
+    pub(crate) fn end_frame(&mut self) {
+        self.frames += 1;
+    }
+}
+
+// The model which holds the published stats, built at the root.
+#[derive(Lens)]
+pub(crate) struct FrameStatsData {
+    pub(crate) stats: FrameStats,
+}
+
+impl Model for FrameStatsData {}
+
+// Publishes the stats accumulated since they were last published, if the publish interval has elapsed.
+pub(crate) fn publish_frame_stats(cx: &mut Context) {
+    let elapsed = cx.profiler.start.elapsed();
+    if elapsed < PUBLISH_INTERVAL {
+        return;
+    }
+
+    let profiler = std::mem::take(&mut cx.profiler);
+    let frames = profiler.frames.max(1) as f32;
+    let ms =
+        |system: ProfiledSystem| profiler.totals[system as usize].as_secs_f32() * 1000.0 / frames;
+
+    let stats = FrameStats {
+        fps: profiler.frames as f32 / elapsed.as_secs_f32(),
+        event_ms: ms(ProfiledSystem::Event),
+        style_ms: ms(ProfiledSystem::Style),
+        layout_ms: ms(ProfiledSystem::Layout),
+        draw_ms: ms(ProfiledSystem::Draw),
+        accessibility_ms: ms(ProfiledSystem::Accessibility),
+        entity_count: cx.tree.into_iter().count(),
+        rule_count: cx.style.rules.len(),
+    };
+
+    if let Some(data) = cx
+        .models
+        .get_mut(&Entity::root())
+        .and_then(|models| models.get_mut(&TypeId::of::<FrameStatsData>()))
+        .and_then(|model| model.downcast_mut::<FrameStatsData>())
+    {
+        data.stats = stats;
+    }
+}
+
+/// A panel in the corner of the window which shows the [`FrameStats`] of the application.
+///
+/// # Example
+/// ```
+/// # use vizia_core::prelude::*;
+/// # let cx = &mut Context::default();
+/// FrameStatsOverlay::new(cx);
+/// ```
+pub struct FrameStatsOverlay {}
+
+impl FrameStatsOverlay {
+    /// Creates a new [FrameStatsOverlay] view.
+    pub fn new(cx: &mut Context) -> Handle<Self> {
+        let stats = cx.frame_stats();
+
+        Self {}
+            .build(cx, |cx| {
+                // The stats are published when the event loop wakes, so a timer, which is stopped when the overlay is
+                // removed, keeps them updating while the application is otherwise idle.
+                let timer = cx.add_timer(PUBLISH_INTERVAL, None, |_, _| {});
+                cx.start_timer(timer);
+
+                Label::new(cx, stats.map(|stats| format!("{:.0} fps", stats.fps)));
+                Label::new(cx, stats.map(|stats| format!("events {:.2} ms", stats.event_ms)));
+                Label::new(cx, stats.map(|stats| format!("style {:.2} ms", stats.style_ms)));
+                Label::new(cx, stats.map(|stats| format!("layout {:.2} ms", stats.layout_ms)));
+                Label::new(cx, stats.map(|stats| format!("draw {:.2} ms", stats.draw_ms)));
+                Label::new(
+                    cx,
+                    stats.map(|stats| format!("accessibility {:.2} ms", stats.accessibility_ms)),
+                );
+                Label::new(cx, stats.map(|stats| format!("{} entities", stats.entity_count)));
+                Label::new(cx, stats.map(|stats| format!("{} rules", stats.rule_count)));
+            })
+            .position_type(PositionType::Absolute)
+            .left(Pixels(8.0))
+            .top(Pixels(8.0))
+            .size(Auto)
+            .padding(Pixels(8.0))
+            .background_color(Color::rgba(30, 30, 30, 200))
+            .color(Color::rgb(230, 230, 230))
+            .font_size(12.0)
+            .pointer_events(PointerEvents::None)
+            .z_index(1000)
+    }
+}
+
+impl View for FrameStatsOverlay {
+    fn element(&self) -> Option<&'static str> {
+        Some("frame-stats-overlay")
+    }
+}
@@ -7,17 +7,50 @@ use vizia_id::{GenerationalId, IdManager};
 
 use crate::context::ResourceContext;
 use crate::entity::Entity;
+use crate::environment::ThemeMode;
 use crate::prelude::IntoCssStr;
 // use crate::view::Canvas;
-use fluent_bundle::{FluentBundle, FluentResource};
+use fluent_bundle::{FluentBundle, FluentResource, FluentValue};
 use hashbrown::{HashMap, HashSet};
+use std::cell::RefCell;
 use unic_langid::LanguageIdentifier;
 
+// Creates a translation bundle for a language with the built-in `NUMBER` function, which formats a number with
+// options, such as `NUMBER($position, type: "ordinal")` to select an ordinal plural category.
+fn new_bundle(lang: LanguageIdentifier) -> FluentBundle<FluentResource> {
+    let mut bundle = FluentBundle::new(vec![lang]);
+    let result = bundle.add_function("NUMBER", |positional, named| match positional.first() {
+        Some(FluentValue::Number(number)) => {
+            let mut number = number.clone();
+            number.options.merge(named);
+            FluentValue::Number(number)
+        }
+        _ => FluentValue::Error,
+    });
+
+    if let Err(err) = result {
+        log::warn!("Failed to add NUMBER function to translation bundle: {}", err);
+    }
+
+    bundle
+}
+
 pub(crate) enum ImageOrSvg {
     Svg(skia_safe::svg::Dom),
     Image(skia_safe::Image),
 }
 
+// Returns true if the data is an SVG document rather than an encoded raster image.
+pub(crate) fn is_svg(data: &[u8]) -> bool {
+    let data = data.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(data);
+    let start = data.iter().position(|b| !b.is_ascii_whitespace()).unwrap_or(data.len());
+    let data = &data[start..];
+
+    data.starts_with(b"<svg")
+        || ((data.starts_with(b"<?xml") || data.starts_with(b"<!"))
+            && data.windows(4).any(|window| window == b"<svg"))
+}
+
 pub(crate) struct StoredImage {
     pub image: ImageOrSvg,
     pub retention_policy: ImageRetentionPolicy,
@@ -42,6 +75,10 @@ pub enum ImageRetentionPolicy {
 pub struct ResourceManager {
     pub themes: Vec<String>, // Themes are the string content stylesheets
     pub styles: Vec<Box<dyn IntoCssStr>>,
+    // Stylesheets which only apply while the application is in a particular theme mode.
+    pub(crate) theme_styles: Vec<(ThemeMode, Box<dyn IntoCssStr>)>,
+    // The theme mode which the stylesheets are loaded for.
+    pub(crate) theme_mode: ThemeMode,
 
     pub(crate) image_id_manager: IdManager<ImageId>,
     pub(crate) images: HashMap<ImageId, StoredImage>,
@@ -50,8 +87,13 @@ pub struct ResourceManager {
     pub translations: HashMap<LanguageIdentifier, FluentBundle<FluentResource>>,
 
     pub language: LanguageIdentifier,
+    // The translation used for each locale without a translation of its own, negotiated when first requested and
+    // cleared whenever the language is renegotiated.
+    pub(crate) fallback_languages: RefCell<HashMap<LanguageIdentifier, Option<LanguageIdentifier>>>,
 
     pub image_loader: Option<Box<dyn Fn(&mut ResourceContext, &str)>>,
+    // The names of images which have been requested from the image loader.
+    pub(crate) loader_images: HashSet<String>,
 }
 
 impl ResourceManager {
@@ -117,14 +159,18 @@ impl ResourceManager {
             images,
             image_ids: HashMap::new(),
             styles: Vec::new(),
+            theme_styles: Vec::new(),
+            theme_mode: ThemeMode::default(),
 
             translations: HashMap::from([(
                 LanguageIdentifier::default(),
-                FluentBundle::new(vec![LanguageIdentifier::default()]),
+                new_bundle(LanguageIdentifier::default()),
             )]),
 
             language: locale,
+            fallback_languages: RefCell::default(),
             image_loader: default_image_loader,
+            loader_images: HashSet::new(),
         }
     }
 
@@ -146,13 +192,13 @@ impl ResourceManager {
             fluent_langneg::NegotiationStrategy::Filtering,
         );
         self.language = (**langs.first().unwrap()).clone();
+        self.fallback_languages.get_mut().clear();
     }
 
     pub fn add_translation(&mut self, lang: LanguageIdentifier, ftl: String) {
         let res = fluent_bundle::FluentResource::try_new(ftl)
             .expect("Failed to parse translation as FTL");
-        let bundle =
-            self.translations.entry(lang.clone()).or_insert_with(|| FluentBundle::new(vec![lang]));
+        let bundle = self.translations.entry(lang.clone()).or_insert_with(|| new_bundle(lang));
         bundle.add_resource(res).expect("Failed to add resource to bundle");
         self.renegotiate_language();
     }
@@ -162,10 +208,33 @@ impl ResourceManager {
         locale: &LanguageIdentifier,
     ) -> &FluentBundle<FluentResource> {
         if let Some(bundle) = self.translations.get(locale) {
-            bundle
-        } else {
-            self.translations.get(&self.language).unwrap()
+            return bundle;
         }
+
+        // A locale without a translation of its own, such as `en-GB`, uses the closest translation, such as `en-US`.
+        let fallback = self
+            .fallback_languages
+            .borrow_mut()
+            .entry(locale.clone())
+            .or_insert_with(|| {
+                let available = self
+                    .translations
+                    .keys()
+                    .filter(|&x| x != &LanguageIdentifier::default())
+                    .collect::<Vec<_>>();
+                let langs = fluent_langneg::negotiate::negotiate_languages(
+                    &[locale],
+                    &available,
+                    None,
+                    fluent_langneg::NegotiationStrategy::Filtering,
+                );
+                langs.first().map(|lang| (**lang).clone())
+            })
+            .clone();
+
+        fallback
+            .and_then(|lang| self.translations.get(&lang))
+            .unwrap_or_else(|| self.translations.get(&self.language).unwrap())
     }
 
     pub fn mark_images_unused(&mut self) {
@@ -194,5 +263,75 @@ impl ResourceManager {
             self.image_ids.retain(|_, img| *img != id);
             self.image_id_manager.destroy(id);
         }
+
+        self.loader_images.retain(|name| self.image_ids.contains_key(name));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fluent_bundle::FluentArgs;
+
+    const FTL: &str = r#"
+emails = { $count ->
+    [one] One email
+   *[other] { $count } emails
+}
+place = { NUMBER($position, type: "ordinal") ->
+    [one] { $position }st
+    [two] { $position }nd
+    [few] { $position }rd
+   *[other] { $position }th
+}
+"#;
+
+    fn format(bundle: &FluentBundle<FluentResource>, key: &str, arg: &str, value: i64) -> String {
+        let mut args = FluentArgs::new();
+        args.set(arg, value);
+        let pattern = bundle.get_message(key).unwrap().value().unwrap();
+        let mut errors = Vec::new();
+        let text = bundle.format_pattern(pattern, Some(&args), &mut errors).to_string();
+        assert!(errors.is_empty(), "{:?}", errors);
+        text
+    }
+
+    fn resource_manager() -> ResourceManager {
+        let mut manager = ResourceManager::new();
+        manager.add_translation("en-US".parse().unwrap(), FTL.to_owned());
+        for bundle in manager.translations.values_mut() {
+            bundle.set_use_isolating(false);
+        }
+        manager
+    }
+
+    #[test]
+    fn plural_selection() {
+        let manager = resource_manager();
+        let bundle = manager.current_translation(&"en-US".parse().unwrap());
+        assert_eq!(format(bundle, "emails", "count", 1), "One email");
+        assert_eq!(format(bundle, "emails", "count", 5), "5 emails");
+    }
+
+    #[test]
+    fn ordinal_number() {
+        let manager = resource_manager();
+        let bundle = manager.current_translation(&"en-US".parse().unwrap());
+        let places =
+            [1, 2, 3, 4, 11, 22].map(|position| format(bundle, "place", "position", position));
+        assert_eq!(places, ["1st", "2nd", "3rd", "4th", "11th", "22nd"]);
+    }
+
+    #[test]
+    fn regional_fallback() {
+        let manager = resource_manager();
+        let locale: LanguageIdentifier = "en-GB".parse().unwrap();
+        let bundle = manager.current_translation(&locale);
+        assert_eq!(bundle.locales, ["en-US".parse::<LanguageIdentifier>().unwrap()]);
+        assert_eq!(format(bundle, "emails", "count", 2), "2 emails");
+
+        // The negotiated fallback is cached until the language is renegotiated.
+        let fallback = manager.fallback_languages.borrow().get(&locale).cloned();
+        assert_eq!(fallback, Some(Some("en-US".parse().unwrap())));
     }
 }
@@ -124,6 +124,15 @@ impl SparseSetIndex for SharedIndex {
     }
 }
 
+/// The target of a transition, which is either a style rule or a specific entity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum TransitionTarget {
+    /// The transition plays when an entity is linked to the rule.
+    Rule(Rule),
+    /// The transition plays whenever the entity is linked to a new rule, overriding any transitions of the rule.
+    Entity(Entity),
+}
+
 /// Animatable set is used for storing inline and shared data for entities as well as definitions for
 /// animations, which can be played for entities, and transitions, which play when an entity matches a new shared style
 /// rule which defines a trnasition.
@@ -140,6 +149,8 @@ pub(crate) struct AnimatableSet<T: Interpolator> {
     animations: SparseSet<AnimationState<T>>,
     /// Animations which are currently playing
     active_animations: Vec<AnimationState<T>>,
+    /// Transitions defined on specific entities
+    entity_transitions: SparseSet<Animation>,
 }
 
 impl<T> AnimatableSet<T>
@@ -155,6 +166,10 @@ where
     pub fn remove(&mut self, entity: Entity) -> Option<T> {
         let entity_index = entity.index();
 
+        if let Some(animation) = self.entity_transitions.remove(entity) {
+            self.animations.remove(animation);
+        }
+
         if entity_index < self.inline_data.sparse.len() {
             let active_anim_index = self.inline_data.sparse[entity_index].anim_index as usize;
 
@@ -297,15 +312,32 @@ where
     //     self.shared_data.remove(rule)
     // }
 
-    /// Inserts a transition for a given rule
+    /// Inserts a transition for a given rule or entity
     ///
     /// Transitions are animations which are defined for a particular rule. When an entity is linked to
-    /// a rule any transition associated with that rule will play for that entity.
+    /// a rule any transition associated with that rule will play for that entity. A transition defined for an
+    /// entity plays when the entity is linked to any rule and takes precedence over the transition of the rule.
     ///
-    pub(crate) fn insert_transition(&mut self, rule: Rule, animation: Animation) {
-        // Check if the rule exists
-        if self.shared_data.contains(rule) && self.animations.contains(animation) {
-            self.shared_data.sparse[rule.index()].animation = animation;
+    pub(crate) fn insert_transition(&mut self, target: TransitionTarget, animation: Animation) {
+        if !self.animations.contains(animation) {
+            return;
+        }
+
+        match target {
+            TransitionTarget::Rule(rule) => {
+                // Check if the rule exists
+                if self.shared_data.contains(rule) {
+                    self.shared_data.sparse[rule.index()].animation = animation;
+                }
+            }
+
+            TransitionTarget::Entity(entity) => {
+                if let Some(previous) = self.entity_transitions.get(entity).copied() {
+                    self.animations.remove(previous);
+                }
+
+                self.entity_transitions.insert(entity, animation);
+            }
         }
     }
 
@@ -395,22 +427,79 @@ where
             let active_anim_index = self.inline_data.sparse[entity_index].anim_index as usize;
             if active_anim_index < self.active_animations.len() {
                 let anim_state = &mut self.active_animations[active_anim_index];
-                if anim_state.id == animation {
-                    anim_state.entities.remove(&entity);
+                if anim_state.id != animation {
+                    // Leave any other animation linked to the entity playing.
+                    return;
                 }
+                anim_state.entities.remove(&entity);
             }
             self.inline_data.sparse[entity_index].anim_index = u32::MAX;
         }
     }
 
+    /// Inserts a copy of an animation with its keyframes in reverse order, used to play the animation backwards.
+    pub(crate) fn insert_reversed_animation(&mut self, animation: Animation, reversed: Animation) {
+        if let Some(mut state) = self.animations.get(animation).cloned() {
+            // The timing function of a keyframe applies to the segment which starts at it, so the functions shift
+            // by one keyframe when the order is reversed.
+            let timing_functions =
+                state.keyframes.iter().map(|keyframe| keyframe.timing_function).collect::<Vec<_>>();
+            state.keyframes.reverse();
+            let count = state.keyframes.len();
+            for (index, keyframe) in state.keyframes.iter_mut().enumerate() {
+                keyframe.time = 1.0 - keyframe.time;
+                if index + 1 < count {
+                    keyframe.timing_function = timing_functions[count - 2 - index];
+                }
+            }
+
+            state.id = reversed;
+            self.animations.insert(reversed, state);
+        }
+    }
+
+    /// Pauses an animation playing for a given entity, holding its current value.
+    pub(crate) fn pause_animation(&mut self, entity: Entity, animation: Animation, time: Instant) {
+        if let Some(state) = self.active_animation_mut(entity, animation) {
+            state.paused_at.get_or_insert(time);
+        }
+    }
+
+    /// Resumes a paused animation for a given entity from where it was paused.
+    pub(crate) fn resume_animation(&mut self, entity: Entity, animation: Animation, time: Instant) {
+        if let Some(state) = self.active_animation_mut(entity, animation) {
+            if let Some(paused_at) = state.paused_at.take() {
+                state.start_time += time.duration_since(paused_at);
+            }
+        }
+    }
+
+    // Returns the active animation state linked to the entity if it is the given animation.
+    fn active_animation_mut(
+        &mut self,
+        entity: Entity,
+        animation: Animation,
+    ) -> Option<&mut AnimationState<T>> {
+        let anim_index = self.inline_data.sparse.get(entity.index())?.anim_index as usize;
+        self.active_animations.get_mut(anim_index).filter(|state| state.id == animation)
+    }
+
+    /// Delays the active animations by the given duration, e.g. to resume them after the application was frozen.
+    /// Paused animations are skipped, as they're delayed by the time they were paused for when resumed.
+    pub(crate) fn delay_animations(&mut self, delay: Duration) {
+        for state in self.active_animations.iter_mut().filter(|state| state.paused_at.is_none()) {
+            state.start_time += delay;
+        }
+    }
+
     /// Tick the animation for the given time and return a list of entities which have been animated.
     pub fn tick(&mut self, time: Instant) -> Vec<Entity> {
         self.remove_innactive_animations();
 
         if self.has_animations() {
             for state in self.active_animations.iter_mut() {
-                // If the animation is already finished then skip
-                if state.t == 1.0 {
+                // If the animation is already finished or is paused then skip
+                if state.t == 1.0 || state.paused_at.is_some() {
                     continue;
                 }
 
@@ -444,6 +533,7 @@ where
 
             self.active_animations
                 .iter()
+                .filter(|state| state.paused_at.is_none())
                 .flat_map(|state| state.entities.clone())
                 .collect::<Vec<Entity>>()
         } else {
@@ -589,7 +679,16 @@ where
             if animation_index < self.active_animations.len() {
                 return self.active_animations[animation_index].get_output();
             }
+        }
+
+        self.get_linked(entity)
+    }
 
+    /// Returns the inline or shared value of an entity, ignoring any animation playing for it. While a transition is
+    /// playing this is the value the transition is playing towards.
+    pub(crate) fn get_linked(&self, entity: Entity) -> Option<&T> {
+        let entity_index = entity.index();
+        if entity_index < self.inline_data.sparse.len() {
             let data_index = self.inline_data.sparse[entity_index].data_index;
             if data_index.is_inline() {
                 if data_index.index() < self.inline_data.dense.len() {
@@ -603,6 +702,11 @@ where
         None
     }
 
+    /// Returns true if a transition is playing for an entity.
+    pub(crate) fn is_transitioning(&self, entity: Entity) -> bool {
+        self.get_active_animation(entity).is_some_and(|state| state.is_transition())
+    }
+
     /// Link an entity to some shared data.
     pub(crate) fn link(&mut self, entity: Entity, rules: &[(Rule, u32)]) -> bool {
         let entity_index = entity.index();
@@ -624,8 +728,12 @@ where
                     self.inline_data.sparse.resize(entity_index + 1, InlineIndex::null());
                 }
 
-                // Get the animation state index of any animations (transitions) defined for the rule
-                let rule_animation = shared_data_index.animation;
+                // Get the animation state index of any animations (transitions) defined for the entity or rule
+                let rule_animation = self
+                    .entity_transitions
+                    .get(entity)
+                    .copied()
+                    .unwrap_or(shared_data_index.animation);
 
                 //if let Some(transition_state) = self.animations.get_mut(rule_animation) {
                 let entity_anim_index = self.inline_data.sparse[entity_index].anim_index as usize;
@@ -642,29 +750,40 @@ where
                                 // Transitioning back to previous rule
                                 current_anim_state.from_rule = current_anim_state.to_rule;
                                 current_anim_state.to_rule = rule_data_index;
-                                current_anim_state.keyframes.first_mut().unwrap().value =
-                                    self.shared_data.dense[current_anim_state.from_rule]
-                                        .value
-                                        .clone();
-
-                                current_anim_state.keyframes.last_mut().unwrap().value =
-                                    self.shared_data.dense[current_anim_state.to_rule]
-                                        .value
-                                        .clone();
-
-                                current_anim_state.dt = current_anim_state.t - 1.0;
-                                current_anim_state.start_time = Instant::now();
+
+                                // Springs continue from the current value and velocity
+                                let end = self.shared_data.dense[rule_data_index].value.clone();
+                                if !current_anim_state.retarget_spring(current_value, end, true) {
+                                    current_anim_state.keyframes.first_mut().unwrap().value =
+                                        self.shared_data.dense[current_anim_state.from_rule]
+                                            .value
+                                            .clone();
+
+                                    current_anim_state.keyframes.last_mut().unwrap().value =
+                                        self.shared_data.dense[current_anim_state.to_rule]
+                                            .value
+                                            .clone();
+
+                                    current_anim_state.dt = current_anim_state.t - 1.0;
+                                    current_anim_state.start_time = Instant::now();
+                                }
                             } else {
                                 // Transitioning to new rule
                                 current_anim_state.to_rule = rule_data_index;
-                                current_anim_state.keyframes.first_mut().unwrap().value =
-                                    current_value;
-                                current_anim_state.keyframes.last_mut().unwrap().value =
-                                    self.shared_data.dense[current_anim_state.to_rule]
-                                        .value
-                                        .clone();
-                                current_anim_state.t = 0.0;
-                                current_anim_state.start_time = Instant::now();
+
+                                // Springs continue from the current value and velocity
+                                let end = self.shared_data.dense[rule_data_index].value.clone();
+                                if !current_anim_state.retarget_spring(
+                                    current_value.clone(),
+                                    end.clone(),
+                                    false,
+                                ) {
+                                    current_anim_state.keyframes.first_mut().unwrap().value =
+                                        current_value;
+                                    current_anim_state.keyframes.last_mut().unwrap().value = end;
+                                    current_anim_state.t = 0.0;
+                                    current_anim_state.start_time = Instant::now();
+                                }
                             }
                         }
                     }
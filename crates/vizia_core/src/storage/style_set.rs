@@ -309,6 +309,17 @@ where
         None
     }
 
+    /// Returns the inline or shared value of an entity, which is the same as [`get`](Self::get) as the values of a
+    /// style set aren't animated.
+    pub(crate) fn get_linked(&self, entity: Entity) -> Option<&T> {
+        self.get(entity)
+    }
+
+    /// Returns false, as the values of a style set aren't animated.
+    pub(crate) fn is_transitioning(&self, _entity: Entity) -> bool {
+        false
+    }
+
     pub fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
         let entity_index = entity.index();
         if entity_index < self.inline_data.sparse.len() {
@@ -0,0 +1,237 @@
+use hashbrown::HashMap;
+use vizia_style::selectors::parser::{Combinator, Component, Selector};
+use vizia_style::{PseudoClass, Selectors};
+
+use super::PseudoClassFlags;
+
+/// The views which need to be restyled when a pseudo-class or class of a view changes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Invalidation {
+    /// No selector depends on the change.
+    #[default]
+    None,
+    /// Only the view itself, for selectors such as `a:hover`.
+    Entity,
+    /// The view and its descendants, for selectors such as `a:hover b`.
+    Descendants,
+    /// The parent of the view and all of its descendants, for selectors such as `a:hover + b`.
+    Siblings,
+    /// The views restyled for siblings, along with every ancestor of the view and the siblings of each ancestor, for
+    /// selectors such as `a:has(b:hover)` whose anchor may be any of them.
+    Ancestors,
+    /// Every view in the tree, for selectors such as `a:has(b:hover) c` which can match anywhere below an anchor.
+    Tree,
+}
+
+/// Records which views can be affected by a change to a pseudo-class or class, based on where the pseudo-class or
+/// class appears within the selectors of the style rules.
+#[derive(Debug, Default)]
+pub(crate) struct InvalidationMap {
+    // Pseudo-classes which only appear in the rightmost compound selector.
+    entity_pseudo_classes: PseudoClassFlags,
+    // Pseudo-classes which appear to the left of a child or descendant combinator.
+    descendant_pseudo_classes: PseudoClassFlags,
+    // Pseudo-classes which appear to the left of a sibling combinator.
+    sibling_pseudo_classes: PseudoClassFlags,
+    // Pseudo-classes which appear within `:has()` in the rightmost compound selector.
+    ancestor_pseudo_classes: PseudoClassFlags,
+    // Pseudo-classes which appear within `:has()` to the left of a combinator.
+    tree_pseudo_classes: PseudoClassFlags,
+    classes: HashMap<String, Invalidation>,
+    // Whether any selector contains `:has()`.
+    relative_selectors: bool,
+}
+
+impl InvalidationMap {
+    /// Records the pseudo-classes and classes the given selector depends on.
+    pub(crate) fn add_selector(&mut self, selector: &Selector<Selectors>) {
+        self.add_components(selector, Invalidation::Entity);
+    }
+
+    // Components are visited from right to left, so each combinator widens the invalidation of the compound
+    // selectors to its left.
+    fn add_components(&mut self, selector: &Selector<Selectors>, mut invalidation: Invalidation) {
+        for component in selector.iter_raw_match_order() {
+            match component {
+                Component::Combinator(combinator) => {
+                    invalidation = invalidation.max(match combinator {
+                        Combinator::Child | Combinator::Descendant => Invalidation::Descendants,
+                        Combinator::NextSibling | Combinator::LaterSibling => {
+                            Invalidation::Siblings
+                        }
+                        _ => Invalidation::Entity,
+                    });
+                }
+
+                Component::NonTSPseudoClass(pseudo_class) => {
+                    if let Some(flag) = pseudo_class_flag(pseudo_class) {
+                        match invalidation {
+                            Invalidation::None => {}
+                            Invalidation::Entity => self.entity_pseudo_classes.insert(flag),
+                            Invalidation::Descendants => {
+                                self.descendant_pseudo_classes.insert(flag)
+                            }
+                            Invalidation::Siblings => self.sibling_pseudo_classes.insert(flag),
+                            Invalidation::Ancestors => self.ancestor_pseudo_classes.insert(flag),
+                            Invalidation::Tree => self.tree_pseudo_classes.insert(flag),
+                        }
+                    }
+                }
+
+                Component::Class(name) => {
+                    let entry = self.classes.entry(name.0.clone()).or_default();
+                    *entry = (*entry).max(invalidation);
+                }
+
+                Component::Negation(list) | Component::Is(list) | Component::Where(list) => {
+                    for selector in list.slice() {
+                        self.add_components(selector, invalidation);
+                    }
+                }
+
+                // Whether a view matches `:nth-child(An+B of S)` depends on the state of its siblings.
+                Component::NthOf(nth) => {
+                    for selector in nth.selectors() {
+                        self.add_components(selector, Invalidation::Siblings);
+                    }
+                }
+
+                // Whether a view matches `:has()` depends on the state of its descendants and later siblings, so a
+                // change can affect any ancestor of the changed view, or the earlier siblings of one. If the anchor
+                // is followed by a combinator, the views it affects can be anywhere below those.
+                Component::Has(relative_selectors) => {
+                    self.relative_selectors = true;
+
+                    let invalidation = if invalidation == Invalidation::Entity {
+                        Invalidation::Ancestors
+                    } else {
+                        Invalidation::Tree
+                    };
+
+                    for relative_selector in relative_selectors.iter() {
+                        self.add_components(&relative_selector.selector, invalidation);
+                    }
+                }
+
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns the views which need to be restyled when the given pseudo-classes of a view change.
+    pub(crate) fn pseudo_classes(&self, changed: PseudoClassFlags) -> Invalidation {
+        if self.tree_pseudo_classes.intersects(changed) {
+            Invalidation::Tree
+        } else if self.ancestor_pseudo_classes.intersects(changed) {
+            Invalidation::Ancestors
+        } else if self.sibling_pseudo_classes.intersects(changed) {
+            Invalidation::Siblings
+        } else if self.descendant_pseudo_classes.intersects(changed) {
+            Invalidation::Descendants
+        } else if self.entity_pseudo_classes.intersects(changed) {
+            Invalidation::Entity
+        } else {
+            Invalidation::None
+        }
+    }
+
+    /// Returns true if any selector contains `:has()`, whose matching depends on the descendants of a view.
+    pub(crate) fn has_relative_selectors(&self) -> bool {
+        self.relative_selectors
+    }
+
+    /// Returns the views which need to be restyled when the given class is added to or removed from a view.
+    pub(crate) fn class(&self, name: &str) -> Invalidation {
+        self.classes.get(name).copied().unwrap_or_default()
+    }
+}
+
+// Returns the flag used to store the state matched by the given pseudo-class, if the state is stored as a flag.
+fn pseudo_class_flag(pseudo_class: &PseudoClass) -> Option<PseudoClassFlags> {
+    Some(match pseudo_class {
+        PseudoClass::Hover => PseudoClassFlags::HOVER,
+        PseudoClass::Active => PseudoClassFlags::ACTIVE,
+        PseudoClass::Over => PseudoClassFlags::OVER,
+        PseudoClass::Focus => PseudoClassFlags::FOCUS,
+        PseudoClass::FocusVisible => PseudoClassFlags::FOCUS_VISIBLE,
+        PseudoClass::FocusWithin => PseudoClassFlags::FOCUS_WITHIN,
+        PseudoClass::ReadOnly => PseudoClassFlags::READ_ONLY,
+        PseudoClass::ReadWrite => PseudoClassFlags::READ_WRITE,
+        PseudoClass::PlaceholderShown => PseudoClassFlags::PLACEHOLDER_SHOWN,
+        PseudoClass::Default => PseudoClassFlags::DEFAULT,
+        PseudoClass::Checked => PseudoClassFlags::CHECKED,
+        PseudoClass::Indeterminate => PseudoClassFlags::INDETERMINATE,
+        PseudoClass::Blank => PseudoClassFlags::BLANK,
+        PseudoClass::Valid => PseudoClassFlags::VALID,
+        PseudoClass::Invalid => PseudoClassFlags::INVALID,
+        PseudoClass::InRange => PseudoClassFlags::IN_RANGE,
+        PseudoClass::OutOfRange => PseudoClassFlags::OUT_OF_RANGE,
+        PseudoClass::Required => PseudoClassFlags::REQUIRED,
+        PseudoClass::Optional => PseudoClassFlags::OPTIONAL,
+        PseudoClass::UserValid => PseudoClassFlags::USER_VALID,
+        PseudoClass::UserInvalid => PseudoClassFlags::USER_INVALID,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vizia_style::{ParserOptions, StyleSheet};
+
+    fn invalidation_map(css: &str) -> InvalidationMap {
+        let mut map = InvalidationMap::default();
+        let stylesheet = StyleSheet::parse(css, ParserOptions::new()).unwrap();
+        for rule in stylesheet.rules.0 {
+            if let vizia_style::CssRule::Style(style_rule) = rule {
+                for selector in style_rule.selectors.slice() {
+                    map.add_selector(selector);
+                }
+            }
+        }
+        map
+    }
+
+    #[test]
+    fn pseudo_class_invalidation() {
+        let map = invalidation_map(
+            "button:hover { width: 10px; } list:focus-within item { width: 10px; } a:checked + b { width: 10px; }",
+        );
+
+        assert_eq!(map.pseudo_classes(PseudoClassFlags::HOVER), Invalidation::Entity);
+        assert_eq!(map.pseudo_classes(PseudoClassFlags::FOCUS_WITHIN), Invalidation::Descendants);
+        assert_eq!(map.pseudo_classes(PseudoClassFlags::CHECKED), Invalidation::Siblings);
+        assert_eq!(map.pseudo_classes(PseudoClassFlags::ACTIVE), Invalidation::None);
+        assert_eq!(
+            map.pseudo_classes(PseudoClassFlags::HOVER | PseudoClassFlags::FOCUS_WITHIN),
+            Invalidation::Descendants
+        );
+    }
+
+    #[test]
+    fn class_invalidation() {
+        let map = invalidation_map(
+            ".selected { width: 10px; } .open > .item:not(.hidden) { width: 10px; } :is(.a ~ b) { width: 10px; }",
+        );
+
+        assert_eq!(map.class("selected"), Invalidation::Entity);
+        assert_eq!(map.class("open"), Invalidation::Descendants);
+        assert_eq!(map.class("item"), Invalidation::Entity);
+        assert_eq!(map.class("hidden"), Invalidation::Entity);
+        assert_eq!(map.class("a"), Invalidation::Siblings);
+        assert_eq!(map.class("missing"), Invalidation::None);
+    }
+
+    #[test]
+    fn has_invalidation() {
+        let map = invalidation_map(
+            ".card:has(.icon:hover) { width: 10px; } .panel:has(> .open) .title { width: 10px; }",
+        );
+
+        assert_eq!(map.pseudo_classes(PseudoClassFlags::HOVER), Invalidation::Ancestors);
+        assert_eq!(map.class("icon"), Invalidation::Ancestors);
+        assert_eq!(map.class("card"), Invalidation::Entity);
+        assert_eq!(map.class("open"), Invalidation::Tree);
+        assert_eq!(map.class("panel"), Invalidation::Descendants);
+    }
+}
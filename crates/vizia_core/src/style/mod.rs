@@ -71,17 +71,18 @@ use crate::prelude::*;
 
 pub use vizia_style::{
     Alignment, Angle, BackgroundImage, BackgroundSize, BorderStyleKeyword, ClipPath, Color,
-    CornerShape, CssRule, CursorIcon, Display, Filter, FontFamily, FontSize, FontSlant,
-    FontVariation, FontWeight, FontWeightKeyword, FontWidth, GenericFontFamily, Gradient,
-    HorizontalPosition, HorizontalPositionKeyword, Length, LengthOrPercentage, LengthValue,
-    LineClamp, LineDirection, LinearGradient, Matrix, Opacity, Overflow, PointerEvents, Position,
-    PositionType, Scale, Shadow, TextAlign, TextDecorationLine, TextDecorationStyle, TextOverflow,
-    TextStroke, TextStrokeStyle, Transform, Transition, Translate, VerticalPosition,
-    VerticalPositionKeyword, Visibility, RGBA,
+    CornerShape, CssRule, CursorIcon, Direction, Display, Filter, FlexWrap, FontFamily, FontSize,
+    FontSlant, FontVariation, FontWeight, FontWeightKeyword, FontWidth, GenericFontFamily,
+    Gradient, HorizontalPosition, HorizontalPositionKeyword, Length, LengthOrPercentage,
+    LengthValue, LineClamp, LineDirection, LinearGradient, Matrix, Opacity, Overflow,
+    PointerEvents, Position, PositionType, Scale, SelfAlignment, Shadow, Spring, TextAlign,
+    TextDecorationLine, TextDecorationStyle, TextOverflow, TextShadow, TextStroke, TextStrokeStyle,
+    Transform, Transition, Translate, VerticalPosition, VerticalPositionKeyword, Visibility, RGBA,
 };
 
 use vizia_style::{
-    BlendMode, EasingFunction, KeyframeSelector, ParserOptions, Property, Selectors, StyleSheet,
+    BlendMode, ColorScheme, EasingFunction, GridPlacement, KeyframeSelector, MediaQueryList,
+    ParserOptions, Property, Selectors, StyleSheet,
 };
 
 mod rule;
@@ -90,21 +91,27 @@ pub(crate) use rule::Rule;
 mod pseudoclass;
 pub(crate) use pseudoclass::*;
 
+mod invalidation;
+pub(crate) use invalidation::*;
+
 mod transform;
 pub(crate) use transform::*;
 
-use crate::animation::{AnimationState, Interpolator, Keyframe, TimingFunction};
-use crate::storage::animatable_set::AnimatableSet;
+use crate::animation::{
+    AnimationPlayback, AnimationState, Interpolator, Keyframe, SpringCurve, TimingFunction,
+};
+use crate::storage::animatable_set::{AnimatableSet, TransitionTarget};
 use crate::storage::style_set::StyleSet;
 use bitflags::bitflags;
 use vizia_id::IdManager;
-use vizia_storage::SparseSet;
+use vizia_storage::{LayoutChildIterator, LayoutParentIterator, LayoutTreeIterator, SparseSet};
 
 bitflags! {
     /// Describes the capabilities of a view with respect to user interaction.
     #[derive(Debug, Clone, Copy)]
     pub(crate) struct Abilities: u8 {
-        // Whether a view will be included in hit tests and receive mouse input events.
+        // Whether a view will be included in hit tests and receive mouse input events. This is the hit-test ability
+        // of a view, which unlike the `pointer-events` property also excludes all of its descendants.
         const HOVERABLE = 1 << 0;
         // Whether a view can be focused to receive keyboard events.
         const FOCUSABLE = 1 << 1;
@@ -148,6 +155,19 @@ pub enum ImageOrGradient {
     Gradient(Gradient),
 }
 
+/// A change to a style property of an entity, recorded when the entity is restyled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StyleChange {
+    /// The CSS name of the property which changed, e.g. `background-color`.
+    pub property: String,
+    /// The computed value of the property before the restyle, formatted with `Debug`.
+    pub old: String,
+    /// The computed value of the property after the restyle, formatted with `Debug`.
+    pub new: String,
+    /// Whether a transition plays from the old value to the new value.
+    pub transition: bool,
+}
+
 /// A font-family.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FamilyOwned {
@@ -207,6 +227,59 @@ impl StyleRule {
     }
 }
 
+// Calls a method with the given arguments on each of the animatable properties of the style.
+macro_rules! for_each_animatable {
+    ($style:expr, $method:ident($($arg:expr),*)) => {
+        $style.display.$method($($arg),*);
+        $style.opacity.$method($($arg),*);
+        $style.clip_path.$method($($arg),*);
+        $style.transform.$method($($arg),*);
+        $style.transform_origin.$method($($arg),*);
+        $style.translate.$method($($arg),*);
+        $style.rotate.$method($($arg),*);
+        $style.scale.$method($($arg),*);
+        $style.border_width.$method($($arg),*);
+        $style.border_color.$method($($arg),*);
+        $style.corner_top_left_radius.$method($($arg),*);
+        $style.corner_top_right_radius.$method($($arg),*);
+        $style.corner_bottom_left_radius.$method($($arg),*);
+        $style.corner_bottom_right_radius.$method($($arg),*);
+        $style.outline_width.$method($($arg),*);
+        $style.outline_color.$method($($arg),*);
+        $style.outline_offset.$method($($arg),*);
+        $style.background_color.$method($($arg),*);
+        $style.background_image.$method($($arg),*);
+        $style.background_size.$method($($arg),*);
+        $style.shadow.$method($($arg),*);
+        $style.font_color.$method($($arg),*);
+        $style.font_size.$method($($arg),*);
+        $style.caret_color.$method($($arg),*);
+        $style.selection_color.$method($($arg),*);
+        $style.left.$method($($arg),*);
+        $style.right.$method($($arg),*);
+        $style.top.$method($($arg),*);
+        $style.bottom.$method($($arg),*);
+        $style.padding_left.$method($($arg),*);
+        $style.padding_right.$method($($arg),*);
+        $style.padding_top.$method($($arg),*);
+        $style.padding_bottom.$method($($arg),*);
+        $style.horizontal_gap.$method($($arg),*);
+        $style.vertical_gap.$method($($arg),*);
+        $style.width.$method($($arg),*);
+        $style.height.$method($($arg),*);
+        $style.min_width.$method($($arg),*);
+        $style.max_width.$method($($arg),*);
+        $style.min_height.$method($($arg),*);
+        $style.max_height.$method($($arg),*);
+        $style.min_horizontal_gap.$method($($arg),*);
+        $style.max_horizontal_gap.$method($($arg),*);
+        $style.min_vertical_gap.$method($($arg),*);
+        $style.max_vertical_gap.$method($($arg),*);
+        $style.underline_color.$method($($arg),*);
+        $style.fill.$method($($arg),*);
+    };
+}
+
 /// Stores the style properties of all entities in the application.
 #[derive(Default)]
 pub struct Style {
@@ -217,9 +290,20 @@ pub struct Style {
     pub(crate) animations: HashMap<String, Animation>,
     // List of animations to be started on the next frame
     pub(crate) pending_animations: Vec<(Entity, Animation, Duration, Duration)>,
+    // Animations played with options, which can be repeated, paused and cancelled
+    pub(crate) animation_playbacks: Vec<AnimationPlayback>,
 
     // List of rules
     pub(crate) rules: IndexMap<Rule, StyleRule>,
+    // Media queries of the rules declared within `@media` blocks.
+    pub(crate) media_rules: Vec<(Rule, MediaQueryList)>,
+    // Rules whose media query doesn't currently match the window they're matched in, which are skipped when matching
+    // the views of that window.
+    pub(crate) inactive_rules: HashMap<Entity, HashSet<Rule>>,
+    // Rules added by the inspector, which take precedence over all other rules.
+    pub(crate) inspector_rules: HashSet<Rule>,
+    // Which views are restyled when a pseudo-class or class of a view changes.
+    pub(crate) invalidation: InvalidationMap,
 
     pub(crate) default_font: Vec<FamilyOwned>,
 
@@ -310,9 +394,13 @@ pub struct Style {
     pub(crate) text_overflow: StyleSet<TextOverflow>,
     pub(crate) line_clamp: StyleSet<LineClamp>,
     pub(crate) text_align: StyleSet<TextAlign>,
+    pub(crate) direction: StyleSet<Direction>,
     pub(crate) text_decoration_line: StyleSet<TextDecorationLine>,
     pub(crate) text_stroke_width: StyleSet<Length>,
     pub(crate) text_stroke_style: StyleSet<TextStrokeStyle>,
+    pub(crate) letter_spacing: StyleSet<Length>,
+    pub(crate) word_spacing: StyleSet<Length>,
+    pub(crate) text_shadow: StyleSet<Vec<TextShadow>>,
     pub(crate) underline_style: StyleSet<TextDecorationLine>,
     pub(crate) overline_style: StyleSet<TextDecorationStyle>,
     pub(crate) strikethrough_style: StyleSet<TextDecorationStyle>,
@@ -348,6 +436,15 @@ pub struct Style {
     pub(crate) position_type: StyleSet<PositionType>,
 
     pub(crate) alignment: StyleSet<Alignment>,
+    pub(crate) align_self: StyleSet<SelfAlignment>,
+    pub(crate) justify_self: StyleSet<SelfAlignment>,
+    pub(crate) flex_wrap: StyleSet<FlexWrap>,
+    // The cross-axis size, in physical pixels, of views with wrapped children and an auto cross-axis size, which is
+    // measured from the wrapped lines by the layout system.
+    pub(crate) wrapped_size: SparseSet<f32>,
+    // The minimum logical size of the content of a window, set with `Context::set_minimum_content_size`, which the
+    // window is laid out at no less than in addition to its own minimum size.
+    pub(crate) min_content_size: SparseSet<(f32, f32)>,
 
     // Grid
     pub(crate) grid_columns: StyleSet<Vec<Units>>,
@@ -357,6 +454,10 @@ pub struct Style {
     pub(crate) column_span: StyleSet<usize>,
     pub(crate) row_start: StyleSet<usize>,
     pub(crate) row_span: StyleSet<usize>,
+    // The placements set with the `grid-column` and `grid-row` properties, which use one-based CSS line numbers and
+    // are converted to track indices by the layout system when `column-start` or `row-start` aren't set.
+    pub(crate) grid_column: StyleSet<GridPlacement>,
+    pub(crate) grid_row: StyleSet<GridPlacement>,
 
     // Spacing
     pub(crate) left: AnimatableSet<Units>,
@@ -401,11 +502,68 @@ pub struct Style {
 
     pub(crate) text_range: SparseSet<Range<usize>>,
     pub(crate) text_span: SparseSet<bool>,
+    // The byte ranges of the misspelled words in the text of a textbox, which are underlined when the text is built.
+    pub(crate) misspelled: SparseSet<Vec<Range<usize>>>,
 
     /// This includes both the system's HiDPI scaling factor as well as `cx.user_scale_factor`.
+    ///
+    /// While the views of a window are laid out or drawn this is the scale factor of that window, and otherwise it's
+    /// the scale factor of the main window.
     pub(crate) dpi_factor: f64,
+    // The scale factor of each window, which differs between windows on displays with different scale factors.
+    pub(crate) window_scale_factors: SparseSet<f64>,
 }
 
+// The properties which are transitioned by a transition of `all`.
+const ANIMATABLE_PROPERTIES: &[&str] = &[
+    "opacity",
+    "clip-path",
+    "transform",
+    "transform-origin",
+    "translate",
+    "rotate",
+    "scale",
+    "border-width",
+    "border-color",
+    "corner-top-left-radius",
+    "corner-top-right-radius",
+    "corner-bottom-left-radius",
+    "corner-bottom-right-radius",
+    "outline-width",
+    "outline-color",
+    "outline-offset",
+    "background-color",
+    "background-image",
+    "background-size",
+    "shadow",
+    "color",
+    "font-size",
+    "caret-color",
+    "selection-color",
+    "left",
+    "right",
+    "top",
+    "bottom",
+    "padding-left",
+    "padding-right",
+    "padding-top",
+    "padding-bottom",
+    "horizontal-gap",
+    "vertical-gap",
+    "width",
+    "height",
+    "min-width",
+    "max-width",
+    "min-height",
+    "max-height",
+    "min-horizontal-gap",
+    "max-horizontal-gap",
+    "min-vertical-gap",
+    "max-vertical-gap",
+    "underline-color",
+    "fill",
+];
+
 impl Style {
     /// Returns the scale factor of the application.
     pub fn scale_factor(&self) -> f32 {
@@ -422,9 +580,46 @@ impl Style {
         physical / self.dpi_factor as f32
     }
 
+    // Returns the scale factor of a window.
+    pub(crate) fn window_scale_factor(&self, window: Entity) -> f64 {
+        self.window_scale_factors.get(window).copied().unwrap_or(self.dpi_factor)
+    }
+
+    // Converts between logical and physical pixels using the scale factor of a window, while its views are laid out
+    // or drawn.
+    pub(crate) fn use_window_scale_factor(&mut self, window: Entity) {
+        self.dpi_factor = self.window_scale_factor(window);
+    }
+
     pub(crate) fn remove_rules(&mut self) {
         self.rule_manager.reset();
         self.rules.clear();
+        self.media_rules.clear();
+        self.inactive_rules.clear();
+        self.inspector_rules.clear();
+        self.invalidation = InvalidationMap::default();
+    }
+
+    /// Updates which rules declared within `@media` blocks are active in a window with the given logical size and
+    /// color scheme. Returns true if any rules were activated or deactivated.
+    pub(crate) fn update_media_rules(
+        &mut self,
+        window: Entity,
+        width: f32,
+        height: f32,
+        color_scheme: ColorScheme,
+    ) -> bool {
+        let inactive_rules = self.inactive_rules.entry(window).or_default();
+        let mut changed = false;
+        for (rule, query) in self.media_rules.iter() {
+            changed |= if query.matches(width, height, color_scheme) {
+                inactive_rules.remove(rule)
+            } else {
+                inactive_rules.insert(*rule)
+            };
+        }
+
+        changed
     }
 
     pub(crate) fn get_animation(&self, name: &str) -> Option<&Animation> {
@@ -586,7 +781,7 @@ impl Style {
                 }
 
                 // BOX SHADOW
-                Property::Shadow(value) => {
+                Property::Shadow(value) | Property::BoxShadow(value) => {
                     insert_keyframe(&mut self.shadow, animation_id, time, value.clone());
                 }
 
@@ -822,6 +1017,142 @@ impl Style {
         self.fill.play_animation(entity, animation, start_time, duration, delay);
     }
 
+    pub(crate) fn play_animation_with(
+        &mut self,
+        entity: Entity,
+        animation: Animation,
+        options: AnimationOptions,
+    ) {
+        self.cancel_animation(entity, animation);
+
+        let mut playback = AnimationPlayback {
+            entity,
+            animation,
+            reversed: None,
+            duration: options.duration,
+            iteration_count: options.iteration_count,
+            direction: options.direction,
+            iteration: 0,
+            end_time: Instant::now() + options.delay + options.duration,
+            paused_at: None,
+            on_complete: options.on_complete,
+        };
+
+        if playback.iteration_count != IterationCount::Count(0) {
+            let id = self.playback_animation(&mut playback);
+            self.enqueue_animation(entity, id, options.duration, options.delay);
+        }
+
+        self.animation_playbacks.push(playback);
+    }
+
+    // Returns the animation to play for the current iteration of a playback, creating a reversed copy of the
+    // animation if the iteration plays backwards.
+    fn playback_animation(&mut self, playback: &mut AnimationPlayback) -> Animation {
+        if !playback.direction.is_reversed(playback.iteration) {
+            return playback.animation;
+        }
+
+        *playback.reversed.get_or_insert_with(|| {
+            let reversed = self.animation_manager.create();
+            for_each_animatable!(self, insert_reversed_animation(playback.animation, reversed));
+            reversed
+        })
+    }
+
+    pub(crate) fn pause_animation(&mut self, entity: Entity, animation: Animation) {
+        self.play_pending_animations();
+
+        let now = Instant::now();
+        for playback in self.animation_playbacks.iter_mut() {
+            if playback.entity == entity && playback.animation == animation {
+                playback.paused_at.get_or_insert(now);
+            }
+        }
+
+        for_each_animatable!(self, pause_animation(entity, animation, now));
+        if let Some(reversed) = self.reversed_animation(entity, animation) {
+            for_each_animatable!(self, pause_animation(entity, reversed, now));
+        }
+    }
+
+    pub(crate) fn resume_animation(&mut self, entity: Entity, animation: Animation) {
+        let now = Instant::now();
+        for playback in self.animation_playbacks.iter_mut() {
+            if playback.entity == entity && playback.animation == animation {
+                if let Some(paused_at) = playback.paused_at.take() {
+                    playback.end_time += now.duration_since(paused_at);
+                }
+            }
+        }
+
+        for_each_animatable!(self, resume_animation(entity, animation, now));
+        if let Some(reversed) = self.reversed_animation(entity, animation) {
+            for_each_animatable!(self, resume_animation(entity, reversed, now));
+        }
+    }
+
+    /// Delays the end of the current iteration of each playback which isn't paused by the given duration, e.g. to
+    /// resume the playbacks after the application was frozen.
+    pub(crate) fn delay_animation_playbacks(&mut self, delay: Duration) {
+        for playback in self.animation_playbacks.iter_mut() {
+            if playback.paused_at.is_none() {
+                playback.end_time += delay;
+            }
+        }
+    }
+
+    pub(crate) fn cancel_animation(&mut self, entity: Entity, animation: Animation) {
+        let reversed = self.reversed_animation(entity, animation);
+
+        self.pending_animations
+            .retain(|(e, a, _, _)| *e != entity || (*a != animation && Some(*a) != reversed));
+        self.animation_playbacks
+            .retain(|playback| playback.entity != entity || playback.animation != animation);
+
+        for_each_animatable!(self, stop_animation(entity, animation));
+        if let Some(reversed) = reversed {
+            for_each_animatable!(self, stop_animation(entity, reversed));
+        }
+    }
+
+    // Returns the reversed copy of an animation created for a playback on the given entity.
+    fn reversed_animation(&self, entity: Entity, animation: Animation) -> Option<Animation> {
+        self.animation_playbacks
+            .iter()
+            .find(|playback| playback.entity == entity && playback.animation == animation)
+            .and_then(|playback| playback.reversed)
+    }
+
+    /// Starts the next iteration of any playbacks whose current iteration has finished, and returns the playbacks
+    /// which have completed their last iteration.
+    pub(crate) fn advance_animation_playbacks(&mut self, time: Instant) -> Vec<AnimationPlayback> {
+        let mut completed = Vec::new();
+        let mut index = 0;
+        while index < self.animation_playbacks.len() {
+            let playback = &self.animation_playbacks[index];
+            if playback.paused_at.is_some() || time < playback.end_time {
+                index += 1;
+                continue;
+            }
+
+            if playback.is_last_iteration(playback.iteration) {
+                completed.push(self.animation_playbacks.remove(index));
+                continue;
+            }
+
+            let mut playback = self.animation_playbacks.remove(index);
+            playback.iteration += 1;
+            playback.end_time = time + playback.duration;
+            let id = self.playback_animation(&mut playback);
+            self.enqueue_animation(playback.entity, id, playback.duration, Duration::ZERO);
+            self.animation_playbacks.insert(index, playback);
+            index += 1;
+        }
+
+        completed
+    }
+
     pub(crate) fn is_animating(&self, entity: Entity, animation: Animation) -> bool {
         self.display.has_active_animation(entity, animation)
             | self.opacity.has_active_animation(entity, animation)
@@ -874,346 +1205,418 @@ impl Style {
 
     pub(crate) fn parse_theme(&mut self, stylesheet: &str) {
         if let Ok(stylesheet) = StyleSheet::parse(stylesheet, ParserOptions::new()) {
-            let rules = stylesheet.rules.0;
-
-            for rule in rules {
-                match rule {
-                    CssRule::Style(style_rule) => {
-                        // let selectors = style_rule.selectors;
-
-                        for selector in style_rule.selectors.slice() {
-                            let rule_id = self.rule_manager.create();
-
-                            for property in style_rule.declarations.declarations.iter() {
-                                match property {
-                                    Property::Transition(transitions) => {
-                                        for transition in transitions.iter() {
-                                            self.insert_transition(rule_id, transition);
-                                        }
-                                    }
+            self.add_rules(stylesheet.rules.0, None);
+        } else {
+            println!("Failed to parse stylesheet");
+        }
+    }
+
+    // Adds a rule with the given declarations for views with the given class, which takes precedence over all other
+    // rules. Returns false if the declarations could not be parsed.
+    pub(crate) fn add_inspector_rule(&mut self, class: &str, declarations: &str) -> bool {
+        let css = format!(".{} {{ {} }}", class, declarations);
+        let Ok(stylesheet) = StyleSheet::parse(&css, ParserOptions::new()) else {
+            return false;
+        };
+
+        let first_rule = self.rules.len();
+        self.add_rules(stylesheet.rules.0, None);
+        let rules = self.rules.keys().skip(first_rule).copied().collect::<Vec<_>>();
+        self.inspector_rules.extend(rules);
 
-                                    _ => {
-                                        self.insert_property(rule_id, property);
+        true
+    }
+
+    // Adds parsed rules to the style, recording the media query of rules declared within `@media` blocks.
+    fn add_rules(&mut self, rules: Vec<CssRule>, media: Option<&MediaQueryList>) {
+        for rule in rules {
+            match rule {
+                CssRule::Style(style_rule) => {
+                    // let selectors = style_rule.selectors;
+
+                    for selector in style_rule.selectors.slice() {
+                        let rule_id = self.rule_manager.create();
+
+                        for property in style_rule.declarations.declarations.iter() {
+                            match property {
+                                Property::Transition(transitions) => {
+                                    for transition in transitions.iter() {
+                                        self.insert_transition(
+                                            TransitionTarget::Rule(rule_id),
+                                            transition,
+                                        );
                                     }
                                 }
+
+                                _ => {
+                                    self.insert_property(rule_id, property);
+                                }
                             }
+                        }
+
+                        self.invalidation.add_selector(selector);
+                        self.rules.insert(rule_id, StyleRule::new(selector.clone()));
 
-                            self.rules.insert(rule_id, StyleRule::new(selector.clone()));
+                        if let Some(media) = media {
+                            self.media_rules.push((rule_id, media.clone()));
                         }
                     }
+                }
 
-                    CssRule::Keyframes(keyframes_rule) => {
-                        let name = keyframes_rule.name.as_string();
+                CssRule::Keyframes(keyframes_rule) => {
+                    let name = keyframes_rule.name.as_string();
 
-                        let animation_id = self.animation_manager.create();
+                    let animation_id = self.animation_manager.create();
 
-                        for keyframes in keyframes_rule.keyframes {
-                            for selector in keyframes.selectors.iter() {
-                                let time = match selector {
-                                    KeyframeSelector::From => 0.0,
-                                    KeyframeSelector::To => 1.0,
-                                    KeyframeSelector::Percentage(percentage) => {
-                                        percentage.0 / 100.0
-                                    }
-                                };
+                    for keyframes in keyframes_rule.keyframes {
+                        for selector in keyframes.selectors.iter() {
+                            let time = match selector {
+                                KeyframeSelector::From => 0.0,
+                                KeyframeSelector::To => 1.0,
+                                KeyframeSelector::Percentage(percentage) => percentage.0 / 100.0,
+                            };
 
-                                self.add_keyframe(
-                                    animation_id,
-                                    time,
-                                    &keyframes.declarations.declarations,
-                                );
-                            }
+                            self.add_keyframe(
+                                animation_id,
+                                time,
+                                &keyframes.declarations.declarations,
+                            );
                         }
-
-                        self.animations.insert(name, animation_id);
                     }
 
-                    _ => {}
+                    self.animations.insert(name, animation_id);
+                }
+
+                CssRule::Media(media_rule) => {
+                    let query = match media {
+                        Some(outer) => outer.and(&media_rule.query),
+                        None => media_rule.query,
+                    };
+
+                    self.add_rules(media_rule.rules.0, Some(&query));
                 }
+
+                _ => {}
             }
-        } else {
-            println!("Failed to parse stylesheet");
         }
     }
 
-    fn insert_transition(&mut self, rule_id: Rule, transition: &Transition) {
+    // Inserts a spring transition for a property of the given entity, which plays when the entity matches a new rule.
+    pub(crate) fn insert_spring_transition(
+        &mut self,
+        entity: Entity,
+        property: &str,
+        spring: Spring,
+    ) {
+        let transition = Transition::new(
+            property.to_string(),
+            Duration::ZERO,
+            None,
+            Some(EasingFunction::Spring(spring)),
+        );
+
+        self.insert_transition(TransitionTarget::Entity(entity), &transition);
+    }
+
+    fn insert_transition(&mut self, target: TransitionTarget, transition: &Transition) {
+        // A transition of `all` or of a shorthand is inserted for each of the properties it covers.
+        let longhands: &[&str] = match transition.property.as_ref() {
+            "all" => ANIMATABLE_PROPERTIES,
+            "space" => &["left", "right", "top", "bottom"],
+            "padding" => &["padding-left", "padding-right", "padding-top", "padding-bottom"],
+            "size" => &["width", "height"],
+            "min-size" => &["min-width", "min-height"],
+            "max-size" => &["max-width", "max-height"],
+            _ => &[],
+        };
+
+        if !longhands.is_empty() {
+            for property in longhands {
+                let transition =
+                    Transition { property: property.to_string(), ..transition.clone() };
+                self.insert_transition(target, &transition);
+            }
+
+            return;
+        }
+
         let animation = self.animation_manager.create();
         match transition.property.as_ref() {
             "display" => {
                 self.display.insert_animation(animation, self.add_transition(transition));
-                self.display.insert_transition(rule_id, animation);
+                self.display.insert_transition(target, animation);
             }
 
             "opacity" => {
                 self.opacity.insert_animation(animation, self.add_transition(transition));
-                self.opacity.insert_transition(rule_id, animation);
+                self.opacity.insert_transition(target, animation);
             }
 
             "clip-path" => {
                 self.clip_path.insert_animation(animation, self.add_transition(transition));
-                self.clip_path.insert_transition(rule_id, animation);
+                self.clip_path.insert_transition(target, animation);
             }
 
             "transform" => {
                 self.transform.insert_animation(animation, self.add_transition(transition));
-                self.transform.insert_transition(rule_id, animation);
+                self.transform.insert_transition(target, animation);
             }
 
             "transform-origin" => {
                 self.transform_origin.insert_animation(animation, self.add_transition(transition));
-                self.transform_origin.insert_transition(rule_id, animation);
+                self.transform_origin.insert_transition(target, animation);
             }
 
             "translate" => {
                 self.translate.insert_animation(animation, self.add_transition(transition));
-                self.translate.insert_transition(rule_id, animation);
+                self.translate.insert_transition(target, animation);
             }
 
             "rotate" => {
                 self.rotate.insert_animation(animation, self.add_transition(transition));
-                self.rotate.insert_transition(rule_id, animation);
+                self.rotate.insert_transition(target, animation);
             }
 
             "scale" => {
                 self.scale.insert_animation(animation, self.add_transition(transition));
-                self.scale.insert_transition(rule_id, animation);
+                self.scale.insert_transition(target, animation);
             }
 
             "border" => {
                 self.border_width.insert_animation(animation, self.add_transition(transition));
-                self.border_width.insert_transition(rule_id, animation);
+                self.border_width.insert_transition(target, animation);
                 self.border_color.insert_animation(animation, self.add_transition(transition));
-                self.border_color.insert_transition(rule_id, animation);
+                self.border_color.insert_transition(target, animation);
             }
 
             "border-width" => {
                 self.border_width.insert_animation(animation, self.add_transition(transition));
-                self.border_width.insert_transition(rule_id, animation);
+                self.border_width.insert_transition(target, animation);
             }
 
             "border-color" => {
                 self.border_color.insert_animation(animation, self.add_transition(transition));
-                self.border_color.insert_transition(rule_id, animation);
+                self.border_color.insert_transition(target, animation);
             }
 
             "corner-radius" => {
                 self.corner_bottom_left_radius
                     .insert_animation(animation, self.add_transition(transition));
-                self.corner_bottom_left_radius.insert_transition(rule_id, animation);
+                self.corner_bottom_left_radius.insert_transition(target, animation);
                 self.corner_bottom_right_radius
                     .insert_animation(animation, self.add_transition(transition));
-                self.corner_bottom_right_radius.insert_transition(rule_id, animation);
+                self.corner_bottom_right_radius.insert_transition(target, animation);
                 self.corner_top_left_radius
                     .insert_animation(animation, self.add_transition(transition));
-                self.corner_top_left_radius.insert_transition(rule_id, animation);
+                self.corner_top_left_radius.insert_transition(target, animation);
                 self.corner_top_right_radius
                     .insert_animation(animation, self.add_transition(transition));
-                self.corner_top_right_radius.insert_transition(rule_id, animation);
+                self.corner_top_right_radius.insert_transition(target, animation);
             }
 
             "corner-top-left-radius" => {
                 self.corner_top_left_radius
                     .insert_animation(animation, self.add_transition(transition));
-                self.corner_top_left_radius.insert_transition(rule_id, animation);
+                self.corner_top_left_radius.insert_transition(target, animation);
             }
 
             "corner-top-right-radius" => {
                 self.corner_top_right_radius
                     .insert_animation(animation, self.add_transition(transition));
-                self.corner_top_right_radius.insert_transition(rule_id, animation);
+                self.corner_top_right_radius.insert_transition(target, animation);
             }
 
             "corner-bottom-left-radius" => {
                 self.corner_bottom_left_radius
                     .insert_animation(animation, self.add_transition(transition));
-                self.corner_bottom_left_radius.insert_transition(rule_id, animation);
+                self.corner_bottom_left_radius.insert_transition(target, animation);
             }
 
             "corner-bottom-right-radius" => {
                 self.corner_bottom_right_radius
                     .insert_animation(animation, self.add_transition(transition));
-                self.corner_bottom_right_radius.insert_transition(rule_id, animation);
+                self.corner_bottom_right_radius.insert_transition(target, animation);
             }
 
             "outline" => {
                 self.outline_width.insert_animation(animation, self.add_transition(transition));
-                self.outline_width.insert_transition(rule_id, animation);
+                self.outline_width.insert_transition(target, animation);
                 self.outline_color.insert_animation(animation, self.add_transition(transition));
-                self.outline_color.insert_transition(rule_id, animation);
+                self.outline_color.insert_transition(target, animation);
             }
 
             "outline-width" => {
                 self.outline_width.insert_animation(animation, self.add_transition(transition));
-                self.outline_width.insert_transition(rule_id, animation);
+                self.outline_width.insert_transition(target, animation);
             }
 
             "outline-color" => {
                 self.outline_color.insert_animation(animation, self.add_transition(transition));
-                self.outline_color.insert_transition(rule_id, animation);
+                self.outline_color.insert_transition(target, animation);
             }
 
             "outline-offset" => {
                 self.outline_offset.insert_animation(animation, self.add_transition(transition));
-                self.outline_offset.insert_transition(rule_id, animation);
+                self.outline_offset.insert_transition(target, animation);
             }
 
             "background-color" => {
                 self.background_color.insert_animation(animation, self.add_transition(transition));
-                self.background_color.insert_transition(rule_id, animation);
+                self.background_color.insert_transition(target, animation);
             }
 
             "background-image" => {
                 self.background_image.insert_animation(animation, self.add_transition(transition));
-                self.background_image.insert_transition(rule_id, animation);
+                self.background_image.insert_transition(target, animation);
             }
 
             "background-size" => {
                 self.background_size.insert_animation(animation, self.add_transition(transition));
-                self.background_size.insert_transition(rule_id, animation);
+                self.background_size.insert_transition(target, animation);
             }
 
-            "shadow" => {
+            "shadow" | "box-shadow" => {
                 self.shadow.insert_animation(animation, self.add_transition(transition));
-                self.shadow.insert_transition(rule_id, animation);
+                self.shadow.insert_transition(target, animation);
             }
 
             "color" => {
                 self.font_color.insert_animation(animation, self.add_transition(transition));
-                self.font_color.insert_transition(rule_id, animation);
+                self.font_color.insert_transition(target, animation);
             }
 
             "font-size" => {
                 self.font_size.insert_animation(animation, self.add_transition(transition));
-                self.font_size.insert_transition(rule_id, animation);
+                self.font_size.insert_transition(target, animation);
             }
 
             "caret-color" => {
                 self.caret_color.insert_animation(animation, self.add_transition(transition));
-                self.caret_color.insert_transition(rule_id, animation);
+                self.caret_color.insert_transition(target, animation);
             }
 
             "selection-color" => {
                 self.selection_color.insert_animation(animation, self.add_transition(transition));
-                self.selection_color.insert_transition(rule_id, animation);
+                self.selection_color.insert_transition(target, animation);
             }
 
             "left" => {
                 self.left.insert_animation(animation, self.add_transition(transition));
-                self.left.insert_transition(rule_id, animation);
+                self.left.insert_transition(target, animation);
             }
 
             "right" => {
                 self.right.insert_animation(animation, self.add_transition(transition));
-                self.right.insert_transition(rule_id, animation);
+                self.right.insert_transition(target, animation);
             }
 
             "top" => {
                 self.top.insert_animation(animation, self.add_transition(transition));
-                self.top.insert_transition(rule_id, animation);
+                self.top.insert_transition(target, animation);
             }
 
             "bottom" => {
                 self.bottom.insert_animation(animation, self.add_transition(transition));
-                self.bottom.insert_transition(rule_id, animation);
+                self.bottom.insert_transition(target, animation);
             }
 
             "padding-left" => {
                 self.padding_left.insert_animation(animation, self.add_transition(transition));
-                self.padding_left.insert_transition(rule_id, animation);
+                self.padding_left.insert_transition(target, animation);
             }
 
             "padding-right" => {
                 self.padding_right.insert_animation(animation, self.add_transition(transition));
-                self.padding_right.insert_transition(rule_id, animation);
+                self.padding_right.insert_transition(target, animation);
             }
 
             "padding-top" => {
                 self.padding_top.insert_animation(animation, self.add_transition(transition));
-                self.padding_top.insert_transition(rule_id, animation);
+                self.padding_top.insert_transition(target, animation);
             }
 
             "padding-bottom" => {
                 self.padding_bottom.insert_animation(animation, self.add_transition(transition));
-                self.padding_bottom.insert_transition(rule_id, animation);
+                self.padding_bottom.insert_transition(target, animation);
             }
 
             "horizontal-gap" => {
                 self.horizontal_gap.insert_animation(animation, self.add_transition(transition));
-                self.horizontal_gap.insert_transition(rule_id, animation);
+                self.horizontal_gap.insert_transition(target, animation);
             }
 
             "vertical-gap" => {
                 self.vertical_gap.insert_animation(animation, self.add_transition(transition));
-                self.vertical_gap.insert_transition(rule_id, animation);
+                self.vertical_gap.insert_transition(target, animation);
             }
 
             "gap" => {
                 self.horizontal_gap.insert_animation(animation, self.add_transition(transition));
-                self.horizontal_gap.insert_transition(rule_id, animation);
+                self.horizontal_gap.insert_transition(target, animation);
                 self.vertical_gap.insert_animation(animation, self.add_transition(transition));
-                self.vertical_gap.insert_transition(rule_id, animation);
+                self.vertical_gap.insert_transition(target, animation);
             }
 
             "width" => {
                 self.width.insert_animation(animation, self.add_transition(transition));
-                self.width.insert_transition(rule_id, animation);
+                self.width.insert_transition(target, animation);
             }
 
             "height" => {
                 self.height.insert_animation(animation, self.add_transition(transition));
-                self.height.insert_transition(rule_id, animation);
+                self.height.insert_transition(target, animation);
             }
 
             "min-width" => {
                 self.min_width.insert_animation(animation, self.add_transition(transition));
-                self.min_width.insert_transition(rule_id, animation);
+                self.min_width.insert_transition(target, animation);
             }
 
             "max-width" => {
                 self.max_width.insert_animation(animation, self.add_transition(transition));
-                self.max_width.insert_transition(rule_id, animation);
+                self.max_width.insert_transition(target, animation);
             }
 
             "min-height" => {
                 self.min_height.insert_animation(animation, self.add_transition(transition));
-                self.min_height.insert_transition(rule_id, animation);
+                self.min_height.insert_transition(target, animation);
             }
 
             "max-height" => {
                 self.max_height.insert_animation(animation, self.add_transition(transition));
-                self.max_height.insert_transition(rule_id, animation);
+                self.max_height.insert_transition(target, animation);
             }
 
             "min-horizontal-gap" => {
                 self.min_horizontal_gap
                     .insert_animation(animation, self.add_transition(transition));
-                self.min_horizontal_gap.insert_transition(rule_id, animation);
+                self.min_horizontal_gap.insert_transition(target, animation);
             }
 
             "max-horizontal-gap" => {
                 self.max_horizontal_gap
                     .insert_animation(animation, self.add_transition(transition));
-                self.max_horizontal_gap.insert_transition(rule_id, animation);
+                self.max_horizontal_gap.insert_transition(target, animation);
             }
 
             "min-vertical-gap" => {
                 self.min_vertical_gap.insert_animation(animation, self.add_transition(transition));
-                self.min_vertical_gap.insert_transition(rule_id, animation);
+                self.min_vertical_gap.insert_transition(target, animation);
             }
 
             "max-vertical-gap" => {
                 self.max_vertical_gap.insert_animation(animation, self.add_transition(transition));
-                self.max_vertical_gap.insert_transition(rule_id, animation);
+                self.max_vertical_gap.insert_transition(target, animation);
             }
 
             "underline-color" => {
                 self.underline_color.insert_animation(animation, self.add_transition(transition));
-                self.underline_color.insert_transition(rule_id, animation);
+                self.underline_color.insert_transition(target, animation);
             }
 
             "fill" => {
                 self.fill.insert_animation(animation, self.add_transition(transition));
-                self.fill.insert_transition(rule_id, animation);
+                self.fill.insert_transition(target, animation);
             }
 
             _ => {}
@@ -1266,6 +1669,18 @@ impl Style {
                 self.alignment.insert_rule(rule_id, alignment);
             }
 
+            Property::AlignSelf(align_self) => {
+                self.align_self.insert_rule(rule_id, align_self);
+            }
+
+            Property::JustifySelf(justify_self) => {
+                self.justify_self.insert_rule(rule_id, justify_self);
+            }
+
+            Property::FlexWrap(flex_wrap) => {
+                self.flex_wrap.insert_rule(rule_id, flex_wrap);
+            }
+
             // Grid
             Property::GridColumns(columns) => {
                 self.grid_columns.insert_rule(rule_id, columns);
@@ -1291,6 +1706,22 @@ impl Style {
                 self.row_span.insert_rule(rule_id, span);
             }
 
+            Property::GridTemplateColumns(columns) => {
+                self.grid_columns.insert_rule(rule_id, columns);
+            }
+
+            Property::GridTemplateRows(rows) => {
+                self.grid_rows.insert_rule(rule_id, rows);
+            }
+
+            Property::GridColumn(placement) => {
+                self.grid_column.insert_rule(rule_id, placement);
+            }
+
+            Property::GridRow(placement) => {
+                self.grid_row.insert_rule(rule_id, placement);
+            }
+
             // Space
             Property::Space(space) => {
                 self.left.insert_rule(rule_id, space);
@@ -1651,8 +2082,13 @@ impl Style {
                 self.text_align.insert_rule(rule_id, text_align);
             }
 
+            // Direction
+            Property::Direction(direction) => {
+                self.direction.insert_rule(rule_id, direction);
+            }
+
             // Box Shadows
-            Property::Shadow(shadows) => {
+            Property::Shadow(shadows) | Property::BoxShadow(shadows) => {
                 self.shadow.insert_rule(rule_id, shadows);
             }
 
@@ -1693,6 +2129,15 @@ impl Style {
             Property::TextStrokeStyle(stroke_style) => {
                 self.text_stroke_style.insert_rule(rule_id, stroke_style);
             }
+            Property::LetterSpacing(letter_spacing) => {
+                self.letter_spacing.insert_rule(rule_id, letter_spacing);
+            }
+            Property::WordSpacing(word_spacing) => {
+                self.word_spacing.insert_rule(rule_id, word_spacing);
+            }
+            Property::TextShadow(text_shadow) => {
+                self.text_shadow.insert_rule(rule_id, text_shadow);
+            }
             Property::Fill(fill) => {
                 self.fill.insert_rule(rule_id, fill);
             }
@@ -1714,11 +2159,20 @@ impl Style {
                 EasingFunction::EaseOut => TimingFunction::ease_out(),
                 EasingFunction::EaseInOut => TimingFunction::ease_in_out(),
                 EasingFunction::CubicBezier(x1, y1, x2, y2) => TimingFunction::new(x1, y1, x2, y2),
+                EasingFunction::Spring(spring) => {
+                    TimingFunction::spring(SpringCurve::new(spring, 0.0))
+                }
             })
             .unwrap_or_default();
 
+        // The duration of a spring is the time taken for it to settle.
+        let duration = timing_function
+            .spring
+            .map(|curve| Duration::from_secs_f32(curve.duration))
+            .unwrap_or(transition.duration);
+
         AnimationState::new(Animation::null())
-            .with_duration(transition.duration)
+            .with_duration(duration)
             .with_delay(transition.delay.unwrap_or_default())
             .with_keyframe(Keyframe { time: 0.0, value: Default::default(), timing_function })
             .with_keyframe(Keyframe { time: 1.0, value: Default::default(), timing_function })
@@ -1820,6 +2274,7 @@ impl Style {
         self.text_overflow.remove(entity);
         self.line_clamp.remove(entity);
         self.text_align.remove(entity);
+        self.direction.remove(entity);
         self.font_family.remove(entity);
         self.font_color.remove(entity);
         self.font_size.remove(entity);
@@ -1832,6 +2287,9 @@ impl Style {
         self.text_decoration_line.remove(entity);
         self.text_stroke_width.remove(entity);
         self.text_stroke_style.remove(entity);
+        self.letter_spacing.remove(entity);
+        self.word_spacing.remove(entity);
+        self.text_shadow.remove(entity);
 
         // Cursor
         self.cursor.remove(entity);
@@ -1845,6 +2303,13 @@ impl Style {
         self.position_type.remove(entity);
 
         self.alignment.remove(entity);
+        self.align_self.remove(entity);
+        self.justify_self.remove(entity);
+        self.flex_wrap.remove(entity);
+        self.wrapped_size.remove(entity);
+        self.min_content_size.remove(entity);
+        self.window_scale_factors.remove(entity);
+        self.inactive_rules.remove(&entity);
 
         // Grid
         self.grid_columns.remove(entity);
@@ -1853,6 +2318,8 @@ impl Style {
         self.column_span.remove(entity);
         self.row_start.remove(entity);
         self.row_span.remove(entity);
+        self.grid_column.remove(entity);
+        self.grid_row.remove(entity);
 
         // Space
         self.left.remove(entity);
@@ -1889,14 +2356,87 @@ impl Style {
 
         self.text_range.remove(entity);
         self.text_span.remove(entity);
+        self.misspelled.remove(entity);
 
         self.fill.remove(entity);
     }
 
+    /// Adds a class to an entity, returning `true` if the entity did not already have the class.
+    pub(crate) fn add_class(&mut self, entity: Entity, class_name: &str) -> bool {
+        if let Some(class_list) = self.classes.get_mut(entity) {
+            class_list.insert(class_name.to_string())
+        } else {
+            let mut class_list = HashSet::new();
+            class_list.insert(class_name.to_string());
+            self.classes.insert(entity, class_list);
+            true
+        }
+    }
+
+    /// Removes a class from an entity, returning `true` if the entity had the class.
+    pub(crate) fn remove_class(&mut self, entity: Entity, class_name: &str) -> bool {
+        self.classes.get_mut(entity).is_some_and(|class_list| class_list.remove(class_name))
+    }
+
     pub(crate) fn needs_restyle(&mut self, entity: Entity) {
         self.restyle.0.insert(entity).unwrap();
     }
 
+    /// Marks the views whose matched rules can be affected by a change to the given pseudo-classes of an entity as
+    /// needing a restyle.
+    pub(crate) fn invalidate_pseudo_classes(
+        &mut self,
+        tree: &Tree<Entity>,
+        entity: Entity,
+        changed: PseudoClassFlags,
+    ) {
+        let invalidation = self.invalidation.pseudo_classes(changed);
+        self.invalidate(tree, entity, invalidation);
+    }
+
+    /// Marks the views whose matched rules can be affected by adding or removing a class of an entity as needing a
+    /// restyle.
+    pub(crate) fn invalidate_class(
+        &mut self,
+        tree: &Tree<Entity>,
+        entity: Entity,
+        class_name: &str,
+    ) {
+        let invalidation = self.invalidation.class(class_name);
+        self.invalidate(tree, entity, invalidation);
+    }
+
+    fn invalidate(&mut self, tree: &Tree<Entity>, entity: Entity, invalidation: Invalidation) {
+        let root = match invalidation {
+            Invalidation::None => return,
+            Invalidation::Entity => {
+                self.needs_restyle(entity);
+                return;
+            }
+            Invalidation::Descendants => entity,
+            Invalidation::Siblings => tree.get_layout_parent(entity).unwrap_or(entity),
+            Invalidation::Ancestors => {
+                for ancestor in LayoutParentIterator::new(tree, entity) {
+                    match tree.get_layout_parent(ancestor) {
+                        Some(parent) => {
+                            for sibling in LayoutChildIterator::new(tree, parent) {
+                                self.needs_restyle(sibling);
+                            }
+                        }
+                        None => self.needs_restyle(ancestor),
+                    }
+                }
+
+                tree.get_layout_parent(entity).unwrap_or(entity)
+            }
+            Invalidation::Tree => Entity::root(),
+        };
+
+        for descendant in LayoutTreeIterator::subtree(tree, root) {
+            self.restyle.0.insert(descendant).unwrap();
+        }
+    }
+
     pub(crate) fn needs_relayout(&mut self) {
         self.system_flags.set(SystemFlags::RELAYOUT, true);
     }
@@ -1989,12 +2529,19 @@ impl Style {
         self.layout_type.clear_rules();
         self.position_type.clear_rules();
         self.alignment.clear_rules();
+        self.align_self.clear_rules();
+        self.justify_self.clear_rules();
+        self.flex_wrap.clear_rules();
 
         // Grid
         self.grid_columns.clear_rules();
         self.grid_rows.clear_rules();
         self.column_start.clear_rules();
         self.column_span.clear_rules();
+        self.row_start.clear_rules();
+        self.row_span.clear_rules();
+        self.grid_column.clear_rules();
+        self.grid_row.clear_rules();
 
         // Space
         self.left.clear_rules();
@@ -2034,6 +2581,7 @@ impl Style {
         self.text_overflow.clear_rules();
         self.line_clamp.clear_rules();
         self.text_align.clear_rules();
+        self.direction.clear_rules();
         self.font_family.clear_rules();
         self.font_weight.clear_rules();
         self.font_slant.clear_rules();
@@ -2045,6 +2593,9 @@ impl Style {
         self.text_decoration_line.clear_rules();
         self.text_stroke_width.clear_rules();
         self.text_stroke_style.clear_rules();
+        self.letter_spacing.clear_rules();
+        self.word_spacing.clear_rules();
+        self.text_shadow.clear_rules();
 
         self.cursor.clear_rules();
 
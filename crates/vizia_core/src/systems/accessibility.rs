@@ -156,6 +156,9 @@ pub(crate) fn get_access_node(
         node_builder.remove_action(Action::Focus);
     }
 
+    // Any view can be scrolled into view by its ancestor scrollviews.
+    node_builder.add_action(Action::ScrollIntoView);
+
     if let Some(value) = cx.style.text_value.get(entity) {
         node_builder.set_value(value.clone().into_boxed_str());
     }
@@ -196,13 +199,10 @@ pub(crate) fn get_access_node(
         .unwrap_or_default();
 
     if checkable {
-        if let Some(checked) = cx
-            .style
-            .pseudo_classes
-            .get(entity)
-            .map(|pseudoclass| pseudoclass.contains(PseudoClassFlags::CHECKED))
-        {
-            if checked {
+        if let Some(pseudo_classes) = cx.style.pseudo_classes.get(entity) {
+            if pseudo_classes.contains(PseudoClassFlags::INDETERMINATE) {
+                node_builder.set_toggled(Toggled::Mixed);
+            } else if pseudo_classes.contains(PseudoClassFlags::CHECKED) {
                 node_builder.set_toggled(Toggled::True);
             } else {
                 node_builder.set_toggled(Toggled::False);
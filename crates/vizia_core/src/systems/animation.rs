@@ -26,6 +26,7 @@ macro_rules! process_auto_animations {
                     &mut SubLayout {
                         text_context: &mut $cx.text_context,
                         resource_manager: &$cx.resource_manager,
+                        reuse_text: false,
                     },
                 );
 
@@ -58,7 +59,15 @@ macro_rules! process_auto_animations {
     };
 }
 
-pub(crate) fn animation_system(cx: &mut Context) -> bool {
+pub(crate) fn animation_system(cx: &mut Context, time: Instant) -> bool {
+    // Repeat or complete the animations played with options.
+    cx.style.animation_playbacks.retain(|playback| cx.entity_manager.is_alive(playback.entity));
+    for playback in cx.style.advance_animation_playbacks(time) {
+        if let Some(on_complete) = playback.on_complete {
+            cx.with_current(playback.entity, |cx| on_complete(&mut EventContext::new(cx)));
+        }
+    }
+
     cx.style.play_pending_animations();
 
     process_auto_animations!(cx, cx.style.max_height, true);
@@ -112,8 +121,6 @@ pub(crate) fn animation_system(cx: &mut Context) -> bool {
 
     // Tick all animations
 
-    let time = Instant::now();
-
     let mut redraw_entities = Vec::new();
     let mut reflow_entities = Vec::new();
     let mut relayout_entities = Vec::new();
@@ -197,5 +204,141 @@ pub(crate) fn animation_system(cx: &mut Context) -> bool {
         cx.style.text_construction.insert(*entity).unwrap();
     }
 
-    !redraw_entities.is_empty() | !relayout_entities.is_empty() | !reflow_entities.is_empty()
+    !redraw_entities.is_empty()
+        | !relayout_entities.is_empty()
+        | !reflow_entities.is_empty()
+        | cx.style.animation_playbacks.iter().any(|playback| playback.paused_at.is_none())
+}
+
+// Delays all active animations by the given duration so that they resume where they left off after a freeze.
+pub(crate) fn delay_animations(cx: &mut Context, delay: Duration) {
+    cx.style.delay_animation_playbacks(delay);
+
+    cx.style.opacity.delay_animations(delay);
+    cx.style.border_color.delay_animations(delay);
+    cx.style.corner_top_left_radius.delay_animations(delay);
+    cx.style.corner_top_right_radius.delay_animations(delay);
+    cx.style.corner_bottom_left_radius.delay_animations(delay);
+    cx.style.corner_bottom_right_radius.delay_animations(delay);
+    cx.style.background_color.delay_animations(delay);
+    cx.style.background_image.delay_animations(delay);
+    cx.style.background_size.delay_animations(delay);
+    cx.style.shadow.delay_animations(delay);
+    cx.style.transform.delay_animations(delay);
+    cx.style.transform_origin.delay_animations(delay);
+    cx.style.translate.delay_animations(delay);
+    cx.style.rotate.delay_animations(delay);
+    cx.style.scale.delay_animations(delay);
+    cx.style.outline_color.delay_animations(delay);
+    cx.style.outline_offset.delay_animations(delay);
+    cx.style.outline_width.delay_animations(delay);
+    cx.style.clip_path.delay_animations(delay);
+    cx.style.fill.delay_animations(delay);
+    cx.style.font_color.delay_animations(delay);
+    cx.style.font_size.delay_animations(delay);
+    cx.style.display.delay_animations(delay);
+    cx.style.border_width.delay_animations(delay);
+    cx.style.left.delay_animations(delay);
+    cx.style.right.delay_animations(delay);
+    cx.style.top.delay_animations(delay);
+    cx.style.bottom.delay_animations(delay);
+    cx.style.width.delay_animations(delay);
+    cx.style.height.delay_animations(delay);
+    cx.style.max_width.delay_animations(delay);
+    cx.style.max_height.delay_animations(delay);
+    cx.style.min_width.delay_animations(delay);
+    cx.style.min_height.delay_animations(delay);
+    cx.style.max_horizontal_gap.delay_animations(delay);
+    cx.style.max_vertical_gap.delay_animations(delay);
+    cx.style.min_horizontal_gap.delay_animations(delay);
+    cx.style.min_vertical_gap.delay_animations(delay);
+    cx.style.vertical_gap.delay_animations(delay);
+    cx.style.horizontal_gap.delay_animations(delay);
+    cx.style.padding_left.delay_animations(delay);
+    cx.style.padding_right.delay_animations(delay);
+    cx.style.padding_top.delay_animations(delay);
+    cx.style.padding_bottom.delay_animations(delay);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use crate::context::test_support::{update, update_at, window_context};
+    use crate::prelude::*;
+
+    #[test]
+    fn stagger_offsets() {
+        let mut cx = window_context(400, 300);
+
+        let fade_in = cx.0.add_animation(
+            AnimationBuilder::new()
+                .keyframe(0.0, |key| key.opacity(0.0))
+                .keyframe(1.0, |key| key.opacity(1.0)),
+        );
+
+        let items = (0..3).map(|_| Element::new(cx.context()).entity()).collect::<Vec<_>>();
+
+        cx.0.stagger_animation(
+            fade_in,
+            &items,
+            Duration::from_millis(300),
+            Duration::from_millis(50),
+        );
+
+        // Each item starts an additional delay after the previous one, and all of them play for the same duration.
+        let pending =
+            cx.0.style
+                .pending_animations
+                .iter()
+                .map(|(entity, _, duration, delay)| (*entity, *duration, *delay))
+                .collect::<Vec<_>>();
+        assert_eq!(
+            pending,
+            [
+                (items[0], Duration::from_millis(300), Duration::ZERO),
+                (items[1], Duration::from_millis(300), Duration::from_millis(50)),
+                (items[2], Duration::from_millis(300), Duration::from_millis(100)),
+            ]
+        );
+
+        update(&mut cx);
+
+        assert!(cx.0.style.pending_animations.is_empty());
+        assert!(cx.0.style.is_animating(items[0], fade_in));
+    }
+
+    #[test]
+    fn thaw_delays_animation_playbacks() {
+        let mut cx = window_context(400, 300);
+
+        let fade_in = cx.0.add_animation(
+            AnimationBuilder::new()
+                .keyframe(0.0, |key| key.opacity(0.0))
+                .keyframe(1.0, |key| key.opacity(1.0)),
+        );
+
+        let entity = Element::new(cx.context()).entity();
+        let completed = Rc::new(RefCell::new(0));
+        let counter = completed.clone();
+        cx.0.play_animation_with(
+            fade_in,
+            entity,
+            AnimationOptions { duration: Duration::from_millis(100), ..Default::default() }
+                .on_complete(move |_| *counter.borrow_mut() += 1),
+        );
+        update(&mut cx);
+
+        // The time spent frozen doesn't count towards the duration of the animation.
+        cx.0.freeze(false);
+        let (frozen_at, _) = cx.0.frozen.unwrap();
+        let thawed_at = frozen_at + Duration::from_millis(150);
+        cx.0.thaw_at(thawed_at);
+        update_at(&mut cx, thawed_at);
+        assert_eq!(*completed.borrow(), 0);
+
+        update_at(&mut cx, thawed_at + Duration::from_millis(120));
+        assert_eq!(*completed.borrow(), 1);
+    }
 }
@@ -3,6 +3,9 @@ use hashbrown::{HashMap, HashSet};
 use std::any::TypeId;
 
 pub(crate) fn binding_system(cx: &mut Context) {
+    // Models and views may also have been changed directly, e.g. with `Handle::modify`.
+    crate::context::data_changed();
+
     let mut observers: HashMap<Entity, (Entity, TypeId, StoreId)> = HashMap::new();
 
     for (entity, stores) in cx.stores.iter_mut() {
@@ -68,6 +71,10 @@ fn update_binding(cx: &mut Context, observer: Entity) {
         cx.with_current(observer, |cx| {
             binding.update(cx);
         });
-        cx.bindings.insert(observer, binding);
+
+        // The binding may have been removed while updating, e.g. by an ancestor binding rebuilding its content.
+        if cx.entity_manager.is_alive(observer) {
+            cx.bindings.insert(observer, binding);
+        }
     }
 }
@@ -1,10 +1,10 @@
+use crate::inspector::{Inspector, HIGHLIGHT_COLOR};
 use crate::{animation::Interpolator, cache::CachedData, prelude::*};
 use morphorm::Node;
 use skia_safe::{
-    canvas::SaveLayerRec, ClipOp, ImageFilter, Matrix, Paint, Rect, SamplingOptions, Surface,
+    canvas::SaveLayerRec, region::RegionOp, ClipOp, ImageFilter, Matrix, Paint, Rect, Region,
+    SamplingOptions, Surface,
 };
-use std::cmp::Ordering;
-use std::collections::BinaryHeap;
 use vizia_storage::{DrawChildIterator, LayoutTreeIterator};
 use vizia_style::BlendMode;
 
@@ -158,10 +158,7 @@ pub(crate) fn draw_system(
 
     transform_system(cx);
 
-    let window = cx.windows.get_mut(&window_entity).unwrap();
-
-    let mut dirty_rect = std::mem::take(&mut window.dirty_rect);
-    let redraw_list = std::mem::take(&mut window.redraw_list);
+    let redraw_list = std::mem::take(&mut cx.windows.get_mut(&window_entity).unwrap().redraw_list);
 
     // if redraw_list.is_empty() {
     //     return false;
@@ -186,13 +183,7 @@ pub(crate) fn draw_system(
                 dirty_bounds = dirty_bounds.union(previous_draw_bounds);
             }
 
-            if dirty_bounds.w != 0.0 && dirty_bounds.h != 0.0 {
-                if let Some(dr) = &mut dirty_rect {
-                    *dr = dr.union(&dirty_bounds);
-                } else {
-                    dirty_rect = Some(dirty_bounds);
-                }
-            }
+            cx.windows.get_mut(&window_entity).unwrap().add_dirty_region(dirty_bounds);
 
             if let Some(dr) = cx.cache.draw_bounds.get_mut(entity) {
                 *dr = draw_bounds;
@@ -206,50 +197,81 @@ pub(crate) fn draw_system(
     //     return true;
     // }
 
+    let window = cx.windows.get_mut(&window_entity).unwrap();
+    let dirty_rect = std::mem::take(&mut window.dirty_rect);
+    let dirty_regions = std::mem::take(&mut window.dirty_regions);
+
     let canvas = dirty_surface.canvas();
 
     canvas.save();
 
-    if let Some(rect) = dirty_rect.map(Rect::from) {
-        canvas.clip_rect(rect, ClipOp::Intersect, false);
+    // Only repaint the damaged regions of the window.
+    if dirty_rect.is_some() {
+        let mut region = Region::new();
+        for dirty_region in dirty_regions.iter() {
+            region.op_rect(Rect::from(*dirty_region).round_out(), RegionOp::Union);
+        }
+
+        canvas.clip_region(&region, ClipOp::Intersect);
         canvas.clear(Color::transparent());
     }
 
     cx.resource_manager.mark_images_unused();
 
-    let mut queue = BinaryHeap::new();
-    queue.push(ZEntity { index: 0, entity: window_entity, visible: true });
-
-    while let Some(zentity) = queue.pop() {
-        canvas.save();
-        draw_entity(
-            &mut DrawContext {
-                current: zentity.entity,
-                style: &cx.style,
-                cache: &mut cx.cache,
-                tree: &cx.tree,
-                models: &cx.models,
-                views: &mut cx.views,
-                resource_manager: &cx.resource_manager,
-                text_context: &mut cx.text_context,
-                modifiers: &cx.modifiers,
-                mouse: &cx.mouse,
-                windows: &mut cx.windows,
-            },
-            &dirty_rect,
-            canvas,
-            zentity.index,
-            &mut queue,
-            zentity.visible,
-        );
-        canvas.restore();
-    }
+    draw_stacking_context(
+        &mut DrawContext {
+            current: window_entity,
+            style: &cx.style,
+            cache: &mut cx.cache,
+            tree: &cx.tree,
+            models: &cx.models,
+            views: &mut cx.views,
+            resource_manager: &cx.resource_manager,
+            text_context: &mut cx.text_context,
+            modifiers: &cx.modifiers,
+            mouse: &cx.mouse,
+            windows: &mut cx.windows,
+        },
+        &dirty_rect,
+        &dirty_regions,
+        canvas,
+        true,
+    );
 
     canvas.restore();
 
     surface.canvas().clear(Color::transparent());
     dirty_surface.draw(surface.canvas(), (0, 0), SamplingOptions::default(), None);
 
+    // Highlight the bounds of the hovered view, or else the selected view, while the inspector is shown.
+    if let Some(inspector) = cx.inspector {
+        let hovered = cx.hovered;
+        let highlighted = if hovered != window_entity
+            && hovered != inspector
+            && !hovered.is_descendant_of(&cx.tree, inspector)
+        {
+            Some(hovered)
+        } else {
+            cx.views
+                .get(&inspector)
+                .and_then(|view| view.downcast_ref::<Inspector>())
+                .and_then(|inspector| inspector.selected)
+        };
+
+        if let Some(entity) = highlighted.filter(|entity| {
+            cx.tree.get_parent_window(*entity).unwrap_or(Entity::root()) == window_entity
+        }) {
+            let rect = Rect::from(cx.cache.get_bounds(entity));
+            let mut paint = Paint::default();
+            paint.set_color(HIGHLIGHT_COLOR);
+            surface.canvas().draw_rect(rect, &paint);
+            paint.set_style(skia_safe::PaintStyle::Stroke);
+            paint.set_stroke_width(1.0);
+            paint.set_alpha(255);
+            surface.canvas().draw_rect(rect, &paint);
+        }
+    }
+
     // Debug draw dirty rect
     // if let Some(rect) = dirty_rect.map(Rect::from) {
     //     let mut paint = Paint::default();
@@ -262,12 +284,85 @@ pub(crate) fn draw_system(
     true
 }
 
+// A view with a non-zero z-index, which is drawn separately from the other views of its stacking context.
+struct Layer {
+    z_index: i32,
+    entity: Entity,
+    // Whether the parent of the view is visible.
+    visible: bool,
+}
+
+// Draws a view which forms a stacking context, along with its descendants.
+//
+// Descendants with a non-zero z-index form stacking contexts of their own, which are drawn in order of z-index, and
+// then in tree order, below the view if the z-index is negative or above it if the z-index is positive. The z-index of
+// a view only orders it within the stacking context of its nearest layered ancestor.
+fn draw_stacking_context(
+    cx: &mut DrawContext,
+    dirty_rect: &Option<BoundingBox>,
+    dirty_regions: &[BoundingBox],
+    canvas: &Canvas,
+    visible: bool,
+) {
+    let current = cx.current;
+
+    let mut layers = Vec::new();
+    collect_layers(cx, current, visible, &mut layers);
+    // The sort is stable so layers with the same z-index stay in tree order.
+    layers.sort_by_key(|layer| layer.z_index);
+
+    let split = layers.partition_point(|layer| layer.z_index < 0);
+    let draw_layer = |cx: &mut DrawContext, layer: &Layer| {
+        cx.current = layer.entity;
+        canvas.save();
+        draw_stacking_context(cx, dirty_rect, dirty_regions, canvas, layer.visible);
+        canvas.restore();
+    };
+
+    for layer in layers[..split].iter() {
+        draw_layer(cx, layer);
+    }
+
+    cx.current = current;
+    canvas.save();
+    draw_entity(cx, dirty_rect, dirty_regions, canvas, true, visible);
+    canvas.restore();
+
+    for layer in layers[split..].iter() {
+        draw_layer(cx, layer);
+    }
+
+    cx.current = current;
+}
+
+// Collects the descendants of the entity which form stacking contexts, without descending into them.
+fn collect_layers(cx: &DrawContext, entity: Entity, visible: bool, layers: &mut Vec<Layer>) {
+    if cx.style.display.get(entity).copied().unwrap_or_default() == Display::None {
+        return;
+    }
+
+    let is_visible = match (visible, cx.style.visibility.get(entity)) {
+        (v, None) => v,
+        (_, Some(Visibility::Hidden)) => false,
+        (_, Some(Visibility::Visible)) => true,
+    };
+
+    for child in DrawChildIterator::new(cx.tree, entity) {
+        let z_index = cx.style.z_index.get(child).copied().unwrap_or_default();
+        if z_index != 0 {
+            layers.push(Layer { z_index, entity: child, visible: is_visible });
+        } else {
+            collect_layers(cx, child, is_visible, layers);
+        }
+    }
+}
+
 fn draw_entity(
     cx: &mut DrawContext,
     dirty_rect: &Option<BoundingBox>,
+    dirty_regions: &[BoundingBox],
     canvas: &Canvas,
-    current_z: i32,
-    queue: &mut BinaryHeap<ZEntity>,
+    is_stacking_context: bool,
     visible: bool,
 ) {
     let current = cx.current;
@@ -277,10 +372,8 @@ fn draw_entity(
         return;
     }
 
-    let z_index = cx.z_index();
-
-    if z_index > current_z {
-        queue.push(ZEntity { index: z_index, entity: current, visible });
+    // Views with a non-zero z-index are drawn with their own stacking context.
+    if !is_stacking_context && cx.z_index() != 0 {
         return;
     }
 
@@ -332,8 +425,20 @@ fn draw_entity(
     if is_visible {
         if let Some(dirty_rect) = dirty_rect {
             let bounds = draw_bounds(cx.style, cx.cache, cx.tree, current);
-            if bounds.intersects(dirty_rect) {
-                if let Some(view) = cx.views.remove(&current) {
+            if bounds.intersects(dirty_rect)
+                && dirty_regions.iter().any(|region| bounds.intersects(region))
+            {
+                if let Some(mut view) = cx.views.remove(&current) {
+                    let clipped_out = cx
+                        .cache
+                        .clip_path
+                        .get(current)
+                        .is_some_and(|clip_bounds| !bounds.intersects(clip_bounds));
+
+                    if !clipped_out {
+                        view.on_pre_draw(cx);
+                    }
+
                     view.draw(cx, canvas);
                     cx.views.insert(current, view);
                 }
@@ -347,7 +452,7 @@ fn draw_entity(
     for child in child_iter {
         cx.current = child;
         // TODO: Skip views with zero-sized bounding boxes here? Or let user decide if they want to skip?
-        draw_entity(cx, dirty_rect, canvas, current_z, queue, is_visible);
+        draw_entity(cx, dirty_rect, dirty_regions, canvas, false, is_visible);
     }
 
     if let Some(count) = layer_count {
@@ -364,30 +469,24 @@ pub(crate) fn draw_bounds(
     tree: &Tree<Entity>,
     entity: Entity,
 ) -> BoundingBox {
-    let mut layout_bounds = cache.bounds.get(entity).copied().unwrap();
+    let bounds = cache.bounds.get(entity).copied().unwrap();
+    let mut layout_bounds = bounds;
 
     if let Some(shadows) = style.shadow.get(entity) {
         for shadow in shadows.iter().filter(|shadow| !shadow.inset) {
-            let mut shadow_bounds = layout_bounds;
-
-            let x = shadow.x_offset.to_px().unwrap() * style.scale_factor();
-            let y = shadow.y_offset.to_px().unwrap() * style.scale_factor();
-
-            shadow_bounds = shadow_bounds.offset(x, y);
-
             let scale_factor = style.scale_factor();
 
-            if let Some(blur_radius) =
-                shadow.blur_radius.as_ref().map(|br| br.clone().to_px().unwrap() * scale_factor)
-            {
-                shadow_bounds = shadow_bounds.expand(blur_radius);
-            }
+            let x = shadow.x_offset.to_px().unwrap_or(0.0) * scale_factor;
+            let y = shadow.y_offset.to_px().unwrap_or(0.0) * scale_factor;
+            let blur_radius =
+                shadow.blur_radius.as_ref().and_then(|br| br.to_px()).unwrap_or(0.0).max(0.0)
+                    * scale_factor;
+            let spread_radius =
+                shadow.spread_radius.as_ref().and_then(|sr| sr.to_px()).unwrap_or(0.0)
+                    * scale_factor;
 
-            if let Some(spread_radius) =
-                shadow.spread_radius.as_ref().map(|sr| sr.clone().to_px().unwrap() * scale_factor)
-            {
-                shadow_bounds = shadow_bounds.expand(spread_radius * style.scale_factor());
-            }
+            // The blur, with a standard deviation of half the blur radius, fades out within three deviations.
+            let shadow_bounds = bounds.offset(x, y).expand(spread_radius + blur_radius * 1.5);
 
             layout_bounds = layout_bounds.union(&shadow_bounds);
         }
@@ -454,26 +553,75 @@ pub(crate) fn draw_bounds(
     }
 }
 
-struct ZEntity {
-    pub index: i32,
-    pub entity: Entity,
-    pub visible: bool,
-}
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
 
-impl Ord for ZEntity {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.index.cmp(&self.index)
-    }
-}
-impl PartialOrd for ZEntity {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    use super::*;
+    use crate::context::test_support::window_context;
+
+    #[test]
+    fn pre_draw() {
+        struct PreDraw {
+            name: &'static str,
+            log: Arc<Mutex<Vec<&'static str>>>,
+        }
+
+        impl View for PreDraw {
+            fn on_pre_draw(&mut self, _: &mut DrawContext) {
+                self.log.lock().unwrap().push(self.name);
+            }
+        }
+
+        let mut cx = window_context(400, 300);
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        PreDraw { name: "visible", log: log.clone() }
+            .build(cx.context(), |_| {})
+            .size(Pixels(50.0));
+        PreDraw { name: "hidden", log: log.clone() }
+            .build(cx.context(), |_| {})
+            .size(Pixels(50.0))
+            .display(false);
+        VStack::new(cx.context(), |cx| {
+            PreDraw { name: "clipped", log: log.clone() }
+                .build(cx, |_| {})
+                .size(Pixels(50.0))
+                .left(Pixels(100.0));
+        })
+        .size(Pixels(50.0))
+        .overflow(Overflow::Hidden);
+
+        cx.render_to_image(400, 300).unwrap();
+
+        // Only views which are drawn and aren't clipped out are updated before drawing.
+        assert_eq!(*log.lock().unwrap(), ["visible"]);
     }
-}
-impl PartialEq for ZEntity {
-    fn eq(&self, other: &Self) -> bool {
-        self.index == other.index
+
+    #[test]
+    fn dirty_region() {
+        let mut cx = window_context(400, 300);
+
+        let entity = Element::new(cx.context()).size(Pixels(100.0)).entity();
+
+        // Draw the window so that the drawn bounds of the view are known.
+        cx.render_to_image(400, 300).unwrap();
+
+        cx.0.with_current(entity, |cx| {
+            EventContext::new(cx)
+                .needs_redraw_region(BoundingBox::from_min_max(50.0, 50.0, 150.0, 150.0))
+        });
+
+        // Only the part of the region within the view is marked as dirty, without redrawing the whole view.
+        let window_state = &cx.0.windows[&Entity::root()];
+        assert!(window_state.redraw_list.is_empty());
+        assert_eq!(
+            window_state.dirty_rect,
+            Some(BoundingBox::from_min_max(50.0, 50.0, 100.0, 100.0))
+        );
+        assert_eq!(
+            window_state.dirty_regions,
+            [BoundingBox::from_min_max(50.0, 50.0, 100.0, 100.0)]
+        );
     }
 }
-
-impl Eq for ZEntity {}
@@ -1,5 +1,3 @@
-use std::{cmp::Ordering, collections::BinaryHeap};
-
 use crate::prelude::*;
 use log::debug;
 use skia_safe::Matrix;
@@ -9,35 +7,30 @@ use vizia_storage::{DrawChildIterator, LayoutParentIterator};
 pub fn hover_system(cx: &mut Context, window_entity: Entity) {
     cx.current = window_entity;
 
+    // The hovered view is frozen while hover is captured.
+    if cx.hover_capture.is_some() {
+        return;
+    }
+
     if let Some(pseudo_classes) = cx.style.pseudo_classes.get(window_entity) {
         if !pseudo_classes.contains(PseudoClassFlags::OVER) {
             return;
         }
     }
 
-    let mut queue = BinaryHeap::new();
     let pointer_events: bool =
         cx.style.pointer_events.get(window_entity).copied().unwrap_or_default().into();
-    queue.push(ZEntity { index: 0, pointer_events, entity: window_entity });
+    let window_layer = Layer {
+        z_index: 0,
+        entity: window_entity,
+        pointer_events,
+        visible: true,
+        transform: Matrix::new_identity(),
+    };
     let mut hovered = window_entity;
-    let transform = Matrix::new_identity();
-    // let clip_bounds = cx.cache.get_bounds(window_entity);
-    let clip_bounds: BoundingBox =
-        BoundingBox { x: -f32::MAX / 2.0, y: -f32::MAX / 2.0, w: f32::MAX, h: f32::MAX };
-    while !queue.is_empty() {
-        let zentity = queue.pop().unwrap();
-        cx.with_current(zentity.entity, |cx| {
-            hover_entity(
-                &mut EventContext::new(cx),
-                zentity.index,
-                zentity.pointer_events,
-                &mut queue,
-                &mut hovered,
-                transform,
-                &clip_bounds,
-            );
-        });
-    }
+    cx.with_current(window_entity, |cx| {
+        hover_stacking_context(&mut EventContext::new(cx), &window_layer, &mut hovered);
+    });
 
     // Set hover state for hovered view and ancestors
     let parent_iter = LayoutParentIterator::new(&cx.tree, hovered);
@@ -78,19 +71,77 @@ pub fn hover_system(cx: &mut Context, window_entity: Entity) {
         cx.event_queue.push_back(Event::new(WindowEvent::MouseOver).target(hovered));
         cx.event_queue.push_back(Event::new(WindowEvent::MouseOut).target(cx.hovered));
 
-        cx.style.needs_restyle(cx.hovered);
-        cx.style.needs_restyle(hovered);
+        let changed = PseudoClassFlags::OVER | PseudoClassFlags::HOVER;
+        cx.style.invalidate_pseudo_classes(&cx.tree, cx.hovered, changed);
+        cx.style.invalidate_pseudo_classes(&cx.tree, hovered, changed);
+
+        // Redraw to move the highlight of the inspector to the hovered view.
+        if cx.inspector.is_some() {
+            cx.needs_redraw(hovered);
+        }
 
         cx.hovered = hovered;
     }
 }
 
+// A view with a non-zero z-index, which is hit-tested separately from the other views of its stacking context.
+struct Layer {
+    z_index: i32,
+    entity: Entity,
+    // Whether the parent of the view accepts pointer events.
+    pointer_events: bool,
+    // Whether the parent of the view is visible.
+    visible: bool,
+    // The transform of the parent of the view.
+    transform: Matrix,
+}
+
+// Hit-tests a view which forms a stacking context, along with its descendants, in the same order they are drawn so
+// that the topmost view under the cursor is hovered.
+fn hover_stacking_context(cx: &mut EventContext, layer: &Layer, hovered: &mut Entity) {
+    // Layers escape the clipping of their ancestors, as when drawn.
+    let clip_bounds: BoundingBox =
+        BoundingBox { x: -f32::MAX / 2.0, y: -f32::MAX / 2.0, w: f32::MAX, h: f32::MAX };
+
+    let mut layers = Vec::new();
+    let mut hit = None;
+    cx.current = layer.entity;
+    hover_entity(
+        cx,
+        true,
+        layer.pointer_events,
+        layer.visible,
+        &mut layers,
+        &mut hit,
+        layer.transform,
+        &clip_bounds,
+    );
+
+    // The sort is stable so layers with the same z-index stay in tree order.
+    layers.sort_by_key(|layer| layer.z_index);
+    let split = layers.partition_point(|layer| layer.z_index < 0);
+
+    // Views drawn later are on top, so each hit replaces the hits below it.
+    for layer in layers[..split].iter() {
+        hover_stacking_context(cx, layer, hovered);
+    }
+
+    if let Some(hit) = hit {
+        *hovered = hit;
+    }
+
+    for layer in layers[split..].iter() {
+        hover_stacking_context(cx, layer, hovered);
+    }
+}
+
 fn hover_entity(
     cx: &mut EventContext,
-    current_z: i32,
+    is_stacking_context: bool,
     parent_pointer_events: bool,
-    queue: &mut BinaryHeap<ZEntity>,
-    hovered: &mut Entity,
+    parent_visible: bool,
+    layers: &mut Vec<Layer>,
+    hovered: &mut Option<Entity>,
     parent_transform: Matrix,
     clip_bounds: &BoundingBox,
 ) {
@@ -125,10 +176,24 @@ fn hover_entity(
         })
         .unwrap_or(parent_pointer_events);
 
-    // Push to queue if the z-index is higher than the current z-index.
+    // Hidden views are passed through like views which don't accept pointer events, but their descendants can still be
+    // hovered if they're made visible.
+    let visible = match cx.style.visibility.get(cx.current) {
+        Some(Visibility::Hidden) => false,
+        Some(Visibility::Visible) => true,
+        None => parent_visible,
+    };
+
+    // Views with a non-zero z-index are hit-tested with their own stacking context.
     let z_index = cx.style.z_index.get(cx.current).copied().unwrap_or_default();
-    if z_index > current_z {
-        queue.push(ZEntity { index: z_index, entity: cx.current, pointer_events });
+    if !is_stacking_context && z_index != 0 {
+        layers.push(Layer {
+            z_index,
+            entity: cx.current,
+            pointer_events: parent_pointer_events,
+            visible: parent_visible,
+            transform: parent_transform,
+        });
         return;
     }
 
@@ -158,9 +223,9 @@ fn hover_entity(
         pseudo_classes.set(PseudoClassFlags::HOVER, false);
     }
 
-    if pointer_events {
+    if pointer_events && visible {
         if tx >= b.left() && tx < b.right() && ty >= b.top() && ty < b.bottom() {
-            *hovered = cx.current;
+            *hovered = Some(cx.current);
 
             if !cx
                 .style
@@ -173,7 +238,11 @@ fn hover_entity(
                 if let Some(pseudo_class) = cx.style.pseudo_classes.get_mut(cx.current) {
                     pseudo_class.set(PseudoClassFlags::OVER, true);
 
-                    cx.needs_restyle();
+                    cx.style.invalidate_pseudo_classes(
+                        cx.tree,
+                        cx.current,
+                        PseudoClassFlags::OVER | PseudoClassFlags::HOVER,
+                    );
                 }
             }
         } else if cx
@@ -187,7 +256,11 @@ fn hover_entity(
             if let Some(pseudo_class) = cx.style.pseudo_classes.get_mut(cx.current) {
                 pseudo_class.set(PseudoClassFlags::OVER, false);
 
-                cx.needs_restyle();
+                cx.style.invalidate_pseudo_classes(
+                    cx.tree,
+                    cx.current,
+                    PseudoClassFlags::OVER | PseudoClassFlags::HOVER,
+                );
             }
         }
     }
@@ -195,30 +268,97 @@ fn hover_entity(
     let child_iter = DrawChildIterator::new(cx.tree, cx.current);
     for child in child_iter {
         cx.current = child;
-        hover_entity(cx, current_z, pointer_events, queue, hovered, transform, &clipping);
+        hover_entity(cx, false, pointer_events, visible, layers, hovered, transform, &clipping);
     }
 }
 
-struct ZEntity {
-    pub index: i32,
-    pub pointer_events: bool,
-    pub entity: Entity,
-}
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
 
-impl Ord for ZEntity {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.index.cmp(&self.index)
-    }
-}
-impl PartialOrd for ZEntity {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    use crate::context::backend::BackendContext;
+    use crate::context::test_support::{update, window_context};
+    use crate::prelude::*;
+
+    #[test]
+    fn hover_capture() {
+        let mut cx = window_context(400, 300);
+        cx.context().add_stylesheet(".item:hover { background-color: red; }").unwrap();
+
+        let mut items = Vec::new();
+        HStack::new(cx.context(), |cx| {
+            items.push(Element::new(cx).class("item").size(Pixels(100.0)).entity());
+            items.push(Element::new(cx).class("item").size(Pixels(100.0)).entity());
+        });
+        let (first, second) = (items[0], items[1]);
+        update(&mut cx);
+
+        let is_red = |cx: &BackendContext, entity: Entity| {
+            cx.0.style.background_color.get(entity).copied() == Some(Color::red())
+        };
+
+        cx.send_event(Event::new(WindowEvent::MouseMove(50.0, 50.0)).origin(Entity::root()));
+        update(&mut cx);
+        assert!(is_red(&cx, first));
+
+        // The hover is frozen while it's captured, even as the cursor crosses other views.
+        cx.0.with_current(first, |cx| cx.capture_hover());
+        cx.send_event(Event::new(WindowEvent::MouseMove(150.0, 50.0)).origin(Entity::root()));
+        update(&mut cx);
+        assert_eq!(cx.0.hovered, first);
+        assert!(is_red(&cx, first) && !is_red(&cx, second));
+
+        // Releasing the hover restyles both the previously captured view and the view under the cursor.
+        cx.0.with_current(first, |cx| cx.release_hover());
+        update(&mut cx);
+        assert_eq!(cx.0.hovered, second);
+        assert!(!is_red(&cx, first) && is_red(&cx, second));
+
+        // Capturing the hover of another view moves the hover state to it immediately.
+        cx.0.with_current(first, |cx| cx.capture_hover());
+        update(&mut cx);
+        assert_eq!(cx.0.hovered, first);
+        assert!(is_red(&cx, first) && !is_red(&cx, second));
     }
-}
-impl PartialEq for ZEntity {
-    fn eq(&self, other: &Self) -> bool {
-        self.index == other.index
+
+    #[test]
+    fn pointer_events_pass_through() {
+        let mut cx = window_context(400, 300);
+
+        let presses = Arc::new(Mutex::new(0));
+        let on_press = presses.clone();
+        let mut views = Vec::new();
+        ZStack::new(cx.context(), |cx| {
+            views.push(
+                Element::new(cx)
+                    .size(Pixels(100.0))
+                    .on_press(move |_| *on_press.lock().unwrap() += 1)
+                    .entity(),
+            );
+            views.push(
+                Element::new(cx).size(Pixels(100.0)).pointer_events(PointerEvents::None).entity(),
+            );
+        });
+        let (button, overlay) = (views[0], views[1]);
+        update(&mut cx);
+
+        // The overlay is drawn on top of the button but clicks pass through it.
+        cx.send_event(Event::new(WindowEvent::MouseMove(50.0, 50.0)).origin(Entity::root()));
+        cx.send_event(Event::new(WindowEvent::MouseDown(MouseButton::Left)).origin(Entity::root()));
+        cx.send_event(Event::new(WindowEvent::MouseUp(MouseButton::Left)).origin(Entity::root()));
+        update(&mut cx);
+        assert_eq!(cx.0.hovered, button);
+        assert_eq!(*presses.lock().unwrap(), 1);
+
+        // Changing the hit-testing of a view rehovers without the cursor moving.
+        cx.0.with_current(overlay, |cx| {
+            EventContext::new(cx).set_pointer_events(PointerEvents::Auto)
+        });
+        update(&mut cx);
+        assert_eq!(cx.0.hovered, overlay);
+
+        cx.0.with_current(overlay, |cx| EventContext::new(cx).set_hoverable(false));
+        update(&mut cx);
+        assert_eq!(cx.0.hovered, button);
     }
 }
-
-impl Eq for ZEntity {}
@@ -37,6 +37,7 @@ fn load_image(cx: &mut ResourceContext, entity: Entity, image_name: &str) {
             (callback)(cx, image_name);
 
             cx.resource_manager.image_loader = Some(callback);
+            cx.resource_manager.loader_images.insert(image_name.to_owned());
 
             // Then try to load the image again
             try_load_image(cx, entity, image_name);
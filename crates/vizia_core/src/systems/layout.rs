@@ -6,6 +6,9 @@ use crate::prelude::*;
 
 use super::{text_layout_system, text_system};
 
+// The maximum number of times the tree is laid out in one frame while views with wrapped children grow to fit them.
+const MAX_LAYOUT_PASSES: usize = 4;
+
 /// Determines the size and position of views.
 /// TODO: Currently relayout is done on an entire tree rather than incrementally.
 /// Incremental relayout can be done by keeping a list of nodes that need relayout,
@@ -14,20 +17,34 @@ use super::{text_layout_system, text_system};
 pub(crate) fn layout_system(cx: &mut Context) {
     text_system(cx);
 
-    if cx.style.system_flags.contains(SystemFlags::RELAYOUT) {
-        // Perform layout on the whole tree.
-        Entity::root().layout(
-            &mut cx.cache,
-            &cx.tree,
-            &cx.style,
-            &mut SubLayout {
-                text_context: &mut cx.text_context,
-                resource_manager: &cx.resource_manager,
-            },
-        );
+    // Throttle relayout while a window is being resized, with a final relayout once resizing has ended. Between the
+    // throttled relayouts the size of the text from the previous layout is reused, which is cheap enough to keep
+    // following the size of the window.
+    let mut throttled = false;
+    if let (Some(interval), Some(last_resize)) = (cx.resize_debounce, cx.last_resize) {
+        let now = Instant::now();
+        if now.duration_since(last_resize) >= interval {
+            cx.last_resize = None;
+            cx.style.needs_relayout();
+        } else if now.duration_since(cx.last_resize_layout) < interval {
+            throttled = true;
+        } else {
+            cx.last_resize_layout = now;
+        }
+    }
 
+    if cx.style.system_flags.contains(SystemFlags::RELAYOUT) {
         let cx = &mut EventContext::new(cx);
 
+        // Perform layout on the whole tree, and again while views with wrapped children grow to fit their lines.
+        for _ in 0..MAX_LAYOUT_PASSES {
+            layout_windows(cx, throttled);
+
+            if !measure_wrapped_lines(cx) {
+                break;
+            }
+        }
+
         let iter = LayoutTreeIterator::full(cx.tree);
 
         for entity in iter {
@@ -40,7 +57,15 @@ pub(crate) fn layout_system(cx: &mut Context) {
                 let parent_bounds = cx.cache.get_bounds(parent);
                 if let Some(bounds) = cx.cache.bounds.get_mut(entity) {
                     if let Some(relative_bounds) = cx.cache.relative_bounds.get(entity) {
-                        let x = relative_bounds.x + parent_bounds.x;
+                        // The children of a right-to-left view are mirrored horizontally, which reverses the order of
+                        // rows and swaps the left and right spacing of the children.
+                        let x = if cx.style.direction.get(parent) == Some(&Direction::Rtl) {
+                            parent_bounds.x + parent_bounds.w
+                                - relative_bounds.x
+                                - relative_bounds.w
+                        } else {
+                            relative_bounds.x + parent_bounds.x
+                        };
                         let y = relative_bounds.y + parent_bounds.y;
                         let w = relative_bounds.w;
                         let h = relative_bounds.h;
@@ -80,6 +105,18 @@ pub(crate) fn layout_system(cx: &mut Context) {
                 }
             }
 
+            // Adjust the relative positions of the children before they are converted to absolute positions.
+            if !apply_flex_wrap(cx, entity) {
+                let tree = cx.tree;
+                for child in entity.children(tree) {
+                    if child.visible(cx.style) {
+                        apply_self_alignment(cx, child, entity);
+                    }
+                }
+
+                apply_baseline_alignment(cx, entity);
+            }
+
             if let Some(geo) = cx.cache.geo_changed.get(entity).copied() {
                 if !geo.is_empty()
                 // && cx.style.text.get(entity).is_some()
@@ -118,10 +155,420 @@ pub(crate) fn layout_system(cx: &mut Context) {
         cx.style.system_flags.set(SystemFlags::RELAYOUT, false);
     }
 
-    text_layout_system(cx);
+    // Resize any windows which should fit their content now that it has been measured.
+    let unfitted = cx
+        .windows
+        .iter()
+        .filter(|(_, state)| state.window_description.fit_content && !state.content_fitted)
+        .map(|(window, _)| *window)
+        .collect::<Vec<_>>();
+
+    for window in unfitted {
+        cx.measure_and_fit(window);
+    }
+
+    // Text is laid out again for its new bounds by the next relayout which isn't throttled.
+    if !throttled {
+        text_layout_system(cx);
+    }
+}
+
+/// Returns the left, top, right, and bottom edges of the content box of a view, relative to the view itself.
+fn content_box(cx: &EventContext, entity: Entity) -> (f32, f32, f32, f32) {
+    let width = cx.cache.get_width(entity);
+    let height = cx.cache.get_height(entity);
+
+    let resolve = |units: Option<Units>, parent_size: f32| {
+        units.map(|units| units.to_px(parent_size, 0.0)).unwrap_or_default()
+    };
+
+    let left = resolve(entity.padding_left(cx.style), width)
+        + resolve(entity.border_left(cx.style), width);
+    let right = width
+        - resolve(entity.padding_right(cx.style), width)
+        - resolve(entity.border_right(cx.style), width);
+    let top = resolve(entity.padding_top(cx.style), height)
+        + resolve(entity.border_top(cx.style), height);
+    let bottom = height
+        - resolve(entity.padding_bottom(cx.style), height)
+        - resolve(entity.border_bottom(cx.style), height);
+
+    (left, top, right, bottom)
+}
+
+/// Repositions a view within the content box of its parent according to its `align-self` and `justify-self` properties.
+///
+/// Morphorm positions every child using the alignment of the parent, so per-child overrides are applied to the
+/// relative bounds after layout. Only the position of the view changes, so the layout of its descendants is unaffected.
+///
+/// `justify-self` only applies to views with an absolute position type, because relative children are stacked one
+/// after another along the main axis of the parent. For relative children only `align-self` is used.
+fn apply_self_alignment(cx: &mut EventContext, entity: Entity, parent: Entity) {
+    let align_self = cx.style.align_self.get(entity).copied().unwrap_or_default();
+    let justify_self = cx.style.justify_self.get(entity).copied().unwrap_or_default();
+
+    if align_self == SelfAlignment::Auto && justify_self == SelfAlignment::Auto {
+        return;
+    }
+
+    let layout_type = parent.layout_type(cx.style).unwrap_or_default();
+
+    if layout_type == LayoutType::Grid {
+        return;
+    }
+
+    let position_type = entity.position_type(cx.style).unwrap_or_default();
+
+    // Relative children are stacked along the main axis so only the cross axis can be overridden.
+    let justify_self =
+        if position_type == PositionType::Absolute { justify_self } else { SelfAlignment::Auto };
+
+    let (content_left, content_top, content_right, content_bottom) = content_box(cx, parent);
+
+    let align =
+        |alignment: SelfAlignment, start: f32, end: f32, size: f32, current: f32| match alignment {
+            SelfAlignment::Auto | SelfAlignment::Baseline => current,
+            SelfAlignment::Start => start,
+            SelfAlignment::Center => (start + (end - start - size) / 2.0).round(),
+            SelfAlignment::End => end - size,
+        };
+
+    let (horizontal, vertical) = match layout_type {
+        LayoutType::Row => (justify_self, align_self),
+        _ => (align_self, justify_self),
+    };
+
+    if let Some(relative_bounds) = cx.cache.relative_bounds.get_mut(entity) {
+        relative_bounds.x =
+            align(horizontal, content_left, content_right, relative_bounds.w, relative_bounds.x);
+        relative_bounds.y =
+            align(vertical, content_top, content_bottom, relative_bounds.h, relative_bounds.y);
+    }
+}
+
+/// Returns the distance from the top of a view to the baseline of the first line of its text, in physical pixels.
+pub(crate) fn baseline_offset(cx: &EventContext, entity: Entity) -> Option<f32> {
+    let paragraph = cx.text_context.text_paragraphs.get(entity)?;
+
+    let height = cx.cache.get_height(entity);
+    let resolve =
+        |units: Option<Units>| units.map(|units| units.to_px(height, 0.0)).unwrap_or_default();
+
+    let padding_top = resolve(entity.padding_top(cx.style));
+    let padding_bottom = resolve(entity.padding_bottom(cx.style));
+
+    // Text is positioned vertically within the padding box by the alignment of the view.
+    let vertical = match cx.style.alignment.get(entity).copied().unwrap_or_default() {
+        Alignment::TopLeft | Alignment::TopCenter | Alignment::TopRight => 0.0,
+        Alignment::Left | Alignment::Center | Alignment::Right => 0.5,
+        Alignment::BottomLeft | Alignment::BottomCenter | Alignment::BottomRight => 1.0,
+    };
+
+    let top = (height - padding_top - padding_bottom - paragraph.height()) * vertical;
+
+    Some(padding_top + top + paragraph.alphabetic_baseline())
+}
+
+/// Aligns the relative children of a `Row` with `align-self: baseline` so that the baselines of their text line up.
+///
+/// The children are moved down so that their baselines match the lowest baseline of the group, which is first
+/// placed at the top of the content box. Children without text are left in place.
+fn apply_baseline_alignment(cx: &mut EventContext, entity: Entity) {
+    if entity.layout_type(cx.style).unwrap_or_default() != LayoutType::Row {
+        return;
+    }
+
+    let baselines = entity
+        .children(cx.tree)
+        .filter(|child| {
+            child.visible(cx.style)
+                && child.position_type(cx.style).unwrap_or_default() == PositionType::Relative
+                && cx.style.align_self.get(*child).copied() == Some(SelfAlignment::Baseline)
+        })
+        .filter_map(|child| baseline_offset(cx, child).map(|baseline| (child, baseline)))
+        .collect::<Vec<_>>();
+
+    if baselines.is_empty() {
+        return;
+    }
+
+    let (_, content_top, _, _) = content_box(cx, entity);
+    let max_baseline = baselines.iter().map(|(_, baseline)| *baseline).fold(0.0f32, f32::max);
+
+    for (child, baseline) in baselines {
+        if let Some(relative_bounds) = cx.cache.relative_bounds.get_mut(child) {
+            relative_bounds.y = (content_top + max_baseline - baseline).round();
+        }
+    }
+}
+
+/// A line of the relative children of a view with `flex-wrap: wrap`.
+struct WrapLine {
+    /// The children in the line, with their sizes along the main and cross axes.
+    children: Vec<(Entity, f32, f32)>,
+    /// The size of the line along the main axis, including the gaps between the children.
+    main_size: f32,
+    /// The size of the largest child in the line along the cross axis.
+    cross_size: f32,
+}
+
+/// The content box and gaps of a view with `flex-wrap: wrap`, mapped onto its main and cross axes so that rows and
+/// columns can share the same logic.
+struct WrapAxes {
+    main_start: f32,
+    main_end: f32,
+    cross_start: f32,
+    cross_end: f32,
+    main_gap: f32,
+    cross_gap: f32,
+}
+
+impl WrapAxes {
+    fn new(cx: &EventContext, entity: Entity, layout_type: LayoutType) -> Self {
+        let (content_left, content_top, content_right, content_bottom) = content_box(cx, entity);
+
+        let width = cx.cache.get_width(entity);
+        let height = cx.cache.get_height(entity);
+
+        let horizontal_gap =
+            entity.horizontal_gap(cx.style).map(|gap| gap.to_px(width, 0.0)).unwrap_or_default();
+        let vertical_gap =
+            entity.vertical_gap(cx.style).map(|gap| gap.to_px(height, 0.0)).unwrap_or_default();
+
+        match layout_type {
+            LayoutType::Row => Self {
+                main_start: content_left,
+                main_end: content_right,
+                cross_start: content_top,
+                cross_end: content_bottom,
+                main_gap: horizontal_gap,
+                cross_gap: vertical_gap,
+            },
+            _ => Self {
+                main_start: content_top,
+                main_end: content_bottom,
+                cross_start: content_left,
+                cross_end: content_right,
+                main_gap: vertical_gap,
+                cross_gap: horizontal_gap,
+            },
+        }
+    }
+}
+
+/// Splits the relative children of a `Row` or `Column` with `flex-wrap: wrap` into lines, using the sizes computed by
+/// morphorm. Returns `None` if the view doesn't wrap or if its children fit on a single line.
+fn wrap_lines(cx: &EventContext, entity: Entity) -> Option<(LayoutType, WrapAxes, Vec<WrapLine>)> {
+    if cx.style.flex_wrap.get(entity).copied().unwrap_or_default() != FlexWrap::Wrap {
+        return None;
+    }
+
+    let layout_type = entity.layout_type(cx.style).unwrap_or_default();
+
+    if layout_type == LayoutType::Grid {
+        return None;
+    }
+
+    let axes = WrapAxes::new(cx, entity, layout_type);
+
+    let children = entity
+        .children(cx.tree)
+        .filter(|child| {
+            child.visible(cx.style)
+                && child.position_type(cx.style).unwrap_or_default() == PositionType::Relative
+        })
+        .map(|child| {
+            let bounds = cx.cache.relative_bounds.get(child).copied().unwrap_or_default();
+            match layout_type {
+                LayoutType::Row => (child, bounds.w, bounds.h),
+                _ => (child, bounds.h, bounds.w),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let total_main = children.iter().map(|(_, main, _)| main).sum::<f32>()
+        + axes.main_gap * children.len().saturating_sub(1) as f32;
+
+    if total_main <= axes.main_end - axes.main_start {
+        return None;
+    }
+
+    let mut lines = Vec::new();
+    let mut line = WrapLine { children: Vec::new(), main_size: 0.0, cross_size: 0.0 };
+
+    for (child, main_size, cross_size) in children {
+        if !line.children.is_empty() {
+            if axes.main_start + line.main_size + axes.main_gap + main_size > axes.main_end {
+                lines.push(std::mem::replace(
+                    &mut line,
+                    WrapLine { children: Vec::new(), main_size: 0.0, cross_size: 0.0 },
+                ));
+            } else {
+                line.main_size += axes.main_gap;
+            }
+        }
+
+        line.children.push((child, main_size, cross_size));
+        line.main_size += main_size;
+        line.cross_size = line.cross_size.max(cross_size);
+    }
+
+    lines.push(line);
+
+    Some((layout_type, axes, lines))
+}
+
+/// Returns the total size of a set of wrapped lines along the cross axis, including the gaps between them.
+fn lines_cross_size(axes: &WrapAxes, lines: &[WrapLine]) -> f32 {
+    lines.iter().map(|line| line.cross_size).sum::<f32>()
+        + axes.cross_gap * lines.len().saturating_sub(1) as f32
+}
+
+// Lays out the whole tree with the scale factor of the main window, and then the subtree of each other window again
+// with the scale factor of that window, with nested windows after the windows containing them.
+fn layout_windows(cx: &mut EventContext, throttled: bool) {
+    let mut windows =
+        cx.windows.keys().copied().filter(|window| *window != Entity::root()).collect::<Vec<_>>();
+    windows.sort_by_cached_key(|window| window.parent_iter(cx.tree).count());
+
+    for window in std::iter::once(Entity::root()).chain(windows) {
+        cx.style.use_window_scale_factor(window);
+        window.layout(
+            &mut *cx.cache,
+            cx.tree,
+            &*cx.style,
+            &mut SubLayout {
+                text_context: &mut *cx.text_context,
+                resource_manager: &*cx.resource_manager,
+                reuse_text: throttled,
+            },
+        );
+    }
+
+    cx.style.use_window_scale_factor(Entity::root());
+}
+
+/// Records the cross-axis size needed by the wrapped lines of views with `flex-wrap: wrap` and an `Auto` cross-axis
+/// size, which morphorm can't measure because it places the children of a row or column on a single line.
+///
+/// The recorded size is used as the size of the view by the next layout pass. Returns true if any of the sizes
+/// changed, in which case the tree needs to be laid out again.
+fn measure_wrapped_lines(cx: &mut EventContext) -> bool {
+    let mut changed = false;
+
+    for entity in LayoutTreeIterator::full(cx.tree) {
+        let layout_type = entity.layout_type(cx.style).unwrap_or_default();
+
+        let cross_size = match layout_type {
+            LayoutType::Row => cx.style.height.get(entity),
+            _ => cx.style.width.get(entity),
+        };
+
+        let size = if cross_size == Some(&Units::Auto) {
+            wrap_lines(cx, entity).map(|(_, axes, lines)| {
+                let insets = match layout_type {
+                    LayoutType::Row => cx.cache.get_height(entity) - axes.cross_end,
+                    _ => cx.cache.get_width(entity) - axes.cross_end,
+                };
+
+                (axes.cross_start + lines_cross_size(&axes, &lines) + insets).ceil()
+            })
+        } else {
+            None
+        };
+
+        if cx.style.wrapped_size.get(entity).copied() != size {
+            match size {
+                Some(size) => cx.style.wrapped_size.insert(entity, size),
+                None => {
+                    cx.style.wrapped_size.remove(entity);
+                }
+            }
+
+            changed = true;
+        }
+    }
+
+    changed
+}
+
+/// Moves the relative children of a `Row` or `Column` with `flex-wrap: wrap` onto multiple lines, returning true if the
+/// children were wrapped.
+///
+/// Children keep the positions computed by morphorm if they fit within the main axis of the view. Otherwise they are
+/// placed one after another, separated by the main-axis gap, and a new line is started whenever a child would overflow.
+/// Lines are separated by the cross-axis gap and take the size of their largest child.
+///
+/// The `alignment` of the view positions each line along the main axis and the block of lines along the cross axis,
+/// as well as the children within a line, unless overridden with `align-self`.
+fn apply_flex_wrap(cx: &mut EventContext, entity: Entity) -> bool {
+    let Some((layout_type, axes, lines)) = wrap_lines(cx, entity) else {
+        return false;
+    };
+
+    let alignment = cx.style.alignment.get(entity).copied().unwrap_or_default();
+
+    let horizontal = match alignment {
+        Alignment::TopLeft | Alignment::Left | Alignment::BottomLeft => 0.0,
+        Alignment::TopCenter | Alignment::Center | Alignment::BottomCenter => 0.5,
+        Alignment::TopRight | Alignment::Right | Alignment::BottomRight => 1.0,
+    };
+
+    let vertical = match alignment {
+        Alignment::TopLeft | Alignment::TopCenter | Alignment::TopRight => 0.0,
+        Alignment::Left | Alignment::Center | Alignment::Right => 0.5,
+        Alignment::BottomLeft | Alignment::BottomCenter | Alignment::BottomRight => 1.0,
+    };
+
+    let (main_align, cross_align) = match layout_type {
+        LayoutType::Row => (horizontal, vertical),
+        _ => (vertical, horizontal),
+    };
+
+    // The lines overflow from the start of the cross axis if they don't fit.
+    let free_cross = (axes.cross_end - axes.cross_start - lines_cross_size(&axes, &lines)).max(0.0);
+    let mut cross_pos = axes.cross_start + (free_cross * cross_align).round();
+
+    for line in lines {
+        let free_main = (axes.main_end - axes.main_start - line.main_size).max(0.0);
+        let mut main_pos = axes.main_start + (free_main * main_align).round();
+
+        for (child, main_size, cross_size) in line.children {
+            let align = match cx.style.align_self.get(child).copied().unwrap_or_default() {
+                SelfAlignment::Start => 0.0,
+                SelfAlignment::Center => 0.5,
+                SelfAlignment::End => 1.0,
+                SelfAlignment::Auto | SelfAlignment::Baseline => cross_align,
+            };
+
+            let child_cross_pos = cross_pos + ((line.cross_size - cross_size) * align).round();
+
+            if let Some(relative_bounds) = cx.cache.relative_bounds.get_mut(child) {
+                match layout_type {
+                    LayoutType::Row => {
+                        relative_bounds.x = main_pos;
+                        relative_bounds.y = child_cross_pos;
+                    }
+                    _ => {
+                        relative_bounds.x = child_cross_pos;
+                        relative_bounds.y = main_pos;
+                    }
+                }
+            }
+
+            main_pos += main_size + axes.main_gap;
+        }
+
+        cross_pos += line.cross_size + axes.cross_gap;
+    }
+
+    true
 }
 
 fn visit_entity(cx: &mut EventContext, entity: Entity, event: &mut Event) {
+    // The models and view of the entity may change their data in response to the event.
+    crate::context::data_changed();
+
     // Send event to models attached to the entity
     if let Some(ids) =
         cx.models.get(&entity).map(|models| models.keys().cloned().collect::<Vec<_>>())
@@ -152,3 +599,248 @@ fn visit_entity(cx: &mut EventContext, entity: Entity, event: &mut Event) {
         cx.views.insert(entity, view);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use vizia_window::WindowDescription;
+
+    use super::*;
+    use crate::context::test_support::{update, window_context};
+
+    #[test]
+    fn align_self() {
+        let mut cx = window_context(200, 100);
+
+        let mut children = Vec::new();
+        HStack::new(cx.context(), |cx| {
+            for alignment in [SelfAlignment::Auto, SelfAlignment::Start, SelfAlignment::End] {
+                children.push(Element::new(cx).size(Pixels(20.0)).align_self(alignment).entity());
+            }
+        })
+        .size(Pixels(100.0))
+        .alignment(Alignment::Center);
+
+        update(&mut cx);
+
+        let bounds = children.iter().map(|child| cx.0.cache.get_bounds(*child)).collect::<Vec<_>>();
+
+        // The children are still stacked along the main axis.
+        assert_eq!(bounds.iter().map(|b| b.x).collect::<Vec<_>>(), [20.0, 40.0, 60.0]);
+        assert_eq!(bounds.iter().map(|b| b.y).collect::<Vec<_>>(), [40.0, 0.0, 80.0]);
+    }
+
+    #[test]
+    fn justify_self_only_applies_to_absolute_children() {
+        let mut cx = window_context(200, 100);
+
+        let mut relative = Entity::null();
+        let mut absolute = Entity::null();
+        HStack::new(cx.context(), |cx| {
+            relative =
+                Element::new(cx).size(Pixels(20.0)).justify_self(SelfAlignment::End).entity();
+            absolute = Element::new(cx)
+                .size(Pixels(20.0))
+                .position_type(PositionType::Absolute)
+                .justify_self(SelfAlignment::End)
+                .entity();
+        })
+        .size(Pixels(100.0));
+
+        update(&mut cx);
+
+        assert_eq!(cx.0.cache.get_bounds(relative).x, 0.0);
+        assert_eq!(cx.0.cache.get_bounds(absolute).x, 80.0);
+    }
+
+    #[test]
+    fn flex_wrap() {
+        let mut cx = window_context(400, 400);
+
+        let mut children = Vec::new();
+        let stack = HStack::new(cx.context(), |cx| {
+            for _ in 0..5 {
+                children.push(Element::new(cx).size(Pixels(40.0)).entity());
+            }
+        })
+        .width(Pixels(100.0))
+        .height(Units::Auto)
+        .flex_wrap(FlexWrap::Wrap)
+        .gap(Pixels(10.0))
+        .entity();
+        let sibling = Element::new(cx.context()).size(Pixels(10.0)).entity();
+
+        update(&mut cx);
+
+        let positions = children
+            .iter()
+            .map(|child| {
+                let bounds = cx.0.cache.get_bounds(*child);
+                (bounds.x, bounds.y)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(positions, [(0.0, 0.0), (50.0, 0.0), (0.0, 50.0), (50.0, 50.0), (0.0, 100.0)]);
+
+        // The stack grows to fit the three lines, which moves the views after it.
+        assert_eq!(cx.0.cache.get_height(stack), 140.0);
+        assert_eq!(cx.0.cache.get_bounds(sibling).y, 140.0);
+    }
+
+    #[test]
+    fn flex_wrap_alignment() {
+        let mut cx = window_context(400, 400);
+
+        let mut children = Vec::new();
+        HStack::new(cx.context(), |cx| {
+            children.push(Element::new(cx).size(Pixels(40.0)).entity());
+            children.push(
+                Element::new(cx)
+                    .width(Pixels(40.0))
+                    .height(Pixels(20.0))
+                    .align_self(SelfAlignment::End)
+                    .entity(),
+            );
+            children.push(Element::new(cx).size(Pixels(40.0)).entity());
+            children.push(Element::new(cx).width(Pixels(40.0)).height(Pixels(20.0)).entity());
+        })
+        .width(Pixels(100.0))
+        .height(Pixels(200.0))
+        .alignment(Alignment::Center)
+        .flex_wrap(FlexWrap::Wrap)
+        .gap(Pixels(10.0));
+
+        update(&mut cx);
+
+        let positions = children
+            .iter()
+            .map(|child| {
+                let bounds = cx.0.cache.get_bounds(*child);
+                (bounds.x, bounds.y)
+            })
+            .collect::<Vec<_>>();
+
+        // The two lines are centered in the stack, and the shorter children are aligned within their lines.
+        assert_eq!(positions, [(5.0, 55.0), (55.0, 75.0), (5.0, 105.0), (55.0, 115.0)]);
+    }
+
+    #[test]
+    fn baseline_alignment() {
+        let mut cx = window_context(400, 200);
+
+        let mut labels = Vec::new();
+        let mut element = Entity::null();
+        let stack = HStack::new(cx.context(), |cx| {
+            for font_size in [12.0, 32.0] {
+                labels.push(
+                    Label::new(cx, "Baseline")
+                        .font_size(font_size)
+                        .align_self(SelfAlignment::Baseline)
+                        .entity(),
+                );
+            }
+            element =
+                Element::new(cx).size(Pixels(20.0)).align_self(SelfAlignment::Baseline).entity();
+        })
+        .height(Pixels(100.0))
+        .padding_top(Pixels(10.0))
+        .entity();
+
+        update(&mut cx);
+
+        let baselines = labels
+            .iter()
+            .map(|label| cx.0.cache.get_posy(*label) + cx.0.measure_baseline(*label).unwrap())
+            .collect::<Vec<_>>();
+
+        // The baselines of the labels line up, with the lowest of them at its position from the top of the content box.
+        assert!((baselines[0] - baselines[1]).abs() <= 1.0, "{baselines:?}");
+        assert_eq!(cx.0.cache.get_posy(labels[1]), cx.0.cache.get_posy(stack) + 10.0);
+
+        // Views without text are left in place.
+        assert_eq!(cx.0.measure_baseline(element), None);
+        assert_eq!(cx.0.cache.get_posy(element), cx.0.cache.get_posy(stack) + 10.0);
+    }
+
+    #[test]
+    fn resize_debounce() {
+        let mut cx = window_context(400, 300);
+
+        let interval = Duration::from_secs(10);
+        cx.0.set_resize_debounce(Some(interval));
+
+        cx.context().add_stylesheet(".large { font-size: 40px; }").unwrap();
+
+        let element = Element::new(cx.context()).width(Stretch(1.0)).height(Pixels(10.0)).entity();
+        let label = Label::new(cx.context(), "Hello").entity();
+
+        update(&mut cx);
+
+        // Send a burst of resizes, counting the relayouts which aren't throttled.
+        let mut full_relayouts = 0;
+        for width in 401..=450 {
+            cx.set_window_size(Entity::root(), width as f32, 300.0);
+            cx.needs_refresh(Entity::root());
+
+            let last_resize_layout = cx.0.last_resize_layout;
+            update(&mut cx);
+            if cx.0.last_resize_layout != last_resize_layout {
+                full_relayouts += 1;
+            }
+
+            // The throttled relayouts still follow the size of the window.
+            assert_eq!(cx.0.cache.get_width(element), width as f32);
+        }
+
+        assert!(full_relayouts <= 1);
+        assert!(cx.0.last_resize.is_some());
+
+        // Text whose font size changes is measured again, even while the relayout is throttled.
+        let width = cx.0.cache.get_width(label);
+        EventContext::new_with_current(&mut cx.0, label).toggle_class("large", true);
+        update(&mut cx);
+        assert!(cx.0.last_resize.is_some());
+        assert!(cx.0.cache.get_width(label) > width);
+
+        // A final relayout is performed once the resize has ended.
+        cx.0.last_resize = Some(Instant::now() - interval);
+        update(&mut cx);
+        assert!(cx.0.last_resize.is_none());
+        assert_eq!(cx.0.cache.get_width(element), 450.0);
+    }
+
+    #[test]
+    fn window_scale_factors() {
+        let mut cx = window_context(400, 300);
+
+        let main_content =
+            Element::new(cx.context()).width(Pixels(100.0)).height(Pixels(50.0)).entity();
+        let mut window_content = Entity::null();
+        let window = VStack::new(cx.context(), |cx| {
+            window_content = Element::new(cx).width(Pixels(100.0)).height(Pixels(50.0)).entity();
+        })
+        .entity();
+
+        // A second window on a display with a different scale factor.
+        let window_description = WindowDescription::new().with_inner_size(200, 100);
+        cx.add_main_window(window, &window_description, 2.0);
+        cx.0.windows.insert(window, WindowState { window_description, ..Default::default() });
+        cx.set_window_size(window, 400.0, 200.0);
+        update(&mut cx);
+
+        // The views of each window are laid out with the scale factor of their window.
+        assert_eq!(cx.0.cache.get_width(main_content), 100.0);
+        assert_eq!(cx.0.cache.get_width(window_content), 200.0);
+        assert_eq!(cx.0.cache.get_height(window_content), 100.0);
+        cx.0.with_current(window_content, |cx| assert_eq!(cx.scale_factor(), 2.0));
+        cx.0.with_current(main_content, |cx| assert_eq!(cx.scale_factor(), 1.0));
+
+        // Changing the scale factor of one window leaves the other unchanged.
+        cx.set_scale_factor(window, 1.5);
+        cx.needs_refresh(window);
+        update(&mut cx);
+
+        assert_eq!(cx.0.cache.get_width(main_content), 100.0);
+        assert_eq!(cx.0.cache.get_width(window_content), 150.0);
+        assert_eq!(cx.0.style.dpi_factor, 1.0);
+    }
+}
@@ -1,12 +1,12 @@
-use crate::{cache::CachedData, prelude::*};
+use crate::{cache::CachedData, prelude::*, tree::window_of};
 #[cfg(feature = "rayon")]
 use dashmap::{DashMap, ReadOnlyView};
 use hashbrown::HashMap;
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
-use vizia_storage::{LayoutParentIterator, TreeBreadthIterator};
+use vizia_storage::{LayoutParentIterator, TreeBreadthIterator, TreeIterator};
 use vizia_style::{
-    matches_selector,
+    matches_selector, matches_selector_list,
     precomputed_hash::PrecomputedHash,
     selectors::{
         attr::{AttrSelectorOperation, CaseSensitivity, NamespaceConstraint},
@@ -16,7 +16,8 @@ use vizia_style::{
         parser::{Component, NthType},
         OpaqueElement, SelectorImpl,
     },
-    Element, MatchingContext, MatchingMode, PseudoClass, QuirksMode, SelectorIdent, Selectors,
+    ColorScheme, Element, MatchingContext, MatchingMode, PseudoClass, QuirksMode, SelectorIdent,
+    SelectorList, Selectors,
 };
 
 /// A node used for style matching.
@@ -230,7 +231,11 @@ impl Element for Node<'_, '_> {
     }
 
     fn first_element_child(&self) -> Option<Self> {
-        None
+        self.tree.get_layout_first_child(self.entity).map(|child| Node {
+            entity: child,
+            store: self.store,
+            tree: self.tree,
+        })
     }
 
     fn apply_selector_flags(&self, _flags: ElementSelectorFlags) {}
@@ -267,10 +272,19 @@ pub(crate) fn inline_inheritance_system(cx: &mut Context, redraw_entities: &mut
                 | cx.style.text_decoration_line.inherit_inline(entity, parent)
                 | cx.style.text_stroke_width.inherit_inline(entity, parent)
                 | cx.style.text_stroke_style.inherit_inline(entity, parent)
+                | cx.style.letter_spacing.inherit_inline(entity, parent)
+                | cx.style.word_spacing.inherit_inline(entity, parent)
+                | cx.style.text_shadow.inherit_inline(entity, parent)
                 | cx.style.font_variation_settings.inherit_inline(entity, parent)
             {
                 cx.style.needs_text_update(entity);
             }
+
+            if cx.style.direction.inherit_inline(entity, parent) {
+                cx.style.needs_text_update(entity);
+                cx.style.needs_relayout();
+                redraw_entities.push(entity);
+            }
         }
     }
 }
@@ -288,11 +302,20 @@ pub(crate) fn shared_inheritance_system(cx: &mut Context, redraw_entities: &mut
                 | cx.style.text_decoration_line.inherit_shared(entity, parent)
                 | cx.style.text_stroke_width.inherit_shared(entity, parent)
                 | cx.style.text_stroke_style.inherit_shared(entity, parent)
+                | cx.style.letter_spacing.inherit_shared(entity, parent)
+                | cx.style.word_spacing.inherit_shared(entity, parent)
+                | cx.style.text_shadow.inherit_shared(entity, parent)
                 | cx.style.font_variation_settings.inherit_shared(entity, parent)
             {
                 cx.style.needs_text_update(entity);
             }
 
+            if cx.style.direction.inherit_shared(entity, parent) {
+                cx.style.needs_text_update(entity);
+                cx.style.needs_relayout();
+                redraw_entities.push(entity);
+            }
+
             if cx.style.caret_color.inherit_shared(entity, parent)
                 | cx.style.selection_color.inherit_shared(entity, parent)
             {
@@ -309,421 +332,493 @@ fn link_style_data(
     entity: Entity,
     redraw_entities: &mut Vec<Entity>,
     matched_rules: &[(Rule, u32)],
+    mut diff: Option<&mut Vec<StyleChange>>,
 ) {
     let mut should_relayout = false;
     let mut should_redraw = false;
     let mut should_reflow = false;
 
+    // Links a property to the matched rules, returning true if it changed. When recording a diff, changes to the
+    // linked value are added to the diff under the CSS name of the property, which is only given when it differs from
+    // the name of the field. The linked value is used rather than the computed value so that a change which starts a
+    // transition is recorded with the value the transition is playing towards.
+    macro_rules! link {
+        ($property:ident) => {
+            link!($property, stringify!($property).replace('_', "-"))
+        };
+
+        ($property:ident, $name:expr) => {{
+            let old = diff.is_some().then(|| format!("{:?}", style.$property.get_linked(entity)));
+            let changed = style.$property.link(entity, matched_rules);
+            if let (true, Some(diff), Some(old)) = (changed, diff.as_mut(), old) {
+                let new = format!("{:?}", style.$property.get_linked(entity));
+                if new != old {
+                    diff.push(StyleChange {
+                        property: $name.into(),
+                        old,
+                        new,
+                        transition: style.$property.is_transitioning(entity),
+                    });
+                }
+            }
+            changed
+        }};
+    }
+
     // Display
-    if style.display.link(entity, matched_rules) {
+    if link!(display) {
         should_relayout = true;
         should_redraw = true;
     }
 
-    if style.visibility.link(entity, matched_rules) {
+    if link!(visibility) {
         should_relayout = true;
         should_redraw = true;
     }
 
-    if style.z_index.link(entity, matched_rules) {
+    if link!(z_index) {
         should_redraw = true;
     }
 
-    if style.overflowx.link(entity, matched_rules) {
+    if link!(overflowx, "overflow-x") {
         should_redraw = true;
     }
 
-    if style.overflowy.link(entity, matched_rules) {
+    if link!(overflowy, "overflow-y") {
         should_redraw = true;
     }
 
-    if style.clip_path.link(entity, matched_rules) {
+    if link!(clip_path) {
         should_redraw = true;
     }
 
-    if style.backdrop_filter.link(entity, matched_rules) {
+    if link!(backdrop_filter) {
         should_redraw = true;
     }
 
-    if style.blend_mode.link(entity, matched_rules) {
+    if link!(blend_mode) {
         should_redraw = true;
     }
 
     // Opacity
-    if style.opacity.link(entity, matched_rules) {
+    if link!(opacity) {
         should_redraw = true;
     }
 
     // Grid
-    if style.grid_columns.link(entity, matched_rules) {
+    if link!(grid_columns) {
+        should_relayout = true;
+    }
+
+    if link!(grid_rows) {
+        should_relayout = true;
+    }
+
+    if link!(column_start) {
         should_relayout = true;
     }
 
-    if style.grid_rows.link(entity, matched_rules) {
+    if link!(column_span) {
         should_relayout = true;
     }
 
-    if style.column_start.link(entity, matched_rules) {
+    if link!(row_start) {
         should_relayout = true;
     }
 
-    if style.column_span.link(entity, matched_rules) {
+    if link!(row_span) {
         should_relayout = true;
     }
 
-    if style.row_start.link(entity, matched_rules) {
+    if link!(grid_column) {
         should_relayout = true;
     }
 
-    if style.row_span.link(entity, matched_rules) {
+    if link!(grid_row) {
         should_relayout = true;
     }
 
     // Position
 
-    if style.left.link(entity, matched_rules) {
+    if link!(left) {
         should_relayout = true;
         should_redraw = true;
     }
 
-    if style.right.link(entity, matched_rules) {
+    if link!(right) {
         should_relayout = true;
         should_redraw = true;
     }
 
-    if style.top.link(entity, matched_rules) {
+    if link!(top) {
         should_relayout = true;
         should_redraw = true;
     }
 
-    if style.bottom.link(entity, matched_rules) {
+    if link!(bottom) {
         should_relayout = true;
         should_redraw = true;
     }
 
     // Size
-    if style.width.link(entity, matched_rules) {
+    if link!(width) {
         should_relayout = true;
         should_redraw = true;
     }
 
-    if style.height.link(entity, matched_rules) {
+    if link!(height) {
         should_relayout = true;
         should_redraw = true;
     }
 
     // Size Constraints
-    if style.max_width.link(entity, matched_rules) {
+    if link!(max_width) {
         should_relayout = true;
         should_redraw = true;
     }
 
-    if style.min_width.link(entity, matched_rules) {
+    if link!(min_width) {
         should_relayout = true;
         should_redraw = true;
     }
 
-    if style.max_height.link(entity, matched_rules) {
+    if link!(max_height) {
         should_relayout = true;
         should_redraw = true;
     }
 
-    if style.min_height.link(entity, matched_rules) {
+    if link!(min_height) {
         should_relayout = true;
         should_redraw = true;
     }
 
     // Gap Constraints
-    if style.max_horizontal_gap.link(entity, matched_rules) {
+    if link!(max_horizontal_gap) {
         should_relayout = true;
         should_redraw = true;
     }
 
-    if style.min_horizontal_gap.link(entity, matched_rules) {
+    if link!(min_horizontal_gap) {
         should_relayout = true;
         should_redraw = true;
     }
 
-    if style.max_vertical_gap.link(entity, matched_rules) {
+    if link!(max_vertical_gap) {
         should_relayout = true;
         should_redraw = true;
     }
 
-    if style.min_vertical_gap.link(entity, matched_rules) {
+    if link!(min_vertical_gap) {
         should_relayout = true;
         should_redraw = true;
     }
 
     // Border
-    if style.border_width.link(entity, matched_rules) {
+    if link!(border_width) {
         should_relayout = true;
         should_redraw = true;
         cache.path.remove(entity);
     }
 
-    if style.border_color.link(entity, matched_rules) {
+    if link!(border_color) {
         should_redraw = true;
     }
 
-    if style.border_style.link(entity, matched_rules) {
+    if link!(border_style) {
         should_redraw = true;
     }
 
     // Corner
 
-    if style.corner_top_left_shape.link(entity, matched_rules) {
+    if link!(corner_top_left_shape) {
         should_redraw = true;
     }
 
-    if style.corner_top_right_shape.link(entity, matched_rules) {
+    if link!(corner_top_right_shape) {
         should_redraw = true;
     }
 
-    if style.corner_bottom_left_shape.link(entity, matched_rules) {
+    if link!(corner_bottom_left_shape) {
         should_redraw = true;
     }
 
-    if style.corner_bottom_right_shape.link(entity, matched_rules) {
+    if link!(corner_bottom_right_shape) {
         should_redraw = true;
     }
 
-    if style.corner_top_left_radius.link(entity, matched_rules) {
+    if link!(corner_top_left_radius) {
         should_redraw = true;
     }
 
-    if style.corner_top_right_radius.link(entity, matched_rules) {
+    if link!(corner_top_right_radius) {
         should_redraw = true;
     }
 
-    if style.corner_bottom_left_radius.link(entity, matched_rules) {
+    if link!(corner_bottom_left_radius) {
         should_redraw = true;
     }
 
-    if style.corner_bottom_right_radius.link(entity, matched_rules) {
+    if link!(corner_bottom_right_radius) {
         should_redraw = true;
     }
 
-    if style.outline_width.link(entity, matched_rules) {
+    if link!(outline_width) {
         should_redraw = true;
     }
 
-    if style.outline_color.link(entity, matched_rules) {
+    if link!(outline_color) {
         should_redraw = true;
     }
 
-    if style.outline_offset.link(entity, matched_rules) {
+    if link!(outline_offset) {
         should_redraw = true;
     }
 
-    if style.layout_type.link(entity, matched_rules) {
+    if link!(layout_type) {
         should_relayout = true;
         should_redraw = true;
     }
 
-    if style.position_type.link(entity, matched_rules) {
+    if link!(position_type) {
         should_relayout = true;
         should_redraw = true;
     }
 
-    if style.alignment.link(entity, matched_rules) {
+    if link!(alignment) {
+        should_relayout = true;
+        should_redraw = true;
+    }
+
+    if link!(align_self) {
+        should_relayout = true;
+        should_redraw = true;
+    }
+
+    if link!(justify_self) {
+        should_relayout = true;
+        should_redraw = true;
+    }
+
+    if link!(flex_wrap) {
         should_relayout = true;
         should_redraw = true;
     }
 
     // Background
-    if style.background_color.link(entity, matched_rules) {
+    if link!(background_color) {
         should_redraw = true;
     }
 
-    if style.background_image.link(entity, matched_rules) {
+    if link!(background_image) {
         should_redraw = true;
     }
 
-    if style.background_size.link(entity, matched_rules) {
+    if link!(background_size) {
         should_redraw = true;
     }
 
     // Font
-    if style.font_color.link(entity, matched_rules) {
+    if link!(font_color, "color") {
         should_redraw = true;
         should_reflow = true;
     }
 
-    if style.font_size.link(entity, matched_rules) {
+    if link!(font_size) {
         should_relayout = true;
         should_redraw = true;
         should_reflow = true;
     }
 
-    if style.font_family.link(entity, matched_rules) {
+    if link!(font_family) {
         should_relayout = true;
         should_redraw = true;
         should_reflow = true;
     }
 
-    if style.font_weight.link(entity, matched_rules) {
+    if link!(font_weight) {
         should_redraw = true;
         should_relayout = true;
         should_reflow = true;
     }
 
-    if style.font_slant.link(entity, matched_rules) {
+    if link!(font_slant) {
         should_redraw = true;
         should_relayout = true;
         should_reflow = true;
     }
 
-    if style.font_width.link(entity, matched_rules) {
+    if link!(font_width) {
         should_redraw = true;
         should_relayout = true;
         should_reflow = true;
     }
 
-    if style.font_variation_settings.link(entity, matched_rules) {
+    if link!(font_variation_settings) {
         should_redraw = true;
         should_relayout = true;
         should_reflow = true;
     }
 
-    if style.text_wrap.link(entity, matched_rules) {
+    if link!(text_wrap) {
         should_redraw = true;
         should_relayout = true;
         should_reflow = true;
     }
 
-    if style.text_align.link(entity, matched_rules) {
+    if link!(text_align) {
+        should_redraw = true;
+        should_reflow = true;
+    }
+
+    if link!(direction) {
         should_redraw = true;
+        should_relayout = true;
         should_reflow = true;
     }
 
-    if style.text_overflow.link(entity, matched_rules) {
+    if link!(text_overflow) {
         should_redraw = true;
         should_reflow = true;
     }
 
-    if style.line_clamp.link(entity, matched_rules) {
+    if link!(line_clamp) {
         should_redraw = true;
         should_reflow = true;
     }
 
-    if style.selection_color.link(entity, matched_rules) {
+    if link!(selection_color) {
         should_redraw = true;
     }
 
-    if style.caret_color.link(entity, matched_rules) {
+    if link!(caret_color) {
         should_redraw = true;
     }
 
-    if style.text_decoration_line.link(entity, matched_rules) {
+    if link!(text_decoration_line) {
         should_redraw = true;
         should_reflow = true;
     }
 
-    if style.text_stroke_width.link(entity, matched_rules) {
+    if link!(text_stroke_width) {
         should_redraw = true;
         should_reflow = true;
     }
 
-    if style.text_stroke_style.link(entity, matched_rules) {
+    if link!(text_stroke_style) {
         should_redraw = true;
         should_reflow = true;
     }
 
-    if style.underline_style.link(entity, matched_rules) {
+    if link!(letter_spacing) {
         should_redraw = true;
         should_reflow = true;
     }
 
-    if style.underline_color.link(entity, matched_rules) {
+    if link!(word_spacing) {
         should_redraw = true;
         should_reflow = true;
     }
 
-    if style.overline_style.link(entity, matched_rules) {
+    if link!(text_shadow) {
         should_redraw = true;
         should_reflow = true;
     }
 
-    if style.overline_color.link(entity, matched_rules) {
+    if link!(underline_style) {
         should_redraw = true;
         should_reflow = true;
     }
 
-    if style.strikethrough_style.link(entity, matched_rules) {
+    if link!(underline_color) {
         should_redraw = true;
         should_reflow = true;
     }
 
-    if style.strikethrough_color.link(entity, matched_rules) {
+    if link!(overline_style) {
+        should_redraw = true;
+        should_reflow = true;
+    }
+
+    if link!(overline_color) {
+        should_redraw = true;
+        should_reflow = true;
+    }
+
+    if link!(strikethrough_style) {
+        should_redraw = true;
+        should_reflow = true;
+    }
+
+    if link!(strikethrough_color) {
         should_redraw = true;
         should_reflow = true;
     }
 
     // Outer Shadow
-    if style.shadow.link(entity, matched_rules) {
+    if link!(shadow, "box-shadow") {
         should_redraw = true;
     }
 
-    if style.padding_left.link(entity, matched_rules) {
+    if link!(padding_left) {
         should_relayout = true;
         should_redraw = true;
     }
 
-    if style.padding_right.link(entity, matched_rules) {
+    if link!(padding_right) {
         should_relayout = true;
         should_redraw = true;
     }
 
-    if style.padding_top.link(entity, matched_rules) {
+    if link!(padding_top) {
         should_relayout = true;
         should_redraw = true;
     }
 
-    if style.padding_bottom.link(entity, matched_rules) {
+    if link!(padding_bottom) {
         should_relayout = true;
         should_redraw = true;
     }
 
-    if style.vertical_gap.link(entity, matched_rules) {
+    if link!(vertical_gap) {
         should_relayout = true;
         should_redraw = true;
     }
 
-    if style.horizontal_gap.link(entity, matched_rules) {
+    if link!(horizontal_gap) {
         should_relayout = true;
         should_redraw = true;
     }
 
-    if style.cursor.link(entity, matched_rules) {
+    if link!(cursor) {
         should_redraw = true;
     }
 
-    if style.pointer_events.link(entity, matched_rules) {
+    if link!(pointer_events) {
         should_redraw = true;
     }
 
     // Transform
-    if style.transform.link(entity, matched_rules) {
+    if link!(transform) {
         should_redraw = true;
     }
 
-    if style.transform_origin.link(entity, matched_rules) {
+    if link!(transform_origin) {
         should_redraw = true;
     }
 
-    if style.translate.link(entity, matched_rules) {
+    if link!(translate) {
         should_redraw = true;
     }
 
-    if style.rotate.link(entity, matched_rules) {
+    if link!(rotate) {
         should_redraw = true;
     }
 
-    if style.scale.link(entity, matched_rules) {
+    if link!(scale) {
         should_redraw = true;
     }
 
-    if style.fill.link(entity, matched_rules) {
+    if link!(fill) {
         should_redraw = true;
     }
 
@@ -767,12 +862,23 @@ pub(crate) fn compute_matched_rules(
     );
 
     let node = Node { entity, store, tree };
+    let inactive_rules = store.inactive_rules.get(&window_of(tree, entity));
 
     for (rule_id, rule) in store.rules.iter() {
+        if inactive_rules.is_some_and(|inactive_rules| inactive_rules.contains(rule_id)) {
+            continue;
+        }
+
         let matches = matches_selector(&rule.selector, 0, Some(&rule.hashes), &node, &mut context);
 
         if matches {
-            matched_rules.push((*rule_id, rule.selector.specificity()));
+            let specificity = if store.inspector_rules.contains(rule_id) {
+                u32::MAX
+            } else {
+                rule.selector.specificity()
+            };
+
+            matched_rules.push((*rule_id, specificity));
         }
     }
 
@@ -781,6 +887,30 @@ pub(crate) fn compute_matched_rules(
     matched_rules
 }
 
+/// Returns the entities in the tree which match a list of selectors, in tree order.
+pub(crate) fn query_selector(
+    selectors: &SelectorList<Selectors>,
+    store: &Style,
+    tree: &Tree<Entity>,
+) -> Vec<Entity> {
+    let mut cache = SelectorCaches::default();
+    let mut context = MatchingContext::new(
+        MatchingMode::Normal,
+        None,
+        &mut cache,
+        QuirksMode::NoQuirks,
+        NeedsSelectorFlags::No,
+        MatchingForInvalidation::No,
+    );
+
+    TreeIterator::full(tree)
+        .filter(|entity| {
+            let node = Node { entity: *entity, store, tree };
+            matches_selector_list(selectors, &node, &mut context)
+        })
+        .collect()
+}
+
 fn has_same_selector(style: &Style, entity1: Entity, entity2: Entity) -> bool {
     if let Some(element1) = style.element.get(entity1) {
         if let Some(element2) = style.element.get(entity2) {
@@ -918,7 +1048,11 @@ impl MatchedRules {
 
         let mut matched_index = None;
 
-        if !tree.is_first_child(entity) && !tree.is_last_child(entity) {
+        // Views which match `:has()` selectors depend on their descendants, so they can't share the rules of a sibling.
+        if !tree.is_first_child(entity)
+            && !tree.is_last_child(entity)
+            && !style.invalidation.has_relative_selectors()
+        {
             if let Some(cache) = rule_cache.get(&parent) {
                 matched_index = cache.iter().position(|entry| {
                     has_same_selector(style, entry.entity, entity)
@@ -962,10 +1096,41 @@ impl MatchedRules {
 
 // Iterates the tree and determines the matching style rules for each entity, then links the entity to the corresponding style rule data.
 pub(crate) fn style_system(cx: &mut Context) {
+    restyle(cx, None);
+}
+
+// Performs a restyle, recording the changed properties of each restyled entity if `diffs` is provided.
+pub(crate) fn restyle(cx: &mut Context, mut diffs: Option<&mut HashMap<Entity, Vec<StyleChange>>>) {
     let mut redraw_entities = Vec::new();
 
     inline_inheritance_system(cx, &mut redraw_entities);
 
+    // Re-evaluate media queries against the logical size of each window and the current theme mode.
+    if !cx.style.media_rules.is_empty() {
+        let color_scheme = match cx.environment().theme.get_current_theme() {
+            ThemeMode::DarkMode => ColorScheme::Dark,
+            ThemeMode::LightMode => ColorScheme::Light,
+        };
+
+        let windows = std::iter::once(Entity::root())
+            .chain(cx.windows.keys().copied().filter(|window| *window != Entity::root()))
+            .collect::<Vec<_>>();
+
+        for window in windows {
+            let bounds = cx.cache.get_bounds(window);
+            let scale_factor = cx.style.window_scale_factor(window) as f32;
+            let (width, height) = (bounds.w / scale_factor, bounds.h / scale_factor);
+
+            if cx.style.update_media_rules(window, width, height, color_scheme) {
+                for entity in cx.tree.into_iter() {
+                    if window_of(&cx.tree, entity) == window {
+                        cx.style.needs_restyle(entity);
+                    }
+                }
+            }
+        }
+    }
+
     if cx.style.restyle.is_empty() {
         return;
     }
@@ -988,6 +1153,8 @@ pub(crate) fn style_system(cx: &mut Context) {
     //  Apply matched rules to entities
     for entity in entities {
         if let Some(matched_rules) = matched_rules.get(&entity) {
+            let mut diff = diffs.as_ref().map(|_| Vec::new());
+
             link_style_data(
                 &mut cx.style,
                 &mut cx.cache,
@@ -995,7 +1162,14 @@ pub(crate) fn style_system(cx: &mut Context) {
                 entity,
                 &mut redraw_entities,
                 matched_rules,
+                diff.as_mut(),
             );
+
+            if let (Some(diffs), Some(diff)) = (diffs.as_mut(), diff) {
+                if !diff.is_empty() {
+                    diffs.insert(entity, diff);
+                }
+            }
         }
     }
     cx.style.restyle.clear();
@@ -1006,3 +1180,156 @@ pub(crate) fn style_system(cx: &mut Context) {
         cx.needs_redraw(entity);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use vizia_window::WindowDescription;
+
+    use crate::context::backend::BackendContext;
+    use crate::context::test_support::{update, window_context};
+    use crate::prelude::*;
+
+    #[test]
+    fn style_diff() {
+        let mut cx = window_context(400, 300);
+        cx.context()
+            .add_stylesheet(
+                r#"
+                .item { background-color: #0000ff; }
+                .item:checked {
+                    background-color: #ff0000;
+                    color: #00ff00;
+                    overflow-x: hidden;
+                    transition: background-color 100ms;
+                }
+                "#,
+            )
+            .unwrap();
+
+        let entity = Element::new(cx.context()).class("item").size(Pixels(100.0)).entity();
+        update(&mut cx);
+
+        EventContext::new_with_current(&mut cx.0, entity).set_checked(true);
+        let diffs = cx.0.diff_and_patch_style();
+
+        let change = |property: &str, old: String, new: String, transition: bool| StyleChange {
+            property: property.to_string(),
+            old,
+            new,
+            transition,
+        };
+
+        // Only the properties set by the `:checked` rule change, named as in CSS and in the order they're restyled. The
+        // background color records the color the transition plays towards rather than the start of the transition.
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(
+            diffs[&entity],
+            [
+                change(
+                    "overflow-x",
+                    "None".into(),
+                    format!("{:?}", Some(&Overflow::Hidden)),
+                    false
+                ),
+                change(
+                    "background-color",
+                    format!("{:?}", Some(&Color::rgb(0, 0, 255))),
+                    format!("{:?}", Some(&Color::rgb(255, 0, 0))),
+                    true
+                ),
+                change(
+                    "color",
+                    "None".into(),
+                    format!("{:?}", Some(&Color::rgb(0, 255, 0))),
+                    false
+                ),
+            ]
+        );
+
+        // Restyling again without any change to the pseudo-classes doesn't change any properties.
+        cx.0.style.needs_restyle(entity);
+        assert!(cx.0.diff_and_patch_style().is_empty());
+    }
+
+    #[test]
+    fn has_restyles_ancestors() {
+        let mut cx = window_context(400, 300);
+        cx.context()
+            .add_stylesheet(
+                ".card:has(.icon:checked) { background-color: #ff0000; } .card { width: 100px; }",
+            )
+            .unwrap();
+
+        // The icon is a grandchild of the second card, which has a sibling with the same classes.
+        let mut cards = Vec::new();
+        let mut icon = Entity::null();
+        for index in 0..3 {
+            cards.push(
+                VStack::new(cx.context(), |cx| {
+                    HStack::new(cx, |cx| {
+                        let entity = Element::new(cx).class("icon").entity();
+                        if index == 1 {
+                            icon = entity;
+                        }
+                    });
+                })
+                .class("card")
+                .entity(),
+            );
+        }
+        update(&mut cx);
+
+        let is_red = |cx: &BackendContext, entity: Entity| {
+            cx.0.style.background_color.get(entity).copied() == Some(Color::rgb(255, 0, 0))
+        };
+        assert!(cards.iter().all(|card| !is_red(&cx, *card)));
+
+        // Checking the icon restyles the card two levels above it, but not the other cards.
+        EventContext::new_with_current(&mut cx.0, icon).set_checked(true);
+        update(&mut cx);
+        assert!(!is_red(&cx, cards[0]));
+        assert!(is_red(&cx, cards[1]));
+        assert!(!is_red(&cx, cards[2]));
+
+        EventContext::new_with_current(&mut cx.0, icon).set_checked(false);
+        update(&mut cx);
+        assert!(!is_red(&cx, cards[1]));
+    }
+
+    #[test]
+    fn media_rules_per_window() {
+        let mut cx = window_context(400, 300);
+        cx.context()
+            .add_stylesheet("@media (max-width: 300px) { .item { background-color: #ff0000; } }")
+            .unwrap();
+
+        let main_item = Element::new(cx.context()).class("item").entity();
+        let mut window_item = Entity::null();
+        let window = VStack::new(cx.context(), |cx| {
+            window_item = Element::new(cx).class("item").entity();
+        })
+        .entity();
+
+        // A second window which is 400 physical pixels wide on a display with a scale factor of 2.
+        let window_description = WindowDescription::new().with_inner_size(200, 100);
+        cx.add_main_window(window, &window_description, 2.0);
+        cx.0.windows.insert(window, WindowState { window_description, ..Default::default() });
+        cx.set_window_size(window, 400.0, 200.0);
+        update(&mut cx);
+
+        // The media query is matched against the logical size of the window each view is in.
+        let is_red = |cx: &BackendContext, entity: Entity| {
+            cx.0.style.background_color.get(entity).copied() == Some(Color::rgb(255, 0, 0))
+        };
+        assert!(!is_red(&cx, main_item));
+        assert!(is_red(&cx, window_item));
+
+        // Resizing one window re-evaluates the media query for its views only.
+        cx.set_window_size(Entity::root(), 250.0, 300.0);
+        cx.set_window_size(window, 800.0, 200.0);
+        cx.needs_refresh(Entity::root());
+        update(&mut cx);
+        assert!(is_red(&cx, main_item));
+        assert!(!is_red(&cx, window_item));
+    }
+}
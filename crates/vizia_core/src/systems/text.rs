@@ -2,13 +2,14 @@ use skia_safe::{
     font_arguments::VariationPosition,
     textlayout::{
         FontCollection, Paragraph, ParagraphBuilder, ParagraphStyle, RectHeightStyle,
-        RectWidthStyle, TextStyle,
+        RectWidthStyle, TextDecoration, TextDecorationStyle, TextDirection, TextShadow, TextStyle,
     },
     BlendMode, FontArguments, FontStyle, Paint,
 };
 use vizia_storage::{LayoutChildIterator, LayoutTreeIterator};
 
-use crate::{cache::CachedData, prelude::*};
+use crate::tree::window_of;
+use crate::{cache::CachedData, prelude::*, text::FontFallback};
 
 pub(crate) fn text_system(cx: &mut Context) {
     let iterator = LayoutTreeIterator::full(&cx.tree);
@@ -20,16 +21,24 @@ pub(crate) fn text_system(cx: &mut Context) {
         if cx.style.text.contains(entity)
             && cx.style.display.get(entity).copied().unwrap_or_default() != Display::None
         {
-            if let Some(paragraph) =
-                build_paragraph(entity, &mut cx.style, &cx.tree, cx.text_context.font_collection())
-            {
+            cx.style.use_window_scale_factor(window_of(&cx.tree, entity));
+            if let Some(paragraph) = build_paragraph(
+                entity,
+                &mut cx.style,
+                &cx.tree,
+                &cx.text_context.font_collection,
+                &mut cx.text_context.font_fallback,
+            ) {
                 cx.text_context.text_paragraphs.insert(entity, paragraph);
+                // The text, font or size changed, so the size of the text can't be reused by a throttled relayout.
+                cx.text_context.text_bounds.remove(entity);
                 cx.style.needs_relayout();
                 cx.style.needs_text_layout(entity);
             }
         }
     }
 
+    cx.style.use_window_scale_factor(Entity::root());
     cx.style.text_construction.clear();
 }
 
@@ -42,6 +51,7 @@ pub(crate) fn text_layout_system(cx: &mut Context) {
         }
 
         if let Some(paragraph) = cx.text_context.text_paragraphs.get_mut(entity) {
+            cx.style.use_window_scale_factor(window_of(&cx.tree, entity));
             let bounds = cx.cache.get_bounds(entity);
             let padding_left = cx
                 .style
@@ -66,18 +76,35 @@ pub(crate) fn text_layout_system(cx: &mut Context) {
                 .copied()
                 .unwrap_or(bounds.shrink_sides(padding_left, 0.0, padding_right, 0.0));
 
+            let text_overflow = cx.style.text_overflow.get(entity).copied().unwrap_or_default();
             if !cx.style.width.get(entity).copied().unwrap_or_default().is_auto()
                 && !cx.style.height.get(entity).copied().unwrap_or_default().is_auto()
             {
-                if cx.style.text_overflow.get(entity).copied().unwrap_or_default()
-                    == TextOverflow::Clip
-                {
+                if text_overflow == TextOverflow::Clip {
                     paragraph.layout(f32::MAX);
                     paragraph
                         .layout(text_bounds.width().max(paragraph.min_intrinsic_width() + 1.0));
                 } else {
                     paragraph.layout(text_bounds.width());
                 }
+            } else if text_overflow == TextOverflow::Ellipsis {
+                // The text is measured before the view is constrained, such as by a max-width or a parent which is
+                // too small, so lay it out again against the final width to truncate it with an ellipsis.
+                let border_width = cx
+                    .style
+                    .border_width
+                    .get(entity)
+                    .cloned()
+                    .unwrap_or_default()
+                    .to_pixels(0.0, cx.style.scale_factor());
+                let available_width =
+                    (bounds.width() - padding_left - padding_right - 2.0 * border_width).max(0.0);
+                if paragraph.longest_line() > available_width {
+                    paragraph.layout(available_width);
+                    if let Some(text_bounds) = cx.text_context.text_bounds.get_mut(entity) {
+                        text_bounds.w = available_width;
+                    }
+                }
             }
 
             layout_span(&cx.style, &mut cx.cache, &cx.tree, entity, paragraph, bounds);
@@ -85,6 +112,7 @@ pub(crate) fn text_layout_system(cx: &mut Context) {
             redraw_entities.push(entity);
         }
     }
+    cx.style.use_window_scale_factor(Entity::root());
     for entity in redraw_entities {
         cx.needs_redraw(entity);
     }
@@ -147,6 +175,7 @@ pub fn build_paragraph(
     style: &mut Style,
     tree: &Tree<Entity>,
     font_collection: &FontCollection,
+    font_fallback: &mut FontFallback,
 ) -> Option<Paragraph> {
     let mut paragraph_style = ParagraphStyle::default();
     // paragraph_style.turn_hinting_off();
@@ -177,27 +206,33 @@ pub fn build_paragraph(
     //     }
     // }
 
+    // Direction
+    if style.direction.get(entity) == Some(&Direction::Rtl) {
+        paragraph_style.set_text_direction(TextDirection::RTL);
+    }
+
     // Text Align
+    // The alignment of the view is mirrored along with its layout when the direction is right-to-left.
     paragraph_style.set_text_align(
         if let Some(text_align) = style.text_align.get(entity) {
             *text_align
         } else if let Some(alignment) = style.alignment.get(entity) {
             match alignment {
-                Alignment::TopLeft | Alignment::Left | Alignment::BottomLeft => TextAlign::Left,
+                Alignment::TopLeft | Alignment::Left | Alignment::BottomLeft => TextAlign::Start,
                 Alignment::TopCenter | Alignment::Center | Alignment::BottomCenter => {
                     TextAlign::Center
                 }
-                Alignment::TopRight | Alignment::Right | Alignment::BottomRight => TextAlign::Right,
+                Alignment::TopRight | Alignment::Right | Alignment::BottomRight => TextAlign::End,
             }
         } else {
-            TextAlign::Left
+            TextAlign::Start
         }
         .into(),
     );
 
     let mut paragraph_builder = ParagraphBuilder::new(&paragraph_style, font_collection);
 
-    add_block(style, tree, entity, &mut paragraph_builder, &mut 0);
+    add_block(style, tree, entity, &mut paragraph_builder, font_fallback, &mut 0);
 
     paragraph_builder.add_text("\u{200B}");
     paragraph_builder.build().into()
@@ -208,6 +243,7 @@ fn add_block(
     tree: &Tree<Entity>,
     entity: Entity,
     paragraph_builder: &mut ParagraphBuilder,
+    font_fallback: &mut FontFallback,
     current: &mut usize,
 ) {
     // let mut new_current = current;
@@ -225,13 +261,16 @@ fn add_block(
                 text_style.set_decoration_color(font_color);
             }
 
-            // Font Families
+            // Font Families, followed by the fallback fonts for the scripts in the text
             text_style.set_font_families(
-                style
-                    .font_family
-                    .get(entity)
-                    .map(Vec::as_slice)
-                    .unwrap_or(&[FamilyOwned::Generic(GenericFontFamily::SansSerif)]),
+                &font_fallback.families(
+                    text,
+                    style
+                        .font_family
+                        .get(entity)
+                        .map(Vec::as_slice)
+                        .unwrap_or(&[FamilyOwned::Generic(GenericFontFamily::SansSerif)]),
+                ),
             );
 
             let mut paint = Paint::default();
@@ -282,6 +321,41 @@ fn add_block(
                 }
             }
 
+            // Letter and Word Spacing
+            if let Some(letter_spacing) = style.letter_spacing.get(entity) {
+                text_style.set_letter_spacing(
+                    letter_spacing.to_px().unwrap_or(0.0) * style.scale_factor(),
+                );
+            }
+
+            if let Some(word_spacing) = style.word_spacing.get(entity) {
+                text_style
+                    .set_word_spacing(word_spacing.to_px().unwrap_or(0.0) * style.scale_factor());
+            }
+
+            // Text Shadows
+            if let Some(text_shadows) = style.text_shadow.get(entity) {
+                for text_shadow in text_shadows.iter() {
+                    let offset = (
+                        text_shadow.x_offset.to_px().unwrap_or(0.0) * style.scale_factor(),
+                        text_shadow.y_offset.to_px().unwrap_or(0.0) * style.scale_factor(),
+                    );
+                    let blur_radius = text_shadow
+                        .blur_radius
+                        .as_ref()
+                        .and_then(|radius| radius.to_px())
+                        .unwrap_or(0.0)
+                        * style.scale_factor();
+
+                    // Skia takes the standard deviation of the blur, which is half of the blur radius in CSS.
+                    text_style.add_shadow(TextShadow::new(
+                        text_shadow.color.unwrap_or(font_color),
+                        offset,
+                        (blur_radius / 2.0) as f64,
+                    ));
+                }
+            }
+
             // Font Variations
             if let Some(coordinates) = style.font_variation_settings.get(entity) {
                 let coordinates = coordinates.iter().map(|c| c.0).collect::<Vec<_>>();
@@ -292,7 +366,36 @@ fn add_block(
 
             paragraph_builder.push_style(&text_style);
             style.text_range.insert(entity, *current..*current + text.len());
-            paragraph_builder.add_text(text.as_str());
+
+            // Underline any misspelled words with a wavy red line.
+            let mut start = 0;
+            if let Some(misspelled) = style.misspelled.get(entity) {
+                let mut misspelled_style = text_style.clone();
+                misspelled_style
+                    .set_decoration_type(text_style.decoration_type() | TextDecoration::UNDERLINE);
+                misspelled_style.set_decoration_style(TextDecorationStyle::Wavy);
+                misspelled_style.set_decoration_color(Color::red());
+
+                for range in misspelled {
+                    // Skip ranges which overlap the previous range or aren't within the text.
+                    if range.start < start
+                        || range.end < range.start
+                        || range.end > text.len()
+                        || !text.is_char_boundary(range.start)
+                        || !text.is_char_boundary(range.end)
+                    {
+                        continue;
+                    }
+
+                    paragraph_builder.add_text(&text[start..range.start]);
+                    paragraph_builder.push_style(&misspelled_style);
+                    paragraph_builder.add_text(&text[range.clone()]);
+                    paragraph_builder.pop();
+                    start = range.end;
+                }
+            }
+
+            paragraph_builder.add_text(&text[start..]);
             *current += text.len();
         }
     }
@@ -300,7 +403,7 @@ fn add_block(
     let iter = LayoutChildIterator::new(tree, entity);
     for child in iter {
         if style.text_span.get(child).copied().unwrap_or_default() {
-            add_block(style, tree, child, paragraph_builder, current);
+            add_block(style, tree, child, paragraph_builder, font_fallback, current);
         }
     }
 }
@@ -0,0 +1,205 @@
+use std::ops::Range;
+
+use super::Direction;
+
+// A single position within an input mask.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MaskToken {
+    // Accepts an ASCII digit.
+    Digit,
+    // Accepts an alphabetic character.
+    Letter,
+    // Accepts an alphanumeric character.
+    Alphanumeric,
+    // A character which is inserted automatically.
+    Literal(char),
+}
+
+impl MaskToken {
+    fn accepts(&self, c: char) -> bool {
+        match self {
+            MaskToken::Digit => c.is_ascii_digit(),
+            MaskToken::Letter => c.is_alphabetic(),
+            MaskToken::Alphanumeric => c.is_alphanumeric(),
+            MaskToken::Literal(_) => false,
+        }
+    }
+}
+
+/// A pattern which constrains the text of a textbox, such as `(###) ###-####` for a phone number.
+///
+/// Within the pattern, `#` accepts a digit, `A` accepts a letter and `*` accepts a letter or a digit. Any other
+/// character is a literal which is inserted automatically as the user types. A literal `#`, `A`, `*` or `\` can be
+/// included by escaping it with a `\`.
+///
+/// The characters entered by the user, without the literals, make up the unmasked value of the text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputMask {
+    tokens: Vec<MaskToken>,
+}
+
+impl InputMask {
+    /// Creates a new input mask from a pattern.
+    pub fn new(pattern: &str) -> Self {
+        let mut tokens = Vec::new();
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            tokens.push(match c {
+                '#' => MaskToken::Digit,
+                'A' => MaskToken::Letter,
+                '*' => MaskToken::Alphanumeric,
+                '\\' => MaskToken::Literal(chars.next().unwrap_or('\\')),
+                c => MaskToken::Literal(c),
+            });
+        }
+
+        Self { tokens }
+    }
+
+    /// Returns the number of characters the user can enter into the mask.
+    pub fn slots(&self) -> usize {
+        self.tokens.iter().filter(|token| !matches!(token, MaskToken::Literal(_))).count()
+    }
+
+    /// Formats the given text with the mask, inserting literals and dropping any characters which are not accepted.
+    ///
+    /// The text can be either formatted or unmasked.
+    pub fn format(&self, text: &str) -> String {
+        self.apply(text).0
+    }
+
+    /// Returns the characters entered by the user, without the literals of the mask.
+    pub fn unmask(&self, text: &str) -> String {
+        self.apply(text).1
+    }
+
+    /// Returns true if every character of the mask has been entered.
+    pub fn is_complete(&self, text: &str) -> bool {
+        self.unmask(text).chars().count() == self.slots()
+    }
+
+    // Replaces a byte range of the formatted text, returning the new formatted text and the byte offset of the caret.
+    pub(crate) fn edit(&self, text: &str, range: Range<usize>, insert: &str) -> (String, usize) {
+        let start = self.unmask(&text[..range.start]).chars().count();
+        let end = self.unmask(&text[..range.end]).chars().count();
+        self.splice(text, start..end, insert)
+    }
+
+    // Deletes a byte range of the formatted text, returning the new formatted text and the byte offset of the caret.
+    // If the range only contains literals then the entered character in the given direction is deleted instead.
+    pub(crate) fn delete(
+        &self,
+        text: &str,
+        range: Range<usize>,
+        direction: Direction,
+    ) -> (String, usize) {
+        let mut start = self.unmask(&text[..range.start]).chars().count();
+        let mut end = self.unmask(&text[..range.end]).chars().count();
+        if start == end {
+            if direction == Direction::Upstream {
+                start = start.saturating_sub(1);
+            } else {
+                end = (end + 1).min(self.unmask(text).chars().count());
+            }
+        }
+
+        self.splice(text, start..end, "")
+    }
+
+    // Replaces a range of the unmasked characters of the text.
+    fn splice(&self, text: &str, range: Range<usize>, insert: &str) -> (String, usize) {
+        let unmasked = self.unmask(text).chars().collect::<Vec<_>>();
+
+        let mut prefix = unmasked[..range.start].iter().collect::<String>();
+        prefix.push_str(insert);
+        let caret = self.format(&prefix).len();
+
+        prefix.extend(&unmasked[range.end..]);
+        (self.format(&prefix), caret)
+    }
+
+    // Walks the text and the mask together, returning the formatted text and the unmasked characters.
+    //
+    // A character which matches an upcoming literal consumes it, otherwise the literals before the next slot are
+    // inserted automatically and the character fills the slot if accepted or is dropped.
+    fn apply(&self, text: &str) -> (String, String) {
+        let mut formatted = String::new();
+        let mut unmasked = String::new();
+        let mut index = 0;
+
+        for c in text.chars() {
+            let mut next = index;
+            while let Some(MaskToken::Literal(literal)) = self.tokens.get(next) {
+                if *literal == c {
+                    break;
+                }
+                next += 1;
+            }
+
+            match self.tokens.get(next) {
+                Some(MaskToken::Literal(_)) => {}
+                Some(token) if token.accepts(c) => {
+                    unmasked.push(c);
+                }
+                Some(_) => continue,
+                None => break,
+            }
+
+            formatted.extend(self.tokens[index..next].iter().map(|token| match token {
+                MaskToken::Literal(literal) => *literal,
+                _ => unreachable!(),
+            }));
+            formatted.push(c);
+            index = next + 1;
+        }
+
+        (formatted, unmasked)
+    }
+}
+
+impl From<&str> for InputMask {
+    fn from(pattern: &str) -> Self {
+        InputMask::new(pattern)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_inserts_literals() {
+        let mask = InputMask::new("(###) ###-####");
+        assert_eq!(mask.format("5551234567"), "(555) 123-4567");
+        assert_eq!(mask.format("5551"), "(555) 1");
+        assert_eq!(mask.format("(555) 1"), "(555) 1");
+        assert_eq!(mask.format("555a1234567890"), "(555) 123-4567");
+        assert_eq!(mask.unmask("(555) 123-4567"), "5551234567");
+        assert!(mask.is_complete("(555) 123-4567"));
+        assert!(!mask.is_complete("(555) 123"));
+    }
+
+    #[test]
+    fn escaped_literals() {
+        let mask = InputMask::new("\\#A-*");
+        assert_eq!(mask.slots(), 2);
+        assert_eq!(mask.format("b2"), "#b-2");
+        assert_eq!(mask.format("22"), "");
+    }
+
+    #[test]
+    fn edit_moves_caret_past_literals() {
+        let mask = InputMask::new("##/##/####");
+        assert_eq!(mask.edit("12", 2..2, "3"), ("12/3".to_string(), 4));
+        assert_eq!(mask.edit("12/34", 1..1, "9"), ("19/23/4".to_string(), 2));
+        assert_eq!(mask.edit("12/3", 4..4, "x"), ("12/3".to_string(), 4));
+    }
+
+    #[test]
+    fn delete_skips_literals() {
+        let mask = InputMask::new("##/##/####");
+        assert_eq!(mask.delete("12/3", 3..3, Direction::Upstream), ("13".to_string(), 1));
+        assert_eq!(mask.delete("12/34", 2..3, Direction::Upstream), ("13/4".to_string(), 1));
+        assert_eq!(mask.delete("12/34", 2..2, Direction::Downstream), ("12/4".to_string(), 2));
+    }
+}
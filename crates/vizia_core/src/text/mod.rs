@@ -5,6 +5,7 @@ pub use movement::*;
 // pub(crate) use scrolling::*;
 
 pub(crate) mod text_context;
+pub use text_context::FontScript;
 pub(crate) use text_context::*;
 
 pub mod editable_text;
@@ -15,3 +16,6 @@ pub use selection::*;
 
 pub mod backspace;
 pub use backspace::*;
+
+pub mod input_mask;
+pub use input_mask::*;
@@ -68,20 +68,28 @@ pub enum WritingDirection {
 /// If `modify` is true, only the 'active' edge (the `end`) of the selection
 /// should be changed; this is the case when the user moves with the shift
 /// key pressed.
+///
+/// The writing direction determines whether left and right movements go backwards or
+/// forwards through the text. A natural writing direction is resolved from the first
+/// character of the text with a strong direction.
 pub fn apply_movement<T: EditableText>(
     m: Movement,
     s: Selection,
     text: &T,
     paragraph: &Paragraph,
+    writing_direction: WritingDirection,
     modify: bool,
 ) -> Selection {
-    // let writing_direction = if crate::piet::util::first_strong_rtl(text.as_str()) {
-    //     WritingDirection::RightToLeft
-    // } else {
-    //     WritingDirection::LeftToRight
-    // };
-
-    let writing_direction = WritingDirection::LeftToRight;
+    let writing_direction = match writing_direction {
+        WritingDirection::Natural => {
+            if text.slice(0..text.len()).is_some_and(|text| first_strong_rtl(&text)) {
+                WritingDirection::RightToLeft
+            } else {
+                WritingDirection::LeftToRight
+            }
+        }
+        writing_direction => writing_direction,
+    };
 
     let (offset, h_pos) = match m {
         Movement::Grapheme(d) if d.is_upstream_for_direction(writing_direction) => {
@@ -203,3 +211,15 @@ pub fn apply_movement<T: EditableText>(
     let start = if modify { s.anchor } else { offset };
     Selection::new(start, offset).with_h_pos(h_pos)
 }
+
+// Returns whether the first character of the text with a strong direction is right-to-left.
+fn first_strong_rtl(text: &str) -> bool {
+    text.chars()
+        .find(|c| c.is_alphabetic())
+        .is_some_and(|c| {
+            matches!(
+                c as u32,
+                0x0590..=0x08FF | 0xFB1D..=0xFDFF | 0xFE70..=0xFEFF | 0x10800..=0x10FFF | 0x1E800..=0x1EFFF
+            )
+        })
+}
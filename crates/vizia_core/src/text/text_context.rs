@@ -2,7 +2,12 @@ use skia_safe::textlayout::{Paragraph, TypefaceFontProvider};
 use skia_safe::{textlayout::FontCollection, FontMgr};
 use vizia_storage::SparseSet;
 
-use crate::{entity::Entity, layout::BoundingBox};
+#[cfg(feature = "system-fonts")]
+use hashbrown::HashMap;
+#[cfg(feature = "system-fonts")]
+use skia_safe::FontStyle;
+
+use crate::{entity::Entity, layout::BoundingBox, style::FamilyOwned};
 
 pub struct TextContext {
     pub font_collection: FontCollection,
@@ -10,6 +15,7 @@ pub struct TextContext {
     pub asset_provider: TypefaceFontProvider,
     pub text_bounds: SparseSet<BoundingBox>,
     pub text_paragraphs: SparseSet<Paragraph>,
+    pub(crate) font_fallback: FontFallback,
 }
 
 impl TextContext {
@@ -22,3 +28,185 @@ impl TextContext {
         self.text_bounds.insert(entity, bounds);
     }
 }
+
+/// A writing system which a fallback font can be registered for with
+/// [`add_font_fallback`](crate::context::Context::add_font_fallback).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FontScript {
+    /// The Latin alphabet.
+    Latin,
+    /// The Greek alphabet.
+    Greek,
+    /// The Cyrillic alphabet.
+    Cyrillic,
+    /// The Armenian alphabet.
+    Armenian,
+    /// The Hebrew alphabet.
+    Hebrew,
+    /// The Arabic script.
+    Arabic,
+    /// The Devanagari script.
+    Devanagari,
+    /// The Bengali script.
+    Bengali,
+    /// The Thai script.
+    Thai,
+    /// The Georgian alphabet.
+    Georgian,
+    /// The Korean Hangul script.
+    Hangul,
+    /// The Japanese Hiragana and Katakana scripts.
+    Kana,
+    /// Chinese characters, also used in Japanese and Korean.
+    Han,
+    /// Emoji and pictographic symbols.
+    Emoji,
+}
+
+impl FontScript {
+    /// Returns the script of a character, or `None` if the character is shared between scripts, such as digits,
+    /// punctuation and whitespace.
+    pub fn of(c: char) -> Option<Self> {
+        Some(match c as u32 {
+            0x0041..=0x005A | 0x0061..=0x007A | 0x00C0..=0x024F | 0x1E00..=0x1EFF => Self::Latin,
+            0x0370..=0x03FF | 0x1F00..=0x1FFF => Self::Greek,
+            0x0400..=0x052F => Self::Cyrillic,
+            0x0530..=0x058F => Self::Armenian,
+            0x0590..=0x05FF => Self::Hebrew,
+            0x0600..=0x06FF | 0x0750..=0x077F | 0xFB50..=0xFDFF | 0xFE70..=0xFEFF => Self::Arabic,
+            0x0900..=0x097F => Self::Devanagari,
+            0x0980..=0x09FF => Self::Bengali,
+            0x0E00..=0x0E7F => Self::Thai,
+            0x10A0..=0x10FF => Self::Georgian,
+            0x1100..=0x11FF | 0x3130..=0x318F | 0xAC00..=0xD7AF => Self::Hangul,
+            0x3040..=0x30FF | 0x31F0..=0x31FF => Self::Kana,
+            0x2E80..=0x2FDF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF => Self::Han,
+            0x20000..=0x3134F => Self::Han,
+            // The emoji presentation selector and the combining keycap are included so that emoji sequences built
+            // from characters of other scripts, such as keycaps, fall back to an emoji font.
+            0x2600..=0x27BF | 0xFE0F | 0x20E3 | 0x1F000..=0x1FAFF => Self::Emoji,
+            _ => return None,
+        })
+    }
+
+    // A character of the script, used to find a system font which covers the script.
+    #[cfg(feature = "system-fonts")]
+    fn sample(self) -> char {
+        match self {
+            Self::Latin => 'a',
+            Self::Greek => 'α',
+            Self::Cyrillic => 'ж',
+            Self::Armenian => 'ա',
+            Self::Hebrew => 'א',
+            Self::Arabic => 'ع',
+            Self::Devanagari => 'क',
+            Self::Bengali => 'ক',
+            Self::Thai => 'ก',
+            Self::Georgian => 'ა',
+            Self::Hangul => '한',
+            Self::Kana => 'あ',
+            Self::Han => '中',
+            Self::Emoji => '😀',
+        }
+    }
+}
+
+// The chain of font families used for the characters of a text which its own font families don't cover.
+pub(crate) struct FontFallback {
+    // The fallback font families in the order they were registered, with the script they're used for. Families
+    // without a script are used for every script.
+    families: Vec<(Option<FontScript>, String)>,
+    // The system font families found for scripts without a registered fallback font.
+    #[cfg(feature = "system-fonts")]
+    system_families: HashMap<FontScript, Option<String>>,
+    #[cfg(feature = "system-fonts")]
+    font_manager: FontMgr,
+}
+
+impl Default for FontFallback {
+    fn default() -> Self {
+        let mut font_fallback = Self {
+            families: Vec::new(),
+            #[cfg(feature = "system-fonts")]
+            system_families: HashMap::new(),
+            #[cfg(feature = "system-fonts")]
+            font_manager: FontMgr::default(),
+        };
+
+        // The color emoji font which ships with each platform.
+        let emoji_family = if cfg!(any(target_os = "macos", target_os = "ios")) {
+            "Apple Color Emoji"
+        } else if cfg!(target_os = "windows") {
+            "Segoe UI Emoji"
+        } else {
+            "Noto Color Emoji"
+        };
+
+        font_fallback.add(Some(FontScript::Emoji), emoji_family);
+
+        font_fallback
+    }
+}
+
+impl FontFallback {
+    pub(crate) fn add(&mut self, script: Option<FontScript>, family: &str) {
+        self.families.push((script, family.to_owned()));
+    }
+
+    // Returns the font families of a text followed by the fallback families for the scripts used in the text.
+    pub(crate) fn families(&mut self, text: &str, families: &[FamilyOwned]) -> Vec<String> {
+        let mut scripts = Vec::new();
+        for script in text.chars().filter_map(FontScript::of) {
+            if !scripts.contains(&script) {
+                scripts.push(script);
+            }
+        }
+
+        let mut result: Vec<String> =
+            families.iter().map(|family| family.as_ref().to_owned()).collect();
+        for (script, family) in self.families.iter() {
+            if script.is_none_or(|script| scripts.contains(&script)) && !result.contains(family) {
+                result.push(family.clone());
+            }
+        }
+
+        #[cfg(feature = "system-fonts")]
+        for script in scripts {
+            if self.families.iter().any(|(registered, _)| *registered == Some(script)) {
+                continue;
+            }
+
+            let font_manager = &self.font_manager;
+            let system_family = self
+                .system_families
+                .entry(script)
+                .or_insert_with(|| find_system_family(font_manager, script));
+
+            if let Some(family) = system_family {
+                if !result.contains(family) {
+                    result.push(family.clone());
+                }
+            }
+        }
+
+        result
+    }
+}
+
+// Finds a system font family which covers a script, first by asking the platform and then by enumerating the
+// installed font families.
+#[cfg(feature = "system-fonts")]
+fn find_system_family(font_manager: &FontMgr, script: FontScript) -> Option<String> {
+    let sample = script.sample() as i32;
+
+    font_manager
+        .match_family_style_character("", FontStyle::default(), &[], sample)
+        .map(|typeface| typeface.family_name())
+        .or_else(|| {
+            font_manager.family_names().find(|family| {
+                font_manager
+                    .match_family_style(family, FontStyle::default())
+                    .is_some_and(|typeface| typeface.unichar_to_glyph(sample) != 0)
+            })
+        })
+}
@@ -1,3 +1,5 @@
+use hashbrown::HashMap;
+
 use crate::entity::Entity;
 use crate::prelude::Style;
 use crate::style::{Abilities, Display};
@@ -45,6 +47,30 @@ pub(crate) fn is_navigatable(
         .unwrap_or(false)
 }
 
+/// Returns the window containing an entity, which is the entity itself if it is a window.
+pub(crate) fn window_of(tree: &Tree<Entity>, entity: Entity) -> Entity {
+    if tree.is_window(entity) {
+        entity
+    } else {
+        tree.get_parent_window(entity).unwrap_or(Entity::root())
+    }
+}
+
+/// Returns the subtree which keyboard navigation from the focused entity is locked to, which is the innermost of the
+/// focus scope on top of the stack of the window containing the focused entity and the nearest ancestor with the lock
+/// focus flag set.
+pub(crate) fn focus_lock(
+    tree: &Tree<Entity>,
+    focus_scopes: &HashMap<Entity, Vec<(Entity, Entity)>>,
+    focused: Entity,
+) -> Entity {
+    let lock_focus_to = tree.lock_focus_within(focused);
+    match focus_scopes.get(&window_of(tree, focused)).and_then(|scopes| scopes.last()) {
+        Some((scope, _)) if !lock_focus_to.is_descendant_of(tree, *scope) => *scope,
+        _ => lock_focus_to,
+    }
+}
+
 /// Get the next entity to be focused during forward keyboard navigation.
 pub(crate) fn focus_forward(
     tree: &Tree<Entity>,
@@ -92,3 +118,38 @@ pub(crate) fn focus_backward(
     iter.next_back();
     iter.filter(|node| is_navigatable(tree, style, *node, lock_focus_to)).next_back()
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::context::test_support::{update, window_context};
+    use crate::prelude::*;
+
+    #[test]
+    fn focus_scopes_per_window() {
+        let mut cx = window_context(400, 300);
+
+        let outside = Element::new(cx.context()).navigable(true).entity();
+        let scope = VStack::new(cx.context(), |cx| {
+            Element::new(cx).navigable(true);
+        })
+        .entity();
+        let mut inside_window = Entity::null();
+        let window = VStack::new(cx.context(), |cx| {
+            inside_window = Element::new(cx).navigable(true).entity();
+        })
+        .entity();
+        cx.0.tree.set_window(window, true);
+        update(&mut cx);
+
+        cx.0.push_focus_scope(scope);
+
+        // The scope traps focus within the window it was pushed in, but not within other windows.
+        assert_eq!(crate::tree::focus_lock(&cx.0.tree, &cx.0.focus_scopes, outside), scope);
+        assert_eq!(
+            crate::tree::focus_lock(&cx.0.tree, &cx.0.focus_scopes, inside_window),
+            Entity::root()
+        );
+        assert_eq!(cx.0.focus_scopes.get(&Entity::root()).map(Vec::len), Some(1));
+        assert!(!cx.0.focus_scopes.contains_key(&window));
+    }
+}
@@ -0,0 +1,165 @@
+//! Undo and redo history for models and views.
+
+use std::collections::VecDeque;
+
+use web_time::{Duration, Instant};
+
+/// Events used to step through the undo history of a model or view.
+///
+/// Models which keep an [`UndoManager`] should respond to these events. A focused [`Textbox`](crate::prelude::Textbox)
+/// responds to them while it is being edited and consumes the event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UndoEvent {
+    /// Restore the state before the last edit.
+    Undo,
+    /// Restore the state before the last undo.
+    Redo,
+}
+
+/// A history of state snapshots used to undo and redo edits.
+///
+/// The state before each edit is recorded with [`UndoManager::record`]. Edits recorded in quick succession, such as
+/// typing, are grouped into a single undo step.
+///
+/// # Examples
+///
+/// ```
+/// # use vizia_core::prelude::*;
+/// #
+/// pub struct AppData {
+///     count: i32,
+///     history: UndoManager<i32>,
+/// }
+///
+/// enum AppEvent {
+///     Increment,
+/// }
+///
+/// impl Model for AppData {
+///     fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+///         event.map(|app_event, _| match app_event {
+///             AppEvent::Increment => {
+///                 self.history.record(&self.count);
+///                 self.count += 1;
+///             }
+///         });
+///
+///         event.map(|undo_event, _| match undo_event {
+///             UndoEvent::Undo => {
+///                 if let Some(count) = self.history.undo(&self.count) {
+///                     self.count = count;
+///                 }
+///             }
+///
+///             UndoEvent::Redo => {
+///                 if let Some(count) = self.history.redo(&self.count) {
+///                     self.count = count;
+///                 }
+///             }
+///         });
+///     }
+/// }
+/// ```
+pub struct UndoManager<S: Clone> {
+    undo_stack: VecDeque<S>,
+    redo_stack: Vec<S>,
+    max_depth: usize,
+    group_interval: Duration,
+    last_record: Option<Instant>,
+}
+
+impl<S: Clone> Default for UndoManager<S> {
+    fn default() -> Self {
+        Self::new(100)
+    }
+}
+
+impl<S: Clone> UndoManager<S> {
+    /// Creates a new [UndoManager] which keeps at most `max_depth` undo steps.
+    pub fn new(max_depth: usize) -> Self {
+        Self {
+            undo_stack: VecDeque::new(),
+            redo_stack: Vec::new(),
+            max_depth,
+            group_interval: Duration::from_millis(500),
+            last_record: None,
+        }
+    }
+
+    /// Sets the maximum time between two edits for them to be grouped into one undo step. Defaults to 500ms.
+    ///
+    /// A zero interval disables grouping.
+    pub fn with_group_interval(mut self, interval: Duration) -> Self {
+        self.group_interval = interval;
+        self
+    }
+
+    /// Sets the maximum number of undo steps kept in the history, dropping the oldest steps if necessary.
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+        while self.undo_stack.len() > self.max_depth {
+            self.undo_stack.pop_front();
+        }
+    }
+
+    /// Records the state before an edit and clears the redo history.
+    ///
+    /// If the previous edit was recorded within the group interval, the edits are grouped and the state is not
+    /// recorded again.
+    pub fn record(&mut self, state: &S) {
+        let now = Instant::now();
+        let grouped =
+            self.last_record.is_some_and(|last| now.duration_since(last) < self.group_interval)
+                && !self.undo_stack.is_empty();
+
+        self.last_record = Some(now);
+        self.redo_stack.clear();
+
+        if grouped || self.max_depth == 0 {
+            return;
+        }
+
+        self.undo_stack.push_back(state.clone());
+        if self.undo_stack.len() > self.max_depth {
+            self.undo_stack.pop_front();
+        }
+    }
+
+    /// Ends the current group of edits, so that the next recorded edit starts a new undo step.
+    pub fn break_group(&mut self) {
+        self.last_record = None;
+    }
+
+    /// Returns the state before the last undo step, moving the current state to the redo history.
+    pub fn undo(&mut self, current: &S) -> Option<S> {
+        let state = self.undo_stack.pop_back()?;
+        self.redo_stack.push(current.clone());
+        self.last_record = None;
+        Some(state)
+    }
+
+    /// Returns the state before the last undo, moving the current state to the undo history.
+    pub fn redo(&mut self, current: &S) -> Option<S> {
+        let state = self.redo_stack.pop()?;
+        self.undo_stack.push_back(current.clone());
+        self.last_record = None;
+        Some(state)
+    }
+
+    /// Returns true if there is an edit which can be undone.
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    /// Returns true if there is an undo which can be redone.
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Removes all undo and redo steps from the history.
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.last_record = None;
+    }
+}
@@ -16,6 +16,7 @@ use crate::accessibility::IntoNode;
 use crate::prelude::*;
 use crate::systems::get_access_node;
 use std::any::{Any, TypeId};
+use vizia_storage::LayoutTreeIterator;
 mod handle;
 pub use handle::Handle;
 use hashbrown::HashMap;
@@ -121,6 +122,13 @@ pub trait View: 'static + Sized {
         cx.style.add(id);
         cx.needs_redraw(id);
 
+        // The previous sibling may no longer match structural pseudo-classes such as `:last-child`.
+        if let Some(prev_sibling) = cx.tree.get_prev_layout_sibling(id) {
+            for entity in LayoutTreeIterator::subtree(&cx.tree, prev_sibling) {
+                cx.style.needs_restyle(entity);
+            }
+        }
+
         if let Some(element) = self.element() {
             cx.style.element.insert(id, fxhash::hash32(element));
         }
@@ -220,6 +228,14 @@ pub trait View: 'static + Sized {
     #[allow(unused_variables)]
     fn event(&mut self, cx: &mut EventContext, event: &mut Event) {}
 
+    /// Called immediately before the view is drawn.
+    ///
+    /// Unlike [`draw`](View::draw), this method has mutable access to the view, allowing last-moment updates such as
+    /// syncing a texture with the current state of the view. It is only called for views which are visible and are
+    /// not entirely clipped out.
+    #[allow(unused_variables)]
+    fn on_pre_draw(&mut self, cx: &mut DrawContext) {}
+
     /// Provides custom drawing for the view.
     ///
     /// Usually the look of a view is determined by the style and layout properties of the view. However, the `draw` method of
@@ -287,6 +303,10 @@ where
         <T as View>::event(self, cx, event);
     }
 
+    fn on_pre_draw(&mut self, cx: &mut DrawContext) {
+        <T as View>::on_pre_draw(self, cx);
+    }
+
     fn draw(&self, cx: &mut DrawContext, canvas: &Canvas) {
         <T as View>::draw(self, cx, canvas);
     }
@@ -73,20 +73,7 @@ impl<V> Handle<'_, V> {
     /// Stop the user from tabbing out of a subtree, which is useful for modal dialogs.
     pub fn lock_focus_to_within(self) -> Self {
         self.cx.tree.set_lock_focus_within(self.entity, true);
-        self.cx.focus_stack.push(self.cx.focused);
-        if !self.cx.focused.is_descendant_of(&self.cx.tree, self.entity) {
-            let new_focus = vizia_storage::TreeIterator::subtree(&self.cx.tree, self.entity)
-                .find(|node| {
-                    crate::tree::is_navigatable(
-                        &self.cx.tree,
-                        &self.cx.style,
-                        *node,
-                        Entity::root(),
-                    )
-                })
-                .unwrap_or(self.cx.focus_stack.pop().unwrap());
-            self.cx.with_current(new_focus, |cx| cx.focus());
-        }
+        self.cx.push_focus_scope(self.entity);
         self
     }
 
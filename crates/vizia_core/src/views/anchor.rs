@@ -0,0 +1,459 @@
+//! Positioning of views, such as popups and tooltips, against an anchor view.
+//!
+//! A view is placed on a side of its anchor, flipped to a placement which fits within the window if there isn't enough
+//! room for it, and shifted along the anchor if no placement fits.
+use bitflags::bitflags;
+
+use crate::prelude::*;
+use crate::vg;
+
+/// The position of a view placed against an anchor.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct AnchoredPosition {
+    /// The placement of the view after it has been flipped to fit within the window.
+    pub placement: Placement,
+    /// The offset of the top-left corner of the view from the top-left corner of the anchor, in physical pixels.
+    pub offset: (f32, f32),
+    /// The distance along the edge of the view which faces the anchor to the point closest to the center of the anchor,
+    /// in physical pixels, which an arrow should point from.
+    pub arrow_offset: f32,
+}
+
+/// Positions a view of the given size against the anchor, separated from it by the gap.
+///
+/// When `reposition` is true the view is flipped and shifted to keep it within the window, otherwise the view is
+/// placed as requested.
+pub(crate) fn position_anchored(
+    placement: Placement,
+    anchor: BoundingBox,
+    width: f32,
+    height: f32,
+    window: BoundingBox,
+    gap: f32,
+    reposition: bool,
+) -> AnchoredPosition {
+    if matches!(placement, Placement::Over | Placement::Cursor) {
+        return AnchoredPosition { placement, offset: (0.0, 0.0), arrow_offset: 0.0 };
+    }
+
+    let mut placement = placement;
+    if reposition {
+        let mut available = AvailablePlacement::empty();
+        for index in 0..12 {
+            let candidate = Placement::from_int(index);
+            available.set(
+                AvailablePlacement::of(candidate),
+                window.contains(&placed_bounds(candidate, anchor, width, height, gap)),
+            );
+        }
+
+        placement = if available.is_empty() {
+            // Nothing fits entirely, so use the side of the anchor with the most room and shift the view along it.
+            let opposite = placement.opposite();
+            let room = |placement| main_axis_room(placement, anchor, window);
+            if room(opposite) > room(placement) {
+                opposite
+            } else {
+                placement
+            }
+        } else {
+            placement.place(available)
+        };
+    }
+
+    let mut bounds = placed_bounds(placement, anchor, width, height, gap);
+
+    if reposition {
+        if placement.is_vertical() {
+            bounds.x += shift_within(bounds.left(), bounds.right(), window.left(), window.right());
+        } else {
+            bounds.y += shift_within(bounds.top(), bounds.bottom(), window.top(), window.bottom());
+        }
+    }
+
+    // Keep the arrow away from the corners of the view.
+    let (center, start, extent) = if placement.is_vertical() {
+        (anchor.center().0, bounds.left(), bounds.width())
+    } else {
+        (anchor.center().1, bounds.top(), bounds.height())
+    };
+    let margin = (2.0 * gap).min(extent / 2.0);
+    let arrow_offset = (center - start).clamp(margin, extent - margin);
+
+    AnchoredPosition {
+        placement,
+        offset: (bounds.left() - anchor.left(), bounds.top() - anchor.top()),
+        arrow_offset,
+    }
+}
+
+// Returns the bounds of a view of the given size placed against the anchor, separated from it by the gap.
+fn placed_bounds(
+    placement: Placement,
+    anchor: BoundingBox,
+    width: f32,
+    height: f32,
+    gap: f32,
+) -> BoundingBox {
+    let x = match placement {
+        Placement::TopStart | Placement::BottomStart | Placement::Over | Placement::Cursor => {
+            anchor.left()
+        }
+        Placement::Top | Placement::Bottom => anchor.center().0 - width / 2.0,
+        Placement::TopEnd | Placement::BottomEnd => anchor.right() - width,
+        Placement::LeftStart | Placement::Left | Placement::LeftEnd => anchor.left() - width - gap,
+        Placement::RightStart | Placement::Right | Placement::RightEnd => anchor.right() + gap,
+    };
+
+    let y = match placement {
+        Placement::TopStart | Placement::Top | Placement::TopEnd => anchor.top() - height - gap,
+        Placement::BottomStart | Placement::Bottom | Placement::BottomEnd => anchor.bottom() + gap,
+        Placement::LeftStart | Placement::RightStart | Placement::Over | Placement::Cursor => {
+            anchor.top()
+        }
+        Placement::Left | Placement::Right => anchor.center().1 - height / 2.0,
+        Placement::LeftEnd | Placement::RightEnd => anchor.bottom() - height,
+    };
+
+    BoundingBox::from_min_max(x, y, x + width, y + height)
+}
+
+// Returns the space between the anchor and the edge of the window on the side of the placement.
+fn main_axis_room(placement: Placement, anchor: BoundingBox, window: BoundingBox) -> f32 {
+    match placement {
+        Placement::TopStart | Placement::Top | Placement::TopEnd => anchor.top() - window.top(),
+        Placement::BottomStart | Placement::Bottom | Placement::BottomEnd => {
+            window.bottom() - anchor.bottom()
+        }
+        Placement::LeftStart | Placement::Left | Placement::LeftEnd => {
+            anchor.left() - window.left()
+        }
+        Placement::RightStart | Placement::Right | Placement::RightEnd => {
+            window.right() - anchor.right()
+        }
+        Placement::Over | Placement::Cursor => 0.0,
+    }
+}
+
+// Returns the distance to move a span so that it lies within the limits, aligning it with the start of the limits
+// if it's larger than them.
+fn shift_within(start: f32, end: f32, min: f32, max: f32) -> f32 {
+    if end - start > max - min || start < min {
+        min - start
+    } else if end > max {
+        max - end
+    } else {
+        0.0
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Clone, Copy)]
+    pub(crate) struct AvailablePlacement: u16 {
+        const TOP_START = 1 << 0;
+        const TOP = 1 << 1;
+        const TOP_END = 1 << 2;
+        const LEFT_START = 1 << 3;
+        const LEFT = 1 << 4;
+        const LEFT_END = 1 << 5;
+        const BOTTOM_START = 1 << 6;
+        const BOTTOM = 1 << 7;
+        const BOTTOM_END = 1 << 8;
+        const RIGHT_START = 1 << 9;
+        const RIGHT = 1 << 10;
+        const RIGHT_END = 1 << 11;
+    }
+}
+
+impl AvailablePlacement {
+    fn of(placement: Placement) -> Self {
+        match placement {
+            Placement::Bottom => AvailablePlacement::BOTTOM,
+            Placement::BottomStart => AvailablePlacement::BOTTOM_START,
+            Placement::BottomEnd => AvailablePlacement::BOTTOM_END,
+            Placement::Top => AvailablePlacement::TOP,
+            Placement::TopStart => AvailablePlacement::TOP_START,
+            Placement::TopEnd => AvailablePlacement::TOP_END,
+            Placement::Left => AvailablePlacement::LEFT,
+            Placement::LeftStart => AvailablePlacement::LEFT_START,
+            Placement::LeftEnd => AvailablePlacement::LEFT_END,
+            Placement::Right => AvailablePlacement::RIGHT,
+            Placement::RightStart => AvailablePlacement::RIGHT_START,
+            Placement::RightEnd => AvailablePlacement::RIGHT_END,
+            _ => AvailablePlacement::empty(),
+        }
+    }
+
+    fn can_place(&self, placement: Placement) -> bool {
+        let flag = AvailablePlacement::of(placement);
+        !flag.is_empty() && self.contains(flag)
+    }
+}
+
+impl Placement {
+    fn from_int(int: u16) -> Placement {
+        match int {
+            0 => Placement::TopStart,
+            1 => Placement::Top,
+            2 => Placement::TopEnd,
+            3 => Placement::BottomStart,
+            4 => Placement::Bottom,
+            5 => Placement::BottomEnd,
+            6 => Placement::RightStart,
+            7 => Placement::Right,
+            8 => Placement::RightEnd,
+            9 => Placement::LeftStart,
+            10 => Placement::Left,
+            11 => Placement::LeftEnd,
+            12 => Placement::Over,
+            _ => Placement::Cursor,
+        }
+    }
+
+    // Returns whether the placement is above or below the anchor.
+    fn is_vertical(&self) -> bool {
+        matches!(
+            self,
+            Placement::TopStart
+                | Placement::Top
+                | Placement::TopEnd
+                | Placement::BottomStart
+                | Placement::Bottom
+                | Placement::BottomEnd
+        )
+    }
+
+    // Returns the placement on the opposite side of the anchor with the same alignment.
+    fn opposite(&self) -> Placement {
+        match self {
+            Placement::TopStart => Placement::BottomStart,
+            Placement::Top => Placement::Bottom,
+            Placement::TopEnd => Placement::BottomEnd,
+            Placement::BottomStart => Placement::TopStart,
+            Placement::Bottom => Placement::Top,
+            Placement::BottomEnd => Placement::TopEnd,
+            Placement::LeftStart => Placement::RightStart,
+            Placement::Left => Placement::Right,
+            Placement::LeftEnd => Placement::RightEnd,
+            Placement::RightStart => Placement::LeftStart,
+            Placement::Right => Placement::Left,
+            Placement::RightEnd => Placement::LeftEnd,
+            _ => *self,
+        }
+    }
+
+    pub(crate) fn place(&self, available: AvailablePlacement) -> Placement {
+        if *self == Placement::Over || *self == Placement::Cursor {
+            return *self;
+        }
+
+        if available.is_empty() {
+            return Placement::Over;
+        }
+
+        let mut placement = *self;
+
+        while !available.can_place(placement) {
+            placement = placement.next(*self);
+        }
+
+        placement
+    }
+
+    fn next(&self, original: Self) -> Self {
+        const TOP_START: [u16; 12] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+        const TOP: [u16; 12] = [2, 0, 4, 5, 3, 7, 8, 6, 10, 11, 9, 12];
+        const TOP_END: [u16; 12] = [5, 0, 1, 8, 3, 4, 11, 6, 7, 12, 9, 10];
+        const BOTTOM_START: [u16; 12] = [1, 2, 6, 4, 5, 0, 7, 8, 9, 10, 11, 12];
+        const BOTTOM: [u16; 12] = [2, 0, 7, 5, 3, 1, 8, 6, 10, 11, 9, 12];
+        const BOTTOM_END: [u16; 12] = [8, 0, 1, 2, 3, 4, 11, 6, 7, 12, 9, 10];
+        const LEFT_START: [u16; 12] = [1, 2, 12, 4, 5, 0, 7, 8, 3, 10, 11, 6];
+        const LEFT: [u16; 12] = [2, 0, 12, 5, 3, 1, 8, 6, 4, 11, 9, 7];
+        const LEFT_END: [u16; 12] = [12, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+        const RIGHT_START: [u16; 12] = [1, 2, 12, 4, 5, 0, 7, 8, 9, 10, 11, 3];
+        const RIGHT: [u16; 12] = [2, 0, 12, 5, 3, 1, 8, 6, 10, 11, 9, 4];
+        const RIGHT_END: [u16; 12] = [12, 0, 1, 2, 3, 4, 11, 6, 7, 5, 9, 10];
+
+        let states = match original {
+            Placement::TopStart => TOP_START,
+            Placement::Top => TOP,
+            Placement::TopEnd => TOP_END,
+            Placement::BottomStart => BOTTOM_START,
+            Placement::Bottom => BOTTOM,
+            Placement::BottomEnd => BOTTOM_END,
+            Placement::RightStart => RIGHT_START,
+            Placement::Right => RIGHT,
+            Placement::RightEnd => RIGHT_END,
+            Placement::LeftStart => LEFT_START,
+            Placement::Left => LEFT,
+            Placement::LeftEnd => LEFT_END,
+            _ => unreachable!(),
+        };
+
+        Placement::from_int(states[*self as usize])
+    }
+}
+
+/// An arrow which points from a popup or tooltip to its anchor.
+pub(crate) struct Arrow {
+    placement: Placement,
+}
+
+impl Arrow {
+    /// Creates an arrow bound to the placement of the view it's within, the offset of the arrow along the edge of the
+    /// view in logical pixels, and the size of the arrow.
+    pub(crate) fn new(
+        cx: &mut Context,
+        placement: impl Lens<Target = Placement>,
+        offset: impl Lens<Target = f32>,
+        size: impl Lens<Target = Length>,
+    ) -> Handle<Self> {
+        Self { placement: Placement::Over }
+            .build(cx, |_| {})
+            .position_type(PositionType::Absolute)
+            .hoverable(false)
+            .bind(placement, move |handle, placement| {
+                let placement = placement.get(&handle);
+
+                let (t, b) = match placement {
+                    Placement::TopStart | Placement::Top | Placement::TopEnd => {
+                        (Percentage(100.0), Stretch(1.0))
+                    }
+                    Placement::BottomStart | Placement::Bottom | Placement::BottomEnd => {
+                        (Stretch(1.0), Percentage(100.0))
+                    }
+                    _ => (Stretch(1.0), Stretch(1.0)),
+                };
+
+                let (l, r) = match placement {
+                    Placement::LeftStart | Placement::Left | Placement::LeftEnd => {
+                        (Percentage(100.0), Stretch(1.0))
+                    }
+                    Placement::RightStart | Placement::Right | Placement::RightEnd => {
+                        (Stretch(1.0), Percentage(100.0))
+                    }
+                    _ => (Stretch(1.0), Stretch(1.0)),
+                };
+
+                handle
+                    .modify(|arrow| arrow.placement = placement)
+                    .top(t)
+                    .bottom(b)
+                    .left(l)
+                    .right(r)
+                    .bind(offset, move |handle, offset| {
+                        // Move the arrow along the edge of the view to point at the anchor.
+                        let offset = Pixels(offset.get(&handle));
+                        if placement.is_vertical() {
+                            handle.left(offset).right(Stretch(1.0));
+                        } else if placement != Placement::Over && placement != Placement::Cursor {
+                            handle.top(offset).bottom(Stretch(1.0));
+                        }
+                    })
+                    .bind(size, move |handle, arrow_size| {
+                        let arrow_size = arrow_size.get(&handle).to_px().unwrap_or(8.0);
+                        let (w, h) = if placement.is_vertical() {
+                            (Pixels(arrow_size * 2.0), Pixels(arrow_size))
+                        } else {
+                            (Pixels(arrow_size), Pixels(arrow_size * 2.0))
+                        };
+
+                        handle.width(w).height(h);
+                    });
+            })
+    }
+}
+
+impl View for Arrow {
+    fn element(&self) -> Option<&'static str> {
+        Some("arrow")
+    }
+
+    fn draw(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        let bounds = cx.bounds();
+        let mut path = vg::Path::new();
+        match self.placement {
+            Placement::Bottom | Placement::BottomStart | Placement::BottomEnd => {
+                path.move_to(bounds.bottom_left());
+                path.line_to(bounds.center_top());
+                path.line_to(bounds.bottom_right());
+                path.line_to(bounds.bottom_left());
+            }
+
+            Placement::Top | Placement::TopStart | Placement::TopEnd => {
+                path.move_to(bounds.top_left());
+                path.line_to(bounds.center_bottom());
+                path.line_to(bounds.top_right());
+                path.line_to(bounds.top_left());
+            }
+
+            Placement::Left | Placement::LeftStart | Placement::LeftEnd => {
+                path.move_to(bounds.top_left());
+                path.line_to(bounds.center_right());
+                path.line_to(bounds.bottom_left());
+                path.line_to(bounds.top_left());
+            }
+
+            Placement::Right | Placement::RightStart | Placement::RightEnd => {
+                path.move_to(bounds.top_right());
+                path.line_to(bounds.center_left());
+                path.line_to(bounds.bottom_right());
+                path.line_to(bounds.top_right());
+            }
+
+            _ => {}
+        }
+        path.close();
+
+        let bg = cx.background_color();
+        let mut paint = vg::Paint::default();
+        paint.set_color(bg);
+        canvas.draw_path(&path, &paint);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: BoundingBox = BoundingBox { x: 0.0, y: 0.0, w: 400.0, h: 300.0 };
+
+    #[test]
+    fn places_below_anchor() {
+        let anchor = BoundingBox { x: 100.0, y: 100.0, w: 50.0, h: 20.0 };
+        let position = position_anchored(Placement::Bottom, anchor, 100.0, 50.0, WINDOW, 4.0, true);
+
+        assert_eq!(position.placement, Placement::Bottom);
+        assert_eq!(position.offset, (-25.0, 24.0));
+        assert_eq!(position.arrow_offset, 50.0);
+    }
+
+    #[test]
+    fn flips_at_window_edge() {
+        let anchor = BoundingBox { x: 100.0, y: 260.0, w: 50.0, h: 20.0 };
+        let position = position_anchored(Placement::Bottom, anchor, 100.0, 50.0, WINDOW, 4.0, true);
+
+        assert_eq!(position.placement, Placement::Top);
+        assert_eq!(position.offset, (-25.0, -54.0));
+    }
+
+    #[test]
+    fn shifts_when_nothing_fits() {
+        let anchor = BoundingBox { x: 0.0, y: 100.0, w: 20.0, h: 20.0 };
+        let position = position_anchored(Placement::Bottom, anchor, 500.0, 50.0, WINDOW, 4.0, true);
+
+        assert_eq!(position.placement, Placement::Bottom);
+        assert_eq!(position.offset, (0.0, 24.0));
+        assert_eq!(position.arrow_offset, 10.0);
+    }
+
+    #[test]
+    fn keeps_placement_without_reposition() {
+        let anchor = BoundingBox { x: 100.0, y: 260.0, w: 50.0, h: 20.0 };
+        let position =
+            position_anchored(Placement::Bottom, anchor, 100.0, 50.0, WINDOW, 4.0, false);
+
+        assert_eq!(position.placement, Placement::Bottom);
+        assert_eq!(position.offset, (-25.0, 24.0));
+    }
+}
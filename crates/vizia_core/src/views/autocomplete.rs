@@ -0,0 +1,257 @@
+use std::ops::Deref;
+
+use crate::prelude::*;
+use crate::views::textbox::TextEdited;
+
+/// A view which shows a popup of suggestions below the textbox within it, filtered by the text typed into it.
+///
+/// The suggestions which contain the text, ignoring case, are shown while the textbox is edited. The arrow keys
+/// highlight a suggestion and the enter key, or pressing a suggestion, commits it by replacing the text of the
+/// textbox, which triggers the `on_edit` and `on_submit` callbacks of the textbox. The escape key closes the popup.
+/// The textbox keeps keyboard focus while the popup is open.
+///
+/// # Example
+/// ```
+/// # use vizia_core::prelude::*;
+/// #
+/// # #[derive(Lens)]
+/// # struct AppData {
+/// #     fruit: String,
+/// #     fruits: Vec<String>,
+/// # }
+/// #
+/// # impl Model for AppData {}
+/// #
+/// # enum AppEvent {
+/// #     SetFruit(String),
+/// # }
+/// #
+/// # let cx = &mut Context::default();
+/// #
+/// # AppData { fruit: String::new(), fruits: vec![String::from("Apple")] }.build(cx);
+/// #
+/// Autocomplete::new(cx, AppData::fruits, |cx| {
+///     Textbox::new(cx, AppData::fruit).on_edit(|cx, text| cx.emit(AppEvent::SetFruit(text)));
+/// });
+/// ```
+#[derive(Lens)]
+pub struct Autocomplete {
+    options: Vec<String>,
+    query: String,
+    matches: Vec<String>,
+    highlighted: Option<usize>,
+    is_open: bool,
+    // Whether the textbox is being edited.
+    #[lens(ignore)]
+    editing: bool,
+    // The textbox within the view, which is known once it has been edited.
+    #[lens(ignore)]
+    textbox: Entity,
+    #[lens(ignore)]
+    on_select: Option<Box<dyn Fn(&mut EventContext, &str)>>,
+}
+
+enum AutocompleteEvent {
+    Commit(usize),
+}
+
+impl Autocomplete {
+    /// Creates a new [Autocomplete] view with a list of suggestions and content containing a [Textbox].
+    pub fn new<L, T>(
+        cx: &mut Context,
+        suggestions: L,
+        content: impl FnOnce(&mut Context),
+    ) -> Handle<Self>
+    where
+        L: Lens<Target: Deref<Target = [T]> + Data>,
+        T: 'static + ToString,
+    {
+        Self {
+            options: Vec::new(),
+            query: String::new(),
+            matches: Vec::new(),
+            highlighted: None,
+            is_open: false,
+            editing: false,
+            textbox: Entity::null(),
+            on_select: None,
+        }
+        .build(cx, |cx| {
+            // Handle the keys and presses of the popup before they reach the textbox, which captures the mouse while
+            // it's edited.
+            cx.add_listener(|autocomplete: &mut Self, cx, event| {
+                if !autocomplete.is_open {
+                    return;
+                }
+
+                event.map(|window_event, meta| match window_event {
+                    WindowEvent::KeyDown(code, _) if meta.target == autocomplete.textbox => {
+                        let len = autocomplete.matches.len();
+                        match code {
+                            Code::ArrowDown => {
+                                autocomplete.highlighted = Some(
+                                    autocomplete.highlighted.map_or(0, |index| (index + 1) % len),
+                                );
+                                meta.consume();
+                            }
+
+                            Code::ArrowUp => {
+                                autocomplete.highlighted = Some(
+                                    autocomplete
+                                        .highlighted
+                                        .map_or(len - 1, |index| (index + len - 1) % len),
+                                );
+                                meta.consume();
+                            }
+
+                            Code::Enter | Code::NumpadEnter => {
+                                if let Some(index) = autocomplete.highlighted {
+                                    cx.emit(AutocompleteEvent::Commit(index));
+                                    meta.consume();
+                                }
+                            }
+
+                            Code::Escape => {
+                                autocomplete.is_open = false;
+                                meta.consume();
+                            }
+
+                            _ => {}
+                        }
+                    }
+
+                    WindowEvent::MouseDown(MouseButton::Left) => {
+                        let suggestion = cx.hovered.parent_iter(cx.tree).find_map(|entity| {
+                            cx.views.get(&entity)?.downcast_ref::<Suggestion>().map(|s| s.index)
+                        });
+
+                        if let Some(index) = suggestion {
+                            if cx.hovered.is_descendant_of(cx.tree, cx.current) {
+                                cx.emit(AutocompleteEvent::Commit(index));
+                                meta.consume();
+                            }
+                        }
+                    }
+
+                    _ => {}
+                });
+            });
+
+            (content)(cx);
+
+            Binding::new(cx, Autocomplete::is_open, |cx, is_open| {
+                if is_open.get(cx) {
+                    Popup::new(cx, |cx| {
+                        Binding::new(cx, Autocomplete::matches, |cx, matches| {
+                            for (index, suggestion) in matches.get(cx).into_iter().enumerate() {
+                                Suggestion { index }
+                                    .build(cx, |cx| {
+                                        Label::new(cx, suggestion);
+                                    })
+                                    .role(Role::ListBoxOption)
+                                    .checked(
+                                        Autocomplete::highlighted
+                                            .map(move |highlighted| *highlighted == Some(index)),
+                                    );
+                            }
+                        });
+                    })
+                    .role(Role::ListBox)
+                    .show_arrow(false)
+                    .should_reposition(false)
+                    .trap_focus(false);
+                }
+            });
+        })
+        .bind(
+            suggestions.map(|suggestions| {
+                suggestions.iter().map(|suggestion| suggestion.to_string()).collect::<Vec<_>>()
+            }),
+            |handle, options| {
+                let options = options.get(&handle);
+                handle.modify(|autocomplete| {
+                    autocomplete.options = options;
+                    autocomplete.update_matches();
+                });
+            },
+        )
+    }
+
+    // Filters the suggestions which contain the query, leaving out a suggestion which matches it exactly, and opens
+    // the popup while the textbox is edited if there are any.
+    fn update_matches(&mut self) {
+        let query = self.query.to_lowercase();
+        self.matches = self
+            .options
+            .iter()
+            .filter(|option| {
+                let option = option.to_lowercase();
+                option.contains(&query) && option != query
+            })
+            .cloned()
+            .collect();
+        self.highlighted = None;
+        self.is_open = self.editing && !query.is_empty() && !self.matches.is_empty();
+    }
+}
+
+impl View for Autocomplete {
+    fn element(&self) -> Option<&'static str> {
+        Some("autocomplete")
+    }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.map(|text_edited: &TextEdited, meta| {
+            self.textbox = meta.origin;
+            self.editing = true;
+            self.query.clone_from(&text_edited.0);
+            self.update_matches();
+            meta.consume();
+        });
+
+        event.map(|text_event, _| {
+            if let TextEvent::EndEdit = text_event {
+                self.editing = false;
+                self.is_open = false;
+            }
+        });
+
+        event.map(|autocomplete_event, meta| match autocomplete_event {
+            AutocompleteEvent::Commit(index) => {
+                if let Some(suggestion) = self.matches.get(*index).cloned() {
+                    cx.emit_to(self.textbox, TextEvent::SelectAll);
+                    cx.emit_to(self.textbox, TextEvent::InsertText(suggestion.clone()));
+                    cx.emit_to(self.textbox, TextEvent::Submit(true));
+                    self.is_open = false;
+
+                    if let Some(callback) = &self.on_select {
+                        (callback)(cx, &suggestion);
+                    }
+                }
+
+                meta.consume();
+            }
+        });
+    }
+}
+
+impl Handle<'_, Autocomplete> {
+    /// Sets the callback triggered when a suggestion is committed, which is passed the text of the suggestion.
+    pub fn on_select<F>(self, callback: F) -> Self
+    where
+        F: 'static + Fn(&mut EventContext, &str),
+    {
+        self.modify(|autocomplete| autocomplete.on_select = Some(Box::new(callback)))
+    }
+}
+
+// A suggestion within the popup of an autocomplete.
+struct Suggestion {
+    index: usize,
+}
+
+impl View for Suggestion {
+    fn element(&self) -> Option<&'static str> {
+        Some("suggestion")
+    }
+}
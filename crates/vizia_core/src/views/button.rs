@@ -120,16 +120,6 @@ impl View for Button {
                 }
             }
 
-            WindowEvent::ActionRequest(action) => match action.action {
-                Action::Click => {
-                    if let Some(action) = &self.action {
-                        (action)(cx);
-                    }
-                }
-
-                _ => {}
-            },
-
             _ => {}
         });
     }
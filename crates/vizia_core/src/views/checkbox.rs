@@ -1,6 +1,35 @@
-use crate::icons::ICON_CHECK;
+use crate::icons::{ICON_CHECK, ICON_MINUS};
 use crate::prelude::*;
 
+/// The state of a tri-state [Checkbox].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Data)]
+pub enum CheckState {
+    /// The checkbox is not checked.
+    #[default]
+    Unchecked,
+    /// The checkbox is checked.
+    Checked,
+    /// The checkbox is neither checked nor unchecked, such as a checkbox which selects all of a list when only some
+    /// of the list is selected.
+    Indeterminate,
+}
+
+impl From<bool> for CheckState {
+    fn from(checked: bool) -> Self {
+        if checked {
+            CheckState::Checked
+        } else {
+            CheckState::Unchecked
+        }
+    }
+}
+
+impl From<Option<bool>> for CheckState {
+    fn from(checked: Option<bool>) -> Self {
+        checked.map(CheckState::from).unwrap_or(CheckState::Indeterminate)
+    }
+}
+
 /// A checkbox used to display and toggle a boolean state.
 ///
 /// Pressing the checkbox triggers the [`on_toggle`](Checkbox::on_toggle) callback.
@@ -205,6 +234,69 @@ impl Checkbox {
             .navigable(true)
     }
 
+    /// Creates a new tri-state checkbox, which is bound to a [`CheckState`] or to an `Option<bool>` where `None` is the
+    /// indeterminate state.
+    ///
+    /// The indeterminate state shows a dash and can be styled with the `:indeterminate` pseudo-class.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use vizia_core::prelude::*;
+    /// #
+    /// # #[derive(Lens)]
+    /// # struct AppData {
+    /// #     selected: Vec<bool>,
+    /// # }
+    /// #
+    /// # impl Model for AppData {}
+    /// #
+    /// # enum AppEvent {
+    /// #     ToggleAll,
+    /// # }
+    /// #
+    /// # let cx = &mut Context::default();
+    /// #
+    /// # AppData { selected: vec![true, false] }.build(cx);
+    /// #
+    /// let all_selected = AppData::selected.map(|selected| {
+    ///     if selected.iter().all(|s| *s) {
+    ///         Some(true)
+    ///     } else if selected.iter().any(|s| *s) {
+    ///         None
+    ///     } else {
+    ///         Some(false)
+    ///     }
+    /// });
+    ///
+    /// Checkbox::tristate(cx, all_selected)
+    ///     .on_toggle(|cx| cx.emit(AppEvent::ToggleAll));
+    /// ```
+    pub fn tristate<L, T>(cx: &mut Context, state: L) -> Handle<Self>
+    where
+        L: Lens<Target = T>,
+        T: Clone + Into<CheckState>,
+    {
+        let state = state.map(|state| state.clone().into());
+
+        Self { on_toggle: None }
+            .build(cx, |cx| {
+                Binding::new(cx, state, |cx, state| match state.get(cx) {
+                    CheckState::Checked => {
+                        Svg::new(cx, ICON_CHECK);
+                    }
+                    CheckState::Indeterminate => {
+                        Svg::new(cx, ICON_MINUS);
+                    }
+                    CheckState::Unchecked => {}
+                })
+            })
+            .checked(state.map(|state| *state == CheckState::Checked))
+            .indeterminate(state.map(|state| *state == CheckState::Indeterminate))
+            .role(Role::CheckBox)
+            .navigable(true)
+    }
+
     /// Creates a new checkbox in an intermediate state.
     pub fn intermediate(
         cx: &mut Context,
@@ -216,15 +308,19 @@ impl Checkbox {
             .bind(checked, move |handle, c| {
                 handle.bind(intermediate, move |handle, i| {
                     if c.get(&handle) {
-                        handle.text(ICON_CHECK).toggle_class("intermediate", false);
+                        handle
+                            .text(ICON_CHECK)
+                            .toggle_class("intermediate", false)
+                            .indeterminate(false);
                     } else if i.get(&handle) {
-                        handle.text("-").toggle_class("intermediate", true);
+                        handle.text("-").toggle_class("intermediate", true).indeterminate(true);
                     } else {
-                        handle.text("").toggle_class("intermediate", false);
+                        handle.text("").toggle_class("intermediate", false).indeterminate(false);
                     }
                 });
             })
             .checked(checked)
+            .role(Role::CheckBox)
             .navigable(true)
     }
 }
@@ -284,16 +380,6 @@ impl View for Checkbox {
                 }
             }
 
-            WindowEvent::ActionRequest(action) => match action.action {
-                Action::Click => {
-                    if let Some(callback) = &self.on_toggle {
-                        (callback)(cx);
-                    }
-                }
-
-                _ => {}
-            },
-
             _ => {}
         });
     }
@@ -0,0 +1,100 @@
+use std::ops::Deref;
+
+use crate::prelude::*;
+
+/// A group of checkboxes with labels, of which any number of options can be checked.
+///
+/// Toggling an option triggers the `on_toggle` callback, which is usually used to emit an event which adds the index
+/// of the option to, or removes it from, the bound list of checked options.
+///
+/// # Examples
+///
+/// ```
+/// # use vizia_core::prelude::*;
+/// #
+/// # #[derive(Lens)]
+/// # struct AppData {
+/// #     toppings: Vec<String>,
+/// #     checked: Vec<usize>,
+/// # }
+/// #
+/// # impl Model for AppData {}
+/// #
+/// # enum AppEvent {
+/// #     ToggleTopping(usize),
+/// # }
+/// #
+/// # let cx = &mut Context::default();
+/// #
+/// # AppData { toppings: vec![String::from("Cheese"), String::from("Olives")], checked: vec![] }.build(cx);
+/// #
+/// CheckboxGroup::new(cx, AppData::toppings, AppData::checked)
+///     .on_toggle(|cx, index| cx.emit(AppEvent::ToggleTopping(index)));
+/// ```
+pub struct CheckboxGroup {
+    on_toggle: Option<Box<dyn Fn(&mut EventContext, usize)>>,
+}
+
+pub(crate) enum CheckboxGroupEvent {
+    Toggle(usize),
+}
+
+impl CheckboxGroup {
+    /// Creates a new [CheckboxGroup] view with a checkbox for each option in the list, where the checked options are
+    /// given by a list of their indices.
+    pub fn new<L1, L2, T>(cx: &mut Context, options: L1, checked: L2) -> Handle<Self>
+    where
+        L1: Lens,
+        L1::Target: Deref<Target = [T]> + Data,
+        T: 'static + Data + ToStringLocalized,
+        L2: Lens,
+        L2::Target: Deref<Target = [usize]>,
+    {
+        Self { on_toggle: None }
+            .build(cx, move |cx| {
+                Binding::new(cx, options.map(|options| options.len()), move |cx, len| {
+                    for index in 0..len.get(cx) {
+                        HStack::new(cx, |cx| {
+                            Checkbox::new(cx, checked.map(move |checked| checked.contains(&index)))
+                                .on_toggle(move |cx| cx.emit(CheckboxGroupEvent::Toggle(index)));
+
+                            Label::new(cx, options.idx(index))
+                                .on_press(move |cx| cx.emit(CheckboxGroupEvent::Toggle(index)));
+                        })
+                        .class("option");
+                    }
+                });
+            })
+            .role(Role::Group)
+    }
+}
+
+impl View for CheckboxGroup {
+    fn element(&self) -> Option<&'static str> {
+        Some("checkboxgroup")
+    }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.map(|checkbox_group_event, meta| match checkbox_group_event {
+            CheckboxGroupEvent::Toggle(index) => {
+                if !cx.is_disabled() {
+                    if let Some(callback) = &self.on_toggle {
+                        (callback)(cx, *index);
+                    }
+                }
+
+                meta.consume();
+            }
+        });
+    }
+}
+
+impl Handle<'_, CheckboxGroup> {
+    /// Sets the callback triggered when an option is toggled, which is passed the index of the option.
+    pub fn on_toggle<F>(self, callback: F) -> Self
+    where
+        F: 'static + Fn(&mut EventContext, usize),
+    {
+        self.modify(|checkbox_group| checkbox_group.on_toggle = Some(Box::new(callback)))
+    }
+}
@@ -0,0 +1,411 @@
+use crate::prelude::*;
+
+// The parts of a color picker which can be dragged to change the color.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ColorPickerPart {
+    SaturationValue,
+    Hue,
+    Alpha,
+}
+
+enum ColorPickerEvent {
+    Drag(ColorPickerPart, f32, f32),
+    Release,
+    SetHex(String),
+    SetRed(u8),
+    SetGreen(u8),
+    SetBlue(u8),
+}
+
+/// A view for picking a color, with an area for choosing the saturation and value, sliders for the hue and alpha,
+/// and text entry for the hex and rgb components of the color.
+///
+/// # Example
+/// ```
+/// # use vizia_core::prelude::*;
+/// #
+/// # #[derive(Lens)]
+/// # struct AppData {
+/// #     color: Color,
+/// # }
+/// #
+/// # impl Model for AppData {}
+/// #
+/// # let cx = &mut Context::default();
+/// #
+/// # AppData { color: Color::red() }.build(cx);
+/// #
+/// ColorPicker::new(cx, AppData::color)
+///     .on_change(|_cx, color| println!("Dragging: {:?}", color))
+///     .on_submit(|_cx, color| println!("Picked: {:?}", color));
+/// ```
+#[derive(Lens)]
+pub struct ColorPicker {
+    hue: f32,
+    saturation: f32,
+    value: f32,
+    alpha: f32,
+    color: Color,
+    hex: String,
+
+    on_change: Option<Box<dyn Fn(&mut EventContext, Color)>>,
+    on_submit: Option<Box<dyn Fn(&mut EventContext, Color)>>,
+}
+
+impl ColorPicker {
+    /// Creates a new [ColorPicker] view which displays the color of the given lens.
+    pub fn new(cx: &mut Context, lens: impl Lens<Target = Color>) -> Handle<Self> {
+        Self {
+            hue: 0.0,
+            saturation: 0.0,
+            value: 0.0,
+            alpha: 1.0,
+            color: Color::black(),
+            hex: hex_text(Color::black()),
+            on_change: None,
+            on_submit: None,
+        }
+        .build(cx, |cx| {
+            // Saturation and value area
+            ColorPickerArea::new(cx, ColorPickerPart::SaturationValue, |cx| {
+                Element::new(cx)
+                    .class("color-picker-saturation")
+                    .hoverable(false)
+                    .background_gradient(
+                        LinearGradientBuilder::with_direction(LineDirection::Horizontal(
+                            HorizontalPositionKeyword::Right,
+                        ))
+                        .add_stop(Color::white())
+                        .add_stop(Color::transparent()),
+                    );
+
+                Element::new(cx).class("color-picker-value").hoverable(false).background_gradient(
+                    LinearGradientBuilder::with_direction(LineDirection::Vertical(
+                        VerticalPositionKeyword::Bottom,
+                    ))
+                    .add_stop(Color::transparent())
+                    .add_stop(Color::black()),
+                );
+
+                Element::new(cx)
+                    .class("thumb")
+                    .hoverable(false)
+                    .left(ColorPicker::saturation.map(|saturation| Percentage(saturation * 100.0)))
+                    .top(ColorPicker::value.map(|value| Percentage((1.0 - value) * 100.0)))
+                    .background_color(ColorPicker::color.map(|color| with_alpha(*color, 255)));
+            })
+            .class("color-picker-area")
+            .background_color(ColorPicker::hue.map(|hue| hsv_to_color(*hue, 1.0, 1.0, 1.0)));
+
+            // Hue slider
+            ColorPickerArea::new(cx, ColorPickerPart::Hue, |cx| {
+                Element::new(cx)
+                    .class("thumb")
+                    .hoverable(false)
+                    .left(ColorPicker::hue.map(|hue| Percentage(hue * 100.0)));
+            })
+            .class("color-picker-hue")
+            .background_gradient(
+                (0..=6).fold(LinearGradientBuilder::new(), |gradient, i| {
+                    gradient.add_stop(hsv_to_color(i as f32 / 6.0, 1.0, 1.0, 1.0))
+                }),
+            );
+
+            // Alpha slider
+            ColorPickerArea::new(cx, ColorPickerPart::Alpha, |cx| {
+                Binding::new(cx, ColorPicker::color, |cx, color| {
+                    let color = color.get(cx);
+                    Element::new(cx)
+                        .class("color-picker-alpha-track")
+                        .hoverable(false)
+                        .background_gradient(
+                            LinearGradientBuilder::new()
+                                .add_stop(with_alpha(color, 0))
+                                .add_stop(with_alpha(color, 255)),
+                        );
+                });
+
+                Element::new(cx)
+                    .class("thumb")
+                    .hoverable(false)
+                    .left(ColorPicker::alpha.map(|alpha| Percentage(alpha * 100.0)));
+            })
+            .class("color-picker-alpha");
+
+            // Text entry
+            HStack::new(cx, |cx| {
+                Textbox::new(cx, ColorPicker::hex)
+                    .class("color-picker-hex")
+                    .on_submit(|cx, text, _| cx.emit(ColorPickerEvent::SetHex(text)));
+
+                Textbox::new(cx, ColorPicker::color.map(|color| color.r()))
+                    .on_submit(|cx, red, _| cx.emit(ColorPickerEvent::SetRed(red)));
+                Textbox::new(cx, ColorPicker::color.map(|color| color.g()))
+                    .on_submit(|cx, green, _| cx.emit(ColorPickerEvent::SetGreen(green)));
+                Textbox::new(cx, ColorPicker::color.map(|color| color.b()))
+                    .on_submit(|cx, blue, _| cx.emit(ColorPickerEvent::SetBlue(blue)));
+            })
+            .class("color-picker-inputs");
+        })
+        .bind(lens, |handle, lens| {
+            let color = lens.get(&handle);
+            handle.modify(|color_picker: &mut ColorPicker| {
+                color_picker.set_color(color);
+            });
+        })
+    }
+
+    // Sets the color shown by the picker, keeping the hue and saturation when they can't be derived from the color.
+    // Returns false if the color is unchanged.
+    fn set_color(&mut self, color: Color) -> bool {
+        if color == self.color {
+            return false;
+        }
+
+        let (hue, saturation, value, alpha) = color_to_hsv(color);
+
+        if value > 0.0 {
+            if saturation > 0.0 {
+                self.hue = hue;
+            }
+            self.saturation = saturation;
+        }
+
+        self.value = value;
+        self.alpha = alpha;
+        self.color = color;
+        self.hex = hex_text(color);
+
+        true
+    }
+
+    // Updates the color from the hue, saturation, value and alpha of the picker.
+    fn update_color(&mut self) {
+        self.color = hsv_to_color(self.hue, self.saturation, self.value, self.alpha);
+        self.hex = hex_text(self.color);
+    }
+
+    fn change(&self, cx: &mut EventContext) {
+        if let Some(callback) = &self.on_change {
+            (callback)(cx, self.color);
+        }
+    }
+
+    // Sets the color from text entry and notifies both callbacks if it changed.
+    fn enter_color(&mut self, cx: &mut EventContext, color: Color) {
+        if self.set_color(color) {
+            self.change(cx);
+            self.submit(cx);
+        }
+    }
+
+    fn submit(&self, cx: &mut EventContext) {
+        if let Some(callback) = &self.on_submit {
+            (callback)(cx, self.color);
+        }
+    }
+}
+
+impl View for ColorPicker {
+    fn element(&self) -> Option<&'static str> {
+        Some("color-picker")
+    }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.take(|color_picker_event, _| match color_picker_event {
+            ColorPickerEvent::Drag(part, x, y) => {
+                match part {
+                    ColorPickerPart::SaturationValue => {
+                        self.saturation = x;
+                        self.value = 1.0 - y;
+                    }
+
+                    ColorPickerPart::Hue => self.hue = x,
+
+                    ColorPickerPart::Alpha => self.alpha = x,
+                }
+
+                self.update_color();
+                self.change(cx);
+            }
+
+            ColorPickerEvent::Release => {
+                self.submit(cx);
+            }
+
+            ColorPickerEvent::SetHex(text) => {
+                let text = text.trim();
+                let text = text.strip_prefix('#').unwrap_or(text);
+                if text.len() != 6 && text.len() != 8 {
+                    return;
+                }
+
+                let color = Color::from(format!("#{}", text).as_str());
+                if color != Color::CurrentColor {
+                    self.enter_color(cx, color);
+                }
+            }
+
+            ColorPickerEvent::SetRed(red) => {
+                self.enter_color(
+                    cx,
+                    Color::rgba(red, self.color.g(), self.color.b(), self.color.a()),
+                );
+            }
+
+            ColorPickerEvent::SetGreen(green) => {
+                self.enter_color(
+                    cx,
+                    Color::rgba(self.color.r(), green, self.color.b(), self.color.a()),
+                );
+            }
+
+            ColorPickerEvent::SetBlue(blue) => {
+                self.enter_color(
+                    cx,
+                    Color::rgba(self.color.r(), self.color.g(), blue, self.color.a()),
+                );
+            }
+        });
+    }
+}
+
+impl Handle<'_, ColorPicker> {
+    /// Sets the callback triggered when the color is changed, which is called continuously while dragging.
+    pub fn on_change<F>(self, callback: F) -> Self
+    where
+        F: 'static + Fn(&mut EventContext, Color),
+    {
+        self.modify(|color_picker: &mut ColorPicker| {
+            color_picker.on_change = Some(Box::new(callback))
+        })
+    }
+
+    /// Sets the callback triggered when a color is picked, i.e. when the mouse is released after dragging or a
+    /// color is entered as text.
+    pub fn on_submit<F>(self, callback: F) -> Self
+    where
+        F: 'static + Fn(&mut EventContext, Color),
+    {
+        self.modify(|color_picker: &mut ColorPicker| {
+            color_picker.on_submit = Some(Box::new(callback))
+        })
+    }
+}
+
+// An area of a color picker which emits the normalized position of the mouse while it is dragged.
+struct ColorPickerArea {
+    part: ColorPickerPart,
+    is_dragging: bool,
+}
+
+impl ColorPickerArea {
+    fn new(
+        cx: &mut Context,
+        part: ColorPickerPart,
+        content: impl FnOnce(&mut Context),
+    ) -> Handle<Self> {
+        Self { part, is_dragging: false }.build(cx, content)
+    }
+
+    fn drag(&self, cx: &mut EventContext, x: f32, y: f32) {
+        let bounds = cx.bounds();
+        let dx = ((x - bounds.x) / bounds.w).clamp(0.0, 1.0);
+        let dy = ((y - bounds.y) / bounds.h).clamp(0.0, 1.0);
+        cx.emit(ColorPickerEvent::Drag(self.part, dx, dy));
+    }
+}
+
+impl View for ColorPickerArea {
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.map(|window_event, _| match window_event {
+            WindowEvent::MouseDown(MouseButton::Left) => {
+                if cx.is_disabled() {
+                    return;
+                }
+
+                cx.capture();
+                cx.set_active(true);
+                self.is_dragging = true;
+                let (x, y) = cx.mouse().left.pos_down;
+                self.drag(cx, x, y);
+            }
+
+            WindowEvent::MouseMove(x, y) => {
+                if self.is_dragging {
+                    self.drag(cx, *x, *y);
+                }
+            }
+
+            WindowEvent::MouseUp(MouseButton::Left) => {
+                if self.is_dragging {
+                    self.is_dragging = false;
+                    cx.set_active(false);
+                    cx.release();
+                    cx.emit(ColorPickerEvent::Release);
+                }
+            }
+
+            _ => {}
+        });
+    }
+}
+
+// Returns the color with the given alpha component.
+fn with_alpha(color: Color, alpha: u8) -> Color {
+    Color::rgba(color.r(), color.g(), color.b(), alpha)
+}
+
+// Returns the hex representation of a color, including the alpha component only if the color is translucent.
+fn hex_text(color: Color) -> String {
+    if color.a() == 255 {
+        format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b())
+    } else {
+        format!("#{:02x}{:02x}{:02x}{:02x}", color.r(), color.g(), color.b(), color.a())
+    }
+}
+
+// Converts hue, saturation, value and alpha components between 0.0 and 1.0 to a color.
+fn hsv_to_color(hue: f32, saturation: f32, value: f32, alpha: f32) -> Color {
+    let h = (hue.rem_euclid(1.0)) * 6.0;
+    let c = value * saturation;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let m = value - c;
+
+    let (r, g, b) = match h as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+
+    let channel = |v: f32| ((v + m) * 255.0).round() as u8;
+    Color::rgba(channel(r), channel(g), channel(b), (alpha * 255.0).round() as u8)
+}
+
+// Converts a color to hue, saturation, value and alpha components between 0.0 and 1.0.
+fn color_to_hsv(color: Color) -> (f32, f32, f32, f32) {
+    let r = color.r() as f32 / 255.0;
+    let g = color.g() as f32 / 255.0;
+    let b = color.b() as f32 / 255.0;
+
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+
+    let hue = if delta == 0.0 {
+        0.0
+    } else if max == r {
+        ((g - b) / delta).rem_euclid(6.0) / 6.0
+    } else if max == g {
+        ((b - r) / delta + 2.0) / 6.0
+    } else {
+        ((r - g) / delta + 4.0) / 6.0
+    };
+
+    let saturation = if max == 0.0 { 0.0 } else { delta / max };
+
+    (hue, saturation, max, color.a() as f32 / 255.0)
+}
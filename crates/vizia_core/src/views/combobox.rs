@@ -114,7 +114,8 @@ where
                         });
                     })
                     .should_reposition(false)
-                    .arrow_size(Pixels(4.0));
+                    .arrow_size(Pixels(4.0))
+                    .trap_focus(false);
                 }
             });
         })
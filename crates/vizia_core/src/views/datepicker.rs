@@ -1,13 +1,22 @@
-use chrono::{Datelike, NaiveDate, Weekday};
+use chrono::{Datelike, Days, Months, NaiveDate};
 
 use crate::prelude::*;
 
 /// A control used to select a date.
+///
+/// Once a day is focused, the calendar can be navigated with the arrow keys, where the page up and page down keys
+/// move by a month and the home and end keys move to the start and end of the week. The dates which can be selected
+/// can be limited with the [`min_date`](Handle::min_date) and [`max_date`](Handle::max_date) modifiers.
 #[derive(Lens)]
 pub struct Datepicker {
     view_date: NaiveDate,
     months: Vec<Localized>,
     selected_month: usize,
+    min_date: Option<NaiveDate>,
+    max_date: Option<NaiveDate>,
+    focused_date: Option<NaiveDate>,
+    // The day to move the keyboard focus to, which is cleared once the day is focused.
+    focus_request: Option<NaiveDate>,
 
     #[lens(ignore)]
     on_select: Option<Box<dyn Fn(&mut EventContext, NaiveDate)>>,
@@ -41,71 +50,74 @@ pub(crate) enum DatepickerEvent {
     SelectYear(String),
 
     SelectDate(NaiveDate),
+
+    FocusDate(NaiveDate),
+    MoveFocus(i64),
+    MoveFocusMonths(i32),
+    FocusStartOfWeek,
+    FocusEndOfWeek,
 }
 
-impl Datepicker {
-    fn first_day_of_month(year: i32, month: u32) -> Option<Weekday> {
-        NaiveDate::from_ymd_opt(year, month, 1).map(|date| date.weekday())
+// Returns the date offset by the given number of days.
+fn add_days(date: NaiveDate, days: i64) -> Option<NaiveDate> {
+    if days >= 0 {
+        date.checked_add_days(Days::new(days as u64))
+    } else {
+        date.checked_sub_days(Days::new(days.unsigned_abs()))
     }
+}
 
-    fn last_day_of_month(year: i32, month: u32) -> Option<u32> {
-        if month == 12 {
-            NaiveDate::from_ymd_opt(year + 1, 1, 1)
-        } else {
-            NaiveDate::from_ymd_opt(year, month + 1, 1)
-        }
-        .map(|date| {
-            date.signed_duration_since(NaiveDate::from_ymd_opt(year, month, 1).unwrap()).num_days()
-                as u32
-        })
+// Returns the date offset by the given number of months, clamping the day to the length of the month.
+fn add_months(date: NaiveDate, months: i32) -> Option<NaiveDate> {
+    if months >= 0 {
+        date.checked_add_months(Months::new(months as u32))
+    } else {
+        date.checked_sub_months(Months::new(months.unsigned_abs()))
     }
+}
 
-    // Given a date and a month offset, returns the first day of the month and the number of days in the month
-    fn view_month_info(view_date: &NaiveDate, month_offset: i32) -> (Weekday, u32) {
-        let month = view_date.month();
-        let mut year = view_date.year();
-
-        let mut month = month as i32 + month_offset;
-
-        if month < 1 {
-            year -= 1;
-            month += 12;
-        } else if month > 12 {
-            year += 1;
-            month -= 12;
-        }
-
-        let month = month as u32;
+// Returns whether the date is within the optional limits.
+fn in_range(date: NaiveDate, min_date: Option<NaiveDate>, max_date: Option<NaiveDate>) -> bool {
+    min_date.is_none_or(|min_date| date >= min_date)
+        && max_date.is_none_or(|max_date| date <= max_date)
+}
 
-        (
-            Self::first_day_of_month(year, month).unwrap(),
-            Self::last_day_of_month(year, month).unwrap(),
-        )
+impl Datepicker {
+    // Returns the date shown in the given row and column of the calendar for the month of the view date, which may
+    // be within the previous or next month.
+    fn cell_date(y: u32, x: u32, view_date: &NaiveDate) -> NaiveDate {
+        let first = view_date.with_day(1).unwrap();
+
+        // A month which starts on a Monday is preceded by a full week of the previous month.
+        let offset = match first.weekday().num_days_from_monday() {
+            0 => 7,
+            days => days,
+        };
+
+        add_days(first, (y * 7 + x) as i64 - offset as i64).unwrap_or(first)
     }
 
-    fn get_day_number(y: u32, x: u32, view_date: &NaiveDate) -> (u32, bool) {
-        let (_, days_prev_month) = Self::view_month_info(view_date, -1);
-        let (first_day_this_month, days_this_month) = Self::view_month_info(view_date, 0);
+    // Moves the keyboard focus to the given date, limited to the selectable dates, showing the month of the date.
+    fn move_focus(&mut self, date: Option<NaiveDate>) {
+        let Some(mut date) = date else {
+            return;
+        };
 
-        let mut fdtm_i = first_day_this_month as usize as u32;
-        if fdtm_i == 0 {
-            fdtm_i = 7;
+        if let Some(min_date) = self.min_date {
+            date = date.max(min_date);
         }
 
-        if y == 0 {
-            if x < fdtm_i {
-                (days_prev_month - (fdtm_i - x - 1), true)
-            } else {
-                (x - fdtm_i + 1, false)
-            }
-        } else {
-            let day_number = y * 7 + x - fdtm_i + 1;
-            if day_number > days_this_month {
-                (day_number - days_this_month, true)
-            } else {
-                (day_number, false)
-            }
+        if let Some(max_date) = self.max_date {
+            date = date.min(max_date);
         }
+
+        if date.year() != self.view_date.year() || date.month() != self.view_date.month() {
+            self.view_date = date.with_day(1).unwrap();
+            self.selected_month = date.month() as usize - 1;
+        }
+
+        self.focused_date = Some(date);
+        self.focus_request = Some(date);
     }
 
     /// Create a new [Datepicker] view.
@@ -120,6 +132,10 @@ impl Datepicker {
             months: MONTHS.iter().map(|m| Localized::new(m)).collect::<Vec<_>>(),
             selected_month: view_date.month() as usize - 1,
             view_date: NaiveDate::from_ymd_opt(view_date.year(), view_date.month(), 1).unwrap(),
+            min_date: None,
+            max_date: None,
+            focused_date: None,
+            focus_request: None,
             on_select: None,
         }
         .build(cx, move |cx| {
@@ -148,11 +164,63 @@ impl Datepicker {
             Divider::new(cx);
 
             VStack::new(cx, move |cx| {
+                Keymap::from(vec![
+                    (
+                        KeyChord::new(Modifiers::empty(), Code::ArrowLeft),
+                        KeymapEntry::new("Previous Day", |cx| {
+                            cx.emit(DatepickerEvent::MoveFocus(-1))
+                        }),
+                    ),
+                    (
+                        KeyChord::new(Modifiers::empty(), Code::ArrowRight),
+                        KeymapEntry::new("Next Day", |cx| cx.emit(DatepickerEvent::MoveFocus(1))),
+                    ),
+                    (
+                        KeyChord::new(Modifiers::empty(), Code::ArrowUp),
+                        KeymapEntry::new("Previous Week", |cx| {
+                            cx.emit(DatepickerEvent::MoveFocus(-7))
+                        }),
+                    ),
+                    (
+                        KeyChord::new(Modifiers::empty(), Code::ArrowDown),
+                        KeymapEntry::new("Next Week", |cx| cx.emit(DatepickerEvent::MoveFocus(7))),
+                    ),
+                    (
+                        KeyChord::new(Modifiers::empty(), Code::PageUp),
+                        KeymapEntry::new("Previous Month", |cx| {
+                            cx.emit(DatepickerEvent::MoveFocusMonths(-1))
+                        }),
+                    ),
+                    (
+                        KeyChord::new(Modifiers::empty(), Code::PageDown),
+                        KeymapEntry::new("Next Month", |cx| {
+                            cx.emit(DatepickerEvent::MoveFocusMonths(1))
+                        }),
+                    ),
+                    (
+                        KeyChord::new(Modifiers::empty(), Code::Home),
+                        KeymapEntry::new("Start of Week", |cx| {
+                            cx.emit(DatepickerEvent::FocusStartOfWeek)
+                        }),
+                    ),
+                    (
+                        KeyChord::new(Modifiers::empty(), Code::End),
+                        KeymapEntry::new("End of Week", |cx| {
+                            cx.emit(DatepickerEvent::FocusEndOfWeek)
+                        }),
+                    ),
+                ])
+                .build(cx);
+
                 // Days of the week
                 HStack::new(cx, |cx| {
                     for h in DAYS_HEADER {
-                        Label::new(cx, Localized::new(h).map(|day| day[0..2].to_string()))
-                            .class("datepicker-calendar-header");
+                        // Localized names are shortened by character, as they may not be ASCII.
+                        Label::new(
+                            cx,
+                            Localized::new(h).map(|day| day.chars().take(2).collect::<String>()),
+                        )
+                        .class("datepicker-calendar-header");
                     }
                 })
                 .class("datepicker-calendar-headers");
@@ -166,41 +234,48 @@ impl Datepicker {
                                     Datepicker::view_date,
                                     move |handle, view_date| {
                                         let view_date = view_date.get(&handle);
-
-                                        let (day_number, disabled) =
-                                            Self::get_day_number(y, x, &view_date);
-
-                                        handle.bind(lens, move |handle, selected_date| {
-                                            let selected_date = selected_date.get(&handle);
-
-                                            handle
-                                                .text(&day_number.to_string())
-                                                .class("datepicker-calendar-day")
-                                                .navigable(!disabled)
-                                                .toggle_class(
-                                                    "datepicker-calendar-day-disabled",
-                                                    disabled,
-                                                )
-                                                .on_press(move |ex| {
-                                                    if !disabled {
-                                                        ex.emit(DatepickerEvent::SelectDate(
-                                                            NaiveDate::from_ymd_opt(
-                                                                view_date.year(),
-                                                                view_date.month(),
-                                                                day_number,
-                                                            )
-                                                            .unwrap(),
-                                                        ))
-                                                    }
-                                                })
-                                                .checked(
-                                                    !disabled
-                                                        && selected_date.day() == day_number
-                                                        && selected_date.month()
-                                                            == view_date.month()
-                                                        && selected_date.year() == view_date.year(),
+                                        let date = Self::cell_date(y, x, &view_date);
+                                        let outside_month = date.month() != view_date.month();
+
+                                        handle
+                                            .text(&date.day().to_string())
+                                            .class("datepicker-calendar-day")
+                                            .checked(lens.map(move |selected_date| {
+                                                !outside_month
+                                                    && selected_date.day() == date.day()
+                                                    && selected_date.month() == date.month()
+                                                    && selected_date.year() == date.year()
+                                            }))
+                                            .focused_with_visibility(
+                                                Datepicker::focus_request
+                                                    .map(move |request| *request == Some(date)),
+                                                true,
+                                            )
+                                            .on_focus_in(move |ex| {
+                                                ex.emit(DatepickerEvent::FocusDate(date))
+                                            })
+                                            .on_press(move |ex| {
+                                                ex.emit(DatepickerEvent::SelectDate(date))
+                                            })
+                                            .bind(Datepicker::min_date, move |handle, min_date| {
+                                                let min_date = min_date.get(&handle);
+                                                handle.bind(
+                                                    Datepicker::max_date,
+                                                    move |handle, max_date| {
+                                                        let max_date = max_date.get(&handle);
+                                                        let enabled = !outside_month
+                                                            && in_range(date, min_date, max_date);
+
+                                                        handle
+                                                            .navigable(enabled)
+                                                            .disabled(!enabled)
+                                                            .toggle_class(
+                                                                "datepicker-calendar-day-disabled",
+                                                                !enabled,
+                                                            );
+                                                    },
                                                 );
-                                        });
+                                            });
                                     },
                                 );
                             }
@@ -295,15 +370,60 @@ impl View for Datepicker {
             }
 
             DatepickerEvent::SelectDate(date) => {
+                if !in_range(*date, self.min_date, self.max_date) {
+                    return;
+                }
+
                 if let Some(callback) = &self.on_select {
                     (callback)(cx, *date);
                 }
             }
+
+            DatepickerEvent::FocusDate(date) => {
+                self.focused_date = Some(*date);
+                self.focus_request = None;
+            }
+
+            DatepickerEvent::MoveFocus(days) => {
+                let date = self.focused_date.unwrap_or(self.view_date);
+                self.move_focus(add_days(date, *days));
+            }
+
+            DatepickerEvent::MoveFocusMonths(months) => {
+                let date = self.focused_date.unwrap_or(self.view_date);
+                self.move_focus(add_months(date, *months));
+            }
+
+            DatepickerEvent::FocusStartOfWeek => {
+                let date = self.focused_date.unwrap_or(self.view_date);
+                self.move_focus(add_days(date, -(date.weekday().num_days_from_monday() as i64)));
+            }
+
+            DatepickerEvent::FocusEndOfWeek => {
+                let date = self.focused_date.unwrap_or(self.view_date);
+                self.move_focus(add_days(date, 6 - date.weekday().num_days_from_monday() as i64));
+            }
         })
     }
 }
 
 impl Handle<'_, Datepicker> {
+    /// Sets the earliest date which can be selected, or removes the limit if `None`.
+    pub fn min_date<U: Into<Option<NaiveDate>>>(self, date: impl Res<U>) -> Self {
+        self.bind(date, |handle, date| {
+            let date = date.get(&handle).into();
+            handle.modify(|datepicker: &mut Datepicker| datepicker.min_date = date);
+        })
+    }
+
+    /// Sets the latest date which can be selected, or removes the limit if `None`.
+    pub fn max_date<U: Into<Option<NaiveDate>>>(self, date: impl Res<U>) -> Self {
+        self.bind(date, |handle, date| {
+            let date = date.get(&handle).into();
+            handle.modify(|datepicker: &mut Datepicker| datepicker.max_date = date);
+        })
+    }
+
     /// Set the callback triggered when a date is selected from the [Datepicker] view.
     pub fn on_select<F: 'static + Fn(&mut EventContext, NaiveDate)>(self, callback: F) -> Self {
         self.modify(|datepicker: &mut Datepicker| datepicker.on_select = Some(Box::new(callback)))
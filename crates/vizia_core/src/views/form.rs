@@ -0,0 +1,248 @@
+use std::any::TypeId;
+
+use crate::prelude::*;
+
+/// A container for the fields of a form which only triggers its `on_submit` callback when every field is valid.
+///
+/// Views within the form become fields with the [`form_field`](FormModifiers::form_field) modifier, which validates
+/// the data the field is bound to whenever it changes. Once a field has been edited, or the form has been submitted,
+/// the field can be styled with the `:valid` and `:invalid` CSS pseudo-classes. The form is submitted by emitting a
+/// [`FormEvent::Submit`] from within it, usually from a button.
+///
+/// The form builds a [`FormModel`], so the content of the form can bind to whether the form is valid.
+///
+/// # Examples
+///
+/// ```
+/// # use vizia_core::prelude::*;
+/// #
+/// # #[derive(Lens)]
+/// # struct AppData {
+/// #     name: String,
+/// #     sizes: Vec<String>,
+/// #     size: Option<usize>,
+/// # }
+/// #
+/// # impl Model for AppData {}
+/// #
+/// # enum AppEvent {
+/// #     SetSize(usize),
+/// #     PlaceOrder,
+/// # }
+/// #
+/// # let cx = &mut Context::default();
+/// #
+/// # AppData { name: String::new(), sizes: vec![String::from("Small")], size: None }.build(cx);
+/// #
+/// Form::new(cx, |cx| {
+///     Textbox::new(cx, AppData::name).form_field(AppData::name, |name| !name.is_empty());
+///     RadioGroup::new(cx, AppData::sizes, AppData::size)
+///         .on_select(|cx, index| cx.emit(AppEvent::SetSize(index)))
+///         .form_field(AppData::size, |size| size.is_some());
+///     Button::new(cx, |cx| Label::new(cx, "Order"))
+///         .on_press(|cx| cx.emit(FormEvent::Submit))
+///         .disabled(FormModel::valid.map(|valid| !valid));
+/// })
+/// .on_submit(|cx| cx.emit(AppEvent::PlaceOrder));
+/// ```
+pub struct Form {
+    on_submit: Option<Box<dyn Fn(&mut EventContext)>>,
+}
+
+/// Events which can be emitted from within a [`Form`].
+pub enum FormEvent {
+    /// Validates every field of the form and triggers the `on_submit` callback of the form if they're all valid.
+    Submit,
+}
+
+// Emitted by a field of a form when the data it's bound to changes.
+pub(crate) enum FormFieldEvent {
+    Changed(Entity),
+}
+
+impl Form {
+    /// Creates a new [Form] view.
+    pub fn new<F>(cx: &mut Context, content: F) -> Handle<Self>
+    where
+        F: FnOnce(&mut Context),
+    {
+        Self { on_submit: None }.build(cx, |cx| {
+            FormModel { valid: true, submitted: false, fields: Vec::new() }.build(cx);
+
+            (content)(cx);
+        })
+    }
+}
+
+impl View for Form {
+    fn element(&self) -> Option<&'static str> {
+        Some("form")
+    }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        // The form model consumes the submit event if any of the fields are invalid.
+        event.map(|form_event, meta| match form_event {
+            FormEvent::Submit => {
+                if let Some(callback) = &self.on_submit {
+                    (callback)(cx);
+                }
+
+                meta.consume();
+            }
+        });
+    }
+}
+
+impl Handle<'_, Form> {
+    /// Sets the callback triggered when the form is submitted and all of its fields are valid.
+    pub fn on_submit<F>(self, callback: F) -> Self
+    where
+        F: 'static + Fn(&mut EventContext),
+    {
+        self.modify(|form| form.on_submit = Some(Box::new(callback)))
+    }
+}
+
+// A view within a form and the validator of the data it's bound to.
+struct FormField {
+    entity: Entity,
+    validate: Box<dyn Fn(&mut EventContext) -> bool>,
+    valid: bool,
+    // Whether the data of the field has changed since the field was added to the form.
+    edited: bool,
+    // Whether the field has been validated once, which happens when it's added to the form.
+    initialized: bool,
+}
+
+/// The model built by a [`Form`], which aggregates the validators of its fields.
+#[derive(Lens)]
+pub struct FormModel {
+    /// Whether every field of the form is valid.
+    pub valid: bool,
+    /// Whether the form has been submitted at least once.
+    pub submitted: bool,
+    #[lens(ignore)]
+    fields: Vec<FormField>,
+}
+
+impl FormModel {
+    // Validates a field, updating its pseudo-classes once it has been edited or the form has been submitted.
+    fn validate_field(cx: &mut EventContext, field: &mut FormField, submitted: bool) {
+        field.valid = cx.with_current(field.entity, |cx| (field.validate)(cx));
+        if field.edited || submitted {
+            let valid = field.valid;
+            cx.with_current(field.entity, |cx| cx.set_valid(valid));
+        }
+    }
+}
+
+impl Model for FormModel {
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.map(|form_field_event, meta| match form_field_event {
+            FormFieldEvent::Changed(entity) => {
+                if let Some(field) = self.fields.iter_mut().find(|field| field.entity == *entity) {
+                    if field.initialized {
+                        field.edited = true;
+                    }
+
+                    field.initialized = true;
+                    Self::validate_field(cx, field, self.submitted);
+                }
+
+                self.valid = self.fields.iter().all(|field| field.valid);
+                meta.consume();
+            }
+        });
+
+        event.map(|form_event, meta| match form_event {
+            FormEvent::Submit => {
+                // Forget the fields which have been removed from the tree, such as by a binding rebuilding its content.
+                self.fields.retain(|field| cx.views.contains_key(&field.entity));
+
+                self.submitted = true;
+                for field in self.fields.iter_mut() {
+                    Self::validate_field(cx, field, true);
+                }
+
+                self.valid = self.fields.iter().all(|field| field.valid);
+                if !self.valid {
+                    meta.consume();
+                }
+            }
+        });
+    }
+}
+
+/// Modifiers for adding views to a [`Form`].
+pub trait FormModifiers {
+    /// Adds the view as a field of the closest form it's within, which is valid when the closure returns true for the
+    /// data the field is bound to.
+    ///
+    /// # Example
+    /// ```
+    /// # use vizia_core::prelude::*;
+    /// #
+    /// # #[derive(Lens)]
+    /// # struct AppData {
+    /// #     agreed: bool,
+    /// # }
+    /// #
+    /// # impl Model for AppData {}
+    /// #
+    /// # let cx = &mut Context::default();
+    /// #
+    /// # AppData { agreed: false }.build(cx);
+    /// #
+    /// Form::new(cx, |cx| {
+    ///     Checkbox::new(cx, AppData::agreed).form_field(AppData::agreed, |agreed| *agreed);
+    /// });
+    /// ```
+    fn form_field<L, F>(self, lens: L, is_valid: F) -> Self
+    where
+        L: Lens<Target: Data>,
+        F: 'static + Fn(&L::Target) -> bool;
+}
+
+impl<V: View> FormModifiers for Handle<'_, V> {
+    fn form_field<L, F>(self, lens: L, is_valid: F) -> Self
+    where
+        L: Lens<Target: Data>,
+        F: 'static + Fn(&L::Target) -> bool,
+    {
+        let entity = self.entity();
+        let validate = move |cx: &mut EventContext| {
+            cx.data().and_then(|source| lens.view(source)).is_some_and(|value| is_valid(&value))
+        };
+
+        let mut ancestor = self.cx.tree.get_parent(entity);
+        while let Some(parent) = ancestor {
+            if let Some(form_model) = self
+                .cx
+                .models
+                .get_mut(&parent)
+                .and_then(|models| models.get_mut(&TypeId::of::<FormModel>()))
+                .and_then(|model| model.downcast_mut::<FormModel>())
+            {
+                form_model.fields.push(FormField {
+                    entity,
+                    validate: Box::new(validate),
+                    valid: false,
+                    edited: false,
+                    initialized: false,
+                });
+                break;
+            }
+
+            ancestor = self.cx.tree.get_parent(parent);
+        }
+
+        self.bind(lens, move |mut handle, _| {
+            handle.context().emit_custom(
+                Event::new(FormFieldEvent::Changed(entity))
+                    .target(entity)
+                    .origin(entity)
+                    .propagate(Propagation::Up),
+            );
+        })
+    }
+}
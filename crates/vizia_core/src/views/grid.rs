@@ -2,7 +2,20 @@ use crate::prelude::*;
 
 /// A view which arranges its children into a grid.
 ///
+/// The sizes of the columns and rows can be fixed (`Pixels`), stretch to fill the remaining space (`Stretch`) or
+/// size to their contents (`Auto`). Children are placed with the `column_start`, `column_span`, `row_start` and
+/// `row_span` modifiers, which take zero-based track indices, or with the `grid-column` and `grid-row` style
+/// properties, which take one-based line numbers as in CSS. The space between tracks is set with the
+/// `horizontal-gap` and `vertical-gap` properties.
 ///
+/// # Example
+/// ```ignore
+/// Grid::new(cx, vec![Pixels(100.0), Stretch(1.0)], vec![Auto, Auto], |cx| {
+///     Label::new(cx, "Title").column_span(2);
+///     Label::new(cx, "Name").row_start(1);
+///     Textbox::new(cx, AppData::name).column_start(1).row_start(1);
+/// });
+/// ```
 pub struct Grid {}
 
 impl Grid {
@@ -7,9 +7,18 @@ pub struct Image {}
 
 impl Image {
     /// Creates a new [Image] view.
+    ///
+    /// The image is looked up by name in the resource manager. Paths to `.svg` files which haven't been loaded and
+    /// exist on disk are read from disk, otherwise the image loader is asked for them. SVG images are drawn as vectors so they stay sharp when the view is resized or the scale
+    /// factor changes.
     pub fn new<T: ToString>(cx: &mut Context, img: impl Res<T>) -> Handle<'_, Self> {
         // TODO: Make this reactive
-        let img = BackgroundImage::Url(Url { url: img.get(cx).to_string().into() });
+        let path = img.get(cx).to_string();
+        if path.to_lowercase().ends_with(".svg") && std::path::Path::new(&path).is_file() {
+            cx.load_image_async(&path, ImageRetentionPolicy::DropWhenNoObservers);
+        }
+
+        let img = BackgroundImage::Url(Url { url: path.into() });
         Self {}.build(cx, |_| {}).background_image(img)
     }
 }
@@ -48,3 +57,35 @@ impl View for Svg {
         Some("svg")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::test_support::{update, window_context};
+    use crate::resource::ImageOrSvg;
+
+    const SVG: &[u8] = br#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10"/>"#;
+
+    #[test]
+    fn load_svg_after_image() {
+        let mut cx = window_context(400, 300);
+
+        // The path doesn't exist on disk, so the image is left for the application to load.
+        Image::new(cx.context(), "missing.svg");
+        update(&mut cx);
+        assert!(!cx.0.resource_manager.image_ids.contains_key("missing.svg"));
+
+        let id = cx.0.load_svg("missing.svg", SVG, ImageRetentionPolicy::Forever);
+        assert!(matches!(cx.0.resource_manager.images[&id].image, ImageOrSvg::Svg(_)));
+
+        // A placeholder shown while an image loads is replaced by an SVG image loaded with the same name.
+        cx.0.load_image_async("pending.svg", ImageRetentionPolicy::Forever);
+        assert!(matches!(
+            cx.0.resource_manager.images[&cx.0.resource_manager.image_ids["pending.svg"]].image,
+            ImageOrSvg::Image(_)
+        ));
+
+        let id = cx.0.load_svg("pending.svg", SVG, ImageRetentionPolicy::Forever);
+        assert!(matches!(cx.0.resource_manager.images[&id].image, ImageOrSvg::Svg(_)));
+    }
+}
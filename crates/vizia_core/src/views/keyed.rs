@@ -0,0 +1,83 @@
+//! Items of a view, such as a list or a tabview, which are identified by a key.
+//!
+//! When the data of the view changes, only the items with new keys are built and only the items whose keys were
+//! removed are destroyed, while the other items are moved into place, keeping the state of their views.
+use std::{cell::Cell, hash::Hash, rc::Rc};
+
+use hashbrown::HashMap;
+
+use crate::prelude::*;
+
+/// An item identified by a key.
+pub(crate) struct KeyedItem<K> {
+    key: K,
+    entity: Entity,
+    // The index of the item, which is shared with the item view and updated when the item moves.
+    index: Rc<Cell<usize>>,
+}
+
+// An ignored view which holds a keyed item, so that the map lenses of the item are removed along with it.
+struct KeyedItemView {}
+
+impl View for KeyedItemView {}
+
+/// Updates the items to match the keys, building the items with new keys as children of the `parent`, removing the
+/// items whose keys were removed, and moving the rest into order after the current view.
+///
+/// Returns the new index of each previous item, or `None` if it was removed. Only one of the items with the same key
+/// is kept.
+pub(crate) fn update_keyed_items<K: Clone + Hash + Eq>(
+    cx: &mut Context,
+    parent: Entity,
+    items: &mut Vec<KeyedItem<K>>,
+    keys: &[K],
+    build_item: &dyn Fn(&mut Context, Rc<Cell<usize>>),
+) -> Vec<Option<usize>> {
+    let anchor = cx.current();
+    let mut moved = vec![None; items.len()];
+    let mut removed = Vec::new();
+    let mut previous = HashMap::with_capacity(items.len());
+    for (index, item) in items.drain(..).enumerate() {
+        if let Some((_, duplicate)) = previous.insert(item.key.clone(), (index, item)) {
+            removed.push(duplicate.entity);
+        }
+    }
+
+    for (index, key) in keys.iter().enumerate() {
+        if let Some((previous_index, item)) = previous.remove(key) {
+            item.index.set(index);
+            moved[previous_index] = Some(index);
+            items.push(item);
+        } else {
+            let index = Rc::new(Cell::new(index));
+            let entity = cx.with_current(parent, |cx| {
+                KeyedItemView {}.build(cx, |cx| build_item(cx, index.clone())).ignore().entity()
+            });
+            items.push(KeyedItem { key: key.clone(), entity, index });
+        }
+    }
+
+    removed.extend(previous.into_values().map(|(_, item)| item.entity));
+    for entity in removed {
+        cx.remove(entity);
+    }
+
+    let mut prev = anchor;
+    for item in items.iter() {
+        if cx.tree.get_next_sibling(prev) != Some(item.entity) {
+            cx.tree.set_next_sibling(prev, item.entity).ok();
+            cx.needs_restyle(item.entity);
+        }
+
+        prev = item.entity;
+    }
+
+    if let Some(layout_parent) = cx.tree.get_layout_parent(parent) {
+        cx.style.needs_access_update(layout_parent);
+    }
+
+    cx.needs_relayout();
+    cx.needs_redraw(parent);
+
+    moved
+}
@@ -123,6 +123,75 @@ impl Label {
             .role(Role::Label)
             .name(text)
     }
+
+    /// Creates a new rich [Label] view from a [RichText] description, with each span of the rich text
+    /// displayed as a [TextSpan] with its own color, weight, slant, and size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use vizia_core::prelude::*;
+    /// #
+    /// # let cx = &mut Context::default();
+    /// #
+    /// Label::rich_text(
+    ///     cx,
+    ///     RichText::new()
+    ///         .text("Status: ")
+    ///         .span(RichTextSpan::new("Online").bold().color(Color::green())),
+    /// );
+    /// ```
+    pub fn rich_text(cx: &mut Context, rich_text: RichText) -> Handle<Self> {
+        let name = rich_text.to_string();
+        Self::rich(cx, "", move |cx| {
+            for span in rich_text.spans() {
+                let mut handle = TextSpan::new(cx, &span.text, |_| {});
+
+                if let Some(color) = span.color {
+                    handle = handle.color(color);
+                }
+
+                if let Some(font_weight) = span.font_weight {
+                    handle = handle.font_weight(font_weight);
+                }
+
+                if let Some(font_slant) = span.font_slant {
+                    handle = handle.font_slant(font_slant);
+                }
+
+                if let Some(font_size) = span.font_size {
+                    handle = handle.font_size(font_size);
+                }
+
+                if let Some(url) = span.link.clone() {
+                    handle
+                        .class("link")
+                        .role(Role::Link)
+                        .cursor(CursorIcon::Hand)
+                        .pointer_events(PointerEvents::Auto)
+                        .on_press(move |cx| cx.emit(LinkEvent::OpenUrl(url.clone())));
+                }
+            }
+        })
+        .name(name)
+    }
+
+    /// Creates a new rich [Label] view which displays any URLs within the text as clickable links.
+    ///
+    /// Pressing a link emits a [`LinkEvent::OpenUrl`] event containing the URL, which propagates up the tree from the label.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use vizia_core::prelude::*;
+    /// #
+    /// # let cx = &mut Context::default();
+    /// #
+    /// Label::auto_linked(cx, "See https://github.com/vizia/vizia for more.");
+    /// ```
+    pub fn auto_linked(cx: &mut Context, text: &str) -> Handle<Self> {
+        Self::rich_text(cx, RichText::auto_linked(text))
+    }
 }
 
 impl Handle<'_, Label> {
@@ -218,3 +287,293 @@ impl View for TextSpan {
         Some("text-span")
     }
 }
+
+/// A description of styled runs of text which can be displayed by a [Label] using [Label::rich_text].
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RichText {
+    spans: Vec<RichTextSpan>,
+}
+
+impl RichText {
+    /// Creates a new empty [RichText].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a span of text which uses the style of the label.
+    pub fn text(self, text: impl Into<String>) -> Self {
+        self.span(RichTextSpan::new(text))
+    }
+
+    /// Appends a styled span of text.
+    pub fn span(mut self, span: RichTextSpan) -> Self {
+        self.spans.push(span);
+        self
+    }
+
+    /// Creates a new [RichText] from a string, converting any URLs within the text into link spans.
+    ///
+    /// URLs are detected as words starting with `http://`, `https://`, or `www.`. Trailing punctuation is not
+    /// considered part of the URL, except for closing brackets which match an opening bracket within the URL.
+    pub fn auto_linked(text: &str) -> Self {
+        let mut rich_text = Self::new();
+        let mut plain_start = 0;
+        let mut offset = 0;
+
+        for word in text.split_inclusive(char::is_whitespace) {
+            let trimmed = word.trim_end();
+            let url = trim_url(trimmed);
+
+            if ["http://", "https://", "www."].iter().any(|prefix| {
+                url.len() > prefix.len()
+                    && url.get(..prefix.len()).is_some_and(|s| s.eq_ignore_ascii_case(prefix))
+            }) {
+                if plain_start < offset {
+                    rich_text = rich_text.text(&text[plain_start..offset]);
+                }
+
+                let href = if url.starts_with("www.") {
+                    format!("https://{}", url)
+                } else {
+                    url.to_string()
+                };
+                rich_text = rich_text.span(RichTextSpan::new(url).link(href));
+                plain_start = offset + url.len();
+            }
+
+            offset += word.len();
+        }
+
+        if plain_start < text.len() {
+            rich_text = rich_text.text(&text[plain_start..]);
+        }
+
+        rich_text
+    }
+
+    /// Returns the spans of the rich text in order.
+    pub fn spans(&self) -> &[RichTextSpan] {
+        &self.spans
+    }
+}
+
+impl std::fmt::Display for RichText {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for span in self.spans.iter() {
+            f.write_str(&span.text)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A run of text within a [RichText] with optional style overrides.
+///
+/// Any style which is not set is inherited from the label.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RichTextSpan {
+    /// The text of the span.
+    pub text: String,
+    /// The color of the text.
+    pub color: Option<Color>,
+    /// The font weight of the text.
+    pub font_weight: Option<FontWeight>,
+    /// The font slant of the text.
+    pub font_slant: Option<FontSlant>,
+    /// The font size of the text in logical pixels.
+    pub font_size: Option<f32>,
+    /// The URL the span links to, if any.
+    pub link: Option<String>,
+}
+
+impl RichTextSpan {
+    /// Creates a new span of text which uses the style of the label.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self { text: text.into(), ..Default::default() }
+    }
+
+    /// Sets the color of the span.
+    pub fn color(mut self, color: impl Into<Color>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Sets the font weight of the span.
+    pub fn font_weight(mut self, font_weight: impl Into<FontWeight>) -> Self {
+        self.font_weight = Some(font_weight.into());
+        self
+    }
+
+    /// Sets the font weight of the span to bold.
+    pub fn bold(self) -> Self {
+        self.font_weight(FontWeightKeyword::Bold)
+    }
+
+    /// Sets the font slant of the span.
+    pub fn font_slant(mut self, font_slant: FontSlant) -> Self {
+        self.font_slant = Some(font_slant);
+        self
+    }
+
+    /// Sets the font slant of the span to italic.
+    pub fn italic(self) -> Self {
+        self.font_slant(FontSlant::Italic)
+    }
+
+    /// Sets the font size of the span in logical pixels.
+    pub fn font_size(mut self, font_size: f32) -> Self {
+        self.font_size = Some(font_size);
+        self
+    }
+
+    /// Makes the span a link to the given URL.
+    ///
+    /// Pressing the span emits a [`LinkEvent::OpenUrl`] event containing the URL.
+    pub fn link(mut self, url: impl Into<String>) -> Self {
+        self.link = Some(url.into());
+        self
+    }
+}
+
+/// Events emitted by links within a [Label].
+#[derive(Debug, Clone, PartialEq)]
+pub enum LinkEvent {
+    /// Emitted when a link is pressed, containing the URL of the link.
+    OpenUrl(String),
+}
+
+// Trims trailing punctuation from a URL, keeping closing brackets which are balanced within the URL, such as in
+// `https://en.wikipedia.org/wiki/Rust_(programming_language)`.
+fn trim_url(mut url: &str) -> &str {
+    while let Some(c) = url.chars().last() {
+        let open = match c {
+            ')' => '(',
+            ']' => '[',
+            '}' => '{',
+            '.' | ',' | ';' | ':' | '!' | '?' | '\'' | '"' => {
+                url = &url[..url.len() - 1];
+                continue;
+            }
+            _ => break,
+        };
+
+        if url.matches(c).count() <= url.matches(open).count() {
+            break;
+        }
+
+        url = &url[..url.len() - 1];
+    }
+
+    url
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::test_support::{update, window_context};
+
+    #[test]
+    fn rich_text_spans() {
+        let mut cx = window_context(400, 300);
+
+        let label = Label::rich_text(
+            cx.context(),
+            RichText::new()
+                .text("Status: ")
+                .span(RichTextSpan::new("Online").bold().color(Color::green()).font_size(20.0)),
+        )
+        .entity();
+
+        update(&mut cx);
+
+        let spans = label.child_iter(&cx.0.tree).collect::<Vec<_>>();
+        assert_eq!(spans.len(), 2);
+
+        // The spans are laid out one after another within the paragraph of the label.
+        let style = &cx.0.style;
+        assert!(cx.0.text_context.text_paragraphs.get(label).is_some());
+        assert_eq!(style.text_range.get(spans[0]), Some(&(0..8)));
+        assert_eq!(style.text_range.get(spans[1]), Some(&(8..14)));
+
+        // Only the styled span overrides the style of the label.
+        assert_eq!(style.font_weight.get(spans[0]), None);
+        assert_eq!(style.font_size.get(spans[0]), None);
+        assert_eq!(
+            style.font_weight.get(spans[1]),
+            Some(&FontWeight::from(FontWeightKeyword::Bold))
+        );
+        assert_eq!(style.font_color.get(spans[1]), Some(&Color::green()));
+        assert!(style.font_size.get(spans[1]).is_some());
+    }
+
+    #[test]
+    fn link_detection() {
+        let rich_text = RichText::auto_linked("See https://example.com, or www.vizia.dev.");
+
+        let spans = rich_text
+            .spans()
+            .iter()
+            .map(|span| (span.text.as_str(), span.link.as_deref()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            spans,
+            [
+                ("See ", None),
+                ("https://example.com", Some("https://example.com")),
+                (", or ", None),
+                ("www.vizia.dev", Some("https://www.vizia.dev")),
+                (".", None),
+            ]
+        );
+
+        assert_eq!(rich_text.to_string(), "See https://example.com, or www.vizia.dev.");
+
+        // Closing brackets are only trimmed when they aren't balanced within the URL.
+        let rich_text = RichText::auto_linked(
+            "(see https://en.wikipedia.org/wiki/Rust_(programming_language)).",
+        );
+        let spans = rich_text
+            .spans()
+            .iter()
+            .map(|span| (span.text.as_str(), span.link.as_deref()))
+            .collect::<Vec<_>>();
+        let url = "https://en.wikipedia.org/wiki/Rust_(programming_language)";
+        assert_eq!(spans, [("(see ", None), (url, Some(url)), (").", None)]);
+
+        // A bare prefix isn't a link.
+        assert!(RichText::auto_linked("https:// www.")
+            .spans()
+            .iter()
+            .all(|span| span.link.is_none()));
+    }
+
+    #[test]
+    fn pressing_link_emits_url() {
+        struct Links(Vec<String>);
+
+        impl Model for Links {
+            fn event(&mut self, _: &mut EventContext, event: &mut Event) {
+                event.map(|LinkEvent::OpenUrl(url), _| self.0.push(url.clone()));
+            }
+        }
+
+        let mut cx = window_context(400, 300);
+
+        Links(Vec::new()).build(cx.context());
+        let label = Label::auto_linked(cx.context(), "Visit https://example.com now").entity();
+
+        update(&mut cx);
+
+        let link = label
+            .child_iter(&cx.0.tree)
+            .find(|span| cx.0.style.role.get(*span) == Some(&Role::Link))
+            .unwrap();
+        cx.0.press(link);
+
+        update(&mut cx);
+
+        cx.set_current(Entity::root());
+        assert_eq!(cx.0.data::<Links>().unwrap().0, ["https://example.com"]);
+    }
+}
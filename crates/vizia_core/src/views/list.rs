@@ -1,5 +1,13 @@
-use std::{collections::BTreeSet, ops::Deref, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    collections::BTreeSet,
+    hash::Hash,
+    ops::Deref,
+    rc::Rc,
+};
 
+use super::keyed::update_keyed_items;
+use super::reorder::{moved_index, DragReorder};
 use crate::prelude::*;
 
 /// Represents how items can be selected in a list.
@@ -30,6 +38,30 @@ pub enum ListEvent {
     ClearSelection,
 }
 
+// Sent by the list content to update the indices of the group headers when the list is rebuilt.
+enum ListHeadersEvent {
+    Set(BTreeSet<usize>),
+}
+
+// Sent by the items of a keyed list when the list changes.
+enum ListItemsEvent {
+    // The new number of items and, for each previous item, its new index if it's still in the list.
+    Update { len: usize, moved: Option<Vec<Option<usize>>> },
+}
+
+// Events used to reorder the items of a list by dragging.
+enum ListDragEvent {
+    // Sent by a list item when the cursor is dragged out of it.
+    Start(usize),
+    // Sent by the auto-scroll timer while an item is dragged near the edge of the list.
+    AutoScroll,
+}
+
+// The distance, in logical pixels, from the edge of a list within which dragging an item scrolls the list.
+const AUTO_SCROLL_EDGE: f32 = 32.0;
+// The maximum distance, in logical pixels, the list is scrolled per tick while dragging an item.
+const AUTO_SCROLL_SPEED: f32 = 12.0;
+
 /// A view for creating a list of items from a binding to an iteratable list.
 #[derive(Lens)]
 pub struct List {
@@ -40,7 +72,20 @@ pub struct List {
     focus_visible: bool,
     selection_follows_focus: bool,
     horizontal: bool,
+    headers: BTreeSet<usize>,
+    columns: usize,
+    reorderable: bool,
+    // Whether the items of the list are identified by a key, which keeps them when the list changes.
+    #[lens(ignore)]
+    keyed: bool,
+    // The item being dragged to reorder it.
+    #[lens(ignore)]
+    drag: DragReorder,
+    // The timer which scrolls the list while an item is dragged near its edges, added when an item is first dragged.
+    #[lens(ignore)]
+    auto_scroll_timer: Option<Timer>,
     on_select: Option<Box<dyn Fn(&mut EventContext, usize)>>,
+    on_reorder: Option<Box<dyn Fn(&mut EventContext, usize, usize)>>,
 }
 
 impl List {
@@ -84,6 +129,127 @@ impl List {
         )
     }
 
+    /// Creates a new [List] view where the items for which `is_header` returns true are shown as group headers.
+    ///
+    /// Group headers are built as a [ListHeader] and cannot be focused or selected, so keyboard navigation skips over
+    /// them. When combined with [`columns`](Handle::columns), each header starts a new row spanning the whole list.
+    pub fn new_grouped<L: Lens, T: 'static>(
+        cx: &mut Context,
+        list: L,
+        is_header: impl 'static + Fn(&T) -> bool,
+        item_content: impl 'static + Fn(&mut Context, usize, MapRef<L, T>),
+    ) -> Handle<Self>
+    where
+        L::Target: Deref<Target = [T]> + Data,
+    {
+        Self::new_inner(
+            cx,
+            list,
+            |list| list.len(),
+            |list, index| &list[index],
+            |_| true,
+            Rc::new(is_header),
+            item_content,
+        )
+    }
+
+    /// Creates a new [List] view where each item is identified by the key returned by `key`.
+    ///
+    /// When the list changes, only the items with new keys are built and only the items whose keys were removed are
+    /// destroyed, while the other items are moved into place. This keeps the scroll position, the focus, and the state
+    /// of the item views, and the selection follows the items as they move. The index passed to `item_content` is
+    /// the index of the item when it was built.
+    ///
+    /// Keys should be unique, as only one of the items with the same key is kept. Lists with more than one
+    /// [column](Handle::columns) rebuild all of their items when the list changes.
+    ///
+    /// # Example
+    /// ```
+    /// # use vizia_core::prelude::*;
+    /// #
+    /// # #[derive(Clone, Data)]
+    /// # struct Task {
+    /// #     id: u32,
+    /// #     name: String,
+    /// # }
+    /// #
+    /// # #[derive(Lens)]
+    /// # struct AppData {
+    /// #     tasks: Vec<Task>,
+    /// # }
+    /// #
+    /// # impl Model for AppData {}
+    /// #
+    /// # let cx = &mut Context::default();
+    /// #
+    /// # AppData { tasks: vec![] }.build(cx);
+    /// #
+    /// List::new_keyed(cx, AppData::tasks, |task| task.id, |cx, _, task| {
+    ///     Label::new(cx, task.map(|task| task.name.clone()));
+    /// });
+    /// ```
+    pub fn new_keyed<L: Lens, T: 'static, K>(
+        cx: &mut Context,
+        list: L,
+        key: impl 'static + Fn(&T) -> K,
+        item_content: impl 'static + Fn(&mut Context, usize, MapRef<L, T>),
+    ) -> Handle<Self>
+    where
+        L::Target: Deref<Target = [T]> + Data,
+        K: 'static + Data + Hash + Eq,
+    {
+        let content = Rc::new(item_content);
+        let keys = list.map(move |list| list.iter().map(&key).collect::<Vec<_>>());
+        let build_item = Rc::new(move |cx: &mut Context, index: Rc<Cell<usize>>| {
+            let item_index = index.clone();
+            let item = list.map_ref(move |list| &list[item_index.get()]);
+            let content = content.clone();
+            ListItem::new_shared(cx, index, item, move |cx, index, item| {
+                content(cx, index, item);
+            });
+        });
+
+        let list_len = keys.get(cx).len();
+        Self::build_list(cx, list_len, true, move |cx, columns| {
+            let build_item = build_item.clone();
+            if columns > 1 {
+                Binding::new(cx, keys, move |cx, keys| {
+                    let len = keys.get(cx).len();
+                    let build_item = |cx: &mut Context, index: usize| {
+                        build_item(cx, Rc::new(Cell::new(index)));
+                    };
+
+                    let mut row = Vec::with_capacity(columns);
+                    for index in 0..len {
+                        row.push(index);
+                        if row.len() == columns {
+                            build_row(cx, &mut row, &build_item);
+                        }
+                    }
+
+                    build_row(cx, &mut row, &build_item);
+                    cx.emit(ListItemsEvent::Update { len, moved: None });
+                });
+            } else {
+                // The items are siblings placed after the binding, so that the binding updates their indices before
+                // the bindings within the items are updated.
+                let parent = cx.current();
+                let items = RefCell::new(Vec::new());
+                Binding::new(cx, keys, move |cx, keys| {
+                    let keys = keys.get(cx);
+                    let moved = update_keyed_items(
+                        cx,
+                        parent,
+                        &mut items.borrow_mut(),
+                        &keys,
+                        &*build_item,
+                    );
+                    cx.emit(ListItemsEvent::Update { len: keys.len(), moved: Some(moved) });
+                });
+            }
+        })
+    }
+
     /// Creates a new [List] view with a binding to the given lens and a template for constructing the list items.
     pub fn new_generic<L: Lens, T: 'static>(
         cx: &mut Context,
@@ -93,20 +259,127 @@ impl List {
         filter: impl 'static + Clone + FnMut(&&T) -> bool,
         item_content: impl 'static + Fn(&mut Context, usize, MapRef<L, T>),
     ) -> Handle<Self>
+    where
+        L::Target: Deref<Target = [T]> + Data,
+    {
+        Self::new_inner(
+            cx,
+            list,
+            list_len,
+            list_index,
+            filter,
+            Rc::new(|_: &T| false),
+            item_content,
+        )
+    }
+
+    fn new_inner<L: Lens, T: 'static>(
+        cx: &mut Context,
+        list: L,
+        list_len: impl 'static + Fn(&L::Target) -> usize,
+        list_index: impl 'static + Clone + Fn(&L::Target, usize) -> &T,
+        filter: impl 'static + Clone + FnMut(&&T) -> bool,
+        is_header: Rc<dyn Fn(&T) -> bool>,
+        item_content: impl 'static + Fn(&mut Context, usize, MapRef<L, T>),
+    ) -> Handle<Self>
     where
         L::Target: Deref<Target = [T]> + Data,
     {
         let content = Rc::new(item_content);
         let num_items = list.map(list_len);
+        let list_len = num_items.get(cx);
+        Self::build_list(cx, list_len, false, move |cx, columns| {
+            let filter = filter.clone();
+            let list_index = list_index.clone();
+            let is_header = is_header.clone();
+            let content = content.clone();
+
+            // Bind to the list data
+            Binding::new(cx, num_items, move |cx, _| {
+                // If the number of list items is different to the number of children of the ListView
+                // then remove and rebuild all the children
+
+                let mut f = filter.clone();
+                let list_data = list.get(cx);
+                let ll = list_data
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, v)| f(v))
+                    .map(|(idx, _)| idx)
+                    .collect::<Vec<_>>();
+
+                let headers = ll
+                    .iter()
+                    .copied()
+                    .filter(|index| is_header(list_index(&list_data, *index)))
+                    .collect::<BTreeSet<_>>();
+
+                let build_item = |cx: &mut Context, index: usize| {
+                    let ll = list_index.clone();
+                    let item = list.map_ref(move |list| ll(list, index));
+                    let content = content.clone();
+                    if headers.contains(&index) {
+                        ListHeader::new(cx, index, item, move |cx, index, item| {
+                            content(cx, index, item);
+                        });
+                    } else {
+                        ListItem::new(cx, index, item, move |cx, index, item| {
+                            content(cx, index, item);
+                        });
+                    }
+                };
+
+                if columns == 1 {
+                    for index in ll.into_iter() {
+                        build_item(cx, index);
+                    }
+                } else {
+                    // Lay the items out in rows of `columns` items, with each header on its own row.
+                    let mut row = Vec::with_capacity(columns);
+                    for index in ll.into_iter() {
+                        if headers.contains(&index) {
+                            build_row(cx, &mut row, &build_item);
+                            build_item(cx, index);
+                        } else {
+                            row.push(index);
+                            if row.len() == columns {
+                                build_row(cx, &mut row, &build_item);
+                            }
+                        }
+                    }
+
+                    build_row(cx, &mut row, &build_item);
+                }
+
+                cx.emit(ListHeadersEvent::Set(headers));
+            });
+        })
+    }
+
+    // Builds the list, calling `items` with the number of columns to build the items of the list whenever the number
+    // of columns changes.
+    fn build_list(
+        cx: &mut Context,
+        list_len: usize,
+        keyed: bool,
+        items: impl 'static + Fn(&mut Context, usize),
+    ) -> Handle<Self> {
         Self {
-            list_len: num_items.get(cx),
+            list_len,
             selected: BTreeSet::default(),
             selectable: Selectable::None,
             focused: None,
             focus_visible: false,
             selection_follows_focus: false,
             horizontal: false,
+            headers: BTreeSet::default(),
+            columns: 1,
+            reorderable: false,
+            keyed,
+            drag: DragReorder::default(),
+            auto_scroll_timer: None,
             on_select: None,
+            on_reorder: None,
         }
         .build(cx, move |cx| {
             Keymap::from(vec![
@@ -154,46 +427,259 @@ impl List {
             });
 
             ScrollView::new(cx, move |cx| {
-                // Bind to the list data
-                Binding::new(cx, num_items, move |cx, _| {
-                    // If the number of list items is different to the number of children of the ListView
-                    // then remove and rebuild all the children
-
-                    let mut f = filter.clone();
-                    let ll = list
-                        .get(cx)
-                        .iter()
-                        .enumerate()
-                        .filter(|(_, v)| f(v))
-                        .map(|(idx, _)| idx)
-                        .collect::<Vec<_>>();
-
-                    for index in ll.into_iter() {
-                        let ll = list_index.clone();
-                        let item = list.map_ref(move |list| ll(list, index));
-                        let content = content.clone();
-                        ListItem::new(cx, index, item, move |cx, index, item| {
-                            content(cx, index, item);
-                        });
-                    }
+                Binding::new(cx, List::columns, move |cx, columns| {
+                    let columns = columns.get(cx).max(1);
+                    items(cx, columns);
                 });
             });
         })
         .toggle_class("selectable", List::selectable.map(|s| *s != Selectable::None))
         .toggle_class("horizontal", List::horizontal)
+        .toggle_class("reorderable", List::reorderable)
         .navigable(true)
         .role(Role::List)
     }
 }
 
+// Builds the items of a row of a multi-column list, leaving the row empty.
+fn build_row(cx: &mut Context, row: &mut Vec<usize>, build_item: &dyn Fn(&mut Context, usize)) {
+    if row.is_empty() {
+        return;
+    }
+
+    let indices = std::mem::take(row);
+    HStack::new(cx, |cx| {
+        for index in indices {
+            build_item(cx, index);
+        }
+    })
+    .class("list-row");
+}
+
+impl List {
+    // Returns the index of the next item to focus in the given direction, skipping any group headers.
+    fn next_focus(&self, forward: bool) -> Option<usize> {
+        let last = self.list_len.checked_sub(1)?;
+        let mut index = self.focused;
+
+        for _ in 0..self.list_len {
+            let next = match index {
+                Some(i) if forward => {
+                    if i >= last {
+                        0
+                    } else {
+                        i + 1
+                    }
+                }
+                Some(i) => {
+                    if i == 0 {
+                        last
+                    } else {
+                        i - 1
+                    }
+                }
+                None if forward => 0,
+                None => last,
+            };
+
+            if !self.headers.contains(&next) {
+                return Some(next);
+            }
+
+            index = Some(next);
+        }
+
+        self.focused
+    }
+
+    // Returns the items of the list along with their indices.
+    fn items(cx: &EventContext) -> Vec<(Entity, usize)> {
+        cx.current()
+            .branch_iter(cx.tree)
+            .filter_map(|entity| {
+                let item = cx.views.get(&entity)?.downcast_ref::<ListItem>()?;
+                Some((entity, item.index.get()))
+            })
+            .collect()
+    }
+
+    // Moves the drop indicator to the item closest to the cursor and starts or stops auto-scrolling.
+    fn update_drag(&mut self, cx: &mut EventContext) {
+        let items = Self::items(cx);
+        self.drag.update(cx, &items, self.horizontal || self.columns > 1);
+
+        if self.auto_scroll_speed(cx) != 0.0 {
+            let timer = *self.auto_scroll_timer.get_or_insert_with(|| {
+                cx.add_timer(Duration::from_millis(16), None, |cx, action| {
+                    if let TimerAction::Tick(_) = action {
+                        cx.emit(ListDragEvent::AutoScroll);
+                    }
+                })
+            });
+            cx.start_timer(timer);
+        } else if let Some(timer) = self.auto_scroll_timer {
+            cx.stop_timer(timer);
+        }
+    }
+
+    // Returns the speed, in logical pixels per tick, to scroll the list while the cursor is near one of its edges.
+    fn auto_scroll_speed(&self, cx: &EventContext) -> f32 {
+        let bounds = cx.bounds();
+        let edge = AUTO_SCROLL_EDGE * cx.scale_factor();
+        let (position, start, end) = if self.horizontal {
+            (cx.mouse.cursor_x, bounds.left(), bounds.right())
+        } else {
+            (cx.mouse.cursor_y, bounds.top(), bounds.bottom())
+        };
+
+        if position < start + edge {
+            -AUTO_SCROLL_SPEED * ((start + edge - position) / edge).min(1.0)
+        } else if position > end - edge {
+            AUTO_SCROLL_SPEED * ((position - end + edge) / edge).min(1.0)
+        } else {
+            0.0
+        }
+    }
+
+    // Scrolls the scrollview of the list towards the edge the cursor is near.
+    fn auto_scroll(&self, cx: &mut EventContext) {
+        let speed = self.auto_scroll_speed(cx) * cx.scale_factor();
+        let Some((entity, scroll_view)) = cx.current().branch_iter(cx.tree).find_map(|entity| {
+            let scroll_view = cx.views.get(&entity)?.downcast_ref::<ScrollView>()?;
+            Some((entity, scroll_view))
+        }) else {
+            return;
+        };
+
+        let (progress, negative_space) = if self.horizontal {
+            (scroll_view.scroll_x, scroll_view.inner_width - scroll_view.container_width)
+        } else {
+            (scroll_view.scroll_y, scroll_view.inner_height - scroll_view.container_height)
+        };
+
+        if negative_space <= 0.0 {
+            return;
+        }
+
+        // Clamp the delta so that auto-scrolling never overscrolls the content.
+        let delta = (speed / negative_space).clamp(-progress, 1.0 - progress);
+        if delta != 0.0 {
+            if self.horizontal {
+                cx.emit_to(entity, ScrollEvent::ScrollX(delta));
+            } else {
+                cx.emit_to(entity, ScrollEvent::ScrollY(delta));
+            }
+        }
+    }
+
+    // Ends a drag, reordering the dragged item if it was dropped on the list.
+    fn end_drag(&mut self, cx: &mut EventContext, drop: bool) {
+        if let Some(timer) = self.auto_scroll_timer {
+            cx.stop_timer(timer);
+        }
+
+        let Some((from, to)) = self.drag.end(cx, drop) else { return };
+
+        if let Some(on_reorder) = &self.on_reorder {
+            on_reorder(cx, from, to);
+        }
+
+        // Keyed lists move the selection and focus along with the items once the data has changed.
+        if self.keyed {
+            return;
+        }
+
+        // Keep the selection and focus on the same items once they have been moved.
+        let move_index = |index| moved_index(index, from, to);
+        self.selected = self.selected.iter().copied().map(move_index).collect();
+        self.focused = self.focused.map(move_index);
+    }
+}
+
 impl View for List {
     fn element(&self) -> Option<&'static str> {
         Some("list")
     }
 
     fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.take(|headers_event, _| match headers_event {
+            ListHeadersEvent::Set(headers) => {
+                if self.focused.is_some_and(|focused| headers.contains(&focused)) {
+                    self.focused = None;
+                }
+
+                self.selected.retain(|index| !headers.contains(index));
+                self.headers = headers;
+            }
+        });
+
+        event.take(|items_event, _| match items_event {
+            ListItemsEvent::Update { len, moved } => {
+                self.list_len = len;
+                if let Some(moved) = moved {
+                    if self.drag.dragging().is_some() {
+                        self.end_drag(cx, false);
+                    }
+
+                    let move_index = |index: usize| moved.get(index).copied().flatten();
+                    self.selected = self.selected.iter().filter_map(|i| move_index(*i)).collect();
+                    self.focused = self.focused.and_then(move_index);
+                }
+            }
+        });
+
+        event.take(|drag_event, meta| match drag_event {
+            ListDragEvent::Start(index) => {
+                if self.reorderable && self.drag.dragging().is_none() && !cx.is_disabled() {
+                    self.drag.start(cx, meta.origin, index);
+                    self.update_drag(cx);
+                }
+            }
+
+            ListDragEvent::AutoScroll => {
+                if self.drag.dragging().is_some() {
+                    self.auto_scroll(cx);
+                    self.update_drag(cx);
+                }
+            }
+        });
+
+        event.map(|window_event, _| {
+            if let WindowEvent::Destroyed = window_event {
+                if let Some(timer) = self.auto_scroll_timer.take() {
+                    cx.remove_timer(timer);
+                }
+            }
+        });
+
+        event.map(|window_event, meta| {
+            if self.drag.dragging().is_none() {
+                return;
+            }
+
+            match window_event {
+                WindowEvent::MouseMove(_, _) => self.update_drag(cx),
+
+                WindowEvent::MouseUp(MouseButton::Left) => {
+                    self.end_drag(cx, true);
+                    meta.consume();
+                }
+
+                WindowEvent::KeyDown(Code::Escape, _) => {
+                    self.end_drag(cx, false);
+                    meta.consume();
+                }
+
+                _ => {}
+            }
+        });
+
         event.take(|list_event, _| match list_event {
             ListEvent::Select(index) => {
+                if self.headers.contains(&index) {
+                    return;
+                }
+
                 cx.focus();
                 match self.selectable {
                     Selectable::Single => {
@@ -240,15 +726,7 @@ impl View for List {
             }
 
             ListEvent::FocusNext => {
-                if let Some(focused) = &mut self.focused {
-                    *focused = focused.saturating_add(1);
-
-                    if *focused >= self.list_len {
-                        *focused = 0;
-                    }
-                } else {
-                    self.focused = Some(0);
-                }
+                self.focused = self.next_focus(true);
 
                 self.focus_visible = true;
 
@@ -258,15 +736,7 @@ impl View for List {
             }
 
             ListEvent::FocusPrev => {
-                if let Some(focused) = &mut self.focused {
-                    if *focused == 0 {
-                        *focused = self.list_len;
-                    }
-
-                    *focused = focused.saturating_sub(1);
-                } else {
-                    self.focused = Some(self.list_len.saturating_sub(1));
-                }
+                self.focused = self.next_focus(false);
 
                 self.focus_visible = true;
 
@@ -303,6 +773,31 @@ impl Handle<'_, List> {
         self.modify(|list: &mut List| list.on_select = Some(Box::new(callback)))
     }
 
+    /// Sets whether the items of the list can be reordered by dragging them.
+    ///
+    /// While an item is dragged, the item it would be dropped next to has the `drop-before` or `drop-after` class
+    /// and the list scrolls when the cursor is near its edges. Dropping the item triggers the
+    /// [`on_reorder`](Handle::on_reorder) callback, which should move the item within the bound data. Pressing the
+    /// escape key cancels the drag.
+    pub fn reorderable<U: Into<bool>>(self, flag: impl Res<U>) -> Self {
+        self.bind(flag, |handle, reorderable| {
+            let reorderable = reorderable.get(&handle).into();
+            handle.modify(|list: &mut List| list.reorderable = reorderable);
+        })
+    }
+
+    /// Sets the callback triggered when an item of a [reorderable](Handle::reorderable) list is dropped at a new
+    /// position.
+    ///
+    /// Callback provides the index of the dragged item and the index it should be moved to once it has been removed
+    /// from the data, so that a `Vec` can be updated with `let item = vec.remove(from); vec.insert(to, item);`.
+    pub fn on_reorder<F>(self, callback: F) -> Self
+    where
+        F: 'static + Fn(&mut EventContext, usize, usize),
+    {
+        self.modify(|list: &mut List| list.on_reorder = Some(Box::new(callback)))
+    }
+
     /// Set the selectable state of the [List].
     pub fn selectable<U: Into<Selectable>>(self, selectable: impl Res<U>) -> Self {
         self.bind(selectable, |handle, selectable| {
@@ -319,6 +814,16 @@ impl Handle<'_, List> {
         })
     }
 
+    /// Sets the number of columns used to lay out the items of the list, which is useful for large sets of options.
+    ///
+    /// Items are placed in rows from left to right, and each group header starts a new row. Defaults to one column.
+    pub fn columns(self, columns: impl Res<usize>) -> Self {
+        self.bind(columns, |handle, columns| {
+            let columns = columns.get(&handle).max(1);
+            handle.modify(|list: &mut List| list.columns = columns);
+        })
+    }
+
     // todo: replace with orientation
     /// Sets the orientation of the list.
     pub fn horizontal<U: Into<bool>>(self, flag: impl Res<U>) -> Self {
@@ -330,7 +835,9 @@ impl Handle<'_, List> {
 }
 
 /// A view which represents a selectable item within a list.
-pub struct ListItem {}
+pub struct ListItem {
+    index: Rc<Cell<usize>>,
+}
 
 impl ListItem {
     /// Create a new [ListItem] view.
@@ -340,18 +847,32 @@ impl ListItem {
         item: MapRef<L, T>,
         item_content: impl 'static + Fn(&mut Context, usize, MapRef<L, T>),
     ) -> Handle<Self> {
-        Self {}
+        Self::new_shared(cx, Rc::new(Cell::new(index)), item, item_content)
+    }
+
+    // Creates an item whose index is shared with a keyed list, which updates it when the item moves.
+    fn new_shared<L: Lens, T: 'static>(
+        cx: &mut Context,
+        index: Rc<Cell<usize>>,
+        item: MapRef<L, T>,
+        item_content: impl 'static + Fn(&mut Context, usize, MapRef<L, T>),
+    ) -> Handle<Self> {
+        let content_index = index.get();
+        let (checked, focused, pressed, dragged) =
+            (index.clone(), index.clone(), index.clone(), index.clone());
+        Self { index }
             .build(cx, move |cx| {
-                item_content(cx, index, item);
+                item_content(cx, content_index, item);
             })
             .role(Role::ListItem)
-            .checked(List::selected.map(move |selected| selected.contains(&index)))
+            .checked(List::selected.map(move |selected| selected.contains(&checked.get())))
             //.toggle_class("focused", List::focused.map(move |focused| *focused == Some(index)))
             .focused_with_visibility(
-                List::focused.map(move |f| *f == Some(index)),
+                List::focused.map(move |f| *f == Some(focused.get())),
                 List::focus_visible,
             )
-            .on_press(move |cx| cx.emit(ListEvent::Select(index)))
+            .on_press(move |cx| cx.emit(ListEvent::Select(pressed.get())))
+            .on_drag(move |cx| cx.emit(ListDragEvent::Start(dragged.get())))
     }
 }
 
@@ -360,3 +881,284 @@ impl View for ListItem {
         Some("list-item")
     }
 }
+
+/// A view which represents a non-selectable group header within a list.
+pub struct ListHeader {}
+
+impl ListHeader {
+    /// Create a new [ListHeader] view.
+    pub fn new<L: Lens, T: 'static>(
+        cx: &mut Context,
+        index: usize,
+        item: MapRef<L, T>,
+        item_content: impl 'static + Fn(&mut Context, usize, MapRef<L, T>),
+    ) -> Handle<Self> {
+        Self {}
+            .build(cx, move |cx| {
+                item_content(cx, index, item);
+            })
+            .role(Role::Heading)
+    }
+}
+
+impl View for ListHeader {
+    fn element(&self) -> Option<&'static str> {
+        Some("list-header")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::BackendContext;
+    use crate::binding::Store;
+    use crate::context::test_support::{update, window_context};
+
+    #[derive(Lens)]
+    struct AppData {
+        items: Vec<String>,
+    }
+
+    impl Model for AppData {
+        fn event(&mut self, _: &mut EventContext, event: &mut Event) {
+            event.map(|items: &Vec<String>, _| self.items.clone_from(items));
+        }
+    }
+
+    #[test]
+    fn grouped_navigation_skips_headers() {
+        let mut cx = window_context(400, 300);
+
+        let items = ["# Fruit", "Apple", "Pear", "# Vegetables", "Carrot"];
+        AppData { items: items.iter().map(|item| item.to_string()).collect() }.build(cx.context());
+
+        let list = List::new_grouped(
+            cx.context(),
+            AppData::items,
+            |item| item.starts_with('#'),
+            |cx, _, item| {
+                Label::new(cx, item);
+            },
+        )
+        .selectable(Selectable::Single)
+        .entity();
+
+        update(&mut cx);
+
+        let focused = |cx: &BackendContext| {
+            cx.0.views.get(&list).and_then(|view| view.downcast_ref::<List>()).unwrap().focused
+        };
+
+        // Focusing forwards skips the headers and wraps around to the first item.
+        let mut forward = Vec::new();
+        for _ in 0..4 {
+            cx.0.emit_to(list, ListEvent::FocusNext);
+            update(&mut cx);
+            forward.push(focused(&cx));
+        }
+        assert_eq!(forward, [Some(1), Some(2), Some(4), Some(1)]);
+
+        // Focusing backwards from the first item wraps around to the last one.
+        cx.0.emit_to(list, ListEvent::FocusPrev);
+        update(&mut cx);
+        assert_eq!(focused(&cx), Some(4));
+
+        // Headers can't be selected.
+        cx.0.emit_to(list, ListEvent::Select(3));
+        update(&mut cx);
+        let list = cx.0.views.get(&list).and_then(|view| view.downcast_ref::<List>()).unwrap();
+        assert!(list.selected.is_empty());
+    }
+
+    #[test]
+    fn rebuilding_drops_bindings() {
+        let mut cx = window_context(400, 300);
+
+        let items = |len: usize| (0..len).map(|index| index.to_string()).collect::<Vec<_>>();
+        AppData { items: items(3) }.build(cx.context());
+
+        List::new(cx.context(), AppData::items, |cx, _, item| {
+            Label::new(cx, item).bind(AppData::items, |handle, items| {
+                let len = items.get(&handle).len();
+                handle.toggle_class("long", len > 5);
+            });
+        });
+
+        update(&mut cx);
+
+        let count = |cx: &BackendContext| {
+            let observers =
+                cx.0.stores
+                    .values()
+                    .flat_map(|stores| stores.values())
+                    .map(|store| store.num_observers())
+                    .sum::<usize>();
+            (cx.0.bindings.len(), observers)
+        };
+
+        let initial = count(&cx);
+
+        // Growing and shrinking the list rebuilds its items, which must not leave the bindings of removed items behind.
+        for _ in 0..20 {
+            cx.0.emit_to(Entity::root(), items(10));
+            update(&mut cx);
+            cx.0.emit_to(Entity::root(), items(3));
+            update(&mut cx);
+        }
+
+        assert_eq!(count(&cx), initial);
+    }
+
+    #[test]
+    fn auto_scroll_timer_added_when_dragged() {
+        let mut cx = window_context(400, 300);
+
+        AppData { items: (0..50).map(|index| index.to_string()).collect() }.build(cx.context());
+
+        let timers = cx.0.timers.len();
+        let list = List::new(cx.context(), AppData::items, |cx, _, item| {
+            Label::new(cx, item).height(Pixels(30.0));
+        })
+        .reorderable(true)
+        .size(Pixels(200.0))
+        .entity();
+
+        update(&mut cx);
+        assert_eq!(cx.0.timers.len(), timers);
+
+        // Dragging an item near the bottom edge of the list starts auto-scrolling.
+        let item = cx.0.query("list-item")[0];
+        cx.send_event(Event::new(WindowEvent::MouseMove(100.0, 195.0)).origin(Entity::root()));
+        cx.0.emit_custom(Event::new(ListDragEvent::Start(0)).target(list).origin(item));
+        update(&mut cx);
+
+        let timer =
+            cx.0.views
+                .get(&list)
+                .and_then(|view| view.downcast_ref::<List>())
+                .and_then(|list| list.auto_scroll_timer)
+                .unwrap();
+        assert_eq!(cx.0.timers.len(), timers + 1);
+        assert!(cx.0.timer_is_running(timer));
+
+        // Removing the list removes its timer.
+        cx.0.remove(list);
+        assert!(cx.0.timers[timer.0].removed);
+    }
+
+    // Builds a keyed list of the items with an element with the class of the item as the content of each.
+    fn keyed_list(cx: &mut BackendContext, items: &[&str]) -> Handle<'_, List> {
+        AppData { items: items.iter().map(|item| item.to_string()).collect() }.build(cx.context());
+
+        List::new_keyed(
+            cx.context(),
+            AppData::items,
+            |item| item.clone(),
+            |cx, _, item| {
+                let name = item.get(cx);
+                Element::new(cx).class(&name);
+            },
+        )
+    }
+
+    fn set_items(cx: &mut BackendContext, items: &[&str]) {
+        cx.0.emit_to(Entity::root(), items.iter().map(|item| item.to_string()).collect::<Vec<_>>());
+        update(cx);
+    }
+
+    // Returns the content of each list item in order.
+    fn item_contents(cx: &BackendContext) -> Vec<Entity> {
+        cx.0.query("list-item")
+            .into_iter()
+            .filter_map(|item| cx.0.tree.get_first_child(item))
+            .collect()
+    }
+
+    fn list_state(cx: &BackendContext, list: Entity) -> &List {
+        cx.0.views.get(&list).and_then(|view| view.downcast_ref::<List>()).unwrap()
+    }
+
+    #[test]
+    fn keyed_items_keep_entities() {
+        let mut cx = window_context(400, 300);
+
+        keyed_list(&mut cx, &["a", "b", "c"]);
+        update(&mut cx);
+
+        let (a, c) = (cx.0.query(".a")[0], cx.0.query(".c")[0]);
+        assert_eq!(item_contents(&cx).len(), 3);
+
+        // Removing, inserting and reordering items only builds the new items and moves the others into place.
+        set_items(&mut cx, &["c", "a", "d"]);
+
+        assert!(cx.0.query(".b").is_empty());
+        let d = cx.0.query(".d")[0];
+        assert_eq!(item_contents(&cx), [c, a, d]);
+    }
+
+    #[test]
+    fn keyed_selection_follows_items() {
+        let mut cx = window_context(400, 300);
+
+        let list = keyed_list(&mut cx, &["a", "b", "c"]).selectable(Selectable::Single).entity();
+        update(&mut cx);
+
+        cx.0.emit_to(list, ListEvent::Select(1));
+        update(&mut cx);
+        assert_eq!(list_state(&cx, list).selected, BTreeSet::from([1]));
+        assert_eq!(list_state(&cx, list).focused, Some(1));
+
+        // The selection and focus move along with the selected item.
+        set_items(&mut cx, &["b", "c", "a"]);
+        assert_eq!(list_state(&cx, list).selected, BTreeSet::from([0]));
+        assert_eq!(list_state(&cx, list).focused, Some(0));
+
+        // Removing the selected item clears the selection and focus.
+        set_items(&mut cx, &["c", "a"]);
+        assert!(list_state(&cx, list).selected.is_empty());
+        assert_eq!(list_state(&cx, list).focused, None);
+        assert_eq!(list_state(&cx, list).list_len, 2);
+    }
+
+    #[test]
+    fn keyed_duplicate_keys() {
+        let mut cx = window_context(400, 300);
+
+        let list = keyed_list(&mut cx, &["a", "a", "b"]).entity();
+        update(&mut cx);
+
+        // Items with the same key are each built.
+        assert_eq!(cx.0.query(".a").len(), 2);
+        let b = cx.0.query(".b")[0];
+
+        // Only one of the items with a duplicate key is kept when the list changes.
+        set_items(&mut cx, &["b", "a"]);
+        assert_eq!(cx.0.query(".a").len(), 1);
+        assert_eq!(item_contents(&cx), [b, cx.0.query(".a")[0]]);
+        assert_eq!(list_state(&cx, list).list_len, 2);
+
+        // Duplicate keys which are added later are built too.
+        set_items(&mut cx, &["b", "a", "b"]);
+        assert_eq!(item_contents(&cx).len(), 3);
+        assert_eq!(item_contents(&cx)[0], b);
+        assert_eq!(list_state(&cx, list).list_len, 3);
+    }
+
+    #[test]
+    fn keyed_columns_rebuild_items() {
+        let mut cx = window_context(400, 300);
+
+        let list = keyed_list(&mut cx, &["a", "b", "c"]).columns(2).entity();
+        update(&mut cx);
+
+        assert_eq!(cx.0.query(".list-row").len(), 2);
+        let a = cx.0.query(".a")[0];
+
+        // Lists with more than one column rebuild all of their items when the list changes.
+        set_items(&mut cx, &["a", "b", "c", "d"]);
+        assert_eq!(cx.0.query(".list-row").len(), 2);
+        assert_eq!(item_contents(&cx).len(), 4);
+        assert_ne!(cx.0.query(".a")[0], a);
+        assert_eq!(list_state(&cx, list).list_len, 4);
+    }
+}
@@ -2,12 +2,16 @@
 
 use std::cell::RefCell;
 
-use comrak::nodes::{Ast, NodeValue};
+use comrak::nodes::{Ast, ListDelimType, ListType, NodeValue};
 use comrak::{parse_document, Arena, Options};
 
 use crate::prelude::*;
 
 /// A view which parses and displays markdown as rich text.
+///
+/// Headings, paragraphs, emphasis, lists, links, code and tables are supported. Pressing a link emits a
+/// [`LinkEvent::OpenUrl`] event containing the URL, which propagates up the tree and opens the URL with the default
+/// application of the platform once it reaches the markdown view.
 pub struct Markdown {}
 
 impl Markdown {
@@ -37,6 +41,16 @@ impl View for Markdown {
     fn element(&self) -> Option<&'static str> {
         Some("markdown")
     }
+
+    fn event(&mut self, _: &mut EventContext, event: &mut Event) {
+        event.map(|link_event, _| match link_event {
+            LinkEvent::OpenUrl(url) => {
+                if let Err(err) = open::that(url.as_str()) {
+                    log::warn!("Failed to open link '{}': {}", url, err);
+                }
+            }
+        });
+    }
 }
 
 fn parse_node<'a>(
@@ -112,9 +126,21 @@ fn parse_node<'a>(
             .left(Pixels(20.0));
         }
 
-        NodeValue::Item(_list) => {
+        NodeValue::Item(list) => {
+            // Ordered list items are numbered from the start of the list.
+            let marker = match list.list_type {
+                ListType::Bullet => String::from("\u{2022} "),
+                ListType::Ordered => {
+                    let index = list.start + node.preceding_siblings().count() - 1;
+                    match list.delimiter {
+                        ListDelimType::Period => format!("{}. ", index),
+                        ListDelimType::Paren => format!("{}) ", index),
+                    }
+                }
+            };
+
             HStack::new(cx, |cx| {
-                Label::new(cx, "\u{2022} ").width(Auto);
+                Label::new(cx, marker).width(Auto);
                 VStack::new(cx, |cx| {
                     for child in node.children() {
                         parse_node(cx, child, list_level + 1);
@@ -148,11 +174,10 @@ fn parse_node<'a>(
                     parse_node(cx, child, list_level);
                 }
             })
+            .role(Role::Link)
             .cursor(CursorIcon::Hand)
             .pointer_events(PointerEvents::Auto)
-            .on_press(move |_| {
-                open::that(url.as_str()).unwrap();
-            })
+            .on_press(move |cx| cx.emit(LinkEvent::OpenUrl(url.clone())))
             .class("link");
         }
 
@@ -201,3 +226,46 @@ fn parse_node<'a>(
         _ => {}
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::test_support::{update, window_context};
+
+    #[test]
+    fn markdown_tree() {
+        let mut cx = window_context(400, 300);
+
+        Markdown::new(
+            cx.context(),
+            "# Title\n\nSome **bold** and *italic* text with `code` and a [link](https://example.com).\n\n1. one\n2. two\n",
+        );
+
+        update(&mut cx);
+
+        let count = |selector: &str| cx.0.query(selector).len();
+
+        assert_eq!(count("markdown > label.h1"), 1);
+        assert_eq!(count("markdown > label.p"), 1);
+        assert_eq!(count("label.p > text-span.strong"), 1);
+        assert_eq!(count("label.p > text-span.emph"), 1);
+        assert_eq!(count("label.p > text-span.code"), 1);
+        assert_eq!(count(".li"), 2);
+
+        let links = cx.0.query("label.p > text-span.link");
+        assert_eq!(links.len(), 1);
+        assert_eq!(cx.0.style.role.get(links[0]), Some(&Role::Link));
+
+        // The text of the link is a span within the link.
+        let text = links[0].child_iter(&cx.0.tree).next().unwrap();
+        assert_eq!(cx.0.style.text.get(text).map(String::as_str), Some("link"));
+
+        // Ordered list items are numbered.
+        let markers =
+            cx.0.query(".li > label")
+                .into_iter()
+                .filter_map(|marker| cx.0.style.text.get(marker).cloned())
+                .collect::<Vec<_>>();
+        assert_eq!(markers, ["1. ", "2. "]);
+    }
+}
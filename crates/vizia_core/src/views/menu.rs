@@ -1,5 +1,8 @@
 use crate::modifiers::ModalEvent;
-use crate::{icons::ICON_CHEVRON_RIGHT, prelude::*};
+use crate::{
+    icons::{ICON_CHECK, ICON_CHEVRON_RIGHT},
+    prelude::*,
+};
 
 /// A view which represents a horizontal group of menus.
 #[derive(Lens)]
@@ -185,6 +188,25 @@ impl View for Submenu {
             }
 
             WindowEvent::KeyDown(code, _) => match code {
+                Code::ArrowDown
+                    if meta.target == cx.current && !self.is_submenu && !self.is_open =>
+                {
+                    self.is_open = true;
+                    cx.emit(MenuEvent::MenuIsOpen);
+                    meta.consume();
+                }
+
+                Code::ArrowDown | Code::ArrowUp if meta.target == cx.current => {
+                    focus_sibling(cx, *code == Code::ArrowDown);
+                    meta.consume();
+                }
+
+                Code::Escape => {
+                    cx.emit(MenuEvent::CloseAll);
+                    cx.emit(ModalEvent::HideMenu);
+                    meta.consume();
+                }
+
                 Code::ArrowLeft => {
                     // if cx.is_focused() {
                     if self.is_open {
@@ -263,6 +285,32 @@ impl MenuButton {
             .role(Role::MenuItem)
             .navigable(true)
     }
+
+    /// Creates a new [MenuButton] view which shows a check mark when `checked` is true.
+    ///
+    /// The action is typically used to toggle the bound value.
+    ///
+    /// ```ignore
+    /// MenuButton::new_checkable(
+    ///     cx,
+    ///     AppData::show_grid,
+    ///     |cx| cx.emit(AppEvent::ToggleGrid),
+    ///     |cx| Label::new(cx, "Show Grid"),
+    /// );
+    /// ```
+    pub fn new_checkable<V: View>(
+        cx: &mut Context,
+        checked: impl Res<bool>,
+        action: impl Fn(&mut EventContext) + Send + Sync + 'static,
+        content: impl Fn(&mut Context) -> Handle<V> + 'static,
+    ) -> Handle<Self> {
+        Self::new(cx, action, move |cx| {
+            Svg::new(cx, ICON_CHECK).class("check").hoverable(false);
+            (content)(cx)
+        })
+        .role(Role::MenuItemCheckBox)
+        .checked(checked)
+    }
 }
 
 impl View for MenuButton {
@@ -281,11 +329,127 @@ impl View for MenuButton {
                 }
             }
 
+            WindowEvent::KeyDown(code, _) if meta.target == cx.current => match code {
+                Code::ArrowDown | Code::ArrowUp => {
+                    focus_sibling(cx, *code == Code::ArrowDown);
+                    meta.consume();
+                }
+
+                Code::Escape => {
+                    cx.emit(MenuEvent::CloseAll);
+                    cx.emit(ModalEvent::HideMenu);
+                    meta.consume();
+                }
+
+                _ => {}
+            },
+
             _ => {}
         });
     }
 }
 
+// Moves keyboard focus to the next or previous navigable sibling of the current menu item, if there is one.
+fn focus_sibling(cx: &mut EventContext, forward: bool) {
+    let mut sibling = cx.current;
+    while let Some(next) =
+        if forward { cx.tree.get_next_sibling(sibling) } else { cx.tree.get_prev_sibling(sibling) }
+    {
+        sibling = next;
+        let navigable = cx
+            .style
+            .abilities
+            .get(sibling)
+            .is_some_and(|abilities| abilities.contains(Abilities::NAVIGABLE));
+        let disabled = cx.style.disabled.get(sibling).copied().unwrap_or_default();
+        if navigable && !disabled {
+            cx.with_current(sibling, |cx| cx.focus());
+            return;
+        }
+    }
+}
+
+/// Events used by the context menu of a view.
+pub(crate) enum ContextMenuEvent {
+    Open(f32, f32),
+    Close,
+}
+
+// A view which shows a popup menu at the position of the cursor when its parent is right-clicked.
+#[derive(Lens)]
+pub(crate) struct ContextMenu {
+    is_open: bool,
+    position: (f32, f32),
+}
+
+impl ContextMenu {
+    pub(crate) fn new(cx: &mut Context, menu: impl Fn(&mut Context) + 'static) -> Handle<Self> {
+        Self { is_open: false, position: (0.0, 0.0) }
+            .build(cx, |cx| {
+                cx.add_listener(|_: &mut Self, cx, event| {
+                    event.map(|window_event, _| match window_event {
+                        WindowEvent::MouseDown(MouseButton::Right) => {
+                            let parent = cx.parent();
+                            if cx.hovered == parent || cx.hovered.is_descendant_of(cx.tree, parent)
+                            {
+                                let parent_bounds = cx.cache.get_bounds(parent);
+                                let scale = cx.scale_factor();
+                                cx.emit(ContextMenuEvent::Open(
+                                    (cx.mouse.cursor_x - parent_bounds.x) / scale,
+                                    (cx.mouse.cursor_y - parent_bounds.y) / scale,
+                                ));
+                            }
+                        }
+
+                        _ => {}
+                    });
+                });
+
+                Binding::new(cx, ContextMenu::is_open, move |cx, is_open| {
+                    if is_open.get(cx) {
+                        Popup::new(cx, |cx| (menu)(cx))
+                            .placement(Placement::Over)
+                            .show_arrow(false)
+                            .role(Role::Menu)
+                            .on_blur(|cx| cx.emit(ContextMenuEvent::Close));
+                    }
+                });
+            })
+            .position_type(PositionType::Absolute)
+            .left(ContextMenu::position.map(|position| Pixels(position.0)))
+            .top(ContextMenu::position.map(|position| Pixels(position.1)))
+            .size(Pixels(0.0))
+            .hoverable(false)
+    }
+}
+
+impl View for ContextMenu {
+    fn element(&self) -> Option<&'static str> {
+        Some("context-menu")
+    }
+
+    fn event(&mut self, _: &mut EventContext, event: &mut Event) {
+        event.map(|context_menu_event, meta| match context_menu_event {
+            ContextMenuEvent::Open(x, y) => {
+                self.position = (*x, *y);
+                self.is_open = true;
+                meta.consume();
+            }
+
+            ContextMenuEvent::Close => {
+                self.is_open = false;
+                meta.consume();
+            }
+        });
+
+        event.map(|menu_event, _| {
+            if let MenuEvent::CloseAll = menu_event {
+                self.is_open = false;
+            }
+        });
+    }
+}
+
 pub struct MenuDivider {}
 
 impl MenuDivider {
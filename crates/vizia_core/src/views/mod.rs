@@ -1,53 +1,70 @@
 //! Built-in views provided by vizia.
 
+mod anchor;
+mod autocomplete;
 mod avatar;
 mod badge;
 mod button;
 mod checkbox;
+mod checkbox_group;
 mod chip;
 mod collapsible;
+mod color_picker;
 mod combobox;
 mod datepicker;
 mod divider;
 mod dropdown;
 mod element;
+mod form;
 mod grid;
 mod image;
+mod keyed;
 mod knob;
 mod label;
 mod list;
 mod markdown;
 mod menu;
+mod navigator;
 mod picklist;
 mod popup;
 mod progressbar;
 mod radio;
+mod radio_group;
 mod rating;
+mod reorder;
 mod scrollbar;
 mod scrollview;
 mod slider;
 mod spinbox;
+mod splitter;
 mod stack;
 mod switch;
+mod table;
 mod tabview;
+mod text_area;
 mod textbox;
 mod toggle_button;
 mod tooltip;
+mod tree_view;
 mod virtual_list;
 mod xypad;
 
 pub use crate::binding::Binding;
+pub use autocomplete::*;
 pub use avatar::*;
 pub use badge::*;
 pub use button::*;
 pub use checkbox::*;
+pub use checkbox_group::*;
 pub use chip::*;
 pub use collapsible::*;
+pub use color_picker::*;
 pub use combobox::*;
 pub use datepicker::*;
 pub use divider::*;
 pub use dropdown::*;
 pub use element::*;
+pub use form::*;
 pub use grid::*;
 pub use image::*;
 pub use knob::*;
@@ -56,21 +73,27 @@ pub use list::*;
 #[cfg(feature = "markdown")]
 pub use markdown::*;
 pub use menu::*;
+pub use navigator::*;
 pub use picklist::*;
 pub use popup::*;
 pub use progressbar::*;
 pub use radio::*;
+pub use radio_group::*;
 pub use rating::*;
 pub use scrollbar::*;
 pub use scrollview::*;
 pub use slider::*;
 pub use spinbox::*;
+pub use splitter::*;
 pub use stack::*;
 pub use switch::*;
+pub use table::*;
 pub use tabview::*;
+pub use text_area::*;
 pub use textbox::*;
 pub use toggle_button::*;
 pub use tooltip::*;
+pub use tree_view::*;
 pub use virtual_list::*;
 pub use xypad::*;
 
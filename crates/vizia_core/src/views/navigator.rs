@@ -0,0 +1,179 @@
+use crate::prelude::*;
+
+/// Events used to navigate between the routes of a [Navigator].
+pub enum NavigationEvent {
+    /// Shows the route with the given name, adding it to the top of the history.
+    ///
+    /// If the route is already in the history, the routes above it are popped instead.
+    Push(String),
+    /// Pops the current route from the history, returning to the previous route. The first route is never popped.
+    Pop,
+    /// Replaces the current route with the route with the given name.
+    Replace(String),
+}
+
+/// A view which shows one of its [routes](Route) at a time and keeps a history of the routes it has navigated to.
+///
+/// Navigating is done by emitting a [`NavigationEvent`] from within the navigator, so any route can navigate to any
+/// other route by name. The content of a route is built when it's added to the history and is kept while other routes
+/// are pushed above it, so returning to a route restores its state, while popping a route destroys its content.
+///
+/// The route which is shown plays the `navigator_push` or `navigator_pop` animation, which can be replaced with
+/// keyframes of the same name in a stylesheet.
+///
+/// # Example
+/// ```
+/// # use vizia_core::prelude::*;
+/// # let cx = &mut Context::default();
+/// Navigator::new(cx, "home", |cx| {
+///     Route::new(cx, "home", |cx| {
+///         Button::new(cx, |cx| Label::new(cx, "Settings"))
+///             .on_press(|cx| cx.emit(NavigationEvent::Push(String::from("settings"))));
+///     });
+///
+///     Route::new(cx, "settings", |cx| {
+///         Button::new(cx, |cx| Label::new(cx, "Back"))
+///             .on_press(|cx| cx.emit(NavigationEvent::Pop))
+///             .disabled(Navigator::history.map(|history| history.len() < 2));
+///     });
+/// });
+/// ```
+#[derive(Lens)]
+pub struct Navigator {
+    /// The names of the routes which have been navigated to, where the last route is the one which is shown.
+    pub history: Vec<String>,
+    #[lens(ignore)]
+    on_navigate: Option<Box<dyn Fn(&mut EventContext, &str)>>,
+}
+
+impl Navigator {
+    /// Creates a new [Navigator] view which starts at the route with the given name, which can be used to deep-link to
+    /// a route. The routes of the navigator are declared with [`Route`] views directly within the content.
+    pub fn new(
+        cx: &mut Context,
+        initial: impl ToString,
+        content: impl FnOnce(&mut Context),
+    ) -> Handle<Self> {
+        Self { history: vec![initial.to_string()], on_navigate: None }.build(cx, content)
+    }
+
+    // Returns the route view within the navigator with the given name.
+    fn route(cx: &EventContext, name: &str) -> Option<Entity> {
+        cx.current().child_iter(cx.tree).find(|entity| {
+            cx.views
+                .get(entity)
+                .and_then(|view| view.downcast_ref::<Route>())
+                .is_some_and(|route| route.name == name)
+        })
+    }
+
+    // Shows the current route, playing the animation of the navigation, and triggers the `on_navigate` callback.
+    fn navigated(&self, cx: &mut EventContext, animation: &'static str) {
+        let Some(current) = self.history.last() else { return };
+
+        if let Some(entity) = Self::route(cx, current) {
+            cx.play_animation_with(animation, entity, AnimationOptions::default());
+        }
+
+        if let Some(callback) = &self.on_navigate {
+            (callback)(cx, current);
+        }
+    }
+}
+
+impl View for Navigator {
+    fn element(&self) -> Option<&'static str> {
+        Some("navigator")
+    }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.map(|navigation_event, meta| {
+            match navigation_event {
+                NavigationEvent::Push(name) => {
+                    if Self::route(cx, name).is_none() {
+                        log::warn!("Failed to navigate to unknown route: {}", name);
+                    } else if let Some(index) = self.history.iter().position(|route| route == name)
+                    {
+                        if index + 1 < self.history.len() {
+                            self.history.truncate(index + 1);
+                            self.navigated(cx, "navigator_pop");
+                        }
+                    } else {
+                        self.history.push(name.clone());
+                        self.navigated(cx, "navigator_push");
+                    }
+                }
+
+                NavigationEvent::Pop => {
+                    if self.history.len() > 1 {
+                        self.history.pop();
+                        self.navigated(cx, "navigator_pop");
+                    }
+                }
+
+                NavigationEvent::Replace(name) => {
+                    if Self::route(cx, name).is_none() {
+                        log::warn!("Failed to navigate to unknown route: {}", name);
+                    } else if self.history.last() != Some(name) {
+                        self.history.retain(|route| route != name);
+                        self.history.pop();
+                        self.history.push(name.clone());
+                        self.navigated(cx, "navigator_push");
+                    }
+                }
+            }
+
+            meta.consume();
+        });
+    }
+}
+
+impl Handle<'_, Navigator> {
+    /// Sets the callback triggered when the navigator navigates to a route, which is passed the name of the route.
+    pub fn on_navigate<F>(self, callback: F) -> Self
+    where
+        F: 'static + Fn(&mut EventContext, &str),
+    {
+        self.modify(|navigator| navigator.on_navigate = Some(Box::new(callback)))
+    }
+}
+
+/// A screen within a [Navigator], which is shown when it's the current route of the navigator.
+pub struct Route {
+    name: String,
+}
+
+impl Route {
+    /// Creates a new [Route] view with the given name. The content is built when the route is added to the history of
+    /// the navigator and is destroyed when it's removed.
+    pub fn new<F>(cx: &mut Context, name: impl ToString, content: F) -> Handle<Self>
+    where
+        F: 'static + Fn(&mut Context),
+    {
+        let name = name.to_string();
+        let in_history = {
+            let name = name.clone();
+            Navigator::history.map(move |history| history.contains(&name))
+        };
+        let current = {
+            let name = name.clone();
+            Navigator::history.map(move |history| history.last() == Some(&name))
+        };
+
+        Self { name }
+            .build(cx, |cx| {
+                Binding::new(cx, in_history, move |cx, in_history| {
+                    if in_history.get(cx) {
+                        (content)(cx);
+                    }
+                });
+            })
+            .display(current)
+    }
+}
+
+impl View for Route {
+    fn element(&self) -> Option<&'static str> {
+        Some("route")
+    }
+}
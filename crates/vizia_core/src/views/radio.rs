@@ -106,23 +106,14 @@ impl View for RadioButton {
         event.map(|window_event, meta| match window_event {
             WindowEvent::Press { mouse } => {
                 let over = if *mouse { cx.mouse.left.pressed } else { cx.focused() };
-                if over == cx.current() && meta.target == cx.current() && !cx.is_disabled() {
+                if cx.is_press_over(meta, over) && meta.target == cx.current() && !cx.is_disabled()
+                {
                     if let Some(callback) = &self.on_select {
                         (callback)(cx);
                     }
                 }
             }
 
-            WindowEvent::ActionRequest(request) => match request.action {
-                Action::Click => {
-                    if let Some(callback) = &self.on_select {
-                        (callback)(cx);
-                    }
-                }
-
-                _ => {}
-            },
-
             _ => {}
         });
     }
@@ -0,0 +1,174 @@
+use std::ops::Deref;
+
+use crate::prelude::*;
+
+/// A group of radio buttons with labels, of which at most one option can be selected.
+///
+/// The arrow keys select the next and previous options while one of the radio buttons is focused. Selecting an
+/// option triggers the `on_select` callback, which is usually used to emit an event which updates the bound data.
+///
+/// # Examples
+///
+/// ```
+/// # use vizia_core::prelude::*;
+/// #
+/// # #[derive(Lens)]
+/// # struct AppData {
+/// #     sizes: Vec<String>,
+/// #     size: Option<usize>,
+/// # }
+/// #
+/// # impl Model for AppData {}
+/// #
+/// # enum AppEvent {
+/// #     SetSize(usize),
+/// # }
+/// #
+/// # let cx = &mut Context::default();
+/// #
+/// # AppData { sizes: vec![String::from("Small"), String::from("Large")], size: None }.build(cx);
+/// #
+/// RadioGroup::new(cx, AppData::sizes, AppData::size)
+///     .on_select(|cx, index| cx.emit(AppEvent::SetSize(index)));
+/// ```
+pub struct RadioGroup {
+    on_select: Option<Box<dyn Fn(&mut EventContext, usize)>>,
+    selected: Option<usize>,
+    buttons: Vec<Entity>,
+}
+
+pub(crate) enum RadioGroupEvent {
+    SetButtons(Vec<Entity>),
+    Select(usize),
+    SelectNext,
+    SelectPrevious,
+}
+
+impl RadioGroup {
+    /// Creates a new [RadioGroup] view with a radio button for each option in the list.
+    pub fn new<L1, L2, T>(cx: &mut Context, options: L1, selected: L2) -> Handle<Self>
+    where
+        L1: Lens,
+        L1::Target: Deref<Target = [T]> + Data,
+        T: 'static + Data + ToStringLocalized,
+        L2: Lens<Target = Option<usize>>,
+    {
+        Self { on_select: None, selected: None, buttons: Vec::new() }
+            .build(cx, move |cx| {
+                Keymap::from(vec![
+                    (
+                        KeyChord::new(Modifiers::empty(), Code::ArrowDown),
+                        KeymapEntry::new("Select Next", |cx| cx.emit(RadioGroupEvent::SelectNext)),
+                    ),
+                    (
+                        KeyChord::new(Modifiers::empty(), Code::ArrowRight),
+                        KeymapEntry::new("Select Next", |cx| cx.emit(RadioGroupEvent::SelectNext)),
+                    ),
+                    (
+                        KeyChord::new(Modifiers::empty(), Code::ArrowUp),
+                        KeymapEntry::new("Select Previous", |cx| {
+                            cx.emit(RadioGroupEvent::SelectPrevious)
+                        }),
+                    ),
+                    (
+                        KeyChord::new(Modifiers::empty(), Code::ArrowLeft),
+                        KeymapEntry::new("Select Previous", |cx| {
+                            cx.emit(RadioGroupEvent::SelectPrevious)
+                        }),
+                    ),
+                ])
+                .build(cx);
+
+                Binding::new(cx, options.map(|options| options.len()), move |cx, len| {
+                    let buttons = (0..len.get(cx))
+                        .map(|index| {
+                            let mut button = Entity::null();
+                            HStack::new(cx, |cx| {
+                                button = RadioButton::new(
+                                    cx,
+                                    selected.map(move |selected| *selected == Some(index)),
+                                )
+                                .on_select(move |cx| cx.emit(RadioGroupEvent::Select(index)))
+                                .entity();
+
+                                Label::new(cx, options.idx(index))
+                                    .on_press(move |cx| cx.emit(RadioGroupEvent::Select(index)));
+                            })
+                            .class("option");
+
+                            button
+                        })
+                        .collect();
+
+                    cx.emit(RadioGroupEvent::SetButtons(buttons));
+                });
+            })
+            .role(Role::RadioGroup)
+            .bind(selected, |handle, selected| {
+                let selected = selected.get(&handle);
+                handle.modify(|radio_group| radio_group.selected = selected);
+            })
+    }
+
+    fn select(&self, cx: &mut EventContext, index: usize) {
+        if cx.is_disabled() || index >= self.buttons.len() {
+            return;
+        }
+
+        if let Some(callback) = &self.on_select {
+            (callback)(cx, index);
+        }
+    }
+}
+
+impl View for RadioGroup {
+    fn element(&self) -> Option<&'static str> {
+        Some("radiogroup")
+    }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.map(|radio_group_event, meta| match radio_group_event {
+            RadioGroupEvent::SetButtons(buttons) => {
+                self.buttons = buttons.clone();
+                meta.consume();
+            }
+
+            RadioGroupEvent::Select(index) => {
+                self.select(cx, *index);
+                meta.consume();
+            }
+
+            RadioGroupEvent::SelectNext | RadioGroupEvent::SelectPrevious => {
+                let len = self.buttons.len();
+                if len == 0 {
+                    return;
+                }
+
+                // Wrap around at either end of the group, as with the radio buttons of a native form.
+                let index = match (radio_group_event, self.selected) {
+                    (RadioGroupEvent::SelectNext, Some(selected)) => (selected + 1) % len,
+                    (RadioGroupEvent::SelectNext, None) => 0,
+                    (_, Some(selected)) => (selected + len - 1) % len,
+                    (_, None) => len - 1,
+                };
+
+                self.select(cx, index);
+                if !cx.is_disabled() {
+                    cx.with_current(self.buttons[index], |cx| cx.focus_with_visibility(true));
+                }
+
+                meta.consume();
+            }
+        });
+    }
+}
+
+impl Handle<'_, RadioGroup> {
+    /// Sets the callback triggered when an option is selected, which is passed the index of the option.
+    pub fn on_select<F>(self, callback: F) -> Self
+    where
+        F: 'static + Fn(&mut EventContext, usize),
+    {
+        self.modify(|radio_group| radio_group.on_select = Some(Box::new(callback)))
+    }
+}
@@ -0,0 +1,128 @@
+//! Reordering the items of a view, such as a list or a tabview, by dragging them.
+//!
+//! While an item is dragged it has the `dragging` class, and the item it would be dropped next to has the
+//! `drop-before` or `drop-after` class.
+use crate::prelude::*;
+
+/// The state of a drag which reorders the items of a view.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct DragReorder {
+    /// The entity and index of the item being dragged.
+    dragged: Option<(Entity, usize)>,
+    /// The item next to which the dragged item would be dropped, its index, and whether it would be dropped after it.
+    drop_target: Option<(Entity, usize, bool)>,
+}
+
+impl DragReorder {
+    /// Returns the index of the item being dragged, if any.
+    pub fn dragging(&self) -> Option<usize> {
+        self.dragged.map(|(_, index)| index)
+    }
+
+    /// Starts dragging an item, capturing the mouse for the current view.
+    pub fn start(&mut self, cx: &mut EventContext, entity: Entity, index: usize) {
+        self.dragged = Some((entity, index));
+        cx.capture();
+        cx.with_current(entity, |cx| cx.toggle_class("dragging", true));
+    }
+
+    /// Moves the drop indicator to the item closest to the cursor, given the entities and indices of the items and
+    /// whether the items flow horizontally.
+    pub fn update(&mut self, cx: &mut EventContext, items: &[(Entity, usize)], horizontal: bool) {
+        let drop_target = find_drop_target(cx, items, horizontal);
+        if drop_target == self.drop_target {
+            return;
+        }
+
+        if let Some((entity, _, after)) = self.drop_target {
+            cx.with_current(entity, |cx| {
+                cx.toggle_class(if after { "drop-after" } else { "drop-before" }, false)
+            });
+        }
+
+        if let Some((entity, _, after)) = drop_target {
+            cx.with_current(entity, |cx| {
+                cx.toggle_class(if after { "drop-after" } else { "drop-before" }, true)
+            });
+        }
+
+        self.drop_target = drop_target;
+    }
+
+    /// Ends the drag, releasing the mouse. If the item was dropped at a new position, returns the index of the dragged
+    /// item and the index it should be moved to once it has been removed from its current position.
+    pub fn end(&mut self, cx: &mut EventContext, drop: bool) -> Option<(usize, usize)> {
+        let (entity, from) = self.dragged.take()?;
+        let drop_target = self.drop_target.take();
+
+        cx.release();
+        cx.with_current(entity, |cx| cx.toggle_class("dragging", false));
+        if let Some((entity, _, _)) = drop_target {
+            cx.with_current(entity, |cx| {
+                cx.toggle_class("drop-before", false);
+                cx.toggle_class("drop-after", false);
+            });
+        }
+
+        let (_, index, after) = drop_target.filter(|_| drop)?;
+
+        // The index the item should have once it has been removed from its current position.
+        let insert = if after { index + 1 } else { index };
+        let to = if insert > from { insert - 1 } else { insert };
+
+        (to != from).then_some((from, to))
+    }
+}
+
+// Returns the item closest to the cursor and whether the cursor is past its center along the flow of the items.
+fn find_drop_target(
+    cx: &EventContext,
+    items: &[(Entity, usize)],
+    horizontal: bool,
+) -> Option<(Entity, usize, bool)> {
+    let (x, y) = (cx.mouse.cursor_x, cx.mouse.cursor_y);
+    let distance = |bounds: &BoundingBox| {
+        let dx = (bounds.left() - x).max(x - bounds.right()).max(0.0);
+        let dy = (bounds.top() - y).max(y - bounds.bottom()).max(0.0);
+        dx * dx + dy * dy
+    };
+
+    let (entity, index, bounds) = items
+        .iter()
+        .map(|&(entity, index)| (entity, index, cx.cache.get_bounds(entity)))
+        .min_by(|(_, _, a), (_, _, b)| distance(a).total_cmp(&distance(b)))?;
+
+    let (center_x, center_y) = bounds.center();
+    let after = if horizontal { x > center_x } else { y > center_y };
+
+    Some((entity, index, after))
+}
+
+/// Returns the index of the item at `index` once the item at `from` has been moved to `to`.
+pub(crate) fn moved_index(index: usize, from: usize, to: usize) -> usize {
+    if index == from {
+        to
+    } else if from < index && index <= to {
+        index - 1
+    } else if to <= index && index < from {
+        index + 1
+    } else {
+        index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moved_indices() {
+        // Moving the item at index 1 to index 3 of [a, b, c, d, e] gives [a, c, d, b, e].
+        let moved = (0..5).map(|index| moved_index(index, 1, 3)).collect::<Vec<_>>();
+        assert_eq!(moved, [0, 3, 1, 2, 4]);
+
+        // Moving it back restores the original order.
+        let moved = (0..5).map(|index| moved_index(index, 3, 1)).collect::<Vec<_>>();
+        assert_eq!(moved, [0, 2, 3, 1, 4]);
+    }
+}
@@ -1,10 +1,24 @@
 use std::sync::Arc;
 
+use crate::animation::SpringCurve;
 use crate::binding::RatioLens;
 use crate::prelude::*;
 
 pub(crate) const SCROLL_SENSITIVITY: f32 = 20.0;
 
+// The maximum distance, in logical pixels, the content can be scrolled past its bounds when overscroll is enabled.
+const OVERSCROLL_LIMIT: f32 = 80.0;
+// The fraction of scrolling past the bounds which is applied to the overscroll, giving a feeling of resistance.
+const OVERSCROLL_RESISTANCE: f32 = 0.5;
+// The interval between ticks of the overscroll spring-back, kinetic scrolling, and animated scrolls.
+const TICK_INTERVAL: Duration = Duration::from_millis(16);
+// The time, in milliseconds, without scroll input after which kinetic scrolling takes over.
+const KINETIC_IDLE_MS: u64 = 50;
+// The fraction of the kinetic scrolling velocity which remains after each tick.
+const KINETIC_FRICTION: f32 = 0.95;
+// The fraction of the remaining distance covered by each tick of an animated scroll.
+const SCROLL_EASING: f32 = 0.25;
+
 /// Events for setting the properties of a scroll view.
 pub enum ScrollEvent {
     /// Sets the progress of scroll position between 0 and 1 for the x axis
@@ -17,6 +31,34 @@ pub enum ScrollEvent {
     ScrollY(f32),
     /// Sets the size for the inner scroll-content view which holds the content
     ChildGeo(f32, f32),
+    /// Scrolls the nearest ancestor scrollview of the given entity until the entity is visible, optionally animating
+    /// the scroll. Usually sent with [`scroll_to`](crate::context::EventContext::scroll_to).
+    ScrollTo {
+        /// The entity to scroll into view.
+        entity: Entity,
+        /// Whether the scroll position should be animated.
+        animate: bool,
+    },
+    /// Scrolls to the given offset, in logical pixels, from the top-left corner of the content, optionally animating
+    /// the scroll.
+    ScrollToOffset {
+        /// The horizontal offset in logical pixels.
+        x: f32,
+        /// The vertical offset in logical pixels.
+        y: f32,
+        /// Whether the scroll position should be animated.
+        animate: bool,
+    },
+}
+
+// Emitted by the overscroll timer to move any overscroll back towards the bounds of the content.
+enum OverscrollEvent {
+    Relax,
+}
+
+// Emitted by the motion timer to advance kinetic scrolling and animated scrolls.
+enum MotionEvent {
+    Step,
 }
 
 /// A container a view which allows the user to scroll any overflowed content.
@@ -27,6 +69,11 @@ pub struct ScrollView {
     /// Progress of scroll position between 0 and 1 for the y axis
     pub scroll_y: f32,
 
+    /// Horizontal scroll offset of the content in logical pixels.
+    pub offset_x: f32,
+    /// Vertical scroll offset of the content in logical pixels.
+    pub offset_y: f32,
+
     /// Callback called when the scrollview is scrolled.
     #[lens(ignore)]
     pub on_scroll: Option<Arc<dyn Fn(&mut EventContext, f32, f32) + Send + Sync>>,
@@ -45,6 +92,42 @@ pub struct ScrollView {
     pub show_horizontal_scrollbar: bool,
     /// Whether the vertical scrollbar should be visible.
     pub show_vertical_scrollbar: bool,
+    /// Distance in physical pixels the content is currently scrolled past its bounds along the x axis.
+    pub overscroll_x: f32,
+    /// Distance in physical pixels the content is currently scrolled past its bounds along the y axis.
+    pub overscroll_y: f32,
+    /// Whether the content can be scrolled a small distance past its bounds.
+    pub overscroll: bool,
+    // Timer used to spring the content back within its bounds, added when the content is first overscrolled.
+    #[lens(ignore)]
+    #[data(ignore)]
+    overscroll_timer: Option<Timer>,
+    // The spring which moves the content back within its bounds.
+    #[lens(ignore)]
+    #[data(ignore)]
+    spring_back: SpringCurve,
+    // The overscroll when the spring-back started, and the time in seconds the spring has moved for since.
+    #[lens(ignore)]
+    #[data(ignore)]
+    relax: Option<((f32, f32), f32)>,
+    /// Whether scrolling continues with decaying velocity after trackpad scrolling stops.
+    pub kinetic_scrolling: bool,
+    // Velocity of kinetic scrolling in physical pixels per tick.
+    #[lens(ignore)]
+    #[data(ignore)]
+    velocity: (f32, f32),
+    // The time of the last scroll input.
+    #[lens(ignore)]
+    #[data(ignore)]
+    last_scroll: Option<Instant>,
+    // The scroll position, as progress along each axis, which an animated scroll is moving towards.
+    #[lens(ignore)]
+    #[data(ignore)]
+    scroll_target: Option<(f32, f32)>,
+    // Timer used to advance kinetic scrolling and animated scrolls, added when either is first used.
+    #[lens(ignore)]
+    #[data(ignore)]
+    motion_timer: Option<Timer>,
 }
 
 impl ScrollView {
@@ -57,6 +140,8 @@ impl ScrollView {
             scroll_to_cursor: false,
             scroll_x: 0.0,
             scroll_y: 0.0,
+            offset_x: 0.0,
+            offset_y: 0.0,
             on_scroll: None,
             inner_width: 0.0,
             inner_height: 0.0,
@@ -64,6 +149,18 @@ impl ScrollView {
             container_height: 0.0,
             show_horizontal_scrollbar: true,
             show_vertical_scrollbar: true,
+            overscroll_x: 0.0,
+            overscroll_y: 0.0,
+            overscroll: false,
+            overscroll_timer: None,
+            // Damped slightly past critical so the content settles on the bound without overshooting it.
+            spring_back: SpringCurve::new(Spring::new(170.0, 28.0, 1.0), 0.0),
+            relax: None,
+            kinetic_scrolling: false,
+            velocity: (0.0, 0.0),
+            last_scroll: None,
+            scroll_target: None,
+            motion_timer: None,
         }
         .build(cx, move |cx| {
             ScrollContent::new(cx, content);
@@ -107,7 +204,9 @@ impl ScrollView {
                 / scale_factor;
             let left =
                 ((data.inner_width - data.container_width) * data.scroll_x).round() / scale_factor;
-            handle.horizontal_scroll(-left.abs()).vertical_scroll(-top.abs());
+            handle
+                .horizontal_scroll(-left.abs() + data.overscroll_x / scale_factor)
+                .vertical_scroll(-top.abs() + data.overscroll_y / scale_factor);
         })
         .toggle_class(
             "h-scroll",
@@ -119,6 +218,95 @@ impl ScrollView {
         )
     }
 
+    // Adds the distance scrolled past the bounds of the content to an overscroll and restarts the spring-back.
+    fn add_overscroll(&mut self, cx: &mut EventContext, overscroll: f32, excess: f32) -> f32 {
+        if !self.overscroll || excess == 0.0 {
+            return overscroll;
+        }
+
+        let timer = *self.overscroll_timer.get_or_insert_with(|| {
+            cx.add_timer(TICK_INTERVAL, None, |cx, action| {
+                if let TimerAction::Tick(_) = action {
+                    cx.emit(OverscrollEvent::Relax);
+                }
+            })
+        });
+        cx.start_timer(timer);
+        self.relax = None;
+
+        let limit = OVERSCROLL_LIMIT * cx.scale_factor();
+        (overscroll - excess * OVERSCROLL_RESISTANCE).clamp(-limit, limit)
+    }
+
+    // Starts the timer which advances kinetic scrolling and animated scrolls, adding it on first use.
+    fn start_motion(&mut self, cx: &mut EventContext) {
+        let timer = *self.motion_timer.get_or_insert_with(|| {
+            cx.add_timer(TICK_INTERVAL, None, |cx, action| {
+                if let TimerAction::Tick(_) = action {
+                    cx.emit(MotionEvent::Step);
+                }
+            })
+        });
+        cx.start_timer(timer);
+    }
+
+    // Scrolls to the given offset, in physical pixels, from the top-left corner of the content.
+    fn scroll_to_offset(&mut self, cx: &mut EventContext, left: f32, top: f32, animate: bool) {
+        let negative_width = self.inner_width - self.container_width;
+        let negative_height = self.inner_height - self.container_height;
+        let scroll_x =
+            if negative_width > 0.0 { (left / negative_width).clamp(0.0, 1.0) } else { 0.0 };
+        let scroll_y =
+            if negative_height > 0.0 { (top / negative_height).clamp(0.0, 1.0) } else { 0.0 };
+
+        self.velocity = (0.0, 0.0);
+
+        if animate {
+            self.scroll_target = Some((scroll_x, scroll_y));
+            self.start_motion(cx);
+        } else {
+            self.scroll_target = None;
+            self.scroll_x = scroll_x;
+            self.scroll_y = scroll_y;
+            self.scrolled(cx);
+        }
+    }
+
+    // Returns the offset, in physical pixels, which scrolls the given descendant entity into view.
+    fn offset_of(&self, cx: &EventContext, entity: Entity) -> Option<(f32, f32)> {
+        let content = cx.nth_child(0)?;
+        if !entity.is_descendant_of(cx.tree, content) {
+            return None;
+        }
+
+        let content_bounds = cx.cache.get_bounds(content);
+        let bounds = cx.cache.get_bounds(entity);
+
+        let left = (self.inner_width - self.container_width).max(0.0) * self.scroll_x;
+        let top = (self.inner_height - self.container_height).max(0.0) * self.scroll_y;
+
+        let reveal = |offset: f32, start: f32, size: f32, container: f32| {
+            if start < offset {
+                start
+            } else if start + size > offset + container {
+                start + size - container
+            } else {
+                offset
+            }
+        };
+
+        Some((
+            reveal(left, bounds.x - content_bounds.x, bounds.w, self.container_width),
+            reveal(top, bounds.y - content_bounds.y, bounds.h, self.container_height),
+        ))
+    }
+
+    fn scrolled(&self, cx: &mut EventContext) {
+        if let Some(callback) = &self.on_scroll {
+            (callback)(cx, self.scroll_x, self.scroll_y);
+        }
+    }
+
     fn reset(&mut self) {
         if self.inner_width == self.container_width {
             self.scroll_x = 0.0;
@@ -139,7 +327,12 @@ impl View for ScrollView {
         event.map(|scroll_update, meta| {
             match scroll_update {
                 ScrollEvent::ScrollX(f) => {
-                    self.scroll_x = (self.scroll_x + *f).clamp(0.0, 1.0);
+                    let scroll_x = self.scroll_x + *f;
+                    self.scroll_x = scroll_x.clamp(0.0, 1.0);
+
+                    let excess =
+                        (scroll_x - self.scroll_x) * (self.inner_width - self.container_width);
+                    self.overscroll_x = self.add_overscroll(cx, self.overscroll_x, excess);
 
                     if let Some(callback) = &self.on_scroll {
                         (callback)(cx, self.scroll_x, self.scroll_y);
@@ -147,7 +340,13 @@ impl View for ScrollView {
                 }
 
                 ScrollEvent::ScrollY(f) => {
-                    self.scroll_y = (self.scroll_y + *f).clamp(0.0, 1.0);
+                    let scroll_y = self.scroll_y + *f;
+                    self.scroll_y = scroll_y.clamp(0.0, 1.0);
+
+                    let excess =
+                        (scroll_y - self.scroll_y) * (self.inner_height - self.container_height);
+                    self.overscroll_y = self.add_overscroll(cx, self.overscroll_y, excess);
+
                     if let Some(callback) = &self.on_scroll {
                         (callback)(cx, self.scroll_x, self.scroll_y);
                     }
@@ -206,6 +405,20 @@ impl View for ScrollView {
                     self.inner_height = *h;
                     self.reset();
                 }
+
+                ScrollEvent::ScrollTo { entity, animate } => {
+                    // Let the event propagate to an ancestor scrollview if the entity is not within this one.
+                    let Some((left, top)) = self.offset_of(cx, *entity) else {
+                        return;
+                    };
+
+                    self.scroll_to_offset(cx, left, top, *animate);
+                }
+
+                ScrollEvent::ScrollToOffset { x, y, animate } => {
+                    let scale_factor = cx.scale_factor();
+                    self.scroll_to_offset(cx, x * scale_factor, y * scale_factor, *animate);
+                }
             }
 
             // Prevent scroll events propagating to any parent scrollviews.
@@ -213,6 +426,101 @@ impl View for ScrollView {
             meta.consume();
         });
 
+        event.map(|overscroll_event, meta| match overscroll_event {
+            OverscrollEvent::Relax => {
+                let ((from_x, from_y), time) =
+                    self.relax.get_or_insert(((self.overscroll_x, self.overscroll_y), 0.0));
+                *time += TICK_INTERVAL.as_secs_f32();
+
+                if *time < self.spring_back.duration {
+                    let remaining = 1.0 - self.spring_back.position(*time);
+                    self.overscroll_x = *from_x * remaining;
+                    self.overscroll_y = *from_y * remaining;
+                } else {
+                    self.overscroll_x = 0.0;
+                    self.overscroll_y = 0.0;
+                    self.relax = None;
+
+                    if let Some(timer) = self.overscroll_timer {
+                        cx.stop_timer(timer);
+                    }
+                }
+
+                meta.consume();
+            }
+        });
+
+        event.map(|motion_event, meta| match motion_event {
+            MotionEvent::Step => {
+                if let Some((target_x, target_y)) = self.scroll_target {
+                    let distance_x =
+                        (target_x - self.scroll_x) * (self.inner_width - self.container_width);
+                    let distance_y =
+                        (target_y - self.scroll_y) * (self.inner_height - self.container_height);
+
+                    if distance_x.abs() < 0.5 && distance_y.abs() < 0.5 {
+                        self.scroll_x = target_x;
+                        self.scroll_y = target_y;
+                        self.scroll_target = None;
+                    } else {
+                        self.scroll_x += (target_x - self.scroll_x) * SCROLL_EASING;
+                        self.scroll_y += (target_y - self.scroll_y) * SCROLL_EASING;
+                    }
+
+                    self.scrolled(cx);
+                } else if self.velocity != (0.0, 0.0) {
+                    let idle = self.last_scroll.map_or(true, |last| {
+                        last.elapsed() >= Duration::from_millis(KINETIC_IDLE_MS)
+                    });
+
+                    // Wait until scroll input has stopped before continuing the motion.
+                    if idle {
+                        let (velocity_x, velocity_y) = self.velocity;
+                        let negative_width = self.inner_width - self.container_width;
+                        let negative_height = self.inner_height - self.container_height;
+
+                        if negative_width > 0.0 {
+                            self.scroll_x =
+                                (self.scroll_x + velocity_x / negative_width).clamp(0.0, 1.0);
+                        }
+
+                        if negative_height > 0.0 {
+                            self.scroll_y =
+                                (self.scroll_y + velocity_y / negative_height).clamp(0.0, 1.0);
+                        }
+
+                        self.scrolled(cx);
+
+                        // Stop moving along an axis once the content reaches its bounds.
+                        let moving = |velocity: f32, scroll: f32, negative_space: f32| {
+                            if negative_space <= 0.0
+                                || (velocity < 0.0 && scroll <= 0.0)
+                                || (velocity > 0.0 && scroll >= 1.0)
+                                || velocity.abs() < 0.5
+                            {
+                                0.0
+                            } else {
+                                velocity * KINETIC_FRICTION
+                            }
+                        };
+
+                        self.velocity = (
+                            moving(velocity_x, self.scroll_x, negative_width),
+                            moving(velocity_y, self.scroll_y, negative_height),
+                        );
+                    }
+                }
+
+                if self.scroll_target.is_none() && self.velocity == (0.0, 0.0) {
+                    if let Some(timer) = self.motion_timer {
+                        cx.stop_timer(timer);
+                    }
+                }
+
+                meta.consume();
+            }
+        });
+
         event.map(|window_event, meta| match window_event {
             WindowEvent::GeometryChanged(geo) => {
                 if geo.contains(GeoChanged::WIDTH_CHANGED)
@@ -258,6 +566,25 @@ impl View for ScrollView {
                 cx.set_active(true);
                 let (x, y) = if cx.modifiers.shift() { (-*y, -*x) } else { (-*x, -*y) };
 
+                // Scroll input interrupts an animated scroll.
+                self.scroll_target = None;
+
+                if self.kinetic_scrolling {
+                    // Estimate the velocity per tick from the time since the previous scroll input.
+                    let now = Instant::now();
+                    let ticks = self
+                        .last_scroll
+                        .map_or(1.0, |last| (now - last).as_secs_f32() / 0.016)
+                        .clamp(1.0, 4.0);
+                    let (velocity_x, velocity_y) = self.velocity;
+                    self.velocity = (
+                        (velocity_x + x * SCROLL_SENSITIVITY / ticks) * 0.5,
+                        (velocity_y + y * SCROLL_SENSITIVITY / ticks) * 0.5,
+                    );
+                    self.last_scroll = Some(now);
+                    self.start_motion(cx);
+                }
+
                 // What percentage of the negative space does this cross?
                 if x != 0.0 && self.inner_width > self.container_width {
                     let negative_space = self.inner_width - self.container_width;
@@ -279,12 +606,32 @@ impl View for ScrollView {
                 }
             }
 
+            WindowEvent::MouseDown(_) => {
+                // Pressing stops any kinetic scrolling or animated scroll.
+                self.velocity = (0.0, 0.0);
+                self.scroll_target = None;
+            }
+
             WindowEvent::MouseOut => {
                 cx.set_active(false);
             }
 
+            WindowEvent::Destroyed => {
+                for timer in
+                    [self.overscroll_timer.take(), self.motion_timer.take()].into_iter().flatten()
+                {
+                    cx.remove_timer(timer);
+                }
+            }
+
             _ => {}
         });
+
+        let scale_factor = cx.scale_factor();
+        self.offset_x =
+            (self.inner_width - self.container_width).max(0.0) * self.scroll_x / scale_factor;
+        self.offset_y =
+            (self.inner_height - self.container_height).max(0.0) * self.scroll_y / scale_factor;
     }
 }
 
@@ -297,11 +644,23 @@ impl Handle<'_, ScrollView> {
         self.modify(|scrollview: &mut ScrollView| scrollview.on_scroll = Some(Arc::new(callback)))
     }
 
+    /// Sets whether scrolling with a trackpad continues with decaying velocity once the input stops. Disabled by
+    /// default, as some platforms already provide momentum for trackpad scrolling.
+    pub fn kinetic_scrolling(self, flag: bool) -> Self {
+        self.modify(|scrollview: &mut ScrollView| scrollview.kinetic_scrolling = flag)
+    }
+
     /// Sets whether the scrollbar should move to the cursor when pressed.
     pub fn scroll_to_cursor(self, scroll_to_cursor: bool) -> Self {
         self.modify(|scrollview: &mut ScrollView| scrollview.scroll_to_cursor = scroll_to_cursor)
     }
 
+    /// Sets whether the content of the scrollview can be scrolled a small distance past its bounds, springing back
+    /// once scrolling stops. When disabled, which is the default, scrolling is clamped to the bounds of the content.
+    pub fn overscroll(self, flag: bool) -> Self {
+        self.modify(|scrollview: &mut ScrollView| scrollview.overscroll = flag)
+    }
+
     /// Set the horizontal scroll position of the [ScrollView]. Accepts a value or lens to an 'f32' between 0 and 1.
     pub fn scroll_x(self, scrollx: impl Res<f32>) -> Self {
         self.bind(scrollx, |handle, scrollx| {
@@ -364,3 +723,70 @@ impl View for ScrollContent {
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::backend::BackendContext;
+    use crate::context::test_support::{update, window_context};
+
+    fn overscroll_y(cx: &BackendContext, scrollview: Entity) -> f32 {
+        cx.0.views
+            .get(&scrollview)
+            .and_then(|view| view.downcast_ref::<ScrollView>())
+            .unwrap()
+            .overscroll_y
+    }
+
+    #[test]
+    fn overscroll_springs_back() {
+        let mut cx = window_context(400, 300);
+
+        let timers = cx.0.timers.len();
+        let scrollview = ScrollView::new(cx.context(), |cx| {
+            Element::new(cx).size(Pixels(1000.0));
+        })
+        .overscroll(true)
+        .entity();
+
+        update(&mut cx);
+        update(&mut cx);
+
+        // Timers are only added once they're needed.
+        assert_eq!(cx.0.timers.len(), timers);
+
+        // Scrolling up past the top of the content overscrolls it.
+        cx.0.emit_to(scrollview, ScrollEvent::ScrollY(-0.1));
+        update(&mut cx);
+        assert!(overscroll_y(&cx, scrollview) > 0.0);
+        assert_eq!(cx.0.timers.len(), timers + 1);
+
+        // Each tick of the spring moves the content back towards the bound until it rests on it.
+        let mut previous = overscroll_y(&cx, scrollview);
+        let mut ticks = 0;
+        while previous != 0.0 {
+            cx.0.emit_to(scrollview, OverscrollEvent::Relax);
+            update(&mut cx);
+
+            let overscroll = overscroll_y(&cx, scrollview);
+            assert!((0.0..previous).contains(&overscroll));
+            previous = overscroll;
+
+            ticks += 1;
+            assert!(ticks < 120);
+        }
+        assert!(ticks > 1);
+
+        let timer =
+            cx.0.views
+                .get(&scrollview)
+                .and_then(|view| view.downcast_ref::<ScrollView>())
+                .and_then(|scrollview| scrollview.overscroll_timer)
+                .unwrap();
+        assert!(!cx.0.timer_is_running(timer));
+
+        // Removing the scrollview removes its timer.
+        cx.0.remove(scrollview);
+        assert!(cx.0.timers[timer.0].removed);
+    }
+}
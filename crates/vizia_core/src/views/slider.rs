@@ -1,6 +1,6 @@
 use std::ops::Range;
 
-use accesskit::ActionData;
+use accesskit::{ActionData, ActionRequest};
 
 use crate::prelude::*;
 
@@ -9,6 +9,9 @@ enum SliderEventInternal {
     SetThumbSize(f32, f32),
     SetRange(Range<f32>),
     SetKeyboardFraction(f32),
+    SetOrientation(Orientation),
+    SetTicks(Vec<f32>),
+    SetTickLabels(Vec<String>),
 }
 
 /// Internal data used by the slider.
@@ -24,8 +27,180 @@ pub struct SliderDataInternal {
     pub range: Range<f32>,
     /// The step of the slider.
     pub step: f32,
-    /// How much the slider should change in response to keyboard events.
+    /// How much the slider should change in response to the page up and page down keys, as a fraction of the range.
     pub keyboard_fraction: f32,
+    /// The values at which tick marks are shown along the track.
+    pub ticks: Vec<f32>,
+    /// The labels of the tick marks, in the same order as the ticks.
+    pub tick_labels: Vec<String>,
+}
+
+impl SliderDataInternal {
+    fn new() -> Self {
+        Self {
+            orientation: Orientation::Horizontal,
+            thumb_size: 0.0,
+            size: 0.0,
+            range: 0.0..1.0,
+            step: 0.01,
+            keyboard_fraction: 0.1,
+            ticks: Vec::new(),
+            tick_labels: Vec::new(),
+        }
+    }
+
+    fn apply(&mut self, event: &SliderEventInternal) {
+        match event {
+            SliderEventInternal::SetThumbSize(width, height) => match self.orientation {
+                Orientation::Horizontal => {
+                    self.thumb_size = *width;
+                }
+
+                Orientation::Vertical => {
+                    self.thumb_size = *height;
+                }
+            },
+
+            SliderEventInternal::SetRange(range) => {
+                self.range = range.clone();
+            }
+
+            SliderEventInternal::SetKeyboardFraction(keyboard_fraction) => {
+                self.keyboard_fraction = *keyboard_fraction;
+            }
+
+            SliderEventInternal::SetOrientation(orientation) => {
+                self.orientation = *orientation;
+            }
+
+            SliderEventInternal::SetTicks(ticks) => {
+                self.ticks = ticks.clone();
+            }
+
+            SliderEventInternal::SetTickLabels(tick_labels) => {
+                self.tick_labels = tick_labels.clone();
+            }
+        }
+    }
+
+    // Updates the size of the track, inferring the orientation from the bounds unless it was set explicitly.
+    fn resize(&mut self, bounds: BoundingBox, fixed_orientation: bool) {
+        if !fixed_orientation {
+            self.orientation =
+                if bounds.w >= bounds.h { Orientation::Horizontal } else { Orientation::Vertical };
+        }
+
+        self.size = match self.orientation {
+            Orientation::Horizontal => bounds.w,
+            Orientation::Vertical => bounds.h,
+        };
+    }
+
+    // Snaps a value to the closest step from the start of the range and clamps it to the range.
+    fn snap(&self, val: f32) -> f32 {
+        let min = self.range.start;
+        let max = self.range.end;
+        let step = self.step;
+
+        let mut val = val;
+        if step > 0.0 {
+            val = min + step * ((val - min) / step).round();
+        }
+
+        val.clamp(min, max)
+    }
+
+    // Returns the value at a position along the track of the slider with the given bounds.
+    fn value_at(&self, bounds: BoundingBox, x: f32, y: f32) -> f32 {
+        let thumb_size = self.thumb_size;
+
+        let dx = match self.orientation {
+            Orientation::Horizontal => (x - bounds.x - thumb_size / 2.0) / (bounds.w - thumb_size),
+            Orientation::Vertical => {
+                (bounds.h - (y - bounds.y) - thumb_size / 2.0) / (bounds.h - thumb_size)
+            }
+        };
+
+        let min = self.range.start;
+        let max = self.range.end;
+        self.snap(min + dx.clamp(0.0, 1.0) * (max - min))
+    }
+
+    // Returns the offset of the thumb for a value, as a fraction of the track.
+    fn thumb_offset(&self, val: f32) -> f32 {
+        let range = &self.range;
+        let normal_val = (val - range.start) / (range.end - range.start);
+        normal_val * (1.0 - (self.thumb_size / self.size))
+    }
+
+    // Returns the value a key press moves the value to, if the key changes the value.
+    fn key_value(&self, code: Code, val: f32) -> Option<f32> {
+        let page = self.keyboard_fraction * (self.range.end - self.range.start);
+        let val = match code {
+            Code::ArrowUp | Code::ArrowRight => val + self.step,
+            Code::ArrowDown | Code::ArrowLeft => val - self.step,
+            Code::PageUp => val + page,
+            Code::PageDown => val - page,
+            Code::Home => self.range.start,
+            Code::End => self.range.end,
+            _ => return None,
+        };
+
+        Some(self.snap(val))
+    }
+
+    // Returns the value an accessibility action moves the value to, if the action changes the value.
+    fn action_value(&self, action: &ActionRequest, val: f32) -> Option<f32> {
+        match action.action {
+            Action::Increment => Some(self.snap(val + self.step)),
+            Action::Decrement => Some(self.snap(val - self.step)),
+            Action::SetValue => match action.data {
+                Some(ActionData::NumericValue(val)) => Some(self.snap(val as f32)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn accessibility(&self, node: &mut AccessNode) {
+        node.add_action(Action::Increment);
+        node.add_action(Action::Decrement);
+        node.add_action(Action::SetValue);
+        node.set_numeric_value_step(self.step as f64);
+        node.set_min_numeric_value(self.range.start as f64);
+        node.set_max_numeric_value(self.range.end as f64);
+    }
+
+    // Builds the tick marks along the track, each with its label if it has one.
+    fn build_ticks(&self, cx: &mut Context) {
+        let extent = self.thumb_size / self.size;
+        for (index, tick) in self.ticks.iter().enumerate() {
+            let offset = self.thumb_offset(*tick);
+            let handle = VStack::new(cx, |cx| {
+                Element::new(cx).class("tick-mark");
+                if let Some(label) = self.tick_labels.get(index) {
+                    Label::new(cx, label.clone()).class("tick-label");
+                }
+            })
+            .class("tick")
+            .hoverable(false);
+
+            // The tick has the same extent as the thumb so that it lines up with the center of the thumb.
+            if self.orientation == Orientation::Horizontal {
+                handle
+                    .height(Stretch(1.0))
+                    .left(Percentage(100.0 * offset))
+                    .right(Stretch(1.0))
+                    .width(Percentage(100.0 * extent));
+            } else {
+                handle
+                    .width(Stretch(1.0))
+                    .top(Stretch(1.0))
+                    .bottom(Percentage(100.0 * offset))
+                    .height(Percentage(100.0 * extent));
+            }
+        }
+    }
 }
 
 /// The slider control can be used to select from a continuous set of values.
@@ -33,8 +208,14 @@ pub struct SliderDataInternal {
 /// The slider control consists of three main parts, a **thumb** element which can be moved between the extremes of a linear **track**,
 /// and an **active** element which fills the slider to indicate the current value.
 ///
-/// The slider orientation is determined by its dimensions. If the slider width is greater than the height then the thumb
-/// moves horizontally, whereas if the slider height is greater than the width the thumb moves vertically.
+/// Unless it's set with the `orientation` modifier, the slider orientation is determined by its dimensions. If the slider
+/// width is greater than the height then the thumb moves horizontally, whereas if the slider height is greater than the
+/// width the thumb moves vertically.
+///
+/// The value of the slider snaps to multiples of its `step` from the start of its range, and tick marks with optional
+/// labels can be shown along the track with the `ticks` and `tick_labels` modifiers. The arrow keys, and the increment
+/// and decrement accessibility actions, change the value by one step, while the page up and page down keys change it
+/// by the `keyboard_fraction` of the range.
 ///
 /// # Examples
 ///
@@ -80,6 +261,7 @@ pub struct SliderDataInternal {
 pub struct Slider<L: Lens> {
     lens: L,
     is_dragging: bool,
+    fixed_orientation: bool,
     internal: SliderDataInternal,
     on_changing: Option<Box<dyn Fn(&mut EventContext, f32)>>,
 }
@@ -110,15 +292,9 @@ where
         Self {
             lens,
             is_dragging: false,
+            fixed_orientation: false,
 
-            internal: SliderDataInternal {
-                orientation: Orientation::Horizontal,
-                thumb_size: 0.0,
-                size: 0.0,
-                range: 0.0..1.0,
-                step: 0.01,
-                keyboard_fraction: 0.1,
-            },
+            internal: SliderDataInternal::new(),
 
             on_changing: None,
         }
@@ -126,6 +302,8 @@ where
             Binding::new(cx, Slider::<L>::internal, move |cx, slider_data| {
                 ZStack::new(cx, move |cx| {
                     let slider_data = slider_data.get(cx);
+                    slider_data.build_ticks(cx);
+
                     let thumb_size = slider_data.thumb_size;
                     let orientation = slider_data.orientation;
                     let size = slider_data.size;
@@ -203,45 +381,21 @@ impl<L: Lens<Target = f32>> View for Slider<L> {
     }
 
     fn accessibility(&self, _cx: &mut AccessContext, node: &mut AccessNode) {
-        node.set_numeric_value_step(self.internal.step as f64);
-        node.set_min_numeric_value(self.internal.range.start as f64);
-        node.set_max_numeric_value(self.internal.range.end as f64);
+        self.internal.accessibility(node);
     }
 
     fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
-        event.map(|slider_event_internal, _| match slider_event_internal {
-            SliderEventInternal::SetThumbSize(width, height) => match self.internal.orientation {
-                Orientation::Horizontal => {
-                    self.internal.thumb_size = *width;
-                }
-
-                Orientation::Vertical => {
-                    self.internal.thumb_size = *height;
-                }
-            },
-
-            SliderEventInternal::SetRange(range) => {
-                self.internal.range = range.clone();
+        event.map(|slider_event_internal, _| {
+            if let SliderEventInternal::SetOrientation(_) = slider_event_internal {
+                self.fixed_orientation = true;
             }
 
-            SliderEventInternal::SetKeyboardFraction(keyboard_fraction) => {
-                self.internal.keyboard_fraction = *keyboard_fraction;
-            }
+            self.internal.apply(slider_event_internal);
         });
 
         event.map(|window_event, _| match window_event {
             WindowEvent::GeometryChanged(_) => {
-                let current = cx.current();
-                let width = cx.cache.get_width(current);
-                let height = cx.cache.get_height(current);
-
-                if width >= height {
-                    self.internal.orientation = Orientation::Horizontal;
-                    self.internal.size = width;
-                } else {
-                    self.internal.orientation = Orientation::Vertical;
-                    self.internal.size = height;
-                }
+                self.internal.resize(cx.bounds(), self.fixed_orientation);
             }
 
             WindowEvent::MouseDown(button) if *button == MouseButton::Left => {
@@ -253,35 +407,8 @@ impl<L: Lens<Target = f32>> View for Slider<L> {
                         cx.set_pointer_events(false);
                     });
 
-                    let thumb_size = self.internal.thumb_size;
-                    let min = self.internal.range.start;
-                    let max = self.internal.range.end;
-                    let step = self.internal.step;
-
-                    let current = cx.current();
-                    let width = cx.cache.get_width(current);
-                    let height = cx.cache.get_height(current);
-                    let posx = cx.cache.get_posx(current);
-                    let posy = cx.cache.get_posy(current);
-
-                    let mut dx = match self.internal.orientation {
-                        Orientation::Horizontal => {
-                            (cx.mouse.left.pos_down.0 - posx - thumb_size / 2.0)
-                                / (width - thumb_size)
-                        }
-
-                        Orientation::Vertical => {
-                            (height - (cx.mouse.left.pos_down.1 - posy) - thumb_size / 2.0)
-                                / (height - thumb_size)
-                        }
-                    };
-
-                    dx = dx.clamp(0.0, 1.0);
-
-                    let mut val = min + dx * (max - min);
-
-                    val = step * (val / step).ceil();
-                    val = val.clamp(min, max);
+                    let (x, y) = cx.mouse.left.pos_down;
+                    let val = self.internal.value_at(cx.bounds(), x, y);
 
                     if let Some(callback) = self.on_changing.take() {
                         (callback)(cx, val);
@@ -302,34 +429,7 @@ impl<L: Lens<Target = f32>> View for Slider<L> {
 
             WindowEvent::MouseMove(x, y) => {
                 if self.is_dragging {
-                    let thumb_size = self.internal.thumb_size;
-
-                    let min = self.internal.range.start;
-                    let max = self.internal.range.end;
-                    let step = self.internal.step;
-
-                    let current = cx.current();
-                    let width = cx.cache.get_width(current);
-                    let height = cx.cache.get_height(current);
-                    let posx = cx.cache.get_posx(current);
-                    let posy = cx.cache.get_posy(current);
-
-                    let mut dx = match self.internal.orientation {
-                        Orientation::Horizontal => {
-                            (*x - posx - thumb_size / 2.0) / (width - thumb_size)
-                        }
-
-                        Orientation::Vertical => {
-                            (height - (*y - posy) - thumb_size / 2.0) / (height - thumb_size)
-                        }
-                    };
-
-                    dx = dx.clamp(0.0, 1.0);
-
-                    let mut val = min + dx * (max - min);
-
-                    val = step * (val / step).ceil();
-                    val = val.clamp(min, max);
+                    let val = self.internal.value_at(cx.bounds(), *x, *y);
 
                     if let Some(callback) = &self.on_changing {
                         (callback)(cx, val);
@@ -337,69 +437,21 @@ impl<L: Lens<Target = f32>> View for Slider<L> {
                 }
             }
 
-            WindowEvent::KeyDown(Code::ArrowUp | Code::ArrowRight, _) => {
-                let min = self.internal.range.start;
-                let max = self.internal.range.end;
-                let step = self.internal.step;
-                let mut val = self.lens.get(cx) + step;
-                // val = step * (val / step).ceil();
-                val = val.clamp(min, max);
-                if let Some(callback) = &self.on_changing {
-                    (callback)(cx, val);
-                }
-            }
-
-            WindowEvent::KeyDown(Code::ArrowDown | Code::ArrowLeft, _) => {
-                let min = self.internal.range.start;
-                let max = self.internal.range.end;
-                let step = self.internal.step;
-                let mut val = self.lens.get(cx) - step;
-                // val = step * (val / step).ceil();
-                val = val.clamp(min, max);
-                if let Some(callback) = &self.on_changing {
-                    (callback)(cx, val);
-                }
-            }
-
-            WindowEvent::ActionRequest(action) => match action.action {
-                Action::Increment => {
-                    let min = self.internal.range.start;
-                    let max = self.internal.range.end;
-                    let step = self.internal.step;
-                    let mut val = self.lens.get(cx) + step;
-                    val = step * (val / step).ceil();
-                    val = val.clamp(min, max);
+            WindowEvent::KeyDown(code, _) => {
+                if let Some(val) = self.internal.key_value(*code, self.lens.get(cx)) {
                     if let Some(callback) = &self.on_changing {
                         (callback)(cx, val);
                     }
                 }
+            }
 
-                Action::Decrement => {
-                    let min = self.internal.range.start;
-                    let max = self.internal.range.end;
-                    let step = self.internal.step;
-                    let mut val = self.lens.get(cx) - step;
-                    val = step * (val / step).ceil();
-                    val = val.clamp(min, max);
+            WindowEvent::ActionRequest(action) => {
+                if let Some(val) = self.internal.action_value(action, self.lens.get(cx)) {
                     if let Some(callback) = &self.on_changing {
                         (callback)(cx, val);
                     }
                 }
-
-                Action::SetValue => {
-                    if let Some(ActionData::NumericValue(val)) = action.data {
-                        let min = self.internal.range.start;
-                        let max = self.internal.range.end;
-                        let mut v = val as f32;
-                        v = v.clamp(min, max);
-                        if let Some(callback) = &self.on_changing {
-                            (callback)(cx, v);
-                        }
-                    }
-                }
-
-                _ => {}
-            },
+            }
 
             _ => {}
         });
@@ -464,11 +516,69 @@ impl<L: Lens> Handle<'_, Slider<L>> {
     }
 
     /// Set the step value for the slider.
+    ///
+    /// The value of the slider snaps to multiples of the step from the start of the range. A step of zero makes the
+    /// slider continuous.
     pub fn step(self, step: f32) -> Self {
         self.modify(|slider: &mut Slider<L>| slider.internal.step = step)
     }
 
-    /// Sets the fraction of a slider that a press of an arrow key will change.
+    /// Sets the orientation of the slider, which otherwise is determined by its dimensions.
+    ///
+    /// # Example
+    /// ```
+    /// # use vizia_core::prelude::*;
+    /// # use vizia_derive::*;
+    /// # let mut cx = &mut Context::default();
+    /// # #[derive(Lens, Default)]
+    /// # pub struct AppData {
+    /// #     value: f32,
+    /// # }
+    /// # impl Model for AppData {}
+    /// # AppData::default().build(cx);
+    /// Slider::new(cx, AppData::value)
+    ///     .orientation(Orientation::Vertical);
+    /// ```
+    pub fn orientation(self, orientation: Orientation) -> Self {
+        self.cx.emit_to(self.entity, SliderEventInternal::SetOrientation(orientation));
+
+        self.toggle_class("vertical", orientation == Orientation::Vertical)
+    }
+
+    /// Sets the values at which tick marks are shown along the track of the slider.
+    ///
+    /// # Example
+    /// ```
+    /// # use vizia_core::prelude::*;
+    /// # use vizia_derive::*;
+    /// # let mut cx = &mut Context::default();
+    /// # #[derive(Lens, Default)]
+    /// # pub struct AppData {
+    /// #     value: f32,
+    /// # }
+    /// # impl Model for AppData {}
+    /// # AppData::default().build(cx);
+    /// Slider::new(cx, AppData::value)
+    ///     .step(0.25)
+    ///     .ticks([0.0, 0.25, 0.5, 0.75, 1.0])
+    ///     .tick_labels(["0", "", "50", "", "100"]);
+    /// ```
+    pub fn ticks(self, ticks: impl IntoIterator<Item = f32>) -> Self {
+        let ticks = ticks.into_iter().collect();
+        self.cx.emit_to(self.entity, SliderEventInternal::SetTicks(ticks));
+
+        self
+    }
+
+    /// Sets the labels shown next to the tick marks of the slider, in the same order as the ticks.
+    pub fn tick_labels<S: ToString>(self, labels: impl IntoIterator<Item = S>) -> Self {
+        let labels = labels.into_iter().map(|label| label.to_string()).collect();
+        self.cx.emit_to(self.entity, SliderEventInternal::SetTickLabels(labels));
+
+        self
+    }
+
+    /// Sets the fraction of the range of a slider that a press of the page up or page down keys will change.
     ///
     /// # Example
     /// ```
@@ -493,3 +603,360 @@ impl<L: Lens> Handle<'_, Slider<L>> {
         self
     }
 }
+
+// A thumb of a range slider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RangeThumb {
+    Start,
+    End,
+}
+
+enum RangeSliderEvent {
+    SetThumb(RangeThumb, Entity),
+    SetValue(RangeThumb, f32),
+}
+
+/// A slider with two thumbs, which can be used to select a range of values.
+///
+/// The range slider is bound to a pair of values, the start and the end of the selected range, and the thumbs can't be
+/// moved past each other. Each thumb can be focused and moved with the keyboard, or with the increment and decrement
+/// accessibility actions, in the same way as the thumb of a [`Slider`]. Pressing the track moves the closest thumb.
+///
+/// # Example
+/// ```
+/// # use vizia_core::prelude::*;
+/// # use vizia_derive::*;
+/// # let mut cx = &mut Context::default();
+/// # #[derive(Lens, Default)]
+/// # pub struct AppData {
+/// #     price: (f32, f32),
+/// # }
+/// # impl Model for AppData {}
+/// # AppData::default().build(cx);
+/// RangeSlider::new(cx, AppData::price)
+///     .range(0.0..100.0)
+///     .step(5.0)
+///     .on_change(|cx, (start, end)| {
+///         debug!("RangeSlider on_change: {} - {}", start, end);
+///     });
+/// ```
+#[derive(Lens)]
+pub struct RangeSlider<L: Lens> {
+    lens: L,
+    dragging: Option<RangeThumb>,
+    fixed_orientation: bool,
+    thumbs: [Entity; 2],
+    internal: SliderDataInternal,
+    on_changing: Option<Box<dyn Fn(&mut EventContext, (f32, f32))>>,
+}
+
+impl<L> RangeSlider<L>
+where
+    L: Lens<Target = (f32, f32)>,
+{
+    /// Creates a new range slider bound to the pair of values targeted by the lens.
+    pub fn new(cx: &mut Context, lens: L) -> Handle<Self> {
+        Self {
+            lens,
+            dragging: None,
+            fixed_orientation: false,
+            thumbs: [Entity::null(); 2],
+            internal: SliderDataInternal::new(),
+            on_changing: None,
+        }
+        .build(cx, move |cx| {
+            Binding::new(cx, RangeSlider::<L>::internal, move |cx, slider_data| {
+                ZStack::new(cx, move |cx| {
+                    let slider_data = slider_data.get(cx);
+                    slider_data.build_ticks(cx);
+
+                    let orientation = slider_data.orientation;
+                    let extent = slider_data.thumb_size / slider_data.size;
+
+                    // Active track between the centers of the thumbs
+                    let data = slider_data.clone();
+                    Element::new(cx).class("active").bind(lens, move |handle, value| {
+                        let (start, end) = value.get(&handle);
+                        let start = data.thumb_offset(start);
+                        let end = data.thumb_offset(end);
+
+                        if orientation == Orientation::Horizontal {
+                            handle
+                                .height(Stretch(1.0))
+                                .left(Percentage(100.0 * (start + extent / 2.0)))
+                                .right(Stretch(1.0))
+                                .width(Percentage(100.0 * (end - start)));
+                        } else {
+                            handle
+                                .width(Stretch(1.0))
+                                .top(Stretch(1.0))
+                                .bottom(Percentage(100.0 * (start + extent / 2.0)))
+                                .height(Percentage(100.0 * (end - start)));
+                        }
+                    });
+
+                    RangeSliderThumb::new(cx, lens, RangeThumb::Start, slider_data.clone());
+                    RangeSliderThumb::new(cx, lens, RangeThumb::End, slider_data);
+                });
+            });
+        })
+        .role(Role::Group)
+    }
+}
+
+impl<L: Lens<Target = (f32, f32)>> RangeSlider<L> {
+    // Moves a thumb to a value without passing the other thumb.
+    fn set_value(&self, cx: &mut EventContext, thumb: RangeThumb, val: f32) {
+        let (start, end) = self.lens.get(cx);
+        let value = match thumb {
+            RangeThumb::Start => (val.min(end), end),
+            RangeThumb::End => (start, val.max(start)),
+        };
+
+        if let Some(callback) = &self.on_changing {
+            (callback)(cx, value);
+        }
+    }
+}
+
+impl<L: Lens<Target = (f32, f32)>> View for RangeSlider<L> {
+    fn element(&self) -> Option<&'static str> {
+        Some("rangeslider")
+    }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.map(|slider_event_internal, _| {
+            if let SliderEventInternal::SetOrientation(_) = slider_event_internal {
+                self.fixed_orientation = true;
+            }
+
+            self.internal.apply(slider_event_internal);
+        });
+
+        event.map(|range_slider_event, meta| match range_slider_event {
+            RangeSliderEvent::SetThumb(thumb, entity) => {
+                self.thumbs[*thumb as usize] = *entity;
+                meta.consume();
+            }
+
+            RangeSliderEvent::SetValue(thumb, val) => {
+                if !cx.is_disabled() {
+                    self.set_value(cx, *thumb, *val);
+                }
+
+                meta.consume();
+            }
+        });
+
+        event.map(|window_event, _| match window_event {
+            WindowEvent::GeometryChanged(_) => {
+                self.internal.resize(cx.bounds(), self.fixed_orientation);
+            }
+
+            WindowEvent::MouseDown(button) if *button == MouseButton::Left => {
+                if !cx.is_disabled() {
+                    let (x, y) = cx.mouse.left.pos_down;
+                    let val = self.internal.value_at(cx.bounds(), x, y);
+
+                    // Move the closest thumb, or the thumb on the side of the press if they overlap.
+                    let (start, end) = self.lens.get(cx);
+                    let thumb = if (val - start).abs() < (val - end).abs()
+                        || ((val - start).abs() == (val - end).abs() && val < start)
+                    {
+                        RangeThumb::Start
+                    } else {
+                        RangeThumb::End
+                    };
+
+                    self.dragging = Some(thumb);
+                    cx.capture();
+                    cx.with_current(self.thumbs[thumb as usize], |cx| {
+                        cx.focus_with_visibility(false);
+                    });
+                    cx.with_current(Entity::root(), |cx| {
+                        cx.set_pointer_events(false);
+                    });
+
+                    self.set_value(cx, thumb, val);
+                }
+            }
+
+            WindowEvent::MouseUp(button) if *button == MouseButton::Left => {
+                if self.dragging.take().is_some() {
+                    cx.release();
+                    cx.with_current(Entity::root(), |cx| {
+                        cx.set_pointer_events(true);
+                    });
+                }
+            }
+
+            WindowEvent::MouseMove(x, y) => {
+                if let Some(thumb) = self.dragging {
+                    let val = self.internal.value_at(cx.bounds(), *x, *y);
+                    self.set_value(cx, thumb, val);
+                }
+            }
+
+            _ => {}
+        });
+    }
+}
+
+impl<L: Lens> Handle<'_, RangeSlider<L>> {
+    /// Sets the callback triggered when either end of the selected range is changed, which is passed the new range.
+    pub fn on_change<F>(self, callback: F) -> Self
+    where
+        F: 'static + Fn(&mut EventContext, (f32, f32)),
+    {
+        self.modify(|slider| slider.on_changing = Some(Box::new(callback)))
+    }
+
+    /// Sets the range of the slider.
+    pub fn range(self, range: Range<f32>) -> Self {
+        self.cx.emit_to(self.entity, SliderEventInternal::SetRange(range));
+
+        self
+    }
+
+    /// Set the step value for the slider. A step of zero makes the slider continuous.
+    pub fn step(self, step: f32) -> Self {
+        self.modify(|slider: &mut RangeSlider<L>| slider.internal.step = step)
+    }
+
+    /// Sets the orientation of the slider, which otherwise is determined by its dimensions.
+    pub fn orientation(self, orientation: Orientation) -> Self {
+        self.cx.emit_to(self.entity, SliderEventInternal::SetOrientation(orientation));
+
+        self.toggle_class("vertical", orientation == Orientation::Vertical)
+    }
+
+    /// Sets the values at which tick marks are shown along the track of the slider.
+    pub fn ticks(self, ticks: impl IntoIterator<Item = f32>) -> Self {
+        let ticks = ticks.into_iter().collect();
+        self.cx.emit_to(self.entity, SliderEventInternal::SetTicks(ticks));
+
+        self
+    }
+
+    /// Sets the labels shown next to the tick marks of the slider, in the same order as the ticks.
+    pub fn tick_labels<S: ToString>(self, labels: impl IntoIterator<Item = S>) -> Self {
+        let labels = labels.into_iter().map(|label| label.to_string()).collect();
+        self.cx.emit_to(self.entity, SliderEventInternal::SetTickLabels(labels));
+
+        self
+    }
+
+    /// Sets the fraction of the range of the slider that a press of the page up or page down keys will change.
+    pub fn keyboard_fraction(self, keyboard_fraction: f32) -> Self {
+        self.cx.emit_to(self.entity, SliderEventInternal::SetKeyboardFraction(keyboard_fraction));
+
+        self
+    }
+}
+
+// A focusable thumb of a range slider, which handles the keyboard and accessibility actions for its end of the range.
+struct RangeSliderThumb<L> {
+    lens: L,
+    thumb: RangeThumb,
+    internal: SliderDataInternal,
+}
+
+impl<L> RangeSliderThumb<L>
+where
+    L: Lens<Target = (f32, f32)>,
+{
+    fn new(
+        cx: &mut Context,
+        lens: L,
+        thumb: RangeThumb,
+        internal: SliderDataInternal,
+    ) -> Handle<Self> {
+        let orientation = internal.orientation;
+        let data = internal.clone();
+        let value = lens.map(move |value| match thumb {
+            RangeThumb::Start => value.0,
+            RangeThumb::End => value.1,
+        });
+
+        let mut handle = Self { lens, thumb, internal }
+            .build(cx, |_| {})
+            .class("thumb")
+            .toggle_class("start", thumb == RangeThumb::Start)
+            .toggle_class("end", thumb == RangeThumb::End)
+            .on_geo_changed(|cx, geo| {
+                if geo.contains(GeoChanged::WIDTH_CHANGED)
+                    || geo.contains(GeoChanged::HEIGHT_CHANGED)
+                {
+                    let bounds = cx.bounds();
+                    cx.emit(SliderEventInternal::SetThumbSize(bounds.w, bounds.h));
+                }
+            })
+            .bind(value, move |handle, value| {
+                let px = data.thumb_offset(value.get(&handle));
+                if orientation == Orientation::Horizontal {
+                    handle
+                        .right(Stretch(1.0))
+                        .top(Stretch(1.0))
+                        .bottom(Stretch(1.0))
+                        .left(Percentage(100.0 * px));
+                } else {
+                    handle
+                        .top(Stretch(1.0))
+                        .left(Stretch(1.0))
+                        .right(Stretch(1.0))
+                        .bottom(Percentage(100.0 * px));
+                }
+            })
+            .role(Role::Slider)
+            .numeric_value(value.map(|val| (*val as f64 * 100.0).round() / 100.0))
+            .text_value(value.map(|val| {
+                let v = (*val as f64 * 100.0).round() / 100.0;
+                format!("{}", v)
+            }))
+            .navigable(true);
+
+        // Let the range slider know which entity to focus when the thumb is dragged.
+        let entity = handle.entity();
+        handle.context().emit_custom(
+            Event::new(RangeSliderEvent::SetThumb(thumb, entity))
+                .target(entity)
+                .origin(entity)
+                .propagate(Propagation::Up),
+        );
+
+        handle
+    }
+
+    fn value(&self, cx: &mut EventContext) -> f32 {
+        let (start, end) = self.lens.get(cx);
+        match self.thumb {
+            RangeThumb::Start => start,
+            RangeThumb::End => end,
+        }
+    }
+}
+
+impl<L: Lens<Target = (f32, f32)>> View for RangeSliderThumb<L> {
+    fn accessibility(&self, _cx: &mut AccessContext, node: &mut AccessNode) {
+        self.internal.accessibility(node);
+    }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.map(|window_event, meta| match window_event {
+            WindowEvent::KeyDown(code, _) => {
+                if let Some(val) = self.internal.key_value(*code, self.value(cx)) {
+                    cx.emit(RangeSliderEvent::SetValue(self.thumb, val));
+                    meta.consume();
+                }
+            }
+
+            WindowEvent::ActionRequest(action) => {
+                if let Some(val) = self.internal.action_value(action, self.value(cx)) {
+                    cx.emit(RangeSliderEvent::SetValue(self.thumb, val));
+                }
+            }
+
+            _ => {}
+        });
+    }
+}
@@ -122,6 +122,7 @@ impl Spinbox {
         })
         .toggle_class("horizontal", Spinbox::orientation.map(|o| o == &Orientation::Horizontal))
         .toggle_class("vertical", Spinbox::orientation.map(|o| o == &Orientation::Vertical))
+        .role(Role::SpinButton)
         .navigable(true)
     }
 }
@@ -165,6 +166,11 @@ impl View for Spinbox {
         Some("spinbox")
     }
 
+    fn accessibility(&self, _cx: &mut AccessContext, node: &mut AccessNode) {
+        node.add_action(Action::Increment);
+        node.add_action(Action::Decrement);
+    }
+
     fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
         event.map(|spinbox_event, _| match spinbox_event {
             SpinboxEvent::Increment => {
@@ -179,5 +185,15 @@ impl View for Spinbox {
                 }
             }
         });
+
+        event.map(|window_event, _| match window_event {
+            WindowEvent::ActionRequest(action) => match action.action {
+                Action::Increment => cx.emit(SpinboxEvent::Increment),
+                Action::Decrement => cx.emit(SpinboxEvent::Decrement),
+                _ => {}
+            },
+
+            _ => {}
+        });
     }
 }
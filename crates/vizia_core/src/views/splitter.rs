@@ -0,0 +1,373 @@
+use std::rc::Rc;
+
+use crate::prelude::*;
+
+/// Describes a pane of a [Splitter].
+pub struct SplitterPane {
+    content: Rc<dyn Fn(&mut Context)>,
+    proportion: f32,
+    min_size: f32,
+    max_size: Option<f32>,
+    collapsible: bool,
+}
+
+impl SplitterPane {
+    /// Creates a new [SplitterPane] with a closure which builds the content of the pane.
+    ///
+    /// ```ignore
+    /// SplitterPane::new(|cx| {
+    ///     Label::new(cx, "Sidebar");
+    /// })
+    /// .proportion(0.25)
+    /// .min_size(120.0)
+    /// .collapsible(true);
+    /// ```
+    pub fn new(content: impl 'static + Fn(&mut Context)) -> Self {
+        Self {
+            content: Rc::new(content),
+            proportion: 1.0,
+            min_size: 0.0,
+            max_size: None,
+            collapsible: false,
+        }
+    }
+
+    /// Sets the initial share of the space of the splitter taken by the pane, relative to the other panes.
+    /// Defaults to 1.
+    pub fn proportion(mut self, proportion: f32) -> Self {
+        self.proportion = proportion.max(0.0);
+        self
+    }
+
+    /// Sets the minimum size of the pane in logical pixels. Defaults to 0.
+    pub fn min_size(mut self, size: f32) -> Self {
+        self.min_size = size.max(0.0);
+        self
+    }
+
+    /// Sets the maximum size of the pane in logical pixels. Defaults to no maximum.
+    pub fn max_size(mut self, size: f32) -> Self {
+        self.max_size = Some(size.max(self.min_size));
+        self
+    }
+
+    /// Sets whether the pane is collapsed by double clicking a divider next to it. Defaults to false.
+    pub fn collapsible(mut self, collapsible: bool) -> Self {
+        self.collapsible = collapsible;
+        self
+    }
+}
+
+/// Events used by the [Splitter] view.
+pub enum SplitterEvent {
+    /// Sets the proportions of the panes, which are ignored if there isn't one for each pane.
+    SetProportions(Vec<f32>),
+    /// Sets whether the pane with the given index is collapsed.
+    SetCollapsed(usize, bool),
+}
+
+// Sent by a divider of the splitter while it is being dragged or when it's double clicked.
+enum SplitterDividerEvent {
+    Start(usize),
+    Drag(usize, f32),
+    End,
+    Collapse(usize),
+}
+
+/// A container which lays out its panes in a row or column, with a divider between each pair of panes which resizes
+/// them when dragged.
+///
+/// Each pane takes a share of the space of the splitter given by its proportion, so the panes keep their proportions
+/// when the splitter is resized. Dragging a divider moves space between the panes on either side of it, within the
+/// minimum and maximum sizes of the panes, and double clicking a divider collapses the collapsible pane next to it,
+/// or expands it again. The proportions can be bound to a lens with [`proportions`](Handle::proportions) and written
+/// back with [`on_resize`](Handle::on_resize) so that they're persisted with the rest of the application data.
+///
+/// # Example
+/// ```
+/// # use vizia_core::prelude::*;
+/// # let cx = &mut Context::default();
+/// Splitter::new(
+///     cx,
+///     Orientation::Horizontal,
+///     vec![
+///         SplitterPane::new(|cx| {
+///             Label::new(cx, "Files");
+///         })
+///         .proportion(0.25)
+///         .min_size(120.0)
+///         .collapsible(true),
+///         SplitterPane::new(|cx| {
+///             Label::new(cx, "Editor");
+///         })
+///         .proportion(0.75)
+///         .min_size(200.0),
+///     ],
+/// );
+/// ```
+#[derive(Lens)]
+pub struct Splitter {
+    proportions: Vec<f32>,
+    collapsed: Vec<bool>,
+    #[lens(ignore)]
+    limits: Vec<(f32, Option<f32>)>,
+    #[lens(ignore)]
+    collapsible: Vec<bool>,
+    #[lens(ignore)]
+    orientation: Orientation,
+    // The sizes, in logical pixels, of the panes on either side of the divider being dragged when the drag started.
+    #[lens(ignore)]
+    drag_start: Option<(f32, f32)>,
+    #[lens(ignore)]
+    on_resize: Option<Box<dyn Fn(&mut EventContext, &[f32])>>,
+}
+
+impl Splitter {
+    /// Creates a new [Splitter] view with the given panes. A horizontal splitter places the panes side by side, while
+    /// a vertical splitter stacks them.
+    pub fn new(
+        cx: &mut Context,
+        orientation: Orientation,
+        panes: Vec<SplitterPane>,
+    ) -> Handle<Self> {
+        Self {
+            proportions: panes.iter().map(|pane| pane.proportion).collect(),
+            collapsed: vec![false; panes.len()],
+            limits: panes.iter().map(|pane| (pane.min_size, pane.max_size)).collect(),
+            collapsible: panes.iter().map(|pane| pane.collapsible).collect(),
+            orientation,
+            drag_start: None,
+            on_resize: None,
+        }
+        .build(cx, move |cx| {
+            for (index, pane) in panes.into_iter().enumerate() {
+                if index > 0 {
+                    SplitterDivider { index: index - 1, orientation, drag_start: None }
+                        .build(cx, |_| {})
+                        .role(Role::Splitter);
+                }
+
+                let size = Splitter::proportions.map(move |proportions| {
+                    Stretch(proportions.get(index).copied().unwrap_or(1.0))
+                });
+                let handle = SplitterPaneView { index }.build(cx, |cx| (pane.content)(cx)).display(
+                    Splitter::collapsed
+                        .map(move |collapsed| !collapsed.get(index).copied().unwrap_or_default()),
+                );
+
+                let handle = match orientation {
+                    Orientation::Horizontal => handle.width(size).min_width(Pixels(pane.min_size)),
+                    Orientation::Vertical => handle.height(size).min_height(Pixels(pane.min_size)),
+                };
+
+                if let Some(max_size) = pane.max_size {
+                    match orientation {
+                        Orientation::Horizontal => handle.max_width(Pixels(max_size)),
+                        Orientation::Vertical => handle.max_height(Pixels(max_size)),
+                    };
+                }
+            }
+        })
+        .layout_type(match orientation {
+            Orientation::Horizontal => LayoutType::Row,
+            Orientation::Vertical => LayoutType::Column,
+        })
+        .toggle_class("horizontal", orientation == Orientation::Horizontal)
+        .toggle_class("vertical", orientation == Orientation::Vertical)
+    }
+
+    // Returns the size, in logical pixels, of the pane with the given index along the orientation of the splitter.
+    fn pane_size(&self, cx: &EventContext, index: usize) -> Option<f32> {
+        let pane = cx.current().child_iter(cx.tree).find(|entity| {
+            cx.views
+                .get(entity)
+                .and_then(|view| view.downcast_ref::<SplitterPaneView>())
+                .is_some_and(|pane| pane.index == index)
+        })?;
+
+        let size = match self.orientation {
+            Orientation::Horizontal => cx.cache.get_width(pane),
+            Orientation::Vertical => cx.cache.get_height(pane),
+        };
+
+        Some(size / cx.scale_factor())
+    }
+
+    // Moves space between the panes on either side of a divider, keeping both within their limits.
+    fn resize(&mut self, cx: &mut EventContext, divider: usize, delta: f32) {
+        let Some((start, next_start)) = self.drag_start else { return };
+
+        let total = start + next_start;
+        if total <= 0.0 {
+            return;
+        }
+
+        let (min, max) = self.limits[divider];
+        let (next_min, next_max) = self.limits[divider + 1];
+
+        let mut size = (start + delta).max(min).min(max.unwrap_or(f32::INFINITY));
+        size = size.min(total - next_min).max(total - next_max.unwrap_or(f32::INFINITY));
+        size = size.clamp(0.0, total);
+
+        let proportion = self.proportions[divider] + self.proportions[divider + 1];
+        self.proportions[divider] = proportion * size / total;
+        self.proportions[divider + 1] = proportion - self.proportions[divider];
+
+        if let Some(callback) = &self.on_resize {
+            (callback)(cx, &self.proportions);
+        }
+    }
+}
+
+impl View for Splitter {
+    fn element(&self) -> Option<&'static str> {
+        Some("splitter")
+    }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.take(|divider_event, _| match divider_event {
+            SplitterDividerEvent::Start(divider) => {
+                // A divider next to a collapsed pane only expands it again.
+                if self.collapsed[divider] || self.collapsed[divider + 1] {
+                    return;
+                }
+
+                self.drag_start = self.pane_size(cx, divider).zip(self.pane_size(cx, divider + 1));
+            }
+
+            SplitterDividerEvent::Drag(divider, delta) => {
+                self.resize(cx, divider, delta);
+            }
+
+            SplitterDividerEvent::End => {
+                self.drag_start = None;
+            }
+
+            SplitterDividerEvent::Collapse(divider) => {
+                if self.collapsed[divider] {
+                    cx.emit(SplitterEvent::SetCollapsed(divider, false));
+                } else if self.collapsed[divider + 1] {
+                    cx.emit(SplitterEvent::SetCollapsed(divider + 1, false));
+                } else if self.collapsible[divider] {
+                    cx.emit(SplitterEvent::SetCollapsed(divider, true));
+                } else if self.collapsible[divider + 1] {
+                    cx.emit(SplitterEvent::SetCollapsed(divider + 1, true));
+                }
+            }
+        });
+
+        event.map(|splitter_event, meta| match splitter_event {
+            SplitterEvent::SetProportions(proportions) => {
+                if proportions.len() == self.proportions.len() {
+                    self.proportions.clone_from(proportions);
+                }
+
+                meta.consume();
+            }
+
+            SplitterEvent::SetCollapsed(index, collapsed) => {
+                if let Some(flag) = self.collapsed.get_mut(*index) {
+                    *flag = *collapsed;
+                }
+
+                meta.consume();
+            }
+        });
+    }
+}
+
+impl Handle<'_, Splitter> {
+    /// Sets the proportions of the panes, which are ignored if there isn't one for each pane. Accepts a value or a
+    /// lens to the proportions.
+    pub fn proportions(self, proportions: impl Res<Vec<f32>>) -> Self {
+        self.bind(proportions, |handle, proportions| {
+            let proportions = proportions.get(&handle);
+            handle.modify(|splitter: &mut Splitter| {
+                if proportions.len() == splitter.proportions.len() {
+                    splitter.proportions = proportions;
+                }
+            });
+        })
+    }
+
+    /// Sets the callback triggered when the panes are resized by dragging a divider, which is passed the new
+    /// proportions of the panes.
+    pub fn on_resize<F>(self, callback: F) -> Self
+    where
+        F: 'static + Fn(&mut EventContext, &[f32]),
+    {
+        self.modify(|splitter: &mut Splitter| splitter.on_resize = Some(Box::new(callback)))
+    }
+}
+
+// A view which contains the content of a pane of a splitter.
+struct SplitterPaneView {
+    index: usize,
+}
+
+impl View for SplitterPaneView {
+    fn element(&self) -> Option<&'static str> {
+        Some("splitter-pane")
+    }
+}
+
+// The divider between two panes of a splitter, which resizes them when dragged.
+struct SplitterDivider {
+    index: usize,
+    orientation: Orientation,
+    drag_start: Option<f32>,
+}
+
+impl SplitterDivider {
+    fn cursor_position(&self, cx: &EventContext) -> f32 {
+        match self.orientation {
+            Orientation::Horizontal => cx.mouse.cursor_x,
+            Orientation::Vertical => cx.mouse.cursor_y,
+        }
+    }
+}
+
+impl View for SplitterDivider {
+    fn element(&self) -> Option<&'static str> {
+        Some("splitter-divider")
+    }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.map(|window_event, meta| match window_event {
+            WindowEvent::MouseDown(MouseButton::Left) => {
+                self.drag_start = Some(self.cursor_position(cx));
+                cx.capture();
+                cx.set_active(true);
+                cx.emit(SplitterDividerEvent::Start(self.index));
+                meta.consume();
+            }
+
+            WindowEvent::MouseMove(x, y) => {
+                if let Some(start) = self.drag_start {
+                    let position = match self.orientation {
+                        Orientation::Horizontal => *x,
+                        Orientation::Vertical => *y,
+                    };
+                    let delta = (position - start) / cx.scale_factor();
+                    cx.emit(SplitterDividerEvent::Drag(self.index, delta));
+                }
+            }
+
+            WindowEvent::MouseUp(MouseButton::Left) => {
+                if self.drag_start.take().is_some() {
+                    cx.release();
+                    cx.set_active(false);
+                    cx.emit(SplitterDividerEvent::End);
+                    meta.consume();
+                }
+            }
+
+            WindowEvent::MouseDoubleClick(MouseButton::Left) => {
+                cx.emit(SplitterDividerEvent::Collapse(self.index));
+                meta.consume();
+            }
+
+            _ => {}
+        });
+    }
+}
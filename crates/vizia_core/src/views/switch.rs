@@ -164,7 +164,8 @@ impl View for Switch {
         event.map(|window_event, meta| match window_event {
             WindowEvent::Press { mouse } => {
                 let over = if *mouse { cx.mouse.left.pressed } else { cx.focused() };
-                if over == cx.current() && meta.target == cx.current() && !cx.is_disabled() {
+                if cx.is_press_over(meta, over) && meta.target == cx.current() && !cx.is_disabled()
+                {
                     if let Some(callback) = &self.on_toggle {
                         (callback)(cx);
                     }
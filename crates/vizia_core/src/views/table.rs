@@ -0,0 +1,469 @@
+use std::{cmp::Ordering, ops::Deref, rc::Rc};
+
+use crate::prelude::*;
+
+// The minimum width, in logical pixels, a column can be resized to.
+const MIN_COLUMN_WIDTH: f32 = 24.0;
+
+/// The direction in which the rows of a [Table] are sorted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Data)]
+pub enum SortDirection {
+    /// Rows are sorted from the lowest to the highest value.
+    Ascending,
+    /// Rows are sorted from the highest to the lowest value.
+    Descending,
+}
+
+/// Describes a column of a [Table].
+pub struct TableColumn<T> {
+    header: String,
+    width: f32,
+    cell: Rc<dyn Fn(&mut Context, &T)>,
+    compare: Option<Rc<dyn Fn(&T, &T) -> Ordering>>,
+}
+
+impl<T: 'static> TableColumn<T> {
+    /// Creates a new [TableColumn] with the given header text and a closure which builds the cell for an item.
+    ///
+    /// ```ignore
+    /// TableColumn::new("Name", |cx, person: &Person| {
+    ///     Label::new(cx, &person.name);
+    /// })
+    /// .width(150.0)
+    /// .sort_by(|a, b| a.name.cmp(&b.name));
+    /// ```
+    pub fn new(header: impl ToString, cell: impl 'static + Fn(&mut Context, &T)) -> Self {
+        Self { header: header.to_string(), width: 100.0, cell: Rc::new(cell), compare: None }
+    }
+
+    /// Sets the initial width of the column in logical pixels. Defaults to 100.
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width.max(MIN_COLUMN_WIDTH);
+        self
+    }
+
+    /// Makes the table sortable by this column, using the given closure to compare items.
+    pub fn sort_by(mut self, compare: impl 'static + Fn(&T, &T) -> Ordering) -> Self {
+        self.compare = Some(Rc::new(compare));
+        self
+    }
+}
+
+/// Events used by the [Table] view.
+pub enum TableEvent {
+    /// Selects the row of the item with the given index.
+    Select(usize),
+    /// Sorts the rows by the column with the given index, reversing the direction if the rows are already sorted
+    /// by that column.
+    Sort(usize),
+    /// Sets the width, in logical pixels, of the column with the given index.
+    SetColumnWidth(usize, f32),
+}
+
+// Sent by the resize handle of a column header while it is being dragged.
+enum TableResizeEvent {
+    Start(usize),
+    Drag(usize, f32),
+    End,
+}
+
+// Sent when the rows or the header row are scrolled horizontally, so that the other can be scrolled to match.
+struct TableScrollEvent(f32);
+
+/// A view for displaying a list of items as rows, with a column for each field.
+///
+/// Clicking the header of a sortable column sorts the rows, and dragging the edge of a header resizes the column.
+/// The header row stays in place while the rows are scrolled vertically, and scrolls along with them horizontally.
+#[derive(Lens)]
+pub struct Table {
+    widths: Vec<f32>,
+    sortable: Vec<bool>,
+    sort: Option<(usize, SortDirection)>,
+    selected: Option<usize>,
+    resize_start: Option<f32>,
+    scroll_x: f32,
+    on_select: Option<Box<dyn Fn(&mut EventContext, usize)>>,
+}
+
+impl Table {
+    /// Creates a new [Table] view from a lens to a list of items and the columns of the table.
+    pub fn new<L: Lens, T: 'static>(
+        cx: &mut Context,
+        list: L,
+        columns: Vec<TableColumn<T>>,
+    ) -> Handle<Self>
+    where
+        L::Target: Deref<Target = [T]> + Data,
+    {
+        let columns = Rc::new(columns);
+
+        Self {
+            widths: columns.iter().map(|column| column.width).collect(),
+            sortable: columns.iter().map(|column| column.compare.is_some()).collect(),
+            sort: None,
+            selected: None,
+            resize_start: None,
+            scroll_x: 0.0,
+            on_select: None,
+        }
+        .build(cx, move |cx| {
+            ScrollView::new(cx, |cx| {
+                HStack::new(cx, |cx| {
+                    for (index, column) in columns.iter().enumerate() {
+                        TableHeader::new(cx, index, &column.header);
+                    }
+                });
+            })
+            .class("table-header-row")
+            .show_horizontal_scrollbar(false)
+            .show_vertical_scrollbar(false)
+            .scroll_x(Table::scroll_x)
+            .on_scroll(|cx, x, _| cx.emit(TableScrollEvent(x)));
+
+            ScrollView::new(cx, move |cx| {
+                Binding::new(cx, Table::sort, move |cx, sort| {
+                    let columns = columns.clone();
+                    let sort = sort.get(cx);
+                    Binding::new(cx, list, move |cx, list| {
+                        let list_data = list.get(cx);
+                        let items: &[T] = &list_data;
+
+                        let mut order = (0..items.len()).collect::<Vec<_>>();
+                        if let Some((column, direction)) = sort {
+                            if let Some(compare) = &columns[column].compare {
+                                order.sort_by(|a, b| {
+                                    let ordering = compare(&items[*a], &items[*b]);
+                                    match direction {
+                                        SortDirection::Ascending => ordering,
+                                        SortDirection::Descending => ordering.reverse(),
+                                    }
+                                });
+                            }
+                        }
+
+                        for index in order {
+                            TableRow::new(cx, index, |cx| {
+                                for (column_index, column) in columns.iter().enumerate() {
+                                    VStack::new(cx, |cx| (column.cell)(cx, &items[index]))
+                                        .class("table-cell")
+                                        .width(
+                                            Table::widths
+                                                .map(move |widths| Pixels(widths[column_index])),
+                                        );
+                                }
+                            });
+                        }
+                    });
+                });
+            })
+            .scroll_x(Table::scroll_x)
+            .on_scroll(|cx, x, _| cx.emit(TableScrollEvent(x)));
+        })
+        .role(Role::Table)
+    }
+}
+
+impl View for Table {
+    fn element(&self) -> Option<&'static str> {
+        Some("table")
+    }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.take(|resize_event, _| match resize_event {
+            TableResizeEvent::Start(column) => {
+                self.resize_start = self.widths.get(column).copied();
+            }
+
+            TableResizeEvent::Drag(column, delta) => {
+                if let Some(start) = self.resize_start {
+                    cx.emit(TableEvent::SetColumnWidth(column, start + delta));
+                }
+            }
+
+            TableResizeEvent::End => {
+                self.resize_start = None;
+            }
+        });
+
+        event.take(|scroll: TableScrollEvent, _| {
+            self.scroll_x = scroll.0;
+        });
+
+        event.take(|table_event, _| match table_event {
+            TableEvent::Select(index) => {
+                if self.selected != Some(index) {
+                    self.selected = Some(index);
+                    if let Some(on_select) = &self.on_select {
+                        on_select(cx, index);
+                    }
+                }
+            }
+
+            TableEvent::Sort(column) => {
+                if !self.sortable.get(column).copied().unwrap_or_default() {
+                    return;
+                }
+
+                self.sort = match self.sort {
+                    Some((sorted, SortDirection::Ascending)) if sorted == column => {
+                        Some((column, SortDirection::Descending))
+                    }
+                    _ => Some((column, SortDirection::Ascending)),
+                };
+            }
+
+            TableEvent::SetColumnWidth(column, width) => {
+                if let Some(w) = self.widths.get_mut(column) {
+                    *w = width.max(MIN_COLUMN_WIDTH);
+                }
+            }
+        });
+    }
+}
+
+impl Handle<'_, Table> {
+    /// Sets the index of the item whose row is selected.
+    pub fn selected(self, selected: impl Res<Option<usize>>) -> Self {
+        self.bind(selected, |handle, selected| {
+            let selected = selected.get(&handle);
+            handle.modify(|table: &mut Table| table.selected = selected);
+        })
+    }
+
+    /// Sets the callback triggered when a row is selected, which receives the index of the item.
+    pub fn on_select<F>(self, callback: F) -> Self
+    where
+        F: 'static + Fn(&mut EventContext, usize),
+    {
+        self.modify(|table: &mut Table| table.on_select = Some(Box::new(callback)))
+    }
+}
+
+/// A view which represents the header of a column within a table.
+pub struct TableHeader {}
+
+impl TableHeader {
+    /// Creates a new [TableHeader] view.
+    pub fn new(cx: &mut Context, index: usize, text: &str) -> Handle<Self> {
+        Self {}
+            .build(cx, |cx| {
+                Label::new(cx, text).hoverable(false);
+                TableResizeHandle { column: index, drag_start: None }
+                    .build(cx, |_| {})
+                    .class("table-resize-handle");
+            })
+            .width(Table::widths.map(move |widths| Pixels(widths[index])))
+            .toggle_class(
+                "ascending",
+                Table::sort.map(move |sort| *sort == Some((index, SortDirection::Ascending))),
+            )
+            .toggle_class(
+                "descending",
+                Table::sort.map(move |sort| *sort == Some((index, SortDirection::Descending))),
+            )
+            .role(Role::ColumnHeader)
+            .on_press(move |cx| cx.emit(TableEvent::Sort(index)))
+    }
+}
+
+impl View for TableHeader {
+    fn element(&self) -> Option<&'static str> {
+        Some("table-header")
+    }
+}
+
+// The handle on the edge of a column header which resizes the column when dragged.
+struct TableResizeHandle {
+    column: usize,
+    drag_start: Option<f32>,
+}
+
+impl View for TableResizeHandle {
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.map(|window_event, meta| match window_event {
+            WindowEvent::MouseDown(MouseButton::Left) => {
+                self.drag_start = Some(cx.mouse.cursor_x);
+                cx.capture();
+                cx.set_active(true);
+                cx.emit(TableResizeEvent::Start(self.column));
+                meta.consume();
+            }
+
+            WindowEvent::MouseMove(x, _) => {
+                if let Some(start) = self.drag_start {
+                    let delta = (*x - start) / cx.scale_factor();
+                    cx.emit(TableResizeEvent::Drag(self.column, delta));
+                }
+            }
+
+            WindowEvent::MouseUp(MouseButton::Left) => {
+                if self.drag_start.take().is_some() {
+                    cx.release();
+                    cx.set_active(false);
+                    cx.emit(TableResizeEvent::End);
+                    meta.consume();
+                }
+            }
+
+            // Prevent the press from sorting the column.
+            WindowEvent::Press { .. } | WindowEvent::PressDown { .. } => meta.consume(),
+
+            _ => {}
+        });
+    }
+}
+
+/// A view which represents a selectable row within a table.
+///
+/// The selected row has the `selected` class and is reported as selected to assistive technologies.
+pub struct TableRow {
+    selected: bool,
+}
+
+impl TableRow {
+    /// Creates a new [TableRow] view for the item with the given index.
+    pub fn new(cx: &mut Context, index: usize, content: impl FnOnce(&mut Context)) -> Handle<Self> {
+        Self { selected: false }
+            .build(cx, content)
+            .role(Role::Row)
+            .bind(
+                Table::selected.map(move |selected| *selected == Some(index)),
+                |mut handle, selected| {
+                    let selected = selected.get(&handle);
+                    let entity = handle.entity();
+                    handle.context().style.needs_access_update(entity);
+                    handle
+                        .modify(|row: &mut TableRow| row.selected = selected)
+                        .toggle_class("selected", selected);
+                },
+            )
+            .on_press(move |cx| cx.emit(TableEvent::Select(index)))
+    }
+}
+
+impl View for TableRow {
+    fn element(&self) -> Option<&'static str> {
+        Some("table-row")
+    }
+
+    fn accessibility(&self, _cx: &mut AccessContext, node: &mut AccessNode) {
+        node.set_selected(self.selected);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+    use crate::context::backend::BackendContext;
+    use crate::context::test_support::{update, window_context};
+
+    #[derive(Lens)]
+    struct AppData {
+        items: Vec<u32>,
+    }
+
+    impl Model for AppData {}
+
+    // Builds a table of the items with a sortable column, recording the order in which the cells are built.
+    fn table(cx: &mut BackendContext, width: f32) -> (Entity, Rc<RefCell<Vec<u32>>>) {
+        AppData { items: vec![2, 3, 1] }.build(cx.context());
+
+        let built = Rc::new(RefCell::new(Vec::new()));
+        let order = built.clone();
+        let table = Table::new(
+            cx.context(),
+            AppData::items,
+            vec![
+                TableColumn::new("Value", move |cx, item: &u32| {
+                    order.borrow_mut().push(*item);
+                    Label::new(cx, *item);
+                })
+                .width(width)
+                .sort_by(|a: &u32, b: &u32| a.cmp(b)),
+                TableColumn::new("Unsorted", |cx, item: &u32| {
+                    Label::new(cx, *item);
+                })
+                .width(width),
+            ],
+        )
+        .entity();
+
+        (table, built)
+    }
+
+    #[test]
+    fn sort_order() {
+        let mut cx = window_context(400, 300);
+        let (table, built) = table(&mut cx, 100.0);
+        update(&mut cx);
+        assert_eq!(*built.borrow(), [2, 3, 1]);
+
+        // Sorting by a column sorts ascending first and then reverses the direction.
+        built.borrow_mut().clear();
+        cx.0.emit_to(table, TableEvent::Sort(0));
+        update(&mut cx);
+        assert_eq!(*built.borrow(), [1, 2, 3]);
+        assert_eq!(cx.0.query("table-header.ascending").len(), 1);
+
+        built.borrow_mut().clear();
+        cx.0.emit_to(table, TableEvent::Sort(0));
+        update(&mut cx);
+        assert_eq!(*built.borrow(), [3, 2, 1]);
+        assert_eq!(cx.0.query("table-header.descending").len(), 1);
+
+        // Columns without a comparison can't be sorted by.
+        built.borrow_mut().clear();
+        cx.0.emit_to(table, TableEvent::Sort(1));
+        update(&mut cx);
+        assert!(built.borrow().is_empty());
+
+        // Rows are selected by the index of their item rather than their position.
+        cx.0.emit_to(table, TableEvent::Select(2));
+        update(&mut cx);
+        let selected = cx.0.query("table-row.selected");
+        assert_eq!(selected.len(), 1);
+        assert_eq!(cx.0.query("table-row").last(), Some(&selected[0]));
+    }
+
+    #[test]
+    fn column_resize() {
+        let mut cx = window_context(400, 300);
+        let (table, _) = table(&mut cx, 100.0);
+        update(&mut cx);
+
+        let headers = cx.0.query("table-header");
+        let cells = cx.0.query("table-row .table-cell");
+        assert_eq!(cx.0.cache.get_width(headers[0]), 100.0);
+
+        // Resizing a column resizes its header and cells, down to the minimum width.
+        cx.0.emit_to(table, TableEvent::SetColumnWidth(0, 300.0));
+        update(&mut cx);
+        assert_eq!(cx.0.cache.get_width(headers[0]), 300.0);
+        assert_eq!(cx.0.cache.get_width(cells[0]), 300.0);
+        assert_eq!(cx.0.cache.get_width(headers[1]), 100.0);
+
+        cx.0.emit_to(table, TableEvent::SetColumnWidth(0, 0.0));
+        update(&mut cx);
+        assert_eq!(cx.0.cache.get_width(headers[0]), MIN_COLUMN_WIDTH);
+    }
+
+    #[test]
+    fn header_scrolls_with_rows() {
+        let mut cx = window_context(400, 300);
+        table(&mut cx, 300.0);
+        update(&mut cx);
+
+        // The columns are wider than the table, so scrolling the rows horizontally scrolls the header row too.
+        let scrollviews = cx.0.query("table scrollview");
+        let (header_row, rows) = (scrollviews[0], scrollviews[1]);
+        cx.0.emit_to(rows, ScrollEvent::SetX(1.0));
+        update(&mut cx);
+
+        let header = cx.0.query("table-header")[0];
+        let cell = cx.0.query("table-row .table-cell")[0];
+        assert_eq!(cx.0.cache.get_posx(header), cx.0.cache.get_posx(cell));
+        assert!(cx.0.cache.get_posx(header) < cx.0.cache.get_posx(header_row));
+    }
+}
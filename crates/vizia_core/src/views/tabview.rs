@@ -1,68 +1,272 @@
-use std::ops::Deref;
+use std::{
+    cell::{Cell, RefCell},
+    hash::Hash,
+    ops::Deref,
+    rc::Rc,
+};
 
-use crate::{icons::ICON_PLUS, prelude::*};
+use super::keyed::update_keyed_items;
+use super::reorder::{moved_index, DragReorder};
+use crate::{
+    icons::{ICON_PLUS, ICON_X},
+    prelude::*,
+};
 
+/// Events used by a [TabView].
 pub enum TabEvent {
+    /// Selects the tab with the given index.
     SetSelected(usize),
+    /// Requests that the tab with the given index is closed, triggering the `on_close` callback of a closable tabview.
+    Close(usize),
 }
 
+// Sent by a tab header when the cursor is dragged out of it.
+struct TabDragEvent(usize);
+
+// Sent by the tab headers when the tabs change.
+enum TabItemsEvent {
+    // The new number of tabs and, for each previous tab, its new index if it's still in the list.
+    Update { len: usize, moved: Vec<Option<usize>> },
+}
+
+/// A view which displays a tab bar above the content of the selected tab.
+///
+/// The content of each tab is built the first time the tab is selected and is kept while other tabs are selected.
+/// Pressing Ctrl+Tab or Ctrl+Shift+Tab selects the next or previous tab.
 #[derive(Lens)]
 pub struct TabView {
     selected_index: usize,
     is_vertical: bool,
+    closable: bool,
+    reorderable: bool,
+    // Whether the content of each tab has been built.
+    visited: Vec<bool>,
+
+    #[lens(ignore)]
+    tab_count: usize,
+    // The tab header being dragged to reorder the tabs.
+    #[lens(ignore)]
+    drag: DragReorder,
 
     #[lens(ignore)]
     on_select: Option<Box<dyn Fn(&mut EventContext, usize)>>,
+    #[lens(ignore)]
+    on_close: Option<Box<dyn Fn(&mut EventContext, usize)>>,
+    #[lens(ignore)]
+    on_reorder: Option<Box<dyn Fn(&mut EventContext, usize, usize)>>,
 }
 
 impl TabView {
+    /// Creates a new [TabView] with a tab for each item of the bound list, which are identified by their value.
+    ///
+    /// See [`new_keyed`](TabView::new_keyed) for lists whose items aren't unique.
     pub fn new<L, T, F>(cx: &mut Context, lens: L, content: F) -> Handle<Self>
+    where
+        L: Lens<Target: std::ops::Deref<Target = [T]>>,
+        T: Data + Hash + Eq,
+        F: 'static + Fn(&mut Context, MapRef<L, T>) -> TabPair,
+    {
+        Self::new_keyed(cx, lens, |tab: &T| tab.clone(), content)
+    }
+
+    /// Creates a new [TabView] with a tab for each item of the bound list, where each tab is identified by the key
+    /// returned by `key`.
+    ///
+    /// When the list changes, only the tabs with new keys are built and only the tabs whose keys were removed are
+    /// destroyed, so the other tabs keep their headers and the content they've built, and the selected tab stays
+    /// selected as the tabs move. Keys should be unique, as only one of the tabs with the same key is kept.
+    pub fn new_keyed<L, T, K, F>(
+        cx: &mut Context,
+        lens: L,
+        key: impl 'static + Fn(&T) -> K,
+        content: F,
+    ) -> Handle<Self>
     where
         L: Lens<Target: std::ops::Deref<Target = [T]>>,
         T: Clone + 'static,
-        F: 'static + Clone + Fn(&mut Context, Index<L, T>) -> TabPair,
+        K: 'static + Data + Hash + Eq,
+        F: 'static + Fn(&mut Context, MapRef<L, T>) -> TabPair,
     {
-        Self { selected_index: 0, is_vertical: false, on_select: None }
-            .build(cx, move |cx| {
-                let content2 = content.clone();
-                // Tab headers
-                ScrollView::new(cx, move |cx| {
-                    //VStack::new(cx, move |cx| {
-                    Binding::new(cx, lens.map(|list| list.len()), move |cx, list_length| {
-                        let list_length = list_length.get(cx);
-                        for index in 0..list_length {
-                            let l = lens.idx(index);
-                            let builder = (content2)(cx, l).header;
-                            TabHeader::new(cx, index, builder)
-                                .bind(TabView::selected_index, move |handle, selected_index| {
-                                    let selected_index = selected_index.get(handle.cx);
-                                    handle.checked(selected_index == index);
-                                })
-                                .toggle_class("vertical", TabView::is_vertical);
+        let content = Rc::new(content);
+        let keys = lens.map(move |list| list.iter().map(&key).collect::<Vec<_>>());
+        let tab_count = keys.get(cx).len();
+        let tab = move |index: Rc<Cell<usize>>| {
+            let tab_index = index.clone();
+            lens.map_ref(move |list| &list[tab_index.get()])
+        };
+
+        let header_content = content.clone();
+        let build_header = move |cx: &mut Context, index: Rc<Cell<usize>>| {
+            let builder = (header_content)(cx, tab(index.clone())).header;
+            let close_index = index.clone();
+            TabHeader::new_shared(cx, index, move |cx| {
+                (builder)(cx);
+                let close_index = close_index.clone();
+                Button::new(cx, |cx| Svg::new(cx, ICON_X).size(Stretch(1.0)))
+                    .variant(ButtonVariant::Text)
+                    .class("tab-close")
+                    .display(TabView::closable)
+                    .on_press(move |cx| cx.emit(TabEvent::Close(close_index.get())));
+            })
+            .toggle_class("vertical", TabView::is_vertical);
+        };
+
+        let build_content = move |cx: &mut Context, index: Rc<Cell<usize>>| {
+            let content = content.clone();
+            TabContent { index: index.clone() }
+                .build(cx, move |cx| {
+                    let visited_index = index.clone();
+                    let visited = TabView::visited.map(move |visited| {
+                        visited.get(visited_index.get()).copied().unwrap_or_default()
+                    });
+                    Binding::new(cx, visited, move |cx, visited| {
+                        if visited.get(cx) {
+                            ((content)(cx, tab(index.clone())).content)(cx);
                         }
-                    })
-                    //})
-                    //.toggle_class("vertical", TabView::is_vertical)
-                    //.class("tabview-tabheader-wrapper");
-                })
-                .class("tabview-header")
-                .z_index(1)
-                .toggle_class("vertical", TabView::is_vertical);
-
-                Divider::new(cx).toggle_class("vertical", TabView::is_vertical);
-
-                // Tab content
-                VStack::new(cx, |cx| {
-                    Binding::new(cx, TabView::selected_index, move |cx, selected| {
-                        let selected = selected.get(cx);
-                        let l = lens.idx(selected);
-                        ((content)(cx, l).content)(cx);
                     });
                 })
-                .overflow(Overflow::Hidden)
-                .class("tabview-content-wrapper");
+                .class("tabview-content")
+                .display(Display::None);
+        };
+
+        Self {
+            selected_index: 0,
+            is_vertical: false,
+            closable: false,
+            visited: (0..tab_count).map(|index| index == 0).collect(),
+            tab_count,
+            reorderable: false,
+            drag: DragReorder::default(),
+            on_select: None,
+            on_close: None,
+            on_reorder: None,
+        }
+        .build(cx, move |cx| {
+            // Tab headers. The headers are siblings placed after the binding, so that the binding updates their
+            // indices before the bindings within the headers are updated.
+            ScrollView::new(cx, move |cx| {
+                let parent = cx.current();
+                let headers = RefCell::new(Vec::new());
+                Binding::new(cx, keys, move |cx, keys| {
+                    let keys = keys.get(cx);
+                    let moved = update_keyed_items(
+                        cx,
+                        parent,
+                        &mut headers.borrow_mut(),
+                        &keys,
+                        &build_header,
+                    );
+                    cx.emit(TabItemsEvent::Update { len: keys.len(), moved });
+                });
+            })
+            .class("tabview-header")
+            .z_index(1)
+            .toggle_class("vertical", TabView::is_vertical);
+
+            Divider::new(cx).toggle_class("vertical", TabView::is_vertical);
+
+            // Tab content
+            VStack::new(cx, move |cx| {
+                let parent = cx.current();
+                let contents = RefCell::new(Vec::new());
+                Binding::new(cx, keys, move |cx, keys| {
+                    let keys = keys.get(cx);
+                    update_keyed_items(
+                        cx,
+                        parent,
+                        &mut contents.borrow_mut(),
+                        &keys,
+                        &build_content,
+                    );
+                });
             })
-            .toggle_class("vertical", TabView::is_vertical)
+            .overflow(Overflow::Hidden)
+            .class("tabview-content-wrapper");
+        })
+        .toggle_class("vertical", TabView::is_vertical)
+        .toggle_class("closable", TabView::closable)
+        .toggle_class("reorderable", TabView::reorderable)
+    }
+
+    // Updates the number of tabs once the tabs have changed, keeping the selection and the built contents with the
+    // tabs as they move.
+    fn update_tabs(&mut self, cx: &mut EventContext, len: usize, moved: &[Option<usize>]) {
+        if self.drag.dragging().is_some() {
+            self.end_drag(cx, false);
+        }
+
+        let mut visited = vec![false; len];
+        for (previous, index) in moved.iter().enumerate() {
+            if let Some(index) = index {
+                visited[*index] = self.visited.get(previous).copied().unwrap_or_default();
+            }
+        }
+
+        self.tab_count = len;
+        self.visited = visited;
+
+        // If the selected tab was removed, the tab which took its place is selected.
+        let selected = moved
+            .get(self.selected_index)
+            .copied()
+            .flatten()
+            .unwrap_or(self.selected_index.min(len.saturating_sub(1)));
+        self.select(cx, selected);
+    }
+
+    // Selects the tab with the given index, marking its content to be built, showing it, and scrolling its header
+    // into view.
+    fn select(&mut self, cx: &mut EventContext, index: usize) {
+        self.selected_index = index;
+        if let Some(visited) = self.visited.get_mut(index) {
+            *visited = true;
+        }
+
+        for (entity, header) in Self::headers(cx).collect::<Vec<_>>() {
+            cx.with_current(entity, |cx| cx.set_checked(header == index));
+            if header == index {
+                cx.scroll_to(entity, true);
+            }
+        }
+
+        let contents = cx
+            .current()
+            .branch_iter(cx.tree)
+            .filter_map(|entity| {
+                let content = cx.views.get(&entity)?.downcast_ref::<TabContent>()?;
+                Some((entity, content.index.get()))
+            })
+            .collect::<Vec<_>>();
+        for (entity, content) in contents {
+            let display = if content == index { Display::Flex } else { Display::None };
+            cx.with_current(entity, |cx| cx.set_display(display));
+        }
+    }
+
+    // Returns the tab headers of the tabview along with their indices.
+    fn headers<'a>(cx: &'a EventContext) -> impl Iterator<Item = (Entity, usize)> + 'a {
+        cx.current().branch_iter(cx.tree).filter_map(|entity| {
+            let header = cx.views.get(&entity)?.downcast_ref::<TabHeader>()?;
+            Some((entity, header.index.get()))
+        })
+    }
+
+    // Moves the drop indicator to the tab header closest to the cursor.
+    fn update_drag(&mut self, cx: &mut EventContext) {
+        let headers = Self::headers(cx).collect::<Vec<_>>();
+        self.drag.update(cx, &headers, !self.is_vertical);
+    }
+
+    // Ends a drag, reordering the dragged tab if it was dropped on the tab bar.
+    fn end_drag(&mut self, cx: &mut EventContext, drop: bool) {
+        let Some((from, to)) = self.drag.end(cx, drop) else { return };
+
+        // Keep the same tab selected once it has been moved.
+        self.select(cx, moved_index(self.selected_index, from, to));
+
+        if let Some(on_reorder) = &self.on_reorder {
+            on_reorder(cx, from, to);
+        }
     }
 }
 
@@ -74,12 +278,61 @@ impl View for TabView {
     fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
         event.map(|tab_event, meta| match tab_event {
             TabEvent::SetSelected(index) => {
-                self.selected_index = *index;
+                self.select(cx, *index);
                 if let Some(callback) = &self.on_select {
                     (callback)(cx, self.selected_index);
                 }
                 meta.consume();
             }
+
+            // The selection follows the tabs once the closed tab has been removed from the data.
+            TabEvent::Close(index) => {
+                if self.closable {
+                    if let Some(callback) = &self.on_close {
+                        (callback)(cx, *index);
+                    }
+                }
+                meta.consume();
+            }
+        });
+
+        event.take(|items_event, _| match items_event {
+            TabItemsEvent::Update { len, moved } => self.update_tabs(cx, len, &moved),
+        });
+
+        event.take(|TabDragEvent(index), meta| {
+            if self.reorderable && self.drag.dragging().is_none() && !cx.is_disabled() {
+                self.drag.start(cx, meta.origin, index);
+                self.update_drag(cx);
+            }
+        });
+
+        event.map(|window_event, meta| match window_event {
+            WindowEvent::KeyDown(Code::Tab, _) if cx.modifiers().ctrl() => {
+                if self.tab_count > 0 {
+                    let index = if cx.modifiers().shift() {
+                        (self.selected_index + self.tab_count - 1) % self.tab_count
+                    } else {
+                        (self.selected_index + 1) % self.tab_count
+                    };
+                    cx.emit(TabEvent::SetSelected(index));
+                }
+                meta.consume();
+            }
+
+            WindowEvent::MouseMove(_, _) if self.drag.dragging().is_some() => self.update_drag(cx),
+
+            WindowEvent::MouseUp(MouseButton::Left) if self.drag.dragging().is_some() => {
+                self.end_drag(cx, true);
+                meta.consume();
+            }
+
+            WindowEvent::KeyDown(Code::Escape, _) if self.drag.dragging().is_some() => {
+                self.end_drag(cx, false);
+                meta.consume();
+            }
+
+            _ => {}
         });
     }
 }
@@ -102,6 +355,51 @@ impl Handle<'_, TabView> {
 
         self
     }
+
+    /// Sets whether the tabs can be closed, either with the close button of a tab header or by middle-clicking it.
+    ///
+    /// Closing a tab triggers the [`on_close`](Handle::on_close) callback, which should remove the tab from the
+    /// bound data.
+    pub fn closable<U: Into<bool>>(self, flag: impl Res<U>) -> Self {
+        self.bind(flag, |handle, closable| {
+            let closable = closable.get(&handle).into();
+            handle.modify(|tabview: &mut TabView| tabview.closable = closable);
+        })
+    }
+
+    /// Sets the callback triggered when a tab of a [closable](Handle::closable) tabview is closed.
+    ///
+    /// Callback provides the index of the closed tab.
+    pub fn on_close<F>(self, callback: F) -> Self
+    where
+        F: 'static + Fn(&mut EventContext, usize),
+    {
+        self.modify(|tabview: &mut TabView| tabview.on_close = Some(Box::new(callback)))
+    }
+
+    /// Sets whether the tabs can be reordered by dragging their headers.
+    ///
+    /// While a header is dragged, the header it would be dropped next to has the `drop-before` or `drop-after` class.
+    /// Dropping the header triggers the [`on_reorder`](Handle::on_reorder) callback, which should move the tab within
+    /// the bound data. Pressing the escape key cancels the drag.
+    pub fn reorderable<U: Into<bool>>(self, flag: impl Res<U>) -> Self {
+        self.bind(flag, |handle, reorderable| {
+            let reorderable = reorderable.get(&handle).into();
+            handle.modify(|tabview: &mut TabView| tabview.reorderable = reorderable);
+        })
+    }
+
+    /// Sets the callback triggered when the header of a [reorderable](Handle::reorderable) tabview is dropped at a
+    /// new position.
+    ///
+    /// Callback provides the index of the dragged tab and the index it should be moved to once it has been removed
+    /// from the data, so that a `Vec` can be updated with `let tab = vec.remove(from); vec.insert(to, tab);`.
+    pub fn on_reorder<F>(self, callback: F) -> Self
+    where
+        F: 'static + Fn(&mut EventContext, usize, usize),
+    {
+        self.modify(|tabview: &mut TabView| tabview.on_reorder = Some(Box::new(callback)))
+    }
 }
 
 pub struct TabPair {
@@ -120,7 +418,7 @@ impl TabPair {
 }
 
 pub struct TabHeader {
-    index: usize,
+    index: Rc<Cell<usize>>,
 }
 
 impl TabHeader {
@@ -128,7 +426,18 @@ impl TabHeader {
     where
         F: 'static + Fn(&mut Context),
     {
-        Self { index }.build(cx, |cx| (content)(cx))
+        Self::new_shared(cx, Rc::new(Cell::new(index)), content)
+    }
+
+    // Creates a header whose index is shared with the tabview, which updates it when the tab moves.
+    fn new_shared<F>(cx: &mut Context, index: Rc<Cell<usize>>, content: F) -> Handle<Self>
+    where
+        F: 'static + Fn(&mut Context),
+    {
+        let dragged = index.clone();
+        Self { index }
+            .build(cx, |cx| (content)(cx))
+            .on_drag(move |cx| cx.emit(TabDragEvent(dragged.get())))
     }
 }
 
@@ -140,7 +449,11 @@ impl View for TabHeader {
     fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
         event.map(|window_event, _meta| match window_event {
             WindowEvent::PressDown { mouse: _ } => {
-                cx.emit(TabEvent::SetSelected(self.index));
+                cx.emit(TabEvent::SetSelected(self.index.get()));
+            }
+
+            WindowEvent::MouseDown(MouseButton::Middle) => {
+                cx.emit(TabEvent::Close(self.index.get()));
             }
 
             _ => {}
@@ -148,6 +461,13 @@ impl View for TabHeader {
     }
 }
 
+// The content of a tab, which is only shown while the tab is selected.
+struct TabContent {
+    index: Rc<Cell<usize>>,
+}
+
+impl View for TabContent {}
+
 pub struct TabBar {}
 
 impl TabBar {
@@ -179,3 +499,71 @@ impl View for TabBar {
         Some("tabbar")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::backend::BackendContext;
+    use crate::context::test_support::{update, window_context};
+
+    #[derive(Lens)]
+    struct AppData {
+        tabs: Vec<&'static str>,
+    }
+
+    impl Model for AppData {
+        fn event(&mut self, _: &mut EventContext, event: &mut Event) {
+            event.map(|tabs: &Vec<&'static str>, _| self.tabs.clone_from(tabs));
+        }
+    }
+
+    fn tabview(cx: &BackendContext, entity: Entity) -> &TabView {
+        cx.0.views.get(&entity).and_then(|view| view.downcast_ref::<TabView>()).unwrap()
+    }
+
+    #[test]
+    fn tabs_keep_content_when_moved() {
+        let mut cx = window_context(400, 300);
+
+        AppData { tabs: vec!["a", "b", "c"] }.build(cx.context());
+
+        let tabs = TabView::new(cx.context(), AppData::tabs, |cx, item| {
+            let name = item.get(cx);
+            TabPair::new(
+                move |cx| {
+                    Label::new(cx, item);
+                },
+                move |cx| {
+                    Element::new(cx).class(name);
+                },
+            )
+        })
+        .entity();
+
+        update(&mut cx);
+
+        cx.0.emit_to(tabs, TabEvent::SetSelected(1));
+        update(&mut cx);
+
+        let (a, b) = (cx.0.query(".a"), cx.0.query(".b"));
+        assert_eq!((a.len(), b.len()), (1, 1));
+        assert!(cx.0.query(".c").is_empty());
+
+        // Moving the tabs keeps their content and the selected tab.
+        cx.0.emit_to(Entity::root(), vec!["c", "a", "b"]);
+        update(&mut cx);
+
+        assert_eq!((cx.0.query(".a"), cx.0.query(".b")), (a.clone(), b.clone()));
+        assert_eq!(tabview(&cx, tabs).selected_index, 2);
+        assert_eq!(tabview(&cx, tabs).visited, [false, true, true]);
+
+        // Removing a tab only removes its content.
+        cx.0.emit_to(Entity::root(), vec!["c", "b"]);
+        update(&mut cx);
+
+        assert!(cx.0.query(".a").is_empty());
+        assert_eq!(cx.0.query(".b"), b);
+        assert_eq!(tabview(&cx, tabs).selected_index, 1);
+        assert_eq!(tabview(&cx, tabs).tab_count, 2);
+    }
+}
@@ -0,0 +1,107 @@
+use crate::prelude::*;
+
+// Sent by the inner textbox of a text area when its text is edited.
+enum TextAreaEvent {
+    Edit(String),
+}
+
+/// A view for editing multiple lines of text, with vertical scrolling and optional line numbers.
+///
+/// The text area wraps a multi-line [Textbox] within a [ScrollView], so selections can span lines and the text can be
+/// scrolled when it is taller than the view.
+///
+/// # Example
+/// ```
+/// # use vizia_core::prelude::*;
+/// #
+/// # #[derive(Lens)]
+/// # struct AppData {
+/// #     text: String,
+/// # }
+/// #
+/// # impl Model for AppData {}
+/// #
+/// # let cx = &mut Context::default();
+/// #
+/// # AppData { text: String::from("Hello\nWorld") }.build(cx);
+/// #
+/// TextArea::new(cx, AppData::text, false)
+///     .line_numbers(true)
+///     .on_edit(|_cx, text| println!("{}", text));
+/// ```
+#[derive(Lens)]
+pub struct TextArea {
+    line_numbers: bool,
+    on_edit: Option<Box<dyn Fn(&mut EventContext, String)>>,
+}
+
+impl TextArea {
+    /// Creates a new [TextArea] view.
+    ///
+    /// The `wrap` parameter determines whether lines which are too long for the text area should soft-wrap. If
+    /// false, then the text area can be scrolled horizontally instead.
+    pub fn new<L>(cx: &mut Context, lens: L, wrap: bool) -> Handle<Self>
+    where
+        L: Lens<Target = String>,
+    {
+        Self { line_numbers: false, on_edit: None }
+            .build(cx, |cx| {
+                ScrollView::new(cx, move |cx| {
+                    Binding::new(cx, TextArea::line_numbers, move |cx, line_numbers| {
+                        if line_numbers.get(cx) {
+                            Label::new(cx, lens.map(|text| line_numbers_text(text)))
+                                .class("line-numbers")
+                                .hoverable(false);
+                        }
+                    });
+
+                    Textbox::new_multiline(cx, lens, wrap)
+                        .on_edit(|cx, text| cx.emit(TextAreaEvent::Edit(text)));
+                })
+                .show_horizontal_scrollbar(!wrap);
+            })
+            .toggle_class("wrap", wrap)
+    }
+}
+
+// Returns the line numbers for the lines of the given text, separated by line breaks.
+fn line_numbers_text(text: &str) -> String {
+    (1..=text.split('\n').count()).map(|line| line.to_string()).collect::<Vec<_>>().join("\n")
+}
+
+impl View for TextArea {
+    fn element(&self) -> Option<&'static str> {
+        Some("text-area")
+    }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.take(|text_area_event, _| match text_area_event {
+            TextAreaEvent::Edit(text) => {
+                if let Some(callback) = &self.on_edit {
+                    callback(cx, text);
+                }
+            }
+        });
+    }
+}
+
+impl Handle<'_, TextArea> {
+    /// Sets whether a gutter with line numbers is shown next to the text.
+    ///
+    /// Line numbers count the lines separated by line breaks, so they line up with the text when wrapping is
+    /// disabled.
+    pub fn line_numbers(self, line_numbers: impl Res<bool>) -> Self {
+        self.bind(line_numbers, |handle, line_numbers| {
+            let line_numbers = line_numbers.get(&handle);
+            handle.modify(|text_area: &mut TextArea| text_area.line_numbers = line_numbers);
+        })
+    }
+
+    /// Sets the callback triggered when the text is edited, which receives the current text.
+    pub fn on_edit<F>(self, callback: F) -> Self
+    where
+        F: 'static + Fn(&mut EventContext, String),
+    {
+        self.modify(|text_area: &mut TextArea| text_area.on_edit = Some(Box::new(callback)))
+    }
+}
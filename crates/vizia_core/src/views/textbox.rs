@@ -2,13 +2,14 @@
 use crate::prelude::*;
 
 use crate::text::{
-    apply_movement, offset_for_delete_backwards, Direction, EditableText, Movement, Selection,
-    VerticalMovement,
+    apply_movement, offset_for_delete_backwards, Direction, EditableText, InputMask, Movement,
+    Selection, VerticalMovement, WritingDirection,
 };
 // use crate::views::scrollview::SCROLL_SENSITIVITY;
 use accesskit::{ActionData, ActionRequest};
 use skia_safe::textlayout::{RectHeightStyle, RectWidthStyle};
 use skia_safe::{Paint, PaintStyle, Rect};
+use std::ops::Range;
 use unicode_segmentation::UnicodeSegmentation;
 
 /// Events for modifying a textbox.
@@ -23,6 +24,8 @@ pub enum TextEvent {
     MoveCursor(Movement, bool),
     /// Select all text.
     SelectAll,
+    /// Set the selection to span from an anchor to an active byte offset within the text.
+    SetSelection(usize, usize),
     /// Select the word at the current cursor position.
     SelectWord,
     /// Select the paragraph at the current cursor position.
@@ -51,8 +54,18 @@ pub enum TextEvent {
     Blur,
     /// Toggle the visibility of the text Caret.
     ToggleCaret,
+    /// Sent up the tree by a textbox when its selection changes, with the byte range of the new selection, which is
+    /// empty when the selection is a caret.
+    SelectionChanged(Range<usize>),
 }
 
+// Sent up the tree with the text of a textbox when it's edited, so that an enclosing `Autocomplete` can filter its
+// suggestions.
+pub(crate) struct TextEdited(pub(crate) String);
+
+// Sent to a textbox when its bound value changes, so that the new text is spell checked.
+struct BoundTextChanged;
+
 /// The `Textbox` view provides an input control for editing a value as a string.
 ///
 /// The textbox takes a lens to some value, which must be a type which can convert to and from a `String`,
@@ -70,12 +83,24 @@ pub struct Textbox<L: Lens> {
     on_submit: Option<Box<dyn Fn(&mut EventContext, L::Target, bool) + Send + Sync>>,
     on_blur: Option<Box<dyn Fn(&mut EventContext) + Send + Sync>>,
     on_cancel: Option<Box<dyn Fn(&mut EventContext) + Send + Sync>>,
+    on_selection_change: Option<Box<dyn Fn(&mut EventContext, Range<usize>) + Send + Sync>>,
+    spell_check: Option<Box<dyn Fn(&str) -> Vec<Range<usize>> + Send + Sync>>,
+    on_spelling_suggestions:
+        Option<Box<dyn Fn(&mut EventContext, String, Range<usize>) + Send + Sync>>,
+    #[cfg(feature = "clipboard")]
+    on_paste_image: Option<Box<dyn Fn(&mut EventContext, ClipboardImage) + Send + Sync>>,
+    misspelled: Vec<Range<usize>>,
+    spell_checked_text: String,
     validate: Option<Box<dyn Fn(&L::Target) -> bool>>,
+    mask: Option<InputMask>,
     placeholder: String,
     show_placeholder: bool,
     show_caret: bool,
     caret_timer: Timer,
     selection: Selection,
+    // The byte range of the text being composed with an input method, which is shown inline until it's committed.
+    preedit: Option<Range<usize>>,
+    history: UndoManager<(String, Selection)>,
 }
 
 // Determines whether the enter key submits the text or inserts a new line.
@@ -156,12 +181,22 @@ where
             on_submit: None,
             on_blur: None,
             on_cancel: None,
+            on_selection_change: None,
+            spell_check: None,
+            on_spelling_suggestions: None,
+            #[cfg(feature = "clipboard")]
+            on_paste_image: None,
+            misspelled: Vec::new(),
+            spell_checked_text: String::new(),
             validate: None,
+            mask: None,
             placeholder: String::from(""),
             show_placeholder: true,
             show_caret: true,
             caret_timer,
             selection: Selection::new(0, 0),
+            preedit: None,
+            history: UndoManager::default(),
         }
         .build(cx, move |cx| {
             cx.add_listener(move |textbox: &mut Self, cx, event| {
@@ -185,8 +220,23 @@ where
         .toggle_class("caret", Self::show_caret)
         .text(lens)
         .placeholder_shown(Self::show_placeholder)
-        .bind(lens, |handle, lens| {
-            let flag = lens.get(&handle).to_string_local(handle.cx).is_empty();
+        .bind(lens, |mut handle, lens| {
+            let text = lens.get(&handle).to_string_local(handle.cx);
+            let flag = text.is_empty();
+            let entity = handle.entity();
+            handle.context().emit_to(entity, BoundTextChanged);
+
+            // The bound value is unmasked, so it's formatted with the input mask, if any, for display.
+            let mask = handle
+                .cx
+                .views
+                .get(&entity)
+                .and_then(|view| view.downcast_ref::<Self>())
+                .and_then(|textbox| textbox.mask.clone());
+            if let Some(mask) = mask.filter(|_| !flag) {
+                handle = handle.text(mask.format(&text));
+            }
+
             handle.modify(|textbox| textbox.show_placeholder = flag).bind(
                 Self::placeholder,
                 move |handle, placeholder| {
@@ -205,15 +255,66 @@ where
                 text.clear();
                 self.show_placeholder = false;
             }
-            text.edit(self.selection.range(), txt);
-            self.selection = Selection::caret(self.selection.min() + txt.len());
+            if let Some(mask) = &self.mask {
+                let (masked, caret) = mask.edit(text, self.selection.range(), txt);
+                *text = masked;
+                self.selection = Selection::caret(caret);
+            } else {
+                text.edit(self.selection.range(), txt);
+                self.selection = Selection::caret(self.selection.min() + txt.len());
+            }
             self.show_placeholder = text.is_empty();
             cx.style.needs_text_update(cx.current);
         }
     }
 
+    // Replaces the text being composed with an input method, which is inserted in place of the selection, and places
+    // the caret at the end of the given cursor range within it.
+    fn set_preedit(
+        &mut self,
+        cx: &mut EventContext,
+        preedit: &str,
+        cursor: Option<(usize, usize)>,
+    ) {
+        self.clear_preedit(cx);
+        if preedit.is_empty() {
+            return;
+        }
+
+        if let Some(text) = cx.style.text.get_mut(cx.current) {
+            if self.show_placeholder {
+                text.clear();
+                self.show_placeholder = false;
+            }
+
+            let start = self.selection.min();
+            text.edit(self.selection.range(), preedit);
+            self.preedit = Some(start..start + preedit.len());
+            self.selection = Selection::caret(start + cursor.map_or(preedit.len(), |(_, end)| end));
+            cx.style.needs_text_update(cx.current);
+        }
+    }
+
+    // Removes the text being composed with an input method, leaving the caret where the text was.
+    fn clear_preedit(&mut self, cx: &mut EventContext) {
+        let Some(range) = self.preedit.take() else {
+            return;
+        };
+
+        if let Some(text) = cx.style.text.get_mut(cx.current) {
+            // The text may have been replaced by the bound data while composing.
+            if text.get(range.clone()).is_some() {
+                text.edit(range.clone(), "");
+                self.selection = Selection::caret(range.start);
+                cx.style.needs_text_update(cx.current);
+            }
+        }
+    }
+
     fn delete_text(&mut self, cx: &mut EventContext, movement: Movement) {
-        if self.selection.is_caret() {
+        if self.mask.is_some() {
+            self.delete_masked_text(cx, movement);
+        } else if self.selection.is_caret() {
             if movement == Movement::Grapheme(Direction::Upstream) {
                 if self.selection.active == 0 {
                     return;
@@ -230,7 +331,14 @@ where
                 }
             } else if let Some(text) = cx.style.text.get_mut(cx.current) {
                 if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
-                    let to_delete = apply_movement(movement, self.selection, text, paragraph, true);
+                    let to_delete = apply_movement(
+                        movement,
+                        self.selection,
+                        text,
+                        paragraph,
+                        WritingDirection::Natural,
+                        true,
+                    );
                     self.selection = to_delete;
                     let new_cursor_pos = self.selection.min();
                     text.edit(to_delete.range(), "");
@@ -256,6 +364,104 @@ where
         }
     }
 
+    // Deletes text while keeping the literals of the input mask in place.
+    fn delete_masked_text(&mut self, cx: &mut EventContext, movement: Movement) {
+        let Some(mask) = &self.mask else { return };
+        let Some(text) = cx.style.text.get_mut(cx.current) else { return };
+
+        let range = if !self.selection.is_caret() {
+            self.selection.range()
+        } else if movement == Movement::Grapheme(Direction::Upstream) {
+            offset_for_delete_backwards(&self.selection, text)..self.selection.active
+        } else if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
+            apply_movement(
+                movement,
+                self.selection,
+                text,
+                paragraph,
+                WritingDirection::Natural,
+                true,
+            )
+            .range()
+        } else {
+            return;
+        };
+
+        let direction = if range.end <= self.selection.min() {
+            Direction::Upstream
+        } else {
+            Direction::Downstream
+        };
+        let (masked, caret) = mask.delete(text, range, direction);
+        *text = masked;
+        self.selection = Selection::caret(caret);
+        cx.style.needs_text_update(cx.current);
+    }
+
+    // Returns the text with the literals of the input mask, if any, removed.
+    fn unmask_text(&self, text: String) -> String {
+        match &self.mask {
+            Some(mask) => mask.unmask(&text),
+            None => text,
+        }
+    }
+
+    // Notifies the textbox and the `on_edit` callback, which receives the unmasked text, that the text was edited.
+    fn edited(&self, cx: &mut EventContext, text: String) {
+        cx.emit(TextEdited(text.clone()));
+        if let Some(callback) = &self.on_edit {
+            (callback)(cx, self.unmask_text(text));
+        }
+    }
+
+    // Returns the text formatted with the input mask, if any.
+    fn mask_text(&self, text: String) -> String {
+        match &self.mask {
+            Some(mask) => mask.format(&text),
+            None => text,
+        }
+    }
+
+    // Sets the validity of the textbox from the unmasked value of the text and the validation closure, if any.
+    // A non-empty text which does not fill the input mask is invalid.
+    fn update_validity(&self, cx: &mut EventContext, text: &str) {
+        let complete =
+            self.mask.as_ref().map_or(true, |mask| text.is_empty() || mask.is_complete(text));
+
+        match self.unmask_text(text.to_owned()).parse::<L::Target>() {
+            Ok(value) if complete => {
+                if let Some(validate) = &self.validate {
+                    cx.set_valid(validate(&value));
+                } else {
+                    cx.set_valid(true);
+                }
+            }
+
+            _ => cx.set_valid(false),
+        }
+    }
+
+    // Records the current text and selection in the undo history before an edit.
+    fn record_history(&mut self, cx: &mut EventContext) {
+        let state = (self.clone_text(cx), self.selection);
+        self.history.record(&state);
+    }
+
+    // Replaces the text and selection with a state from the undo history.
+    fn restore_history(&mut self, cx: &mut EventContext, state: (String, Selection)) {
+        let (text, selection) = state;
+        self.show_placeholder = text.is_empty();
+        if self.show_placeholder {
+            cx.style.text.insert(cx.current, self.placeholder.clone());
+            self.selection = Selection::caret(0);
+        } else {
+            cx.style.text.insert(cx.current, text);
+            self.selection = selection;
+        }
+
+        cx.style.needs_text_update(cx.current);
+    }
+
     fn reset_text(&mut self, cx: &mut EventContext) {
         if let Some(text) = cx.style.text.get_mut(cx.current) {
             text.clear();
@@ -267,10 +473,23 @@ where
     }
 
     fn move_cursor(&mut self, cx: &mut EventContext, movement: Movement, selection: bool) {
+        // The left and right arrow keys move backwards through right-to-left text.
+        let writing_direction = match cx.style.direction.get(cx.current) {
+            Some(crate::style::Direction::Rtl) => WritingDirection::RightToLeft,
+            Some(crate::style::Direction::Ltr) => WritingDirection::LeftToRight,
+            None => WritingDirection::Natural,
+        };
+
         if let Some(text) = cx.style.text.get_mut(cx.current) {
             if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
-                let new_selection =
-                    apply_movement(movement, self.selection, text, paragraph, selection);
+                let new_selection = apply_movement(
+                    movement,
+                    self.selection,
+                    text,
+                    paragraph,
+                    writing_direction,
+                    selection,
+                );
                 self.selection = new_selection;
                 cx.needs_redraw();
             }
@@ -285,6 +504,21 @@ where
         }
     }
 
+    fn set_selection(&mut self, cx: &mut EventContext, anchor: usize, active: usize) {
+        if let Some(text) = cx.style.text.get(cx.current) {
+            let clamp = |mut offset: usize| {
+                offset = offset.min(text.len());
+                while !text.is_char_boundary(offset) {
+                    offset -= 1;
+                }
+                offset
+            };
+
+            self.selection = Selection::new(clamp(anchor), clamp(active));
+            cx.needs_redraw();
+        }
+    }
+
     fn select_word(&mut self, cx: &mut EventContext) {
         self.move_cursor(cx, Movement::Word(Direction::Upstream), false);
         self.move_cursor(cx, Movement::Word(Direction::Downstream), true);
@@ -379,6 +613,17 @@ where
         }
     }
 
+    /// Returns the byte offset into the text at the given window-global physical coordinates.
+    fn offset_at(&self, cx: &EventContext, x: f32, y: f32) -> Option<usize> {
+        let text = cx.style.text.get(cx.current)?;
+        let paragraph = cx.text_context.text_paragraphs.get(cx.current)?;
+        let gp =
+            paragraph.get_glyph_position_at_coordinate(self.coordinates_global_to_text(cx, x, y));
+        let pos = gp.position as usize;
+
+        Some(text.grapheme_indices(true).nth(pos).map_or(text.len(), |(offset, _)| offset))
+    }
+
     /// This function takes window-global physical coordinates.
     fn drag(&mut self, cx: &mut EventContext, x: f32, y: f32) {
         if let Some(text) = cx.style.text.get(cx.current) {
@@ -501,6 +746,104 @@ where
         }
     }
 
+    /// Returns the misspelled range, if any, containing the byte offset.
+    fn misspelled_at(&self, offset: usize) -> Option<Range<usize>> {
+        self.misspelled.iter().find(|range| range.start <= offset && offset < range.end).cloned()
+    }
+
+    // Spell checks the text if it has changed since it was last checked, underlining any misspelled words.
+    fn check_spelling(&mut self, cx: &mut EventContext) {
+        let Some(spell_check) = &self.spell_check else {
+            return;
+        };
+
+        let text = if self.show_placeholder {
+            ""
+        } else {
+            cx.style.text.get(cx.current).map(String::as_str).unwrap_or_default()
+        };
+
+        if text == self.spell_checked_text {
+            return;
+        }
+
+        self.misspelled = (spell_check)(text);
+        self.spell_checked_text = text.to_string();
+
+        cx.style.misspelled.insert(cx.current, self.misspelled.clone());
+        cx.style.needs_text_update(cx.current);
+    }
+
+    // Underlines the text being composed with an input method.
+    fn draw_preedit(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        let Some(range) = &self.preedit else {
+            return;
+        };
+
+        if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
+            if let Some(text) = cx.style.text.get(cx.current) {
+                if range.end > text.len() {
+                    return;
+                }
+
+                let bounds = cx.bounds();
+
+                let (mut top, _) = match cx.alignment() {
+                    Alignment::TopLeft => (0.0, 0.0),
+                    Alignment::TopCenter => (0.0, 0.5),
+                    Alignment::TopRight => (0.0, 1.0),
+                    Alignment::Left => (0.5, 0.0),
+                    Alignment::Center => (0.5, 0.5),
+                    Alignment::Right => (0.5, 1.0),
+                    Alignment::BottomLeft => (1.0, 0.0),
+                    Alignment::BottomCenter => (1.0, 0.5),
+                    Alignment::BottomRight => (1.0, 1.0),
+                };
+
+                let padding_top = match cx.padding_top() {
+                    Units::Pixels(val) => val,
+                    _ => 0.0,
+                };
+
+                let padding_bottom = match cx.padding_bottom() {
+                    Units::Pixels(val) => val,
+                    _ => 0.0,
+                };
+
+                let padding_left = match cx.padding_left() {
+                    Units::Pixels(val) => val,
+                    _ => 0.0,
+                };
+
+                top *= bounds.height() - padding_top - padding_bottom - paragraph.height();
+
+                let scale = cx.scale_factor();
+
+                let mut paint = Paint::default();
+                paint.set_anti_alias(true);
+                paint.set_style(PaintStyle::Fill);
+                paint.set_color(cx.font_color());
+
+                let min = text.current_grapheme_offset(range.start);
+                let max = text.current_grapheme_offset(range.end);
+
+                let rects = paragraph.get_rects_for_range(
+                    min..max,
+                    RectHeightStyle::Tight,
+                    RectWidthStyle::Tight,
+                );
+
+                for rect in rects {
+                    let x = bounds.x + padding_left + rect.rect.left;
+                    let x2 = bounds.x + padding_left + rect.rect.right;
+                    let y = (bounds.y + padding_top + rect.rect.bottom + top).round();
+
+                    canvas.draw_rect(Rect::new(x, y, x2, y + scale), &paint);
+                }
+            }
+        }
+    }
+
     /// Draw text caret for the current view.
     pub fn draw_text_caret(&self, cx: &mut DrawContext, canvas: &Canvas) {
         if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
@@ -554,6 +897,8 @@ where
                 let x2 = x + 1.0;
                 let y2 = y + (cursor_rect.rect.bottom - cursor_rect.rect.top);
 
+                cx.set_ime_cursor_area(BoundingBox::from_min_max(x, y, x2, y2));
+
                 let mut paint = Paint::default();
                 paint.set_anti_alias(true);
                 paint.set_style(PaintStyle::Fill);
@@ -568,7 +913,7 @@ where
 impl<L: Lens> Handle<'_, Textbox<L>> {
     /// Sets the callback triggered when a textbox is edited, i.e. text is inserted/deleted.
     ///
-    /// Callback provides the current text of the textbox.
+    /// Callback provides the current text of the textbox, without the literals of the input mask, if any.
     pub fn on_edit<F>(self, callback: F) -> Self
     where
         F: 'static + Fn(&mut EventContext, String) + Send + Sync,
@@ -603,6 +948,58 @@ impl<L: Lens> Handle<'_, Textbox<L>> {
         self.modify(|textbox: &mut Textbox<L>| textbox.on_cancel = Some(Box::new(callback)))
     }
 
+    /// Sets the callback triggered when the selection of a textbox changes.
+    ///
+    /// Callback provides the byte range of the current selection, which is empty when the selection is a caret. A
+    /// [`TextEvent::SelectionChanged`] event is also sent up the tree, so that models can react to the selection.
+    pub fn on_selection_change<F>(self, callback: F) -> Self
+    where
+        F: 'static + Fn(&mut EventContext, Range<usize>) + Send + Sync,
+    {
+        self.modify(|textbox: &mut Textbox<L>| {
+            textbox.on_selection_change = Some(Box::new(callback))
+        })
+    }
+
+    /// Sets a spell-checking closure which is called with the text of the textbox whenever it changes.
+    ///
+    /// The closure returns the byte ranges of any misspelled words, which are drawn with a wavy red underline text
+    /// decoration.
+    pub fn spell_check<F>(self, spell_check: F) -> Self
+    where
+        F: 'static + Fn(&str) -> Vec<Range<usize>> + Send + Sync,
+    {
+        self.modify(|textbox: &mut Textbox<L>| {
+            textbox.spell_checked_text.clear();
+            textbox.misspelled.clear();
+            textbox.spell_check = Some(Box::new(spell_check));
+        })
+    }
+
+    /// Sets the callback triggered when a misspelled word is right-clicked, which can be used to show a menu of suggestions.
+    ///
+    /// Callback provides the misspelled word and its byte range within the text of the textbox.
+    pub fn on_spelling_suggestions<F>(self, callback: F) -> Self
+    where
+        F: 'static + Fn(&mut EventContext, String, Range<usize>) + Send + Sync,
+    {
+        self.modify(|textbox: &mut Textbox<L>| {
+            textbox.on_spelling_suggestions = Some(Box::new(callback))
+        })
+    }
+
+    /// Sets the callback triggered when the clipboard contents are pasted into the textbox while the clipboard
+    /// contains an image rather than text.
+    ///
+    /// Callback provides the pasted image, which the textbox itself ignores.
+    #[cfg(feature = "clipboard")]
+    pub fn on_paste_image<F>(self, callback: F) -> Self
+    where
+        F: 'static + Fn(&mut EventContext, ClipboardImage) + Send + Sync,
+    {
+        self.modify(|textbox: &mut Textbox<L>| textbox.on_paste_image = Some(Box::new(callback)))
+    }
+
     /// Sets a validation closure which is called when the textbox is edited and sets the validity attribute to the output of the closure.
     ///
     /// If a textbox is modified with the validate modifier then the `on_submit` will not be called if the text is invalid.
@@ -613,6 +1010,44 @@ impl<L: Lens> Handle<'_, Textbox<L>> {
         self.modify(|textbox| textbox.validate = Some(Box::new(is_valid)))
     }
 
+    /// Sets an input mask which constrains the text of the textbox, such as `(###) ###-####` for a phone number.
+    ///
+    /// Literal characters of the mask are inserted automatically while typing and characters which are not accepted
+    /// by the mask are ignored. The text passed to the `on_edit` callback is unmasked, the value passed to the
+    /// `on_submit` callback and to the `validate` closure is parsed from the unmasked text, and a partially filled mask
+    /// is invalid. See [`InputMask`] for the syntax of the mask.
+    ///
+    /// # Example
+    /// ```ignore
+    /// Textbox::new(cx, AppData::phone)
+    ///     .input_mask("(###) ###-####")
+    ///     .on_submit(|cx, digits, _| cx.emit(AppEvent::SetPhone(digits)));
+    /// ```
+    pub fn input_mask(self, mask: impl Into<InputMask>) -> Self
+    where
+        L::Target: Data + ToStringLocalized,
+    {
+        let Some(lens) = self
+            .cx
+            .views
+            .get(&self.entity)
+            .and_then(|view| view.downcast_ref::<Textbox<L>>())
+            .map(|textbox| textbox.lens)
+        else {
+            return self;
+        };
+
+        // The binding of the lens formats later values with the mask, so only the current value is formatted here.
+        let mask = mask.into();
+        let text = lens.get(&self).to_string_local(self.cx);
+        let handle = self.modify(|textbox| textbox.mask = Some(mask.clone()));
+        if text.is_empty() {
+            handle
+        } else {
+            handle.text(mask.format(&text))
+        }
+    }
+
     /// Sets the placeholder text that appears when the textbox has no value.
     pub fn placeholder<P: ToStringLocalized>(self, text: impl Res<P>) -> Self {
         text.set_or_bind(self.cx, self.entity, move |cx, val| {
@@ -636,6 +1071,15 @@ where
     }
 
     fn accessibility(&self, cx: &mut AccessContext, node: &mut AccessNode) {
+        let read_only = cx
+            .style
+            .pseudo_classes
+            .get(cx.current)
+            .is_some_and(|pseudo_classes| pseudo_classes.contains(PseudoClassFlags::READ_ONLY));
+        if !read_only {
+            node.add_action(accesskit::Action::SetValue);
+        }
+
         let _bounds = cx.bounds();
 
         let node_id = node.node_id();
@@ -781,6 +1225,8 @@ where
     }
 
     fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        let previous_selection = (self.selection.anchor, self.selection.active);
+
         // Window Events
         event.map(|window_event, meta| match window_event {
             WindowEvent::MouseDown(MouseButton::Left) => {
@@ -820,6 +1266,21 @@ where
                 }
             }
 
+            WindowEvent::MouseDown(MouseButton::Right) => {
+                if cx.is_over() && !self.show_placeholder {
+                    if let Some(callback) = &self.on_spelling_suggestions {
+                        let offset = self.offset_at(cx, cx.mouse.cursor_x, cx.mouse.cursor_y);
+                        if let Some(range) = offset.and_then(|offset| self.misspelled_at(offset)) {
+                            let word = self.clone_text(cx).get(range.clone()).map(str::to_string);
+                            if let Some(word) = word {
+                                (callback)(cx, word, range);
+                                meta.consume();
+                            }
+                        }
+                    }
+                }
+            }
+
             WindowEvent::FocusIn => {
                 if cx.mouse.left.pressed != cx.current()
                     || cx.mouse.left.state == MouseButtonState::Released
@@ -863,6 +1324,24 @@ where
                 cx.emit(TextEvent::Scroll(*x, *y));
             }
 
+            WindowEvent::ImePreedit(preedit, cursor) => {
+                if self.edit && !cx.is_read_only() && self.mask.is_none() {
+                    self.reset_caret_timer(cx);
+                    self.set_preedit(cx, preedit, *cursor);
+                }
+            }
+
+            WindowEvent::ImeCommit(text) => {
+                if self.edit && !cx.is_read_only() {
+                    self.clear_preedit(cx);
+                    self.reset_caret_timer(cx);
+                    cx.emit(TextEvent::InsertText(text.clone()));
+                }
+            }
+
+            // Keys are handled by the input method while text is being composed.
+            WindowEvent::CharInput(_) | WindowEvent::KeyDown(_, _) if self.preedit.is_some() => {}
+
             WindowEvent::CharInput(c) => {
                 if *c != '\u{1b}' && // Escape
                     *c != '\u{8}' && // Backspace
@@ -1039,9 +1518,44 @@ where
                     }
                 }
 
+                Code::KeyZ => {
+                    #[cfg(target_os = "macos")]
+                    let modifier = Modifiers::SUPER;
+                    #[cfg(not(target_os = "macos"))]
+                    let modifier = Modifiers::CTRL;
+
+                    if cx.modifiers == &modifier {
+                        cx.emit(UndoEvent::Undo);
+                    } else if cx.modifiers == &(modifier | Modifiers::SHIFT) {
+                        cx.emit(UndoEvent::Redo);
+                    }
+                }
+
+                #[cfg(not(target_os = "macos"))]
+                Code::KeyY => {
+                    if cx.modifiers == &Modifiers::CTRL {
+                        cx.emit(UndoEvent::Redo);
+                    }
+                }
+
                 _ => {}
             },
 
+            WindowEvent::ActionRequest(ActionRequest {
+                action: accesskit::Action::SetValue,
+                target: _,
+                data: Some(ActionData::Value(value)),
+            }) => {
+                // Replaces the text as if it had been typed and submitted by the user.
+                if !cx.is_disabled() && !cx.is_read_only() {
+                    cx.emit(TextEvent::StartEdit);
+                    cx.emit(TextEvent::SelectAll);
+                    cx.emit(TextEvent::InsertText(value.to_string()));
+                    cx.emit(TextEvent::Submit(true));
+                    cx.emit(TextEvent::EndEdit);
+                }
+            }
+
             WindowEvent::ActionRequest(ActionRequest {
                 action: accesskit::Action::SetTextSelection,
                 target: _,
@@ -1102,6 +1616,8 @@ where
         // Textbox Events
         event.map(|text_event, _| match text_event {
             TextEvent::InsertText(text) => {
+                self.record_history(cx);
+
                 if self.show_placeholder {
                     self.reset_text(cx);
                 }
@@ -1110,20 +1626,10 @@ where
 
                 let text = self.clone_text(cx);
 
-                if let Ok(value) = &text.parse::<L::Target>() {
-                    if let Some(validate) = &self.validate {
-                        cx.set_valid(validate(value));
-                    } else {
-                        cx.set_valid(true);
-                    }
-                } else {
-                    cx.set_valid(false);
-                }
+                self.update_validity(cx, &text);
 
                 if self.edit {
-                    if let Some(callback) = &self.on_edit {
-                        (callback)(cx, text);
-                    }
+                    self.edited(cx, text);
                 }
             }
 
@@ -1136,23 +1642,14 @@ where
 
             TextEvent::DeleteText(movement) => {
                 if self.edit {
+                    self.record_history(cx);
                     self.delete_text(cx, *movement);
 
                     let text = self.clone_text(cx);
 
-                    if let Ok(value) = &text.parse::<L::Target>() {
-                        if let Some(validate) = &self.validate {
-                            cx.set_valid(validate(value));
-                        } else {
-                            cx.set_valid(true);
-                        }
-                    } else {
-                        cx.set_valid(false);
-                    }
+                    self.update_validity(cx, &text);
 
-                    if let Some(callback) = &self.on_edit {
-                        (callback)(cx, text);
-                    }
+                    self.edited(cx, text);
                 }
             }
 
@@ -1167,13 +1664,14 @@ where
             TextEvent::StartEdit => {
                 if !cx.is_disabled() && !self.edit {
                     self.edit = true;
+                    self.history.clear();
                     cx.focus_with_visibility(false);
                     cx.capture();
                     cx.set_checked(true);
                     self.reset_caret_timer(cx);
 
                     let text = self.lens.get(cx);
-                    let text = text.to_string_local(cx);
+                    let text = self.mask_text(text.to_string_local(cx));
 
                     if text.is_empty() {
                         self.show_placeholder = true;
@@ -1182,19 +1680,12 @@ where
                         self.select_all(cx);
                     }
 
-                    if let Ok(value) = &text.parse::<L::Target>() {
-                        if let Some(validate) = &self.validate {
-                            cx.set_valid(validate(value));
-                        } else {
-                            cx.set_valid(true);
-                        }
-                    } else {
-                        cx.set_valid(false);
-                    }
+                    self.update_validity(cx, &text);
                 }
             }
 
             TextEvent::EndEdit => {
+                self.preedit = None;
                 self.deselect();
                 self.edit = false;
                 cx.set_checked(false);
@@ -1202,19 +1693,11 @@ where
                 cx.stop_timer(self.caret_timer);
 
                 let text = self.lens.get(cx);
-                let text = text.to_string_local(cx);
+                let text = self.mask_text(text.to_string_local(cx));
 
                 self.select_all(cx);
 
-                if let Ok(value) = &text.parse::<L::Target>() {
-                    if let Some(validate) = &self.validate {
-                        cx.set_valid(validate(value));
-                    } else {
-                        cx.set_valid(true);
-                    }
-                } else {
-                    cx.set_valid(false);
-                }
+                self.update_validity(cx, &text);
                 self.show_placeholder = text.is_empty();
                 if self.show_placeholder {
                     cx.style.text.insert(cx.current, self.placeholder.clone());
@@ -1238,7 +1721,7 @@ where
             TextEvent::Submit(reason) => {
                 if let Some(callback) = &self.on_submit {
                     if cx.is_valid() {
-                        let text = self.clone_text(cx);
+                        let text = self.unmask_text(self.clone_text(cx));
                         if let Ok(value) = text.parse::<L::Target>() {
                             (callback)(cx, value, *reason);
                         }
@@ -1250,6 +1733,12 @@ where
                 self.select_all(cx);
             }
 
+            TextEvent::SetSelection(anchor, active) => {
+                if !self.show_placeholder {
+                    self.set_selection(cx, *anchor, *active);
+                }
+            }
+
             TextEvent::SelectWord => {
                 self.select_word(cx);
             }
@@ -1291,8 +1780,15 @@ where
             {
                 #[cfg(feature = "clipboard")]
                 if self.edit {
-                    if let Ok(text) = cx.get_clipboard() {
-                        cx.emit(TextEvent::InsertText(text));
+                    match cx.get_clipboard() {
+                        Ok(text) if !text.is_empty() => cx.emit(TextEvent::InsertText(text)),
+                        _ => {
+                            if let Some(callback) = &self.on_paste_image {
+                                if let Ok(image) = cx.get_clipboard_image() {
+                                    (callback)(cx, image);
+                                }
+                            }
+                        }
                     }
                 }
             }
@@ -1305,23 +1801,16 @@ where
                         if !selected_text.is_empty() {
                             cx.set_clipboard(selected_text)
                                 .expect("Failed to add text to clipboard");
+                            self.history.break_group();
+                            self.record_history(cx);
+                            self.history.break_group();
                             self.delete_text(cx, Movement::Grapheme(Direction::Upstream));
 
                             let text = self.clone_text(cx);
 
-                            if let Ok(value) = &text.parse::<L::Target>() {
-                                if let Some(validate) = &self.validate {
-                                    cx.set_valid(validate(value));
-                                } else {
-                                    cx.set_valid(true);
-                                }
-                            } else {
-                                cx.set_valid(false);
-                            }
+                            self.update_validity(cx, &text);
 
-                            if let Some(callback) = &self.on_edit {
-                                (callback)(cx, text);
-                            }
+                            self.edited(cx, text);
                         }
                     }
                 }
@@ -1330,7 +1819,43 @@ where
             TextEvent::ToggleCaret => {
                 self.show_caret ^= true;
             }
+
+            TextEvent::SelectionChanged(_) => {}
+        });
+
+        event.map(|undo_event, meta| {
+            if !self.edit || cx.is_read_only() {
+                return;
+            }
+
+            let current = (self.clone_text(cx), self.selection);
+            let state = match undo_event {
+                UndoEvent::Undo => self.history.undo(&current),
+                UndoEvent::Redo => self.history.redo(&current),
+            };
+
+            if let Some(state) = state {
+                self.restore_history(cx, state);
+
+                let text = self.clone_text(cx);
+
+                self.update_validity(cx, &text);
+
+                self.edited(cx, text);
+            }
+
+            meta.consume();
         });
+
+        if previous_selection != (self.selection.anchor, self.selection.active) {
+            cx.emit(TextEvent::SelectionChanged(self.selection.range()));
+
+            if let Some(callback) = &self.on_selection_change {
+                (callback)(cx, self.selection.range());
+            }
+        }
+
+        self.check_spelling(cx);
     }
 
     // Use custom drawing for the textbox so a transform can be applied to just the text.
@@ -1343,6 +1868,7 @@ where
         // canvas.translate(self.transform.0, self.transform.1);
         // cx.draw_text_and_selection(canvas);
         cx.draw_text(canvas);
+        self.draw_preedit(cx, canvas);
         if self.edit {
             self.draw_selection(cx, canvas);
             self.draw_text_caret(cx, canvas);
@@ -1350,3 +1876,255 @@ where
         // canvas.restore();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::test_support::{update, window_context};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Lens)]
+    struct AppData {
+        text: String,
+        #[lens(ignore)]
+        selections: Vec<Range<usize>>,
+        #[lens(ignore)]
+        edits: Vec<String>,
+    }
+
+    impl Model for AppData {
+        fn event(&mut self, _: &mut EventContext, event: &mut Event) {
+            event.map(|text_event, _| {
+                if let TextEvent::SelectionChanged(range) = text_event {
+                    self.selections.push(range.clone());
+                }
+            });
+
+            event.map(|text_edited: &TextEdited, _| self.edits.push(text_edited.0.clone()));
+
+            event.map(|text: &String, _| self.text.clone_from(text));
+        }
+    }
+
+    #[test]
+    fn select_all() {
+        let mut cx = window_context(400, 300);
+
+        AppData { text: String::from("hello world"), selections: Vec::new(), edits: Vec::new() }
+            .build(cx.context());
+
+        let callback_selections = Arc::new(Mutex::new(Vec::new()));
+        let selections = callback_selections.clone();
+        let textbox = Textbox::new(cx.context(), AppData::text)
+            .on_selection_change(move |_, range| selections.lock().unwrap().push(range))
+            .entity();
+
+        update(&mut cx);
+
+        cx.0.emit_to(textbox, TextEvent::SelectAll);
+        update(&mut cx);
+
+        // Selecting the same range again doesn't send another event.
+        cx.0.emit_to(textbox, TextEvent::SetSelection(0, 11));
+        update(&mut cx);
+
+        cx.0.emit_to(textbox, TextEvent::SetSelection(6, 6));
+        update(&mut cx);
+
+        cx.set_current(Entity::root());
+        assert_eq!(cx.0.data::<AppData>().unwrap().selections, [0..11, 6..6]);
+        assert_eq!(*callback_selections.lock().unwrap(), [0..11, 6..6]);
+    }
+
+    #[test]
+    fn caret_blink() {
+        let mut cx = window_context(400, 300);
+
+        AppData { text: String::from("hello"), selections: Vec::new(), edits: Vec::new() }
+            .build(cx.context());
+        let textbox = Textbox::new(cx.context(), AppData::text).entity();
+
+        let rate = Duration::from_millis(50);
+        cx.0.set_caret_blink_rate(rate);
+
+        update(&mut cx);
+
+        cx.0.emit_to(textbox, TextEvent::StartEdit);
+        update(&mut cx);
+
+        // The caret timer runs at the new rate once editing starts, with the caret initially shown.
+        cx.set_current(Entity::root());
+        let caret_timer = cx.0.environment().caret_timer;
+        assert!(cx
+            .0
+            .running_timers
+            .iter()
+            .any(|timer| timer.id == caret_timer && timer.interval == rate));
+        assert_eq!(cx.0.query(".caret"), [textbox]);
+
+        // The timer starts immediately and toggles the caret once the interval has elapsed.
+        let start = Instant::now();
+        cx.0.tick_timers_at(start);
+        update(&mut cx);
+        assert_eq!(cx.0.query(".caret"), [textbox]);
+
+        cx.0.tick_timers_at(start + rate + rate / 5);
+        update(&mut cx);
+        assert!(cx.0.query(".caret").is_empty());
+    }
+
+    #[test]
+    fn misspelled_ranges() {
+        let mut cx = window_context(400, 300);
+
+        AppData { text: String::from("hello wrold"), selections: Vec::new(), edits: Vec::new() }
+            .build(cx.context());
+
+        let checks = Arc::new(Mutex::new(0));
+        let spell_checks = checks.clone();
+        let textbox = Textbox::new(cx.context(), AppData::text)
+            .spell_check(move |text| {
+                *spell_checks.lock().unwrap() += 1;
+                text.match_indices("wrold").map(|(start, word)| start..start + word.len()).collect()
+            })
+            .entity();
+
+        update(&mut cx);
+
+        assert_eq!(cx.0.style.misspelled.get(textbox), Some(&vec![6..11]));
+        assert!(cx.0.text_context.text_paragraphs.get(textbox).is_some());
+        assert_eq!(*checks.lock().unwrap(), 1);
+
+        // The text isn't checked again until it changes.
+        cx.render_to_image(400, 300).unwrap();
+        update(&mut cx);
+        assert_eq!(*checks.lock().unwrap(), 1);
+
+        cx.0.emit_to(Entity::root(), String::from("wrold, hello wrold"));
+        update(&mut cx);
+
+        assert_eq!(cx.0.style.misspelled.get(textbox), Some(&vec![0..5, 13..18]));
+        assert_eq!(*checks.lock().unwrap(), 2);
+    }
+
+    #[test]
+    fn misspelled_ranges_follow_bound_value() {
+        let mut cx = window_context(400, 300);
+
+        AppData { text: String::from("hello wrold"), selections: Vec::new(), edits: Vec::new() }
+            .build(cx.context());
+
+        let textbox = Textbox::new(cx.context(), AppData::text)
+            .spell_check(|text| {
+                text.match_indices("wrold").map(|(start, word)| start..start + word.len()).collect()
+            })
+            .entity();
+
+        update(&mut cx);
+        assert_eq!(cx.0.style.misspelled.get(textbox), Some(&vec![6..11]));
+
+        // Changing the bound value without interacting with the textbox rechecks the new text.
+        cx.0.emit_to(Entity::root(), String::from("hello world"));
+        update(&mut cx);
+        assert_eq!(cx.0.style.misspelled.get(textbox), Some(&vec![]));
+
+        cx.0.emit_to(Entity::root(), String::from("wrold"));
+        update(&mut cx);
+        assert_eq!(cx.0.style.misspelled.get(textbox), Some(&vec![0..5]));
+    }
+
+    #[test]
+    #[cfg(feature = "clipboard")]
+    fn cut_emits_edit() {
+        let mut cx = window_context(400, 300);
+
+        AppData { text: String::from("hello world"), selections: Vec::new(), edits: Vec::new() }
+            .build(cx.context());
+
+        let callback_edits = Arc::new(Mutex::new(Vec::new()));
+        let edits = callback_edits.clone();
+        let textbox = Textbox::new(cx.context(), AppData::text)
+            .on_edit(move |_, text| edits.lock().unwrap().push(text))
+            .entity();
+
+        update(&mut cx);
+
+        cx.0.emit_to(textbox, TextEvent::StartEdit);
+        cx.0.emit_to(textbox, TextEvent::SetSelection(5, 11));
+        cx.0.emit_to(textbox, TextEvent::Cut);
+        update(&mut cx);
+
+        assert_eq!(cx.0.clipboard.get_text().unwrap(), " world");
+
+        cx.set_current(Entity::root());
+        assert_eq!(cx.0.data::<AppData>().unwrap().edits, ["hello"]);
+        assert_eq!(*callback_edits.lock().unwrap(), ["hello"]);
+    }
+
+    #[test]
+    fn undo_redo_emit_edit() {
+        let mut cx = window_context(400, 300);
+
+        AppData { text: String::from("hello"), selections: Vec::new(), edits: Vec::new() }
+            .build(cx.context());
+
+        let callback_edits = Arc::new(Mutex::new(Vec::new()));
+        let edits = callback_edits.clone();
+        let textbox = Textbox::new(cx.context(), AppData::text)
+            .on_edit(move |_, text| edits.lock().unwrap().push(text))
+            .entity();
+
+        update(&mut cx);
+
+        cx.0.emit_to(textbox, TextEvent::StartEdit);
+        cx.0.emit_to(textbox, TextEvent::SetSelection(5, 5));
+        cx.0.emit_to(textbox, TextEvent::InsertText(String::from(" world")));
+        update(&mut cx);
+
+        cx.0.emit_to(textbox, UndoEvent::Undo);
+        update(&mut cx);
+
+        cx.0.emit_to(textbox, UndoEvent::Redo);
+        update(&mut cx);
+
+        let expected = ["hello world", "hello", "hello world"];
+        cx.set_current(Entity::root());
+        assert_eq!(cx.0.data::<AppData>().unwrap().edits, expected);
+        assert_eq!(*callback_edits.lock().unwrap(), expected);
+    }
+
+    #[test]
+    fn input_mask_callbacks_receive_unmasked_text() {
+        let mut cx = window_context(400, 300);
+
+        AppData { text: String::from("555"), selections: Vec::new(), edits: Vec::new() }
+            .build(cx.context());
+
+        let callback_edits = Arc::new(Mutex::new(Vec::new()));
+        let submits = Arc::new(Mutex::new(Vec::new()));
+        let (edits, submitted) = (callback_edits.clone(), submits.clone());
+        let textbox = Textbox::new(cx.context(), AppData::text)
+            .input_mask("(###) ###")
+            .on_edit(move |_, text| edits.lock().unwrap().push(text))
+            .on_submit(move |_, text, _| submitted.lock().unwrap().push(text))
+            .entity();
+
+        update(&mut cx);
+        assert_eq!(cx.0.style.text.get(textbox).map(String::as_str), Some("(555"));
+
+        cx.0.emit_to(textbox, TextEvent::StartEdit);
+        cx.0.emit_to(textbox, TextEvent::SetSelection(4, 4));
+        cx.0.emit_to(textbox, TextEvent::InsertText(String::from("123")));
+        cx.0.emit_to(textbox, TextEvent::Submit(true));
+        update(&mut cx);
+
+        assert_eq!(cx.0.style.text.get(textbox).map(String::as_str), Some("(555) 123"));
+        assert_eq!(*callback_edits.lock().unwrap(), ["555123"]);
+        assert_eq!(*submits.lock().unwrap(), ["555123"]);
+
+        // A new bound value is formatted with the mask by the existing binding.
+        cx.0.emit_to(Entity::root(), String::from("123456"));
+        update(&mut cx);
+        assert_eq!(cx.0.style.text.get(textbox).map(String::as_str), Some("(123) 456"));
+    }
+}
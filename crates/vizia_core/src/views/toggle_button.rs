@@ -39,7 +39,8 @@ impl View for ToggleButton {
 
             WindowEvent::Press { mouse } => {
                 let over = if *mouse { cx.mouse().left.pressed } else { cx.focused() };
-                if over == cx.current() && meta.target == cx.current() && !cx.is_disabled() {
+                if cx.is_press_over(meta, over) && meta.target == cx.current() && !cx.is_disabled()
+                {
                     if let Some(callback) = &self.on_toggle {
                         (callback)(cx);
                     }
@@ -50,16 +51,6 @@ impl View for ToggleButton {
                 cx.release();
             }
 
-            WindowEvent::ActionRequest(action) => match action.action {
-                Action::Click => {
-                    if let Some(callback) = &self.on_toggle {
-                        (callback)(cx);
-                    }
-                }
-
-                _ => {}
-            },
-
             _ => {}
         });
     }
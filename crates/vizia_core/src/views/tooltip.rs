@@ -1,6 +1,6 @@
+use super::anchor::{position_anchored, Arrow};
 use crate::context::TreeProps;
 use crate::prelude::*;
-use crate::vg;
 
 /// A tooltip view.
 ///
@@ -27,6 +27,8 @@ use crate::vg;
 pub struct Tooltip {
     placement: Placement,
     shift: Placement,
+    // The offset of the arrow along the edge of the tooltip, in logical pixels.
+    arrow_offset: f32,
     show_arrow: bool,
     arrow_size: Length,
 }
@@ -57,13 +59,14 @@ impl Tooltip {
         Self {
             placement: Placement::Top,
             shift: Placement::Top,
+            arrow_offset: 0.0,
             show_arrow: true,
             arrow_size: Length::Value(LengthValue::Px(8.0)),
         }
         .build(cx, |cx| {
             Binding::new(cx, Tooltip::show_arrow, |cx, show_arrow| {
                 if show_arrow.get(cx) {
-                    Arrow::new(cx);
+                    Arrow::new(cx, Tooltip::shift, Tooltip::arrow_offset, Tooltip::arrow_size);
                 }
             });
             (content)(cx);
@@ -103,188 +106,30 @@ impl View for Tooltip {
 
     fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
         event.map(|window_event, _| match window_event {
-            // Reposition popup if there isn't enough room for it.
+            // Reposition tooltip if there isn't enough room for it.
             WindowEvent::GeometryChanged(_) => {
                 let parent = cx.parent();
                 let parent_bounds = cx.cache.get_bounds(parent);
                 let bounds = cx.bounds();
                 let window_bounds = cx.cache.get_bounds(cx.parent_window());
-
-                let arrow_size = self.arrow_size.to_px().unwrap() * cx.scale_factor();
-
-                let mut available = AvailablePlacement::all();
-
-                let top_start_bounds = BoundingBox::from_min_max(
-                    parent_bounds.left(),
-                    parent_bounds.top() - bounds.height() - arrow_size,
-                    parent_bounds.left() + bounds.width(),
-                    parent_bounds.top(),
-                );
-
-                available
-                    .set(AvailablePlacement::TOP_START, window_bounds.contains(&top_start_bounds));
-
-                let top_bounds = BoundingBox::from_min_max(
-                    parent_bounds.center().0 - bounds.width() / 2.0,
-                    parent_bounds.top() - bounds.height() - arrow_size,
-                    parent_bounds.center().0 + bounds.width() / 2.0,
-                    parent_bounds.top(),
-                );
-
-                available.set(AvailablePlacement::TOP, window_bounds.contains(&top_bounds));
-
-                let top_end_bounds = BoundingBox::from_min_max(
-                    parent_bounds.right() - bounds.width(),
-                    parent_bounds.top() - bounds.height() - arrow_size,
-                    parent_bounds.right(),
-                    parent_bounds.top(),
-                );
-
-                available.set(AvailablePlacement::TOP_END, window_bounds.contains(&top_end_bounds));
-
-                let bottom_start_bounds = BoundingBox::from_min_max(
-                    parent_bounds.left(),
-                    parent_bounds.bottom(),
-                    parent_bounds.left() + bounds.width(),
-                    parent_bounds.bottom() + bounds.height() + arrow_size,
-                );
-
-                available.set(
-                    AvailablePlacement::BOTTOM_START,
-                    window_bounds.contains(&bottom_start_bounds),
-                );
-
-                let bottom_bounds = BoundingBox::from_min_max(
-                    parent_bounds.center().0 - bounds.width() / 2.0,
-                    parent_bounds.bottom(),
-                    parent_bounds.center().0 + bounds.width() / 2.0,
-                    parent_bounds.bottom() + bounds.height() + arrow_size,
-                );
-
-                available.set(AvailablePlacement::BOTTOM, window_bounds.contains(&bottom_bounds));
-
-                let bottom_end_bounds = BoundingBox::from_min_max(
-                    parent_bounds.right() - bounds.width(),
-                    parent_bounds.bottom(),
-                    parent_bounds.right(),
-                    parent_bounds.bottom() + bounds.height() + arrow_size,
-                );
-
-                available.set(
-                    AvailablePlacement::BOTTOM_END,
-                    window_bounds.contains(&bottom_end_bounds),
-                );
-
-                let left_start_bounds = BoundingBox::from_min_max(
-                    parent_bounds.left() - bounds.width() - arrow_size,
-                    parent_bounds.top(),
-                    parent_bounds.left(),
-                    parent_bounds.top() + bounds.height(),
-                );
-
-                available.set(
-                    AvailablePlacement::LEFT_START,
-                    window_bounds.contains(&left_start_bounds),
-                );
-
-                let left_bounds = BoundingBox::from_min_max(
-                    parent_bounds.left() - bounds.width() - arrow_size,
-                    parent_bounds.center().1 - bounds.height() / 2.0,
-                    parent_bounds.left(),
-                    parent_bounds.center().1 + bounds.height() / 2.0,
-                );
-
-                available.set(AvailablePlacement::LEFT, window_bounds.contains(&left_bounds));
-
-                let left_end_bounds = BoundingBox::from_min_max(
-                    parent_bounds.left() - bounds.width() - arrow_size,
-                    parent_bounds.bottom() - bounds.height(),
-                    parent_bounds.left(),
-                    parent_bounds.bottom(),
-                );
-
-                available
-                    .set(AvailablePlacement::LEFT_END, window_bounds.contains(&left_end_bounds));
-
-                let right_start_bounds = BoundingBox::from_min_max(
-                    parent_bounds.right(),
-                    parent_bounds.top(),
-                    parent_bounds.right() + bounds.width() + arrow_size,
-                    parent_bounds.top() + bounds.height(),
-                );
-
-                available.set(
-                    AvailablePlacement::RIGHT_START,
-                    window_bounds.contains(&right_start_bounds),
-                );
-
-                let right_bounds = BoundingBox::from_min_max(
-                    parent_bounds.right(),
-                    parent_bounds.center().1 - bounds.height() / 2.0,
-                    parent_bounds.right() + bounds.width() + arrow_size,
-                    parent_bounds.center().1 + bounds.height() / 2.0,
-                );
-
-                available.set(AvailablePlacement::RIGHT, window_bounds.contains(&right_bounds));
-
-                let right_end_bounds = BoundingBox::from_min_max(
-                    parent_bounds.right(),
-                    parent_bounds.bottom() - bounds.height(),
-                    parent_bounds.right() + bounds.width() + arrow_size,
-                    parent_bounds.bottom(),
-                );
-
-                available
-                    .set(AvailablePlacement::RIGHT_END, window_bounds.contains(&right_end_bounds));
-
                 let scale = cx.scale_factor();
+                let arrow_size = self.arrow_size.to_px().unwrap() * scale;
 
-                self.shift = self.placement.place(available);
-
-                let arrow_size = self.arrow_size.to_px().unwrap();
-
-                let translate = match self.shift {
-                    Placement::Top => (
-                        -(bounds.width() - parent_bounds.width()) / (2.0 * scale),
-                        -bounds.height() / scale - arrow_size,
-                    ),
-                    Placement::TopStart => (0.0, -bounds.height() / scale - arrow_size),
-                    Placement::TopEnd => (
-                        -(bounds.width() - parent_bounds.width()) / scale,
-                        -bounds.height() / scale - arrow_size,
-                    ),
-                    Placement::Bottom => (
-                        -(bounds.width() - parent_bounds.width()) / (2.0 * scale),
-                        parent_bounds.height() / scale + arrow_size,
-                    ),
-                    Placement::BottomStart => (0.0, parent_bounds.height() / scale + arrow_size),
-                    Placement::BottomEnd => (
-                        -(bounds.width() - parent_bounds.width()) / scale,
-                        parent_bounds.height() / scale + arrow_size,
-                    ),
-                    Placement::LeftStart => (-(bounds.width() / scale) - arrow_size, 0.0),
-                    Placement::Left => (
-                        -(bounds.width() / scale) - arrow_size,
-                        -(bounds.height() - parent_bounds.height()) / (2.0 * scale),
-                    ),
-                    Placement::LeftEnd => (
-                        -(bounds.width() / scale) - arrow_size,
-                        -(bounds.height() - parent_bounds.height()) / scale,
-                    ),
-                    Placement::RightStart => ((parent_bounds.width() / scale) + arrow_size, 0.0),
-                    Placement::Right => (
-                        (parent_bounds.width() / scale) + arrow_size,
-                        -(bounds.height() - parent_bounds.height()) / (2.0 * scale),
-                    ),
-                    Placement::RightEnd => (
-                        (parent_bounds.width() / scale) + arrow_size,
-                        -(bounds.height() - parent_bounds.height()) / scale,
-                    ),
+                let position = position_anchored(
+                    self.placement,
+                    parent_bounds,
+                    bounds.width(),
+                    bounds.height(),
+                    window_bounds,
+                    arrow_size,
+                    true,
+                );
 
-                    _ => (0.0, 0.0),
-                };
+                self.shift = position.placement;
+                self.arrow_offset = (position.arrow_offset - arrow_size) / scale;
 
-                cx.set_translate((Pixels(translate.0.round()), Pixels(translate.1.round())));
+                let (x, y) = position.offset;
+                cx.set_translate((Pixels((x / scale).round()), Pixels((y / scale).round())));
             }
 
             _ => {}
@@ -321,106 +166,3 @@ impl Handle<'_, Tooltip> {
         })
     }
 }
-
-/// An arrow view used by the Tooltip view.
-pub(crate) struct Arrow {}
-
-impl Arrow {
-    pub(crate) fn new(cx: &mut Context) -> Handle<Self> {
-        Self {}.build(cx, |_| {}).bind(Tooltip::shift, |mut handle, placement| {
-            let (t, b) = match placement.get(&handle) {
-                Placement::TopStart | Placement::Top | Placement::TopEnd => {
-                    (Percentage(100.0), Stretch(1.0))
-                }
-                Placement::BottomStart | Placement::Bottom | Placement::BottomEnd => {
-                    (Stretch(1.0), Percentage(100.0))
-                }
-                _ => (Stretch(1.0), Stretch(1.0)),
-            };
-
-            let (l, r) = match placement.get(&handle) {
-                Placement::LeftStart | Placement::Left | Placement::LeftEnd => {
-                    (Percentage(100.0), Stretch(1.0))
-                }
-                Placement::RightStart | Placement::Right | Placement::RightEnd => {
-                    (Stretch(1.0), Percentage(100.0))
-                }
-                Placement::TopStart | Placement::BottomStart => {
-                    // TODO: Use border radius
-                    (Pixels(8.0), Stretch(1.0))
-                }
-                Placement::TopEnd | Placement::BottomEnd => {
-                    // TODO: Use border radius
-                    (Stretch(1.0), Pixels(8.0))
-                }
-                _ => (Stretch(1.0), Stretch(1.0)),
-            };
-
-            handle = handle.top(t).bottom(b).left(l).right(r).position_type(PositionType::Absolute);
-
-            handle.bind(Tooltip::arrow_size, move |handle, arrow_size| {
-                let arrow_size = arrow_size.get(&handle).to_px().unwrap_or(8.0);
-                let (w, h) = match placement.get(&handle) {
-                    Placement::Top
-                    | Placement::Bottom
-                    | Placement::TopStart
-                    | Placement::BottomStart
-                    | Placement::TopEnd
-                    | Placement::BottomEnd => (Pixels(arrow_size * 2.0), Pixels(arrow_size)),
-
-                    _ => (Pixels(arrow_size), Pixels(arrow_size * 2.0)),
-                };
-
-                handle.width(w).height(h);
-            });
-        })
-    }
-}
-
-impl View for Arrow {
-    fn element(&self) -> Option<&'static str> {
-        Some("arrow")
-    }
-    fn draw(&self, cx: &mut DrawContext, canvas: &Canvas) {
-        let bounds = cx.bounds();
-        let mut path = vg::Path::new();
-        match Tooltip::shift.get(cx) {
-            Placement::Bottom | Placement::BottomStart | Placement::BottomEnd => {
-                path.move_to(bounds.bottom_left());
-                path.line_to(bounds.center_top());
-                path.line_to(bounds.bottom_right());
-                path.line_to(bounds.bottom_left());
-            }
-
-            Placement::Top | Placement::TopStart | Placement::TopEnd => {
-                path.move_to(bounds.top_left());
-                path.line_to(bounds.center_bottom());
-                path.line_to(bounds.top_right());
-                path.line_to(bounds.top_left());
-            }
-
-            Placement::Left | Placement::LeftStart | Placement::LeftEnd => {
-                path.move_to(bounds.top_left());
-                path.line_to(bounds.center_right());
-                path.line_to(bounds.bottom_left());
-                path.line_to(bounds.top_left());
-            }
-
-            Placement::Right | Placement::RightStart | Placement::RightEnd => {
-                path.move_to(bounds.top_right());
-                path.line_to(bounds.center_left());
-                path.line_to(bounds.bottom_right());
-                path.line_to(bounds.top_right());
-            }
-
-            _ => {}
-        }
-        path.close();
-
-        let bg = cx.background_color();
-
-        let mut paint = vg::Paint::default();
-        paint.set_color(bg);
-        canvas.draw_path(&path, &paint);
-    }
-}
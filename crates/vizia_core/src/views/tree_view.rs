@@ -0,0 +1,516 @@
+use std::{collections::BTreeSet, ops::Deref, rc::Rc};
+
+use crate::icons::ICON_CHEVRON_RIGHT;
+use crate::prelude::*;
+
+/// Events used by the [TreeView] view.
+///
+/// Nodes are identified by their path, which contains the index of the node within the roots followed by the index
+/// of each descendant within the children of its parent.
+pub enum TreeViewEvent {
+    /// Selects the node with the given path.
+    Select(Vec<usize>),
+    /// Selects the focused node.
+    SelectFocused,
+    /// Moves the focus to the next visible node.
+    FocusNext,
+    /// Moves the focus to the previous visible node.
+    FocusPrev,
+    /// Expands the node with the given path, loading its children if they haven't been loaded.
+    Expand(Vec<usize>),
+    /// Collapses the node with the given path.
+    Collapse(Vec<usize>),
+    /// Expands or collapses the node with the given path.
+    ToggleExpanded(Vec<usize>),
+    /// Expands the focused node, or moves the focus to its first child if it's already expanded.
+    ExpandFocused,
+    /// Collapses the focused node, or moves the focus to its parent if it's already collapsed.
+    CollapseFocused,
+    /// Deselects all nodes of the tree.
+    ClearSelection,
+}
+
+// Sent by the tree content to update the visible rows when the tree is rebuilt.
+enum TreeViewRowsEvent {
+    Set(Vec<TreeRow>),
+}
+
+// A visible node of the tree.
+#[derive(Debug, Clone, PartialEq)]
+struct TreeRow {
+    path: Vec<usize>,
+    // Whether the node can be expanded.
+    expandable: bool,
+    // Whether the children of the node have been loaded.
+    loaded: bool,
+}
+
+/// A view for displaying hierarchical data as a tree of nodes which can be expanded and collapsed.
+///
+/// Only the nodes which are visible, because all of their ancestors are expanded, are built, so large trees such as a
+/// file system can be displayed by loading the children of a node when it's first expanded with
+/// [`on_load_children`](Handle::on_load_children).
+///
+/// The tree can be navigated with the arrow keys, where the right arrow expands the focused node and the left arrow
+/// collapses it.
+#[derive(Lens)]
+pub struct TreeView {
+    // The visible nodes in the order they are displayed.
+    #[lens(ignore)]
+    rows: Vec<TreeRow>,
+    expanded: BTreeSet<Vec<usize>>,
+    selected: BTreeSet<Vec<usize>>,
+    selectable: Selectable,
+    focused: Option<Vec<usize>>,
+    focus_visible: bool,
+    on_select: Option<Box<dyn Fn(&mut EventContext, &[usize])>>,
+    on_load_children: Option<Box<dyn Fn(&mut EventContext, &[usize])>>,
+}
+
+impl TreeView {
+    /// Creates a new [TreeView] view from a lens to the root nodes, a closure which returns the children of a node
+    /// and a template for constructing the content of each node.
+    ///
+    /// # Example
+    /// ```
+    /// # use vizia_core::prelude::*;
+    /// # let cx = &mut Context::default();
+    /// #[derive(Debug, Clone, Data)]
+    /// pub struct Node {
+    ///     name: String,
+    ///     children: Vec<Node>,
+    /// }
+    ///
+    /// #[derive(Lens)]
+    /// pub struct AppData {
+    ///     nodes: Vec<Node>,
+    /// }
+    ///
+    /// # impl Model for AppData {}
+    /// # AppData { nodes: Vec::new() }.build(cx);
+    /// TreeView::new(cx, AppData::nodes, |node| &node.children, |cx, _, node| {
+    ///     Label::new(cx, node.map(|node| node.name.clone())).hoverable(false);
+    /// });
+    /// ```
+    pub fn new<L: Lens, T: 'static>(
+        cx: &mut Context,
+        roots: L,
+        children: impl 'static + Fn(&T) -> &[T],
+        item_content: impl 'static + Fn(&mut Context, &[usize], MapRef<L, T>),
+    ) -> Handle<Self>
+    where
+        L::Target: Deref<Target = [T]> + Data,
+    {
+        let children = Rc::new(children);
+        let c = children.clone();
+        Self::new_lazy(
+            cx,
+            roots,
+            move |node| c(node),
+            move |node| !children(node).is_empty(),
+            item_content,
+        )
+    }
+
+    /// Creates a new [TreeView] view where `has_children` returns whether a node can be expanded, even if its
+    /// children haven't been loaded yet.
+    ///
+    /// When a node with no loaded children is expanded, the [`on_load_children`](Handle::on_load_children) callback
+    /// is triggered so that the children can be added to the bound data.
+    pub fn new_lazy<L: Lens, T: 'static>(
+        cx: &mut Context,
+        roots: L,
+        children: impl 'static + Fn(&T) -> &[T],
+        has_children: impl 'static + Fn(&T) -> bool,
+        item_content: impl 'static + Fn(&mut Context, &[usize], MapRef<L, T>),
+    ) -> Handle<Self>
+    where
+        L::Target: Deref<Target = [T]> + Data,
+    {
+        let children: Rc<dyn Fn(&T) -> &[T]> = Rc::new(children);
+        let has_children: Rc<dyn Fn(&T) -> bool> = Rc::new(has_children);
+        let content = Rc::new(item_content);
+        Self {
+            rows: Vec::new(),
+            expanded: BTreeSet::default(),
+            selected: BTreeSet::default(),
+            selectable: Selectable::None,
+            focused: None,
+            focus_visible: false,
+            on_select: None,
+            on_load_children: None,
+        }
+        .build(cx, move |cx| {
+            Keymap::from(vec![
+                (
+                    KeyChord::new(Modifiers::empty(), Code::ArrowDown),
+                    KeymapEntry::new("Focus Next", |cx| cx.emit(TreeViewEvent::FocusNext)),
+                ),
+                (
+                    KeyChord::new(Modifiers::empty(), Code::ArrowUp),
+                    KeymapEntry::new("Focus Previous", |cx| cx.emit(TreeViewEvent::FocusPrev)),
+                ),
+                (
+                    KeyChord::new(Modifiers::empty(), Code::ArrowRight),
+                    KeymapEntry::new("Expand Focused", |cx| cx.emit(TreeViewEvent::ExpandFocused)),
+                ),
+                (
+                    KeyChord::new(Modifiers::empty(), Code::ArrowLeft),
+                    KeymapEntry::new("Collapse Focused", |cx| {
+                        cx.emit(TreeViewEvent::CollapseFocused)
+                    }),
+                ),
+                (
+                    KeyChord::new(Modifiers::empty(), Code::Space),
+                    KeymapEntry::new("Select Focused", |cx| cx.emit(TreeViewEvent::SelectFocused)),
+                ),
+                (
+                    KeyChord::new(Modifiers::empty(), Code::Enter),
+                    KeymapEntry::new("Select Focused", |cx| cx.emit(TreeViewEvent::SelectFocused)),
+                ),
+            ])
+            .build(cx);
+
+            ScrollView::new(cx, move |cx| {
+                Binding::new(cx, TreeView::expanded, move |cx, expanded| {
+                    let children = children.clone();
+                    let has_children = has_children.clone();
+                    let content = content.clone();
+
+                    // Rebuild the visible nodes whenever the bound data changes, such as when children are loaded.
+                    Binding::new(cx, roots, move |cx, _| {
+                        let expanded = expanded.get(cx);
+                        let data = roots.get(cx);
+
+                        let mut rows = Vec::new();
+                        let mut path = Vec::new();
+                        collect_rows(
+                            &data,
+                            &*children,
+                            &*has_children,
+                            &expanded,
+                            &mut path,
+                            &mut rows,
+                        );
+
+                        for row in rows.iter() {
+                            let path = row.path.clone();
+                            let children = children.clone();
+                            let item =
+                                roots.map_ref(move |roots| node_at(roots, &path, &*children));
+                            let content = content.clone();
+                            TreeViewItem::new(
+                                cx,
+                                row.path.clone(),
+                                row.expandable,
+                                item,
+                                move |cx, path, item| content(cx, path, item),
+                            );
+                        }
+
+                        cx.emit(TreeViewRowsEvent::Set(rows));
+                    });
+                });
+            });
+        })
+        .toggle_class("selectable", TreeView::selectable.map(|s| *s != Selectable::None))
+        .navigable(true)
+        .role(Role::Tree)
+    }
+}
+
+// Appends the visible nodes of the given subtree to the rows in the order they are displayed.
+fn collect_rows<T>(
+    nodes: &[T],
+    children: &dyn Fn(&T) -> &[T],
+    has_children: &dyn Fn(&T) -> bool,
+    expanded: &BTreeSet<Vec<usize>>,
+    path: &mut Vec<usize>,
+    rows: &mut Vec<TreeRow>,
+) {
+    for (index, node) in nodes.iter().enumerate() {
+        path.push(index);
+
+        let node_children = children(node);
+        let expandable = has_children(node);
+        rows.push(TreeRow { path: path.clone(), expandable, loaded: !node_children.is_empty() });
+
+        if expandable && expanded.contains(path) {
+            collect_rows(node_children, children, has_children, expanded, path, rows);
+        }
+
+        path.pop();
+    }
+}
+
+// Returns the node with the given path.
+fn node_at<'a, T>(roots: &'a [T], path: &[usize], children: &dyn Fn(&T) -> &[T]) -> &'a T {
+    let mut node = &roots[path[0]];
+    for index in &path[1..] {
+        node = &children(node)[*index];
+    }
+
+    node
+}
+
+impl TreeView {
+    // Returns the position of the focused node within the visible rows.
+    fn focused_row(&self) -> Option<usize> {
+        let focused = self.focused.as_ref()?;
+        self.rows.iter().position(|row| &row.path == focused)
+    }
+
+    fn expand(&mut self, cx: &mut EventContext, path: Vec<usize>) {
+        let Some(row) = self.rows.iter().find(|row| row.path == path) else { return };
+        if !row.expandable {
+            return;
+        }
+
+        if !row.loaded {
+            if let Some(on_load_children) = &self.on_load_children {
+                on_load_children(cx, &path);
+            }
+        }
+
+        self.expanded.insert(path);
+    }
+
+    fn collapse(&mut self, path: Vec<usize>) {
+        // Move the focus out of the collapsed subtree so that it stays on a visible node.
+        if self
+            .focused
+            .as_ref()
+            .is_some_and(|focused| focused.len() > path.len() && focused.starts_with(&path))
+        {
+            self.focused = Some(path.clone());
+        }
+
+        self.expanded.remove(&path);
+    }
+}
+
+impl View for TreeView {
+    fn element(&self) -> Option<&'static str> {
+        Some("treeview")
+    }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.take(|rows_event, _| match rows_event {
+            TreeViewRowsEvent::Set(rows) => {
+                if self
+                    .focused
+                    .as_ref()
+                    .is_some_and(|focused| !rows.iter().any(|row| &row.path == focused))
+                {
+                    self.focused = None;
+                }
+
+                self.rows = rows;
+            }
+        });
+
+        event.take(|tree_event, _| match tree_event {
+            TreeViewEvent::Select(path) => {
+                cx.focus();
+                match self.selectable {
+                    Selectable::Single => {
+                        if self.selected.contains(&path) {
+                            self.selected.clear();
+                            self.focused = None;
+                        } else {
+                            self.selected.clear();
+                            self.selected.insert(path.clone());
+                            self.focused = Some(path.clone());
+                            self.focus_visible = false;
+                            if let Some(on_select) = &self.on_select {
+                                on_select(cx, &path);
+                            }
+                        }
+                    }
+
+                    Selectable::Multi => {
+                        if self.selected.contains(&path) {
+                            self.selected.remove(&path);
+                            self.focused = None;
+                        } else {
+                            self.selected.insert(path.clone());
+                            self.focused = Some(path.clone());
+                            self.focus_visible = false;
+                            if let Some(on_select) = &self.on_select {
+                                on_select(cx, &path);
+                            }
+                        }
+                    }
+
+                    Selectable::None => {}
+                }
+            }
+
+            TreeViewEvent::SelectFocused => {
+                if let Some(focused) = &self.focused {
+                    cx.emit(TreeViewEvent::Select(focused.clone()));
+                }
+            }
+
+            TreeViewEvent::ClearSelection => {
+                self.selected.clear();
+            }
+
+            TreeViewEvent::FocusNext => {
+                if self.rows.is_empty() {
+                    return;
+                }
+
+                let next = match self.focused_row() {
+                    Some(row) if row + 1 < self.rows.len() => row + 1,
+                    _ => 0,
+                };
+
+                self.focused = Some(self.rows[next].path.clone());
+                self.focus_visible = true;
+            }
+
+            TreeViewEvent::FocusPrev => {
+                if self.rows.is_empty() {
+                    return;
+                }
+
+                let prev = match self.focused_row() {
+                    Some(row) if row > 0 => row - 1,
+                    _ => self.rows.len() - 1,
+                };
+
+                self.focused = Some(self.rows[prev].path.clone());
+                self.focus_visible = true;
+            }
+
+            TreeViewEvent::Expand(path) => self.expand(cx, path),
+
+            TreeViewEvent::Collapse(path) => self.collapse(path),
+
+            TreeViewEvent::ToggleExpanded(path) => {
+                if self.expanded.contains(&path) {
+                    self.collapse(path);
+                } else {
+                    self.expand(cx, path);
+                }
+            }
+
+            TreeViewEvent::ExpandFocused => {
+                let Some(index) = self.focused_row() else { return };
+                let row = &self.rows[index];
+                if !row.expandable {
+                    return;
+                }
+
+                if !self.expanded.contains(&row.path) {
+                    self.expand(cx, row.path.clone());
+                } else if let Some(child) =
+                    self.rows.get(index + 1).filter(|child| child.path.starts_with(&row.path))
+                {
+                    self.focused = Some(child.path.clone());
+                }
+
+                self.focus_visible = true;
+            }
+
+            TreeViewEvent::CollapseFocused => {
+                let Some(focused) = self.focused.clone() else { return };
+                if self.expanded.contains(&focused) {
+                    self.collapse(focused);
+                } else if focused.len() > 1 {
+                    self.focused = Some(focused[..focused.len() - 1].to_vec());
+                }
+
+                self.focus_visible = true;
+            }
+        });
+    }
+}
+
+impl Handle<'_, TreeView> {
+    /// Set the selectable state of the [TreeView].
+    pub fn selectable<U: Into<Selectable>>(self, selectable: impl Res<U>) -> Self {
+        self.bind(selectable, |handle, selectable| {
+            let s = selectable.get(&handle).into();
+            handle.modify(|tree_view: &mut TreeView| tree_view.selectable = s);
+        })
+    }
+
+    /// Sets the callback triggered when a node is selected, which provides the path of the node.
+    pub fn on_select<F>(self, callback: F) -> Self
+    where
+        F: 'static + Fn(&mut EventContext, &[usize]),
+    {
+        self.modify(|tree_view: &mut TreeView| tree_view.on_select = Some(Box::new(callback)))
+    }
+
+    /// Sets the callback triggered when a node with no loaded children is expanded, which provides the path of the
+    /// node. The callback should add the children of the node to the bound data.
+    ///
+    /// Used with [`TreeView::new_lazy`] so that the children of a node are only loaded when they're first shown.
+    pub fn on_load_children<F>(self, callback: F) -> Self
+    where
+        F: 'static + Fn(&mut EventContext, &[usize]),
+    {
+        self.modify(|tree_view: &mut TreeView| {
+            tree_view.on_load_children = Some(Box::new(callback))
+        })
+    }
+}
+
+/// A view which represents a node within a [TreeView].
+///
+/// The node is indented with an `indent-guide` element for each of its ancestors, followed by an `expander` arrow
+/// which is checked while the node is expanded.
+pub struct TreeViewItem {}
+
+impl TreeViewItem {
+    /// Create a new [TreeViewItem] view.
+    pub fn new<L: Lens, T: 'static>(
+        cx: &mut Context,
+        path: Vec<usize>,
+        expandable: bool,
+        item: MapRef<L, T>,
+        item_content: impl 'static + Fn(&mut Context, &[usize], MapRef<L, T>),
+    ) -> Handle<Self> {
+        let p = path.clone();
+        let s = path.clone();
+        let f = path.clone();
+        let press = path.clone();
+        Self {}
+            .build(cx, move |cx| {
+                for _ in 1..path.len() {
+                    Element::new(cx).class("indent-guide");
+                }
+
+                if expandable {
+                    let e = path.clone();
+                    let toggle = path.clone();
+                    Svg::new(cx, ICON_CHEVRON_RIGHT)
+                        .class("expander")
+                        .checked(TreeView::expanded.map(move |expanded| expanded.contains(&e)))
+                        .on_press(move |cx| cx.emit(TreeViewEvent::ToggleExpanded(toggle.clone())));
+                } else {
+                    Element::new(cx).class("expander");
+                }
+
+                item_content(cx, &path, item);
+            })
+            .role(Role::TreeItem)
+            .toggle_class("expandable", expandable)
+            .toggle_class("expanded", TreeView::expanded.map(move |expanded| expanded.contains(&p)))
+            .checked(TreeView::selected.map(move |selected| selected.contains(&s)))
+            .focused_with_visibility(
+                TreeView::focused.map(move |focused| focused.as_ref() == Some(&f)),
+                TreeView::focus_visible,
+            )
+            .on_press(move |cx| cx.emit(TreeViewEvent::Select(press.clone())))
+    }
+}
+
+impl View for TreeViewItem {
+    fn element(&self) -> Option<&'static str> {
+        Some("treeview-item")
+    }
+}
@@ -17,10 +17,36 @@ pub(crate) enum VirtualListEvent {
 struct VirtualListData {
     num_items: usize,
     item_height: f32,
+    // The top of each item followed by the total height, or empty if all items have a height of `item_height`.
+    offsets: Vec<f32>,
     visible_range: Range<usize>,
     scroll_y: f32,
 }
 
+// Returns the total height of the items of a list.
+fn content_height(num_items: usize, item_height: f32, offsets: &[f32]) -> f32 {
+    offsets.last().copied().unwrap_or(num_items as f32 * item_height)
+}
+
+// Returns the range of items with variable heights, described by `offsets`, which are within the visible area.
+fn visible_range(offsets: &[f32], visible_height: f32, scroll_y: f32) -> Range<usize> {
+    let num_items = offsets.len().saturating_sub(1);
+    if num_items == 0 {
+        return 0..0;
+    }
+
+    let total_height = offsets[num_items];
+    let visible_start = (total_height - visible_height).max(0.0) * scroll_y;
+    let visible_end = visible_start + visible_height;
+
+    // The first item which ends after the start of the visible area, and the first item which starts after its end.
+    let start_index =
+        offsets[1..].partition_point(|bottom| *bottom <= visible_start).min(num_items);
+    let end_index = offsets[..num_items].partition_point(|top| *top < visible_end);
+
+    start_index..end_index.max(start_index)
+}
+
 impl VirtualListData {
     fn evaluate_index(index: usize, start: usize, end: usize) -> usize {
         match end - start {
@@ -49,6 +75,11 @@ impl VirtualListData {
         let total_height = item_height * (self.num_items as f32);
         let visible_height = current_height / cx.scale_factor();
 
+        if !self.offsets.is_empty() {
+            self.visible_range = visible_range(&self.offsets, visible_height, self.scroll_y);
+            return;
+        }
+
         let mut num_visible_items = (visible_height / item_height).ceil();
         num_visible_items += 1.0; // To account for partially-visible items.
 
@@ -65,6 +96,14 @@ impl VirtualListData {
 
         self.visible_range = start_index..end_index.min(self.num_items);
     }
+
+    // Returns the top and height of the item with the given index.
+    fn item_bounds(&self, index: usize) -> (f32, f32) {
+        match (self.offsets.get(index), self.offsets.get(index + 1)) {
+            (Some(top), Some(bottom)) => (*top, bottom - top),
+            _ => (index as f32 * self.item_height, self.item_height),
+        }
+    }
 }
 
 impl Model for VirtualListData {
@@ -109,6 +148,34 @@ impl VirtualList {
         )
     }
 
+    /// Creates a new [VirtualList] view where each item has its own height, in logical pixels, given by `item_height`.
+    ///
+    /// The heights are recomputed whenever the list changes, so `item_height` should be cheap to evaluate, for example
+    /// by returning an estimate based on the contents of the item.
+    pub fn new_variable<V: View, L: Lens, T: 'static>(
+        cx: &mut Context,
+        list: L,
+        item_height: impl 'static + Fn(&T) -> f32,
+        item_content: impl 'static + Copy + Fn(&mut Context, usize, MapRef<L, T>) -> Handle<V>,
+    ) -> Handle<Self>
+    where
+        L::Target: Deref<Target = [T]>,
+    {
+        let layout = list.map(move |list| {
+            let mut offsets = Vec::with_capacity(list.len() + 1);
+            let mut top = 0.0;
+            offsets.push(top);
+            for item in list.iter() {
+                top += item_height(item).max(0.0);
+                offsets.push(top);
+            }
+
+            (list.len(), offsets)
+        });
+
+        Self::new_inner(cx, list, layout, |list, index| &list[index], 0.0, item_content)
+    }
+
     /// Creates a new [VirtualList] view with a binding to the given lens and a template for constructing the list items.
     pub fn new_generic<V: View, L: Lens, T: 'static>(
         cx: &mut Context,
@@ -117,22 +184,38 @@ impl VirtualList {
         list_index: impl 'static + Copy + Fn(&L::Target, usize) -> &T,
         item_height: f32,
         item_content: impl 'static + Copy + Fn(&mut Context, usize, MapRef<L, T>) -> Handle<V>,
+    ) -> Handle<Self> {
+        let layout = list.map(move |list| (list_len(list), Vec::new()));
+        Self::new_inner(cx, list, layout, list_index, item_height, item_content)
+    }
+
+    fn new_inner<V: View, L: Lens, T: 'static>(
+        cx: &mut Context,
+        list: L,
+        layout: impl Lens<Target = (usize, Vec<f32>)>,
+        list_index: impl 'static + Copy + Fn(&L::Target, usize) -> &T,
+        item_height: f32,
+        item_content: impl 'static + Copy + Fn(&mut Context, usize, MapRef<L, T>) -> Handle<V>,
     ) -> Handle<Self> {
         let vl = cx.current;
-        let num_items = list.map(list_len);
         Self { scroll_to_cursor: true, on_change: None }.build(cx, |cx| {
-            Binding::new(cx, num_items, move |cx, lens| {
-                let num_items = lens.get(cx);
+            Binding::new(cx, layout, move |cx, lens| {
+                let (num_items, offsets) = lens.get(cx);
 
-                let mut data =
-                    VirtualListData { num_items, item_height, visible_range: 0..0, scroll_y: 0.0 };
+                let mut data = VirtualListData {
+                    num_items,
+                    item_height,
+                    offsets,
+                    visible_range: 0..0,
+                    scroll_y: 0.0,
+                };
                 data.recalc(&mut EventContext::new_with_current(cx, vl));
                 data.build(cx);
             });
 
             ScrollView::new(cx, move |cx| {
-                Binding::new(cx, num_items, move |cx, lens| {
-                    let num_items = lens.get(cx);
+                Binding::new(cx, layout, move |cx, lens| {
+                    let (num_items, offsets) = lens.get(cx);
                     cx.emit(ScrollEvent::SetY(0.0));
                     // The ScrollView contains a VStack which is sized to the total height
                     // needed to fit all items. This ensures we have a correct scroll bar.
@@ -146,6 +229,12 @@ impl VirtualList {
                                 // As we scroll the index may change, representing an item going in/out of visibility.
                                 // Wrap `item_content` in a binding to said index, so it rebuilds only when necessary.
                                 let item_index = VirtualListData::visible_item_index(i);
+                                let item_bounds = VirtualListData::root.map(move |data| {
+                                    let range = &data.visible_range;
+                                    let index =
+                                        VirtualListData::evaluate_index(i, range.start, range.end);
+                                    data.item_bounds(index)
+                                });
                                 Binding::new(cx, item_index, move |cx, lens| {
                                     let index = lens.get(cx);
                                     HStack::new(cx, move |cx| {
@@ -153,20 +242,23 @@ impl VirtualList {
                                             list.map_ref(move |list| list_index(list, index));
                                         item_content(cx, index, item).height(Percentage(100.0));
                                     })
-                                    .height(Pixels(item_height))
                                     .position_type(PositionType::Absolute)
                                     .bind(
-                                        item_index,
+                                        item_bounds,
                                         move |handle, lens| {
-                                            let index = lens.get(&handle);
-                                            handle.top(Pixels(index as f32 * item_height));
+                                            let (top, height) = lens.get(&handle);
+                                            handle.top(Pixels(top)).height(Pixels(height));
                                         },
                                     );
                                 });
                             }
                         })
                     })
-                    .height(Pixels(num_items as f32 * item_height));
+                    .height(Pixels(content_height(
+                        num_items,
+                        item_height,
+                        &offsets,
+                    )));
                 })
             })
             .show_horizontal_scrollbar(false)
@@ -228,4 +320,21 @@ mod tests {
         // Move forward by 9
         assert_eq!(evaluate_indices(9..13), [12, 9, 10, 11]);
     }
+
+    #[test]
+    fn test_visible_range() {
+        // Items with heights of 10, 20, 30 and 40.
+        let offsets = [0.0, 10.0, 30.0, 60.0, 100.0];
+
+        // Scrolled to the top.
+        assert_eq!(visible_range(&offsets, 25.0, 0.0), 0..2);
+        // Scrolled to the bottom.
+        assert_eq!(visible_range(&offsets, 25.0, 1.0), 3..4);
+        // Scrolled to the middle.
+        assert_eq!(visible_range(&offsets, 50.0, 0.5), 1..4);
+        // Taller than the content.
+        assert_eq!(visible_range(&offsets, 200.0, 0.0), 0..4);
+        // No items.
+        assert_eq!(visible_range(&[0.0], 25.0, 0.0), 0..0);
+    }
 }
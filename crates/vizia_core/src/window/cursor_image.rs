@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use skia_safe::{AlphaType, CachingHint, ColorType, ImageInfo};
+
+/// An image used as the mouse cursor with [`WindowEvent::SetCustomCursor`](crate::window::WindowEvent::SetCustomCursor),
+/// stored as non-premultiplied RGBA pixels.
+///
+/// The cursors created from images are cached by the backend, so an image which is set again, such as when switching
+/// between tools, reuses the cursor which was created for it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CursorImage {
+    rgba: Arc<[u8]>,
+    width: u16,
+    height: u16,
+    hotspot_x: u16,
+    hotspot_y: u16,
+}
+
+impl CursorImage {
+    /// Creates a new cursor image from RGBA pixels, four bytes per pixel, row by row, with the (x,y) position of
+    /// the hotspot, which is the point of the image at the position of the mouse.
+    pub fn from_rgba(
+        rgba: impl Into<Arc<[u8]>>,
+        width: u16,
+        height: u16,
+        hotspot_x: u16,
+        hotspot_y: u16,
+    ) -> Self {
+        Self { rgba: rgba.into(), width, height, hotspot_x, hotspot_y }
+    }
+
+    /// Creates a cursor image by reading the pixels of a skia image, such as a brush preview drawn onto a surface,
+    /// returning `None` if they can't be read.
+    pub fn from_image(image: &skia_safe::Image, hotspot_x: u16, hotspot_y: u16) -> Option<Self> {
+        let (width, height) =
+            (u16::try_from(image.width()).ok()?, u16::try_from(image.height()).ok()?);
+        let info = ImageInfo::new(
+            (width as i32, height as i32),
+            ColorType::RGBA8888,
+            AlphaType::Unpremul,
+            None,
+        );
+        let mut rgba = vec![0; width as usize * height as usize * 4];
+        image
+            .read_pixels(&info, &mut rgba, width as usize * 4, (0, 0), CachingHint::Allow)
+            .then(|| Self::from_rgba(rgba, width, height, hotspot_x, hotspot_y))
+    }
+
+    /// Returns the RGBA pixels of the image.
+    pub fn rgba(&self) -> &[u8] {
+        &self.rgba
+    }
+
+    /// Returns the width of the image in pixels.
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    /// Returns the height of the image in pixels.
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// Returns the (x,y) position of the hotspot within the image.
+    pub fn hotspot(&self) -> (u16, u16) {
+        (self.hotspot_x, self.hotspot_y)
+    }
+}
@@ -1,4 +1,7 @@
 //! Window management and system events.
 
+mod cursor_image;
+pub use cursor_image::*;
+
 mod window_event;
 pub use window_event::*;
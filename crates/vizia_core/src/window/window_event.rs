@@ -1,10 +1,37 @@
 use std::path::PathBuf;
 
-use crate::{entity::Entity, environment::ThemeMode, layout::cache::GeoChanged};
+use crate::{
+    entity::Entity, environment::ThemeMode, layout::cache::GeoChanged, window::CursorImage,
+};
 use vizia_input::{Code, Key, MouseButton};
 use vizia_style::CursorIcon;
 use vizia_window::{WindowPosition, WindowSize};
 
+/// An identifier for an input device, such as one of several mice, assigned by the backend.
+///
+/// Identifiers can be compared to tell events from different devices apart, but aren't stable between runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DeviceId(u64);
+
+impl DeviceId {
+    /// Creates a device identifier from an identifier assigned by the backend.
+    pub fn new(id: u64) -> Self {
+        Self(id)
+    }
+}
+
+/// How the mouse cursor is grabbed by a window.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CursorGrabMode {
+    /// The cursor moves freely.
+    #[default]
+    None,
+    /// The cursor is confined to the window.
+    Confined,
+    /// The cursor is locked in place.
+    Locked,
+}
+
 #[derive(Debug, Clone)]
 /// Data associated with a drop event.
 pub enum DropData {
@@ -26,6 +53,23 @@ impl From<PathBuf> for DropData {
     }
 }
 
+/// Data dragged out of the window into other applications, started with
+/// [`EventContext::start_external_drag`](crate::context::EventContext::start_external_drag).
+#[derive(Debug, Clone, PartialEq)]
+pub enum DragPayload {
+    /// Paths to files.
+    Files(Vec<PathBuf>),
+    /// Plain text, which is dropped as text on macOS and as a text file on Windows.
+    Text(String),
+    /// Bytes with a MIME type, which are dropped as a file with an extension matching the MIME type.
+    Bytes {
+        /// The MIME type of the data, such as `image/png`.
+        mime: String,
+        /// The data.
+        data: Vec<u8>,
+    },
+}
+
 /// Events generated by the application in response to OS events as well as events that can be used
 /// to set properties of the window.
 #[derive(Debug, Clone)]
@@ -34,6 +78,12 @@ pub enum WindowEvent {
     WindowClose,
     /// Emitted when a file is dragged and then dropped onto the window.
     Drop(DropData),
+    /// Starts a native drag of the data out of the window. Emitted by
+    /// [`EventContext::start_external_drag`](crate::context::EventContext::start_external_drag).
+    StartExternalDrag(DragPayload),
+    /// Emitted to the view which started a drag out of the window when the drag ends, with whether the data was
+    /// dropped onto another application. A drag which the platform doesn't support ends immediately without a drop.
+    ExternalDragEnded(bool),
     /// Emitted when a mouse button is double clicked.
     MouseDoubleClick(MouseButton),
     /// Emitted when a mouse button is triple clicked
@@ -56,6 +106,9 @@ pub enum WindowEvent {
     MouseMove(f32, f32),
     /// Emitted when the mouse scroll wheel is scrolled.
     MouseScroll(f32, f32),
+    /// Emitted when a pinch gesture is performed on a touchpad, with the change in scale, which is positive when
+    /// zooming in.
+    PinchGesture(f32),
     /// Emitted when the mouse cursor enters the bounding box of an entity.
     MouseOver,
     /// Emitted when the mouse cursor leaves the bounding box of an entity.
@@ -74,16 +127,59 @@ pub enum WindowEvent {
     WindowFocused(bool),
     /// Emitted when a character is typed.
     CharInput(char),
+    /// Emitted when the text being composed with an input method changes, with the byte range of the cursor within
+    /// the text if it should be shown. An empty string clears the text being composed.
+    ImePreedit(String, Option<(usize, usize)>),
+    /// Emitted when an input method commits text which should be inserted.
+    ImeCommit(String),
     /// Emitted when a keyboard key is pressed.
     KeyDown(Code, Option<Key>),
     /// Emitted when a keyboard key is released.
     KeyUp(Code, Option<Key>),
     /// Emited when the system window theme has changed.
     ThemeChanged(ThemeMode),
+    /// Emitted to all views when the scale factor changes, e.g. when a window is moved to a monitor with a
+    /// different DPI, with the new scale factor.
+    ScaleFactorChanged(f32),
     /// Sets the mouse cursor icon.
     SetCursor(CursorIcon),
+    /// Sets the mouse cursor to an image, such as a preview of a brush. Like [`WindowEvent::SetCursor`], the cursor is
+    /// replaced when the hovered view changes unless the cursor icon is locked with
+    /// [`EventContext::lock_cursor_icon`](crate::context::EventContext::lock_cursor_icon).
+    SetCustomCursor(CursorImage),
     /// Grabs the mouse cursor, preventing it from leaving the window.
     GrabCursor(bool),
+    /// Sets how the mouse cursor is grabbed by the window. A locked cursor which the platform doesn't support is
+    /// confined to the window instead.
+    SetCursorGrab(CursorGrabMode),
+    /// Shows or hides the mouse cursor while it's over the window.
+    SetCursorVisible(bool),
+    /// Emitted to the view which locked the pointer with the relative (x,y) motion of the mouse in physical pixels.
+    MouseMotion(f32, f32),
+    /// Emitted with the unaccelerated (x,y) motion reported by a mouse, in device units, while a window of the
+    /// application is focused. It's sent to the view which locked the pointer, or else to the view which captured
+    /// the mouse, or else to the hovered view, and propagates up the tree.
+    RawMouseDelta {
+        /// The mouse which moved.
+        device: DeviceId,
+        /// The horizontal motion of the mouse.
+        dx: f64,
+        /// The vertical motion of the mouse.
+        dy: f64,
+    },
+    /// Emitted when a key of a keyboard is pressed or released while a window of the application is focused, with
+    /// the platform scancode of the key, which doesn't depend on the keyboard layout. It's sent to the focused view
+    /// and propagates up the tree. Unlike [`WindowEvent::KeyDown`], a held key isn't repeated.
+    RawKey {
+        /// The keyboard with the key.
+        device: DeviceId,
+        /// The physical key.
+        code: Code,
+        /// The platform scancode of the key, if it's known.
+        scancode: Option<u32>,
+        /// Whether the key was pressed or released.
+        pressed: bool,
+    },
     /// Sets the (x,y) position of the mouse cursor in window coordinates.
     SetCursorPosition(u32, u32),
     /// Sets the title of the window.
@@ -94,7 +190,7 @@ pub enum WindowEvent {
     SetPosition(WindowPosition),
     /// Sets the maximum size of the window.
     SetMaxSize(Option<WindowSize>),
-    /// Sets the minimum size of the window.
+    /// Sets the minimum inner size of the window.
     SetMinSize(Option<WindowSize>),
     /// Sets whether the window is resizable.
     SetResizable(bool),
@@ -108,6 +204,9 @@ pub enum WindowEvent {
     SetDecorations(bool),
     /// Sets whether the window remains on top of other windows.
     SetAlwaysOnTop(bool),
+    /// Sets whether the background of the window is transparent, which also toggles the `transparent` class of the
+    /// window so that the theme clears its background color.
+    SetTransparent(bool),
     /// Emitted when mouse events have been captured.
     MouseCaptureEvent,
     /// Emitted when mouse events have been released.
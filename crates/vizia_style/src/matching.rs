@@ -7,6 +7,26 @@ pub use selectors::{
     Element,
 };
 
+use cssparser::{ParseError, Parser, ParserInput};
+
+use crate::{CustomParseError, ParserOptions, SelectorParser, Selectors};
+
+/// Parses a comma separated list of selectors, such as `.my-class button:checked`, which must make up the whole input.
+pub fn parse_selector_list(
+    input: &str,
+) -> Result<SelectorList<Selectors>, ParseError<'_, CustomParseError<'_>>> {
+    let mut parser_input = ParserInput::new(input);
+    let mut parser = Parser::new(&mut parser_input);
+    let options = ParserOptions::default();
+    parser.parse_entirely(|input| {
+        SelectorList::parse(
+            &SelectorParser { options: &options },
+            input,
+            selectors::parser::ParseRelative::No,
+        )
+    })
+}
+
 #[cfg(test)]
 mod test {
     use std::{
@@ -60,6 +80,8 @@ mod test {
         element: HashMap<Entity, String>,
         classes: HashMap<Entity, HashSet<String>>,
         pseudo_class: HashMap<Entity, PseudoClass>,
+        // The children of the root entity, `Entity(0)`, in order.
+        children: Vec<Entity>,
     }
 
     #[derive(Debug, Clone)]
@@ -88,15 +110,22 @@ mod test {
         }
 
         fn parent_element(&self) -> Option<Self> {
-            None
+            self.store
+                .children
+                .contains(&self.entity)
+                .then_some(Node { entity: Entity(0), store: self.store })
         }
 
         fn prev_sibling_element(&self) -> Option<Self> {
-            None
+            let index = self.store.children.iter().position(|child| *child == self.entity)?;
+            let entity = *self.store.children.get(index.checked_sub(1)?)?;
+            Some(Node { entity, store: self.store })
         }
 
         fn next_sibling_element(&self) -> Option<Self> {
-            None
+            let index = self.store.children.iter().position(|child| *child == self.entity)?;
+            let entity = *self.store.children.get(index + 1)?;
+            Some(Node { entity, store: self.store })
         }
 
         fn is_empty(&self) -> bool {
@@ -255,6 +284,7 @@ mod test {
             element: HashMap::new(),
             classes: HashMap::new(),
             pseudo_class: HashMap::new(),
+            children: Vec::new(),
         };
 
         let root = Entity(0);
@@ -288,6 +318,7 @@ mod test {
             element: HashMap::new(),
             classes: HashMap::new(),
             pseudo_class: HashMap::new(),
+            children: Vec::new(),
         };
 
         let root = Entity(0);
@@ -327,6 +358,7 @@ mod test {
             element: HashMap::new(),
             classes: HashMap::new(),
             pseudo_class: HashMap::new(),
+            children: Vec::new(),
         };
 
         let root = Entity(0);
@@ -428,6 +460,7 @@ mod test {
             element: HashMap::new(),
             classes: HashMap::new(),
             pseudo_class: HashMap::new(),
+            children: Vec::new(),
         };
 
         let root = Entity(0);
@@ -464,4 +497,55 @@ mod test {
             assert!(!result);
         }
     }
+
+    #[test]
+    fn structural_pseudoclass_match() {
+        let mut store = Store {
+            element: HashMap::new(),
+            classes: HashMap::new(),
+            pseudo_class: HashMap::new(),
+            children: vec![Entity(1), Entity(2), Entity(3), Entity(4)],
+        };
+
+        store.element.insert(Entity(0), String::from("list"));
+        for child in store.children.clone() {
+            store.element.insert(child, String::from("row"));
+        }
+
+        let matching = |selector: &str| {
+            let selector_list = parse(selector).unwrap();
+            let mut cache = SelectorCaches::default();
+            let mut context = MatchingContext::new(
+                MatchingMode::Normal,
+                None,
+                &mut cache,
+                QuirksMode::NoQuirks,
+                NeedsSelectorFlags::No,
+                MatchingForInvalidation::No,
+            );
+
+            store
+                .children
+                .iter()
+                .filter(|entity| {
+                    let node = Node { entity: **entity, store: &store };
+                    matches_selector_list(&selector_list, &node, &mut context)
+                })
+                .map(|entity| entity.0)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(matching("row:first-child"), vec![1]);
+        assert_eq!(matching("row:last-child"), vec![4]);
+        assert_eq!(matching("row:nth-child(2n+1)"), vec![1, 3]);
+        assert_eq!(matching("list > row:nth-child(even)"), vec![2, 4]);
+        assert_eq!(matching("row:only-child"), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn parse_whole_selector_list() {
+        assert!(crate::parse_selector_list(".foo button:checked, #bar").is_ok());
+        assert!(crate::parse_selector_list(".foo { }").is_err());
+        assert!(crate::parse_selector_list("").is_err());
+    }
 }
@@ -1,11 +1,12 @@
 use crate::{
     define_property, Alignment, Angle, BackgroundImage, BackgroundSize, BlendMode, Border,
     BorderStyle, BorderWidth, ClipPath, Color, CornerRadius, CornerShape, CursorIcon,
-    CustomParseError, CustomProperty, Display, Filter, FontFamily, FontSize, FontSlant,
-    FontVariation, FontWeight, FontWidth, LayoutType, Length, LengthOrPercentage, LineClamp,
-    Opacity, Outline, Overflow, Parse, PointerEvents, Position, PositionType, Rect, Scale, Shadow,
-    TextAlign, TextDecoration, TextDecorationLine, TextDecorationStyle, TextOverflow, TextStroke,
-    TextStrokeStyle, Transform, Transition, Translate, Units, UnparsedProperty, Visibility,
+    CustomParseError, CustomProperty, Direction, Display, Filter, FlexWrap, FontFamily, FontSize,
+    FontSlant, FontVariation, FontWeight, FontWidth, GridPlacement, LayoutType, Length,
+    LengthOrPercentage, LineClamp, Opacity, Outline, Overflow, Parse, PointerEvents, Position,
+    PositionType, Rect, Scale, SelfAlignment, Shadow, TextAlign, TextDecoration,
+    TextDecorationLine, TextDecorationStyle, TextOverflow, TextShadow, TextStroke, TextStrokeStyle,
+    Transform, Transition, Translate, Units, UnparsedProperty, Visibility,
 };
 use cssparser::Parser;
 
@@ -27,6 +28,9 @@ define_property! {
         "position-type": PositionType(PositionType),
 
         "alignment": Alignment(Alignment),
+        "align-self": AlignSelf(SelfAlignment),
+        "justify-self": JustifySelf(SelfAlignment),
+        "flex-wrap": FlexWrap(FlexWrap),
 
         // Grid
         "grid-columns": GridColumns(Vec<Units>),
@@ -35,6 +39,10 @@ define_property! {
         "column-span": ColumnSpan(usize),
         "row-start": RowStart(usize),
         "row-span": RowSpan(usize),
+        "grid-template-columns": GridTemplateColumns(Vec<Units>),
+        "grid-template-rows": GridTemplateRows(Vec<Units>),
+        "grid-column": GridColumn(GridPlacement),
+        "grid-row": GridRow(GridPlacement),
 
         // Position and Size
         "space": Space(Units),
@@ -163,8 +171,12 @@ define_property! {
         "caret-color": CaretColor(Color),
         "text-wrap": TextWrap(bool),
         "text-align": TextAlign(TextAlign),
+        "direction": Direction(Direction),
         "text-overflow": TextOverflow(TextOverflow),
         "line-clamp": LineClamp(LineClamp),
+        "letter-spacing": LetterSpacing(Length),
+        "word-spacing": WordSpacing(Length),
+        "text-shadow": TextShadow(Vec<TextShadow>),
         "text-decoration": TextDecoration(TextDecoration),
         "text-decoration-line": TextDecorationLine(TextDecorationLine),
         "text-stroke": TextStroke(TextStroke),
@@ -182,6 +194,7 @@ define_property! {
 
         // Shadow
         "shadow": Shadow(Vec<Shadow>),
+        "box-shadow": BoxShadow(Vec<Shadow>),
 
         // Backdrop Filter
         "backdrop-filter": BackdropFilter(Filter),
@@ -215,4 +228,46 @@ mod tests {
         let _parsed_property =
             Property::parse_value(CowRcStr::from("background-color"), &mut parser);
     }
+
+    fn assert_property<'i>(name: &'static str, value: &'i str, expected: Property<'i>) {
+        let mut parser_input = ParserInput::new(value);
+        let mut parser = Parser::new(&mut parser_input);
+        let property = Property::parse_value(CowRcStr::from(name), &mut parser).unwrap();
+        assert_eq!(property, expected);
+    }
+
+    #[test]
+    fn parse_grid_properties() {
+        assert_property(
+            "grid-template-columns",
+            "100px 1s auto",
+            Property::GridTemplateColumns(vec![
+                Units::Pixels(100.0),
+                Units::Stretch(1.0),
+                Units::Auto,
+            ]),
+        );
+        assert_property(
+            "grid-template-rows",
+            "auto 50%",
+            Property::GridTemplateRows(vec![Units::Auto, Units::Percentage(50.0)]),
+        );
+
+        // Lines are numbered from one, as in CSS.
+        assert_property(
+            "grid-column",
+            "1 / span 2",
+            Property::GridColumn(GridPlacement::new(1, 2)),
+        );
+        assert_property("grid-column", "2 / 4", Property::GridColumn(GridPlacement::new(2, 2)));
+        assert_property("grid-row", "3", Property::GridRow(GridPlacement::new(3, 1)));
+        assert_property("grid-row", "2 / span 3", Property::GridRow(GridPlacement::new(2, 3)));
+        assert!(matches!(
+            Property::parse_value(
+                CowRcStr::from("grid-row"),
+                &mut Parser::new(&mut ParserInput::new("0 / span 1"))
+            ),
+            Ok(Property::Unparsed(_))
+        ));
+    }
 }
@@ -0,0 +1,186 @@
+use cssparser::*;
+
+use crate::{CssRuleList, CustomParseError, Location, Parse};
+
+/// A preferred color scheme used by the `prefers-color-scheme` media feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorScheme {
+    Light,
+    Dark,
+}
+
+/// A single media feature within a media query, such as `(max-width: 600px)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MediaFeature {
+    MinWidth(f32),
+    MaxWidth(f32),
+    MinHeight(f32),
+    MaxHeight(f32),
+    PrefersColorScheme(ColorScheme),
+}
+
+impl MediaFeature {
+    /// Returns true if the feature matches a viewport with the given logical size and color scheme.
+    pub fn matches(&self, width: f32, height: f32, color_scheme: ColorScheme) -> bool {
+        match *self {
+            MediaFeature::MinWidth(min) => width >= min,
+            MediaFeature::MaxWidth(max) => width <= max,
+            MediaFeature::MinHeight(min) => height >= min,
+            MediaFeature::MaxHeight(max) => height <= max,
+            MediaFeature::PrefersColorScheme(scheme) => scheme == color_scheme,
+        }
+    }
+}
+
+fn parse_pixels<'i>(
+    input: &mut Parser<'i, '_>,
+) -> Result<f32, ParseError<'i, CustomParseError<'i>>> {
+    let location = input.current_source_location();
+    match input.next()? {
+        Token::Dimension { value, unit, .. } if unit.eq_ignore_ascii_case("px") => Ok(*value),
+        Token::Number { value, .. } if *value == 0.0 => Ok(0.0),
+        t => Err(location.new_unexpected_token_error(t.clone())),
+    }
+}
+
+impl<'i> Parse<'i> for MediaFeature {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        input.expect_parenthesis_block()?;
+        input.parse_nested_block(|input| {
+            let location = input.current_source_location();
+            let name = input.expect_ident()?.clone();
+            input.expect_colon()?;
+
+            match_ignore_ascii_case! { &name,
+                "min-width" => Ok(MediaFeature::MinWidth(parse_pixels(input)?)),
+                "max-width" => Ok(MediaFeature::MaxWidth(parse_pixels(input)?)),
+                "min-height" => Ok(MediaFeature::MinHeight(parse_pixels(input)?)),
+                "max-height" => Ok(MediaFeature::MaxHeight(parse_pixels(input)?)),
+                "prefers-color-scheme" => {
+                    let location = input.current_source_location();
+                    let scheme = input.expect_ident()?;
+                    match_ignore_ascii_case! { scheme,
+                        "light" => Ok(MediaFeature::PrefersColorScheme(ColorScheme::Light)),
+                        "dark" => Ok(MediaFeature::PrefersColorScheme(ColorScheme::Dark)),
+                        _ => Err(location.new_unexpected_token_error(Token::Ident(scheme.clone()))),
+                    }
+                },
+                _ => Err(location.new_unexpected_token_error(Token::Ident(name))),
+            }
+        })
+    }
+}
+
+/// A media query made up of media features which must all match, such as
+/// `screen and (min-width: 400px) and (max-width: 600px)`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MediaQuery {
+    pub features: Vec<MediaFeature>,
+}
+
+impl MediaQuery {
+    /// Returns true if all of the features of the query match.
+    pub fn matches(&self, width: f32, height: f32, color_scheme: ColorScheme) -> bool {
+        self.features.iter().all(|feature| feature.matches(width, height, color_scheme))
+    }
+}
+
+impl<'i> Parse<'i> for MediaQuery {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        let mut features = Vec::new();
+
+        // The media types `all` and `screen` always match and can be ignored.
+        let has_media_type = input
+            .try_parse(|input| {
+                let location = input.current_source_location();
+                let ident = input.expect_ident()?;
+                match_ignore_ascii_case! { ident,
+                    "all" | "screen" => Ok(()),
+                    _ => Err(location.new_unexpected_token_error(Token::Ident(ident.clone()))),
+                }
+            })
+            .is_ok();
+
+        if !has_media_type {
+            features.push(MediaFeature::parse(input)?);
+        }
+
+        while input.try_parse(|input| input.expect_ident_matching("and")).is_ok() {
+            features.push(MediaFeature::parse(input)?);
+        }
+
+        Ok(MediaQuery { features })
+    }
+}
+
+/// A comma separated list of media queries, which matches if any of the queries match.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MediaQueryList(pub Vec<MediaQuery>);
+
+impl MediaQueryList {
+    /// Returns true if any of the queries in the list match.
+    pub fn matches(&self, width: f32, height: f32, color_scheme: ColorScheme) -> bool {
+        self.0.iter().any(|query| query.matches(width, height, color_scheme))
+    }
+
+    /// Combines two query lists into a list which matches only when both lists match, as used by nested media rules.
+    pub fn and(&self, other: &MediaQueryList) -> MediaQueryList {
+        let mut queries = Vec::with_capacity(self.0.len() * other.0.len());
+        for a in self.0.iter() {
+            for b in other.0.iter() {
+                let mut features = a.features.clone();
+                features.extend(b.features.iter().copied());
+                queries.push(MediaQuery { features });
+            }
+        }
+
+        MediaQueryList(queries)
+    }
+}
+
+impl<'i> Parse<'i> for MediaQueryList {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        Ok(MediaQueryList(input.parse_comma_separated(MediaQuery::parse)?))
+    }
+}
+
+/// A `@media` rule containing rules which only apply while the media query matches.
+#[derive(Debug, PartialEq, Clone)]
+pub struct MediaRule<'i> {
+    pub query: MediaQueryList,
+    pub rules: CssRuleList<'i>,
+    pub loc: Location,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> MediaQueryList {
+        let mut parser_input = ParserInput::new(input);
+        let mut parser = Parser::new(&mut parser_input);
+        MediaQueryList::parse(&mut parser).unwrap()
+    }
+
+    #[test]
+    fn parse_media_query() {
+        let query = parse("screen and (min-width: 400px) and (max-width: 600px)");
+        assert_eq!(
+            query,
+            MediaQueryList(vec![MediaQuery {
+                features: vec![MediaFeature::MinWidth(400.0), MediaFeature::MaxWidth(600.0)]
+            }])
+        );
+
+        assert!(query.matches(500.0, 300.0, ColorScheme::Light));
+        assert!(!query.matches(700.0, 300.0, ColorScheme::Light));
+    }
+
+    #[test]
+    fn parse_media_query_list() {
+        let query = parse("(prefers-color-scheme: dark), (max-height: 200px)");
+        assert!(query.matches(800.0, 600.0, ColorScheme::Dark));
+        assert!(query.matches(800.0, 100.0, ColorScheme::Light));
+        assert!(!query.matches(800.0, 600.0, ColorScheme::Light));
+    }
+}
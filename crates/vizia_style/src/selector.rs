@@ -86,6 +86,14 @@ impl<'i> selectors::parser::Parser<'i> for SelectorParser<'_, 'i> {
     type Impl = Selectors;
     type Error = CustomParseError<'i>;
 
+    fn parse_is_and_where(&self) -> bool {
+        true
+    }
+
+    fn parse_has(&self) -> bool {
+        true
+    }
+
     fn parse_non_ts_pseudo_class(
         &self,
         _: SourceLocation,
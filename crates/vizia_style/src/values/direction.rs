@@ -1,12 +1,14 @@
 use crate::{macros::define_enum, Parse};
 
 define_enum! {
+    /// Determines the direction of text and of the horizontal layout of an entity and its descendants.
     #[derive(Default)]
     pub enum Direction {
-        /// The entity will be rendered and acted on by the layout system.
+        /// Text is laid out left-to-right and children are positioned from the left.
         #[default]
         "ltr": Ltr,
-        /// The entity will not be rendered and acted on by the layout system.
+        /// Text is laid out right-to-left and the horizontal positions of children are mirrored, so rows are ordered
+        /// from the right and the left and right spacing of children is swapped.
         "rtl": Rtl,
     }
 }
@@ -1,6 +1,33 @@
 use crate::{CustomParseError, Parse};
 use cssparser::*;
 
+/// The physical parameters of a spring used to animate a property.
+///
+/// A stiffer spring moves faster, while more damping reduces how far the spring overshoots and oscillates
+/// around its target.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spring {
+    /// The stiffness of the spring.
+    pub stiffness: f32,
+    /// The damping applied to the motion of the spring.
+    pub damping: f32,
+    /// The mass attached to the spring.
+    pub mass: f32,
+}
+
+impl Spring {
+    /// Creates a new spring with the given stiffness, damping and mass.
+    pub fn new(stiffness: f32, damping: f32, mass: f32) -> Self {
+        Self { stiffness, damping, mass }
+    }
+}
+
+impl Default for Spring {
+    fn default() -> Self {
+        Self::new(170.0, 26.0, 1.0)
+    }
+}
+
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub enum EasingFunction {
     #[default]
@@ -10,6 +37,7 @@ pub enum EasingFunction {
     EaseOut,
     EaseInOut,
     CubicBezier(f32, f32, f32, f32),
+    Spring(Spring),
     // TODO: Steps
 }
 
@@ -44,6 +72,18 @@ impl<'i> Parse<'i> for EasingFunction {
                 let y2 = input.try_parse(|input| input.expect_number())?;
                 Ok(EasingFunction::CubicBezier(x1, y1, x2, y2))
               },
+              "spring" => {
+                let stiffness = input.expect_number()?;
+                input.expect_comma()?;
+                let damping = input.expect_number()?;
+                let mass = input
+                    .try_parse(|input| {
+                        input.expect_comma()?;
+                        input.expect_number()
+                    })
+                    .unwrap_or(1.0);
+                Ok(EasingFunction::Spring(Spring::new(stiffness, damping, mass)))
+              },
             //   "steps" => {
             //     let count = CSSInteger::parse(input)?;
             //     let position = input.try_parse(|input| {
@@ -57,3 +97,25 @@ impl<'i> Parse<'i> for EasingFunction {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::assert_parse;
+
+    assert_parse! {
+        EasingFunction, parse_spring,
+
+        custom {
+            success {
+                "spring(170, 26)" => EasingFunction::Spring(Spring::new(170.0, 26.0, 1.0)),
+                "spring(300, 10, 2)" => EasingFunction::Spring(Spring::new(300.0, 10.0, 2.0)),
+            }
+
+            failure {
+                "spring(170)",
+                "spring(170, 26, 1, 4)",
+            }
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use crate::{define_enum, Parse};
+
+define_enum! {
+    /// Determines whether the children of a stack are forced onto a single line or can wrap onto multiple lines.
+    #[derive(Default)]
+    pub enum FlexWrap {
+        /// The children are laid out in a single line which may overflow the parent.
+        #[default]
+        "nowrap": NoWrap,
+        /// The children wrap onto additional lines when they exceed the main-axis size of the parent.
+        "wrap": Wrap,
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{impl_parse, traits::Parse, CustomParseError};
+
+/// The placement of a view within the columns or rows of a grid.
+///
+/// Parsed from `<start>`, `<start> / span <count>` or `<start> / <end>`, where, as in CSS, `start` and `end` are
+/// the numbers of the grid lines before and after the tracks occupied by the view, starting from one for the line
+/// before the first track. The line numbers are converted to the zero-based track indices of the `column-start` and
+/// `row-start` properties when the grid is laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridPlacement {
+    /// The number of the grid line before the first track occupied by the view, starting from one.
+    pub start: usize,
+    /// The number of tracks occupied by the view.
+    pub span: usize,
+}
+
+impl GridPlacement {
+    /// Creates a new grid placement from the number of the line before the first track, starting from one, and the
+    /// number of tracks.
+    pub fn new(start: usize, span: usize) -> Self {
+        Self { start, span }
+    }
+
+    /// Returns the zero-based index of the first track occupied by the view.
+    pub fn start_index(&self) -> usize {
+        self.start.saturating_sub(1)
+    }
+}
+
+impl Default for GridPlacement {
+    fn default() -> Self {
+        Self { start: 1, span: 1 }
+    }
+}
+
+impl_parse! {
+    GridPlacement,
+
+    custom {
+        |input| {
+            let location = input.current_source_location();
+            let start = usize::parse(input)?;
+            if start == 0 {
+                return Err(location.new_custom_error(CustomParseError::InvalidValue));
+            }
+
+            if input.try_parse(|input| input.expect_delim('/')).is_err() {
+                return Ok(GridPlacement::new(start, 1));
+            }
+
+            if input.try_parse(|input| input.expect_ident_matching("span")).is_ok() {
+                let location = input.current_source_location();
+                let span = usize::parse(input)?;
+                if span == 0 {
+                    return Err(location.new_custom_error(CustomParseError::InvalidValue));
+                }
+
+                return Ok(GridPlacement::new(start, span));
+            }
+
+            let location = input.current_source_location();
+            let end = usize::parse(input)?;
+            if end <= start {
+                return Err(location.new_custom_error(CustomParseError::InvalidValue));
+            }
+
+            Ok(GridPlacement::new(start, end - start))
+        }
+    }
+}
+
+impl From<usize> for GridPlacement {
+    fn from(start: usize) -> Self {
+        GridPlacement::new(start, 1)
+    }
+}
+
+impl From<(usize, usize)> for GridPlacement {
+    fn from(value: (usize, usize)) -> Self {
+        GridPlacement::new(value.0, value.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::assert_parse;
+
+    assert_parse! {
+        GridPlacement, parse_grid_placement,
+
+        success {
+            "2" => GridPlacement::new(2, 1),
+            "1 / span 3" => GridPlacement::new(1, 3),
+            "2 / span 1" => GridPlacement::new(2, 1),
+            "1 / 3" => GridPlacement::new(1, 2),
+            "2 / 5" => GridPlacement::new(2, 3),
+        }
+
+        failure {
+            "test",
+            "0",
+            "0 / 2",
+            "1 / span 0",
+            "2 / 1",
+            "2 / 2",
+            "1 / span",
+            "span 2",
+        }
+    }
+
+    #[test]
+    fn start_index() {
+        assert_eq!(GridPlacement::new(1, 2).start_index(), 0);
+        assert_eq!(GridPlacement::new(3, 1).start_index(), 2);
+    }
+}
@@ -16,6 +16,7 @@ pub mod direction;
 pub mod display;
 pub mod duration;
 pub mod easing;
+pub mod flex_wrap;
 pub mod font_family;
 pub mod font_size;
 pub mod font_size_keyword;
@@ -25,6 +26,7 @@ pub mod font_weight;
 pub mod font_weight_keyword;
 pub mod font_width;
 pub mod gradient;
+pub mod grid_placement;
 pub mod horizontal_position_keyword;
 pub mod image;
 pub mod keywords;
@@ -43,11 +45,13 @@ pub mod position_type;
 pub mod rect;
 pub mod rotate;
 pub mod scale;
+pub mod self_alignment;
 pub mod shadow;
 pub mod stretch;
 pub mod text_align;
 pub mod text_decoration;
 pub mod text_overflow;
+pub mod text_shadow;
 pub mod text_stroke;
 pub mod transform;
 pub mod transition;
@@ -75,6 +79,7 @@ pub use direction::*;
 pub use display::*;
 pub use duration::*;
 pub use easing::*;
+pub use flex_wrap::*;
 pub use font_family::*;
 pub use font_size::*;
 pub use font_size_keyword::*;
@@ -84,6 +89,7 @@ pub use font_weight::*;
 pub use font_weight_keyword::*;
 pub use font_width::*;
 pub use gradient::*;
+pub use grid_placement::*;
 pub use horizontal_position_keyword::*;
 pub use image::*;
 pub use keywords::*;
@@ -102,11 +108,13 @@ pub use position_type::*;
 pub use rect::*;
 pub use rotate::*;
 pub use scale::*;
+pub use self_alignment::*;
 pub use shadow::*;
 pub use stretch::*;
 pub use text_align::*;
 pub use text_decoration::*;
 pub use text_overflow::*;
+pub use text_shadow::*;
 pub use text_stroke::*;
 pub use transform::*;
 pub use transition::*;
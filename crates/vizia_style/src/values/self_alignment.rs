@@ -0,0 +1,20 @@
+use crate::{define_enum, Parse};
+
+define_enum! {
+    /// Determines how a single child is aligned within its parent, overriding the `alignment` of the parent along one axis.
+    #[derive(Default)]
+    pub enum SelfAlignment {
+        /// The child is positioned by the `alignment` of its parent.
+        #[default]
+        "auto": Auto,
+        /// The child is aligned to the start of the axis.
+        "start": Start,
+        /// The child is centered along the axis.
+        "center": Center,
+        /// The child is aligned to the end of the axis.
+        "end": End,
+        /// The child is positioned so that the baseline of its text lines up with the baselines of any siblings
+        /// also aligned to the baseline. Only applies to the cross axis of a row, otherwise behaves as `auto`.
+        "baseline": Baseline,
+    }
+}
@@ -2,6 +2,9 @@ use crate::{Color, CustomParseError, InsetKeyword, Length, Parse};
 use cssparser::{ParseError, Parser, ParserInput};
 
 /// A box shadow adding a shadow effect around an element's frame.
+///
+/// Parsed from the CSS `box-shadow` syntax, where the offsets, optional blur and spread radii, color and `inset`
+/// keyword can be given in any order, such as `inset 0px 2px 4px red`.
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct Shadow {
     /// The horizontal offset of the box shadow.
@@ -12,7 +15,7 @@ pub struct Shadow {
     pub blur_radius: Option<Length>,
     /// The spread radius used for expanding and growing the box shadow.
     pub spread_radius: Option<Length>,
-    /// The color of the box shadow.
+    /// The color of the box shadow, which defaults to the font color of the element.
     pub color: Option<Color>,
     /// Determines if the box shadow should be an outer shadow (outset) or an inner shadow (inset).
     pub inset: bool,
@@ -41,12 +44,40 @@ impl Shadow {
 
 impl<'i> Parse<'i> for Shadow {
     fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
-        let x_offset = Length::parse(input)?;
-        let y_offset = Length::parse(input)?;
-        let blur_radius = input.try_parse(Length::parse).ok();
-        let spread_radius = input.try_parse(Length::parse).ok();
-        let color = input.try_parse(Color::parse).ok();
-        let inset = input.try_parse(InsetKeyword::parse).map(|_| true).unwrap_or(false);
+        let mut lengths = None;
+        let mut color = None;
+        let mut inset = false;
+
+        loop {
+            if !inset && input.try_parse(InsetKeyword::parse).is_ok() {
+                inset = true;
+                continue;
+            }
+
+            if lengths.is_none() {
+                if let Ok(x_offset) = input.try_parse(Length::parse) {
+                    let y_offset = Length::parse(input)?;
+                    let blur_radius = input.try_parse(Length::parse).ok();
+                    let spread_radius =
+                        blur_radius.as_ref().and_then(|_| input.try_parse(Length::parse).ok());
+                    lengths = Some((x_offset, y_offset, blur_radius, spread_radius));
+                    continue;
+                }
+            }
+
+            if color.is_none() {
+                if let Ok(value) = input.try_parse(Color::parse) {
+                    color = Some(value);
+                    continue;
+                }
+            }
+
+            break;
+        }
+
+        let Some((x_offset, y_offset, blur_radius, spread_radius)) = lengths else {
+            return Err(input.new_custom_error(CustomParseError::InvalidValue));
+        };
 
         Ok(Shadow::new(x_offset, y_offset, blur_radius, spread_radius, color, inset))
     }
@@ -54,6 +85,10 @@ impl<'i> Parse<'i> for Shadow {
 
 impl<'i> Parse<'i> for Vec<Shadow> {
     fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        if input.try_parse(|input| input.expect_ident_matching("none")).is_ok() {
+            return Ok(Vec::new());
+        }
+
         input.parse_comma_separated(Shadow::parse)
     }
 }
@@ -92,11 +127,20 @@ mod tests {
                     Some(Color::rgb(255, 0, 0)),
                     true,
                 ),
+                "inset red 10px 20px 30px" => Shadow::new(
+                    Length::px(10.0),
+                    Length::px(20.0),
+                    Some(Length::px(30.0)),
+                    None,
+                    Some(Color::rgb(255, 0, 0)),
+                    true,
+                ),
             }
 
             failure {
                 "test",
                 "123",
+                "red inset",
             }
         }
     }
@@ -124,6 +168,7 @@ mod tests {
                         true,
                     ),
                 ],
+                "none" => vec![],
             }
 
             failure {
@@ -0,0 +1,137 @@
+use crate::{Color, CustomParseError, Length, Parse};
+use cssparser::{ParseError, Parser, ParserInput};
+
+/// A text shadow drawn behind the glyphs of a text.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TextShadow {
+    /// The horizontal offset of the text shadow.
+    pub x_offset: Length,
+    /// The vertical offset of the text shadow.
+    pub y_offset: Length,
+    /// The blur radius of the text shadow.
+    pub blur_radius: Option<Length>,
+    /// The color of the text shadow, which defaults to the font color.
+    pub color: Option<Color>,
+}
+
+impl TextShadow {
+    /// Creates a new text shadow.
+    pub fn new(
+        x_offset: impl Into<Length>,
+        y_offset: impl Into<Length>,
+        blur_radius: Option<Length>,
+        color: Option<Color>,
+    ) -> Self {
+        Self { x_offset: x_offset.into(), y_offset: y_offset.into(), blur_radius, color }
+    }
+}
+
+impl<'i> Parse<'i> for TextShadow {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        // The color can be given either before or after the lengths.
+        let mut color = input.try_parse(Color::parse).ok();
+        let x_offset = Length::parse(input)?;
+        let y_offset = Length::parse(input)?;
+        let blur_radius = input.try_parse(Length::parse).ok();
+        if color.is_none() {
+            color = input.try_parse(Color::parse).ok();
+        }
+
+        Ok(TextShadow::new(x_offset, y_offset, blur_radius, color))
+    }
+}
+
+impl<'i> Parse<'i> for Vec<TextShadow> {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        input.parse_comma_separated(TextShadow::parse)
+    }
+}
+
+impl From<&str> for TextShadow {
+    fn from(s: &str) -> Self {
+        let mut input = ParserInput::new(s);
+        let mut parser = Parser::new(&mut input);
+        TextShadow::parse(&mut parser).unwrap_or_default()
+    }
+}
+
+impl From<TextShadow> for Vec<TextShadow> {
+    fn from(value: TextShadow) -> Self {
+        vec![value]
+    }
+}
+
+impl From<&str> for Vec<TextShadow> {
+    fn from(s: &str) -> Self {
+        let mut input = ParserInput::new(s);
+        let mut parser = Parser::new(&mut input);
+        Vec::<TextShadow>::parse(&mut parser).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::assert_parse;
+
+    assert_parse! {
+        TextShadow, parse_text_shadow,
+
+        custom {
+            success {
+                "1px 2px" => TextShadow::new(
+                    Length::px(1.0),
+                    Length::px(2.0),
+                    None,
+                    None,
+                ),
+                "1px 2px 3px red" => TextShadow::new(
+                    Length::px(1.0),
+                    Length::px(2.0),
+                    Some(Length::px(3.0)),
+                    Some(Color::rgb(255, 0, 0)),
+                ),
+                "red 1px 2px" => TextShadow::new(
+                    Length::px(1.0),
+                    Length::px(2.0),
+                    None,
+                    Some(Color::rgb(255, 0, 0)),
+                ),
+            }
+
+            failure {
+                "test",
+                "123",
+                "1px",
+            }
+        }
+    }
+
+    assert_parse! {
+        Vec<TextShadow>, parse_vec_text_shadow,
+
+        custom {
+            success {
+                "1px 2px, 1px 2px 3px red" => vec![
+                    TextShadow::new(
+                        Length::px(1.0),
+                        Length::px(2.0),
+                        None,
+                        None,
+                    ),
+                    TextShadow::new(
+                        Length::px(1.0),
+                        Length::px(2.0),
+                        Some(Length::px(3.0)),
+                        Some(Color::rgb(255, 0, 0)),
+                    ),
+                ],
+            }
+
+            failure {
+                "1px, 2px",
+                "test",
+            }
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use crate::{Angle, CustomParseError, LengthOrPercentage, Matrix, Parse, PercentageOrNumber};
+use crate::{
+    Angle, CustomParseError, Length, LengthOrPercentage, Matrix, Parse, PercentageOrNumber,
+};
 use cssparser::{match_ignore_ascii_case, ParseError, Parser, Token};
 
 /// An individual transform function.
@@ -44,8 +46,12 @@ impl<'i> Parse<'i> for Transform {
             match_ignore_ascii_case! { &function,
                 "translate" => {
                     let x = LengthOrPercentage::parse(input)?;
-                    input.expect_comma()?;
-                    let y = LengthOrPercentage::parse(input)?;
+                    // A translation with one value only moves along the x axis.
+                    let y = if input.try_parse(|input| input.expect_comma()).is_ok() {
+                        LengthOrPercentage::parse(input)?
+                    } else {
+                        LengthOrPercentage::Length(Length::px(0.0))
+                    };
                     Ok(Transform::Translate((x, y)))
                 },
                 "translatex" => {
@@ -58,8 +64,12 @@ impl<'i> Parse<'i> for Transform {
                 },
                 "scale" => {
                     let x = PercentageOrNumber::parse(input)?;
-                    input.expect_comma()?;
-                    let y = PercentageOrNumber::parse(input)?;
+                    // A scale with one value scales both axes uniformly.
+                    let y = if input.try_parse(|input| input.expect_comma()).is_ok() {
+                        PercentageOrNumber::parse(input)?
+                    } else {
+                        x
+                    };
                     Ok(Transform::Scale((x, y)))
                 },
                 "scalex" => {
@@ -84,8 +94,12 @@ impl<'i> Parse<'i> for Transform {
                 // },
                 "skew" => {
                     let x = Angle::parse(input)?;
-                    input.expect_comma()?;
-                    let y = Angle::parse(input)?;
+                    // A skew with one angle only skews along the x axis.
+                    let y = if input.try_parse(|input| input.expect_comma()).is_ok() {
+                        Angle::parse(input)?
+                    } else {
+                        Angle::Deg(0.0)
+                    };
                     Ok(Transform::Skew(x, y))
                 },
                 "skewx" => {
@@ -145,7 +159,7 @@ impl From<Transform> for Vec<Transform> {
 mod tests {
     use super::*;
     use crate::tests::assert_parse;
-    use crate::Length;
+    use crate::LengthValue;
 
     assert_parse! {
         Transform, assert_transform,
@@ -153,10 +167,12 @@ mod tests {
         custom {
             success {
                 "translate(10px, 50%)" => Transform::Translate((LengthOrPercentage::Length(Length::px(10.0)), LengthOrPercentage::Percentage(50.0))),
+                "translate(5in)" => Transform::Translate((LengthOrPercentage::Length(Length::Value(LengthValue::In(5.0))), LengthOrPercentage::Length(Length::px(0.0)))),
                 "translatex(20px)" => Transform::TranslateX(LengthOrPercentage::Length(Length::px(20.0))),
                 "translatey(10%)" => Transform::TranslateY(LengthOrPercentage::Percentage(10.0)),
 
                 "scale(20, 40%)" => Transform::Scale((PercentageOrNumber::Number(20.0), PercentageOrNumber::Percentage(40.0))),
+                "scale(30%)" => Transform::Scale((PercentageOrNumber::Percentage(30.0), PercentageOrNumber::Percentage(30.0))),
                 "scalex(40)" => Transform::ScaleX(PercentageOrNumber::Number(40.0)),
                 "scaley(50%)" => Transform::ScaleY(PercentageOrNumber::Percentage(50.0)),
 
@@ -165,6 +181,7 @@ mod tests {
                 // "rotatey(20turn)" => Transform::RotateY(Angle::Turn(20.0)),
 
                 "skew(60rad, 70turn)" => Transform::Skew(Angle::Rad(60.0), Angle::Turn(70.0)),
+                "skew(20deg)" => Transform::Skew(Angle::Deg(20.0), Angle::Deg(0.0)),
                 "skewx(90grad)" => Transform::SkewX(Angle::Grad(90.0)),
                 "skewy(120deg)" => Transform::SkewY(Angle::Deg(120.0)),
 
@@ -176,9 +193,9 @@ mod tests {
                 "somefunction(10px)",
                 "scalematrix(1, 2, 3, 4, 5, 6)",
                 "rotate(20)",
-                "scale(30%)",
+                "scale(30%, )",
                 "skewx(20px)",
-                "translate(5in)",
+                "translate(5in, 20deg)",
                 "abc",
             }
         }
@@ -197,6 +214,12 @@ mod tests {
                         Transform::Skew(Angle::Turn(60.0), Angle::Rad(70.0)),
                         Transform::Matrix(Matrix::new(10.0, 20.0, 30.0, 40.0, 50.0, 60.0)),
                     ],
+                "translate(10px, 20px) rotate(30deg) scale(1.2)" =>
+                    vec![
+                        Transform::Translate((LengthOrPercentage::Length(Length::px(10.0)), LengthOrPercentage::Length(Length::px(20.0)))),
+                        Transform::Rotate(Angle::Deg(30.0)),
+                        Transform::Scale((PercentageOrNumber::Number(1.2), PercentageOrNumber::Number(1.2))),
+                    ],
             }
 
             failure {
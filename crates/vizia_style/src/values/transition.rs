@@ -4,13 +4,13 @@ use cssparser::{ParseError, ParseErrorKind, Parser};
 /// Defines a transition that allows to change property values smoothly, over a given duration.
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct Transition {
-    /// A list of properties affected by transition.
+    /// The property affected by the transition, which is `all` to transition every animatable property.
     pub property: String,
     /// The duration of the transition.
     pub duration: Duration,
     /// The delay of the transition.
     pub delay: Option<Duration>,
-
+    /// The easing function of the transition.
     pub timing_function: Option<EasingFunction>,
 }
 
@@ -30,18 +30,54 @@ impl<'i> Parse<'i> for Transition {
     fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
         let location = input.current_source_location();
 
-        let property = Ident::parse(input)?.into();
-        let duration = Duration::parse(input)?;
-        let delay = input.try_parse(Duration::parse).ok();
-        let timing_function = input.try_parse(EasingFunction::parse).ok();
+        // The property can be omitted, in which case the transition applies to all animatable properties.
+        let property = input
+            .try_parse(|input| {
+                if input.try_parse(EasingFunction::parse).is_ok() {
+                    return Err(input.new_custom_error(CustomParseError::InvalidDeclaration));
+                }
+
+                Ident::parse(input)
+            })
+            .map(String::from)
+            .unwrap_or_else(|_| String::from("all"));
+
+        // The first time is the duration and the second is the delay, while the timing function can appear before,
+        // between or after them.
+        let mut duration = None;
+        let mut delay = None;
+        let mut timing_function = None;
+        loop {
+            if timing_function.is_none() {
+                if let Ok(easing) = input.try_parse(EasingFunction::parse) {
+                    timing_function = Some(easing);
+                    continue;
+                }
+            }
+
+            if delay.is_none() {
+                if let Ok(time) = input.try_parse(Duration::parse) {
+                    if duration.is_none() {
+                        duration = Some(time);
+                    } else {
+                        delay = Some(time);
+                    }
+                    continue;
+                }
+            }
+
+            break;
+        }
 
-        if input.is_exhausted() {
-            Ok(Self { property, duration, delay, timing_function })
-        } else {
-            Err(ParseError {
+        match duration {
+            Some(duration) if input.is_exhausted() => {
+                Ok(Self { property, duration, delay, timing_function })
+            }
+
+            _ => Err(ParseError {
                 kind: ParseErrorKind::Custom(CustomParseError::InvalidDeclaration),
                 location,
-            })
+            }),
         }
     }
 }
@@ -65,11 +101,18 @@ mod tests {
                 "width 2s" => Transition::new(String::from("width"), Duration::from_secs(2), None, None),
                 "height 2s 1s" => Transition::new(String::from("height"), Duration::from_secs(2), Some(Duration::from_secs(1)), None),
                 "color 200ms linear" => Transition::new(String::from("color"), Duration::from_millis(200), None, Some(EasingFunction::Linear)),
+                "background-color 200ms ease-in-out" => Transition::new(String::from("background-color"), Duration::from_millis(200), None, Some(EasingFunction::EaseInOut)),
+                "opacity 200ms ease-in 50ms" => Transition::new(String::from("opacity"), Duration::from_millis(200), Some(Duration::from_millis(50)), Some(EasingFunction::EaseIn)),
+                "transform ease 1s" => Transition::new(String::from("transform"), Duration::from_secs(1), None, Some(EasingFunction::Ease)),
+                "300ms" => Transition::new(String::from("all"), Duration::from_millis(300), None, None),
+                "ease-out 300ms" => Transition::new(String::from("all"), Duration::from_millis(300), None, Some(EasingFunction::EaseOut)),
             }
 
             failure {
                 "height 2s 1s 1s",
                 "1s 2s height",
+                "width",
+                "width 1s linear ease",
             }
         }
     }
@@ -11,6 +11,7 @@ define_enum! {
         #[default]
         "visible": Visible,
         /// The entity will not rendered, but will still be acted upon by the layout system.
+        /// A hidden entity does not receive pointer events.
         "hidden": Hidden,
     }
 }
@@ -124,6 +124,8 @@ pub struct WindowDescription {
     pub always_on_top: bool,
     pub vsync: bool,
     pub enabled_window_buttons: WindowButtons,
+    /// Whether the window should be resized to fit its content after the first layout pass.
+    pub fit_content: bool,
 
     // Change this to resource id when the resource manager is working
     pub icon: Option<Vec<u8>>,
@@ -153,6 +155,7 @@ impl Default for WindowDescription {
             always_on_top: false,
             vsync: true,
             enabled_window_buttons: WindowButtons::all(),
+            fit_content: false,
 
             icon: None,
             icon_width: 0,
@@ -216,6 +219,13 @@ impl WindowDescription {
         self
     }
 
+    /// Resize the window to fit its content after the first layout pass, respecting the minimum and maximum inner size.
+    pub fn with_fit_content(mut self, flag: bool) -> Self {
+        self.fit_content = flag;
+
+        self
+    }
+
     pub fn with_icon(mut self, icon: Vec<u8>, width: u32, height: u32) -> Self {
         self.icon = Some(icon);
         self.icon_width = width;
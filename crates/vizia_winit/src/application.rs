@@ -1,14 +1,17 @@
 #[cfg(target_os = "windows")]
 use crate::window::set_cloak;
 use crate::{
-    convert::{winit_key_code_to_code, winit_key_to_key},
-    window::{WinState, Window},
+    convert::{
+        device_id_to_device_id, physical_key_to_code, physical_key_to_scancode,
+        winit_key_code_to_code, winit_key_to_key,
+    },
+    window::{ImageCursors, WinState, Window},
     window_modifiers::WindowModifiers,
 };
 #[cfg(feature = "accesskit")]
 use accesskit_winit::Adapter;
 use hashbrown::HashMap;
-use std::{error::Error, fmt::Display, sync::Arc};
+use std::{cell::RefCell, error::Error, fmt::Display, rc::Rc, sync::Arc};
 
 // #[cfg(feature = "accesskit")]
 // use accesskit::{Action, NodeBuilder, NodeId, TreeUpdate};
@@ -22,7 +25,7 @@ use winit::{
     application::ApplicationHandler,
     dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize},
     error::EventLoopError,
-    event::ElementState,
+    event::{DeviceEvent, DeviceId, ElementState, RawKeyEvent},
     event_loop::{ActiveEventLoop, ControlFlow, EventLoop, EventLoopProxy},
     keyboard::{NativeKeyCode, PhysicalKey},
     window::{CursorIcon, CustomCursor, WindowAttributes, WindowId, WindowLevel},
@@ -93,6 +96,9 @@ impl std::error::Error for ApplicationError {}
 /// .run();
 ///```
 /// Calling `run()` on the `Application` causes the program to enter the event loop and for the main window to display.
+// The number of cursors created from images which are cached before the cache is cleared.
+const MAX_IMAGE_CURSORS: usize = 64;
+
 pub struct Application {
     cx: BackendContext,
     event_manager: EventManager,
@@ -103,6 +109,7 @@ pub struct Application {
     event_loop_proxy: EventLoopProxy<UserEvent>,
     windows: HashMap<WindowId, WinState>,
     window_ids: HashMap<Entity, WindowId>,
+    image_cursors: Rc<RefCell<ImageCursors>>,
     #[cfg(feature = "accesskit")]
     accesskit_adapter: Option<accesskit_winit::Adapter>,
     #[cfg(feature = "accesskit")]
@@ -151,6 +158,7 @@ impl Application {
             event_loop_proxy: proxy,
             windows: HashMap::new(),
             window_ids: HashMap::new(),
+            image_cursors: Default::default(),
             #[cfg(feature = "accesskit")]
             accesskit_adapter: None,
             #[cfg(feature = "accesskit")]
@@ -158,6 +166,44 @@ impl Application {
         }
     }
 
+    // Creates the cursors for the images set by the windows since the event loop was last idle and sets them.
+    fn create_image_cursors(&mut self, event_loop: &ActiveEventLoop) {
+        let mut image_cursors = self.image_cursors.borrow_mut();
+        for (entity, image) in std::mem::take(&mut image_cursors.pending) {
+            // Drawing tools can create a cursor for every brush size, so the cache is cleared rather than left to grow.
+            if !image_cursors.cursors.contains_key(&image)
+                && image_cursors.cursors.len() >= MAX_IMAGE_CURSORS
+            {
+                image_cursors.cursors.clear();
+            }
+
+            let custom_cursor = match image_cursors.cursors.get(&image) {
+                Some(custom_cursor) => custom_cursor.clone(),
+                None => {
+                    let (hotspot_x, hotspot_y) = image.hotspot();
+                    let Ok(source) = CustomCursor::from_rgba(
+                        image.rgba(),
+                        image.width(),
+                        image.height(),
+                        hotspot_x,
+                        hotspot_y,
+                    ) else {
+                        continue;
+                    };
+
+                    let custom_cursor = event_loop.create_custom_cursor(source);
+                    image_cursors.cursors.insert(image, custom_cursor.clone());
+                    custom_cursor
+                }
+            };
+
+            if let Some(window) = self.window_ids.get(&entity).and_then(|id| self.windows.get(id)) {
+                window.window().set_cursor(custom_cursor);
+                window.window().set_cursor_visible(true);
+            }
+        }
+    }
+
     fn create_window(
         &mut self,
         event_loop: &ActiveEventLoop,
@@ -271,6 +317,11 @@ impl Application {
             }
         }
 
+        // Transparent windows have the `transparent` class so that the theme clears their background color.
+        self.cx.0.with_current(window_entity, |cx| {
+            cx.toggle_class("transparent", window_description.transparent)
+        });
+
         let window_id = window_state.window.id();
         self.windows.insert(window_id, window_state);
         self.window_ids.insert(window_entity, window_id);
@@ -346,10 +397,20 @@ impl ApplicationHandler<UserEvent> for Application {
                     }
                     accesskit_winit::WindowEvent::ActionRequested(action_request) => {
                         let node_id = action_request.target;
+                        let entity = Entity::new(node_id.0, 0);
 
-                        if action_request.action != Action::ScrollIntoView {
-                            let entity = Entity::new(node_id.0, 0);
-
+                        if action_request.action == Action::ScrollIntoView {
+                            // Scroll the ancestor scrollviews of the view until it's visible
+                            self.cx.send_event(
+                                Event::new(ScrollEvent::ScrollTo { entity, animate: false })
+                                    .target(entity)
+                                    .origin(Entity::root())
+                                    .propagate(Propagation::Up),
+                            );
+                        } else if action_request.action == Action::Click {
+                            // Clicks from a screen reader are handled like any other press of the view
+                            self.cx.0.press(entity);
+                        } else {
                             // Handle focus action from screen reader
                             if action_request.action == Action::Focus {
                                 self.cx.0.with_current(entity, |cx| {
@@ -387,6 +448,7 @@ impl ApplicationHandler<UserEvent> for Application {
                 on_create: None,
                 should_close: false,
                 custom_cursors: custom_cursors.clone(),
+                image_cursors: self.image_cursors.clone(),
             });
 
             self.cx.0.windows.insert(
@@ -450,6 +512,7 @@ impl ApplicationHandler<UserEvent> for Application {
                 self.cx.mutate_window(window_entity, |cx, win: &mut Window| {
                     win.window = Some(window.clone());
                     win.custom_cursors = custom_cursors.clone();
+                    win.image_cursors = self.image_cursors.clone();
                     if let Some(callback) = &win.on_create {
                         (callback)(&mut EventContext::new_with_current(
                             cx.context(),
@@ -462,6 +525,36 @@ impl ApplicationHandler<UserEvent> for Application {
         }
     }
 
+    fn device_event(
+        &mut self,
+        _event_loop: &ActiveEventLoop,
+        device_id: DeviceId,
+        event: DeviceEvent,
+    ) {
+        // Some platforms report device events while the application is in the background.
+        if !self.cx.0.window_has_focus {
+            return;
+        }
+
+        match event {
+            DeviceEvent::MouseMotion { delta } => {
+                self.cx.emit_pointer_motion(delta.0 as f32, delta.1 as f32);
+                self.cx.emit_raw_mouse_delta(device_id_to_device_id(device_id), delta.0, delta.1);
+            }
+
+            DeviceEvent::Key(RawKeyEvent { physical_key, state }) => {
+                self.cx.emit_raw_key(
+                    device_id_to_device_id(device_id),
+                    physical_key_to_code(physical_key),
+                    physical_key_to_scancode(physical_key),
+                    state == ElementState::Pressed,
+                );
+            }
+
+            _ => {}
+        }
+    }
+
     fn window_event(
         &mut self,
         _event_loop: &ActiveEventLoop,
@@ -552,6 +645,12 @@ impl ApplicationHandler<UserEvent> for Application {
                     _ => None,
                 };
 
+                // Key repeats, along with the characters they input, are generated by the application when a custom
+                // repeat rate is set.
+                if event.repeat && self.cx.has_custom_key_repeat() {
+                    return;
+                }
+
                 if let winit::keyboard::Key::Character(character) = event.logical_key {
                     if event.state == ElementState::Pressed {
                         self.cx.emit_window_event(
@@ -580,7 +679,20 @@ impl ApplicationHandler<UserEvent> for Application {
 
                 window.window().request_redraw();
             }
-            winit::event::WindowEvent::Ime(_) => {}
+            winit::event::WindowEvent::Ime(ime) => {
+                let event = match ime {
+                    winit::event::Ime::Preedit(text, cursor) => {
+                        WindowEvent::ImePreedit(text, cursor)
+                    }
+                    winit::event::Ime::Commit(text) => WindowEvent::ImeCommit(text),
+                    // Clear any text left over from an unfinished composition.
+                    winit::event::Ime::Disabled => WindowEvent::ImePreedit(String::new(), None),
+                    winit::event::Ime::Enabled => return,
+                };
+
+                self.cx.emit_window_event(window.entity, event);
+                window.window().request_redraw();
+            }
             winit::event::WindowEvent::CursorMoved { device_id: _, position } => {
                 self.cx.emit_window_event(
                     window.entity,
@@ -612,6 +724,10 @@ impl ApplicationHandler<UserEvent> for Application {
                 self.cx.emit_window_event(window.entity, out_event);
                 window.window().request_redraw();
             }
+            winit::event::WindowEvent::PinchGesture { device_id: _, delta, phase: _ } => {
+                self.cx.emit_window_event(window.entity, WindowEvent::PinchGesture(delta as f32));
+                window.window().request_redraw();
+            }
             winit::event::WindowEvent::MouseInput { device_id: _, state, button } => {
                 let button = match button {
                     winit::event::MouseButton::Left => MouseButton::Left,
@@ -635,8 +751,14 @@ impl ApplicationHandler<UserEvent> for Application {
                 scale_factor,
                 inner_size_writer: _,
             } => {
-                self.cx.set_scale_factor(scale_factor);
+                self.cx.set_scale_factor(window.entity, scale_factor);
+
+                // The logical size of the window depends on the scale factor, and a resize may not follow if the
+                // physical size of the window is unchanged.
+                let size = window.window().inner_size();
+                self.cx.set_window_size(window.entity, size.width as f32, size.height as f32);
                 self.cx.needs_refresh(window.entity);
+                window.window().request_redraw();
             }
             winit::event::WindowEvent::ThemeChanged(theme) => {
                 let theme = match theme {
@@ -654,6 +776,14 @@ impl ApplicationHandler<UserEvent> for Application {
                         window.swap_buffers();
                     }
 
+                    // Place the candidate window of an input method next to the text cursor.
+                    if let Some(area) = self.cx.take_ime_cursor_area(window.entity) {
+                        window.window().set_ime_cursor_area(
+                            PhysicalPosition::new(area.x, area.y),
+                            PhysicalSize::new(area.w, area.h),
+                        );
+                    }
+
                     // Un-cloak
                     #[cfg(target_os = "windows")]
                     if window.is_initially_cloaked {
@@ -677,9 +807,12 @@ impl ApplicationHandler<UserEvent> for Application {
 
         self.event_manager.flush_events(self.cx.context(), |_| {});
 
+        self.create_image_cursors(event_loop);
+
         self.cx.process_style_updates();
 
-        if self.cx.process_animations() {
+        let animating = self.cx.process_animations();
+        if animating {
             for window in self.windows.values() {
                 window.window().request_redraw();
             }
@@ -708,13 +841,17 @@ impl ApplicationHandler<UserEvent> for Application {
             (idle_callback)(self.cx.context());
         }
 
+        self.cx.process_idle_callbacks(animating);
+
         if self.cx.has_queued_events() {
             self.event_loop_proxy
                 .send_event(UserEvent::Event(Event::new(())))
                 .expect("Failed to send event");
         }
 
-        if self.cx.0.windows.iter().any(|(_, window_state)| !window_state.redraw_list.is_empty()) {
+        if self.cx.0.windows.iter().any(|(_, window_state)| {
+            !window_state.redraw_list.is_empty() || window_state.dirty_rect.is_some()
+        }) {
             for window in self.windows.values() {
                 window.window().request_redraw();
             }
@@ -774,8 +911,10 @@ impl ApplicationHandler<UserEvent> for Application {
                         }
                     });
 
+                    let image_cursors = self.image_cursors.clone();
                     self.cx.mutate_window(*window_entity, |cx, win: &mut Window| {
                         win.window = Some(window.clone());
+                        win.image_cursors = image_cursors.clone();
                         if let Some(callback) = &win.on_create {
                             (callback)(&mut EventContext::new_with_current(
                                 cx.context(),
@@ -797,7 +936,9 @@ impl ApplicationHandler<UserEvent> for Application {
         self.cx.emit_scheduled_events();
     }
 
-    fn exiting(&mut self, _event_loop: &ActiveEventLoop) {}
+    fn exiting(&mut self, _event_loop: &ActiveEventLoop) {
+        self.cx.on_exit();
+    }
 }
 
 impl WindowModifiers for Application {
@@ -936,6 +1077,12 @@ impl WindowModifiers for Application {
         self
     }
 
+    fn fit_content(mut self, flag: bool) -> Self {
+        self.window_description.fit_content = flag;
+
+        self
+    }
+
     fn icon(mut self, width: u32, height: u32, image: Vec<u8>) -> Self {
         self.window_description.icon = Some(image);
         self.window_description.icon_width = width;
@@ -1,10 +1,66 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use vizia_core::prelude::CursorGrabMode as ViziaCursorGrabMode;
 use vizia_core::prelude::CursorIcon as ViziaCursorIcon;
+use vizia_core::prelude::DeviceId as ViziaDeviceId;
 use vizia_input::Code as ViziaCode;
 use vizia_input::Key as ViziaKey;
+use winit::event::DeviceId as WinitDeviceId;
 use winit::keyboard::KeyCode;
 use winit::keyboard::NamedKey;
+use winit::keyboard::PhysicalKey;
+use winit::window::CursorGrabMode as WinitCursorGrabMode;
 use winit::window::CursorIcon as WinitCursorIcon;
 
+pub fn cursor_grab_mode_to_cursor_grab_mode(mode: ViziaCursorGrabMode) -> WinitCursorGrabMode {
+    match mode {
+        ViziaCursorGrabMode::None => WinitCursorGrabMode::None,
+        ViziaCursorGrabMode::Confined => WinitCursorGrabMode::Confined,
+        ViziaCursorGrabMode::Locked => WinitCursorGrabMode::Locked,
+    }
+}
+
+pub fn device_id_to_device_id(device_id: WinitDeviceId) -> ViziaDeviceId {
+    let mut hasher = DefaultHasher::new();
+    device_id.hash(&mut hasher);
+    ViziaDeviceId::new(hasher.finish())
+}
+
+pub fn physical_key_to_code(physical_key: PhysicalKey) -> ViziaCode {
+    match physical_key {
+        PhysicalKey::Code(code) => winit_key_code_to_code(code),
+        PhysicalKey::Unidentified(_) => ViziaCode::Unidentified,
+    }
+}
+
+#[cfg(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+pub fn physical_key_to_scancode(physical_key: PhysicalKey) -> Option<u32> {
+    use winit::platform::scancode::PhysicalKeyExtScancode;
+
+    physical_key.to_scancode()
+}
+
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+)))]
+pub fn physical_key_to_scancode(_physical_key: PhysicalKey) -> Option<u32> {
+    None
+}
+
 pub fn cursor_icon_to_cursor_icon(cursor_icon: ViziaCursorIcon) -> Option<WinitCursorIcon> {
     match cursor_icon {
         ViziaCursorIcon::Default => Some(WinitCursorIcon::Default),
@@ -0,0 +1,125 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use skia_safe::{surfaces, EncodedImageFormat};
+use vizia_core::prelude::*;
+
+// Counts the payloads written to temporary files so that concurrent drags don't overwrite each other.
+static NEXT_PAYLOAD: AtomicUsize = AtomicUsize::new(0);
+
+/// Starts a native drag of the payload out of the window, sending a [`WindowEvent::ExternalDragEnded`] event to the
+/// origin when the drag ends.
+pub(crate) fn start_external_drag(
+    cx: &mut EventContext,
+    window: &winit::window::Window,
+    origin: Entity,
+    payload: &DragPayload,
+) {
+    let Ok((item, temp_files)) = drag_item(payload) else {
+        cx.emit_to(origin, WindowEvent::ExternalDragEnded(false));
+        return;
+    };
+
+    // The drop callback can be called from another thread, so the proxy is shared behind a lock.
+    let proxy = Mutex::new(cx.get_proxy());
+    let result = drag::start_drag(
+        window,
+        item,
+        drag::Image::Raw(preview_image()),
+        move |result, _| {
+            let dropped = matches!(result, drag::DragResult::Dropped);
+            // A dropped file may still be read by the drop target, so it's removed when the next drag starts.
+            if !dropped {
+                remove_files(&temp_files);
+            }
+
+            if let Ok(mut proxy) = proxy.lock() {
+                proxy.emit_to(origin, WindowEvent::ExternalDragEnded(dropped)).ok();
+            }
+        },
+        drag::Options::default(),
+    );
+
+    if result.is_err() {
+        cx.emit_to(origin, WindowEvent::ExternalDragEnded(false));
+    }
+}
+
+// Returns the item to drag for the payload and any temporary files written for it.
+//
+// Text is offered as text where the platform supports dragging data. Otherwise text and bytes are written to temporary
+// files, since file managers only accept files.
+fn drag_item(payload: &DragPayload) -> std::io::Result<(drag::DragItem, Vec<PathBuf>)> {
+    let (extension, data) = match payload {
+        DragPayload::Files(paths) => return Ok((drag::DragItem::Files(paths.clone()), Vec::new())),
+        #[cfg(target_os = "macos")]
+        DragPayload::Text(text) => {
+            let text = text.clone();
+            let item = drag::DragItem::Data {
+                provider: Box::new(move |_| Some(text.clone().into_bytes())),
+                types: vec![String::from("public.utf8-plain-text")],
+            };
+            return Ok((item, Vec::new()));
+        }
+        #[cfg(not(target_os = "macos"))]
+        DragPayload::Text(text) => ("txt", text.as_bytes()),
+        DragPayload::Bytes { mime, data } => (mime_extension(mime), data.as_slice()),
+    };
+
+    let dir = std::env::temp_dir().join("vizia-drag");
+    std::fs::create_dir_all(&dir)?;
+    remove_previous_files(&dir);
+
+    let index = NEXT_PAYLOAD.fetch_add(1, Ordering::Relaxed);
+    let path = dir.join(format!("{}-{}.{}", std::process::id(), index, extension));
+    std::fs::write(&path, data)?;
+
+    Ok((drag::DragItem::Files(vec![path.clone()]), vec![path]))
+}
+
+// Removes the temporary files written by this process for earlier drags.
+fn remove_previous_files(dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    let prefix = format!("{}-", std::process::id());
+    let files = entries
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(&prefix))
+        .map(|entry| entry.path())
+        .collect::<Vec<_>>();
+    remove_files(&files);
+}
+
+fn remove_files(files: &[PathBuf]) {
+    for file in files {
+        std::fs::remove_file(file).ok();
+    }
+}
+
+// Returns the file extension for common MIME types.
+fn mime_extension(mime: &str) -> &'static str {
+    match mime.split(';').next().unwrap_or_default().trim() {
+        "text/plain" => "txt",
+        "text/html" => "html",
+        "text/csv" => "csv",
+        "application/json" => "json",
+        "application/pdf" => "pdf",
+        "image/png" => "png",
+        "image/jpeg" => "jpg",
+        "image/gif" => "gif",
+        "image/svg+xml" => "svg",
+        "image/webp" => "webp",
+        _ => "bin",
+    }
+}
+
+// A transparent image shown under the cursor while dragging, which leaves the platform to show its own indicator.
+fn preview_image() -> Vec<u8> {
+    surfaces::raster_n32_premul((1, 1))
+        .and_then(|mut surface| surface.image_snapshot().encode(None, EncodedImageFormat::PNG, 100))
+        .map(|data| data.as_bytes().to_vec())
+        .unwrap_or_default()
+}
@@ -1,5 +1,7 @@
 pub mod application;
 mod convert;
+#[cfg(all(feature = "drag-out", any(target_os = "windows", target_os = "macos")))]
+mod external_drag;
 pub mod window;
 pub mod window_modifiers;
 
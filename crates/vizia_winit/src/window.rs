@@ -7,10 +7,12 @@ use winit::platform::windows::WindowExtWindows;
 #[cfg(target_os = "windows")]
 use winit::{platform::windows::WindowAttributesExtWindows, raw_window_handle::RawWindowHandle};
 
-use crate::convert::cursor_icon_to_cursor_icon;
+use crate::convert::{cursor_grab_mode_to_cursor_grab_mode, cursor_icon_to_cursor_icon};
 use hashbrown::HashMap;
+use std::cell::RefCell;
 use std::error::Error;
 use std::num::NonZeroU32;
+use std::rc::Rc;
 use std::{ffi::CString, sync::Arc};
 use winit::raw_window_handle::HasWindowHandle;
 
@@ -354,12 +356,21 @@ pub fn create_surface(
 
 type WindowCallback = Option<Box<dyn Fn(&mut EventContext)>>;
 
+// The cursors created from images, shared by the application and its windows. Creating a cursor requires the event
+// loop, so the windows queue the images which aren't cached yet and the application creates them when it's next idle.
+#[derive(Default)]
+pub(crate) struct ImageCursors {
+    pub(crate) cursors: HashMap<CursorImage, CustomCursor>,
+    pub(crate) pending: Vec<(Entity, CursorImage)>,
+}
+
 pub struct Window {
     pub window: Option<Arc<winit::window::Window>>,
     pub on_close: WindowCallback,
     pub on_create: WindowCallback,
     pub should_close: bool,
     pub(crate) custom_cursors: Arc<HashMap<CursorIcon, CustomCursor>>,
+    pub(crate) image_cursors: Rc<RefCell<ImageCursors>>,
 }
 
 impl Window {
@@ -374,6 +385,7 @@ impl Window {
             on_create: None,
             should_close: false,
             custom_cursors: Default::default(),
+            image_cursors: Default::default(),
         }
         .build(cx, |cx| {
             cx.windows.insert(
@@ -389,12 +401,13 @@ impl Window {
         is_modal: bool,
         content: impl 'static + Fn(&mut Context),
     ) -> Handle<Self> {
-        Self {
+        let handle = Self {
             window: None,
             on_close: None,
             on_create: None,
             should_close: false,
             custom_cursors: Default::default(),
+            image_cursors: Default::default(),
         }
         .build(cx, |cx| {
             let parent_window = cx.parent_window();
@@ -406,15 +419,21 @@ impl Window {
                 cx.current(),
                 WindowState {
                     owner: Some(parent_window),
-                    is_modal: true,
+                    is_modal,
                     content: Some(Arc::new(content)),
                     ..Default::default()
                 },
             );
             cx.tree.set_window(cx.current(), true);
         })
-        .anchor_target(AnchorTarget::Window)
-        .lock_focus_to_within()
+        .anchor_target(AnchorTarget::Window);
+
+        // Only modal windows trap keyboard focus, as the rest of the application can't be used while they're open.
+        if is_modal {
+            handle.lock_focus_to_within()
+        } else {
+            handle
+        }
     }
 }
 
@@ -431,19 +450,46 @@ impl View for Window {
             }
 
             WindowEvent::GrabCursor(flag) => {
-                let grab_mode = if *flag { CursorGrabMode::Locked } else { CursorGrabMode::None };
-                self.window().set_cursor_grab(grab_mode).expect("Failed to set cursor grab");
+                if !*flag {
+                    if let Err(err) = self.window().set_cursor_grab(CursorGrabMode::None) {
+                        log::warn!("Failed to release cursor grab: {}", err);
+                    }
+                } else if let Err(err) = self
+                    .window()
+                    .set_cursor_grab(CursorGrabMode::Confined)
+                    .or_else(|_| self.window().set_cursor_grab(CursorGrabMode::Locked))
+                {
+                    // Platforms support either confining or locking the cursor, so only both failing is an error.
+                    log::warn!("Failed to grab cursor: {}", err);
+                }
+            }
+
+            WindowEvent::SetCursorGrab(mode) => {
+                let mode = cursor_grab_mode_to_cursor_grab_mode(*mode);
+                if self.window().set_cursor_grab(mode).is_err() && mode == CursorGrabMode::Locked {
+                    let _ = self.window().set_cursor_grab(CursorGrabMode::Confined);
+                }
+            }
+
+            WindowEvent::SetCursorVisible(flag) => {
+                self.window().set_cursor_visible(*flag);
             }
 
+            // Not all platforms can move the cursor, such as Wayland.
             WindowEvent::SetCursorPosition(x, y) => {
-                self.window()
-                    .set_cursor_position(winit::dpi::Position::Physical(PhysicalPosition::new(
-                        *x as i32, *y as i32,
-                    )))
-                    .expect("Failed to set cursor position");
+                let _ = self.window().set_cursor_position(winit::dpi::Position::Physical(
+                    PhysicalPosition::new(*x as i32, *y as i32),
+                ));
             }
 
             WindowEvent::SetCursor(cursor) => {
+                // An image cursor which hasn't been created yet would otherwise replace this cursor once it is.
+                let window_entity = cx.current();
+                self.image_cursors
+                    .borrow_mut()
+                    .pending
+                    .retain(|(entity, _)| *entity != window_entity);
+
                 let Some(icon) = cursor_icon_to_cursor_icon(*cursor) else {
                     self.window().set_cursor_visible(false);
                     return;
@@ -458,6 +504,16 @@ impl View for Window {
                 self.window().set_cursor_visible(true);
             }
 
+            WindowEvent::SetCustomCursor(image) => {
+                let mut image_cursors = self.image_cursors.borrow_mut();
+                if let Some(custom_cursor) = image_cursors.cursors.get(image) {
+                    self.window().set_cursor(custom_cursor.clone());
+                    self.window().set_cursor_visible(true);
+                } else {
+                    image_cursors.pending.push((cx.current(), image.clone()));
+                }
+            }
+
             WindowEvent::SetTitle(title) => {
                 self.window().set_title(title);
             }
@@ -544,6 +600,20 @@ impl View for Window {
                 meta.consume();
             }
 
+            #[allow(unused_variables)]
+            WindowEvent::StartExternalDrag(payload) => {
+                #[cfg(all(feature = "drag-out", any(target_os = "windows", target_os = "macos")))]
+                crate::external_drag::start_external_drag(cx, self.window(), meta.origin, payload);
+
+                #[cfg(not(all(
+                    feature = "drag-out",
+                    any(target_os = "windows", target_os = "macos")
+                )))]
+                cx.emit_to(meta.origin, WindowEvent::ExternalDragEnded(false));
+
+                meta.consume();
+            }
+
             WindowEvent::SetAlwaysOnTop(flag) => {
                 self.window().set_window_level(if *flag {
                     WindowLevel::AlwaysOnTop
@@ -552,6 +622,16 @@ impl View for Window {
                 });
             }
 
+            WindowEvent::SetTransparent(flag) => {
+                self.window().set_transparent(*flag);
+                cx.toggle_class("transparent", *flag);
+                if let Some(win_state) = cx.windows.get_mut(&cx.current()) {
+                    win_state.window_description.transparent = *flag;
+                }
+
+                cx.needs_redraw();
+            }
+
             _ => {}
         })
     }
@@ -735,6 +815,15 @@ impl WindowModifiers for Handle<'_, Window> {
         self
     }
 
+    fn fit_content(mut self, flag: bool) -> Self {
+        let entity = self.entity();
+        if let Some(win_state) = self.context().windows.get_mut(&entity) {
+            win_state.window_description.fit_content = flag
+        }
+
+        self
+    }
+
     fn icon(mut self, width: u32, height: u32, image: Vec<u8>) -> Self {
         let entity = self.entity();
         if let Some(win_state) = self.context().windows.get_mut(&entity) {
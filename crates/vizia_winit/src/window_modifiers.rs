@@ -189,6 +189,21 @@ pub trait WindowModifiers {
     /// .run();
     /// ```
     fn vsync(self, flag: bool) -> Self;
+    /// Sets whether the window should be resized to fit its content after the first layout pass.
+    ///
+    /// The requested size is clamped to the minimum and maximum inner size of the window.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vizia_core::prelude::*;
+    /// # use vizia_winit::application::Application;
+    /// Application::new(|cx|{
+    ///     Element::new(cx).size(Pixels(200.0));
+    /// })
+    /// .fit_content(true)
+    /// .run();
+    /// ```
+    fn fit_content(self, flag: bool) -> Self;
     /// Sets the icon used for the window.
     ///
     /// # Example
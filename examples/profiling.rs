@@ -0,0 +1,16 @@
+use vizia::prelude::*;
+
+fn main() -> Result<(), ApplicationError> {
+    Application::new(|cx| {
+        VStack::new(cx, |cx| {
+            for index in 0..200 {
+                Button::new(cx, |cx| Label::new(cx, format!("Button {}", index)));
+            }
+        })
+        .height(Auto);
+
+        FrameStatsOverlay::new(cx);
+    })
+    .title("Profiling")
+    .run()
+}
@@ -18,6 +18,16 @@ fn main() -> Result<(), ApplicationError> {
                 .class("span");
         })
         .class("testy");
+
+        Label::rich_text(
+            cx,
+            RichText::new()
+                .text("Styled ")
+                .span(RichTextSpan::new("bold").bold().color(Color::red()))
+                .text(" and ")
+                .span(RichTextSpan::new("italic").italic().font_size(20.0))
+                .text(" runs."),
+        );
     })
     .run()
 }
@@ -0,0 +1,39 @@
+mod helpers;
+use helpers::*;
+use vizia::prelude::*;
+
+#[derive(Debug, Lens)]
+pub struct AppData {
+    pub color: Color,
+}
+
+pub enum AppEvent {
+    SetColor(Color),
+}
+
+impl Model for AppData {
+    fn event(&mut self, _cx: &mut EventContext, event: &mut Event) {
+        event.map(|app_event, _| match app_event {
+            AppEvent::SetColor(color) => self.color = *color,
+        });
+    }
+}
+
+fn main() -> Result<(), ApplicationError> {
+    Application::new(|cx| {
+        AppData { color: Color::rgb(66, 135, 245) }.build(cx);
+
+        ExamplePage::vertical(cx, |cx| {
+            Element::new(cx)
+                .size(Pixels(64.0))
+                .corner_radius(Pixels(8.0))
+                .background_color(AppData::color);
+
+            ColorPicker::new(cx, AppData::color)
+                .on_change(|cx, color| cx.emit(AppEvent::SetColor(color)))
+                .on_submit(|_, color| println!("Picked {:?}", color));
+        });
+    })
+    .title("Color Picker")
+    .run()
+}
@@ -3,8 +3,14 @@ use vizia::prelude::*;
 const STYLE: &str = r#"
     .grid-test {
         layout-type: grid;
-        grid-columns: 200px 200px;
-        grid-rows: 150px 100px;
+        grid-template-columns: 200px 200px;
+        grid-template-rows: 150px 100px 50px;
+        gap: 10px;
+    }
+
+    .grid-test > .wide {
+        grid-column: 1 / span 2;
+        grid-row: 3;
     }
 "#;
 
@@ -29,6 +35,7 @@ fn main() -> Result<(), ApplicationError> {
             Element::new(cx).column_start(1).row_start(0).background_color(Color::blue());
             Element::new(cx).column_start(0).row_start(1).background_color(Color::green());
             Element::new(cx).column_start(1).row_start(1).background_color(Color::yellow());
+            Element::new(cx).class("wide").background_color(Color::gray());
         })
         .class("grid-test");
     })
@@ -10,12 +10,17 @@ pub struct AppData {
 
 pub enum AppEvent {
     ToggleHorizontal,
+    Reorder(usize, usize),
 }
 
 impl Model for AppData {
     fn event(&mut self, _cx: &mut EventContext, event: &mut Event) {
         event.map(|app_event, _| match app_event {
             AppEvent::ToggleHorizontal => self.horizontal = !self.horizontal,
+            AppEvent::Reorder(from, to) => {
+                let item = self.list.remove(*from);
+                self.list.insert(*to, item);
+            }
         });
     }
 }
@@ -33,7 +38,9 @@ fn main() -> Result<(), ApplicationError> {
             })
             .horizontal(AppData::horizontal)
             .selectable(Selectable::Single)
-            .selection_follows_focus(true);
+            .selection_follows_focus(true)
+            .reorderable(true)
+            .on_reorder(|cx, from, to| cx.emit(AppEvent::Reorder(from, to)));
 
             List::new_filtered(
                 cx,
@@ -0,0 +1,39 @@
+mod helpers;
+use helpers::*;
+use vizia::prelude::*;
+
+fn main() -> Result<(), ApplicationError> {
+    Application::new(|cx| {
+        ExamplePage::vertical(cx, |cx| {
+            Navigator::new(cx, "home", |cx| {
+                Route::new(cx, "home", |cx| {
+                    Label::new(cx, "Home");
+                    Button::new(cx, |cx| Label::new(cx, "Open Settings"))
+                        .on_press(|cx| cx.emit(NavigationEvent::Push(String::from("settings"))));
+                })
+                .gap(Pixels(8.0));
+
+                Route::new(cx, "settings", |cx| {
+                    Label::new(cx, "Settings");
+                    Button::new(cx, |cx| Label::new(cx, "Open Profile"))
+                        .on_press(|cx| cx.emit(NavigationEvent::Push(String::from("profile"))));
+                    Button::new(cx, |cx| Label::new(cx, "Back"))
+                        .on_press(|cx| cx.emit(NavigationEvent::Pop));
+                })
+                .gap(Pixels(8.0));
+
+                Route::new(cx, "profile", |cx| {
+                    Label::new(cx, "Profile");
+                    Button::new(cx, |cx| Label::new(cx, "Home"))
+                        .on_press(|cx| cx.emit(NavigationEvent::Push(String::from("home"))));
+                    Button::new(cx, |cx| Label::new(cx, "Back"))
+                        .on_press(|cx| cx.emit(NavigationEvent::Pop));
+                })
+                .gap(Pixels(8.0));
+            })
+            .on_navigate(|_, route| println!("Navigated to {route}"));
+        });
+    })
+    .title("Navigator")
+    .run()
+}
@@ -5,10 +5,12 @@ use vizia::prelude::*;
 #[derive(Debug, Lens)]
 pub struct AppData {
     value: f32,
+    range: (f32, f32),
 }
 
 pub enum AppEvent {
     SetValue(f32),
+    SetRange((f32, f32)),
 }
 
 impl Model for AppData {
@@ -17,13 +19,17 @@ impl Model for AppData {
             AppEvent::SetValue(val) => {
                 self.value = *val;
             }
+
+            AppEvent::SetRange(range) => {
+                self.range = *range;
+            }
         });
     }
 }
 
 fn main() -> Result<(), ApplicationError> {
     Application::new(|cx| {
-        AppData { value: 0.0 }.build(cx);
+        AppData { value: 0.0, range: (-25.0, 25.0) }.build(cx);
 
         ExamplePage::new(cx, |cx| {
             HStack::new(cx, |cx| {
@@ -47,11 +53,39 @@ fn main() -> Result<(), ApplicationError> {
             .height(Auto)
             .horizontal_gap(Pixels(8.0));
 
+            HStack::new(cx, |cx| {
+                Slider::new(cx, AppData::value)
+                    .range(-50.0..50.0)
+                    .step(25.0)
+                    .ticks([-50.0, -25.0, 0.0, 25.0, 50.0])
+                    .tick_labels(["-50", "-25", "0", "25", "50"])
+                    .on_change(move |cx, val| cx.emit(AppEvent::SetValue(val)));
+                Label::new(cx, AppData::value.map(|val| format!("{:.2}", val))).width(Pixels(50.0));
+            })
+            .alignment(Alignment::Center)
+            .height(Auto)
+            .horizontal_gap(Pixels(8.0));
+
+            HStack::new(cx, |cx| {
+                RangeSlider::new(cx, AppData::range)
+                    .range(-50.0..50.0)
+                    .step(1.0)
+                    .on_change(move |cx, range| cx.emit(AppEvent::SetRange(range)));
+                Label::new(
+                    cx,
+                    AppData::range.map(|(start, end)| format!("{:.0} - {:.0}", start, end)),
+                )
+                .width(Pixels(50.0));
+            })
+            .alignment(Alignment::Center)
+            .height(Auto)
+            .horizontal_gap(Pixels(8.0));
+
             VStack::new(cx, |cx| {
                 Slider::new(cx, AppData::value)
                     .range(-50.0..50.0)
                     .on_change(move |cx, val| cx.emit(AppEvent::SetValue(val)))
-                    .class("vertical");
+                    .orientation(Orientation::Vertical);
                 Label::new(cx, AppData::value.map(|val| format!("{:.2}", val)))
                     .alignment(Alignment::Center)
                     .width(Pixels(50.0));
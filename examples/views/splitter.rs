@@ -0,0 +1,64 @@
+mod helpers;
+use helpers::*;
+use vizia::prelude::*;
+
+#[derive(Lens)]
+struct AppData {
+    proportions: Vec<f32>,
+}
+
+enum AppEvent {
+    SetProportions(Vec<f32>),
+}
+
+impl Model for AppData {
+    fn event(&mut self, _: &mut EventContext, event: &mut Event) {
+        event.map(|app_event, _| match app_event {
+            AppEvent::SetProportions(proportions) => self.proportions.clone_from(proportions),
+        });
+    }
+}
+
+fn main() -> Result<(), ApplicationError> {
+    Application::new(|cx| {
+        AppData { proportions: vec![0.25, 0.75] }.build(cx);
+
+        ExamplePage::new(cx, |cx| {
+            Splitter::new(
+                cx,
+                Orientation::Horizontal,
+                vec![
+                    SplitterPane::new(|cx| {
+                        Label::new(cx, "Files");
+                    })
+                    .min_size(120.0)
+                    .max_size(400.0)
+                    .collapsible(true),
+                    SplitterPane::new(|cx| {
+                        Splitter::new(
+                            cx,
+                            Orientation::Vertical,
+                            vec![
+                                SplitterPane::new(|cx| {
+                                    Label::new(cx, "Editor");
+                                })
+                                .proportion(3.0)
+                                .min_size(100.0),
+                                SplitterPane::new(|cx| {
+                                    Label::new(cx, "Terminal");
+                                })
+                                .min_size(60.0)
+                                .collapsible(true),
+                            ],
+                        );
+                    })
+                    .min_size(200.0),
+                ],
+            )
+            .proportions(AppData::proportions)
+            .on_resize(|cx, proportions| cx.emit(AppEvent::SetProportions(proportions.to_vec())));
+        });
+    })
+    .title("Splitter")
+    .run()
+}
@@ -7,36 +7,52 @@ pub struct AppData {
     tabs: Vec<&'static str>,
 }
 
-impl Model for AppData {}
+pub enum AppEvent {
+    CloseTab(usize),
+    MoveTab(usize, usize),
+}
+
+impl Model for AppData {
+    fn event(&mut self, _: &mut EventContext, event: &mut Event) {
+        event.map(|app_event, _| match app_event {
+            AppEvent::CloseTab(index) => {
+                self.tabs.remove(*index);
+            }
+
+            AppEvent::MoveTab(from, to) => {
+                let tab = self.tabs.remove(*from);
+                self.tabs.insert(*to, tab);
+            }
+        });
+    }
+}
 
 fn main() -> Result<(), ApplicationError> {
     Application::new(|cx| {
         AppData { tabs: vec!["Tab1", "Tab2", "Tab3", "Tab4", "Tab5", "Tab6"] }.build(cx);
 
         ExamplePage::new(cx, |cx| {
-            TabView::new(cx, AppData::tabs, |cx, item| match item.get(cx) {
-                "Tab1" => TabPair::new(
-                    move |cx| {
-                        Label::new(cx, item).hoverable(false);
-                        Element::new(cx).class("indicator");
-                    },
-                    |cx| {
-                        Element::new(cx).size(Pixels(200.0)).background_color(Color::red());
-                    },
-                ),
+            TabView::new(cx, AppData::tabs, |cx, item| {
+                let color = match item.get(cx) {
+                    "Tab1" => Color::red(),
+                    "Tab2" => Color::blue(),
+                    _ => Color::green(),
+                };
 
-                "Tab2" => TabPair::new(
+                TabPair::new(
                     move |cx| {
                         Label::new(cx, item).hoverable(false);
                         Element::new(cx).class("indicator");
                     },
-                    |cx| {
-                        Element::new(cx).size(Pixels(200.0)).background_color(Color::blue());
+                    move |cx| {
+                        Element::new(cx).size(Pixels(200.0)).background_color(color);
                     },
-                ),
-
-                _ => unreachable!(),
+                )
             })
+            .closable(true)
+            .on_close(|cx, index| cx.emit(AppEvent::CloseTab(index)))
+            .reorderable(true)
+            .on_reorder(|cx, from, to| cx.emit(AppEvent::MoveTab(from, to)))
             .width(Pixels(500.0))
             .height(Pixels(300.0));
         });
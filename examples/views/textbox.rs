@@ -7,6 +7,7 @@ use vizia::prelude::*;
 pub struct AppData {
     editable_text: String,
     multiline_text: String,
+    phone_number: String,
     non_editable_text: String,
     non_editable_multiline_text: String,
 }
@@ -16,6 +17,7 @@ impl Model for AppData {
         event.map(|app_event, _| match app_event {
             AppEvent::SetEditableText(text) => self.editable_text = text.clone(),
             AppEvent::SetMultilineText(text) => self.multiline_text = text.clone(),
+            AppEvent::SetPhoneNumber(digits) => self.phone_number = digits.clone(),
         });
     }
 }
@@ -23,6 +25,7 @@ impl Model for AppData {
 pub enum AppEvent {
     SetEditableText(String),
     SetMultilineText(String),
+    SetPhoneNumber(String),
 }
 
 fn main() -> Result<(), ApplicationError> {
@@ -31,6 +34,7 @@ fn main() -> Result<(), ApplicationError> {
             editable_text: "".to_string(),
             multiline_text: "This is some text which is editable and spans multiple lines"
                 .to_string(),
+            phone_number: "".to_string(),
             non_editable_text: "This text can be selected but not edited".to_string(),
             non_editable_multiline_text:
                 "This text can be selected but not edited and spans multiple lines".to_string(),
@@ -62,6 +66,12 @@ fn main() -> Result<(), ApplicationError> {
                 .width(Pixels(300.0))
                 .on_edit(|cx, text| cx.emit(AppEvent::SetMultilineText(text)));
 
+            Textbox::new(cx, AppData::phone_number)
+                .width(Pixels(300.0))
+                .placeholder("(###) ###-####")
+                .input_mask("(###) ###-####")
+                .on_submit(|cx, digits, _| cx.emit(AppEvent::SetPhoneNumber(digits)));
+
             Textbox::new(cx, AppData::non_editable_text).width(Auto).read_only(true);
             Textbox::new_multiline(cx, AppData::non_editable_multiline_text, true)
                 .width(Pixels(300.0))
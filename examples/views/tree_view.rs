@@ -0,0 +1,101 @@
+mod helpers;
+use std::path::PathBuf;
+
+use helpers::*;
+use vizia::prelude::*;
+
+#[derive(Debug, Clone, Data)]
+pub struct FileNode {
+    name: String,
+    path: PathBuf,
+    is_dir: bool,
+    children: Vec<FileNode>,
+}
+
+impl FileNode {
+    fn new(path: PathBuf) -> Self {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string_lossy().to_string());
+        let is_dir = path.is_dir();
+        Self { name, path, is_dir, children: Vec::new() }
+    }
+
+    // Reads the entries of the directory, sorted with directories first.
+    fn load_children(&mut self) {
+        let Ok(entries) = std::fs::read_dir(&self.path) else { return };
+        self.children = entries.flatten().map(|entry| FileNode::new(entry.path())).collect();
+        self.children.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name)));
+    }
+
+    fn node_mut(&mut self, path: &[usize]) -> Option<&mut FileNode> {
+        match path.split_first() {
+            Some((index, rest)) => self.children.get_mut(*index)?.node_mut(rest),
+            None => Some(self),
+        }
+    }
+}
+
+#[derive(Lens)]
+pub struct AppData {
+    roots: Vec<FileNode>,
+    selected: String,
+}
+
+pub enum AppEvent {
+    LoadChildren(Vec<usize>),
+    Select(Vec<usize>),
+}
+
+impl AppData {
+    fn node_mut(&mut self, path: &[usize]) -> Option<&mut FileNode> {
+        let (index, rest) = path.split_first()?;
+        self.roots.get_mut(*index)?.node_mut(rest)
+    }
+}
+
+impl Model for AppData {
+    fn event(&mut self, _cx: &mut EventContext, event: &mut Event) {
+        event.map(|app_event, _| match app_event {
+            AppEvent::LoadChildren(path) => {
+                if let Some(node) = self.node_mut(path) {
+                    node.load_children();
+                }
+            }
+
+            AppEvent::Select(path) => {
+                if let Some(node) = self.node_mut(path) {
+                    self.selected = node.path.to_string_lossy().to_string();
+                }
+            }
+        });
+    }
+}
+
+fn main() -> Result<(), ApplicationError> {
+    Application::new(|cx| {
+        let root = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        AppData { roots: vec![FileNode::new(root)], selected: String::new() }.build(cx);
+
+        ExamplePage::vertical(cx, |cx| {
+            Label::new(cx, AppData::selected);
+
+            // Only the entries of expanded directories are read from the file system.
+            TreeView::new_lazy(
+                cx,
+                AppData::roots,
+                |node| &node.children,
+                |node| node.is_dir,
+                |cx, _, node| {
+                    Label::new(cx, node.map(|node| node.name.clone())).hoverable(false);
+                },
+            )
+            .selectable(Selectable::Multi)
+            .on_load_children(|cx, path| cx.emit(AppEvent::LoadChildren(path.to_vec())))
+            .on_select(|cx, path| cx.emit(AppEvent::Select(path.to_vec())));
+        });
+    })
+    .title("TreeView")
+    .run()
+}